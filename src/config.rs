@@ -1,7 +1,13 @@
 use crate::config_loader;
+use crate::config_loader::provenance::{Provenance, ValueSource};
 use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Configuration for the cloud-cli application
 #[derive(Debug, Clone)]
@@ -10,6 +16,40 @@ pub struct Config {
     pub output_dir: PathBuf,
     pub timeout_seconds: u64,
     pub no_progress_animation: bool,
+    pub no_hints: bool,
+    /// Max worker threads for log scanning; `None` uses half of the CPUs
+    pub log_scan_threads: Option<usize>,
+    /// Name of the cluster profile this configuration belongs to
+    pub profile_name: Option<String>,
+    /// Strict mode: heavy tools require typed confirmation and artifacts are stamped
+    pub production: bool,
+    /// Print tool output to stdout instead of writing files into `output_dir`
+    pub stdout_only: bool,
+    /// Also keep the credential key in the user keyring; the key file stays unless
+    /// its removal is confirmed
+    pub credential_keyring: bool,
+    /// Statement length shown in audit log console tables; `None` uses 80
+    pub audit_stmt_max_len: Option<usize>,
+    /// Delete cloud-cli outputs older than this many days at startup; `None` keeps them
+    pub max_output_age_days: Option<u64>,
+    /// Delete the oldest cloud-cli outputs while the total exceeds this size; `None` disables it
+    pub max_output_total_bytes: Option<u64>,
+    /// Copied FE log directory analyzed instead of the live log_dir
+    pub offline_log_dir: Option<PathBuf>,
+    /// Hours after which a RUNNING alter job is highlighted; `None` uses 6
+    pub alter_job_warn_hours: Option<u64>,
+    /// Most worker threads a table-info batch may use; `None` uses 32
+    pub table_info_max_concurrency: Option<usize>,
+    /// Plain output: no colors and ASCII tables (also `CLOUD_CLI_PLAIN` or `NO_COLOR`)
+    pub plain_output: bool,
+    /// Block tools that attach to processes, write into the install dir or change
+    /// cluster state; only collection into `output_dir` is allowed.
+    /// `None` follows `production`, see `is_read_only`
+    pub read_only: Option<bool>,
+    /// Write artifacts straight into `output_dir` instead of a per-session run directory
+    pub flat_output: bool,
+    /// Ticket or case ID put in front of every artifact name (`CLOUD_CLI_TAG` or settings)
+    pub session_tag: Option<String>,
 }
 
 // Environment variable names
@@ -17,6 +57,111 @@ const ENV_JDK_PATH: &str = "JDK_PATH";
 const ENV_OUTPUT_DIR: &str = "OUTPUT_DIR";
 const ENV_TIMEOUT: &str = "CLOUD_CLI_TIMEOUT";
 const ENV_NO_PROGRESS: &str = "CLOUD_CLI_NO_PROGRESS";
+const ENV_NO_HINTS: &str = "CLOUD_CLI_NO_HINTS";
+const ENV_LOG_SCAN_THREADS: &str = "CLOUD_CLI_LOG_SCAN_THREADS";
+const ENV_PROFILE: &str = "CLOUD_CLI_PROFILE";
+const ENV_PRODUCTION: &str = "CLOUD_CLI_PRODUCTION";
+const ENV_STDOUT_ONLY: &str = "CLOUD_CLI_STDOUT_ONLY";
+const ENV_KEYRING: &str = "CLOUD_CLI_KEYRING";
+const ENV_OFFLINE_LOG_DIR: &str = "CLOUD_CLI_OFFLINE_LOG_DIR";
+const ENV_PLAIN: &str = "CLOUD_CLI_PLAIN";
+const ENV_TAG: &str = "CLOUD_CLI_TAG";
+const ENV_READ_ONLY: &str = "CLOUD_CLI_READ_ONLY";
+const ENV_FLAT_OUTPUT: &str = "CLOUD_CLI_FLAT_OUTPUT";
+const ENV_VERBOSE: &str = "CLOUD_CLI_VERBOSE";
+/// Older switch for debug output, same as `CLOUD_CLI_VERBOSE=debug`
+const ENV_DEBUG: &str = "CLOUD_CLI_DEBUG";
+/// https://no-color.org: any non-empty value disables color
+const ENV_NO_COLOR: &str = "NO_COLOR";
+
+/// Set by the `--stdout` command line flag, applies to every Config built afterwards
+static STDOUT_ONLY_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Set by the `--plain` command line flag
+static PLAIN_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Set by the `--quiet` command line flag
+static QUIET_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Start quiet regardless of `CLOUD_CLI_VERBOSE` (`--quiet`)
+pub fn enable_quiet_output() {
+    QUIET_FLAG.store(true, Ordering::Relaxed);
+}
+
+/// Verbosity asked for by `--quiet`, `CLOUD_CLI_VERBOSE` or `CLOUD_CLI_DEBUG`
+pub fn verbosity_requested() -> Option<crate::ui::Verbosity> {
+    if QUIET_FLAG.load(Ordering::Relaxed) {
+        return Some(crate::ui::Verbosity::Quiet);
+    }
+    env::var(ENV_VERBOSE)
+        .ok()
+        .and_then(|v| crate::ui::Verbosity::parse(&v))
+        .or_else(|| env::var_os(ENV_DEBUG).map(|_| crate::ui::Verbosity::Debug))
+}
+
+/// Force plain output for the rest of the process (`--plain`)
+pub fn enable_plain_output() {
+    PLAIN_FLAG.store(true, Ordering::Relaxed);
+}
+
+/// Whether `--plain`, `CLOUD_CLI_PLAIN` or `NO_COLOR` asks for plain output
+pub fn plain_requested() -> bool {
+    let plain = env::var(ENV_PLAIN).is_ok_and(|v| v == "1" || v.to_lowercase() == "true");
+    plain
+        || PLAIN_FLAG.load(Ordering::Relaxed)
+        || env::var_os(ENV_NO_COLOR).is_some_and(|v| !v.is_empty())
+}
+
+/// `CLOUD_CLI_READ_ONLY` when set; it overrides the persisted setting
+pub fn read_only_from_env() -> Option<bool> {
+    env::var(ENV_READ_ONLY)
+        .ok()
+        .map(|v| v == "1" || v.to_lowercase() == "true")
+}
+
+/// Session tag for the rest of the process; starts from `CLOUD_CLI_TAG` and is
+/// replaced from the settings menu
+static SESSION_TAG: Lazy<Mutex<Option<String>>> =
+    Lazy::new(|| Mutex::new(clean_tag(&env::var(ENV_TAG).unwrap_or_default())));
+
+fn clean_tag(tag: &str) -> Option<String> {
+    Some(file_name::sanitize(tag.trim())).filter(|t| !t.is_empty())
+}
+
+/// Active session tag, already filesystem-safe
+pub fn session_tag() -> Option<String> {
+    SESSION_TAG.lock().map(|t| t.clone()).unwrap_or_default()
+}
+
+/// Replace the session tag for every Config built afterwards; blank clears it
+pub fn set_session_tag(tag: &str) -> Option<String> {
+    let tag = clean_tag(tag);
+    if let Ok(mut current) = SESSION_TAG.lock() {
+        *current = tag.clone();
+    }
+    tag
+}
+
+/// Force stdout-only mode for the rest of the process (`--stdout`)
+/// Start of this session; every run directory it creates is named after it
+static SESSION_STARTED: Lazy<String> = Lazy::new(file_name::timestamp);
+
+/// Run directory picked for each output directory this session wrote into
+static RUN_DIRS: Lazy<Mutex<HashMap<PathBuf, PathBuf>>> = Lazy::new(Default::default);
+
+/// Fix the session start time that names the run directory
+pub fn mark_session_start() {
+    Lazy::force(&SESSION_STARTED);
+}
+
+/// Run directory of this session under `output_dir`, if it was picked already
+pub fn current_run_dir(output_dir: &Path) -> Option<PathBuf> {
+    RUN_DIRS.lock().ok()?.get(output_dir).cloned()
+}
+
+pub fn enable_stdout_only() {
+    STDOUT_ONLY_FLAG.store(true, Ordering::Relaxed);
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -34,18 +179,22 @@ impl Config {
             .unwrap_or_else(|_| Self::default());
 
         // Allow environment variables to override config
-        config.load_from_env();
+        config.load_from_env(&mut Provenance::default());
         config
     }
 
-    /// Loads configuration from environment variables
-    fn load_from_env(&mut self) {
+    /// Loads configuration from environment variables, recording each override
+    pub fn load_from_env(&mut self, provenance: &mut Provenance) {
+        let is_true = |v: &str| v == "1" || v.to_lowercase() == "true";
+
         if let Ok(jdk_path) = env::var(ENV_JDK_PATH) {
             self.jdk_path = PathBuf::from(jdk_path);
+            provenance.set("jdk_path", ValueSource::Env(ENV_JDK_PATH));
         }
 
         if let Ok(output_dir) = env::var(ENV_OUTPUT_DIR) {
             self.output_dir = PathBuf::from(output_dir);
+            provenance.set("output_dir", ValueSource::Env(ENV_OUTPUT_DIR));
         }
 
         if let Some(timeout) = env::var(ENV_TIMEOUT)
@@ -53,11 +202,94 @@ impl Config {
             .and_then(|s| s.parse::<u64>().ok())
         {
             self.timeout_seconds = timeout;
+            provenance.set("timeout_seconds", ValueSource::Env(ENV_TIMEOUT));
         }
 
+        // Always taken from the environment, so an unset variable means the default
         self.no_progress_animation = env::var(ENV_NO_PROGRESS)
-            .map(|v| v == "1" || v.to_lowercase() == "true")
+            .map(|v| is_true(&v))
             .unwrap_or(false);
+        provenance.set(
+            "no_progress_animation",
+            if env::var_os(ENV_NO_PROGRESS).is_some() {
+                ValueSource::Env(ENV_NO_PROGRESS)
+            } else {
+                ValueSource::Default
+            },
+        );
+
+        if let Ok(v) = env::var(ENV_NO_HINTS) {
+            self.no_hints = is_true(&v);
+            provenance.set("no_hints", ValueSource::Env(ENV_NO_HINTS));
+        }
+
+        if let Some(threads) = env::var(ENV_LOG_SCAN_THREADS)
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+        {
+            self.log_scan_threads = Some(threads);
+            provenance.set("log_scan_threads", ValueSource::Env(ENV_LOG_SCAN_THREADS));
+        }
+
+        if let Ok(profile) = env::var(ENV_PROFILE) {
+            self.profile_name = Some(profile).filter(|p| !p.trim().is_empty());
+            provenance.set("profile_name", ValueSource::Env(ENV_PROFILE));
+        }
+
+        if let Ok(v) = env::var(ENV_PRODUCTION) {
+            self.production = is_true(&v);
+            provenance.set("production", ValueSource::Env(ENV_PRODUCTION));
+        }
+
+        if let Ok(v) = env::var(ENV_STDOUT_ONLY) {
+            self.stdout_only = is_true(&v);
+            provenance.set("stdout_only", ValueSource::Env(ENV_STDOUT_ONLY));
+        }
+        if STDOUT_ONLY_FLAG.load(Ordering::Relaxed) {
+            self.stdout_only = true;
+            provenance.set("stdout_only", ValueSource::Flag("--stdout"));
+        }
+
+        if let Ok(v) = env::var(ENV_KEYRING) {
+            self.credential_keyring = is_true(&v);
+            provenance.set("credential_keyring", ValueSource::Env(ENV_KEYRING));
+        }
+
+        if plain_requested() {
+            self.plain_output = true;
+            let source = if PLAIN_FLAG.load(Ordering::Relaxed) {
+                ValueSource::Flag("--plain")
+            } else if env::var(ENV_PLAIN).is_ok_and(|v| is_true(&v)) {
+                ValueSource::Env(ENV_PLAIN)
+            } else {
+                ValueSource::Env(ENV_NO_COLOR)
+            };
+            provenance.set("plain_output", source);
+        }
+
+        if let Some(read_only) = read_only_from_env() {
+            self.read_only = Some(read_only);
+            provenance.set("read_only", ValueSource::Env(ENV_READ_ONLY));
+        }
+
+        if let Ok(v) = env::var(ENV_FLAT_OUTPUT) {
+            self.flat_output = is_true(&v);
+            provenance.set("flat_output", ValueSource::Env(ENV_FLAT_OUTPUT));
+        }
+
+        if self.session_tag.is_some() {
+            let source = if clean_tag(&env::var(ENV_TAG).unwrap_or_default()) == self.session_tag {
+                ValueSource::Env(ENV_TAG)
+            } else {
+                ValueSource::Session
+            };
+            provenance.set("session_tag", source);
+        }
+
+        if let Ok(dir) = env::var(ENV_OFFLINE_LOG_DIR) {
+            self.offline_log_dir = Some(PathBuf::from(dir)).filter(|d| !d.as_os_str().is_empty());
+            provenance.set("offline_log_dir", ValueSource::Env(ENV_OFFLINE_LOG_DIR));
+        }
     }
 
     pub fn with_jdk_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
@@ -88,10 +320,11 @@ impl Config {
         let jmap_path = self.get_jmap_path();
         let jstack_path = self.get_jstack_path();
 
-        if !jmap_path.exists() {
+        // Heap dumps fall back to jcmd, so a JDK shipping only jcmd is enough
+        if !jmap_path.exists() && !self.get_jcmd_path().exists() {
             return Err(CliError::ConfigError(format!(
-                "jmap not found: {}. Please verify JDK installation.",
-                jmap_path.display()
+                "Neither jmap nor jcmd found in {}. Please verify JDK installation.",
+                self.jdk_path.join("bin").display()
             )));
         }
 
@@ -106,7 +339,7 @@ impl Config {
     }
 
     fn validate_output_dir(&self) -> Result<()> {
-        if self.output_dir.exists() {
+        if self.output_dir.exists() && !self.stdout_only {
             let test_file = self.output_dir.join(".write_test");
             match std::fs::File::create(&test_file) {
                 Ok(_) => {
@@ -135,24 +368,118 @@ impl Config {
         Ok(())
     }
 
+    /// Create the output directory and this session's run directory in it
     pub fn ensure_output_dir(&self) -> Result<()> {
-        if let Err(e) = std::fs::create_dir_all(&self.output_dir) {
+        let run_dir = self.run_dir_path();
+        if let Err(e) = std::fs::create_dir_all(&run_dir) {
             return Err(CliError::ConfigError(format!(
                 "Failed to create output directory: {}. Error: {e}",
-                self.output_dir.display()
+                run_dir.display()
             )));
         }
         Ok(())
     }
 
+    /// Where this session's artifacts go: `output_dir/<date>_<time>[_<tag>]/`,
+    /// or `output_dir` itself with `flat_output`. Created on first use.
+    pub fn run_dir(&self) -> PathBuf {
+        let dir = self.run_dir_path();
+        if !self.stdout_only {
+            // A failure surfaces when the first artifact is written
+            let _ = std::fs::create_dir_all(&dir);
+        }
+        dir
+    }
+
+    /// [`Config::run_dir`] without creating it.
+    ///
+    /// The name is fixed the first time it is asked for, so a session tag set
+    /// later does not split one session over two directories.
+    pub fn run_dir_path(&self) -> PathBuf {
+        if self.flat_output {
+            return self.output_dir.clone();
+        }
+        let name = || {
+            file_name::labeled(
+                &[Some(SESSION_STARTED.as_str()), self.session_tag.as_deref()],
+                "",
+            )
+            .trim_end_matches('_')
+            .to_string()
+        };
+        match RUN_DIRS.lock() {
+            Ok(mut dirs) => dirs
+                .entry(self.output_dir.clone())
+                .or_insert_with(|| self.output_dir.join(name()))
+                .clone(),
+            Err(_) => self.output_dir.join(name()),
+        }
+    }
+
     pub fn get_jmap_path(&self) -> PathBuf {
         self.jdk_path.join("bin/jmap")
     }
 
+    pub fn get_jcmd_path(&self) -> PathBuf {
+        self.jdk_path.join("bin/jcmd")
+    }
+
     pub fn get_jstack_path(&self) -> PathBuf {
         self.jdk_path.join("bin/jstack")
     }
 
+    pub fn log_scan_workers(&self) -> usize {
+        self.log_scan_threads
+            .filter(|n| *n > 0)
+            .unwrap_or_else(crate::tools::common::log_scan::default_scan_workers)
+    }
+
+    pub fn audit_stmt_len(&self) -> usize {
+        self.audit_stmt_max_len.filter(|n| *n > 0).unwrap_or(80)
+    }
+
+    pub fn alter_job_warn_after_hours(&self) -> u64 {
+        self.alter_job_warn_hours.filter(|n| *n > 0).unwrap_or(6)
+    }
+
+    pub fn table_info_max_concurrency(&self) -> usize {
+        self.table_info_max_concurrency
+            .filter(|n| *n > 0)
+            .unwrap_or(32)
+    }
+
+    /// Read-only mode as set explicitly, otherwise on in production mode
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.unwrap_or(self.production)
+    }
+
+    /// Profile name shown and stamped when strict (production) mode is on
+    pub fn production_profile(&self) -> Option<&str> {
+        if !self.production {
+            return None;
+        }
+        Some(
+            self.profile_name
+                .as_deref()
+                .filter(|p| !p.trim().is_empty())
+                .unwrap_or("production"),
+        )
+    }
+
+    /// Path for a generated artifact in the run directory.
+    /// The filename is prefixed with the session tag and, in production mode,
+    /// the profile name.
+    pub fn artifact_path(&self, file_name: &str) -> PathBuf {
+        self.run_dir_path().join(self.artifact_file_name(file_name))
+    }
+
+    pub fn artifact_file_name(&self, name: &str) -> String {
+        file_name::labeled(
+            &[self.session_tag.as_deref(), self.production_profile()],
+            name,
+        )
+    }
+
     pub fn get_timeout_millis(&self) -> u64 {
         self.timeout_seconds * 1000
     }
@@ -1,6 +1,10 @@
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Mutex;
 
 use crate::config_loader::process_detector;
 use crate::config_loader::regex_utils;
@@ -8,9 +12,60 @@ use crate::config_loader::{DorisConfig, Environment};
 use crate::error::{CliError, Result};
 
 // Type aliases for complex function pointer types
-type PortParserFn = fn(&str, &str, &mut Option<u16>) -> Result<()>;
-type PathParserFn = fn(&str, &str, &mut Option<PathBuf>) -> Result<()>;
-type StringParserFn = fn(&str, &str, &mut Option<String>) -> Result<()>;
+type PortParserFn = fn(&str, &str, &mut Option<u16>) -> ParseOutcome;
+type PathParserFn = fn(&str, &str, &mut Option<PathBuf>) -> ParseOutcome;
+type StringParserFn = fn(&str, &str, &mut Option<String>) -> ParseOutcome;
+
+/// Ok when the line is not for the key or its value was applied
+type ParseOutcome = std::result::Result<(), Rejected>;
+
+/// Value of a recognised key that could not be used
+#[derive(Debug)]
+struct Rejected {
+    key: String,
+    raw_value: String,
+    reason: String,
+}
+
+/// A conf line whose value was ignored or overrides an earlier one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConfigWarning {
+    pub file: String,
+    pub line: usize,
+    pub key: String,
+    pub raw_value: String,
+    pub reason: String,
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} = '{}': {}",
+            self.file, self.line, self.key, self.raw_value, self.reason
+        )
+    }
+}
+
+/// Parsed conf file together with the problems found in it
+#[derive(Debug, Clone)]
+pub struct ParsedConfig {
+    pub config: DorisConfig,
+    pub warnings: Vec<ConfigWarning>,
+}
+
+/// Print each distinct warning once per run; configs are re-parsed often
+pub fn report_warnings(warnings: &[ConfigWarning]) {
+    static REPORTED: Lazy<Mutex<HashSet<ConfigWarning>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+    let Ok(mut reported) = REPORTED.lock() else {
+        return;
+    };
+    for w in warnings {
+        if reported.insert(w.clone()) {
+            crate::ui::print_warning(&format!("Config: {w}"));
+        }
+    }
+}
 
 // Common configuration keys
 const LOG_DIR_KEY: &str = "LOG_DIR";
@@ -18,7 +73,7 @@ const PRIORITY_NETWORKS_KEY: &str = "priority_networks";
 const META_SERVICE_KEY: &str = "meta_service_endpoint";
 
 trait ConfigParser {
-    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> Result<()>;
+    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> ParseOutcome;
 }
 
 struct PortConfigParser<'a> {
@@ -32,7 +87,7 @@ impl<'a> PortConfigParser<'a> {
 }
 
 impl<'a> ConfigParser for PortConfigParser<'a> {
-    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> Result<()> {
+    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> ParseOutcome {
         for (key, parse_fn) in &self.items {
             let target = match *key {
                 "be_port" => &mut config.be_port,
@@ -64,7 +119,7 @@ impl<'a> PathConfigParser<'a> {
 }
 
 impl<'a> ConfigParser for PathConfigParser<'a> {
-    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> Result<()> {
+    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> ParseOutcome {
         for (key, parse_fn) in &self.items {
             let target = match *key {
                 "meta_dir" => &mut config.meta_dir,
@@ -88,7 +143,7 @@ impl<'a> StringConfigParser<'a> {
 }
 
 impl<'a> ConfigParser for StringConfigParser<'a> {
-    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> Result<()> {
+    fn parse_line(&self, line: &str, config: &mut DorisConfig) -> ParseOutcome {
         for (key, parse_fn) in &self.items {
             let target = match *key {
                 "priority_networks" => &mut config.priority_networks,
@@ -103,7 +158,7 @@ impl<'a> ConfigParser for StringConfigParser<'a> {
 }
 
 /// Parse configuration from specified path
-pub fn parse_config_from_path(env: Environment, install_dir: &Path) -> Result<DorisConfig> {
+pub fn parse_config_from_path(env: Environment, install_dir: &Path) -> Result<ParsedConfig> {
     let jdk_path = PathBuf::from("/opt/jdk");
     parse_config_internal(env, install_dir, &jdk_path)
 }
@@ -113,7 +168,7 @@ fn parse_config_internal(
     env: Environment,
     install_dir: &Path,
     jdk_path: &Path,
-) -> Result<DorisConfig> {
+) -> Result<ParsedConfig> {
     let conf_dir = install_dir.join("conf");
 
     let config_file = match env {
@@ -147,41 +202,63 @@ fn parse_config_internal(
     } else {
         None
     };
-    parse_config_content(env, &content, &mut config, install_dir_param)?;
+    let warnings = parse_config_content(env, &content, &mut config, install_dir_param);
 
-    Ok(config)
+    Ok(ParsedConfig { config, warnings })
 }
 
 /// Parse BE configuration
-pub fn parse_be_config() -> Result<DorisConfig> {
+pub fn parse_be_config() -> Result<ParsedConfig> {
     let (install_dir, jdk_path) = process_detector::get_paths(Environment::BE)?;
     parse_config_internal(Environment::BE, &install_dir, &jdk_path)
 }
 
 /// Parse FE configuration
-pub fn parse_fe_config() -> Result<DorisConfig> {
+pub fn parse_fe_config() -> Result<ParsedConfig> {
     let (install_dir, jdk_path) = process_detector::get_paths(Environment::FE)?;
     parse_config_internal(Environment::FE, &install_dir, &jdk_path)
 }
 
-/// Parse config content based on environment
+/// Parse config content based on environment.
+///
+/// Malformed values are skipped and reported; for duplicate keys the later line wins.
 fn parse_config_content(
     env: Environment,
     content: &str,
     config: &mut DorisConfig,
     install_dir: Option<&Path>,
-) -> Result<()> {
+) -> Vec<ConfigWarning> {
     let port_parser = PortConfigParser::new(get_env_config_items(env));
     let path_parser = PathConfigParser::new(get_env_path_config_items(env));
     let common_parser = StringConfigParser::new(get_common_config_items());
 
-    for line in content.lines() {
+    let file = match env {
+        Environment::BE => "be.conf",
+        _ => "fe.conf",
+    };
+    let mut warnings = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
         let line = line.trim();
 
         if line.starts_with('#') || line.is_empty() {
             continue;
         }
 
+        if let Some(key) = regex_utils::extract_key_from_line(line)
+            && let Some(previous) = seen.insert(key.clone(), line_no)
+        {
+            warnings.push(ConfigWarning {
+                file: file.to_string(),
+                line: line_no,
+                raw_value: extract_value(line).unwrap_or_default(),
+                key,
+                reason: format!("duplicate key, overrides the value on line {previous}"),
+            });
+        }
+
         if line.starts_with(LOG_DIR_KEY)
             && let Some(log_dir) = extract_value(line)
         {
@@ -196,31 +273,42 @@ fn parse_config_content(
             }
         }
 
-        port_parser.parse_line(line, config)?;
-        path_parser.parse_line(line, config)?;
-        common_parser.parse_line(line, config)?;
+        let outcomes = [
+            port_parser.parse_line(line, config),
+            path_parser.parse_line(line, config),
+            common_parser.parse_line(line, config),
+        ];
+        for rejected in outcomes.into_iter().filter_map(|o| o.err()) {
+            warnings.push(ConfigWarning {
+                file: file.to_string(),
+                line: line_no,
+                key: rejected.key,
+                raw_value: rejected.raw_value,
+                reason: rejected.reason,
+            });
+        }
     }
 
-    Ok(())
+    warnings
 }
 
 fn get_env_config_items<'a>(env: Environment) -> Vec<(&'a str, PortParserFn)> {
     match env {
         Environment::BE => {
             vec![
-                ("be_port", parse_key_value::<u16>),
-                ("brpc_port", parse_key_value::<u16>),
-                ("heartbeat_service_port", parse_key_value::<u16>),
-                ("webserver_port", parse_key_value::<u16>),
+                ("be_port", parse_port),
+                ("brpc_port", parse_port),
+                ("heartbeat_service_port", parse_port),
+                ("webserver_port", parse_port),
             ]
         }
         Environment::FE => {
             vec![
-                ("http_port", parse_key_value::<u16>),
-                ("rpc_port", parse_key_value::<u16>),
-                ("query_port", parse_key_value::<u16>),
-                ("edit_log_port", parse_key_value::<u16>),
-                ("cloud_http_port", parse_key_value::<u16>),
+                ("http_port", parse_port),
+                ("rpc_port", parse_port),
+                ("query_port", parse_port),
+                ("edit_log_port", parse_port),
+                ("cloud_http_port", parse_port),
             ]
         }
         _ => vec![],
@@ -248,20 +336,106 @@ fn extract_value(line: &str) -> Option<String> {
     regex_utils::extract_value_from_line(line)
 }
 
+fn rejected(key: &str, raw_value: &str, reason: &str) -> Rejected {
+    Rejected {
+        key: key.to_string(),
+        raw_value: raw_value.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
 /// Parse PathBuf key-value
-fn parse_path_key_value(line: &str, key: &str, value: &mut Option<PathBuf>) -> Result<()> {
+fn parse_path_key_value(line: &str, key: &str, value: &mut Option<PathBuf>) -> ParseOutcome {
     if let Some(val_str) = regex_utils::extract_key_value(line, key) {
+        if val_str.is_empty() {
+            return Err(rejected(key, &val_str, "empty path"));
+        }
         *value = Some(PathBuf::from(val_str));
     }
     Ok(())
 }
 
+/// Port parser; 0 and anything outside 1-65535 is rejected
+fn parse_port(line: &str, key: &str, value: &mut Option<u16>) -> ParseOutcome {
+    if let Some(val_str) = regex_utils::extract_key_value(line, key) {
+        match val_str.parse::<u16>() {
+            Ok(port) if port > 0 => *value = Some(port),
+            _ => return Err(rejected(key, &val_str, "not a valid port (1-65535)")),
+        }
+    }
+    Ok(())
+}
+
 /// Generic key-value parser
-fn parse_key_value<T: FromStr>(line: &str, key: &str, value: &mut Option<T>) -> Result<()> {
-    if let Some(parsed_val) =
-        regex_utils::extract_key_value(line, key).and_then(|s| s.parse::<T>().ok())
-    {
-        *value = Some(parsed_val);
+fn parse_key_value<T: FromStr>(line: &str, key: &str, value: &mut Option<T>) -> ParseOutcome {
+    if let Some(val_str) = regex_utils::extract_key_value(line, key) {
+        match val_str.parse::<T>() {
+            Ok(parsed_val) => *value = Some(parsed_val),
+            Err(_) => return Err(rejected(key, &val_str, "value could not be parsed")),
+        }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(env: Environment, content: &str) -> (DorisConfig, Vec<ConfigWarning>) {
+        let mut config = DorisConfig::default();
+        let warnings = parse_config_content(env, content, &mut config, None);
+        (config, warnings)
+    }
+
+    #[test]
+    fn malformed_ports_are_reported() {
+        let (config, warnings) = parse(
+            Environment::BE,
+            "be_port = 9060\nwebserver_port = 8040x\nbrpc_port = 0\n",
+        );
+        assert_eq!(config.be_port, Some(9060));
+        assert_eq!(config.webserver_port, None);
+        assert_eq!(config.brpc_port, None);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].line, 2);
+        assert_eq!(warnings[0].key, "webserver_port");
+        assert_eq!(warnings[0].raw_value, "8040x");
+        assert_eq!(
+            warnings[0].to_string(),
+            "be.conf:2: webserver_port = '8040x': not a valid port (1-65535)"
+        );
+    }
+
+    #[test]
+    fn quoted_values_and_inline_comments() {
+        let (config, warnings) = parse(
+            Environment::FE,
+            "# header\n\
+             http_port = 8030 # web ui\n\
+             query_port = \"9030\"\n\
+             priority_networks = \"10.0.0.0/8\" # office\n\
+             meta_dir = ${DORIS_HOME}/doris-meta#1\n",
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(config.http_port, Some(8030));
+        assert_eq!(config.query_port, Some(9030));
+        assert_eq!(config.priority_networks.as_deref(), Some("10.0.0.0/8"));
+        assert_eq!(
+            config.meta_dir,
+            Some(PathBuf::from("${DORIS_HOME}/doris-meta#1"))
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_later_value_wins() {
+        let (config, warnings) = parse(
+            Environment::BE,
+            "webserver_port = 8040\nmem_limit = 80%\nwebserver_port = 8041\n",
+        );
+        assert_eq!(config.webserver_port, Some(8041));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 3);
+        assert_eq!(warnings[0].raw_value, "8041");
+        assert!(warnings[0].reason.contains("line 1"));
+    }
+}
@@ -1,15 +1,32 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::config_loader::{DorisConfig, Environment, MySQLConfig};
 use crate::error::{CliError, Result};
 use crate::tools::common::fs_utils;
+use crate::tools::common::redact::redact_command;
+
+/// Layout version written to `metadata.schema_version`.
+///
+/// - v1: flat `paths`/`ports` sections, `process` and `mysql` optional
+/// - v2: organized `fe`/`be` sections, no version field
+/// - v3: v2 plus an explicit `schema_version`
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+
+/// Set when the user keeps an unloadable config file; nothing is written for the session
+static PERSIST_SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_persist_suspended() -> bool {
+    PERSIST_SUSPENDED.load(Ordering::Relaxed)
+}
 
 trait ConfigConverter<T> {
     fn convert_to(&self) -> T;
 }
 
-/// Serializable configuration structure with organized FE and BE sections
+/// Serializable configuration structure with organized FE and BE sections (schema v2+)
 #[derive(Serialize, Deserialize)]
 struct OrganizedConfig {
     metadata: Metadata,
@@ -20,12 +37,18 @@ struct OrganizedConfig {
     settings: Settings,
     process: ProcessInfo,
     mysql: Option<MySQLConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    bundles: BTreeMap<String, Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize)]
 struct Metadata {
+    #[serde(default)]
+    schema_version: u32,
     environment: String,
     version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    doris_version: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -61,9 +84,48 @@ struct Network {
 struct Settings {
     timeout_seconds: u64,
     no_progress_animation: bool,
+    #[serde(default)]
+    no_hints: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    log_scan_threads: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profile_name: Option<String>,
+    #[serde(default)]
+    production: bool,
+    #[serde(default)]
+    stdout_only: bool,
+    #[serde(default)]
+    credential_keyring: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    audit_stmt_max_len: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_output_age_days: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_output_total_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    offline_log_dir: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alter_job_warn_hours: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    table_info_max_concurrency: Option<usize>,
+    #[serde(default)]
+    plain_output: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    read_only: Option<bool>,
+    #[serde(default)]
+    flat_output: bool,
+    #[serde(default)]
+    https: bool,
+    #[serde(default)]
+    http_insecure: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    http_auth_user: Option<String>,
+    /// Ciphertext; left out of exported configs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    http_auth_password: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 struct ProcessInfo {
     pid: Option<u32>,
     command: Option<String>,
@@ -93,14 +155,29 @@ struct FeConfig {
     process_command: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// `be` section; without a BE process it only carries `selection`
+#[derive(Serialize, Deserialize, Default)]
 struct BeConfig {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     install_dir: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     conf_dir: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     log_dir: String,
+    #[serde(default, skip_serializing_if = "BePorts::is_empty")]
     ports: BePorts,
     process_pid: Option<u32>,
     process_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    selection: Option<BeSelection>,
+}
+
+/// `[be.selection]`: default BE target kept across sessions
+#[derive(Serialize, Deserialize)]
+struct BeSelection {
+    host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    compute_group: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -112,7 +189,7 @@ struct FePorts {
     cloud_http_port: Option<u16>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 struct BePorts {
     be_port: Option<u16>,
     brpc_port: Option<u16>,
@@ -120,6 +197,15 @@ struct BePorts {
     webserver_port: Option<u16>,
 }
 
+impl BePorts {
+    fn is_empty(&self) -> bool {
+        self.be_port.is_none()
+            && self.brpc_port.is_none()
+            && self.heartbeat_service_port.is_none()
+            && self.webserver_port.is_none()
+    }
+}
+
 fn path_to_string(path: &Path) -> String {
     path.to_string_lossy().to_string()
 }
@@ -134,8 +220,10 @@ impl ConfigConverter<Metadata> for DorisConfig {
         };
 
         Metadata {
+            schema_version: CURRENT_SCHEMA_VERSION,
             environment: env_str.to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
+            doris_version: self.doris_version.clone(),
         }
     }
 }
@@ -183,6 +271,25 @@ impl ConfigConverter<Settings> for DorisConfig {
         Settings {
             timeout_seconds: self.timeout_seconds,
             no_progress_animation: self.no_progress_animation,
+            no_hints: self.no_hints,
+            log_scan_threads: self.log_scan_threads,
+            profile_name: self.profile_name.clone(),
+            production: self.production,
+            stdout_only: self.stdout_only,
+            credential_keyring: self.credential_keyring,
+            audit_stmt_max_len: self.audit_stmt_max_len,
+            max_output_age_days: self.max_output_age_days,
+            max_output_total_bytes: self.max_output_total_bytes,
+            offline_log_dir: self.offline_log_dir.clone(),
+            alter_job_warn_hours: self.alter_job_warn_hours,
+            table_info_max_concurrency: self.table_info_max_concurrency,
+            plain_output: self.plain_output,
+            read_only: self.read_only,
+            flat_output: self.flat_output,
+            https: self.https,
+            http_insecure: self.http_insecure,
+            http_auth_user: self.http_auth_user.clone(),
+            http_auth_password: self.http_auth_password.clone(),
         }
     }
 }
@@ -191,67 +298,18 @@ impl ConfigConverter<ProcessInfo> for DorisConfig {
     fn convert_to(&self) -> ProcessInfo {
         ProcessInfo {
             pid: self.process_pid,
-            command: self.process_command.clone(),
+            command: self.process_command.as_deref().map(redact_command),
             last_detected: self.last_detected.map(|dt| dt.to_rfc3339()),
             be_process_pid: self.be_process_pid,
-            be_process_command: self.be_process_command.clone(),
+            be_process_command: self.be_process_command.as_deref().map(redact_command),
             be_install_dir: self.be_install_dir.as_ref().map(|p| path_to_string(p)),
             fe_process_pid: self.fe_process_pid,
-            fe_process_command: self.fe_process_command.clone(),
+            fe_process_command: self.fe_process_command.as_deref().map(redact_command),
             fe_install_dir: self.fe_install_dir.as_ref().map(|p| path_to_string(p)),
         }
     }
 }
 
-impl ConfigConverter<DorisConfig> for PersistentConfig {
-    fn convert_to(&self) -> DorisConfig {
-        let environment = match self.metadata.environment.as_str() {
-            "FE" => Environment::FE,
-            "BE" => Environment::BE,
-            "FE + BE" => Environment::Mixed,
-            _ => Environment::Unknown,
-        };
-
-        DorisConfig {
-            environment,
-            install_dir: PathBuf::from(&self.paths.install_dir),
-            conf_dir: PathBuf::from(&self.paths.conf_dir),
-            log_dir: PathBuf::from(&self.paths.log_dir),
-            jdk_path: PathBuf::from(&self.paths.jdk_path),
-            output_dir: PathBuf::from(&self.paths.output_dir),
-            timeout_seconds: self.settings.timeout_seconds,
-            no_progress_animation: self.settings.no_progress_animation,
-            process_pid: self.process.pid,
-            process_command: self.process.command.clone(),
-            last_detected: self
-                .process
-                .last_detected
-                .as_ref()
-                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
-                .map(|dt| dt.with_timezone(&chrono::Utc)),
-            be_process_pid: self.process.be_process_pid,
-            be_process_command: self.process.be_process_command.clone(),
-            be_install_dir: self.process.be_install_dir.as_ref().map(PathBuf::from),
-            fe_process_pid: self.process.fe_process_pid,
-            fe_process_command: self.process.fe_process_command.clone(),
-            fe_install_dir: self.process.fe_install_dir.as_ref().map(PathBuf::from),
-            be_port: self.ports.be_port,
-            brpc_port: self.ports.brpc_port,
-            heartbeat_service_port: self.ports.heartbeat_service_port,
-            webserver_port: self.ports.webserver_port,
-            http_port: self.ports.http_port,
-            rpc_port: self.ports.rpc_port,
-            query_port: self.ports.query_port,
-            edit_log_port: self.ports.edit_log_port,
-            cloud_http_port: self.ports.cloud_http_port,
-            meta_dir: self.paths.meta_dir.as_ref().map(PathBuf::from),
-            priority_networks: self.network.priority_networks.clone(),
-            meta_service_endpoint: self.network.meta_service_endpoint.clone(),
-            mysql: self.mysql.clone(),
-        }
-    }
-}
-
 /// Convert persistent format to internal config
 fn from_persistent_config(persistent: PersistentConfig) -> DorisConfig {
     let environment = match persistent.metadata.environment.as_str() {
@@ -270,6 +328,25 @@ fn from_persistent_config(persistent: PersistentConfig) -> DorisConfig {
         output_dir: PathBuf::from(&persistent.paths.output_dir),
         timeout_seconds: persistent.settings.timeout_seconds,
         no_progress_animation: persistent.settings.no_progress_animation,
+        no_hints: persistent.settings.no_hints,
+        log_scan_threads: persistent.settings.log_scan_threads,
+        profile_name: persistent.settings.profile_name.clone(),
+        production: persistent.settings.production,
+        plain_output: persistent.settings.plain_output,
+        read_only: persistent.settings.read_only,
+        flat_output: persistent.settings.flat_output,
+        https: persistent.settings.https,
+        http_insecure: persistent.settings.http_insecure,
+        http_auth_user: persistent.settings.http_auth_user.clone(),
+        http_auth_password: persistent.settings.http_auth_password.clone(),
+        alter_job_warn_hours: persistent.settings.alter_job_warn_hours,
+        table_info_max_concurrency: persistent.settings.table_info_max_concurrency,
+        offline_log_dir: persistent.settings.offline_log_dir.clone(),
+        max_output_total_bytes: persistent.settings.max_output_total_bytes,
+        max_output_age_days: persistent.settings.max_output_age_days,
+        audit_stmt_max_len: persistent.settings.audit_stmt_max_len,
+        credential_keyring: persistent.settings.credential_keyring,
+        stdout_only: persistent.settings.stdout_only,
         process_pid: persistent.process.pid,
         process_command: persistent.process.command.clone(),
         last_detected: persistent
@@ -305,6 +382,10 @@ fn from_persistent_config(persistent: PersistentConfig) -> DorisConfig {
         priority_networks: persistent.network.priority_networks.clone(),
         meta_service_endpoint: persistent.network.meta_service_endpoint.clone(),
         mysql: persistent.mysql.clone(),
+        doris_version: persistent.metadata.doris_version.clone(),
+        bundles: BTreeMap::new(),
+        be_selected_host: None,
+        be_selected_compute_group: None,
     }
 }
 
@@ -333,12 +414,22 @@ impl PersistResult {
 
 /// Persist configuration to file
 pub fn persist_config(config: &DorisConfig) -> Result<PersistResult> {
+    if is_persist_suspended() {
+        return Err(CliError::ConfigError(
+            "Config file could not be loaded and is kept unchanged for this session".to_string(),
+        ));
+    }
     let config_paths = get_config_file_paths()?;
     let organized_config = to_organized_config(config);
+    let content = toml::to_string_pretty(&organized_config)
+        .map_err(|e| CliError::ConfigError(format!("Failed to serialize config: {e}")))?;
 
     let mut errors = Vec::new();
 
     for config_path in &config_paths {
+        if let Err(e) = rotate_backups(config_path, &content) {
+            crate::ui::print_warning(&format!("Could not back up config file: {e}"));
+        }
         match fs_utils::save_toml_to_file(&organized_config, config_path) {
             Ok(_) => {
                 if errors.is_empty() {
@@ -362,172 +453,524 @@ pub fn persist_config(config: &DorisConfig) -> Result<PersistResult> {
     }
 }
 
-fn migrate_legacy_config(content: &str, config_path: &Path) -> Option<DorisConfig> {
-    #[derive(Deserialize)]
-    struct LegacyConfig {
-        metadata: Metadata,
-        paths: Paths,
-        ports: Ports,
-        network: Network,
-        settings: Settings,
-    }
-
-    match toml::from_str::<LegacyConfig>(content) {
-        Ok(legacy) => {
-            let new_config = PersistentConfig {
-                metadata: legacy.metadata,
-                paths: legacy.paths,
-                ports: legacy.ports,
-                network: legacy.network,
-                settings: legacy.settings,
-                process: ProcessInfo {
-                    pid: None,
-                    command: None,
-                    last_detected: None,
-                    be_process_pid: None,
-                    be_process_command: None,
-                    be_install_dir: None,
-                    fe_process_pid: None,
-                    fe_process_command: None,
-                    fe_install_dir: None,
-                },
-                mysql: None,
-            };
+/// Layout version of a config file; files without the field are detected by shape
+fn detect_schema_version(value: &toml::Value) -> u32 {
+    if let Some(v) = value
+        .get("metadata")
+        .and_then(|m| m.get("schema_version"))
+        .and_then(|v| v.as_integer())
+    {
+        return v.max(0) as u32;
+    }
+    let flat = value.get("ports").is_some()
+        || value
+            .get("paths")
+            .and_then(|p| p.get("install_dir"))
+            .is_some();
+    if flat { 1 } else { 2 }
+}
 
-            match fs_utils::save_toml_to_file(&new_config, config_path) {
-                Ok(_) => {
-                    // Migration successful
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to save migrated config: {e}");
-                }
-            }
+/// v1 → v2: move flat paths and ports into the FE/BE sections
+fn migrate_v1_to_v2(v1: PersistentConfig) -> OrganizedConfig {
+    to_organized_config(&from_persistent_config(v1))
+}
+
+/// v2 → v3: same layout, the version is now recorded
+fn migrate_v2_to_v3(mut v2: OrganizedConfig) -> OrganizedConfig {
+    v2.metadata.schema_version = 3;
+    v2
+}
+
+/// Parse a config file of any known version and bring it up to the current layout.
+///
+/// Returns the migrated config and the version it was read as.
+fn migrate_config(content: &str) -> Result<(OrganizedConfig, u32)> {
+    let value: toml::Value = toml::from_str(content)?;
+    let from_version = detect_schema_version(&value);
+
+    let mut config = match from_version {
+        1 => migrate_v1_to_v2(toml::from_str::<PersistentConfig>(content)?),
+        2 | CURRENT_SCHEMA_VERSION => toml::from_str::<OrganizedConfig>(content)?,
+        v if v > CURRENT_SCHEMA_VERSION => {
+            return Err(CliError::ConfigError(format!(
+                "schema version {v} is newer than this cloud-cli supports ({CURRENT_SCHEMA_VERSION})"
+            )));
+        }
+        v => {
+            return Err(CliError::ConfigError(format!("unknown schema version {v}")));
+        }
+    };
+    if from_version < 3 {
+        config = migrate_v2_to_v3(config);
+    }
+
+    Ok((config, from_version))
+}
 
-            Some(new_config.convert_to())
+fn backup_path(config_path: &Path) -> PathBuf {
+    let mut name = config_path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// Copy the config file to `<file>.bak` before it is rewritten or reset
+fn backup_config_file(config_path: &Path) -> Result<PathBuf> {
+    let bak = backup_path(config_path);
+    std::fs::copy(config_path, &bak)?;
+    Ok(bak)
+}
+
+/// Rotating copies kept before each overwrite, `.bak.1` being the newest
+pub const BACKUP_COUNT: usize = 3;
+
+fn rotated_backup_path(config_path: &Path, n: usize) -> PathBuf {
+    let mut name = config_path.as_os_str().to_owned();
+    name.push(format!(".bak.{n}"));
+    PathBuf::from(name)
+}
+
+/// Shift `.bak.1..` up by one and copy the file to `.bak.1`.
+///
+/// Skipped when there is no file yet or it already holds `new_content`, so
+/// repeated saves of the same config do not push older backups out.
+fn rotate_backups(config_path: &Path, new_content: &str) -> Result<()> {
+    match std::fs::read_to_string(config_path) {
+        Ok(current) if current == new_content => return Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        _ => {}
+    }
+    for n in (1..BACKUP_COUNT).rev() {
+        let from = rotated_backup_path(config_path, n);
+        if from.exists() {
+            std::fs::rename(&from, rotated_backup_path(config_path, n + 1))?;
+        }
+    }
+    std::fs::copy(config_path, rotated_backup_path(config_path, 1))?;
+    Ok(())
+}
+
+/// A saved copy of the config file offered for restore
+pub struct ConfigBackup {
+    pub path: PathBuf,
+    pub modified: Option<chrono::DateTime<chrono::Local>>,
+    /// The parse error when the copy cannot be restored
+    pub config: std::result::Result<DorisConfig, String>,
+}
+
+/// Rotated backups newest first, then the `.bak` left by a migration or reset
+fn backups_of(config_path: &Path) -> Vec<ConfigBackup> {
+    (1..=BACKUP_COUNT)
+        .map(|n| rotated_backup_path(config_path, n))
+        .chain(std::iter::once(backup_path(config_path)))
+        .filter(|path| path.is_file())
+        .map(|path| ConfigBackup {
+            modified: std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(chrono::DateTime::from),
+            config: read_backup(&path).map_err(|e| e.to_string()),
+            path,
+        })
+        .collect()
+}
+
+pub fn list_backups() -> Result<Vec<ConfigBackup>> {
+    Ok(get_config_file_paths()?
+        .first()
+        .map(|p| backups_of(p))
+        .unwrap_or_default())
+}
+
+fn read_backup(path: &Path) -> Result<DorisConfig> {
+    let content = std::fs::read_to_string(path)?;
+    let (config, _) = migrate_config(&content)?;
+    Ok(from_organized_config(&config))
+}
+
+/// What restoring `backup` would change: environment, ports and MySQL credentials
+pub fn backup_differences(current: &DorisConfig, backup: &DorisConfig) -> Vec<String> {
+    fn port(p: Option<u16>) -> String {
+        p.map_or_else(|| "unset".to_string(), |p| p.to_string())
+    }
+    let mut out = Vec::new();
+    if current.environment != backup.environment {
+        out.push(format!(
+            "environment {} -> {}",
+            current.environment, backup.environment
+        ));
+    }
+    let ports = [
+        ("http_port", current.http_port, backup.http_port),
+        ("query_port", current.query_port, backup.query_port),
+        ("rpc_port", current.rpc_port, backup.rpc_port),
+        ("edit_log_port", current.edit_log_port, backup.edit_log_port),
+        ("be_port", current.be_port, backup.be_port),
+        (
+            "webserver_port",
+            current.webserver_port,
+            backup.webserver_port,
+        ),
+        ("brpc_port", current.brpc_port, backup.brpc_port),
+        (
+            "heartbeat_service_port",
+            current.heartbeat_service_port,
+            backup.heartbeat_service_port,
+        ),
+    ];
+    for (key, now, then) in ports {
+        if now != then {
+            out.push(format!("{key} {} -> {}", port(now), port(then)));
+        }
+    }
+    match (&current.mysql, &backup.mysql) {
+        (Some(_), None) => out.push("MySQL credentials removed".to_string()),
+        (None, Some(m)) => out.push(format!("MySQL credentials for '{}' restored", m.user)),
+        (Some(a), Some(b)) if a.user != b.user => {
+            out.push(format!("MySQL user '{}' -> '{}'", a.user, b.user))
         }
-        Err(_) => None,
+        _ => {}
     }
+    out
 }
 
-/// Load persisted configuration from file
+/// Put a backup back in place of the config file and load it as at startup.
+///
+/// A backup that does not parse is refused; the replaced file becomes `.bak.1`.
+pub fn restore_backup(backup: &Path) -> Result<DorisConfig> {
+    let content = std::fs::read_to_string(backup)?;
+    migrate_config(&content).map_err(|e| {
+        CliError::ConfigError(format!(
+            "Backup {} cannot be restored: {e}",
+            backup.display()
+        ))
+    })?;
+    let config_path = get_config_file_paths()?
+        .into_iter()
+        .next()
+        .ok_or_else(|| CliError::ConfigError("No config file path".to_string()))?;
+
+    rotate_backups(&config_path, &content)?;
+    fs_utils::ensure_dir_exists(&config_path)?;
+    fs_utils::write_atomic(&config_path, content.as_bytes())?;
+    // A readable file is back, so saving is safe again
+    PERSIST_SUSPENDED.store(false, Ordering::Relaxed);
+    load_persisted_config()
+}
+
+fn rewrite_migrated(config_path: &Path, config: &OrganizedConfig, from_version: u32) {
+    crate::ui::print_warning(&format!(
+        "Config file {} uses schema v{from_version}, migrating to v{CURRENT_SCHEMA_VERSION}",
+        config_path.display()
+    ));
+    match backup_config_file(config_path) {
+        Ok(bak) => crate::ui::print_warning(&format!("Previous config saved to {}", bak.display())),
+        Err(e) => {
+            crate::ui::print_warning(&format!(
+                "Could not back up config file, leaving it unchanged: {e}"
+            ));
+            return;
+        }
+    }
+    if let Err(e) = fs_utils::save_toml_to_file(config, config_path) {
+        crate::ui::print_warning(&format!("Failed to save migrated config: {e}"));
+    }
+}
+
+/// Load persisted configuration from file.
+///
+/// Older layouts are migrated (with a `.bak` copy); a file that cannot be parsed is an
+/// error rather than silently replaced by defaults.
 pub fn load_persisted_config() -> Result<DorisConfig> {
     let config_paths = get_config_file_paths()?;
-    let mut last_error = None;
 
     for config_path in config_paths {
         if !config_path.exists() {
             continue;
         }
 
-        match fs_utils::read_file_content(&config_path) {
-            Ok(content) => {
-                if let Some(config) = parse_legacy_config_with_mysql(&content) {
-                    return Ok(config);
-                }
+        let content = fs_utils::read_file_content(&config_path).map_err(|e| {
+            CliError::ConfigError(format!(
+                "Failed to read config file {}: {e}",
+                config_path.display()
+            ))
+        })?;
+        let (config, from_version) = migrate_config(&content).map_err(|e| {
+            CliError::ConfigError(format!(
+                "Failed to parse config file {}: {e}",
+                config_path.display()
+            ))
+        })?;
+        if from_version < CURRENT_SCHEMA_VERSION && !is_persist_suspended() {
+            rewrite_migrated(&config_path, &config, from_version);
+        }
+        return Ok(from_organized_config(&config));
+    }
+
+    Ok(DorisConfig::default())
+}
 
-                if let Ok(organized_config) = toml::from_str::<OrganizedConfig>(&content) {
-                    return Ok(from_organized_config(&organized_config));
+/// Ask how to proceed when the config file exists but cannot be loaded.
+///
+/// Run once at startup, before anything can overwrite the file with defaults.
+pub fn recover_unloadable_config() -> Result<()> {
+    let Err(e) = load_persisted_config() else {
+        return Ok(());
+    };
+    let Some(config_path) = get_config_file_paths()?.into_iter().find(|p| p.exists()) else {
+        return Ok(());
+    };
+
+    crate::ui::print_error(&e.to_string());
+    let choice = crate::ui::dialogs::select_index(
+        "How do you want to continue?",
+        &[
+            "Reset config (current file is kept as .bak)",
+            "Keep the file unchanged and continue with detected settings",
+        ],
+    )?;
+
+    if choice == 0 {
+        let bak = backup_config_file(&config_path)?;
+        std::fs::remove_file(&config_path)?;
+        crate::ui::print_warning(&format!(
+            "Config reset, previous file saved to {}",
+            bak.display()
+        ));
+    } else {
+        PERSIST_SUSPENDED.store(true, Ordering::Relaxed);
+        crate::ui::print_warning("Config changes will not be saved this session.");
+    }
+    Ok(())
+}
+
+/// Keys an import never changes: the stored ciphertext only decrypts with this
+/// host's key (so HTTP auth is set up per host), the key backend stays as chosen
+/// here, and process and version fields describe this host rather than the exporting one
+const IMPORT_PROTECTED: &[&str] = &[
+    "mysql.password",
+    "settings.http_auth_",
+    "settings.credential_keyring",
+    "metadata.schema_version",
+    "metadata.version",
+    "metadata.doris_version",
+    "process.",
+    "fe.process_",
+    "be.process_",
+];
+
+fn is_import_protected(key: &str) -> bool {
+    IMPORT_PROTECTED.iter().any(|p| key.starts_with(p))
+}
+
+/// Leaf values of a config keyed by dotted path, e.g. `fe.ports.http_port`
+fn flatten_config(config: &OrganizedConfig) -> Result<BTreeMap<String, toml::Value>> {
+    let value = toml::Value::try_from(config)
+        .map_err(|e| CliError::ConfigError(format!("Failed to serialize config: {e}")))?;
+    Ok(flatten_value(value))
+}
+
+fn flatten_value(value: toml::Value) -> BTreeMap<String, toml::Value> {
+    fn walk(prefix: &str, value: toml::Value, out: &mut BTreeMap<String, toml::Value>) {
+        match value {
+            // Kept as a leaf so required sections with only unset fields survive
+            toml::Value::Table(table) if table.is_empty() && !prefix.is_empty() => {
+                out.insert(prefix.to_string(), toml::Value::Table(table));
+            }
+            toml::Value::Table(table) => {
+                for (key, value) in table {
+                    let path = if prefix.is_empty() {
+                        key
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    walk(&path, value, out);
                 }
+            }
+            leaf => {
+                out.insert(prefix.to_string(), leaf);
+            }
+        }
+    }
+    let mut out = BTreeMap::new();
+    walk("", value, &mut out);
+    out
+}
+
+/// The config file in use, if one exists
+pub fn persisted_config_path() -> Option<PathBuf> {
+    get_config_file_paths()
+        .ok()?
+        .into_iter()
+        .find(|p| p.exists())
+}
+
+/// Dotted keys set in the config file; empty when there is none or it does not parse
+pub fn persisted_keys() -> BTreeSet<String> {
+    persisted_config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .map(|value| flatten_value(value).into_keys().collect())
+        .unwrap_or_default()
+}
+
+fn unflatten_config(flat: BTreeMap<String, toml::Value>) -> Result<OrganizedConfig> {
+    let mut root = toml::Table::new();
+    for (path, value) in flat {
+        let mut parts: Vec<&str> = path.split('.').collect();
+        let leaf = parts.pop().unwrap_or_default();
+        let mut table = &mut root;
+        for part in parts {
+            table = table
+                .entry(part)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| CliError::ConfigError(format!("Conflicting key {path}")))?;
+        }
+        table.insert(leaf.to_string(), value);
+    }
+    Ok(toml::Value::Table(root).try_into()?)
+}
 
-                match toml::from_str::<PersistentConfig>(&content) {
-                    Ok(persistent_config) => {
-                        return Ok(from_persistent_config(persistent_config));
-                    }
-                    Err(e) => {
-                        if e.to_string().contains("missing field `process`")
-                            && migrate_legacy_config(&content, &config_path).is_some()
-                        {
-                            return Ok(migrate_legacy_config(&content, &config_path).unwrap());
-                        }
-
-                        last_error = Some(CliError::ConfigError(format!(
-                            "Failed to parse config file {}: {e}",
-                            config_path.display()
-                        )));
-                    }
+/// Config as exported for other hosts: no MySQL ciphertext and no process details
+fn to_export_config(config: &DorisConfig) -> OrganizedConfig {
+    let mut organized = to_organized_config(config);
+    organized.process = ProcessInfo::default();
+    if let Some(fe) = organized.fe.as_mut() {
+        fe.process_pid = None;
+        fe.process_command = None;
+    }
+    if let Some(be) = organized.be.as_mut() {
+        be.process_pid = None;
+        be.process_command = None;
+        be.selection = None;
+    }
+    // A selection-only section has nothing left to export
+    if organized
+        .be
+        .as_ref()
+        .is_some_and(|be| be.install_dir.is_empty())
+    {
+        organized.be = None;
+    }
+    if let Some(mysql) = organized.mysql.as_mut() {
+        mysql.password.clear();
+    }
+    organized.settings.http_auth_password = None;
+    organized
+}
+
+/// Write the current config to `path` for provisioning other hosts
+pub fn export_config(config: &DorisConfig, path: &Path) -> Result<()> {
+    fs_utils::save_toml_to_file(&to_export_config(config), path)
+}
+
+/// Ports must be non-zero; missing paths only warn since the exporting host's
+/// layout may not exist here yet
+fn validate_import(flat: &BTreeMap<String, toml::Value>) -> Result<Vec<String>> {
+    let mut warnings = Vec::new();
+    for (key, value) in flat {
+        let leaf = key.rsplit('.').next().unwrap_or(key);
+        if leaf.ends_with("_port") && value.as_integer() == Some(0) {
+            return Err(CliError::InvalidInput(format!(
+                "{key} must be a port from 1 to 65535"
+            )));
+        }
+        if (leaf.ends_with("_dir") || leaf.ends_with("_path"))
+            && let Some(path) = value.as_str()
+            && !path.is_empty()
+            && !Path::new(path).exists()
+        {
+            warnings.push(format!("{key} = {path} does not exist on this host"));
+        }
+    }
+    Ok(warnings)
+}
+
+/// Merge `imported` into `live`. `resolve(key, current, imported)` decides differing
+/// values (true takes the imported one); protected keys and keys only in `live` are
+/// kept, so an export without a `[mysql]` section leaves the stored one in place.
+fn merge_import(
+    live: &OrganizedConfig,
+    imported: &OrganizedConfig,
+    resolve: &mut dyn FnMut(&str, &toml::Value, &toml::Value) -> Result<bool>,
+) -> Result<(OrganizedConfig, Vec<String>)> {
+    let mut merged = flatten_config(live)?;
+    let mut notes = Vec::new();
+    for (key, value) in flatten_config(imported)? {
+        if is_import_protected(&key) {
+            continue;
+        }
+        match merged.get(&key) {
+            Some(current) if *current == value => {}
+            Some(current) => {
+                if resolve(&key, current, &value)? {
+                    merged.insert(key, value);
                 }
             }
-            Err(e) => {
-                last_error = Some(CliError::ConfigError(format!(
-                    "Failed to read config file {}: {e}",
-                    config_path.display()
-                )));
+            None => {
+                merged.insert(key, value);
             }
         }
     }
+    if merged.contains_key("mysql.user") && !merged.contains_key("mysql.password") {
+        // A user without ciphertext from this host cannot log in
+        merged.retain(|k, _| !k.starts_with("mysql."));
+        notes.push("Imported MySQL user skipped; set credentials on this host".to_string());
+    }
+    Ok((unflatten_config(merged)?, notes))
+}
 
-    match last_error {
-        Some(e) => {
-            eprintln!("Warning: {e}");
-            Ok(DorisConfig::default())
+/// Ask per differing key, with an option to apply the same answer to the rest
+fn interactive_resolver() -> impl FnMut(&str, &toml::Value, &toml::Value) -> Result<bool> {
+    let mut remembered: Option<bool> = None;
+    move |key, current, imported| {
+        if let Some(choice) = remembered {
+            return Ok(choice);
         }
-        None => Ok(DorisConfig::default()),
-    }
-}
-
-/// Parse legacy config format that includes mysql section
-fn parse_legacy_config_with_mysql(content: &str) -> Option<DorisConfig> {
-    #[derive(Deserialize)]
-    struct LegacyConfigWithMySQL {
-        metadata: Metadata,
-        paths: CommonPaths,
-        network: Network,
-        settings: Settings,
-        process: ProcessInfo,
-        mysql: Option<MySQLConfig>,
-    }
-
-    match toml::from_str::<LegacyConfigWithMySQL>(content) {
-        Ok(legacy) => {
-            let environment = match legacy.metadata.environment.as_str() {
-                "FE" => Environment::FE,
-                "BE" => Environment::BE,
-                "FE + BE" => Environment::Mixed,
-                _ => Environment::Unknown,
-            };
-
-            Some(DorisConfig {
-                environment,
-                install_dir: PathBuf::from("/opt/selectdb"),
-                conf_dir: PathBuf::from("/opt/selectdb/conf"),
-                log_dir: PathBuf::from("/opt/selectdb/log"),
-                jdk_path: PathBuf::from(&legacy.paths.jdk_path),
-                output_dir: PathBuf::from(&legacy.paths.output_dir),
-                timeout_seconds: legacy.settings.timeout_seconds,
-                no_progress_animation: legacy.settings.no_progress_animation,
-                process_pid: legacy.process.pid,
-                process_command: legacy.process.command.clone(),
-                last_detected: legacy
-                    .process
-                    .last_detected
-                    .as_ref()
-                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
-                    .map(|dt| dt.with_timezone(&chrono::Utc)),
-                be_process_pid: legacy.process.be_process_pid,
-                be_process_command: legacy.process.be_process_command.clone(),
-                be_install_dir: legacy.process.be_install_dir.as_ref().map(PathBuf::from),
-                fe_process_pid: legacy.process.fe_process_pid,
-                fe_process_command: legacy.process.fe_process_command.clone(),
-                fe_install_dir: legacy.process.fe_install_dir.as_ref().map(PathBuf::from),
-                be_port: None,
-                brpc_port: None,
-                heartbeat_service_port: None,
-                webserver_port: None,
-                http_port: None,
-                rpc_port: None,
-                query_port: None,
-                edit_log_port: None,
-                cloud_http_port: None,
-                meta_dir: None,
-                priority_networks: legacy.network.priority_networks.clone(),
-                meta_service_endpoint: legacy.network.meta_service_endpoint.clone(),
-                mysql: legacy.mysql,
-            })
+        let choice = crate::ui::dialogs::select_index(
+            &format!("{key}: current {current}, imported {imported}"),
+            &[
+                "Keep current",
+                "Use imported",
+                "Keep current for all remaining",
+                "Use imported for all remaining",
+            ],
+        )?;
+        let take = choice % 2 == 1;
+        if choice >= 2 {
+            remembered = Some(take);
         }
-        Err(_) => None,
+        Ok(take)
     }
 }
 
+/// Import a config exported by [`export_config`] into `live` and persist the result
+pub fn import_config(live: &DorisConfig, path: &Path) -> Result<DorisConfig> {
+    let content = fs_utils::read_file_content(path)
+        .map_err(|e| CliError::ConfigError(format!("Failed to read {}: {e}", path.display())))?;
+    let (imported, _) = migrate_config(&content)
+        .map_err(|e| CliError::ConfigError(format!("Failed to parse {}: {e}", path.display())))?;
+    for warning in validate_import(&flatten_config(&imported)?)? {
+        crate::ui::print_warning(&warning);
+    }
+
+    let (merged, notes) = merge_import(
+        &to_organized_config(live),
+        &imported,
+        &mut interactive_resolver(),
+    )?;
+    for note in &notes {
+        crate::ui::print_warning(note);
+    }
+    let config = from_organized_config(&merged);
+    if !persist_config(&config)?.is_success() {
+        return Err(CliError::ConfigError(
+            "Imported config could not be saved".to_string(),
+        ));
+    }
+    Ok(config)
+}
+
 /// Convert organized config to internal config
 fn from_organized_config(organized: &OrganizedConfig) -> DorisConfig {
     let environment = match organized.metadata.environment.as_str() {
@@ -551,6 +994,25 @@ fn from_organized_config(organized: &OrganizedConfig) -> DorisConfig {
         // Settings
         timeout_seconds: organized.settings.timeout_seconds,
         no_progress_animation: organized.settings.no_progress_animation,
+        no_hints: organized.settings.no_hints,
+        log_scan_threads: organized.settings.log_scan_threads,
+        profile_name: organized.settings.profile_name.clone(),
+        production: organized.settings.production,
+        plain_output: organized.settings.plain_output,
+        read_only: organized.settings.read_only,
+        flat_output: organized.settings.flat_output,
+        https: organized.settings.https,
+        http_insecure: organized.settings.http_insecure,
+        http_auth_user: organized.settings.http_auth_user.clone(),
+        http_auth_password: organized.settings.http_auth_password.clone(),
+        alter_job_warn_hours: organized.settings.alter_job_warn_hours,
+        table_info_max_concurrency: organized.settings.table_info_max_concurrency,
+        offline_log_dir: organized.settings.offline_log_dir.clone(),
+        max_output_total_bytes: organized.settings.max_output_total_bytes,
+        max_output_age_days: organized.settings.max_output_age_days,
+        audit_stmt_max_len: organized.settings.audit_stmt_max_len,
+        credential_keyring: organized.settings.credential_keyring,
+        stdout_only: organized.settings.stdout_only,
 
         // Network
         priority_networks: organized.network.priority_networks.clone(),
@@ -584,10 +1046,23 @@ fn from_organized_config(organized: &OrganizedConfig) -> DorisConfig {
         fe_process_command: None,
         fe_install_dir: None,
         mysql: organized.mysql.clone(),
+        doris_version: organized.metadata.doris_version.clone(),
+        bundles: organized.bundles.clone(),
+        be_selected_host: None,
+        be_selected_compute_group: None,
     };
 
+    if let Some(selection) = organized.be.as_ref().and_then(|be| be.selection.as_ref()) {
+        config.be_selected_host = Some(selection.host.clone());
+        config.be_selected_compute_group = selection.compute_group.clone();
+    }
+
     // Set BE specific configurations if available
-    if let Some(be) = &organized.be {
+    if let Some(be) = organized
+        .be
+        .as_ref()
+        .filter(|be| !be.install_dir.is_empty())
+    {
         config.be_port = be.ports.be_port;
         config.brpc_port = be.ports.brpc_port;
         config.heartbeat_service_port = be.ports.heartbeat_service_port;
@@ -626,7 +1101,11 @@ fn from_organized_config(organized: &OrganizedConfig) -> DorisConfig {
 
     // For mixed environment, prioritize BE for main install_dir
     if environment == Environment::Mixed {
-        if let Some(be) = &organized.be {
+        if let Some(be) = organized
+            .be
+            .as_ref()
+            .filter(|be| !be.install_dir.is_empty())
+        {
             config.install_dir = PathBuf::from(&be.install_dir);
             config.conf_dir = PathBuf::from(&be.conf_dir);
             config.log_dir = PathBuf::from(&be.log_dir);
@@ -640,7 +1119,7 @@ fn from_organized_config(organized: &OrganizedConfig) -> DorisConfig {
     config
 }
 
-/// Serializable configuration structure
+/// Schema v1 layout with flat paths and ports
 #[derive(Serialize, Deserialize)]
 struct PersistentConfig {
     metadata: Metadata,
@@ -648,7 +1127,9 @@ struct PersistentConfig {
     ports: Ports,
     network: Network,
     settings: Settings,
+    #[serde(default)]
     process: ProcessInfo,
+    #[serde(default)]
     mysql: Option<MySQLConfig>,
 }
 
@@ -685,9 +1166,9 @@ fn to_organized_config(config: &DorisConfig) -> OrganizedConfig {
                     config.fe_process_pid
                 },
                 process_command: if config.environment == Environment::FE {
-                    config.process_command.clone()
+                    config.process_command.as_deref().map(redact_command)
                 } else {
-                    config.fe_process_command.clone()
+                    config.fe_process_command.as_deref().map(redact_command)
                 },
             })
         } else {
@@ -717,15 +1198,29 @@ fn to_organized_config(config: &DorisConfig) -> OrganizedConfig {
                     config.be_process_pid
                 },
                 process_command: if config.environment == Environment::BE {
-                    config.process_command.clone()
+                    config.process_command.as_deref().map(redact_command)
                 } else {
-                    config.be_process_command.clone()
+                    config.be_process_command.as_deref().map(redact_command)
                 },
+                selection: None,
             })
         } else {
             None
         };
 
+    let be_selection = config.be_selected_host.as_ref().map(|host| BeSelection {
+        host: host.clone(),
+        compute_group: config.be_selected_compute_group.clone(),
+    });
+    let be_config = match (be_config, be_selection) {
+        (Some(be), selection) => Some(BeConfig { selection, ..be }),
+        (None, Some(selection)) => Some(BeConfig {
+            selection: Some(selection),
+            ..Default::default()
+        }),
+        (None, None) => None,
+    };
+
     // Create organized config
     OrganizedConfig {
         metadata: config.convert_to(),
@@ -736,5 +1231,313 @@ fn to_organized_config(config: &DorisConfig) -> OrganizedConfig {
         settings: config.convert_to(),
         process: config.convert_to(),
         mysql: config.mysql.clone(),
+        bundles: config.bundles.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1_WITHOUT_PROCESS: &str = r#"
+[metadata]
+environment = "BE"
+version = "0.1.0"
+
+[paths]
+install_dir = "/opt/doris/be"
+conf_dir = "/opt/doris/be/conf"
+log_dir = "/opt/doris/be/log"
+jdk_path = "/opt/jdk"
+output_dir = "/tmp/doris"
+
+[ports]
+be_port = 9060
+brpc_port = 8060
+heartbeat_service_port = 9050
+webserver_port = 8040
+
+[network]
+
+[settings]
+timeout_seconds = 60
+no_progress_animation = false
+"#;
+
+    const V1_WITH_MYSQL: &str = r#"
+[metadata]
+environment = "FE"
+version = "0.2.0"
+
+[paths]
+install_dir = "/opt/doris/fe"
+conf_dir = "/opt/doris/fe/conf"
+log_dir = "/opt/doris/fe/log"
+jdk_path = "/opt/jdk"
+output_dir = "/tmp/doris"
+
+[ports]
+http_port = 8030
+query_port = 9030
+
+[network]
+priority_networks = "10.0.0.0/24"
+
+[settings]
+timeout_seconds = 60
+no_progress_animation = true
+
+[process]
+pid = 1234
+
+[mysql]
+user = "root"
+password = "encrypted"
+"#;
+
+    const V2_ORGANIZED: &str = r#"
+[metadata]
+environment = "FE + BE"
+version = "0.3.0"
+
+[paths]
+jdk_path = "/opt/jdk"
+output_dir = "/tmp/doris"
+
+[fe]
+install_dir = "/opt/doris/fe"
+conf_dir = "/opt/doris/fe/conf"
+log_dir = "/opt/doris/fe/log"
+
+[fe.ports]
+http_port = 8030
+query_port = 9030
+
+[be]
+install_dir = "/opt/doris/be"
+conf_dir = "/opt/doris/be/conf"
+log_dir = "/opt/doris/be/log"
+
+[be.ports]
+webserver_port = 8040
+
+[network]
+
+[settings]
+timeout_seconds = 60
+no_progress_animation = false
+
+[process]
+
+[mysql]
+user = "admin"
+password = "encrypted"
+"#;
+
+    /// Migrate a sample, then check the rewritten file loads as current with the same content
+    fn migrate_round_trip(sample: &str, expected_version: u32) -> DorisConfig {
+        let (migrated, from_version) = migrate_config(sample).unwrap();
+        assert_eq!(from_version, expected_version);
+        assert_eq!(migrated.metadata.schema_version, CURRENT_SCHEMA_VERSION);
+        let loaded = from_organized_config(&migrated);
+
+        let rewritten = toml::to_string_pretty(&migrated).unwrap();
+        let (again, again_version) = migrate_config(&rewritten).unwrap();
+        assert_eq!(again_version, CURRENT_SCHEMA_VERSION);
+        let reloaded = from_organized_config(&again);
+        assert_eq!(
+            toml::to_string(&to_organized_config(&loaded)).unwrap(),
+            toml::to_string(&to_organized_config(&reloaded)).unwrap()
+        );
+        loaded
+    }
+
+    #[test]
+    fn v1_without_process_keeps_be_ports() {
+        let loaded = migrate_round_trip(V1_WITHOUT_PROCESS, 1);
+        assert_eq!(loaded.environment, Environment::BE);
+        assert_eq!(loaded.webserver_port, Some(8040));
+        assert_eq!(loaded.heartbeat_service_port, Some(9050));
+        assert_eq!(loaded.install_dir, PathBuf::from("/opt/doris/be"));
+    }
+
+    #[test]
+    fn v1_with_mysql_keeps_fe_ports_and_credentials() {
+        let loaded = migrate_round_trip(V1_WITH_MYSQL, 1);
+        assert_eq!(loaded.query_port, Some(9030));
+        assert_eq!(loaded.http_port, Some(8030));
+        assert_eq!(loaded.mysql.as_ref().map(|m| m.user.as_str()), Some("root"));
+        assert_eq!(loaded.priority_networks.as_deref(), Some("10.0.0.0/24"));
+    }
+
+    #[test]
+    fn v2_organized_is_stamped_with_version() {
+        let loaded = migrate_round_trip(V2_ORGANIZED, 2);
+        assert_eq!(loaded.environment, Environment::Mixed);
+        assert_eq!(loaded.query_port, Some(9030));
+        assert_eq!(loaded.webserver_port, Some(8040));
+        assert_eq!(
+            loaded.mysql.as_ref().map(|m| m.user.as_str()),
+            Some("admin")
+        );
+    }
+
+    #[test]
+    fn current_config_round_trips_and_bad_files_fail() {
+        let config = DorisConfig {
+            environment: Environment::FE,
+            profile_name: Some("prod-bj".to_string()),
+            production: true,
+            read_only: Some(false),
+            https: true,
+            http_auth_user: Some("ops".to_string()),
+            table_info_max_concurrency: Some(8),
+            query_port: Some(9030),
+            be_selected_host: Some("10.0.0.1".to_string()),
+            be_selected_compute_group: Some("etl".to_string()),
+            doris_version: Some("doris-2.1.4-rc01".to_string()),
+            bundles: BTreeMap::from([(
+                "standard FE triage".to_string(),
+                vec!["jstack".to_string(), "fe-metrics".to_string()],
+            )]),
+            ..Default::default()
+        };
+        let content = toml::to_string_pretty(&to_organized_config(&config)).unwrap();
+        let loaded = migrate_round_trip(&content, CURRENT_SCHEMA_VERSION);
+        assert!(loaded.production);
+        assert_eq!(loaded.read_only, Some(false));
+        assert!(loaded.https && !loaded.http_insecure);
+        assert_eq!(loaded.http_auth_user.as_deref(), Some("ops"));
+        assert_eq!(loaded.table_info_max_concurrency, Some(8));
+        assert_eq!(loaded.doris_version.as_deref(), Some("doris-2.1.4-rc01"));
+        assert_eq!(loaded.profile_name.as_deref(), Some("prod-bj"));
+        assert_eq!(loaded.query_port, Some(9030));
+        assert_eq!(loaded.bundles, config.bundles);
+        assert_eq!(loaded.environment, Environment::FE);
+        assert_eq!(loaded.be_selected_host.as_deref(), Some("10.0.0.1"));
+        assert_eq!(loaded.be_selected_compute_group.as_deref(), Some("etl"));
+        assert!(
+            !toml::to_string(&to_export_config(&loaded))
+                .unwrap()
+                .contains("10.0.0.1")
+        );
+
+        let newer = content.replace(
+            &format!("schema_version = {CURRENT_SCHEMA_VERSION}"),
+            "schema_version = 99",
+        );
+        assert!(migrate_config(&newer).is_err());
+        assert!(migrate_config("[metadata]\nenvironment = 1").is_err());
+    }
+
+    #[test]
+    fn backups_rotate_and_describe_their_changes() {
+        let dir = std::env::temp_dir().join(format!("cloud-cli-bak-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+
+        rotate_backups(&config_path, "unused").unwrap();
+        assert!(backups_of(&config_path).is_empty());
+
+        for version in 1..=5 {
+            let content = V2_ORGANIZED.replace("9030", &format!("{}", 9030 + version));
+            rotate_backups(&config_path, &content).unwrap();
+            std::fs::write(&config_path, &content).unwrap();
+        }
+        // Saving the same content again keeps the older copies
+        let unchanged = std::fs::read_to_string(&config_path).unwrap();
+        rotate_backups(&config_path, &unchanged).unwrap();
+
+        let backups = backups_of(&config_path);
+        assert_eq!(backups.len(), BACKUP_COUNT);
+        let ports: Vec<Option<u16>> = backups
+            .iter()
+            .map(|b| b.config.as_ref().unwrap().query_port)
+            .collect();
+        assert_eq!(ports, [Some(9034), Some(9033), Some(9032)]);
+
+        std::fs::write(rotated_backup_path(&config_path, 3), "not = [toml").unwrap();
+        assert!(backups_of(&config_path)[2].config.is_err());
+
+        let current = from_organized_config(&migrate_config(&unchanged).unwrap().0);
+        let mut older = backups[0].config.clone().unwrap();
+        older.environment = Environment::BE;
+        older.mysql = None;
+        assert_eq!(
+            backup_differences(&current, &older),
+            [
+                "environment FE + BE -> BE",
+                "query_port 9035 -> 9034",
+                "MySQL credentials removed"
+            ]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn import_merges_without_touching_credentials_or_process() {
+        let (live, _) = migrate_config(V2_ORGANIZED).unwrap();
+        let mut live = from_organized_config(&live);
+        live.process_pid = Some(42);
+        live.credential_keyring = true;
+
+        let source = DorisConfig {
+            environment: Environment::FE,
+            timeout_seconds: 120,
+            http_port: Some(18030),
+            query_port: Some(9030),
+            process_pid: Some(7),
+            http_auth_user: Some("ops".to_string()),
+            http_auth_password: Some("other-host-http-ciphertext".to_string()),
+            mysql: Some(MySQLConfig {
+                user: "ops".to_string(),
+                password: "other-host-ciphertext".to_string(),
+                host: None,
+                port: None,
+            }),
+            ..Default::default()
+        };
+        let exported = toml::to_string_pretty(&to_export_config(&source)).unwrap();
+        assert!(!exported.contains("ciphertext"));
+        assert!(!exported.contains("pid"));
+        let (imported, _) = migrate_config(&exported).unwrap();
+
+        let mut asked = Vec::new();
+        let (merged, notes) =
+            merge_import(&to_organized_config(&live), &imported, &mut |key, _, _| {
+                asked.push(key.to_string());
+                Ok(key != "mysql.user")
+            })
+            .unwrap();
+        assert!(notes.is_empty());
+        assert!(asked.contains(&"fe.ports.http_port".to_string()));
+        assert!(asked.iter().all(|k| !is_import_protected(k)));
+
+        let merged = from_organized_config(&merged);
+        assert_eq!(merged.http_port, Some(18030));
+        assert_eq!(merged.timeout_seconds, 120);
+        assert_eq!(merged.webserver_port, Some(8040));
+        assert!(merged.credential_keyring);
+        assert_eq!(merged.http_auth_user, None);
+        let mysql = merged.mysql.unwrap();
+        assert_eq!(
+            (mysql.user.as_str(), mysql.password.as_str()),
+            ("admin", "encrypted")
+        );
+
+        // Nothing to keep: a user without ciphertext is dropped rather than stored broken
+        let (merged, notes) = merge_import(
+            &to_organized_config(&DorisConfig::default()),
+            &imported,
+            &mut |_, _, _| Ok(true),
+        )
+        .unwrap();
+        assert!(merged.mysql.is_none());
+        assert_eq!(notes.len(), 1);
+
+        let mut flat = flatten_config(&imported).unwrap();
+        assert!(validate_import(&flat).is_ok());
+        flat.insert("fe.ports.query_port".into(), toml::Value::Integer(0));
+        assert!(validate_import(&flat).is_err());
     }
 }
@@ -1,10 +1,14 @@
 use crate::error::Result;
+use crate::tools::common::host_selection;
+use crate::tools::common::redact::redact_command;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 pub mod config_parser;
 pub mod config_persister;
 pub mod process_detector;
+pub mod provenance;
 pub mod regex_utils;
 
 #[derive(Debug, Clone, PartialEq, Copy)]
@@ -29,7 +33,14 @@ impl std::fmt::Display for Environment {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MySQLConfig {
     pub user: String,
+    /// Ciphertext; left out of exported configs
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub password: String,
+    /// FE host to connect to when no FE process runs locally
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
 }
 
 /// Doris configuration model with all system settings
@@ -43,6 +54,29 @@ pub struct DorisConfig {
     pub output_dir: PathBuf,
     pub timeout_seconds: u64,
     pub no_progress_animation: bool,
+    pub no_hints: bool,
+    pub log_scan_threads: Option<usize>,
+    pub profile_name: Option<String>,
+    pub production: bool,
+    pub stdout_only: bool,
+    pub credential_keyring: bool,
+    pub audit_stmt_max_len: Option<usize>,
+    pub max_output_age_days: Option<u64>,
+    pub max_output_total_bytes: Option<u64>,
+    pub offline_log_dir: Option<PathBuf>,
+    pub alter_job_warn_hours: Option<u64>,
+    pub table_info_max_concurrency: Option<usize>,
+    pub plain_output: bool,
+    /// `None` unless set explicitly; production mode then implies read-only
+    pub read_only: Option<bool>,
+    pub flat_output: bool,
+    /// Use https:// for FE and BE HTTP endpoints
+    pub https: bool,
+    /// Accept self-signed certificates on those endpoints
+    pub http_insecure: bool,
+    /// Basic auth for the BE webserver and FE HTTP; the password is ciphertext
+    pub http_auth_user: Option<String>,
+    pub http_auth_password: Option<String>,
 
     // Process information
     pub process_pid: Option<u32>,
@@ -77,6 +111,16 @@ pub struct DorisConfig {
     pub priority_networks: Option<String>,
     pub meta_service_endpoint: Option<String>,
     pub mysql: Option<MySQLConfig>,
+
+    /// Version reported by the cluster (e.g. `doris-2.1.4-rc01`), see `core::version`
+    pub doris_version: Option<String>,
+
+    /// Named lists of tool names run back-to-back as a diagnostic bundle
+    pub bundles: BTreeMap<String, Vec<String>>,
+
+    /// Default BE target picked via `be-list`, and its compute group in cloud mode
+    pub be_selected_host: Option<String>,
+    pub be_selected_compute_group: Option<String>,
 }
 
 impl Default for DorisConfig {
@@ -90,6 +134,25 @@ impl Default for DorisConfig {
             output_dir: PathBuf::from("/tmp/doris/collection"),
             timeout_seconds: 60,
             no_progress_animation: false,
+            no_hints: false,
+            log_scan_threads: None,
+            profile_name: None,
+            production: false,
+            stdout_only: false,
+            credential_keyring: false,
+            audit_stmt_max_len: None,
+            max_output_age_days: None,
+            max_output_total_bytes: None,
+            offline_log_dir: None,
+            alter_job_warn_hours: None,
+            table_info_max_concurrency: None,
+            plain_output: false,
+            read_only: None,
+            flat_output: false,
+            https: false,
+            http_insecure: false,
+            http_auth_user: None,
+            http_auth_password: None,
             process_pid: None,
             process_command: None,
             last_detected: None,
@@ -112,17 +175,47 @@ impl Default for DorisConfig {
             fe_process_command: None,
             fe_install_dir: None,
             mysql: None,
+            doris_version: None,
+            bundles: BTreeMap::new(),
+            be_selected_host: None,
+            be_selected_compute_group: None,
         }
     }
 }
 
+/// Install, conf and log directories of one service
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServicePaths {
+    pub install_dir: PathBuf,
+    pub conf_dir: PathBuf,
+    pub log_dir: PathBuf,
+}
+
 impl DorisConfig {
-    /// Get BE HTTP ports from configuration or return default ports[8040, 8041]
-    pub fn get_be_http_ports(&self) -> Vec<u16> {
-        if let Some(port) = self.webserver_port {
-            vec![port]
-        } else {
-            vec![8040, 8041]
+    /// Read-only mode as set explicitly, otherwise on in production mode
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.unwrap_or(self.production)
+    }
+
+    /// Directories of the `env` install. On a mixed host the top-level dirs follow
+    /// whichever process was detected last, so the FE/BE install dirs win there.
+    pub fn service_paths(&self, env: Environment) -> ServicePaths {
+        let dedicated = match env {
+            Environment::FE => self.fe_install_dir.as_ref(),
+            Environment::BE => self.be_install_dir.as_ref(),
+            _ => None,
+        };
+        match dedicated {
+            Some(dir) if *dir != self.install_dir => ServicePaths {
+                install_dir: dir.clone(),
+                conf_dir: dir.join("conf"),
+                log_dir: dir.join("log"),
+            },
+            _ => ServicePaths {
+                install_dir: self.install_dir.clone(),
+                conf_dir: self.conf_dir.clone(),
+                log_dir: self.log_dir.clone(),
+            },
         }
     }
 
@@ -132,22 +225,33 @@ impl DorisConfig {
         self.output_dir = config.output_dir.clone();
         self.timeout_seconds = config.timeout_seconds;
         self.no_progress_animation = config.no_progress_animation;
+        self.no_hints = config.no_hints;
+        self.log_scan_threads = config.log_scan_threads;
+        self.profile_name = config.profile_name.clone();
+        self.production = config.production;
+        self.stdout_only = config.stdout_only;
+        self.credential_keyring = config.credential_keyring;
+        self.audit_stmt_max_len = config.audit_stmt_max_len;
+        self.max_output_age_days = config.max_output_age_days;
+        self.max_output_total_bytes = config.max_output_total_bytes;
+        self.offline_log_dir = config.offline_log_dir.clone();
+        self.alter_job_warn_hours = config.alter_job_warn_hours;
+        self.table_info_max_concurrency = config.table_info_max_concurrency;
+        self.plain_output = config.plain_output;
+        self.read_only = config.read_only;
+        self.flat_output = config.flat_output;
         self
     }
 
-    /// Check if the current process PID is still valid
+    /// Check if the current process PID is still valid and, when the command was
+    /// recorded, still runs it; after a reboot the PID may belong to something else
     pub fn is_process_valid(&self) -> bool {
-        match self.process_pid {
-            Some(pid) => {
-                // Check if process is still running
-                std::process::Command::new("kill")
-                    .args(["-0", &pid.to_string()])
-                    .output()
-                    .map(|output| output.status.success())
-                    .unwrap_or(false)
+        self.process_pid.is_some_and(|pid| {
+            match pid_status_in(Path::new(PROC_ROOT), pid, self.process_command.as_deref()) {
+                Some(valid) => valid,
+                None => kill_probe(pid),
             }
-            None => false,
-        }
+        })
     }
 
     /// Get current process PID if available and valid
@@ -156,6 +260,51 @@ impl DorisConfig {
     }
 }
 
+const PROC_ROOT: &str = "/proc";
+
+/// Whether a process with `pid` is still running
+pub fn is_pid_running(pid: u32) -> bool {
+    pid_status_in(Path::new(PROC_ROOT), pid, None).unwrap_or_else(|| kill_probe(pid))
+}
+
+/// Liveness from a procfs mounted at `proc_root`, None when it is not mounted.
+///
+/// With `expected_command`, the cmdline must match it too (ps may have truncated
+/// the recorded command, so a prefix is accepted).
+fn pid_status_in(proc_root: &Path, pid: u32, expected_command: Option<&str>) -> Option<bool> {
+    if !proc_root.join("self").exists() {
+        return None;
+    }
+    let dir = proc_root.join(pid.to_string());
+    if !dir.is_dir() {
+        return Some(false);
+    }
+    let Some(expected) = expected_command.map(str::trim).filter(|c| !c.is_empty()) else {
+        return Some(true);
+    };
+    // The persisted command is redacted, so compare redacted forms
+    let normalize = |s: &str| redact_command(&s.split_whitespace().collect::<Vec<_>>().join(" "));
+    let cmdline = std::fs::read(dir.join("cmdline"))
+        .map(|raw| normalize(&String::from_utf8_lossy(&raw).replace('\0', " ")))
+        .unwrap_or_default();
+    // Unreadable or kernel-thread cmdline: the PID is alive but not verifiably ours
+    Some(!cmdline.is_empty() && cmdline.starts_with(&normalize(expected)))
+}
+
+/// `kill(pid, 0)`: EPERM still means the process exists
+fn kill_probe(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
 fn clean_process_info(config: &mut DorisConfig) {
     config.process_pid = None;
     config.process_command = None;
@@ -182,13 +331,29 @@ fn update_mixed_environment(config: &mut DorisConfig) -> Result<()> {
     Ok(())
 }
 
-/// Persist configuration to file
+/// Persist configuration to file.
+///
+/// The BE host selected in this session wins over the one in `config`, which
+/// may be a copy loaded before the selection changed.
 pub fn persist_configuration(config: &DorisConfig) {
-    if let Err(e) = config_persister::persist_config(config) {
+    if config_persister::is_persist_suspended() {
+        return;
+    }
+    if let Err(e) = config_persister::persist_config(&with_session_selection(config)) {
         eprintln!("Warning: Failed to persist configuration: {e}");
     }
 }
 
+/// `config` with the BE host selected in this session
+pub fn with_session_selection(config: &DorisConfig) -> DorisConfig {
+    let mut config = config.clone();
+    if let Some(host) = host_selection::get_selected_host(true) {
+        config.be_selected_host = Some(host);
+        config.be_selected_compute_group = host_selection::get_selected_compute_group();
+    }
+    config
+}
+
 /// Apply environment-specific port configurations
 fn apply_environment_specific_ports(
     config: &mut DorisConfig,
@@ -231,14 +396,62 @@ fn apply_fe_ports(config: &mut DorisConfig, parsed_config: &DorisConfig) {
     config.meta_dir = parsed_config.meta_dir.clone();
 }
 
-/// Load configuration, first from persisted file, then detect environment and generate if needed
+/// Load configuration, first from persisted file, then detect environment and generate if needed.
+///
+/// Problems found while parsing fe.conf/be.conf are printed once per run.
 pub fn load_config() -> Result<DorisConfig> {
+    load_config_with_provenance().map(|(config, _)| config)
+}
+
+/// `load_config`, also recording which source each key field came from.
+///
+/// Environment overrides are applied later by `Config`, see `Config::load_from_env`.
+pub fn load_config_with_provenance() -> Result<(DorisConfig, provenance::Provenance)> {
+    let mut warnings = Vec::new();
+    let mut provenance = provenance::Provenance::default();
+    let result = load_config_collecting(&mut warnings, &mut provenance);
+    config_parser::report_warnings(&warnings);
+    result.map(|config| (config, provenance))
+}
+
+/// Fallback configs are rebuilt from the conf files: anything not detected is a
+/// default, except the carried-over MySQL settings
+fn record_fallback(
+    provenance: &mut provenance::Provenance,
+    config: &DorisConfig,
+    persisted: &std::collections::BTreeSet<String>,
+) {
+    if config.environment == Environment::Unknown {
+        provenance.mark_persisted(persisted);
+        return;
+    }
+    *provenance = provenance::Provenance::default();
+    provenance.mark_detected();
+    if config.mysql.is_some() {
+        let mysql_keys = persisted
+            .iter()
+            .filter(|k| k.starts_with("mysql."))
+            .cloned()
+            .collect();
+        provenance.mark_persisted(&mysql_keys);
+    }
+}
+
+fn load_config_collecting(
+    warnings: &mut Vec<config_parser::ConfigWarning>,
+    provenance: &mut provenance::Provenance,
+) -> Result<DorisConfig> {
     let config_result = config_persister::load_persisted_config();
+    let persisted = config_persister::persisted_keys();
 
     let mut config = match config_result {
-        Ok(config) => config,
+        Ok(config) => {
+            provenance.mark_persisted(&persisted);
+            config
+        }
         Err(_) => {
-            let fallback_config = fallback_load_config()?;
+            let fallback_config = fallback_load_config(warnings)?;
+            record_fallback(provenance, &fallback_config, &persisted);
             persist_configuration(&fallback_config);
             return Ok(fallback_config);
         }
@@ -247,7 +460,8 @@ pub fn load_config() -> Result<DorisConfig> {
     match process_detector::detect_current_process() {
         Ok(current_process) => {
             if needs_config_update(&config, &current_process) {
-                config = update_config_from_process(config, current_process)?;
+                config = update_config_from_process(config, current_process, warnings)?;
+                provenance.mark_detected();
                 let _ = update_mixed_environment(&mut config);
                 persist_configuration(&config);
             } else {
@@ -265,13 +479,17 @@ pub fn load_config() -> Result<DorisConfig> {
                 && config.fe_process_pid.is_none()
                 && config.be_process_pid.is_none()
             {
-                let fallback_config = fallback_load_config()?;
+                let fallback_config = fallback_load_config(warnings)?;
+                record_fallback(provenance, &fallback_config, &persisted);
+                let mut new_config = fallback_config;
                 if config.mysql.is_some() {
-                    let mut new_config = fallback_config;
                     new_config.mysql = config.mysql;
-                    return Ok(new_config);
+                    new_config.doris_version = config.doris_version;
                 }
-                return Ok(fallback_config);
+                new_config.bundles = config.bundles;
+                new_config.be_selected_host = config.be_selected_host;
+                new_config.be_selected_compute_group = config.be_selected_compute_group;
+                return Ok(new_config);
             }
         }
     }
@@ -280,20 +498,32 @@ pub fn load_config() -> Result<DorisConfig> {
 }
 
 /// Parse configuration based on environment type with fallback to default
-fn parse_env_specific_config(env: Environment) -> DorisConfig {
+fn parse_env_specific_config(
+    env: Environment,
+    warnings: &mut Vec<config_parser::ConfigWarning>,
+) -> DorisConfig {
     let result = match env {
         Environment::BE => config_parser::parse_be_config(),
         Environment::FE => config_parser::parse_fe_config(),
         Environment::Mixed => config_parser::parse_be_config(),
         Environment::Unknown => return DorisConfig::default(),
     };
-    result.unwrap_or_else(|_| DorisConfig::default())
+    match result {
+        Ok(parsed) => {
+            warnings.extend(parsed.warnings);
+            parsed.config
+        }
+        Err(_) => DorisConfig::default(),
+    }
 }
 
 /// Fallback to original configuration loading behavior
-fn fallback_load_config() -> Result<DorisConfig> {
+fn fallback_load_config(warnings: &mut Vec<config_parser::ConfigWarning>) -> Result<DorisConfig> {
     let existing_config = config_persister::load_persisted_config().ok();
     let existing_mysql = existing_config.as_ref().and_then(|c| c.mysql.clone());
+    let existing_version = existing_config
+        .as_ref()
+        .and_then(|c| c.doris_version.clone());
 
     let env = match process_detector::detect_environment() {
         Ok(env) => env,
@@ -305,12 +535,13 @@ fn fallback_load_config() -> Result<DorisConfig> {
         }
     };
 
-    let mut config = parse_env_specific_config(env);
+    let mut config = parse_env_specific_config(env, warnings);
     config.environment = env;
 
     if let Some(mysql_config) = existing_mysql {
         config.mysql = Some(mysql_config);
     }
+    config.doris_version = existing_version;
 
     if env != Environment::Unknown {
         let _ = update_mixed_environment(&mut config);
@@ -327,14 +558,25 @@ pub fn to_app_config(doris_config: DorisConfig) -> crate::config::Config {
         output_dir: doris_config.output_dir,
         timeout_seconds: doris_config.timeout_seconds,
         no_progress_animation: doris_config.no_progress_animation,
+        no_hints: doris_config.no_hints,
+        log_scan_threads: doris_config.log_scan_threads,
+        profile_name: doris_config.profile_name,
+        production: doris_config.production,
+        stdout_only: doris_config.stdout_only,
+        credential_keyring: doris_config.credential_keyring,
+        audit_stmt_max_len: doris_config.audit_stmt_max_len,
+        max_output_age_days: doris_config.max_output_age_days,
+        max_output_total_bytes: doris_config.max_output_total_bytes,
+        offline_log_dir: doris_config.offline_log_dir,
+        alter_job_warn_hours: doris_config.alter_job_warn_hours,
+        table_info_max_concurrency: doris_config.table_info_max_concurrency,
+        plain_output: doris_config.plain_output,
+        read_only: doris_config.read_only,
+        flat_output: doris_config.flat_output,
+        session_tag: crate::config::session_tag(),
     }
 }
 
-/// Get current process PID from configuration (convenience function)
-pub fn get_current_pid() -> Option<u32> {
-    load_config().ok()?.get_valid_pid()
-}
-
 /// Check if configuration needs to be updated based on detected process
 fn needs_config_update(
     config: &DorisConfig,
@@ -352,6 +594,7 @@ fn needs_config_update(
 fn update_config_from_process(
     mut config: DorisConfig,
     process: process_detector::ProcessDetectionResult,
+    warnings: &mut Vec<config_parser::ConfigWarning>,
 ) -> Result<DorisConfig> {
     let mysql_config = config.mysql.clone();
 
@@ -366,13 +609,55 @@ fn update_config_from_process(
     config.conf_dir = process.doris_home.join("conf");
     config.log_dir = process.doris_home.join("log");
 
-    if let Ok(parsed_config) =
+    if let Ok(parsed) =
         config_parser::parse_config_from_path(process.environment, &process.doris_home)
     {
-        apply_environment_specific_ports(&mut config, &parsed_config, process.environment);
+        warnings.extend(parsed.warnings);
+        apply_environment_specific_ports(&mut config, &parsed.config, process.environment);
     }
 
     config.mysql = mysql_config;
 
     Ok(config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proc_check_guards_against_pid_reuse() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let fake = |pid: &str, cmdline: &[u8]| {
+            let dir = root.join(pid);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("cmdline"), cmdline).unwrap();
+        };
+        fake("self", b"cloud-cli\0");
+        fake(
+            "100",
+            b"/opt/jdk/bin/java\0-Xmx8g\0org.apache.doris.DorisFE\0",
+        );
+        fake("200", b"/usr/sbin/sshd\0-D\0");
+        fake("300", b"");
+        let cmd = "/opt/jdk/bin/java -Xmx8g org.apache.doris.DorisFE";
+
+        assert_eq!(pid_status_in(root, 100, Some(cmd)), Some(true));
+        assert_eq!(
+            pid_status_in(root, 100, Some("/opt/jdk/bin/java -Xmx8g")),
+            Some(true)
+        );
+        // Reused after a reboot by an unrelated process
+        assert_eq!(pid_status_in(root, 200, Some(cmd)), Some(false));
+        assert_eq!(pid_status_in(root, 200, None), Some(true));
+        assert_eq!(pid_status_in(root, 300, Some(cmd)), Some(false));
+        assert_eq!(pid_status_in(root, 400, None), Some(false));
+
+        std::fs::remove_dir_all(root.join("self")).unwrap();
+        assert_eq!(pid_status_in(root, 100, Some(cmd)), None);
+
+        assert!(kill_probe(std::process::id()));
+        assert!(!kill_probe(0));
+    }
+}
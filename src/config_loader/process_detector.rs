@@ -1,6 +1,10 @@
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 
 use crate::config_loader::Environment;
 use crate::config_loader::regex_utils;
@@ -74,7 +78,7 @@ pub fn execute_command(cmd: &str) -> Result<String> {
 fn detect_process_detailed(env: Environment) -> Result<ProcessDetectionResult> {
     let pid = get_pid_by_env(env)?;
     let command = get_process_command(pid)?;
-    let (doris_home, java_home) = get_paths_by_pid(pid);
+    let (doris_home, java_home) = get_paths_by_pid(pid, env);
 
     Ok(ProcessDetectionResult {
         pid,
@@ -118,6 +122,54 @@ pub fn get_process_command(pid: u32) -> Result<String> {
     Ok(format!("unknown_process_{pid}"))
 }
 
+/// When the process started, from `/proc` on Linux or `ps -o lstart` elsewhere
+pub fn get_process_start_time(pid: u32) -> Option<SystemTime> {
+    proc_start_time(pid).or_else(|| ps_start_time(pid))
+}
+
+fn proc_start_time(pid: u32) -> Option<SystemTime> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let boot = std::fs::read_to_string("/proc/stat").ok()?;
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks_per_sec <= 0 {
+        return None;
+    }
+    let secs =
+        parse_boot_time(&boot)? as f64 + parse_start_ticks(&stat)? as f64 / ticks_per_sec as f64;
+    Some(SystemTime::UNIX_EPOCH + Duration::from_secs_f64(secs))
+}
+
+/// Field 22 of `/proc/<pid>/stat`: clock ticks after boot. The command name in
+/// field 2 may contain spaces and parentheses, so count from its closing paren.
+fn parse_start_ticks(stat: &str) -> Option<u64> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// `btime` line of `/proc/stat`, seconds since the epoch
+fn parse_boot_time(proc_stat: &str) -> Option<u64> {
+    proc_stat
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn ps_start_time(pid: u32) -> Option<SystemTime> {
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "lstart="])
+        .env("LC_ALL", "C")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let local = chrono::NaiveDateTime::parse_from_str(&text, "%a %b %d %H:%M:%S %Y").ok()?;
+    let local = local.and_local_timezone(chrono::Local).earliest()?;
+    Some(local.into())
+}
+
 fn extract_pid_from_output(output: &str, regex_pattern: &str, first_only: bool) -> Result<u32> {
     regex_utils::extract_pid_from_output(output, regex_pattern, first_only)
         .ok_or_else(|| CliError::ProcessNotFound("Invalid process info format".to_string()))
@@ -178,10 +230,10 @@ pub fn get_paths(env: Environment) -> Result<(PathBuf, PathBuf)> {
     let pid = get_pid_by_env(env)?;
 
     // Use the simplified function to get paths
-    let (install_path, jdk_path) = get_paths_by_pid(pid);
+    let (install_path, jdk_path) = get_paths_by_pid(pid, env);
 
     // Verify that we have a valid DORIS_HOME path
-    if install_path == PathBuf::from("/opt/selectdb") {
+    if install_path == Path::new(DEFAULT_DORIS_HOME) {
         return Err(CliError::ConfigError(format!(
             "DORIS_HOME not found in {env} process environment"
         )));
@@ -190,24 +242,161 @@ pub fn get_paths(env: Environment) -> Result<(PathBuf, PathBuf)> {
     Ok((install_path, jdk_path))
 }
 
+/// Used when DORIS_HOME can be neither read nor inferred
+const DEFAULT_DORIS_HOME: &str = "/opt/selectdb";
+const DEFAULT_JAVA_HOME: &str = "/opt/jdk";
+/// Control-group path fragments of Docker, Kubernetes, containerd, Podman, CRI-O and LXC
+const CONTAINER_CGROUP_MARKERS: &[&str] =
+    &["docker", "kubepods", "containerd", "libpod", "crio", "lxc"];
+
+/// Where the DORIS_HOME of a detected process came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomeSource {
+    Environ,
+    /// The binary path, `<home>/lib/doris_be` for BE
+    Exe,
+    /// The working directory, or its parent when that is `bin` or `log`
+    Cwd,
+    Default,
+}
+
 /// Get paths by PID for the specified environment
-fn get_paths_by_pid(pid: u32) -> (PathBuf, PathBuf) {
+fn get_paths_by_pid(pid: u32, env: Environment) -> (PathBuf, PathBuf) {
     let grep_pattern = "DORIS_HOME|JAVA_HOME";
-    if let Some((doris_home, java_home)) = read_proc_environ_by_pid(pid, grep_pattern)
-        .ok()
-        .and_then(|envs| {
-            regex_utils::extract_env_var(&envs, "DORIS_HOME").map(|home| {
-                let java_home = regex_utils::extract_env_var(&envs, "JAVA_HOME")
-                    .unwrap_or_else(|| "/opt/jdk".to_string());
-                (home, java_home)
-            })
+    let envs = read_proc_environ_by_pid(pid, grep_pattern).unwrap_or_default();
+    let environ_home = regex_utils::extract_env_var(&envs, "DORIS_HOME");
+    let environ_java = regex_utils::extract_env_var(&envs, "JAVA_HOME");
+
+    let proc_root = Path::new("/proc");
+    let (doris_home, source) = match environ_home {
+        Some(home) => (PathBuf::from(home), HomeSource::Environ),
+        None => infer_doris_home(proc_root, pid, env)
+            .unwrap_or_else(|| (PathBuf::from(DEFAULT_DORIS_HOME), HomeSource::Default)),
+    };
+    let java_home = environ_java
+        .map(PathBuf::from)
+        .or_else(|| infer_java_home(proc_root, pid))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_JAVA_HOME));
+
+    if source != HomeSource::Environ {
+        report_inferred_home(pid, env, source, &doris_home);
+    }
+    (doris_home, java_home)
+}
+
+/// DORIS_HOME from `/proc/<pid>/exe` and `/proc/<pid>/cwd`, for when the
+/// environment cannot be read (other users' processes, containers).
+///
+/// A candidate only counts when it holds `conf/be.conf` or `conf/fe.conf`.
+pub fn infer_doris_home(
+    proc_root: &Path,
+    pid: u32,
+    env: Environment,
+) -> Option<(PathBuf, HomeSource)> {
+    let proc_dir = proc_root.join(pid.to_string());
+    let conf = match env {
+        Environment::BE => "be.conf",
+        _ => "fe.conf",
+    };
+    let is_home = |dir: &Path| dir.join("conf").join(conf).is_file();
+
+    // FE runs on the JDK's java binary, so only the BE binary points into the install dir
+    let from_exe = (env == Environment::BE)
+        .then(|| read_proc_link(&proc_dir.join("exe")))
+        .flatten()
+        .and_then(|exe| {
+            let lib = exe.parent()?;
+            (lib.file_name()? == "lib").then(|| lib.parent().map(Path::to_path_buf))?
         })
-    {
-        return (PathBuf::from(doris_home), PathBuf::from(java_home));
+        .filter(|home| is_home(home))
+        .map(|home| (home, HomeSource::Exe));
+
+    from_exe.or_else(|| {
+        let cwd = read_proc_link(&proc_dir.join("cwd"))?;
+        let parent = cwd
+            .parent()
+            .filter(|_| cwd.file_name().is_some_and(|n| n == "bin" || n == "log"));
+        [Some(cwd.as_path()), parent]
+            .into_iter()
+            .flatten()
+            .find(|dir| is_home(dir))
+            .map(|home| (home.to_path_buf(), HomeSource::Cwd))
+    })
+}
+
+/// JAVA_HOME from a `<java_home>/bin/java` binary, which is what FE runs on
+pub fn infer_java_home(proc_root: &Path, pid: u32) -> Option<PathBuf> {
+    let exe = read_proc_link(&proc_root.join(pid.to_string()).join("exe"))?;
+    if exe.file_name()? != "java" {
+        return None;
     }
+    let bin = exe.parent()?;
+    (bin.file_name()? == "bin").then(|| bin.parent().map(Path::to_path_buf))?
+}
+
+/// Target of a `/proc` symlink, without the ` (deleted)` suffix of replaced binaries
+fn read_proc_link(link: &Path) -> Option<PathBuf> {
+    let target = std::fs::read_link(link).ok()?;
+    let text = target.to_string_lossy();
+    Some(match text.strip_suffix(" (deleted)") {
+        Some(stripped) => PathBuf::from(stripped),
+        None => target,
+    })
+}
+
+/// Whether a `/proc/<pid>/cgroup` file places the process inside a container
+pub fn cgroup_indicates_container(cgroup: &str) -> bool {
+    cgroup.lines().any(|line| {
+        let path = line.rsplit(':').next().unwrap_or_default();
+        CONTAINER_CGROUP_MARKERS.iter().any(|m| path.contains(m))
+    })
+}
+
+/// Container markers below `fs_root`: `/.dockerenv`, `/run/.containerenv`, or
+/// the cgroup of PID 1 under `proc_root`
+pub fn detect_container(fs_root: &Path, proc_root: &Path) -> bool {
+    fs_root.join(".dockerenv").exists()
+        || fs_root.join("run/.containerenv").exists()
+        || std::fs::read_to_string(proc_root.join("1/cgroup"))
+            .is_ok_and(|c| cgroup_indicates_container(&c))
+}
 
-    // Default paths if environment variables are not available
-    (PathBuf::from("/opt/selectdb"), PathBuf::from("/opt/jdk"))
+/// Whether cloud-cli itself runs in a Docker/Kubernetes style container
+pub fn running_in_container() -> bool {
+    static IN_CONTAINER: Lazy<bool> = Lazy::new(|| {
+        std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+            || detect_container(Path::new("/"), Path::new("/proc"))
+    });
+    *IN_CONTAINER
+}
+
+/// Say once per process where DORIS_HOME came from when it was not the environment
+fn report_inferred_home(pid: u32, env: Environment, source: HomeSource, home: &Path) {
+    static REPORTED: Lazy<Mutex<HashSet<u32>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+    if !REPORTED.lock().is_ok_and(|mut r| r.insert(pid)) {
+        return;
+    }
+    let context = if running_in_container() {
+        "running in container"
+    } else {
+        "environment of the process not readable"
+    };
+    let message = match source {
+        HomeSource::Exe => format!(
+            "{context}; DORIS_HOME of {env} inferred from /proc/{pid}/exe: {}",
+            home.display()
+        ),
+        HomeSource::Cwd => format!(
+            "{context}; DORIS_HOME of {env} inferred from /proc/{pid}/cwd: {}",
+            home.display()
+        ),
+        HomeSource::Default | HomeSource::Environ => format!(
+            "{context}; DORIS_HOME of {env} (pid {pid}) unknown, assuming {}. \
+             Run cloud-cli as the Doris user or inside the Doris container",
+            home.display()
+        ),
+    };
+    crate::ui::print_warning(&message);
 }
 
 /// Verify that a config file exists
@@ -245,11 +434,13 @@ pub fn detect_mixed_deployment(config: &mut crate::config_loader::DorisConfig) -
             config.fe_install_dir = Some(fe_process.doris_home.clone());
 
             if config.environment != crate::config_loader::Environment::FE
-                && let Ok(fe_config) = crate::config_loader::config_parser::parse_config_from_path(
+                && let Ok(parsed) = crate::config_loader::config_parser::parse_config_from_path(
                     crate::config_loader::Environment::FE,
                     &fe_process.doris_home,
                 )
             {
+                crate::config_loader::config_parser::report_warnings(&parsed.warnings);
+                let fe_config = parsed.config;
                 config.http_port = fe_config.http_port;
                 config.rpc_port = fe_config.rpc_port;
                 config.query_port = fe_config.query_port;
@@ -265,11 +456,13 @@ pub fn detect_mixed_deployment(config: &mut crate::config_loader::DorisConfig) -
             config.be_install_dir = Some(be_process.doris_home.clone());
 
             if config.environment != crate::config_loader::Environment::BE
-                && let Ok(be_config) = crate::config_loader::config_parser::parse_config_from_path(
+                && let Ok(parsed) = crate::config_loader::config_parser::parse_config_from_path(
                     crate::config_loader::Environment::BE,
                     &be_process.doris_home,
                 )
             {
+                crate::config_loader::config_parser::report_warnings(&parsed.warnings);
+                let be_config = parsed.config;
                 config.be_port = be_config.be_port;
                 config.brpc_port = be_config.brpc_port;
                 config.webserver_port = be_config.webserver_port;
@@ -280,3 +473,77 @@ pub fn detect_mixed_deployment(config: &mut crate::config_loader::DorisConfig) -
 
     Ok(is_mixed)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    /// `<root>/proc/<pid>/{exe,cwd}` symlinks next to a BE and an FE install
+    fn fake_proc(root: &Path, pid: u32, exe: &Path, cwd: &Path) -> PathBuf {
+        let proc_root = root.join("proc");
+        let dir = proc_root.join(pid.to_string());
+        fs::create_dir_all(&dir).unwrap();
+        symlink(exe, dir.join("exe")).unwrap();
+        symlink(cwd, dir.join("cwd")).unwrap();
+        proc_root
+    }
+
+    fn install(root: &Path, name: &str, conf: &str) -> PathBuf {
+        let home = root.join(name);
+        for sub in ["conf", "lib", "bin", "log"] {
+            fs::create_dir_all(home.join(sub)).unwrap();
+        }
+        fs::write(home.join("conf").join(conf), "").unwrap();
+        home
+    }
+
+    #[test]
+    fn infers_doris_home_from_exe_and_cwd() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let be = install(root, "be", "be.conf");
+        let fe = install(root, "fe", "fe.conf");
+        let jdk_java = root.join("jdk/bin/java");
+
+        let proc_root = fake_proc(root, 100, &be.join("lib/doris_be"), Path::new("/"));
+        assert_eq!(
+            infer_doris_home(&proc_root, 100, Environment::BE),
+            Some((be.clone(), HomeSource::Exe))
+        );
+
+        // FE's binary is java; its home comes from the working directory
+        let proc_root = fake_proc(root, 200, &jdk_java, &fe.join("bin"));
+        assert_eq!(
+            infer_doris_home(&proc_root, 200, Environment::FE),
+            Some((fe.clone(), HomeSource::Cwd))
+        );
+        assert_eq!(infer_java_home(&proc_root, 200), Some(root.join("jdk")));
+
+        // Neither link leads to an install dir
+        let proc_root = fake_proc(root, 300, &root.join("usr/bin/doris_be"), root);
+        assert_eq!(infer_doris_home(&proc_root, 300, Environment::BE), None);
+        assert_eq!(infer_doris_home(&proc_root, 999, Environment::BE), None);
+    }
+
+    #[test]
+    fn recognises_container_cgroups() {
+        assert!(cgroup_indicates_container(
+            "12:memory:/docker/3f2a9c\n11:cpu:/docker/3f2a9c\n"
+        ));
+        assert!(cgroup_indicates_container(
+            "0::/kubepods.slice/kubepods-burstable.slice/cri-containerd-ab12.scope\n"
+        ));
+        assert!(!cgroup_indicates_container("0::/init.scope\n"));
+        assert!(!cgroup_indicates_container("12:memory:/user.slice\n"));
+
+        let root = std::env::temp_dir().join(format!("cloud-cli-ctr-{}", std::process::id()));
+        fs::create_dir_all(root.join("proc/1")).unwrap();
+        fs::write(root.join("proc/1/cgroup"), "0::/\n").unwrap();
+        assert!(!detect_container(&root, &root.join("proc")));
+        fs::write(root.join(".dockerenv"), "").unwrap();
+        assert!(detect_container(&root, &root.join("proc")));
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+
+/// Where the effective value of a config field came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueSource {
+    /// The persisted config file
+    Persisted,
+    /// An environment variable, by name
+    Env(&'static str),
+    /// A command line flag such as `--stdout`
+    Flag(&'static str),
+    /// The running FE/BE process or its conf file
+    Detected,
+    /// Set from the settings menu for this session only
+    Session,
+    /// Unset, so it follows another field, by name
+    Implied(&'static str),
+    #[default]
+    Default,
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::Persisted => write!(f, "persisted file"),
+            ValueSource::Env(name) => write!(f, "env {name}"),
+            ValueSource::Flag(flag) => write!(f, "flag {flag}"),
+            ValueSource::Detected => write!(f, "process detection"),
+            ValueSource::Session => write!(f, "settings (this session)"),
+            ValueSource::Implied(field) => write!(f, "implied by {field}"),
+            ValueSource::Default => write!(f, "built-in default"),
+        }
+    }
+}
+
+/// Fields refreshed from the running process when it is detected
+const DETECTED_FIELDS: &[&str] = &[
+    "environment",
+    "install_dir",
+    "conf_dir",
+    "log_dir",
+    "jdk_path",
+    "http_port",
+    "query_port",
+    "webserver_port",
+    "process_pid",
+];
+
+/// Config file keys that hold each field; the first present one counts
+const FILE_KEYS: &[(&str, &[&str])] = &[
+    ("environment", &["metadata.environment"]),
+    ("install_dir", &["fe.install_dir", "be.install_dir"]),
+    ("conf_dir", &["fe.conf_dir", "be.conf_dir"]),
+    ("log_dir", &["fe.log_dir", "be.log_dir"]),
+    ("jdk_path", &["paths.jdk_path"]),
+    ("output_dir", &["paths.output_dir"]),
+    ("http_port", &["fe.ports.http_port"]),
+    ("query_port", &["fe.ports.query_port"]),
+    ("webserver_port", &["be.ports.webserver_port"]),
+    ("process_pid", &["process.pid"]),
+    ("mysql.user", &["mysql.user"]),
+    ("mysql.host", &["mysql.host"]),
+    ("mysql.port", &["mysql.port"]),
+];
+
+/// Source of each field of `Config` and the key `DorisConfig` fields
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Provenance {
+    sources: BTreeMap<&'static str, ValueSource>,
+}
+
+impl Provenance {
+    /// Unrecorded fields are built-in defaults
+    pub fn source(&self, field: &str) -> ValueSource {
+        self.sources.get(field).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, field: &'static str, source: ValueSource) {
+        self.sources.insert(field, source);
+    }
+
+    /// Fields present in the config file (dotted `keys`) become persisted
+    pub fn mark_persisted(&mut self, keys: &BTreeSet<String>) {
+        for (field, candidates) in FILE_KEYS {
+            if candidates.iter().any(|k| keys.contains(*k)) {
+                self.set(field, ValueSource::Persisted);
+            }
+        }
+        for key in keys {
+            if let Some(setting) = key.strip_prefix("settings.")
+                && let Some(field) = SETTINGS.iter().find(|f| **f == setting)
+            {
+                self.set(field, ValueSource::Persisted);
+            }
+        }
+    }
+
+    pub fn mark_detected(&mut self) {
+        for field in DETECTED_FIELDS {
+            self.set(field, ValueSource::Detected);
+        }
+    }
+}
+
+/// Fields stored under `[settings]`; all but the HTTP ones are mirrored in `Config`
+const SETTINGS: &[&str] = &[
+    "timeout_seconds",
+    "no_progress_animation",
+    "no_hints",
+    "log_scan_threads",
+    "profile_name",
+    "production",
+    "stdout_only",
+    "credential_keyring",
+    "audit_stmt_max_len",
+    "max_output_age_days",
+    "max_output_total_bytes",
+    "offline_log_dir",
+    "alter_job_warn_hours",
+    "table_info_max_concurrency",
+    "plain_output",
+    "read_only",
+    "flat_output",
+    "https",
+    "http_insecure",
+    "http_auth_user",
+];
+
+fn optional<T: fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+/// Field, value and source rows of the effective configuration
+pub fn effective_rows(
+    config: &Config,
+    doris: &DorisConfig,
+    provenance: &Provenance,
+) -> Vec<(&'static str, String, ValueSource)> {
+    let mysql = doris.mysql.as_ref();
+    let values: Vec<(&'static str, String)> = vec![
+        ("jdk_path", config.jdk_path.display().to_string()),
+        ("output_dir", config.output_dir.display().to_string()),
+        ("timeout_seconds", config.timeout_seconds.to_string()),
+        (
+            "no_progress_animation",
+            config.no_progress_animation.to_string(),
+        ),
+        ("no_hints", config.no_hints.to_string()),
+        ("log_scan_threads", optional(config.log_scan_threads)),
+        ("profile_name", optional(config.profile_name.as_deref())),
+        ("production", config.production.to_string()),
+        ("stdout_only", config.stdout_only.to_string()),
+        ("credential_keyring", config.credential_keyring.to_string()),
+        ("audit_stmt_max_len", optional(config.audit_stmt_max_len)),
+        ("max_output_age_days", optional(config.max_output_age_days)),
+        (
+            "max_output_total_bytes",
+            optional(config.max_output_total_bytes),
+        ),
+        (
+            "offline_log_dir",
+            optional(config.offline_log_dir.as_ref().map(|d| d.display())),
+        ),
+        (
+            "alter_job_warn_hours",
+            optional(config.alter_job_warn_hours),
+        ),
+        (
+            "table_info_max_concurrency",
+            optional(config.table_info_max_concurrency),
+        ),
+        ("plain_output", config.plain_output.to_string()),
+        ("read_only", config.is_read_only().to_string()),
+        ("flat_output", config.flat_output.to_string()),
+        ("session_tag", optional(config.session_tag.as_deref())),
+        ("environment", doris.environment.to_string()),
+        ("install_dir", doris.install_dir.display().to_string()),
+        ("conf_dir", doris.conf_dir.display().to_string()),
+        ("log_dir", doris.log_dir.display().to_string()),
+        ("http_port", optional(doris.http_port)),
+        ("query_port", optional(doris.query_port)),
+        ("webserver_port", optional(doris.webserver_port)),
+        ("https", doris.https.to_string()),
+        ("http_insecure", doris.http_insecure.to_string()),
+        ("http_auth_user", optional(doris.http_auth_user.as_deref())),
+        ("process_pid", optional(doris.process_pid)),
+        ("mysql.user", optional(mysql.map(|m| m.user.as_str()))),
+        (
+            "mysql.host",
+            optional(mysql.and_then(|m| m.host.as_deref())),
+        ),
+        ("mysql.port", optional(mysql.and_then(|m| m.port))),
+    ];
+    values
+        .into_iter()
+        .map(|(field, value)| {
+            let source = match field {
+                "read_only" if config.read_only.is_none() && config.production => {
+                    ValueSource::Implied("production")
+                }
+                _ => provenance.source(field),
+            };
+            (field, value, source)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_keys_mark_persisted_and_detection_overrides() {
+        let keys: BTreeSet<String> = [
+            "paths.output_dir",
+            "fe.install_dir",
+            "settings.timeout_seconds",
+            "settings.unknown_key",
+            "mysql.user",
+        ]
+        .iter()
+        .map(|k| k.to_string())
+        .collect();
+        let mut provenance = Provenance::default();
+        provenance.mark_persisted(&keys);
+        assert_eq!(provenance.source("output_dir"), ValueSource::Persisted);
+        assert_eq!(provenance.source("timeout_seconds"), ValueSource::Persisted);
+        assert_eq!(provenance.source("log_scan_threads"), ValueSource::Default);
+        assert_eq!(provenance.source("mysql.host"), ValueSource::Default);
+
+        provenance.mark_detected();
+        assert_eq!(provenance.source("install_dir"), ValueSource::Detected);
+        assert_eq!(provenance.source("output_dir"), ValueSource::Persisted);
+
+        provenance.set("output_dir", ValueSource::Env("OUTPUT_DIR"));
+        let rows = effective_rows(&Config::default(), &DorisConfig::default(), &provenance);
+        let (_, value, source) = rows.iter().find(|(f, _, _)| *f == "output_dir").unwrap();
+        assert_eq!(value, "/tmp/doris/collection");
+        assert_eq!(source.to_string(), "env OUTPUT_DIR");
+    }
+
+    #[test]
+    fn production_implies_read_only_unless_set() {
+        let mut config = Config {
+            production: true,
+            ..Config::default()
+        };
+        let mut provenance = Provenance::default();
+        provenance.set("production", ValueSource::Env("CLOUD_CLI_PRODUCTION"));
+        let read_only = |config: &Config, provenance: &Provenance| {
+            effective_rows(config, &DorisConfig::default(), provenance)
+                .into_iter()
+                .find(|(f, _, _)| *f == "read_only")
+                .map(|(_, value, source)| (value, source))
+                .unwrap()
+        };
+
+        assert!(config.is_read_only());
+        assert_eq!(
+            read_only(&config, &provenance),
+            ("true".to_string(), ValueSource::Implied("production"))
+        );
+
+        config.read_only = Some(false);
+        provenance.set("read_only", ValueSource::Env("CLOUD_CLI_READ_ONLY"));
+        assert!(!config.is_read_only());
+        assert_eq!(
+            read_only(&config, &provenance),
+            ("false".to_string(), ValueSource::Env("CLOUD_CLI_READ_ONLY"))
+        );
+    }
+}
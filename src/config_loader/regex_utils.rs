@@ -31,17 +31,39 @@ pub fn extract_pid_from_output(output: &str, regex_pattern: &str, first_only: bo
 
 pub fn extract_value_from_line(line: &str) -> Option<String> {
     static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*[^=\s]+\s*=\s*(.*?)\s*$").unwrap());
-    RE.captures(line).and_then(|caps| {
-        caps.get(1)
-            .map(|m| m.as_str().trim().trim_matches('"').to_string())
-    })
+    RE.captures(line)
+        .and_then(|caps| caps.get(1).map(|m| clean_value(m.as_str())))
+}
+
+/// Key of a `key = value` line
+pub fn extract_key_from_line(line: &str) -> Option<String> {
+    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*([^=\s]+)\s*=").unwrap());
+    RE.captures(line)
+        .and_then(|caps| caps.get(1).map(|m| m.as_str().to_string()))
+}
+
+/// Drop a trailing inline comment (` # ...` outside quotes) and surrounding quotes
+fn clean_value(raw: &str) -> String {
+    let mut in_quotes = false;
+    let mut prev_space = false;
+    let mut end = raw.len();
+    for (i, c) in raw.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '#' if !in_quotes && (i == 0 || prev_space) => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+        prev_space = c.is_whitespace();
+    }
+    raw[..end].trim().trim_matches('"').to_string()
 }
 
 pub fn extract_key_value(line: &str, key: &str) -> Option<String> {
     let pattern = format!(r"^\s*{}\s*=\s*(.*?)\s*$", regex::escape(key));
     let re = Regex::new(&pattern).ok()?;
-    re.captures(line).and_then(|caps| {
-        caps.get(1)
-            .map(|m| m.as_str().trim().trim_matches('"').to_string())
-    })
+    re.captures(line)
+        .and_then(|caps| caps.get(1).map(|m| clean_value(m.as_str())))
 }
@@ -1,25 +1,37 @@
 use crate::config::Config;
 use crate::config_loader;
+use crate::core::background_tasks::{self, SHUTDOWN_GRACE, TaskManager};
 use crate::tools::ToolRegistry;
+use crate::tools::common::retention::{self, RetentionPolicy};
 
 pub struct AppState {
     pub config: Config,
     pub doris_config: crate::config_loader::DorisConfig,
     pub registry: ToolRegistry,
-    pub background_handle: Option<std::thread::JoinHandle<()>>,
+    pub background_tasks: TaskManager,
 }
 
 impl AppState {
     pub fn new() -> crate::error::Result<Self> {
+        crate::config::mark_session_start();
         let doris_config = config_loader::load_config()?;
-        let config = config_loader::to_app_config(doris_config.clone());
+        let mut config = config_loader::to_app_config(doris_config.clone());
+        // Environment overrides such as CLOUD_CLI_READ_ONLY apply from the first menu on
+        config.load_from_env(&mut config_loader::provenance::Provenance::default());
         let registry = ToolRegistry::new();
+        crate::ui::configure_output(&config);
+        crate::executor::configure_command_log(&config);
+
+        let policy = RetentionPolicy::from_config(&config);
+        if let Err(e) = retention::enforce(&config, &policy) {
+            crate::ui::print_warning(&format!("Output cleanup skipped: {e}"));
+        }
 
         Ok(Self {
             config,
             doris_config,
             registry,
-            background_handle: None,
+            background_tasks: TaskManager::new(),
         })
     }
 
@@ -27,15 +39,22 @@ impl AppState {
         let fe_process_exists =
             config_loader::process_detector::get_pid_by_env(config_loader::Environment::FE).is_ok();
         let has_mysql = self.doris_config.mysql.is_some();
-        if fe_process_exists && has_mysql {
-            self.background_handle =
-                Some(crate::core::background_tasks::spawn_cluster_info_collector(
-                    self.doris_config.clone(),
-                ));
+        let has_remote_fe = self
+            .doris_config
+            .mysql
+            .as_ref()
+            .is_some_and(|m| m.host.is_some());
+        if (fe_process_exists || has_remote_fe) && has_mysql {
+            background_tasks::spawn_cluster_info_collector(
+                &mut self.background_tasks,
+                self.doris_config.clone(),
+            );
         }
     }
 
     pub fn update_config(&mut self, new_config: Config) {
+        crate::ui::configure_output(&new_config);
+        crate::executor::configure_command_log(&new_config);
         self.config = new_config.clone();
         self.doris_config = self.doris_config.clone().with_app_config(&new_config);
         config_loader::persist_configuration(&self.doris_config);
@@ -43,11 +62,18 @@ impl AppState {
 
     pub fn reset_runtime_config(&mut self) {
         self.config = Config::new();
+        crate::ui::configure_output(&self.config);
+        crate::executor::configure_command_log(&self.config);
     }
 
     pub fn cleanup(&mut self) {
-        if let Some(handle) = self.background_handle.take() {
-            let _ = handle.join();
+        // Anything still running is abandoned; clusters.toml is replaced atomically
+        let pending = self.background_tasks.shutdown(SHUTDOWN_GRACE);
+        if !pending.is_empty() {
+            crate::ui::print_debug(&format!(
+                "Background tasks still running at exit: {}",
+                pending.join(", ")
+            ));
         }
     }
 }
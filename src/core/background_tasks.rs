@@ -1,55 +1,154 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
 use crate::error::Result;
 
-/// Collect cluster info asynchronously in the background
-pub fn spawn_cluster_info_collector(
-    doris_config: crate::config_loader::DorisConfig,
-) -> std::thread::JoinHandle<()> {
-    std::thread::spawn(move || {
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        if should_update_cluster_info() {
-            collect_cluster_info_with_retry(&doris_config);
-        }
-    })
+/// How long `cleanup()` waits for background work before leaving it behind
+pub const SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskState {
+    Running,
+    Done,
+    Failed(String),
 }
 
-/// Collect cluster info with retry mechanism and timeout
-pub fn collect_cluster_info_with_retry(doris_config: &crate::config_loader::DorisConfig) {
-    const MAX_RETRIES: u32 = 3;
-    const RETRY_DELAY_SECS: u64 = 2;
-    const TIMEOUT_SECS: u64 = 30;
+struct TrackedTask {
+    name: String,
+    state: Arc<Mutex<TaskState>>,
+    handle: Option<JoinHandle<()>>,
+}
 
-    let start = std::time::Instant::now();
-    let timeout = std::time::Duration::from_secs(TIMEOUT_SECS);
-    let mut retry_count = 0;
+/// Named background threads with a shared cancellation flag
+#[derive(Default)]
+pub struct TaskManager {
+    tasks: Vec<TrackedTask>,
+    cancel: Arc<AtomicBool>,
+}
 
-    while retry_count < MAX_RETRIES && start.elapsed() < timeout {
-        match collect_cluster_info_background(doris_config) {
-            Ok(_) => break,
-            Err(e) => {
-                retry_count += 1;
+impl TaskManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-                if let crate::error::CliError::MySQLAccessDenied(_) = e {
-                    break;
-                }
-                if let crate::error::CliError::ConfigError(_) = e {
-                    break;
-                }
+    /// Run `work` on its own thread; it should return early once the flag it is
+    /// given is set
+    pub fn spawn<F>(&mut self, name: &str, work: F)
+    where
+        F: FnOnce(&AtomicBool) -> Result<()> + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(TaskState::Running));
+        let cancel = Arc::clone(&self.cancel);
+        let task_state = Arc::clone(&state);
+        let handle = std::thread::spawn(move || {
+            let outcome = match work(&cancel) {
+                Ok(()) => TaskState::Done,
+                Err(e) => TaskState::Failed(e.to_string()),
+            };
+            if let Ok(mut state) = task_state.lock() {
+                *state = outcome;
+            }
+        });
+        self.tasks.push(TrackedTask {
+            name: name.to_string(),
+            state,
+            handle: Some(handle),
+        });
+    }
 
-                if retry_count >= MAX_RETRIES || start.elapsed() >= timeout {
-                    if std::env::var("CLOUD_CLI_DEBUG").is_ok() {
-                        eprintln!(
-                            "Background cluster info collection failed after {retry_count} attempts: {e}"
-                        );
+    /// Name and state of every task spawned so far
+    pub fn status(&self) -> Vec<(String, TaskState)> {
+        self.tasks
+            .iter()
+            .map(|t| {
+                let state = t
+                    .state
+                    .lock()
+                    .map(|s| s.clone())
+                    .unwrap_or_else(|_| TaskState::Failed("panicked".to_string()));
+                (t.name.clone(), state)
+            })
+            .collect()
+    }
+
+    /// Request cancellation and join tasks that finish within `grace`; returns
+    /// the names of tasks still running, which are left detached
+    pub fn shutdown(&mut self, grace: Duration) -> Vec<String> {
+        self.cancel.store(true, Ordering::Relaxed);
+        let deadline = Instant::now() + grace;
+        while Instant::now() < deadline
+            && self
+                .tasks
+                .iter()
+                .any(|t| t.handle.as_ref().is_some_and(|h| !h.is_finished()))
+        {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let mut pending = Vec::new();
+        for task in &mut self.tasks {
+            match task.handle.take() {
+                Some(handle) if handle.is_finished() => {
+                    if handle.join().is_err()
+                        && let Ok(mut state) = task.state.lock()
+                    {
+                        *state = TaskState::Failed("panicked".to_string());
                     }
-                    break;
-                } else {
-                    std::thread::sleep(std::time::Duration::from_secs(RETRY_DELAY_SECS));
                 }
+                Some(handle) => {
+                    pending.push(task.name.clone());
+                    drop(handle);
+                }
+                None => {}
             }
         }
+        pending
     }
 }
 
+/// Sleep for `duration`, returning false early if `cancel` is set
+fn sleep_unless_cancelled(duration: Duration, cancel: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    !cancel.load(Ordering::Relaxed)
+}
+
+/// Collect cluster info asynchronously in the background
+pub fn spawn_cluster_info_collector(
+    tasks: &mut TaskManager,
+    doris_config: crate::config_loader::DorisConfig,
+) {
+    tasks.spawn("cluster-info", move |cancel| {
+        if !sleep_unless_cancelled(Duration::from_millis(100), cancel) {
+            return Ok(());
+        }
+        if should_update_cluster_info() {
+            collect_cluster_info_with_retry(&doris_config, cancel)?;
+        }
+        Ok(())
+    });
+}
+
+/// Collect cluster info, retrying connection failures with backoff while the FE starts up
+pub fn collect_cluster_info_with_retry(
+    doris_config: &crate::config_loader::DorisConfig,
+    cancel: &AtomicBool,
+) -> Result<()> {
+    if cancel.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    collect_cluster_info_background(doris_config).inspect_err(|e| {
+        crate::ui::print_debug(&format!("Background cluster info collection failed: {e}"));
+    })
+}
+
 /// Check if cluster info needs to be updated
 pub fn should_update_cluster_info() -> bool {
     let clusters_file = match dirs::home_dir() {
@@ -91,7 +190,43 @@ pub fn collect_cluster_info_background(
         return Ok(());
     }
     let mysql_tool = crate::tools::mysql::MySQLTool;
-    let cluster_info = mysql_tool.query_cluster_info(doris_config)?;
+    let cluster_info = mysql_tool.query_cluster_info_with_policy(
+        doris_config,
+        &crate::tools::mysql::RetryPolicy::background(),
+    )?;
     cluster_info.save_to_file()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CliError;
+
+    #[test]
+    fn tracks_task_states_and_cancels_on_shutdown() {
+        let mut tasks = TaskManager::new();
+        tasks.spawn("ok", |_| Ok(()));
+        tasks.spawn("fails", |_| {
+            Err(CliError::ToolExecutionFailed("boom".to_string()))
+        });
+        tasks.spawn("waits", |cancel| {
+            while !cancel.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            Ok(())
+        });
+        tasks.spawn("stuck", |_| {
+            std::thread::sleep(Duration::from_secs(2));
+            Ok(())
+        });
+
+        let pending = tasks.shutdown(Duration::from_millis(500));
+        assert_eq!(pending, ["stuck"]);
+        let status = tasks.status();
+        assert_eq!(status[0], ("ok".to_string(), TaskState::Done));
+        assert!(matches!(&status[1].1, TaskState::Failed(msg) if msg.contains("boom")));
+        assert_eq!(status[2].1, TaskState::Done);
+        assert_eq!(status[3].1, TaskState::Running);
+    }
+}
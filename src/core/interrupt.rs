@@ -0,0 +1,109 @@
+use crate::executor;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::time::{Duration, Instant};
+
+/// Write end of the self-pipe the SIGINT handler signals through
+static SIGNAL_PIPE_WRITE: AtomicI32 = AtomicI32::new(-1);
+/// Set while a watch loop wants Ctrl-C to stop it instead of exiting
+static CATCHING: AtomicBool = AtomicBool::new(false);
+static CAUGHT: AtomicBool = AtomicBool::new(false);
+
+/// Exit code conventionally used for termination by SIGINT
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+extern "C" fn on_sigint(_: libc::c_int) {
+    // Only async-signal-safe work here: wake the watcher thread
+    let fd = SIGNAL_PIPE_WRITE.load(Ordering::SeqCst);
+    if fd >= 0 {
+        let byte = 1u8;
+        unsafe {
+            libc::write(fd, &byte as *const u8 as *const libc::c_void, 1);
+        }
+    }
+}
+
+/// Install the Ctrl-C handler.
+///
+/// The signal handler only writes to a pipe; a watcher thread does the actual cleanup
+/// (restore the cursor, stop tracked child processes, remove the artifacts they were
+/// writing, flush output) and exits.
+pub fn install_handler() {
+    let mut fds = [0 as libc::c_int; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return;
+    }
+    let (read_fd, write_fd) = (fds[0], fds[1]);
+    SIGNAL_PIPE_WRITE.store(write_fd, Ordering::SeqCst);
+
+    let spawned = std::thread::Builder::new()
+        .name("sigint-watcher".into())
+        .spawn(move || {
+            let mut byte = 0u8;
+            loop {
+                let n =
+                    unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+                if n == 1 {
+                    if CATCHING.load(Ordering::SeqCst) {
+                        CAUGHT.store(true, Ordering::SeqCst);
+                        continue;
+                    }
+                    handle_interrupt();
+                }
+                if n == 0 {
+                    return;
+                }
+            }
+        });
+    if spawned.is_err() {
+        return;
+    }
+
+    unsafe {
+        libc::signal(libc::SIGINT, on_sigint as *const () as libc::sighandler_t);
+    }
+}
+
+/// Run `f` with Ctrl-C recorded instead of exiting; `f` polls [`interrupted`] to stop
+pub fn catching<T>(f: impl FnOnce() -> T) -> T {
+    CAUGHT.store(false, Ordering::SeqCst);
+    CATCHING.store(true, Ordering::SeqCst);
+    let result = f();
+    CATCHING.store(false, Ordering::SeqCst);
+    result
+}
+
+/// Whether Ctrl-C was pressed inside the current [`catching`] scope
+pub fn interrupted() -> bool {
+    CAUGHT.load(Ordering::SeqCst)
+}
+
+/// Sleep up to `duration`, returning false as soon as Ctrl-C is pressed
+pub fn sleep_unless_interrupted(duration: Duration) -> bool {
+    let until = Instant::now() + duration;
+    while Instant::now() < until {
+        if interrupted() {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(200).min(until - Instant::now()));
+    }
+    !interrupted()
+}
+
+fn handle_interrupt() -> ! {
+    let _ = console::Term::stdout().show_cursor();
+
+    for (pid, name) in executor::kill_tracked_children() {
+        crate::ui::print_warning(&format!("Stopped {name} (pid {pid})"));
+    }
+    for path in executor::remove_partial_artifacts() {
+        crate::ui::print_warning(&format!("Removed incomplete {}", path.display()));
+    }
+
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+
+    println!();
+    crate::ui::print_warning("Interrupted by user");
+    std::process::exit(INTERRUPTED_EXIT_CODE);
+}
@@ -1,5 +1,7 @@
 pub mod app_state;
 pub mod background_tasks;
+pub mod interrupt;
+pub mod version;
 
 pub use app_state::*;
 pub use background_tasks::*;
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use crate::config_loader::{self, DorisConfig};
+use crate::tools::common::http::HttpOptions;
+use crate::tools::mysql::{ClusterInfo, Frontend, MySQLTool, parse_frontends};
+
+const BOOTSTRAP_PATH: &str = "/api/bootstrap";
+const BOOTSTRAP_TIMEOUT_SECS: u64 = 3;
+
+/// Comparable Doris release number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DorisVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl DorisVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// First `x.y[.z]` in a version string such as `doris-2.1.4-rc01-8f1a2b`
+    /// or `selectdb-doris-3.0.2`; a missing patch counts as 0
+    pub fn parse(raw: &str) -> Option<Self> {
+        let start = raw.find(|c: char| c.is_ascii_digit())?;
+        let mut parts = raw[start..]
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|p| p.parse::<u32>());
+        let major = parts.next()?.ok()?;
+        let minor = parts.next()?.ok()?;
+        let patch = parts.next().and_then(|p| p.ok()).unwrap_or(0);
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for DorisVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Version of the master FE, else the first FE that reports one
+pub fn from_frontends(frontends: &[Frontend]) -> Option<String> {
+    frontends
+        .iter()
+        .filter(|fe| !fe.version.is_empty())
+        .max_by_key(|fe| fe.is_master)
+        .map(|fe| fe.version.clone())
+}
+
+/// `data.version` of the FE `/api/bootstrap` response
+pub fn parse_bootstrap(body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    value
+        .get("data")?
+        .get("version")?
+        .as_str()
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn query_bootstrap(doris: &DorisConfig) -> Option<String> {
+    let host = doris
+        .mysql
+        .as_ref()
+        .and_then(|m| m.host.clone())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = doris.http_port.unwrap_or(8030);
+    let options = HttpOptions::from_config(doris).ok()?;
+    let response = options
+        .get(
+            &options.url(&host, port, BOOTSTRAP_PATH),
+            Some(BOOTSTRAP_TIMEOUT_SECS),
+        )
+        .ok()?;
+    parse_bootstrap(&response.text())
+}
+
+/// Ask the cluster: clusters.toml (kept fresh in the background), then
+/// `SHOW FRONTENDS`, then the FE bootstrap endpoint
+pub fn detect(doris: &DorisConfig) -> Option<String> {
+    if let Some(version) = ClusterInfo::load_from_file()
+        .ok()
+        .and_then(|info| from_frontends(&info.frontends))
+    {
+        return Some(version);
+    }
+    if doris.mysql.is_some()
+        && let Some(version) = MySQLTool::query_sql_with_config(doris, "SHOW FRONTENDS \\G")
+            .ok()
+            .and_then(|out| from_frontends(&parse_frontends(&out)))
+    {
+        return Some(version);
+    }
+    query_bootstrap(doris)
+}
+
+/// Detected cluster version, persisting it when it is new or changed since the last run
+pub fn current(doris: &mut DorisConfig) -> Option<DorisVersion> {
+    if let Some(detected) = detect(doris)
+        && doris.doris_version.as_deref() != Some(detected.as_str())
+    {
+        doris.doris_version = Some(detected);
+        config_loader::persist_configuration(doris);
+    }
+    doris.doris_version.as_deref().and_then(DorisVersion::parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_orders_release_strings() {
+        let v = DorisVersion::parse("doris-2.1.4-rc01-8f1a2b").unwrap();
+        assert_eq!(v, DorisVersion::new(2, 1, 4));
+        assert_eq!(
+            DorisVersion::parse("selectdb-doris-3.0").unwrap(),
+            DorisVersion::new(3, 0, 0)
+        );
+        assert_eq!(DorisVersion::parse("doris-trunk"), None);
+        assert!(DorisVersion::new(2, 1, 3) < DorisVersion::new(2, 1, 4));
+        assert!(DorisVersion::new(2, 1, 10) > DorisVersion::new(2, 1, 9));
+        assert!(DorisVersion::new(3, 0, 0) > DorisVersion::new(2, 1, 10));
+        assert_eq!(v.to_string(), "2.1.4");
+
+        let body = r#"{"msg":"success","code":0,"data":{"replayedJournalId":0,"queryPort":9030,"version":"doris-3.0.2-rc03"},"count":0}"#;
+        assert_eq!(parse_bootstrap(body).as_deref(), Some("doris-3.0.2-rc03"));
+        assert_eq!(parse_bootstrap("not json"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use dialoguer;
 use std::fmt;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub enum CliError {
@@ -11,6 +12,33 @@ pub enum CliError {
     ConfigError(String),
     GracefulExit,
     MySQLAccessDenied(String),
+    /// Logged in, but the user may not run the statement; `detail` names the privilege
+    MySQLPrivilegeDenied {
+        detail: String,
+    },
+    MySQLUnknownDatabase,
+    Timeout {
+        tool: String,
+        seconds: u64,
+    },
+    /// Every listed `host:port` was tried without an answer
+    BeUnreachable {
+        targets: Vec<String>,
+    },
+    OutputWriteFailed {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// Refused because read-only mode is on
+    ReadOnlyBlocked(String),
+    /// An FE/BE HTTP endpoint answered 401 or 403
+    HttpAuthFailed {
+        url: String,
+        status: u16,
+        with_credentials: bool,
+    },
+    /// stdin or stdout is not a terminal, so menus and prompts cannot run
+    NoTerminal(String),
 }
 
 impl fmt::Display for CliError {
@@ -24,12 +52,96 @@ impl fmt::Display for CliError {
             CliError::ConfigError(msg) => write!(f, "Configuration error: {msg}"),
             CliError::GracefulExit => write!(f, "Graceful exit"),
             CliError::MySQLAccessDenied(msg) => write!(f, "MySQL access denied: {msg}"),
+            CliError::MySQLPrivilegeDenied { detail } => {
+                write!(f, "MySQL privilege denied: {detail}")
+            }
+            CliError::MySQLUnknownDatabase => write!(
+                f,
+                "Tool execution failed: Unknown database. Please verify the database name."
+            ),
+            CliError::Timeout { tool, seconds } => {
+                write!(
+                    f,
+                    "Tool execution failed: {tool} timed out after {seconds} seconds"
+                )
+            }
+            CliError::BeUnreachable { targets } => match targets.as_slice() {
+                [one] => write!(
+                    f,
+                    "Tool execution failed: Could not connect to BE http port {one}. Check if BE is running."
+                ),
+                all => write!(
+                    f,
+                    "Tool execution failed: Could not connect to any BE http port ({}). Check if BE is running.",
+                    all.join(", ")
+                ),
+            },
+            CliError::OutputWriteFailed { path, source } => {
+                write!(f, "Failed to write output to {}: {source}", path.display())
+            }
+            CliError::ReadOnlyBlocked(msg) => write!(f, "Blocked in read-only mode: {msg}"),
+            CliError::HttpAuthFailed {
+                url,
+                status,
+                with_credentials: true,
+            } => write!(
+                f,
+                "HTTP auth failed: {url} rejected the configured user ({status}). Check http_auth_user/http_auth_password in Settings > http-endpoints."
+            ),
+            CliError::HttpAuthFailed { url, status, .. } => write!(
+                f,
+                "HTTP auth failed: {url} requires authentication ({status}). Set http_auth_user/http_auth_password in Settings > http-endpoints."
+            ),
+            CliError::NoTerminal(msg) => write!(f, "No interactive terminal: {msg}"),
         }
     }
 }
 
 impl std::error::Error for CliError {}
 
+impl CliError {
+    /// Process exit status for wrapping scripts; stable across releases.
+    ///
+    /// 1 is the catch-all, so new variants should get their own code.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::GracefulExit => 0,
+            CliError::ToolExecutionFailed(_)
+            | CliError::ProcessExecutionFailed(_)
+            | CliError::IoError(_) => 1,
+            CliError::InvalidInput(_) => 2,
+            CliError::ConfigError(_) => 3,
+            CliError::ProcessNotFound(_) => 4,
+            CliError::MySQLAccessDenied(_) => 5,
+            CliError::MySQLUnknownDatabase => 6,
+            CliError::BeUnreachable { .. } => 7,
+            CliError::Timeout { .. } => 8,
+            CliError::OutputWriteFailed { .. } => 9,
+            CliError::ReadOnlyBlocked(_) => 10,
+            CliError::HttpAuthFailed { .. } => 11,
+            CliError::NoTerminal(_) => 12,
+            CliError::MySQLPrivilegeDenied { .. } => 13,
+        }
+    }
+
+    /// Map a failed prompt to an error, treating Ctrl-C as a graceful exit
+    pub fn prompt_failed(context: &str, err: dialoguer::Error) -> Self {
+        match err {
+            dialoguer::Error::IO(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                Self::interrupted_prompt()
+            }
+            other => CliError::InvalidInput(format!("{context}: {other}")),
+        }
+    }
+
+    /// Ctrl-C at a prompt: key reads report it as Interrupted, and the prompt may
+    /// have hidden the cursor
+    fn interrupted_prompt() -> Self {
+        let _ = console::Term::stdout().show_cursor();
+        CliError::GracefulExit
+    }
+}
+
 impl From<std::io::Error> for CliError {
     fn from(err: std::io::Error) -> Self {
         CliError::IoError(err)
@@ -44,7 +156,12 @@ impl From<anyhow::Error> for CliError {
 
 impl From<dialoguer::Error> for CliError {
     fn from(err: dialoguer::Error) -> Self {
-        CliError::InvalidInput(err.to_string())
+        match err {
+            dialoguer::Error::IO(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                CliError::interrupted_prompt()
+            }
+            other => CliError::InvalidInput(other.to_string()),
+        }
     }
 }
 
@@ -61,3 +178,70 @@ impl From<toml::de::Error> for CliError {
 }
 
 pub type Result<T> = std::result::Result<T, CliError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structured_variants_keep_messages_and_get_distinct_codes() {
+        let timeout = CliError::Timeout {
+            tool: "jmap".to_string(),
+            seconds: 60,
+        };
+        assert_eq!(
+            timeout.to_string(),
+            "Tool execution failed: jmap timed out after 60 seconds"
+        );
+        let one = CliError::BeUnreachable {
+            targets: vec!["10.0.0.1:8040".to_string()],
+        };
+        assert!(one.to_string().contains("BE http port 10.0.0.1:8040."));
+        let many = CliError::BeUnreachable {
+            targets: vec!["a:8040".to_string(), "b:8040".to_string()],
+        };
+        assert!(
+            many.to_string()
+                .contains("any BE http port (a:8040, b:8040)")
+        );
+
+        let codes = [
+            CliError::ToolExecutionFailed(String::new()).exit_code(),
+            CliError::MySQLAccessDenied(String::new()).exit_code(),
+            CliError::MySQLUnknownDatabase.exit_code(),
+            one.exit_code(),
+            timeout.exit_code(),
+            CliError::ReadOnlyBlocked(String::new()).exit_code(),
+            CliError::HttpAuthFailed {
+                url: String::new(),
+                status: 401,
+                with_credentials: false,
+            }
+            .exit_code(),
+            CliError::NoTerminal(String::new()).exit_code(),
+            CliError::MySQLPrivilegeDenied {
+                detail: String::new(),
+            }
+            .exit_code(),
+        ];
+        assert_eq!(codes, [1, 5, 6, 7, 8, 10, 11, 12, 13]);
+        assert_eq!(CliError::GracefulExit.exit_code(), 0);
+    }
+
+    #[test]
+    fn only_prompts_turn_interrupted_into_graceful_exit() {
+        let interrupted = || std::io::Error::from(std::io::ErrorKind::Interrupted);
+        assert!(matches!(
+            CliError::from(interrupted()),
+            CliError::IoError(_)
+        ));
+        assert!(matches!(
+            CliError::from(dialoguer::Error::IO(interrupted())),
+            CliError::GracefulExit
+        ));
+        assert!(matches!(
+            CliError::prompt_failed("Select", dialoguer::Error::IO(interrupted())),
+            CliError::GracefulExit
+        ));
+    }
+}
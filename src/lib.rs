@@ -7,6 +7,17 @@ pub mod process;
 pub mod tools;
 pub mod ui;
 
+// Entry points for programs embedding the collectors instead of running the menus
+pub use tools::context::{InteractiveContext, PresetContext, ToolContext};
+pub use tools::fe::routine_load::{
+    MinuteTraffic, PerformanceReport, RoutineLoadPerformanceAnalyzer, RoutineLoadTrafficMonitor,
+    TrafficReport,
+};
+pub use tools::fe::table_info::{BatchOutcome, FeTableInfoTool, TableIdentity, TableInfoReport};
+pub use tools::mysql::{ClusterInfo, MySQLTool};
+pub use ui::sink::{BufferedSink, ConsoleSink, OutputSink};
+pub use ui::{MessageType, TimeRange};
+
 use config::Config;
 use config_loader::persist_configuration;
 use dialoguer::Confirm;
@@ -17,18 +28,29 @@ use ui::*;
 
 /// Main CLI application runner
 pub fn run_cli() -> Result<()> {
+    crate::core::interrupt::install_handler();
+    ui::terminal::require_terminal("Menus disabled")?;
+    config_loader::config_persister::recover_unloadable_config()?;
+
     let mut app_state = crate::core::AppState::new()?;
+    tools::be::restore_selected_be_host(&mut app_state.doris_config);
+    ui::print_header_for(
+        app_state.config.production_profile(),
+        app_state.config.session_tag.as_deref(),
+    );
 
     if let Err(e) = app_state.config.validate() {
         ui::print_error(&format!("Config warning: {e}"));
     }
 
-    let fe_process_exists =
-        config_loader::process_detector::get_pid_by_env(config_loader::Environment::FE).is_ok();
+    let doris_process_exists =
+        config_loader::process_detector::get_pid_by_env(config_loader::Environment::FE).is_ok()
+            || config_loader::process_detector::get_pid_by_env(config_loader::Environment::BE)
+                .is_ok();
     let has_mysql = app_state.doris_config.mysql.is_some();
 
-    let cred_mgr = CredentialManager::new()?;
-    if fe_process_exists
+    let cred_mgr = CredentialManager::new(&app_state.config)?;
+    if doris_process_exists
         && !has_mysql
         && Confirm::new()
             .with_prompt("MySQL credentials not detected. Configure now?")
@@ -36,8 +58,12 @@ pub fn run_cli() -> Result<()> {
             .interact()?
     {
         match cred_mgr.prompt_credentials_with_connection_test() {
-            Ok((user, password)) => {
-                let mysql_config = cred_mgr.encrypt_credentials(&user, &password)?;
+            Ok((user, password, endpoint)) => {
+                let mut mysql_config = cred_mgr.encrypt_credentials(&user, &password)?;
+                if let Some((host, port)) = endpoint {
+                    mysql_config.host = Some(host);
+                    mysql_config.port = Some(port);
+                }
                 app_state.doris_config.mysql = Some(mysql_config);
                 persist_configuration(&app_state.doris_config);
 
@@ -65,7 +91,12 @@ pub fn run_cli() -> Result<()> {
     let mut current_config = app_state.config.clone();
 
     loop {
-        match show_main_menu()? {
+        let action = match show_main_menu() {
+            Ok(action) => action,
+            Err(error::CliError::GracefulExit) => break,
+            Err(e) => return Err(e),
+        };
+        match action {
             MainMenuAction::Fe => {
                 if let Err(e) =
                     ui::handle_service_loop(&current_config, "FE", app_state.registry.fe_tools())
@@ -86,6 +117,18 @@ pub fn run_cli() -> Result<()> {
                     }
                 }
             }
+            MainMenuAction::Doctor => {
+                if let Err(e) =
+                    execute_tool_enhanced(&current_config, &tools::doctor::DoctorTool, "Doctor")
+                {
+                    print_error(&format!("Doctor error: {e}"));
+                }
+            }
+            MainMenuAction::Settings => {
+                if let Err(e) = handle_settings_loop(&mut app_state) {
+                    print_error(&format!("Settings error: {e}"));
+                }
+            }
             MainMenuAction::Exit => break,
         }
 
@@ -99,6 +142,267 @@ pub fn run_cli() -> Result<()> {
     Ok(())
 }
 
-fn execute_tool_enhanced(config: &Config, tool: &dyn Tool, service_name: &str) -> Result<()> {
+fn handle_settings_loop(app_state: &mut crate::core::AppState) -> Result<()> {
+    loop {
+        match show_settings_menu() {
+            Ok(SettingsAction::RekeyCredentials) => rekey_credentials(app_state)?,
+            Ok(SettingsAction::CleanOutputs) => clean_outputs(&app_state.config)?,
+            Ok(SettingsAction::ExportConfig) => export_config(app_state)?,
+            Ok(SettingsAction::ImportConfig) => import_config(app_state)?,
+            Ok(SettingsAction::RestoreConfig) => restore_config(app_state)?,
+            Ok(SettingsAction::ShowConfig) => show_effective_config()?,
+            Ok(SettingsAction::SessionTag) => set_session_tag(app_state)?,
+            Ok(SettingsAction::HttpEndpoints) => set_http_endpoints(app_state)?,
+            Ok(SettingsAction::Verbosity) => set_verbosity()?,
+            Ok(SettingsAction::Back) | Err(error::CliError::GracefulExit) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn rekey_credentials(app_state: &mut crate::core::AppState) -> Result<()> {
+    use config_loader::config_persister::{self, PersistResult};
+
+    let doris = &app_state.doris_config;
+    if doris.mysql.is_none() && doris.http_auth_password.is_none() {
+        ui::print_warning("No stored MySQL or HTTP credentials to re-encrypt.");
+        return Ok(());
+    }
+    if config_persister::is_persist_suspended() {
+        ui::print_warning(
+            "Config changes are not saved this session, so the credential key is left unchanged.",
+        );
+        return Ok(());
+    }
+
+    // Re-encrypt with the new key in memory; the old key stays in place until
+    // the config holding the new ciphertext is saved
+    let cred_mgr = CredentialManager::new(&app_state.config)?;
+    let rotated = cred_mgr.rotated();
+    let mut updated = config_loader::with_session_selection(doris);
+    if let Some(mysql) = updated.mysql.as_mut() {
+        mysql.password = rotated.encrypt_password(&cred_mgr.decrypt_password(&mysql.password)?)?;
+    }
+    if let Some(encrypted) = updated.http_auth_password.as_mut() {
+        *encrypted = rotated.encrypt_password(&cred_mgr.decrypt_password(encrypted)?)?;
+    }
+    if let PersistResult::AllFailed(errors) = config_persister::persist_config(&updated)? {
+        let reasons: Vec<String> = errors
+            .iter()
+            .map(|(path, e)| format!("{}: {e}", path.display()))
+            .collect();
+        return Err(error::CliError::ConfigError(format!(
+            "Config could not be saved, credential key left unchanged ({})",
+            reasons.join("; ")
+        )));
+    }
+    if let Err(e) = rotated.store_key() {
+        // The saved ciphertext needs the new key; put back the config the old key reads
+        config_persister::persist_config(&config_loader::with_session_selection(doris))?;
+        return Err(e);
+    }
+
+    app_state.doris_config = updated;
+    ui::print_success(&format!(
+        "Credential key rotated ({:?} backend)",
+        rotated.active_source()
+    ));
+    Ok(())
+}
+
+fn export_config(app_state: &crate::core::AppState) -> Result<()> {
+    let default = app_state.config.output_dir.join("cloud-cli-config.toml");
+    let path = dialogs::input_text("Export to", &default.to_string_lossy())?;
+    let path = std::path::PathBuf::from(path.trim());
+    config_loader::config_persister::export_config(&app_state.doris_config, &path)?;
+    ui::print_success(&format!(
+        "Config exported to {} (MySQL password and process details left out)",
+        path.display()
+    ));
+    Ok(())
+}
+
+fn import_config(app_state: &mut crate::core::AppState) -> Result<()> {
+    let path = dialogs::input_text("Import from", "")?;
+    let path = std::path::PathBuf::from(path.trim());
+    let merged = config_loader::config_persister::import_config(&app_state.doris_config, &path)?;
+    app_state.config = config_loader::to_app_config(merged.clone());
+    app_state.doris_config = merged;
+    ui::print_success(&format!("Config imported from {}", path.display()));
+    Ok(())
+}
+
+/// Pick one of the config backups and put it back after confirmation
+fn restore_config(app_state: &mut crate::core::AppState) -> Result<()> {
+    use config_loader::config_persister;
+
+    let backups = config_persister::list_backups()?;
+    if backups.is_empty() {
+        ui::print_info("No config backups yet; one is kept each time the config changes.");
+        return Ok(());
+    }
+    let labels: Vec<String> = backups
+        .iter()
+        .map(|b| {
+            let name = b.path.file_name().unwrap_or_default().to_string_lossy();
+            let when = b.modified.map_or_else(
+                || "unknown time".to_string(),
+                |t| t.format("%Y-%m-%d %H:%M:%S").to_string(),
+            );
+            let summary = match &b.config {
+                Ok(backup) => {
+                    let changes =
+                        config_persister::backup_differences(&app_state.doris_config, backup);
+                    if changes.is_empty() {
+                        "same environment, ports and MySQL".to_string()
+                    } else {
+                        changes.join(", ")
+                    }
+                }
+                Err(e) => format!("unreadable: {e}"),
+            };
+            format!("{name} ({when}): {summary}")
+        })
+        .collect();
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let backup = &backups[dialogs::select_index("Restore which backup?", &labels)?];
+    if let Err(e) = &backup.config {
+        ui::print_error(&format!(
+            "{} cannot be restored: {e}",
+            backup.path.display()
+        ));
+        return Ok(());
+    }
+
+    if !Confirm::new()
+        .with_prompt(format!(
+            "Replace the current config with {}?",
+            backup.path.display()
+        ))
+        .default(false)
+        .interact()?
+    {
+        return Ok(());
+    }
+    let restored = config_persister::restore_backup(&backup.path)?;
+    app_state.config = config_loader::to_app_config(restored.clone());
+    app_state.doris_config = restored;
+    ui::print_success(&format!(
+        "Config restored from {} (the replaced file is now the newest backup)",
+        backup.path.display()
+    ));
+    Ok(())
+}
+
+/// Effective values as a fresh start would see them, each with its source
+fn show_effective_config() -> Result<()> {
+    use config_loader::provenance;
+
+    let (doris, mut sources) = config_loader::load_config_with_provenance()?;
+    let mut config = config_loader::to_app_config(doris.clone());
+    config.load_from_env(&mut sources);
+
+    match config_loader::config_persister::persisted_config_path() {
+        Some(path) => ui::print_info(&format!("Config file: {}", path.display())),
+        None => ui::print_info("Config file: none (nothing persisted yet)"),
+    }
+    println!();
+    println!("{:<24} {:<40} Source", "Field", "Value");
+    println!("{}", "-".repeat(84));
+    for (field, value, source) in provenance::effective_rows(&config, &doris, &sources) {
+        println!(
+            "{field:<24} {:<40} {source}",
+            ui::FormatHelper::truncate_string(&value, 40)
+        );
+    }
+    Ok(())
+}
+
+/// Tag the rest of this session's outputs, e.g. with the support ticket ID
+fn set_session_tag(app_state: &mut crate::core::AppState) -> Result<()> {
+    let current = app_state.config.session_tag.clone().unwrap_or_default();
+    let input = dialogs::input_text("Session tag (blank to clear)", &current)?;
+    app_state.config.session_tag = config::set_session_tag(&input);
+    match &app_state.config.session_tag {
+        Some(tag) => ui::print_success(&format!("Output files now start with '{tag}_'")),
+        None => ui::print_info("Session tag cleared"),
+    }
+    ui::print_header_for(
+        app_state.config.production_profile(),
+        app_state.config.session_tag.as_deref(),
+    );
+    Ok(())
+}
+
+/// Output level for the rest of the session; `CLOUD_CLI_VERBOSE` sets it at startup
+fn set_verbosity() -> Result<()> {
+    let names: Vec<&str> = ui::Verbosity::ALL.iter().map(|v| v.as_str()).collect();
+    let index = dialoguer::Select::new()
+        .with_prompt("Output verbosity")
+        .items(&names)
+        .default(ui::verbosity() as usize)
+        .interact()?;
+    ui::set_verbosity(ui::Verbosity::ALL[index]);
+    ui::print_success(&format!("Verbosity set to {}", names[index]));
+    Ok(())
+}
+
+/// Scheme, certificate policy and basic auth for the FE/BE HTTP endpoints
+fn set_http_endpoints(app_state: &mut crate::core::AppState) -> Result<()> {
+    const SCHEMES: &[&str] = &["http", "https", "https, accepting self-signed certificates"];
+    let doris = &mut app_state.doris_config;
+    let scheme = dialogs::select_index("FE/BE HTTP endpoints use", SCHEMES)?;
+    doris.https = scheme > 0;
+    doris.http_insecure = scheme == 2;
+
+    let current = doris.http_auth_user.clone().unwrap_or_default();
+    let user = dialogs::input_text("HTTP auth user (blank for none)", &current)?;
+    let user = user.trim();
+    if user.is_empty() {
+        doris.http_auth_user = None;
+        doris.http_auth_password = None;
+    } else {
+        let password = dialoguer::Password::new()
+            .with_prompt("HTTP auth password (press Enter for empty)")
+            .allow_empty_password(true)
+            .interact()?;
+        doris.http_auth_password =
+            Some(CredentialManager::new(&app_state.config)?.encrypt_password(&password)?);
+        doris.http_auth_user = Some(user.to_string());
+    }
+
+    persist_configuration(&app_state.doris_config);
+    let doris = &app_state.doris_config;
+    let auth = match &doris.http_auth_user {
+        Some(user) => format!("basic auth as '{user}'"),
+        None => "no auth".to_string(),
+    };
+    ui::print_success(&format!("HTTP endpoints: {}, {auth}", SCHEMES[scheme]));
+    Ok(())
+}
+
+/// Manual cleanup; without a configured policy the user picks an age limit
+fn clean_outputs(config: &Config) -> Result<()> {
+    use tools::common::retention::{self, RetentionPolicy};
+
+    let mut policy = RetentionPolicy::from_config(config);
+    if !policy.is_enabled() {
+        let days = InputHelper::prompt_number_with_default(
+            "Remove outputs older than",
+            7,
+            0,
+            None,
+            Some("days"),
+        )?;
+        policy.max_age = Some(std::time::Duration::from_secs(days as u64 * 24 * 3600));
+    }
+    retention::enforce(config, &policy)
+}
+
+fn execute_tool_enhanced(
+    config: &Config,
+    tool: &dyn Tool,
+    service_name: &str,
+) -> Result<Option<tools::ExecutionResult>> {
     ui::tool_executor::execute_tool_enhanced(config, tool, service_name)
 }
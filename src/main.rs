@@ -1,7 +1,29 @@
-use cloud_cli::error::Result;
-use cloud_cli::{run_cli, ui};
+use cloud_cli::error::CliError;
+use cloud_cli::run_cli;
 
-fn main() -> Result<()> {
-    ui::print_header();
-    run_cli()
+fn main() {
+    cloud_cli::ui::terminal::install_closed_output_hook();
+    if std::env::args().skip(1).any(|a| a == "--stdout") {
+        cloud_cli::config::enable_stdout_only();
+    }
+    if std::env::args().skip(1).any(|a| a == "--plain") {
+        cloud_cli::config::enable_plain_output();
+    }
+    if std::env::args().skip(1).any(|a| a == "--quiet") {
+        cloud_cli::config::enable_quiet_output();
+    }
+
+    match run_cli() {
+        Ok(()) => {}
+        // Ctrl-C at a top-level prompt
+        Err(CliError::GracefulExit) => cloud_cli::ui::print_goodbye(),
+        // `cloud-cli | head` or a closed terminal: nobody left to tell
+        Err(CliError::IoError(e)) if cloud_cli::ui::terminal::is_closed_output(&e) => {
+            cloud_cli::ui::terminal::exit_on_closed_output()
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(e.exit_code());
+        }
+    }
 }
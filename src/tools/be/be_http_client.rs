@@ -1,97 +1,457 @@
 use crate::config_loader;
 use crate::error::{CliError, Result};
-use crate::executor;
-use crate::tools::{be, mysql};
+use crate::tools::common::http::HttpOptions;
+use crate::tools::{ExecutionResult, be, mysql};
 use crate::ui;
+use crate::ui::InteractiveSelector;
+use once_cell::sync::Lazy;
 use std::collections::BTreeSet;
-use std::process::Command;
+use std::fmt;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::sync::Mutex;
 
 const BE_DEFAULT_IP: &str = "127.0.0.1";
+/// Ports BE webservers listen on when nothing else is known
+const DEFAULT_BE_HTTP_PORTS: [u16; 2] = [8040, 8041];
+const ALL_BACKENDS: &str = "All backends";
+const ALL_COMPUTE_GROUPS: &str = "All compute groups";
 
-/// Send an HTTP GET request to a BE API endpoint
-pub fn request_be_webserver_port(endpoint: &str, filter_pattern: Option<&str>) -> Result<String> {
-    let mut be_targets: BTreeSet<(String, u16)> = BTreeSet::new();
+/// Port of the last first-responder lookup that succeeded in this session
+static WORKING_HTTP_PORT: Lazy<Mutex<Option<u16>>> = Lazy::new(|| Mutex::new(None));
 
-    let ports = get_be_http_ports()?;
+/// Webserver endpoint of one BE
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BeTarget {
+    pub host: String,
+    pub port: u16,
+}
 
-    let selected_host = be::list::get_selected_be_host();
+impl fmt::Display for BeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
 
-    let cluster_hosts = get_be_ip().unwrap_or_default();
+impl BeTarget {
+    /// Suffix for per-host output files
+    pub fn file_suffix(&self) -> String {
+        format!("{}_{}", self.host, self.port)
+    }
 
-    let mut all_hosts = BTreeSet::new();
-    if let Some(host) = &selected_host {
-        all_hosts.insert(host.clone());
+    /// Whether the BE runs on this machine: its address can be bound here
+    pub fn is_local(&self) -> bool {
+        (self.host.as_str(), 0)
+            .to_socket_addrs()
+            .map(|mut addrs| addrs.any(|addr| UdpSocket::bind((addr.ip(), 0)).is_ok()))
+            .unwrap_or(false)
     }
-    for host in cluster_hosts {
-        all_hosts.insert(host);
+}
+
+/// Which backends a BE tool should query
+#[derive(Debug, Clone, PartialEq)]
+pub enum BeSelection {
+    /// Single-node or unknown cluster: first BE that answers
+    Auto,
+    One(BeTarget),
+    All(Vec<BeTarget>),
+}
+
+/// Let the user narrow the cluster to one compute group.
+///
+/// `None` means every backend: the cluster has fewer than two groups or the
+/// user chose all of them.
+pub fn select_compute_group(info: &mysql::ClusterInfo) -> Result<Option<String>> {
+    if info.compute_groups.len() < 2 {
+        return Ok(None);
     }
+    let mut items: Vec<String> = info
+        .compute_groups
+        .iter()
+        .map(|g| {
+            format!(
+                "{} ({} alive BE)",
+                g.name,
+                info.backends_in_group(&g.name).len()
+            )
+        })
+        .collect();
+    items.push(ALL_COMPUTE_GROUPS.to_string());
+
+    let default = be::list::get_selected_compute_group()
+        .and_then(|group| info.compute_groups.iter().position(|g| g.name == group))
+        .or_else(|| {
+            let host = be::list::get_selected_be_host()?;
+            info.compute_groups
+                .iter()
+                .position(|g| info.list_be_hosts_in_group(&g.name).contains(&host))
+        })
+        .unwrap_or(0);
+    let selected = InteractiveSelector::new(items.clone(), "Select compute group:".to_string())
+        .with_default(default)
+        .select()?
+        .clone();
+    let index = items.iter().position(|i| *i == selected);
+    Ok(index.and_then(|i| info.compute_groups.get(i).map(|g| g.name.clone())))
+}
 
-    if all_hosts.is_empty() {
-        all_hosts.insert(BE_DEFAULT_IP.to_string());
+/// Let the user pick a BE when the cluster has several alive backends.
+///
+/// In cloud mode a compute group is picked first. The host chosen via
+/// `be-list` is pre-selected.
+pub fn select_be_targets() -> Result<BeSelection> {
+    let targets = match mysql::ClusterInfo::load_from_file() {
+        Ok(info) => {
+            let group = select_compute_group(&info)?;
+            alive_be_targets(&info, group.as_deref())
+        }
+        Err(_) => Vec::new(),
+    };
+    if targets.len() <= 1 {
+        return Ok(targets
+            .into_iter()
+            .next()
+            .map(BeSelection::One)
+            .unwrap_or(BeSelection::Auto));
     }
 
-    for host in all_hosts {
-        be_targets.extend(ports.iter().map(|p| (host.clone(), *p)));
+    let mut items: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+    items.push(ALL_BACKENDS.to_string());
+
+    let default = be::list::get_selected_be_host()
+        .and_then(|host| targets.iter().position(|t| t.host == host))
+        .unwrap_or(0);
+
+    let selected = InteractiveSelector::new(items, "Select BE target:".to_string())
+        .with_default(default)
+        .select()?
+        .clone();
+
+    if selected == ALL_BACKENDS {
+        return Ok(BeSelection::All(targets));
     }
+    Ok(targets
+        .into_iter()
+        .find(|t| t.to_string() == selected)
+        .map(BeSelection::One)
+        .unwrap_or(BeSelection::Auto))
+}
 
-    for (host, port) in &be_targets {
-        let url = format!("http://{host}:{port}{endpoint}");
-        let mut curl_cmd = Command::new("curl");
-        curl_cmd.args(["-sS", &url]);
+/// Alive backends, optionally only those of compute group `group`
+fn alive_be_targets(info: &mysql::ClusterInfo, group: Option<&str>) -> Vec<BeTarget> {
+    let backends: Vec<&mysql::Backend> = match group {
+        Some(name) => info.backends_in_group(name),
+        None => info.backends.iter().filter(|b| b.alive).collect(),
+    };
+    let targets: BTreeSet<BeTarget> = backends
+        .into_iter()
+        .map(|b| BeTarget {
+            host: b.host.clone(),
+            port: b.http_port,
+        })
+        .collect();
+    targets.into_iter().collect()
+}
+
+/// Run `handle` against the selected backend(s).
+///
+/// With several backends each section is labelled with its host and one failing
+/// node does not stop the others: its error is printed as it happens and the
+/// failed count is reported at the end. The first produced output path is returned.
+pub fn run_on_selection<F>(
+    selection: &BeSelection,
+    endpoint: &str,
+    filter_pattern: Option<&str>,
+    handle: F,
+) -> Result<ExecutionResult>
+where
+    F: FnMut(Option<&BeTarget>, Result<String>) -> Result<ExecutionResult>,
+{
+    run_selection_with(
+        selection,
+        |target| match target {
+            Some(target) => request_be_target(target, endpoint, filter_pattern),
+            None => request_be_webserver_port(endpoint, filter_pattern),
+        },
+        handle,
+    )
+}
 
-        if let Ok(output) = executor::execute_command(&mut curl_cmd, "curl") {
-            let content = String::from_utf8_lossy(&output.stdout);
+/// `run_on_selection` with the request left to `request`
+fn run_selection_with<R, F>(
+    selection: &BeSelection,
+    mut request: R,
+    mut handle: F,
+) -> Result<ExecutionResult>
+where
+    R: FnMut(Option<&BeTarget>) -> Result<String>,
+    F: FnMut(Option<&BeTarget>, Result<String>) -> Result<ExecutionResult>,
+{
+    let targets = match selection {
+        BeSelection::Auto => return handle(None, request(None)),
+        BeSelection::One(target) => return handle(Some(target), request(Some(target))),
+        BeSelection::All(targets) => targets,
+    };
 
-            // If a filter pattern is provided, filter the content
-            if let Some(pattern) = filter_pattern {
-                let filtered_lines: Vec<&str> = content
-                    .lines()
-                    .filter(|line| line.contains(pattern))
-                    .collect();
-                return Ok(filtered_lines.join("\n"));
+    let mut results = Vec::new();
+    let mut last_err = None;
+    for target in targets {
+        ui::print_info("");
+        ui::print_info(&format!("===== BE {target} ====="));
+        match handle(Some(target), request(Some(target))) {
+            Ok(r) => results.push(r),
+            Err(CliError::GracefulExit) => return Err(CliError::GracefulExit),
+            Err(e) => {
+                ui::print_error(&format!("BE {target} failed: {e}"));
+                last_err = Some(e);
             }
+        }
+    }
+
+    let (ok, total) = (results.len(), targets.len());
+    if ok < total {
+        ui::print_warning(&format!("{}/{total} backends failed", total - ok));
+    }
+    match results.into_iter().next() {
+        Some(first) => Ok(ExecutionResult::new(
+            first.output_path,
+            format!("{ok}/{total} backends succeeded"),
+        )),
+        None => Err(last_err.unwrap_or_else(|| {
+            CliError::ToolExecutionFailed("No backends were queried".to_string())
+        })),
+    }
+}
+
+/// Send an HTTP GET request to one specific BE
+pub fn request_be_target(
+    target: &BeTarget,
+    endpoint: &str,
+    filter_pattern: Option<&str>,
+) -> Result<String> {
+    let options = HttpOptions::load()?;
+    fetch(&options, target, endpoint, filter_pattern)
+        .map_err(|e| unreachable_unless_auth(e, std::slice::from_ref(target)))
+}
+
+fn fetch(
+    options: &HttpOptions,
+    target: &BeTarget,
+    endpoint: &str,
+    filter_pattern: Option<&str>,
+) -> Result<String> {
+    let url = options.url(&target.host, target.port, endpoint);
+    let content = options.get(&url, None)?.text();
+
+    // If a filter pattern is provided, filter the content
+    if let Some(pattern) = filter_pattern {
+        return Ok(filter_lines(&content, pattern));
+    }
+
+    Ok(content)
+}
+
+/// Auth failures keep their own error so the user is pointed at the HTTP settings
+fn unreachable_unless_auth(err: CliError, targets: &[BeTarget]) -> CliError {
+    match err {
+        CliError::HttpAuthFailed { .. } => err,
+        _ => CliError::BeUnreachable {
+            targets: targets.iter().map(|t| t.to_string()).collect(),
+        },
+    }
+}
+
+/// Lines of `content` containing `pattern`, as applied by `filter_pattern`
+pub fn filter_lines(content: &str, pattern: &str) -> String {
+    content
+        .lines()
+        .filter(|line| line.contains(pattern))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Send an HTTP GET request to a BE API endpoint
+pub fn request_be_webserver_port(endpoint: &str, filter_pattern: Option<&str>) -> Result<String> {
+    request_first_responder(endpoint, filter_pattern).map(|(_, content)| content)
+}
 
-            return Ok(content.to_string());
+/// [`request_be_webserver_port`] that also returns the candidate which answered.
+/// Its port is tried first for the rest of the session.
+pub fn request_first_responder(
+    endpoint: &str,
+    filter_pattern: Option<&str>,
+) -> Result<(BeTarget, String)> {
+    let be_targets = candidate_targets()?;
+    let options = HttpOptions::load()?;
+
+    for target in &be_targets {
+        match fetch(&options, target, endpoint, filter_pattern) {
+            Ok(content) => {
+                promote_http_port(target);
+                return Ok((target.clone(), content));
+            }
+            Err(e @ CliError::HttpAuthFailed { .. }) => return Err(e),
+            Err(e) => ui::print_debug(&format!("BE probe {target}{endpoint} failed: {e}")),
         }
     }
 
-    let ports_str = be_targets
+    Err(no_be_reachable(&be_targets))
+}
+
+/// Remember the port `target` answered on so later lookups try it first
+fn promote_http_port(target: &BeTarget) {
+    if let Ok(mut port) = WORKING_HTTP_PORT.lock()
+        && *port != Some(target.port)
+    {
+        ui::print_debug(&format!("BE webserver answered on {target}"));
+        *port = Some(target.port);
+    }
+}
+
+fn working_http_port() -> Option<u16> {
+    WORKING_HTTP_PORT.lock().ok().and_then(|p| *p)
+}
+
+/// Every host:port the first-responder lookup tries, in order: the selected host,
+/// then the cluster hosts, each crossed with [`get_be_http_ports`]
+pub fn candidate_targets() -> Result<Vec<BeTarget>> {
+    let ports = get_be_http_ports()?;
+
+    let mut hosts: Vec<String> = Vec::new();
+    let selected_host = be::list::get_selected_be_host();
+    let cluster_hosts = get_be_ip().unwrap_or_default();
+    for host in selected_host.into_iter().chain(cluster_hosts) {
+        if !hosts.contains(&host) {
+            hosts.push(host);
+        }
+    }
+    if hosts.is_empty() {
+        hosts.push(BE_DEFAULT_IP.to_string());
+    }
+
+    Ok(hosts
         .iter()
-        .map(|(h, p)| format!("{h}:{p}"))
-        .collect::<Vec<_>>()
-        .join(", ");
+        .flat_map(|host| {
+            ports.iter().map(|port| BeTarget {
+                host: host.clone(),
+                port: *port,
+            })
+        })
+        .collect())
+}
 
+fn no_be_reachable(be_targets: &[BeTarget]) -> CliError {
     ui::print_warning(
         "Could not connect to any BE http endpoint. You can select a host via 'be-list'.",
     );
-    Err(CliError::ToolExecutionFailed(format!(
-        "Could not connect to any BE http port ({ports_str}). Check if BE is running."
-    )))
+    CliError::BeUnreachable {
+        targets: be_targets.iter().map(|t| t.to_string()).collect(),
+    }
 }
 
-/// Get BE HTTP ports from configuration or use defaults
-pub fn get_be_http_ports() -> Result<Vec<u16>> {
-    if let Ok(doris_config) = config_loader::load_config() {
-        let config_ports = doris_config.get_be_http_ports();
-        if !config_ports.is_empty() && config_ports != vec![8040, 8041] {
-            return Ok(config_ports);
+/// Fetch a binary response from the selected backend(s).
+///
+/// `Auto` returns the first candidate that answers; otherwise every target is queried.
+pub fn request_bytes_on_selection(
+    selection: &BeSelection,
+    endpoint: &str,
+    max_secs: u64,
+) -> Result<Vec<(BeTarget, Result<Vec<u8>>)>> {
+    let options = HttpOptions::load()?;
+    match selection {
+        BeSelection::Auto => {
+            let candidates = candidate_targets()?;
+            for target in &candidates {
+                match fetch_bytes(&options, target, endpoint, max_secs) {
+                    Ok(body) => {
+                        promote_http_port(target);
+                        return Ok(vec![(target.clone(), Ok(body))]);
+                    }
+                    Err(e @ CliError::HttpAuthFailed { .. }) => return Err(e),
+                    Err(_) => {}
+                }
+            }
+            Err(no_be_reachable(&candidates))
         }
+        BeSelection::One(target) => Ok(vec![(
+            target.clone(),
+            fetch_bytes(&options, target, endpoint, max_secs),
+        )]),
+        BeSelection::All(targets) => Ok(targets
+            .iter()
+            .map(|t| (t.clone(), fetch_bytes(&options, t, endpoint, max_secs)))
+            .collect()),
     }
+}
 
-    if let Ok(info) = mysql::ClusterInfo::load_from_file() {
-        let be_ports: Vec<u16> = info
-            .backends
-            .iter()
-            .filter(|b| b.alive)
-            .map(|b| b.http_port)
-            .collect();
+/// Binary-safe GET against one BE, giving up after `max_secs`
+pub fn request_be_target_bytes(
+    target: &BeTarget,
+    endpoint: &str,
+    max_secs: u64,
+) -> Result<Vec<u8>> {
+    fetch_bytes(&HttpOptions::load()?, target, endpoint, max_secs)
+}
+
+fn fetch_bytes(
+    options: &HttpOptions,
+    target: &BeTarget,
+    endpoint: &str,
+    max_secs: u64,
+) -> Result<Vec<u8>> {
+    let url = options.url(&target.host, target.port, endpoint);
+    options.get(&url, Some(max_secs)).map(|r| r.body)
+}
 
-        if !be_ports.is_empty() {
-            return Ok(be_ports);
+/// BE HTTP ports to probe, most trusted first; see [`order_http_ports`]
+pub fn get_be_http_ports() -> Result<Vec<u16>> {
+    let configured = config_loader::load_config()
+        .ok()
+        .and_then(|doris| doris.webserver_port);
+    let info = mysql::ClusterInfo::load_from_file().ok();
+    let selected = be::list::get_selected_be_host()
+        .zip(info.as_ref())
+        .and_then(|(host, info)| {
+            info.backends
+                .iter()
+                .find(|b| b.host == host)
+                .map(|b| b.http_port)
+        });
+    let cluster: Vec<u16> = info
+        .iter()
+        .flat_map(|info| info.backends.iter().filter(|b| b.alive))
+        .map(|b| b.http_port)
+        .collect();
+
+    Ok(order_http_ports(
+        working_http_port(),
+        selected,
+        configured,
+        &cluster,
+    ))
+}
+
+/// Candidate ports without repeats: the port that already answered this session,
+/// the selected BE's http_port from clusters.toml, webserver_port from be.conf,
+/// the other alive backends' ports, then the 8040/8041 defaults
+pub fn order_http_ports(
+    working: Option<u16>,
+    selected: Option<u16>,
+    configured: Option<u16>,
+    cluster: &[u16],
+) -> Vec<u16> {
+    let mut ports: Vec<u16> = Vec::new();
+    let candidates = working
+        .into_iter()
+        .chain(selected)
+        .chain(configured)
+        .chain(cluster.iter().copied())
+        .chain(DEFAULT_BE_HTTP_PORTS);
+    for port in candidates.filter(|p| *p != 0) {
+        if !ports.contains(&port) {
+            ports.push(port);
         }
     }
-
-    Ok(vec![8040, 8041])
+    ports
 }
 
 pub fn get_be_ip() -> Result<Vec<String>> {
@@ -104,3 +464,88 @@ pub fn get_be_ip() -> Result<Vec<String>> {
 
     Ok(vec![BE_DEFAULT_IP.to_string()])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_http_ports_by_source() {
+        // Nothing known: the defaults
+        assert_eq!(order_http_ports(None, None, None, &[]), [8040, 8041]);
+        // be.conf alone keeps the defaults as fallback
+        assert_eq!(order_http_ports(None, None, Some(8041), &[]), [8041, 8040]);
+        // webserver_port commented out, SHOW BACKENDS says 8050
+        assert_eq!(
+            order_http_ports(None, None, None, &[8050, 8050]),
+            [8050, 8040, 8041]
+        );
+        // The selected BE goes before be.conf and the rest of the cluster
+        assert_eq!(
+            order_http_ports(None, Some(8050), Some(8040), &[8060, 8050]),
+            [8050, 8040, 8060, 8041]
+        );
+        // A port that answered earlier in the session leads
+        assert_eq!(
+            order_http_ports(Some(8041), Some(8050), Some(8040), &[8050]),
+            [8041, 8050, 8040]
+        );
+    }
+
+    #[test]
+    fn all_backends_keep_going_past_failures() {
+        let target = |host: &str| BeTarget {
+            host: host.to_string(),
+            port: 8040,
+        };
+        let request = |t: Option<&BeTarget>| match t.map(|t| t.host.as_str()) {
+            Some("be2") => Err(CliError::ToolExecutionFailed("refused".to_string())),
+            Some(host) => Ok(format!("body of {host}")),
+            None => Ok("auto".to_string()),
+        };
+        let mut seen = Vec::new();
+        let mut handle = |t: Option<&BeTarget>, body: Result<String>| {
+            seen.push(t.map(|t| t.host.clone()));
+            body.map(ExecutionResult::console)
+        };
+
+        let all = BeSelection::All(vec![target("be1"), target("be2"), target("be3")]);
+        let result = run_selection_with(&all, request, &mut handle).unwrap();
+        assert_eq!(result.message, "2/3 backends succeeded");
+
+        let one = BeSelection::One(target("be2"));
+        assert!(run_selection_with(&one, request, &mut handle).is_err());
+        let auto = run_selection_with(&BeSelection::Auto, request, &mut handle).unwrap();
+        assert_eq!(auto.message, "auto");
+        assert_eq!(
+            seen,
+            [
+                Some("be1".to_string()),
+                Some("be2".to_string()),
+                Some("be3".to_string()),
+                Some("be2".to_string()),
+                None
+            ]
+        );
+
+        let broken = BeSelection::All(vec![target("be2")]);
+        let err = run_selection_with(&broken, request, |_, body| {
+            body.map(ExecutionResult::console)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("refused"));
+    }
+
+    #[test]
+    fn only_addresses_of_this_machine_are_local() {
+        let target = |host: &str| BeTarget {
+            host: host.to_string(),
+            port: 8040,
+        };
+        assert!(target("127.0.0.1").is_local());
+        assert!(target("localhost").is_local());
+        // TEST-NET-1, never assigned to a real interface
+        assert!(!target("192.0.2.1").is_local());
+        assert!(!target("no-such-host.invalid").is_local());
+    }
+}
@@ -0,0 +1,405 @@
+use std::fs;
+use std::process::Command;
+use std::thread;
+
+use super::be_http_client::{self, BeSelection, BeTarget};
+use super::mem_tracker::{parse_mem_tracker, process_total, top_trackers};
+use super::pipeline_tasks::{fetch_dump, parse_pipeline_tasks};
+use crate::config::Config;
+use crate::config_loader::{Environment, process_detector};
+use crate::error::{CliError, Result};
+use crate::executor;
+use crate::tools::common::{file_name, format_utils, fs_utils};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+const PIPELINE_ENDPOINT: &str = "/api/running_pipeline_tasks";
+const MEM_TRACKER_ENDPOINT: &str = "/mem_tracker";
+const VARZ_ENDPOINT: &str = "/varz";
+/// `/varz` entries kept in the snapshot: scanner pools and memory limits
+const VARZ_KEYWORDS: &[&str] = &["scan", "mem"];
+const DIGEST_ROWS: usize = 5;
+
+/// One thread line of `top -H -b -n1`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadCpu {
+    pub tid: u32,
+    pub cpu: f64,
+    pub name: String,
+}
+
+/// Threads of a `top -H -b` batch, busiest first, and the load average of its header
+pub fn parse_top_threads(output: &str) -> (Vec<ThreadCpu>, Option<String>) {
+    let load = output
+        .lines()
+        .find_map(|l| l.split_once("load average:"))
+        .map(|(_, avg)| avg.trim().to_string());
+
+    let mut lines = output.lines().skip_while(|l| !l.contains("%CPU"));
+    let Some(header) = lines.next() else {
+        return (Vec::new(), load);
+    };
+    let columns: Vec<&str> = header.split_whitespace().collect();
+    let position = |name: &str| columns.iter().position(|c| *c == name);
+    let (Some(pid_col), Some(cpu_col), Some(cmd_col)) =
+        (position("PID"), position("%CPU"), position("COMMAND"))
+    else {
+        return (Vec::new(), load);
+    };
+
+    let mut threads: Vec<ThreadCpu> = lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            Some(ThreadCpu {
+                tid: fields.get(pid_col)?.parse().ok()?,
+                cpu: fields.get(cpu_col)?.replace(',', ".").parse().ok()?,
+                name: fields.get(cmd_col..)?.join(" "),
+            })
+        })
+        .collect();
+    threads.sort_by(|a, b| b.cpu.total_cmp(&a.cpu));
+    (threads, load)
+}
+
+/// `name=value` lines of `/varz` whose name mentions one of [`VARZ_KEYWORDS`]
+pub fn filter_varz(content: &str) -> String {
+    content
+        .lines()
+        .filter(|line| {
+            let name = line.split('=').next().unwrap_or(line).to_ascii_lowercase();
+            VARZ_KEYWORDS.iter().any(|k| name.contains(k))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `/proc/<pid>/status` fields worth a line in the digest
+fn status_fields(status: &str) -> Vec<String> {
+    status
+        .lines()
+        .filter(|l| {
+            ["State:", "Threads:", "VmRSS:", "VmHWM:"]
+                .iter()
+                .any(|k| l.starts_with(k))
+        })
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect()
+}
+
+/// Outcome of one collector; failures end up in the digest instead of aborting
+struct Part {
+    file: &'static str,
+    result: Result<String>,
+}
+
+/// Everything captured at one moment, ready to be written and summarized
+struct Snapshot {
+    target: String,
+    pid: Option<u32>,
+    pipeline: Part,
+    mem_tracker: Part,
+    varz: Part,
+    top: Part,
+    status: Part,
+}
+
+impl Snapshot {
+    fn parts(&self) -> [&Part; 5] {
+        [
+            &self.pipeline,
+            &self.mem_tracker,
+            &self.varz,
+            &self.top,
+            &self.status,
+        ]
+    }
+
+    fn failures(&self) -> Vec<String> {
+        self.parts()
+            .iter()
+            .filter_map(|p| p.result.as_ref().err().map(|e| format!("{}: {e}", p.file)))
+            .collect()
+    }
+
+    fn digest(&self) -> String {
+        let mut out = format!("BE snapshot of {}\n", self.target);
+
+        match &self.pipeline.result {
+            Ok(body) => {
+                let summary = parse_pipeline_tasks(body);
+                out.push_str(&format!(
+                    "Running queries: {} ({} pipeline tasks, {} running, {} blocked)\n",
+                    summary.queries.len(),
+                    summary.tasks,
+                    summary.running,
+                    summary.blocked
+                ));
+            }
+            Err(_) => out.push_str("Running queries: unavailable\n"),
+        }
+
+        if let Ok(body) = &self.mem_tracker.result {
+            let rows = parse_mem_tracker(body);
+            let total = process_total(&rows)
+                .map(|b| format!(" (process total {})", size(b)))
+                .unwrap_or_default();
+            out.push_str(&format!("\nTop memory trackers{total}:\n"));
+            for r in top_trackers(&rows, DIGEST_ROWS) {
+                out.push_str(&format!(
+                    "  {:>10}  {} [{}]\n",
+                    size(r.current),
+                    ui::FormatHelper::truncate_string(&r.label, 60),
+                    r.kind
+                ));
+            }
+        }
+
+        if let Ok(top) = &self.top.result {
+            let (threads, load) = parse_top_threads(top);
+            let pid = self
+                .pid
+                .map(|p| format!(", local pid {p}"))
+                .unwrap_or_default();
+            out.push_str(&format!("\nTop CPU threads{pid}:\n"));
+            for t in threads.iter().take(DIGEST_ROWS) {
+                out.push_str(&format!("  {:>6.1}%  {:<8} {}\n", t.cpu, t.tid, t.name));
+            }
+            if let Some(load) = load {
+                out.push_str(&format!("\nLoad average: {load}\n"));
+            }
+        }
+
+        if let Ok(status) = &self.status.result {
+            let fields = status_fields(status);
+            if !fields.is_empty() {
+                out.push_str(&format!("Process: {}\n", fields.join(", ")));
+            }
+        }
+
+        let failures = self.failures();
+        if !failures.is_empty() {
+            out.push_str("\nNot collected:\n");
+            for f in failures {
+                out.push_str(&format!("  {f}\n"));
+            }
+        }
+        out
+    }
+}
+
+fn size(bytes: i64) -> String {
+    format_utils::format_bytes(bytes.max(0) as u64, 2, false)
+}
+
+fn http_get(target: Option<&BeTarget>, endpoint: &str) -> Result<String> {
+    let body = match target {
+        Some(t) => be_http_client::request_be_target(t, endpoint, None),
+        None => be_http_client::request_be_webserver_port(endpoint, None),
+    }?;
+    if body.trim().is_empty() {
+        return Err(CliError::ToolExecutionFailed(format!(
+            "{endpoint} returned an empty response"
+        )));
+    }
+    Ok(body)
+}
+
+fn local_pid_or_err(pid: Option<u32>) -> Result<u32> {
+    pid.ok_or_else(|| CliError::ProcessNotFound("no local doris_be process to inspect".to_string()))
+}
+
+fn top_threads(pid: Option<u32>) -> Result<String> {
+    let pid = local_pid_or_err(pid)?;
+    let mut command = Command::new("top");
+    command.args(["-H", "-b", "-n1", "-p", &pid.to_string()]);
+    let output = executor::execute_command(&mut command, "top")?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn proc_status(pid: Option<u32>) -> Result<String> {
+    let pid = local_pid_or_err(pid)?;
+    let path = format!("/proc/{pid}/status");
+    fs::read_to_string(&path)
+        .map_err(|e| CliError::ToolExecutionFailed(format!("cannot read {path}: {e}")))
+}
+
+/// Capture pipeline tasks, memory trackers, scanner/memory vars, CPU threads
+/// and process status of one BE at the same moment
+pub struct BeSnapshotTool;
+
+impl Tool for BeSnapshotTool {
+    fn name(&self) -> &str {
+        "be-snapshot"
+    }
+
+    fn description(&self) -> &str {
+        "One-shot snapshot of running queries, memory and CPU on a BE"
+    }
+
+    /// The HTTP parts work against any BE; CPU and status need a local doris_be
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let target = match be_http_client::select_be_targets()? {
+            BeSelection::Auto => None,
+            BeSelection::One(t) => Some(t),
+            BeSelection::All(targets) => {
+                let first = targets.into_iter().next();
+                if let Some(t) = &first {
+                    ui::print_info(&format!(
+                        "A snapshot covers one BE at a time; capturing {t}"
+                    ));
+                }
+                first
+            }
+        };
+        let pid = process_detector::get_pid_by_env(Environment::BE).ok();
+
+        ui::print_info("Capturing BE snapshot...");
+        let target_ref = target.as_ref();
+        let snapshot = thread::scope(|s| {
+            let pipeline =
+                s.spawn(|| fetch_dump(target_ref, http_get(target_ref, PIPELINE_ENDPOINT)));
+            let mem_tracker = s.spawn(|| http_get(target_ref, MEM_TRACKER_ENDPOINT));
+            let varz = s.spawn(|| http_get(target_ref, VARZ_ENDPOINT).map(|v| filter_varz(&v)));
+            let top = s.spawn(|| top_threads(pid));
+            let status = s.spawn(|| proc_status(pid));
+            let join = |h: thread::ScopedJoinHandle<'_, Result<String>>| {
+                h.join().unwrap_or_else(|_| {
+                    Err(CliError::ToolExecutionFailed(
+                        "collector panicked".to_string(),
+                    ))
+                })
+            };
+            Snapshot {
+                target: target_ref
+                    .map_or_else(|| "first reachable BE".to_string(), |t| t.to_string()),
+                pid,
+                pipeline: Part {
+                    file: "pipeline_tasks.txt",
+                    result: join(pipeline),
+                },
+                mem_tracker: Part {
+                    file: "mem_tracker.html",
+                    result: join(mem_tracker),
+                },
+                varz: Part {
+                    file: "varz_scanner_memory.txt",
+                    result: join(varz),
+                },
+                top: Part {
+                    file: "top_threads.txt",
+                    result: join(top),
+                },
+                status: Part {
+                    file: "proc_status.txt",
+                    result: join(status),
+                },
+            }
+        });
+
+        let prefix = match &target {
+            Some(t) => format!("be_snapshot_{}", t.file_suffix()),
+            None => "be_snapshot".to_string(),
+        };
+        let dir_name = file_name::timestamped(&prefix, "");
+        let dir = config.artifact_path(&dir_name);
+        if !config.stdout_only {
+            fs::create_dir_all(&dir).map_err(|source| CliError::OutputWriteFailed {
+                path: dir.clone(),
+                source,
+            })?;
+        }
+        for part in snapshot.parts() {
+            if let Ok(content) = &part.result {
+                fs_utils::write_artifact(
+                    config,
+                    &format!("{dir_name}/{}", part.file),
+                    content.as_bytes(),
+                )?;
+            }
+        }
+        let digest = snapshot.digest();
+        fs_utils::write_artifact(config, &format!("{dir_name}/digest.txt"), digest.as_bytes())?;
+
+        println!();
+        for line in digest.lines() {
+            ui::print_info(line);
+        }
+
+        let total = snapshot.parts().len();
+        let message = format!(
+            "BE snapshot captured ({}/{total} parts)",
+            total - snapshot.failures().len()
+        );
+        if config.stdout_only {
+            Ok(ExecutionResult::console(message))
+        } else {
+            Ok(ExecutionResult::new(dir, message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digests_top_threads_and_varz() {
+        let top = "top - 10:01:02 up 3 days,  2:11,  1 user,  load average: 12.50, 9.80, 7.10\n\
+Threads: 812 total,   9 running, 803 sleeping,   0 stopped,   0 zombie\n\
+\n\
+    PID USER      PR  NI    VIRT    RES    SHR S  %CPU  %MEM     TIME+ COMMAND\n\
+  41233 doris     20   0   60.1g  20.3g  88.2m R  98.7  32.1   1:02.33 Pipe_normal [wo\n\
+  41201 doris     20   0   60.1g  20.3g  88.2m S   3.0  32.1   0:10.00 brpc_light\n\
+  41240 doris     20   0   60.1g  20.3g  88.2m R  45,5  32.1   0:50.12 Scan_normal\n";
+        let (threads, load) = parse_top_threads(top);
+        assert_eq!(load.as_deref(), Some("12.50, 9.80, 7.10"));
+        assert_eq!(
+            threads.iter().map(|t| t.tid).collect::<Vec<_>>(),
+            [41233, 41240, 41201]
+        );
+        assert_eq!(threads[0].name, "Pipe_normal [wo");
+        assert_eq!(threads[1].cpu, 45.5);
+        assert_eq!(parse_top_threads("no header"), (Vec::new(), None));
+
+        let varz = "doris_scanner_thread_pool_thread_num=48\nmem_limit=90%\nbe_port=9060\n";
+        assert_eq!(
+            filter_varz(varz),
+            "doris_scanner_thread_pool_thread_num=48\nmem_limit=90%"
+        );
+
+        let snapshot = Snapshot {
+            target: "10.0.0.5:8040".to_string(),
+            pid: None,
+            pipeline: Part {
+                file: "pipeline_tasks.txt",
+                result: Err(CliError::ToolExecutionFailed("timed out".to_string())),
+            },
+            mem_tracker: Part {
+                file: "mem_tracker.html",
+                result: Ok(r#"[{"type":"query","label":"Query#Id=1","limit":"-1","current_consumption":"512.00 MB","peak_consumption":"1.00 GB"}]"#.to_string()),
+            },
+            varz: Part {
+                file: "varz_scanner_memory.txt",
+                result: Ok(String::new()),
+            },
+            top: Part {
+                file: "top_threads.txt",
+                result: Ok(top.to_string()),
+            },
+            status: Part {
+                file: "proc_status.txt",
+                result: Ok("Name:\tdoris_be\nState:\tS (sleeping)\nThreads:\t812\n".to_string()),
+            },
+        };
+        let digest = snapshot.digest();
+        assert!(digest.contains("Running queries: unavailable"));
+        assert!(digest.contains("512.00 MB  Query#Id=1 [query]"));
+        assert!(digest.contains("Load average: 12.50, 9.80, 7.10"));
+        assert!(digest.contains("Process: State: S (sleeping), Threads: 812"));
+        assert!(digest.contains("Not collected:\n  pipeline_tasks.txt: "));
+    }
+}
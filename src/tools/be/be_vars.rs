@@ -1,10 +1,17 @@
-use super::BeResponseHandler;
-use super::be_http_client;
+use super::be_http_client::{self, BeTarget};
 use crate::config::Config;
 use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
 use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
 use dialoguer::{Input, theme::ColorfulTheme};
+use std::collections::BTreeMap;
+
+/// Prefixes with fewer variables are folded into "other"
+const MIN_GROUP_SIZE: usize = 3;
+const OTHER_GROUP: &str = "other";
+const DONE: &str = "Done";
 
 /// Tool to query BE configuration variables
 pub struct BeVarsTool;
@@ -18,26 +25,27 @@ impl Tool for BeVarsTool {
         "Query BE configuration variables"
     }
 
-    fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
-        let variable_name = prompt_for_variable_name()?;
-        if variable_name.is_empty() {
-            return Err(CliError::GracefulExit);
-        }
-
-        ui::print_info(&format!(
-            "Querying BE for variables matching: '{variable_name}'"
-        ));
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let keyword = prompt_for_keyword()?;
+        let selection = be_http_client::select_be_targets()?;
 
-        let result = be_http_client::request_be_webserver_port("/varz", Some(&variable_name));
-
-        let handler = BeResponseHandler {
-            success_message: "Query completed!",
-            empty_warning: "No variables found matching '{}'.",
-            error_context: "Failed to query BE",
-            tips: "Ensure the BE service is running and accessible.",
-        };
+        match &keyword {
+            Some(k) => ui::print_info(&format!("Querying BE for variables matching: '{k}'")),
+            None => ui::print_info("Querying all BE variables..."),
+        }
 
-        handler.handle_console_result(result, &variable_name)
+        // The full dump is always saved; the keyword only narrows the console view
+        let result =
+            be_http_client::run_on_selection(&selection, "/varz", None, |target, result| {
+                show_vars(config, target, result, keyword.as_deref())
+            })?;
+        Ok(match &keyword {
+            Some(k) => ExecutionResult {
+                message: format!("{} (filter: '{k}')", result.message),
+                ..result
+            },
+            None => result,
+        })
     }
 
     fn requires_pid(&self) -> bool {
@@ -45,16 +53,158 @@ impl Tool for BeVarsTool {
     }
 }
 
-fn prompt_for_variable_name() -> Result<String> {
+fn prompt_for_keyword() -> Result<Option<String>> {
     let input: String = Input::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enter BE variable name to query (or part of it)")
+        .with_prompt("Filter variables by keyword (empty to browse by section)")
+        .allow_empty(true)
         .interact_text()
-        .map_err(|e| CliError::InvalidInput(format!("Variable name input failed: {e}")))?;
+        .map_err(|e| CliError::prompt_failed("Variable name input failed", e))?;
+    let input = input.trim();
+    Ok((!input.is_empty()).then(|| input.to_string()))
+}
+
+fn show_vars(
+    config: &Config,
+    target: Option<&BeTarget>,
+    result: Result<String>,
+    keyword: Option<&str>,
+) -> Result<ExecutionResult> {
+    let content = result.inspect_err(|e| {
+        ui::print_error(&format!("Failed to query BE: {e}."));
+        ui::print_info("Tips: Ensure the BE service is running and accessible.");
+    })?;
+    let file_name = match target {
+        Some(t) => file_name::timestamped(&format!("be_vars_{}", t.file_suffix()), "txt"),
+        None => file_name::timestamped("be_vars", "txt"),
+    };
+    let output_path = fs_utils::write_artifact(config, &file_name, content.as_bytes())?;
+
+    let total = content.lines().filter(|l| !l.trim().is_empty()).count();
+    match keyword {
+        Some(k) => {
+            let matched = be_http_client::filter_lines(&content, k);
+            println!();
+            if matched.is_empty() {
+                ui::print_warning(&format!("No variables found matching '{k}'."));
+            } else {
+                println!("{matched}");
+            }
+            Ok(ExecutionResult::new(
+                output_path,
+                format!("{} of {total} variable(s) matched", matched.lines().count()),
+            ))
+        }
+        None => {
+            browse_groups(&group_by_prefix(&content))?;
+            Ok(ExecutionResult::new(
+                output_path,
+                format!("{total} BE variable(s) saved"),
+            ))
+        }
+    }
+}
+
+/// `name=value` lines grouped by the part of the name before its first `_`
+pub fn group_by_prefix(content: &str) -> BTreeMap<String, Vec<String>> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let name = line.split('=').next().unwrap_or(line).trim();
+        let prefix = name.split('_').next().unwrap_or(name).to_ascii_lowercase();
+        groups.entry(prefix).or_default().push(line.to_string());
+    }
+
+    let small: Vec<String> = groups
+        .iter()
+        .filter(|(prefix, lines)| lines.len() < MIN_GROUP_SIZE && *prefix != OTHER_GROUP)
+        .map(|(prefix, _)| prefix.clone())
+        .collect();
+    for prefix in small {
+        if let Some(lines) = groups.remove(&prefix) {
+            groups
+                .entry(OTHER_GROUP.to_string())
+                .or_default()
+                .extend(lines);
+        }
+    }
+    groups
+}
+
+/// Section headers with counts; picking one expands it
+fn browse_groups(groups: &BTreeMap<String, Vec<String>>) -> Result<()> {
+    if groups.is_empty() {
+        ui::print_warning("BE returned no variables.");
+        return Ok(());
+    }
+    let mut items: Vec<String> = groups
+        .iter()
+        .map(|(prefix, lines)| {
+            let label = if prefix == OTHER_GROUP {
+                prefix.clone()
+            } else {
+                format!("{prefix}_*")
+            };
+            format!("▸ {label} ({})", lines.len())
+        })
+        .collect();
+    items.push(DONE.to_string());
+
+    println!();
+    for item in &items[..items.len() - 1] {
+        ui::print_info(item);
+    }
+
+    let mut last = 0;
+    loop {
+        let selector = ui::InteractiveSelector::new(items.clone(), "Expand section:".to_string())
+            .with_default(last);
+        let picked = match selector.select() {
+            Ok(picked) => picked.clone(),
+            Err(CliError::GracefulExit) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let Some(index) = items.iter().position(|i| *i == picked) else {
+            return Ok(());
+        };
+        let Some(lines) = groups.values().nth(index) else {
+            return Ok(());
+        };
+        last = index;
+        println!();
+        ui::print_info(&format!("▾ {}", picked.trim_start_matches("▸ ")));
+        for line in lines {
+            println!("  {line}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_vars_by_prefix_and_folds_small_groups() {
+        let content = "compaction_task_num_per_disk=4
+compaction_promotion_size_mbytes=1024
+max_compaction_threads=10
+storage_root_path=/data1
+storage_flood_stage_usage_percent=90
+storage_min_left_capacity_bytes=2147483648
+compaction_policy=size_based
+
+be_port=9060
+";
+        let groups = group_by_prefix(content);
+        let keys: Vec<&str> = groups.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["compaction", "other", "storage"]);
+        assert_eq!(groups["compaction"].len(), 3);
+        assert_eq!(
+            groups["other"],
+            ["be_port=9060", "max_compaction_threads=10"]
+        );
 
-    if input.trim().is_empty() {
-        ui::print_warning("Variable name cannot be empty!");
-        Ok("".to_string())
-    } else {
-        Ok(input)
+        assert_eq!(
+            be_http_client::filter_lines(content, "compaction_p"),
+            "compaction_promotion_size_mbytes=1024\ncompaction_policy=size_based"
+        );
     }
 }
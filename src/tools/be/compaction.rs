@@ -0,0 +1,413 @@
+use chrono::Local;
+use console::style;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use super::BeResponseHandler;
+use super::be_http_client::{self, BeSelection, BeTarget};
+use crate::config::Config;
+use crate::core::interrupt;
+use crate::error::Result;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::InputHelper;
+
+const RUN_STATUS_ENDPOINT: &str = "/api/compaction/run_status";
+const SCORES_ENDPOINT: &str = "/api/compaction_score?top_n=20";
+const METRICS_ENDPOINT: &str = "/metrics";
+/// Tablets above this compaction score are highlighted
+const SCORE_ALERT: u64 = 100;
+const WATCH_DURATION: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompactionMetrics {
+    pub used_permits: Option<u64>,
+    pub waiting_permits: Option<u64>,
+    pub max_cumulative_score: Option<u64>,
+    pub max_base_score: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabletScore {
+    pub tablet_id: String,
+    pub score: u64,
+}
+
+/// One poll of a BE
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactionSnapshot {
+    /// Running tablet ids per compaction kind, e.g. `CumulativeCompaction`
+    pub running: BTreeMap<String, Vec<String>>,
+    pub metrics: CompactionMetrics,
+    /// Highest score first; empty when the BE has no score endpoint
+    pub top_tablets: Vec<TabletScore>,
+}
+
+impl CompactionSnapshot {
+    fn running_count(&self, kind: &str) -> usize {
+        self.running.get(kind).map_or(0, Vec::len)
+    }
+
+    fn top_score_sum(&self) -> u64 {
+        self.top_tablets.iter().map(|t| t.score).sum()
+    }
+}
+
+/// Running compactions by kind; both the per-disk map and a flat list of ids are accepted
+pub fn parse_run_status(body: &str) -> BTreeMap<String, Vec<String>> {
+    let Ok(Value::Object(root)) = serde_json::from_str::<Value>(body) else {
+        return BTreeMap::new();
+    };
+    let mut running = BTreeMap::new();
+    for (kind, value) in root {
+        let mut tablets = Vec::new();
+        match value {
+            Value::Object(disks) => {
+                for ids in disks.values() {
+                    tablets.extend(json_ids(ids));
+                }
+            }
+            Value::Array(_) => tablets.extend(json_ids(&value)),
+            _ => continue,
+        }
+        running.insert(kind, tablets);
+    }
+    running
+}
+
+fn json_ids(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|ids| {
+            ids.iter()
+                .map(|id| match id {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Permit and max-score gauges from the BE `/metrics` page
+pub fn parse_metrics(body: &str) -> CompactionMetrics {
+    let mut metrics = CompactionMetrics::default();
+    for line in body.lines().filter(|l| !l.starts_with('#')) {
+        let Some((name, value)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let name = name.split('{').next().unwrap_or(name).trim();
+        let Ok(value) = value.trim().parse::<f64>() else {
+            continue;
+        };
+        let slot = match name {
+            "doris_be_compaction_used_permits" => &mut metrics.used_permits,
+            "doris_be_compaction_waitting_permits" | "doris_be_compaction_waiting_permits" => {
+                &mut metrics.waiting_permits
+            }
+            "doris_be_tablet_cumulative_max_compaction_score" => &mut metrics.max_cumulative_score,
+            "doris_be_tablet_base_max_compaction_score" => &mut metrics.max_base_score,
+            _ => continue,
+        };
+        *slot = Some(value as u64);
+    }
+    metrics
+}
+
+/// `[{"tablet_id": "10005", "compaction_score": "312"}, ...]`, highest score first
+pub fn parse_tablet_scores(body: &str) -> Vec<TabletScore> {
+    let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(body) else {
+        return Vec::new();
+    };
+    let number = |v: Option<&Value>| match v? {
+        Value::Number(n) => n.as_f64().map(|f| f as u64),
+        Value::String(s) => s.trim().parse::<f64>().ok().map(|f| f as u64),
+        _ => None,
+    };
+    let mut scores: Vec<TabletScore> = entries
+        .iter()
+        .filter_map(|e| {
+            Some(TabletScore {
+                tablet_id: match e.get("tablet_id")? {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                },
+                score: number(e.get("compaction_score"))?,
+            })
+        })
+        .collect();
+    scores.sort_by_key(|t| std::cmp::Reverse(t.score));
+    scores
+}
+
+fn optional(value: Option<u64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |v| v.to_string())
+}
+
+/// Report lines, each paired with whether it is over the score threshold
+pub fn render_report(snapshot: &CompactionSnapshot) -> Vec<(String, bool)> {
+    let mut lines = vec![("Compaction run status".to_string(), false)];
+    if snapshot.running.is_empty() {
+        lines.push(("  No run status reported".to_string(), false));
+    }
+    for (kind, tablets) in &snapshot.running {
+        let ids = if tablets.is_empty() {
+            String::new()
+        } else {
+            format!(" (tablets: {})", tablets.join(", "))
+        };
+        lines.push((format!("  {kind}: {} running{ids}", tablets.len()), false));
+    }
+    let m = &snapshot.metrics;
+    lines.push((
+        format!(
+            "  Permits: {} used, {} waiting",
+            optional(m.used_permits),
+            optional(m.waiting_permits)
+        ),
+        false,
+    ));
+    let max_flagged = [m.max_cumulative_score, m.max_base_score]
+        .iter()
+        .any(|s| s.is_some_and(|s| s > SCORE_ALERT));
+    lines.push((
+        format!(
+            "  Max score: cumulative {}, base {}",
+            optional(m.max_cumulative_score),
+            optional(m.max_base_score)
+        ),
+        max_flagged,
+    ));
+
+    lines.push((String::new(), false));
+    if snapshot.top_tablets.is_empty() {
+        lines.push((
+            "Per-tablet scores unavailable (this BE has no /api/compaction_score)".to_string(),
+            false,
+        ));
+        return lines;
+    }
+    lines.push((
+        format!(
+            "Top {} tablets by compaction score",
+            snapshot.top_tablets.len()
+        ),
+        false,
+    ));
+    lines.push((format!("  {:<20} {:>8}", "TabletId", "Score"), false));
+    for tablet in &snapshot.top_tablets {
+        lines.push((
+            format!("  {:<20} {:>8}", tablet.tablet_id, tablet.score),
+            tablet.score > SCORE_ALERT,
+        ));
+    }
+    lines
+}
+
+fn signed(now: u64, before: u64) -> String {
+    format!("{:+}", now as i64 - before as i64)
+}
+
+/// One watch line comparing `now` with the previous poll
+pub fn render_delta(before: &CompactionSnapshot, now: &CompactionSnapshot) -> String {
+    let score = |s: &CompactionSnapshot| {
+        (
+            s.metrics.max_cumulative_score.unwrap_or(0),
+            s.metrics.max_base_score.unwrap_or(0),
+        )
+    };
+    let (cumu, base) = score(now);
+    let (prev_cumu, prev_base) = score(before);
+    let mut line = format!(
+        "running cumu {} base {} | max score cumu {cumu} ({}) base {base} ({})",
+        now.running_count("CumulativeCompaction"),
+        now.running_count("BaseCompaction"),
+        signed(cumu, prev_cumu),
+        signed(base, prev_base)
+    );
+    if !now.top_tablets.is_empty() {
+        line.push_str(&format!(
+            " | top-{} sum {} ({})",
+            now.top_tablets.len(),
+            now.top_score_sum(),
+            signed(now.top_score_sum(), before.top_score_sum())
+        ));
+    }
+    line
+}
+
+fn fetch(target: Option<&BeTarget>, endpoint: &str) -> Result<String> {
+    match target {
+        Some(t) => be_http_client::request_be_target(t, endpoint, None),
+        None => be_http_client::request_be_webserver_port(endpoint, None),
+    }
+}
+
+/// The score and metrics endpoints are best effort; only run status is required
+fn snapshot_from(target: Option<&BeTarget>, run_status: &str) -> CompactionSnapshot {
+    CompactionSnapshot {
+        running: parse_run_status(run_status),
+        metrics: fetch(target, METRICS_ENDPOINT)
+            .map(|b| parse_metrics(&b))
+            .unwrap_or_default(),
+        top_tablets: fetch(target, SCORES_ENDPOINT)
+            .map(|b| parse_tablet_scores(&b))
+            .unwrap_or_default(),
+    }
+}
+
+fn print_lines(lines: &[(String, bool)]) -> String {
+    lines
+        .iter()
+        .map(|(line, flagged)| {
+            if *flagged {
+                style(line).red().to_string()
+            } else {
+                line.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Poll `target` every `interval` for [`WATCH_DURATION`] or until Ctrl-C
+fn watch(target: Option<&BeTarget>, first: CompactionSnapshot, interval: Duration) -> Result<()> {
+    ui::print_info(&format!(
+        "Polling every {}s for {} minutes; press Ctrl-C to stop.",
+        interval.as_secs(),
+        WATCH_DURATION.as_secs() / 60
+    ));
+    interrupt::catching(|| {
+        let started = Instant::now();
+        let mut last = first.clone();
+        while started.elapsed() < WATCH_DURATION {
+            if !interrupt::sleep_unless_interrupted(interval) {
+                ui::print_info("Stopped watching.");
+                return Ok(());
+            }
+            let now = snapshot_from(target, &fetch(target, RUN_STATUS_ENDPOINT)?);
+            ui::print_info(&format!(
+                "{} {}",
+                Local::now().format("%H:%M:%S"),
+                render_delta(&last, &now)
+            ));
+            last = now;
+        }
+        ui::print_info(&format!("Since start: {}", render_delta(&first, &last)));
+        Ok(())
+    })
+}
+
+/// Tool to inspect BE compaction progress and backlog
+pub struct BeCompactionTool;
+
+impl Tool for BeCompactionTool {
+    fn name(&self) -> &str {
+        "be-compaction"
+    }
+
+    fn description(&self) -> &str {
+        "Show running compactions and the tablets with the highest compaction score"
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let selection = be_http_client::select_be_targets()?;
+        ui::print_info("Fetching compaction status from BE...");
+
+        let handler = BeResponseHandler {
+            success_message: "Compaction status fetched successfully!",
+            empty_warning: "BE returned no compaction status.",
+            error_context: "Failed to fetch compaction status",
+            tips: "Ensure the BE service is running and accessible.",
+        };
+
+        let mut last = None;
+        let result = be_http_client::run_on_selection(
+            &selection,
+            RUN_STATUS_ENDPOINT,
+            None,
+            |target, result| {
+                let Ok(body) = &result else {
+                    return handler.handle_console_result(result, "compaction status");
+                };
+                let snapshot = snapshot_from(target, body);
+                let lines = render_report(&snapshot);
+                let report: String = lines.iter().map(|(l, _)| format!("{l}\n")).collect();
+                let prefix = match target {
+                    Some(t) => format!("be_compaction_{}", t.file_suffix()),
+                    None => "be_compaction".to_string(),
+                };
+                last = Some((target.cloned(), snapshot));
+                let combined = format!("{report}\n===== run_status =====\n{body}");
+                handler.handle_file_result(config, Ok(combined), &prefix, |_| print_lines(&lines))
+            },
+        )?;
+
+        // Watching several backends at once would interleave their deltas
+        if !matches!(selection, BeSelection::All(_))
+            && let Some((target, snapshot)) = last
+            && ui::dialogs::select_index("Next", &["Watch score changes", "Done"])? == 0
+        {
+            let secs = InputHelper::prompt_duration_secs("Poll interval", 10, 300)?;
+            watch(
+                target.as_ref(),
+                snapshot,
+                Duration::from_secs(u64::from(secs)),
+            )?;
+        }
+        Ok(result)
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_status_metrics_and_scores() {
+        let run_status = r#"{
+            "CumulativeCompaction": {"/data1": ["10001", "10002"], "/data2": []},
+            "BaseCompaction": {"/data1": ["10004"]}
+        }"#;
+        let metrics = "# TYPE doris_be_compaction_used_permits gauge
+doris_be_compaction_used_permits 12
+doris_be_compaction_waitting_permits 3
+doris_be_tablet_cumulative_max_compaction_score{type=\"cumulative\"} 312
+doris_be_tablet_base_max_compaction_score 4
+";
+        let scores = r#"[{"tablet_id": "10009", "compaction_score": "87"},
+                         {"tablet_id": 10005, "compaction_score": 312}]"#;
+
+        let before = CompactionSnapshot {
+            running: parse_run_status(run_status),
+            metrics: parse_metrics(metrics),
+            top_tablets: parse_tablet_scores(scores),
+        };
+        assert_eq!(before.running_count("CumulativeCompaction"), 2);
+        assert_eq!(before.metrics.used_permits, Some(12));
+        assert_eq!(before.metrics.waiting_permits, Some(3));
+        assert_eq!(before.top_tablets[0].tablet_id, "10005");
+
+        let lines = render_report(&before);
+        let flagged: Vec<&str> = lines
+            .iter()
+            .filter(|(_, f)| *f)
+            .map(|(l, _)| l.trim())
+            .collect();
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged[1].starts_with("10005"));
+
+        let mut after = before.clone();
+        after.metrics.max_cumulative_score = Some(250);
+        after.top_tablets[0].score = 250;
+        assert_eq!(
+            render_delta(&before, &after),
+            "running cumu 2 base 1 | max score cumu 250 (-62) base 4 (+0) | top-2 sum 337 (-62)"
+        );
+    }
+}
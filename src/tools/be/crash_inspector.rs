@@ -0,0 +1,350 @@
+use chrono::{DateTime, Local};
+use console::style;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::format_utils;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+const TAIL_LINES: usize = 500;
+
+/// Markers glog and the BE signal handler write to be.out on a crash
+const CRASH_MARKERS: &[&str] = &[
+    "*** Aborted at",
+    "*** SIG",
+    "Segmentation fault",
+    "Aborted",
+    "*** Check failure stack trace",
+];
+
+/// A crash-related file found on disk
+#[derive(Debug, Clone)]
+pub struct CrashArtifact {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub readable: bool,
+}
+
+/// Crash markers found in be.out
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BeOutSummary {
+    /// `*** Aborted at` blocks from the BE failure signal handler, one per crash
+    pub crashes: usize,
+    pub aborted: usize,
+    pub segfaults: usize,
+    pub stack_traces: usize,
+    pub last_marker: Option<(usize, String)>,
+}
+
+/// Scan be.out line by line for crash markers
+pub fn summarize_be_out<R: BufRead>(reader: R) -> BeOutSummary {
+    let mut summary = BeOutSummary::default();
+    // The `*** SIGxxx ... received by PID` line that follows an abort header is the same crash
+    let mut in_abort_block = false;
+    for (idx, line) in reader.lines().map_while(|l| l.ok()).enumerate() {
+        let Some(marker) = CRASH_MARKERS.iter().find(|m| line.contains(*m)) else {
+            continue;
+        };
+        match *marker {
+            "*** Aborted at" => {
+                summary.crashes += 1;
+                in_abort_block = true;
+            }
+            "*** SIG" if in_abort_block => in_abort_block = false,
+            "*** SIG" => summary.crashes += 1,
+            "Segmentation fault" => summary.segfaults += 1,
+            "Aborted" => summary.aborted += 1,
+            _ => summary.stack_traces += 1,
+        }
+        summary.last_marker = Some((idx + 1, line.trim().to_string()));
+    }
+    summary
+}
+
+pub struct BeCrashInspectorTool;
+
+impl Tool for BeCrashInspectorTool {
+    fn name(&self) -> &str {
+        "be-crash-inspector"
+    }
+
+    fn description(&self) -> &str {
+        "Inspect be.out, core files and minidumps after a BE crash"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let install_dir = doris
+            .be_install_dir
+            .clone()
+            .unwrap_or_else(|| doris.install_dir.clone());
+        let log_dir = install_dir.join("log");
+        let be_out = log_dir.join("be.out");
+
+        ui::print_info(&format!(
+            "Inspecting BE crash artifacts under {}",
+            install_dir.display()
+        ));
+
+        let summary = match fs::File::open(&be_out) {
+            Ok(f) => Some(summarize_be_out(BufReader::new(f))),
+            Err(e) => {
+                ui::print_warning(&format!("Cannot read {}: {e}", be_out.display()));
+                None
+            }
+        };
+        let cores = find_core_files(&[install_dir.clone(), install_dir.join("bin")]);
+        let minidumps = find_minidumps(&log_dir);
+
+        display_report(&be_out, summary.as_ref(), &cores, &minidumps);
+
+        if summary.is_none() && minidumps.is_empty() {
+            return Ok(console_result("No crash artifacts to collect"));
+        }
+        if config.stdout_only {
+            ui::print_info("Stdout-only mode: skipping artifact copy.");
+            return Ok(console_result("Crash inspection completed"));
+        }
+        if !ui::ask_continue("Copy the be.out tail and newest minidump to the output directory?")? {
+            return Ok(console_result("Crash inspection completed"));
+        }
+
+        config.ensure_output_dir()?;
+        let timestamp = file_name::timestamp();
+        let mut copied = Vec::new();
+
+        if summary.is_some() {
+            let tail_path =
+                config.artifact_path(&file_name::timestamped_at("be_out_tail", &timestamp, "log"));
+            write_tail(&be_out, &tail_path, TAIL_LINES)?;
+            copied.push(tail_path);
+        }
+        if let Some(newest) = minidumps
+            .iter()
+            .filter(|m| m.readable)
+            .max_by_key(|m| m.modified)
+        {
+            let file_name = newest
+                .path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("minidump.dmp");
+            let dest = config.artifact_path(&format!("{timestamp}_{file_name}"));
+            fs::copy(&newest.path, &dest).map_err(CliError::IoError)?;
+            copied.push(dest);
+        }
+
+        for path in &copied {
+            ui::print_info(&format!("Copied: {}", path.display()));
+        }
+
+        Ok(ExecutionResult::new(
+            copied
+                .first()
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from(crate::tools::CONSOLE_OUTPUT)),
+            format!(
+                "Crash inspection completed, {} file(s) copied",
+                copied.len()
+            ),
+        ))
+    }
+}
+
+fn console_result(message: &str) -> ExecutionResult {
+    ExecutionResult::console(message)
+}
+
+fn artifact_at(path: PathBuf) -> Option<CrashArtifact> {
+    let meta = fs::metadata(&path).ok()?;
+    if !meta.is_file() {
+        return None;
+    }
+    // Core files are often root-owned with mode 0600; report them even when unreadable
+    let readable = fs::File::open(&path).is_ok();
+    Some(CrashArtifact {
+        path,
+        size: meta.len(),
+        modified: meta.modified().ok(),
+        readable,
+    })
+}
+
+fn find_core_files(dirs: &[PathBuf]) -> Vec<CrashArtifact> {
+    let mut found: Vec<CrashArtifact> = dirs
+        .iter()
+        .filter_map(|d| fs::read_dir(d).ok())
+        .flat_map(|entries| entries.filter_map(|e| e.ok()))
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            name == "core" || name.starts_with("core.")
+        })
+        .filter_map(|e| artifact_at(e.path()))
+        .collect();
+    found.sort_by_key(|a| std::cmp::Reverse(a.modified));
+    found
+}
+
+fn find_minidumps(log_dir: &Path) -> Vec<CrashArtifact> {
+    // Minidumps land in log/minidump by default, older layouts put them in log/ directly
+    let mut found: Vec<CrashArtifact> = [log_dir.to_path_buf(), log_dir.join("minidump")]
+        .iter()
+        .filter_map(|d| fs::read_dir(d).ok())
+        .flat_map(|entries| entries.filter_map(|e| e.ok()))
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "dmp"))
+        .filter_map(|e| artifact_at(e.path()))
+        .collect();
+    found.sort_by_key(|a| std::cmp::Reverse(a.modified));
+    found
+}
+
+fn write_tail(src: &Path, dest: &Path, lines: usize) -> Result<()> {
+    let f = fs::File::open(src).map_err(CliError::IoError)?;
+    let mut tail: VecDeque<String> = VecDeque::with_capacity(lines);
+    for line in BufReader::new(f).lines().map_while(|l| l.ok()) {
+        if tail.len() == lines {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+    let mut content = tail.into_iter().collect::<Vec<_>>().join("\n");
+    content.push('\n');
+    fs::write(dest, content).map_err(CliError::IoError)
+}
+
+fn format_mtime(t: Option<SystemTime>) -> String {
+    t.map(|t| {
+        DateTime::<Local>::from(t)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    })
+    .unwrap_or_else(|| "-".to_string())
+}
+
+fn display_artifacts(title: &str, artifacts: &[CrashArtifact]) {
+    ui::print_info("");
+    ui::print_info(&format!("{title}: {}", artifacts.len()));
+    for a in artifacts {
+        let access = if a.readable {
+            String::new()
+        } else {
+            style("  (permission denied, not copied)")
+                .yellow()
+                .to_string()
+        };
+        ui::print_info(&format!(
+            "  {}  {:>10}  {}{access}",
+            format_mtime(a.modified),
+            format_utils::format_bytes(a.size, 1, false),
+            a.path.display()
+        ));
+    }
+}
+
+fn display_report(
+    be_out: &Path,
+    summary: Option<&BeOutSummary>,
+    cores: &[CrashArtifact],
+    minidumps: &[CrashArtifact],
+) {
+    ui::print_info("");
+    ui::print_info(&"=".repeat(80));
+    ui::print_info("BE Crash Inspection");
+    ui::print_info(&"-".repeat(80));
+
+    if let Some(s) = summary {
+        ui::print_info(&format!("be.out: {}", be_out.display()));
+        ui::print_info(&format!(
+            "  Crashes: {}  Aborted: {}  Segmentation fault: {}  Check failure traces: {}",
+            s.crashes, s.aborted, s.segfaults, s.stack_traces
+        ));
+        match &s.last_marker {
+            Some((line_no, line)) => ui::print_info(&format!(
+                "  Last crash marker (line {line_no}): {}",
+                style(line).red()
+            )),
+            None => ui::print_info("  No crash markers found"),
+        }
+    }
+
+    display_artifacts("Core files", cores);
+    display_artifacts("Minidumps", minidumps);
+    ui::print_info(&"=".repeat(80));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn be_out_summary_counts_crash_markers() {
+        let log = "\
+start BE
+*** Aborted at 1712345678 (unix time) try \"date -d @1712345678\" if you are using GNU date ***
+*** SIGSEGV address not mapped to object (@0x0) received by PID 1234 ***
+    @     0x55d0c1a2b3c4 doris::Foo::bar()
+Segmentation fault (core dumped)
+";
+        let summary = summarize_be_out(log.as_bytes());
+        assert_eq!(summary.crashes, 1);
+        assert_eq!(summary.stack_traces, 0);
+        assert_eq!(summary.segfaults, 1);
+        assert_eq!(summary.aborted, 0);
+        assert_eq!(
+            summary.last_marker.as_ref().map(|(n, _)| *n),
+            Some(5),
+            "last marker is the segfault line"
+        );
+
+        assert!(
+            summarize_be_out("all good\n".as_bytes())
+                .last_marker
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn abort_headers_count_as_crashes() {
+        let log = "\
+I20240406 03:14:15.926535 12400 fragment_mgr.cpp:612] exec plan fragment
+*** Query id: 5f3e2a1b9c8d4e7f-a1b2c3d4e5f60718 ***
+*** is nereids: 1 ***
+*** tablet id: 0 ***
+*** Aborted at 1712373255 (unix time) try \"date -d @1712373255\" if you are using GNU date ***
+*** Current BE git commitID: 4d8c0f9f3e ***
+*** SIGSEGV address not mapped to object (@0x0) received by PID 12345 (TID 12400 OR 0x7f1c2b3a4700) from PID 0; stack trace: ***
+ 0# doris::signal::(anonymous namespace)::FailureSignalHandler(int, siginfo_t*, void*) at /root/doris/be/src/common/signal_handler.h:421
+ 1# PosixSignals::chained_handler(int, siginfo*, void*) [clone .part.0] in /usr/lib/jvm/java-17-openjdk-amd64/lib/server/libjvm.so
+ 2# doris::vectorized::ColumnString::insert_from(doris::vectorized::IColumn const&, unsigned long) at /root/doris/be/src/vec/columns/column_string.h:195
+F20240406 03:20:01.000000 13001 tablet.cpp:101] Check failed: rowset != nullptr
+*** Check failure stack trace: ***
+    @     0x55d0c1a2b3c4  google::LogMessage::Fail()
+*** Aborted at 1712373601 (unix time) try \"date -d @1712373601\" if you are using GNU date ***
+*** Current BE git commitID: 4d8c0f9f3e ***
+*** SIGABRT unknown detail explain (@0x3039) received by PID 12345 (TID 13001 OR 0x7f1c2a000700) from PID 12345; stack trace: ***
+";
+        let summary = summarize_be_out(log.as_bytes());
+        assert_eq!(summary.crashes, 2);
+        assert_eq!(summary.stack_traces, 1);
+        assert_eq!(summary.aborted, 0);
+        assert_eq!(summary.segfaults, 0);
+        assert!(
+            summary
+                .last_marker
+                .is_some_and(|(_, line)| line.starts_with("*** SIGABRT"))
+        );
+    }
+}
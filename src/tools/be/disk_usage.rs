@@ -0,0 +1,233 @@
+use console::style;
+
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::{file_name, format_utils, fs_utils};
+use crate::tools::mysql::{Backend, ClusterInfo, MySQLTool};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+const WARN_PCT: f64 = 70.0;
+const CRITICAL_PCT: f64 = 85.0;
+
+/// Cluster-wide capacity summed over all backends
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DiskTotals {
+    pub data_used: u64,
+    pub avail: u64,
+    pub total: u64,
+}
+
+impl DiskTotals {
+    /// Used share of total capacity, including non-Doris data on the disks
+    pub fn used_pct(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.total.saturating_sub(self.avail) as f64 * 100.0 / self.total as f64
+    }
+}
+
+/// Sort backends by used percentage (fullest first) and sum their capacity
+pub fn summarize_disk_usage(backends: &mut [Backend]) -> DiskTotals {
+    backends.sort_by(|a, b| {
+        b.used_pct
+            .unwrap_or(0.0)
+            .total_cmp(&a.used_pct.unwrap_or(0.0))
+    });
+    backends
+        .iter()
+        .fold(DiskTotals::default(), |acc, b| DiskTotals {
+            data_used: acc.data_used + b.data_used_capacity.unwrap_or(0),
+            avail: acc.avail + b.avail_capacity.unwrap_or(0),
+            total: acc.total + b.total_capacity.unwrap_or(0),
+        })
+}
+
+pub struct BeDiskUsageTool;
+
+impl Tool for BeDiskUsageTool {
+    fn name(&self) -> &str {
+        "be-disk-usage"
+    }
+
+    fn description(&self) -> &str {
+        "Per-BE disk usage breakdown from SHOW BACKENDS"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+
+        ui::print_info("Querying backend capacity...");
+        let output = MySQLTool::query_sql_with_config(&doris, "SHOW BACKENDS \\G")?;
+        let mut backends = ClusterInfo::parse_backends_from_output(&output);
+        if backends.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No backends returned by SHOW BACKENDS".to_string(),
+            ));
+        }
+
+        let totals = summarize_disk_usage(&mut backends);
+        let lines = render_table(&backends, &totals);
+
+        ui::print_info("");
+        for (line, pct) in &lines {
+            ui::print_info(&colorize(line, *pct));
+        }
+
+        let content: String = lines.iter().map(|(l, _)| format!("{l}\n")).collect();
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("be_disk_usage", "txt"),
+            content.as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "Disk usage of {} backend(s), cluster {:.1}% used",
+                backends.len(),
+                totals.used_pct()
+            ),
+        ))
+    }
+}
+
+fn size_cell(bytes: Option<u64>) -> String {
+    bytes
+        .map(|b| format_utils::format_bytes(b, 2, false))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn row(cells: [&str; 7]) -> String {
+    let [host, id, alive, used, avail, total, pct] = cells;
+    format!("{host:<24} {id:>10} {alive:>6} {used:>14} {avail:>14} {total:>14} {pct:>8}")
+}
+
+/// Table lines paired with the used percentage that decides their color
+fn render_table(backends: &[Backend], totals: &DiskTotals) -> Vec<(String, Option<f64>)> {
+    let mut lines = vec![
+        (
+            row([
+                "Host",
+                "BackendId",
+                "Alive",
+                "DataUsed",
+                "Avail",
+                "Total",
+                "Used%",
+            ]),
+            None,
+        ),
+        ("-".repeat(96), None),
+    ];
+    for b in backends {
+        let pct = b
+            .used_pct
+            .map(|p| format!("{p:.2}%"))
+            .unwrap_or_else(|| "-".to_string());
+        lines.push((
+            row([
+                &ui::FormatHelper::truncate_string(&b.host, 24),
+                &b.backend_id,
+                if b.alive { "true" } else { "false" },
+                &size_cell(b.data_used_capacity),
+                &size_cell(b.avail_capacity),
+                &size_cell(b.total_capacity),
+                &pct,
+            ]),
+            b.used_pct,
+        ));
+    }
+    lines.push(("-".repeat(96), None));
+    lines.push((
+        row([
+            "Cluster total",
+            "",
+            "",
+            &size_cell(Some(totals.data_used)),
+            &size_cell(Some(totals.avail)),
+            &size_cell(Some(totals.total)),
+            &format!("{:.2}%", totals.used_pct()),
+        ]),
+        Some(totals.used_pct()),
+    ));
+    lines
+}
+
+fn colorize(line: &str, pct: Option<f64>) -> String {
+    match pct {
+        Some(p) if p > CRITICAL_PCT => style(line).red().to_string(),
+        Some(p) if p > WARN_PCT => style(line).yellow().to_string(),
+        _ => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(id: &str, used: &str, avail: &str, total: &str, pct: &str) -> String {
+        format!(
+            "*************************** 1. row ***************************
+        BackendId: {id}
+             Host: 10.0.0.{id}
+    HeartbeatPort: 9050
+           BePort: 9060
+         HttpPort: 8040
+         BrpcPort: 8060
+            Alive: true
+ DataUsedCapacity: {used}
+    AvailCapacity: {avail}
+    TotalCapacity: {total}
+          UsedPct: {pct}
+          Version: doris-2.1.0
+           Status: {{}}
+         NodeRole: mix
+"
+        )
+    }
+
+    #[test]
+    fn sorts_by_used_pct_and_sums_capacity() {
+        let output = [
+            block("1", "100.000 GB", "1.000 TB", "2.000 TB", "50.00 %"),
+            block("2", "1.500 TB", "100.000 GB", "2.000 TB", "95.12 %"),
+        ]
+        .concat();
+        let mut backends = ClusterInfo::parse_backends_from_output(&output);
+        assert_eq!(backends.len(), 2);
+
+        let totals = summarize_disk_usage(&mut backends);
+        assert_eq!(backends[0].backend_id, "2");
+        assert_eq!(backends[0].used_pct, Some(95.12));
+        assert_eq!(totals.total, 4 * 1024u64.pow(4));
+        assert!((totals.used_pct() - 72.5586).abs() < 0.01);
+    }
+
+    #[test]
+    fn clusters_toml_without_capacity_still_loads() {
+        let toml = r#"
+frontends = []
+
+[[backends]]
+backend_id = "1"
+host = "10.0.0.1"
+heartbeat_port = 9050
+be_port = 9060
+http_port = 8040
+brpc_port = 8060
+alive = true
+version = "doris-2.1.0"
+status = "{}"
+node_role = "mix"
+"#;
+        let info: ClusterInfo = toml::from_str(toml).unwrap();
+        assert!(info.backends[0].used_pct.is_none());
+        assert!(info.backends[0].total_capacity.is_none());
+    }
+}
@@ -1,2 +1,2 @@
 // Re-export common jmap tools
-pub use crate::tools::common::jmap::{JmapDumpTool, JmapHistoTool};
+pub use crate::tools::common::jmap::{JmapDumpTool, JmapHistoDiffTool, JmapHistoTool};
@@ -1,19 +1,70 @@
 use crate::config::Config;
+use crate::config_loader::{self, DorisConfig};
 use crate::error::{CliError, Result};
+use crate::tools::mysql::ClusterInfo;
 use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
 
 pub use crate::tools::common::host_selection::{
-    get_selected_host as get_selected_be_host_generic,
-    set_selected_host as set_selected_be_host_generic,
+    get_selected_compute_group, get_selected_host as get_selected_be_host_generic,
+    set_selected_compute_group, set_selected_host as set_selected_be_host_generic,
 };
+
+/// Select `host` for this session and save it as the default for later ones,
+/// together with the current compute group
 pub fn set_selected_be_host(host: String) {
     set_selected_be_host_generic(true, host);
+    match config_loader::load_config() {
+        Ok(doris) => config_loader::persist_configuration(&doris),
+        Err(e) => ui::print_warning(&format!("BE host not saved for later sessions: {e}")),
+    }
 }
+
 pub fn get_selected_be_host() -> Option<String> {
     get_selected_be_host_generic(true)
 }
 
+/// Why a saved selection no longer fits `info`, or `None` while it does
+fn stale_reason(info: &ClusterInfo, host: &str, group: Option<&str>) -> Option<String> {
+    let listed: Vec<_> = info.backends.iter().filter(|b| b.host == host).collect();
+    if listed.is_empty() {
+        return Some("no longer listed in clusters.toml".to_string());
+    }
+    if !listed.iter().any(|b| b.alive) {
+        return Some("not alive".to_string());
+    }
+    if let Some(group) = group
+        && !info.list_be_hosts_in_group(group).iter().any(|h| h == host)
+    {
+        return Some(format!("no longer in compute group {group}"));
+    }
+    None
+}
+
+/// Load the BE host saved by an earlier `be-list` into this session.
+///
+/// A host that is no longer listed or alive in clusters.toml is dropped with a
+/// warning; without cluster info the saved host is kept as is.
+pub fn restore_selected_be_host(doris: &mut DorisConfig) {
+    let Some(host) = doris.be_selected_host.clone() else {
+        return;
+    };
+    let group = doris.be_selected_compute_group.clone();
+    if let Ok(info) = ClusterInfo::load_from_file()
+        && let Some(reason) = stale_reason(&info, &host, group.as_deref())
+    {
+        ui::print_warning(&format!(
+            "Saved BE host {host} dropped: {reason}. Select another one via 'be-list'."
+        ));
+        doris.be_selected_host = None;
+        doris.be_selected_compute_group = None;
+        config_loader::persist_configuration(doris);
+        return;
+    }
+    set_selected_compute_group(group);
+    set_selected_be_host_generic(true, host);
+}
+
 pub struct BeListTool;
 
 impl Tool for BeListTool {
@@ -22,7 +73,7 @@ impl Tool for BeListTool {
     }
 
     fn description(&self) -> &str {
-        "List and select a BE host (IP) for this session"
+        "List and select the default BE host (IP), kept across sessions"
     }
 
     fn requires_pid(&self) -> bool {
@@ -30,30 +81,107 @@ impl Tool for BeListTool {
     }
 
     fn execute(&self, _config: &Config, _pid: u32) -> Result<crate::tools::ExecutionResult> {
-        let info = crate::tools::mysql::ClusterInfo::load_from_file()?;
-        let hosts = info.list_be_hosts();
+        let saved = config_loader::load_config()
+            .ok()
+            .and_then(|doris| doris.be_selected_host);
+        match &saved {
+            Some(host) => ui::print_info(&format!("Saved default BE target: {host}")),
+            None => ui::print_info("No default BE target saved yet"),
+        }
+
+        let info = ClusterInfo::load_from_file()?;
+        let group = super::be_http_client::select_compute_group(&info)?;
+        let hosts = match &group {
+            Some(group) => info.list_be_hosts_in_group(group),
+            None => info.list_be_hosts(),
+        };
         if hosts.is_empty() {
             return Err(CliError::ConfigError(
                 "No BE hosts found in clusters.toml".to_string(),
             ));
         }
 
-        let items: Vec<String> = hosts;
+        let items: Vec<String> = hosts
+            .iter()
+            .map(|h| {
+                if saved.as_ref() == Some(h) {
+                    format!("{h} (saved default)")
+                } else {
+                    h.clone()
+                }
+            })
+            .collect();
+        let default = saved
+            .as_ref()
+            .and_then(|s| hosts.iter().position(|h| h == s))
+            .unwrap_or(0);
 
         let selection = dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
             .with_prompt("Select Backend (BE) host")
             .items(&items)
-            .default(0)
+            .default(default)
             .interact()
-            .map_err(|e| CliError::InvalidInput(format!("BE selection failed: {e}")))?;
+            .map_err(|e| CliError::prompt_failed("BE selection failed", e))?;
 
-        let host = items[selection].clone();
+        let host = hosts[selection].clone();
+        set_selected_compute_group(group);
         set_selected_be_host(host.clone());
         ui::print_success(&format!("Selected BE host: {host}"));
 
-        Ok(ExecutionResult {
-            output_path: std::path::PathBuf::from("console_output"),
-            message: "BE host updated for this session".to_string(),
-        })
+        Ok(ExecutionResult::console(
+            "BE host updated and saved as the default target",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::mysql::Backend;
+
+    fn backend(id: &str, host: &str, alive: bool, tag: Option<&str>) -> Backend {
+        Backend {
+            backend_id: id.to_string(),
+            host: host.to_string(),
+            heartbeat_port: 9050,
+            be_port: 9060,
+            http_port: 8040,
+            brpc_port: 8060,
+            alive,
+            version: "doris-3.0.0".to_string(),
+            status: String::new(),
+            node_role: "mix".to_string(),
+            tag: tag.map(|t| format!(r#"{{"cloud_cluster_name":"{t}"}}"#)),
+            data_used_capacity: None,
+            avail_capacity: None,
+            total_capacity: None,
+            used_pct: None,
+        }
+    }
+
+    #[test]
+    fn saved_selection_is_dropped_once_the_host_is_gone_or_dead() {
+        let info = ClusterInfo::new(
+            Vec::new(),
+            vec![
+                backend("1", "10.0.0.1", true, Some("etl")),
+                backend("2", "10.0.0.2", false, Some("etl")),
+                backend("3", "10.0.0.3", true, Some("adhoc")),
+            ],
+        );
+        assert_eq!(stale_reason(&info, "10.0.0.1", None), None);
+        assert_eq!(stale_reason(&info, "10.0.0.1", Some("etl")), None);
+        assert_eq!(
+            stale_reason(&info, "10.0.0.9", None).as_deref(),
+            Some("no longer listed in clusters.toml")
+        );
+        assert_eq!(
+            stale_reason(&info, "10.0.0.2", None).as_deref(),
+            Some("not alive")
+        );
+        assert_eq!(
+            stale_reason(&info, "10.0.0.3", Some("etl")).as_deref(),
+            Some("no longer in compute group etl")
+        );
     }
 }
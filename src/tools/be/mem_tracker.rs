@@ -0,0 +1,257 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::be_http_client;
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::{file_name, format_utils, fs_utils};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+/// Trackers listed in the console table
+const TOP_TRACKERS: usize = 30;
+const TRACKER_TYPES: &[&str] = &["global", "query", "load", "compaction"];
+
+/// One memory tracker as reported by `/mem_tracker`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerRow {
+    pub kind: String,
+    pub label: String,
+    /// Negative when the tracker has no limit
+    pub limit: i64,
+    pub current: i64,
+    pub peak: i64,
+}
+
+impl TrackerRow {
+    /// Summary rows such as "process resident memory" rather than real trackers
+    fn is_overview(&self) -> bool {
+        self.kind.eq_ignore_ascii_case("overview")
+    }
+}
+
+/// Parse `/mem_tracker`, which is JSON on some BE versions and an HTML table on others
+pub fn parse_mem_tracker(body: &str) -> Vec<TrackerRow> {
+    let records = match body.trim_start().chars().next() {
+        Some('[' | '{') => json_records(body),
+        _ => html_records(body),
+    };
+    records.iter().filter_map(to_row).collect()
+}
+
+/// Lowercase alphanumerics only, so "Current Consumption(Bytes)" matches "current_consumption"
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn json_records(body: &str) -> Vec<HashMap<String, String>> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+    let rows = match &value {
+        serde_json::Value::Array(rows) => rows.as_slice(),
+        serde_json::Value::Object(obj) => obj
+            .values()
+            .find_map(|v| v.as_array())
+            .map_or(&[][..], Vec::as_slice),
+        _ => &[],
+    };
+    rows.iter()
+        .filter_map(|row| row.as_object())
+        .map(|obj| {
+            obj.iter()
+                .map(|(k, v)| {
+                    let text = match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (normalize_key(k), text)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn html_records(body: &str) -> Vec<HashMap<String, String>> {
+    let row_re = Regex::new(r"(?is)<tr[^>]*>(.*?)</tr>").unwrap();
+    let cell_re = Regex::new(r"(?is)<t[hd][^>]*>(.*?)</t[hd]>").unwrap();
+    let tag_re = Regex::new(r"(?s)<[^>]*>").unwrap();
+
+    let mut header: Option<Vec<String>> = None;
+    let mut records = Vec::new();
+    for row in row_re.captures_iter(body) {
+        let cells: Vec<String> = cell_re
+            .captures_iter(&row[1])
+            .map(|c| tag_re.replace_all(&c[1], "").trim().to_string())
+            .collect();
+        match &header {
+            None if row[1].to_ascii_lowercase().contains("<th") => {
+                header = Some(cells.iter().map(|c| normalize_key(c)).collect());
+            }
+            Some(names) if cells.len() == names.len() => {
+                records.push(names.iter().cloned().zip(cells).collect());
+            }
+            _ => {}
+        }
+    }
+    records
+}
+
+/// Byte value of the first column starting with `prefix`, preferring exact byte counts
+fn bytes_field(record: &HashMap<String, String>, prefix: &str) -> Option<i64> {
+    let mut keys: Vec<&String> = record.keys().filter(|k| k.starts_with(prefix)).collect();
+    keys.sort_by_key(|k| (!k.ends_with("bytes"), k.len()));
+    keys.into_iter().find_map(|k| {
+        let v = record[k].trim();
+        v.parse::<i64>().ok().or_else(|| {
+            let size = format_utils::parse_size(v);
+            (size > 0 || v.starts_with('0')).then_some(size as i64)
+        })
+    })
+}
+
+fn to_row(record: &HashMap<String, String>) -> Option<TrackerRow> {
+    let label = record.get("label").filter(|l| !l.is_empty())?.clone();
+    Some(TrackerRow {
+        kind: record.get("type").cloned().unwrap_or_default(),
+        label,
+        limit: bytes_field(record, "limit").unwrap_or(-1),
+        current: bytes_field(record, "current")?,
+        peak: bytes_field(record, "peak").unwrap_or(0),
+    })
+}
+
+/// Process-level consumption: the largest overview row naming the process
+pub fn process_total(rows: &[TrackerRow]) -> Option<i64> {
+    rows.iter()
+        .filter(|r| r.is_overview() || r.label.to_ascii_lowercase().starts_with("process"))
+        .map(|r| r.current)
+        .max()
+}
+
+/// Real trackers sorted by current consumption, biggest first
+pub fn top_trackers(rows: &[TrackerRow], n: usize) -> Vec<&TrackerRow> {
+    let mut trackers: Vec<&TrackerRow> = rows.iter().filter(|r| !r.is_overview()).collect();
+    trackers.sort_by_key(|r| std::cmp::Reverse(r.current));
+    trackers.truncate(n);
+    trackers
+}
+
+fn size_cell(bytes: i64) -> String {
+    if bytes < 0 {
+        "-".to_string()
+    } else {
+        format_utils::format_bytes(bytes as u64, 2, false)
+    }
+}
+
+fn render_table(rows: &[TrackerRow]) -> String {
+    let mut out = format!(
+        "{:<12} {:<48} {:>12} {:>12} {:>12}\n",
+        "Type", "Label", "Limit", "Current", "Peak"
+    );
+    out.push_str(&"-".repeat(100));
+    out.push('\n');
+    for r in top_trackers(rows, TOP_TRACKERS) {
+        out.push_str(&format!(
+            "{:<12} {:<48} {:>12} {:>12} {:>12}\n",
+            ui::FormatHelper::truncate_string(&r.kind, 12),
+            ui::FormatHelper::truncate_string(&r.label, 48),
+            size_cell(r.limit),
+            size_cell(r.current),
+            size_cell(r.peak)
+        ));
+    }
+    out
+}
+
+/// Tool to break BE memory down by tracker (query, load, compaction, ...)
+pub struct MemTrackerTool;
+
+impl Tool for MemTrackerTool {
+    fn name(&self) -> &str {
+        "mem-tracker"
+    }
+
+    fn description(&self) -> &str {
+        "Break BE memory down by tracker via /mem_tracker"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let kind = TRACKER_TYPES[ui::dialogs::select_index("Tracker type", TRACKER_TYPES)?];
+        let selection = be_http_client::select_be_targets()?;
+        ui::print_info(&format!("Fetching {kind} memory trackers from BE..."));
+
+        be_http_client::run_on_selection(
+            &selection,
+            &format!("/mem_tracker?type={kind}"),
+            None,
+            |target, result| {
+                let body = result?;
+                let suffix = target.map(|t| format!("_{}", t.file_suffix()));
+                let output_path = fs_utils::write_artifact(
+                    config,
+                    &file_name::timestamped(
+                        &format!("mem_tracker_{kind}{}", suffix.unwrap_or_default()),
+                        "html",
+                    ),
+                    body.as_bytes(),
+                )?;
+
+                let rows = parse_mem_tracker(&body);
+                if rows.is_empty() {
+                    return Err(CliError::ToolExecutionFailed(
+                        "No memory trackers found in the /mem_tracker response".to_string(),
+                    ));
+                }
+
+                println!();
+                println!("{}", render_table(&rows));
+                if let Some(total) = process_total(&rows) {
+                    ui::print_info(&format!("Process total: {}", size_cell(total)));
+                }
+                Ok(ExecutionResult::new(
+                    output_path,
+                    format!("{} {kind} memory tracker(s) parsed", rows.len()),
+                ))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_html_and_json_responses() {
+        let html = "<table data-toggle='table'><thead><tr>\
+            <th data-sortable='true'>Type</th><th>Label</th><th>Limit</th>\
+            <th>Current Consumption(Bytes)</th><th>Current Consumption(Normalize)</th>\
+            <th>Peak Consumption(Bytes)</th><th>Peak Consumption(Normalize)</th></tr></thead><tbody>\
+            <tr><td>overview</td><td>process resident memory</td><td>-1</td><td>8589934592</td><td>8.00 GB</td><td>9000000000</td><td>8.38 GB</td></tr>\
+            <tr><td>global</td><td>Orphan</td><td>-1</td><td>1048576</td><td>1.00 MB</td><td>2097152</td><td>2.00 MB</td></tr>\
+            <tr><td>global</td><td>SegmentCache</td><td>2147483648</td><td>1073741824</td><td>1.00 GB</td><td>1073741824</td><td>1.00 GB</td></tr>\
+            </tbody></table>";
+        let rows = parse_mem_tracker(html);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(process_total(&rows), Some(8589934592));
+        let top = top_trackers(&rows, 30);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].label, "SegmentCache");
+        assert_eq!(top[0].limit, 2147483648);
+
+        let json = r#"[{"type":"query","label":"Query#Id=1","limit":"-1","current_consumption":"512.00 MB","peak_consumption":"1.00 GB"}]"#;
+        let rows = parse_mem_tracker(json);
+        assert_eq!(rows[0].current, 512 * 1024 * 1024);
+        assert_eq!(rows[0].limit, -1);
+        assert_eq!(rows[0].peak, 1024 * 1024 * 1024);
+    }
+}
@@ -1,12 +1,11 @@
-use super::be_http_client;
+use super::be_http_client::{self, BeSelection};
 use crate::config::Config;
 use crate::error::Result;
-use crate::tools::common::format_utils;
+use crate::tools::common::{file_name, format_utils, fs_utils};
 use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
-use chrono::Utc;
+use crate::ui::table::TableBuilder;
 use regex::Regex;
-use std::fs;
 use std::path::PathBuf;
 
 /// Tool to analyze Jemalloc memory usage in BE
@@ -25,33 +24,20 @@ impl Tool for MemzTool {
     }
 
     fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let selection = be_http_client::select_be_targets()?;
         ui::print_info("Fetching Jemalloc memory usage from BE...");
 
-        let result = be_http_client::request_be_webserver_port("/memz", None);
-
-        match result {
-            Ok(html_content) => {
-                let (metrics_table, full_html) = extract_memory_metrics(&html_content);
-
-                let output_path = save_html_to_file(config, &full_html, "memz")?;
-                let path_display = output_path.display().to_string();
-
-                ui::print_success("Memory metrics fetched successfully!");
-                println!();
-                ui::print_info("Results:");
-                println!("{metrics_table}");
-
-                Ok(ExecutionResult {
-                    output_path,
-                    message: format!("Jemalloc memory profile saved to {path_display}"),
-                })
-            }
-            Err(e) => {
-                ui::print_error(&format!("Failed to fetch memory metrics: {e}."));
-                ui::print_info("Tips: Ensure the BE service is running and accessible.");
-                Err(e)
-            }
-        }
+        fetch_memz(
+            config,
+            &selection,
+            MemzRequest {
+                endpoint: "/memz",
+                file_prefix: "memz",
+                success_message: "Memory metrics fetched successfully!",
+                error_context: "Failed to fetch memory metrics",
+                saved_label: "Jemalloc memory profile",
+            },
+        )
     }
 
     fn requires_pid(&self) -> bool {
@@ -69,42 +55,78 @@ impl Tool for MemzGlobalTool {
     }
 
     fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let selection = be_http_client::select_be_targets()?;
         ui::print_info("Fetching global memory usage from BE...");
 
-        let result = be_http_client::request_be_webserver_port("/memz?type=global", None);
+        fetch_memz(
+            config,
+            &selection,
+            MemzRequest {
+                endpoint: "/memz?type=global",
+                file_prefix: "memz_global",
+                success_message: "Global memory metrics fetched successfully!",
+                error_context: "Failed to fetch global memory metrics",
+                saved_label: "Global memory profile",
+            },
+        )
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+}
+
+/// Endpoint and wording of one memz variant
+struct MemzRequest<'a> {
+    endpoint: &'a str,
+    file_prefix: &'a str,
+    success_message: &'a str,
+    error_context: &'a str,
+    saved_label: &'a str,
+}
 
-        match result {
+fn fetch_memz(
+    config: &Config,
+    selection: &BeSelection,
+    req: MemzRequest<'_>,
+) -> Result<ExecutionResult> {
+    be_http_client::run_on_selection(
+        selection,
+        req.endpoint,
+        None,
+        |target, result| match result {
             Ok(html_content) => {
                 let (metrics_table, full_html) = extract_memory_metrics(&html_content);
 
-                let output_path = save_html_to_file(config, &full_html, "memz_global")?;
+                let file_prefix = match target {
+                    Some(t) => format!("{}_{}", req.file_prefix, t.file_suffix()),
+                    None => req.file_prefix.to_string(),
+                };
+                let output_path = save_html_to_file(config, &full_html, &file_prefix)?;
                 let path_display = output_path.display().to_string();
 
-                ui::print_success("Global memory metrics fetched successfully!");
+                ui::print_success(req.success_message);
                 println!();
                 ui::print_info("Results:");
-                println!("{metrics_table}");
+                println!("{}", ui::plain_text(&metrics_table));
+                show_memz_hints(config, &full_html);
 
-                Ok(ExecutionResult {
+                Ok(ExecutionResult::new(
                     output_path,
-                    message: format!("Global memory profile saved to {path_display}"),
-                })
+                    format!("{} saved to {path_display}", req.saved_label),
+                ))
             }
             Err(e) => {
-                ui::print_error(&format!("Failed to fetch global memory metrics: {e}."));
+                ui::print_error(&format!("{}: {e}.", req.error_context));
                 ui::print_info("Tips: Ensure the BE service is running and accessible.");
                 Err(e)
             }
-        }
-    }
-
-    fn requires_pid(&self) -> bool {
-        false
-    }
+        },
+    )
 }
 
 /// Extract memory metrics from the HTML response
-fn extract_memory_metrics(html_content: &str) -> (String, String) {
+pub(crate) fn extract_memory_metrics(html_content: &str) -> (String, String) {
     let re = Regex::new(r"Allocated: (\d+), active: (\d+), metadata: (\d+).*?, resident: (\d+), mapped: (\d+), retained: (\d+)").unwrap();
     let thread_cache_re = Regex::new(r"tcache_bytes:\s+(\d+)").unwrap();
     let dirty_pages_re = Regex::new(r"dirty:\s+N/A\s+\d+\s+\d+\s+\d+\s+(\d+)").unwrap();
@@ -165,34 +187,39 @@ fn extract_memory_metrics(html_content: &str) -> (String, String) {
         dirty_pages = format_utils::format_bytes(bytes, 2, true);
     }
 
-    let table = format!(
-        " Key Memory Metrics:\n\
-        ┌───────────────────┬────────────────────────────────────┐\n\
-        │ Metric            │ Value                              │\n\
-        ├───────────────────┼────────────────────────────────────┤\n\
-        │ Allocated         │ {allocated:<34} │\n\
-        │ Active            │ {active:<34} │\n\
-        │ Metadata          │ {metadata:<34} │\n\
-        │ Resident          │ {resident:<34} │\n\
-        │ Mapped            │ {mapped:<34} │\n\
-        │ Retained          │ {retained:<34} │\n\
-        │ Thread Cache      │ {thread_cache:<34} │\n\
-        │ Dirty Pages       │ {dirty_pages:<34} │\n\
-        └───────────────────┴────────────────────────────────────┘"
-    );
+    let rows = [
+        ("Allocated", allocated),
+        ("Active", active),
+        ("Metadata", metadata),
+        ("Resident", resident),
+        ("Mapped", mapped),
+        ("Retained", retained),
+        ("Thread Cache", thread_cache),
+        ("Dirty Pages", dirty_pages),
+    ];
+    let mut builder = TableBuilder::new(["Metric", "Value"]);
+    for (metric, value) in rows {
+        builder.push_row([metric.to_string(), value]);
+    }
+    let table = format!(" Key Memory Metrics:\n{}", builder.render().trim_end());
 
     (table, html_content.to_string())
 }
 
+fn show_memz_hints(config: &Config, html_content: &str) {
+    let re = Regex::new(r"resident: (\d+)").unwrap();
+    if let Some(resident) = re
+        .captures(html_content)
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse::<u64>().ok())
+    {
+        ui::hints::show_hints(config, &[ui::hints::Finding::MemzResident(resident)]);
+    }
+}
+
 /// Save HTML content to file and return the path
 fn save_html_to_file(config: &Config, html_content: &str, file_prefix: &str) -> Result<PathBuf> {
-    config.ensure_output_dir()?;
-
-    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let filename = format!("{file_prefix}_{timestamp}.html");
-    let output_path = config.output_dir.join(filename);
-
-    fs::write(&output_path, html_content)?;
+    let filename = file_name::timestamped(file_prefix, "html");
 
-    Ok(output_path)
+    fs_utils::write_artifact(config, &filename, html_content.as_bytes())
 }
@@ -0,0 +1,289 @@
+use super::be_http_client::{self, BeSelection, BeTarget};
+use crate::config::Config;
+use crate::core::interrupt;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::format_utils::format_bytes;
+use crate::tools::common::fs_utils;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::table::{Align, TableBuilder};
+use chrono::Local;
+use console::Term;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::time::{Duration, Instant};
+
+const MAX_INTERVAL_SECS: u32 = 300;
+const MAX_WATCH_MINUTES: i64 = 24 * 60;
+
+static STATS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Allocated: (\d+), active: (\d+), metadata: \d+.*?, resident: (\d+), mapped: (\d+)")
+        .expect("valid jemalloc stats pattern")
+});
+
+const METRICS: [&str; 4] = ["allocated", "active", "resident", "mapped"];
+
+/// Jemalloc totals of one `/memz` page, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct MemzStats([u64; 4]);
+
+fn parse_stats(html: &str) -> Option<MemzStats> {
+    let caps = STATS_RE.captures(html)?;
+    let mut values = [0u64; 4];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = caps.get(i + 1)?.as_str().parse().ok()?;
+    }
+    Some(MemzStats(values))
+}
+
+/// One sample of one backend; `host` is empty when the BE was found automatically
+#[derive(Debug, Clone, PartialEq)]
+struct Sample {
+    at: chrono::DateTime<Local>,
+    host: String,
+    stats: MemzStats,
+}
+
+/// Least-squares slope of `metric` for `host`, in bytes per minute
+fn growth_per_minute(samples: &[Sample], host: &str, metric: usize) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|s| s.host == host)
+        .map(|s| (s.at.timestamp() as f64 / 60.0, s.stats.0[metric] as f64))
+        .collect();
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let var: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let cov: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    (var > 0.0).then(|| cov / var)
+}
+
+fn signed_bytes(value: f64) -> String {
+    let sign = if value < 0.0 { "-" } else { "+" };
+    format!("{sign}{}", format_bytes(value.abs() as u64, 2, false))
+}
+
+fn hosts(samples: &[Sample]) -> Vec<&str> {
+    let mut hosts: Vec<&str> = Vec::new();
+    for s in samples {
+        if !hosts.contains(&s.host.as_str()) {
+            hosts.push(&s.host);
+        }
+    }
+    hosts
+}
+
+fn render_growth(samples: &[Sample]) -> String {
+    let mut table = TableBuilder::new(["BE", "Metric", "First", "Last", "Growth/min"])
+        .align(2, Align::Right)
+        .align(3, Align::Right)
+        .align(4, Align::Right);
+    for host in hosts(samples) {
+        let of_host: Vec<&Sample> = samples.iter().filter(|s| s.host == host).collect();
+        let (Some(first), Some(last)) = (of_host.first(), of_host.last()) else {
+            continue;
+        };
+        for (i, metric) in METRICS.iter().enumerate() {
+            table.push_row([
+                if host.is_empty() { "-" } else { host }.to_string(),
+                metric.to_string(),
+                format_bytes(first.stats.0[i], 2, false),
+                format_bytes(last.stats.0[i], 2, false),
+                growth_per_minute(samples, host, i)
+                    .map(signed_bytes)
+                    .unwrap_or_else(|| "-".to_string()),
+            ]);
+        }
+    }
+    table.render()
+}
+
+fn render_csv(samples: &[Sample]) -> String {
+    let mut out = format!("timestamp,be,{}\n", METRICS.join(","));
+    for s in samples {
+        let values: Vec<String> = s.stats.0.iter().map(u64::to_string).collect();
+        out.push_str(&format!(
+            "{},{},{}\n",
+            s.at.format("%Y-%m-%d %H:%M:%S"),
+            s.host,
+            values.join(",")
+        ));
+    }
+    out
+}
+
+fn fetch(target: Option<&BeTarget>) -> Result<MemzStats> {
+    let html = match target {
+        Some(t) => be_http_client::request_be_target(t, "/memz", None)?,
+        None => be_http_client::request_be_webserver_port("/memz", None)?,
+    };
+    parse_stats(&html).ok_or_else(|| {
+        CliError::ToolExecutionFailed("No jemalloc statistics in the /memz page".to_string())
+    })
+}
+
+/// Sample every target each `interval` until `duration` passes or Ctrl-C
+fn sample(targets: &[Option<BeTarget>], interval: Duration, duration: Duration) -> Vec<Sample> {
+    let term = Term::stdout();
+    let live = term.is_term() && !ui::plain_output();
+    let started = Instant::now();
+    let mut samples = Vec::new();
+    interrupt::catching(|| {
+        loop {
+            let at = Local::now();
+            let mut line = at.format("%H:%M:%S").to_string();
+            for target in targets {
+                let host = target.as_ref().map(|t| t.to_string()).unwrap_or_default();
+                match fetch(target.as_ref()) {
+                    Ok(stats) => {
+                        line.push_str(&format!(
+                            "  {}alloc {} res {}",
+                            if targets.len() > 1 {
+                                format!("{host} ")
+                            } else {
+                                String::new()
+                            },
+                            format_bytes(stats.0[0], 1, false),
+                            format_bytes(stats.0[2], 1, false)
+                        ));
+                        samples.push(Sample { at, host, stats });
+                    }
+                    Err(_) if interrupt::interrupted() => break,
+                    Err(e) => line.push_str(&format!("  {host} failed: {e}")),
+                }
+            }
+            line.push_str(&format!("  ({} samples)", samples.len()));
+            if live {
+                let _ = term.clear_line();
+                let _ = term.write_str(&line);
+            } else {
+                ui::print_info(&line);
+            }
+            if started.elapsed() + interval > duration
+                || !interrupt::sleep_unless_interrupted(interval)
+            {
+                break;
+            }
+        }
+    });
+    if live {
+        let _ = term.write_line("");
+    }
+    if interrupt::interrupted() {
+        ui::print_info("Stopped watching; keeping the samples taken so far.");
+    }
+    samples
+}
+
+/// Samples `/memz` over time and reports how fast jemalloc memory grows
+pub struct MemzWatchTool;
+
+impl Tool for MemzWatchTool {
+    fn name(&self) -> &str {
+        "memz-watch"
+    }
+
+    fn description(&self) -> &str {
+        "Sample /memz over time and report memory growth per minute"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let targets: Vec<Option<BeTarget>> = match be_http_client::select_be_targets()? {
+            BeSelection::Auto => vec![None],
+            BeSelection::One(target) => vec![Some(target)],
+            BeSelection::All(targets) => targets.into_iter().map(Some).collect(),
+        };
+        let interval =
+            ui::InputHelper::prompt_duration_secs("Sample interval", 10, MAX_INTERVAL_SECS)?;
+        let minutes = ui::InputHelper::prompt_number_with_default(
+            "Watch duration",
+            10,
+            1,
+            Some(MAX_WATCH_MINUTES),
+            Some("minutes"),
+        )?;
+
+        ui::print_info(&format!(
+            "Sampling /memz every {interval}s for {minutes} minute(s); press Ctrl-C to stop early."
+        ));
+        let samples = sample(
+            &targets,
+            Duration::from_secs(u64::from(interval)),
+            Duration::from_secs(minutes as u64 * 60),
+        );
+        if samples.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No /memz sample could be taken".to_string(),
+            ));
+        }
+
+        if !config.stdout_only {
+            ui::print_info("");
+            for line in render_growth(&samples).lines() {
+                ui::print_info(line);
+            }
+        }
+
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("memz_watch", "csv"),
+            render_csv(&samples).as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("{} /memz sample(s) recorded", samples.len()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parses_memz_and_fits_growth_per_minute() {
+        let html = "<pre>Allocated: 1048576, active: 2097152, metadata: 4096 (n_thp 0), \
+                    resident: 3145728, mapped: 4194304, retained: 0</pre>";
+        assert_eq!(
+            parse_stats(html),
+            Some(MemzStats([1048576, 2097152, 3145728, 4194304]))
+        );
+        assert_eq!(parse_stats("<html>Not found</html>"), None);
+
+        let mib = 1024 * 1024;
+        let start = Local.with_ymd_and_hms(2024, 6, 1, 10, 0, 0).unwrap();
+        let samples: Vec<Sample> = (0..5)
+            .map(|i| Sample {
+                at: start + chrono::Duration::seconds(30 * i),
+                host: "10.0.0.2:8040".to_string(),
+                stats: MemzStats([(100 + 2 * i as u64) * mib, 0, 200 * mib, 0]),
+            })
+            .collect();
+        // +2 MiB every 30s is +4 MiB per minute; a flat series does not grow
+        assert_eq!(
+            growth_per_minute(&samples, "10.0.0.2:8040", 0),
+            Some(4.0 * mib as f64)
+        );
+        assert_eq!(growth_per_minute(&samples, "10.0.0.2:8040", 2), Some(0.0));
+        assert_eq!(growth_per_minute(&samples[..1], "10.0.0.2:8040", 0), None);
+
+        let csv = render_csv(&samples);
+        assert_eq!(
+            csv.lines().next(),
+            Some("timestamp,be,allocated,active,resident,mapped")
+        );
+        assert_eq!(csv.lines().count(), 6);
+        assert!(render_growth(&samples).contains("+4.00 MB"));
+    }
+}
@@ -1,16 +1,34 @@
-mod be_http_client;
+pub mod be_http_client;
+mod be_snapshot;
 mod be_vars;
+mod compaction;
+mod crash_inspector;
+mod disk_usage;
 mod jmap;
 mod list;
+mod mem_tracker;
 mod memz;
+mod memz_watch;
 mod pipeline_tasks;
+pub mod port_check;
+mod profiler;
 mod pstack;
+mod pstack_cluster;
 mod response_handler;
 
+pub use be_snapshot::BeSnapshotTool;
 pub use be_vars::BeVarsTool;
-pub use jmap::{JmapDumpTool, JmapHistoTool};
-pub use list::BeListTool;
+pub use compaction::BeCompactionTool;
+pub use crash_inspector::BeCrashInspectorTool;
+pub use disk_usage::BeDiskUsageTool;
+pub use jmap::{JmapDumpTool, JmapHistoDiffTool, JmapHistoTool};
+pub use list::{BeListTool, restore_selected_be_host};
+pub use mem_tracker::MemTrackerTool;
+pub(crate) use memz::extract_memory_metrics;
 pub use memz::{MemzGlobalTool, MemzTool};
+pub use memz_watch::MemzWatchTool;
 pub use pipeline_tasks::PipelineTasksTool;
+pub use profiler::BeProfilerTool;
 pub use pstack::PstackTool;
+pub use pstack_cluster::ClusterPstackTool;
 pub use response_handler::BeResponseHandler;
@@ -1,3 +1,7 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+
 use super::BeResponseHandler;
 use super::be_http_client;
 use crate::config::Config;
@@ -5,6 +9,199 @@ use crate::error::Result;
 use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
 
+/// 2.1 serves the dump at the bare path; some 3.x builds only register the
+/// `/{duration}` form, where 0 lists every running fragment
+const ENDPOINTS: &[&str] = &[
+    "/api/running_pipeline_tasks",
+    "/api/running_pipeline_tasks/0",
+];
+const TOP_QUERIES: usize = 20;
+
+/// `No.3 (elapse_second=12s, query_timeout_second=300s, instance_id=..., is_timeout=false) : ...`
+static FRAGMENT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^No\.\d+\s*\(([^)]*)\)").unwrap());
+static QUERY_ID_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)query_?id\s*[=:]\s*([0-9a-f]+-[0-9a-f]+)").unwrap());
+// Field labels differ between 2.1 and 3.x; any alias counts
+static RUNNING_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:is running|_?running)\s*=\s*(?:true|1)\b").unwrap());
+static BLOCKED_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:block dependency|blocked_dep|blocked by)\s*=\s*([^,\]\s]+)").unwrap()
+});
+static TASK_ELAPSE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)elapse[_ ]time\s*=\s*([\d.]+)\s*s").unwrap());
+
+/// Pipeline tasks of one query, merged across its fragments
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryTasks {
+    pub query_id: String,
+    pub fragments: usize,
+    pub tasks: usize,
+    pub running: usize,
+    pub blocked: usize,
+    /// Longest fragment elapse time (`elapse_second`)
+    pub elapsed_secs: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PipelineSummary {
+    pub queries: Vec<QueryTasks>,
+    pub tasks: usize,
+    pub running: usize,
+    pub blocked: usize,
+}
+
+/// `key=value` attributes of a fragment header
+fn header_attr<'a>(attrs: &'a str, key: &str) -> Option<&'a str> {
+    attrs
+        .split(',')
+        .filter_map(|kv| kv.trim().split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim())
+}
+
+fn is_blocked(line: &str) -> bool {
+    BLOCKED_RE.captures(line).is_some_and(|c| {
+        let dep = c[1].trim_matches(|ch: char| !ch.is_alphanumeric() && ch != '_');
+        !dep.is_empty() && !["null", "nullptr", "none", "0"].contains(&dep.to_lowercase().as_str())
+    })
+}
+
+pub fn parse_pipeline_tasks(body: &str) -> PipelineSummary {
+    let mut by_query: BTreeMap<String, QueryTasks> = BTreeMap::new();
+    let mut current: Option<QueryTasks> = None;
+    let flush = |fragment: Option<QueryTasks>, by_query: &mut BTreeMap<String, QueryTasks>| {
+        if let Some(f) = fragment {
+            let entry = by_query.entry(f.query_id.clone()).or_insert(QueryTasks {
+                query_id: f.query_id.clone(),
+                ..Default::default()
+            });
+            entry.fragments += 1;
+            entry.tasks += f.tasks;
+            entry.running += f.running;
+            entry.blocked += f.blocked;
+            entry.elapsed_secs = entry.elapsed_secs.max(f.elapsed_secs);
+        }
+    };
+
+    for line in body.lines() {
+        if let Some(caps) = FRAGMENT_RE.captures(line) {
+            flush(current.take(), &mut by_query);
+            let attrs = &caps[1];
+            current = Some(QueryTasks {
+                query_id: header_attr(attrs, "query_id")
+                    .or_else(|| header_attr(attrs, "instance_id"))
+                    .unwrap_or("unknown")
+                    .to_string(),
+                elapsed_secs: header_attr(attrs, "elapse_second")
+                    .and_then(|v| v.trim_end_matches('s').parse().ok())
+                    .unwrap_or(0),
+                ..Default::default()
+            });
+        }
+        let Some(fragment) = current.as_mut() else {
+            continue;
+        };
+        if let Some(caps) = QUERY_ID_RE.captures(line) {
+            fragment.query_id = caps[1].to_string();
+        }
+        if line.contains("PipelineTask[") {
+            fragment.tasks += 1;
+            if RUNNING_RE.is_match(line) {
+                fragment.running += 1;
+            } else if is_blocked(line) {
+                fragment.blocked += 1;
+            }
+            // Fragments without a header elapse fall back to their slowest task
+            if let Some(secs) = TASK_ELAPSE_RE
+                .captures(line)
+                .and_then(|c| c[1].parse::<f64>().ok())
+            {
+                fragment.elapsed_secs = fragment.elapsed_secs.max(secs as u64);
+            }
+        }
+    }
+    flush(current, &mut by_query);
+
+    let queries: Vec<QueryTasks> = by_query.into_values().collect();
+    PipelineSummary {
+        tasks: queries.iter().map(|q| q.tasks).sum(),
+        running: queries.iter().map(|q| q.running).sum(),
+        blocked: queries.iter().map(|q| q.blocked).sum(),
+        queries,
+    }
+}
+
+fn render_top(title: &str, queries: &[&QueryTasks]) -> String {
+    let mut out = format!(
+        "{title}\n{:<36} {:>9} {:>7} {:>8} {:>8} {:>10}\n{}\n",
+        "QueryId",
+        "Fragments",
+        "Tasks",
+        "Running",
+        "Blocked",
+        "Elapsed",
+        "-".repeat(83)
+    );
+    for q in queries {
+        out.push_str(&format!(
+            "{:<36} {:>9} {:>7} {:>8} {:>8} {:>9}s\n",
+            ui::FormatHelper::truncate_string(&q.query_id, 36),
+            q.fragments,
+            q.tasks,
+            q.running,
+            q.blocked,
+            q.elapsed_secs
+        ));
+    }
+    out
+}
+
+pub fn render_summary(summary: &PipelineSummary) -> String {
+    let mut by_tasks: Vec<&QueryTasks> = summary.queries.iter().collect();
+    by_tasks.sort_by_key(|q| std::cmp::Reverse(q.tasks));
+    by_tasks.truncate(TOP_QUERIES);
+    let mut by_time: Vec<&QueryTasks> = summary.queries.iter().collect();
+    by_time.sort_by_key(|q| std::cmp::Reverse(q.elapsed_secs));
+    by_time.truncate(TOP_QUERIES);
+
+    format!(
+        "Totals: {} queries, {} tasks ({} running, {} blocked)\n\n{}\n{}",
+        summary.queries.len(),
+        summary.tasks,
+        summary.running,
+        summary.blocked,
+        render_top(
+            &format!("Top {TOP_QUERIES} queries by task count"),
+            &by_tasks
+        ),
+        render_top(
+            &format!("Top {TOP_QUERIES} queries by running time"),
+            &by_time
+        )
+    )
+}
+
+/// The first endpoint that answers with a pipeline dump
+pub(super) fn fetch_dump(
+    target: Option<&be_http_client::BeTarget>,
+    first: Result<String>,
+) -> Result<String> {
+    match (&first, target) {
+        (Ok(body), Some(t)) if looks_missing(body) => {
+            be_http_client::request_be_target(t, ENDPOINTS[1], None)
+        }
+        (Ok(body), None) if looks_missing(body) => {
+            be_http_client::request_be_webserver_port(ENDPOINTS[1], None)
+        }
+        _ => first,
+    }
+}
+
+fn looks_missing(body: &str) -> bool {
+    let head = body.trim_start();
+    head.is_empty() || head.starts_with("<html") || head.contains("Not Found")
+}
+
 /// Tool to fetch running pipeline tasks from BE node
 pub struct PipelineTasksTool;
 
@@ -14,14 +211,13 @@ impl Tool for PipelineTasksTool {
     }
 
     fn description(&self) -> &str {
-        "Get running pipeline tasks from BE node"
+        "Summarize running pipeline tasks on BE nodes"
     }
 
     fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let selection = be_http_client::select_be_targets()?;
         ui::print_info("Fetching running pipeline tasks from BE...");
 
-        let result = be_http_client::request_be_webserver_port("/api/running_pipeline_tasks", None);
-
         let handler = BeResponseHandler {
             success_message: "Pipeline tasks fetched successfully!",
             empty_warning: "No running pipeline tasks found.",
@@ -29,22 +225,25 @@ impl Tool for PipelineTasksTool {
             tips: "Ensure the BE service is running and accessible.",
         };
 
-        // First check if we have a result
-        match &result {
-            Ok(output) => {
-                if output.len() < 100 || output.lines().count() <= 3 {
-                    return handler.handle_console_result(result, "pipeline tasks");
-                }
-
-                // Otherwise save to file
-                config.ensure_output_dir()?;
-                handler.handle_file_result(config, result, "pipeline_tasks", get_summary)
-            }
-            Err(_) => {
-                // For errors, just use the standard error handling
-                handler.handle_console_result(result, "pipeline tasks")
+        be_http_client::run_on_selection(&selection, ENDPOINTS[0], None, |target, result| {
+            let result = fetch_dump(target, result);
+            let summary = match &result {
+                Ok(raw) => parse_pipeline_tasks(raw),
+                Err(_) => return handler.handle_console_result(result, "pipeline tasks"),
+            };
+            if summary.queries.is_empty() {
+                return handler.handle_console_result(result, "pipeline tasks");
             }
-        }
+
+            // Raw dump first for deep dives, the summary appended at the end
+            let rendered = render_summary(&summary);
+            let combined = result.map(|raw| format!("{raw}\n\n===== Summary =====\n{rendered}"));
+            let prefix = match target {
+                Some(t) => format!("pipeline_tasks_{}", t.file_suffix()),
+                None => "pipeline_tasks".to_string(),
+            };
+            handler.handle_file_result(config, combined, &prefix, |_| rendered.clone())
+        })
     }
 
     fn requires_pid(&self) -> bool {
@@ -52,19 +251,40 @@ impl Tool for PipelineTasksTool {
     }
 }
 
-/// Get a summary of the response data for display in the console
-fn get_summary(data: &str) -> String {
-    if data.trim().is_empty() {
-        return "No running pipeline tasks found.".to_string();
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Simple summary: show first few lines
-    let preview_lines: Vec<&str> = data.lines().take(10).collect();
-    let preview = preview_lines.join("\n");
+    #[test]
+    fn summarizes_2_1_and_3_x_dumps() {
+        let v21 = "2 pipeline fragment contexts are still running! duration_limit=0\n\
+No.0 (elapse_second=42s, query_timeout_second=300s, instance_id=a1b2-c3d4, is_timeout=false) : PipelineFragmentContext Info:\n\
+Task 0: PipelineTask[this = 0x1, id = 0, open = true, eos = false, finish = false, dry run = false, elapse time = 41s, wake up by = 0], block dependency = NULL, is running = true\n\
+Task 1: PipelineTask[this = 0x2, id = 1, open = true, eos = false, finish = false, dry run = false, elapse time = 41s, wake up by = 0], block dependency = ExchangeSourceDependency, is running = false\n\
+No.1 (elapse_second=3s, query_timeout_second=300s, instance_id=e5f6-0708, is_timeout=false) : PipelineFragmentContext Info:\n\
+Task 0: PipelineTask[this = 0x3, id = 0, open = false, eos = false, finish = false, dry run = false, elapse time = 2s, wake up by = 0], block dependency = NULL, is running = false\n";
+        let s = parse_pipeline_tasks(v21);
+        assert_eq!(s.queries.len(), 2);
+        assert_eq!((s.tasks, s.running, s.blocked), (3, 1, 1));
+        let slow = s
+            .queries
+            .iter()
+            .find(|q| q.query_id == "a1b2-c3d4")
+            .unwrap();
+        assert_eq!(slow.elapsed_secs, 42);
 
-    if data.lines().count() > 10 {
-        format!("{preview}\n... (more content in output file)")
-    } else {
-        preview
+        // 3.x names the query and merges fragments of the same query
+        let v3 = "No.0 (elapse_second=10s, query_timeout_second=900s, is_timeout=false) : PipelineFragmentContext Info:\n\
+QueryId = 9f8e7d6c5b4a3f2e-1a2b3c4d5e6f7081\n\
+PipelineTask[id = 0, _running = true, blocked_dep = nullptr]\n\
+No.1 (elapse_second=25s, query_timeout_second=900s, is_timeout=false) : PipelineFragmentContext Info:\n\
+QueryId = 9f8e7d6c5b4a3f2e-1a2b3c4d5e6f7081\n\
+PipelineTask[id = 0, _running = false, blocked_dep = LocalExchangeSourceDependency]\n";
+        let s = parse_pipeline_tasks(v3);
+        assert_eq!(s.queries.len(), 1);
+        assert_eq!(s.queries[0].fragments, 2);
+        assert_eq!(s.queries[0].elapsed_secs, 25);
+        assert_eq!((s.running, s.blocked), (1, 1));
+        assert!(render_summary(&s).contains("Totals: 1 queries, 2 tasks"));
     }
 }
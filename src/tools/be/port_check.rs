@@ -0,0 +1,327 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::config_loader::{self, DorisConfig};
+use crate::error::{CliError, Result};
+use crate::tools::doctor::{self, CheckResult};
+use crate::tools::mysql::{Backend, ClusterInfo};
+use crate::ui;
+
+/// BE ports that are both persisted in the config and reported by SHOW BACKENDS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PortKind {
+    Http,
+    Brpc,
+    Heartbeat,
+}
+
+impl PortKind {
+    pub const ALL: [PortKind; 3] = [PortKind::Http, PortKind::Brpc, PortKind::Heartbeat];
+
+    /// The be.conf key, which is also the persisted field name
+    pub fn conf_key(self) -> &'static str {
+        match self {
+            PortKind::Http => "webserver_port",
+            PortKind::Brpc => "brpc_port",
+            PortKind::Heartbeat => "heartbeat_service_port",
+        }
+    }
+
+    fn configured(self, doris: &DorisConfig) -> Option<u16> {
+        match self {
+            PortKind::Http => doris.webserver_port,
+            PortKind::Brpc => doris.brpc_port,
+            PortKind::Heartbeat => doris.heartbeat_service_port,
+        }
+    }
+
+    fn set(self, doris: &mut DorisConfig, port: u16) {
+        let field = match self {
+            PortKind::Http => &mut doris.webserver_port,
+            PortKind::Brpc => &mut doris.brpc_port,
+            PortKind::Heartbeat => &mut doris.heartbeat_service_port,
+        };
+        *field = Some(port);
+    }
+
+    fn reported(self, backend: &Backend) -> u16 {
+        match self {
+            PortKind::Http => backend.http_port,
+            PortKind::Brpc => backend.brpc_port,
+            PortKind::Heartbeat => backend.heartbeat_port,
+        }
+    }
+}
+
+/// One port of one backend as seen by be.conf, SHOW BACKENDS and a TCP connect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortFinding {
+    pub host: String,
+    pub kind: PortKind,
+    /// From be.conf via the persisted config; None when never parsed
+    pub conf: Option<u16>,
+    /// From clusters.toml, i.e. the last SHOW BACKENDS
+    pub reported: u16,
+    pub reported_listening: bool,
+    /// Only probed when it differs from `reported`
+    pub conf_listening: Option<bool>,
+}
+
+impl PortFinding {
+    pub fn is_consistent(&self) -> bool {
+        self.conf.is_none_or(|c| c == self.reported) && self.reported_listening
+    }
+
+    /// The port later lookups should use instead of the persisted one: the
+    /// reported port, once it is the only one of the two that answers
+    pub fn suggested(&self) -> Option<u16> {
+        let conf_dead = match self.conf {
+            None => true,
+            Some(c) if c == self.reported => return None,
+            Some(_) => self.conf_listening == Some(false),
+        };
+        (self.reported_listening && conf_dead).then_some(self.reported)
+    }
+
+    pub fn describe(&self) -> String {
+        let reported = self.reported;
+        match self.conf {
+            Some(conf) if conf != reported => {
+                let tail = match (self.conf_listening == Some(true), self.reported_listening) {
+                    (true, true) => "; both are listening".to_string(),
+                    (true, false) => format!(" and only {conf} is listening"),
+                    (false, true) => format!(" and only {reported} is listening"),
+                    (false, false) => " and neither is listening".to_string(),
+                };
+                format!("be.conf says {conf} but SHOW BACKENDS reports {reported}{tail}")
+            }
+            Some(_) if self.reported_listening => {
+                format!("{reported} (be.conf and SHOW BACKENDS agree), listening")
+            }
+            Some(_) => format!("be.conf and SHOW BACKENDS say {reported} but nothing is listening"),
+            None if self.reported_listening => {
+                format!("SHOW BACKENDS reports {reported}, listening (not in be.conf)")
+            }
+            None => format!("SHOW BACKENDS reports {reported} but nothing is listening"),
+        }
+    }
+}
+
+/// Ports of one backend that SHOW BACKENDS reports more than once
+pub fn port_collisions(backend: &Backend) -> Vec<String> {
+    let ports = [
+        ("heartbeat_service_port", backend.heartbeat_port),
+        ("be_port", backend.be_port),
+        ("webserver_port", backend.http_port),
+        ("brpc_port", backend.brpc_port),
+    ];
+    let mut by_port: BTreeMap<u16, Vec<&str>> = BTreeMap::new();
+    for (key, port) in ports {
+        by_port.entry(port).or_default().push(key);
+    }
+    by_port
+        .into_iter()
+        .filter(|(_, keys)| keys.len() > 1)
+        .map(|(port, keys)| format!("{} all use {port}", keys.join(", ")))
+        .collect()
+}
+
+/// Compare every backend's ports with be.conf and probe both candidates
+pub fn cross_check(
+    doris: &DorisConfig,
+    backends: &[Backend],
+    listening: impl Fn(&str, u16) -> bool + Sync,
+) -> Vec<PortFinding> {
+    // Probe each distinct host:port once, in parallel, so dead backends cost one timeout
+    let mut probes = BTreeSet::new();
+    for b in backends {
+        for kind in PortKind::ALL {
+            probes.insert((b.host.as_str(), kind.reported(b)));
+            if let Some(conf) = kind.configured(doris) {
+                probes.insert((b.host.as_str(), conf));
+            }
+        }
+    }
+    let open: BTreeMap<(&str, u16), bool> = std::thread::scope(|s| {
+        let listening = &listening;
+        let handles: Vec<_> = probes
+            .into_iter()
+            .map(|(host, port)| s.spawn(move || ((host, port), listening(host, port))))
+            .collect();
+        handles.into_iter().filter_map(|h| h.join().ok()).collect()
+    });
+    let is_open = |host: &str, port: u16| open.get(&(host, port)).copied().unwrap_or(false);
+
+    backends
+        .iter()
+        .flat_map(|b| {
+            PortKind::ALL.into_iter().map(|kind| {
+                let conf = kind.configured(doris);
+                let reported = kind.reported(b);
+                PortFinding {
+                    host: b.host.clone(),
+                    kind,
+                    conf,
+                    reported,
+                    reported_listening: is_open(&b.host, reported),
+                    conf_listening: conf.filter(|&c| c != reported).map(|c| is_open(&b.host, c)),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Port per kind that every backend agrees should replace the persisted one
+pub fn suggested_updates(doris: &DorisConfig, findings: &[PortFinding]) -> Vec<(PortKind, u16)> {
+    PortKind::ALL
+        .into_iter()
+        .filter_map(|kind| {
+            let of_kind: Vec<_> = findings.iter().filter(|f| f.kind == kind).collect();
+            let port = of_kind.first()?.suggested()?;
+            let unanimous = of_kind.iter().all(|f| f.suggested() == Some(port));
+            (unanimous && kind.configured(doris) != Some(port)).then_some((kind, port))
+        })
+        .collect()
+}
+
+/// Doctor lines: one per backend and port, plus any collisions
+pub fn check_results(backends: &[Backend], findings: &[PortFinding]) -> Vec<CheckResult> {
+    let mut results: Vec<CheckResult> = findings
+        .iter()
+        .map(|f| {
+            let name = format!("BE {} {}", f.kind.conf_key(), f.host);
+            if f.is_consistent() {
+                CheckResult::pass(&name, f.describe())
+            } else {
+                CheckResult::fail(
+                    &name,
+                    f.describe(),
+                    "Compare be.conf with SHOW BACKENDS; refresh clusters.toml via FE > fe-list",
+                )
+            }
+        })
+        .collect();
+    for b in backends {
+        for collision in port_collisions(b) {
+            results.push(CheckResult::fail(
+                &format!("BE port collision {}", b.host),
+                collision,
+                "Each BE service needs its own port in be.conf",
+            ));
+        }
+    }
+    results
+}
+
+/// Run the cross-check for the doctor; None when there is no cluster info to compare
+pub fn run(doris: Option<&DorisConfig>) -> Option<(Vec<CheckResult>, Vec<PortFinding>)> {
+    let doris = doris?;
+    let info = ClusterInfo::load_from_file().ok()?;
+    if info.backends.is_empty() {
+        return None;
+    }
+    let findings = cross_check(doris, &info.backends, |host, port| {
+        doctor::tcp_connect_latency(host, port).is_ok()
+    });
+    Some((check_results(&info.backends, &findings), findings))
+}
+
+/// Offer to persist the ports that actually answer, so `get_be_http_ports()`
+/// and brpc lookups stop trying dead ports first
+pub fn offer_updates(findings: &[PortFinding]) -> Result<()> {
+    let mut doris = config_loader::load_config()?;
+    let updates = suggested_updates(&doris, findings);
+    if updates.is_empty() {
+        return Ok(());
+    }
+    ui::print_info("");
+    for (kind, port) in &updates {
+        let current = kind
+            .configured(&doris)
+            .map_or_else(|| "unset".to_string(), |p| p.to_string());
+        ui::print_info(&format!(
+            "{}: saved {current}, every backend answers on {port}",
+            kind.conf_key()
+        ));
+    }
+    let confirmed = dialoguer::Confirm::new()
+        .with_prompt("Save these ports for later sessions?")
+        .default(true)
+        .interact()
+        .map_err(|e| CliError::prompt_failed("Port update confirmation failed", e))?;
+    if !confirmed {
+        return Ok(());
+    }
+    for (kind, port) in updates {
+        kind.set(&mut doris, port);
+    }
+    config_loader::persist_configuration(&doris);
+    ui::print_success("BE ports saved");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(host: &str, http: u16, brpc: u16) -> Backend {
+        Backend {
+            backend_id: "1".to_string(),
+            host: host.to_string(),
+            heartbeat_port: 9050,
+            be_port: 9060,
+            http_port: http,
+            brpc_port: brpc,
+            alive: true,
+            version: "doris-3.0.0".to_string(),
+            status: String::new(),
+            node_role: "mix".to_string(),
+            tag: None,
+            data_used_capacity: None,
+            avail_capacity: None,
+            total_capacity: None,
+            used_pct: None,
+        }
+    }
+
+    #[test]
+    fn mismatches_are_explained_and_the_live_port_is_suggested() {
+        let doris = DorisConfig {
+            webserver_port: Some(8040),
+            brpc_port: Some(8060),
+            heartbeat_service_port: Some(9050),
+            ..DorisConfig::default()
+        };
+        let backends = [
+            backend("10.0.0.1", 8041, 8060),
+            backend("10.0.0.2", 8041, 8060),
+        ];
+        let findings = cross_check(&doris, &backends, |_, port| port != 8040);
+
+        let http = &findings[0];
+        assert_eq!(http.kind, PortKind::Http);
+        assert!(!http.is_consistent());
+        assert_eq!(
+            http.describe(),
+            "be.conf says 8040 but SHOW BACKENDS reports 8041 and only 8041 is listening"
+        );
+        assert_eq!(http.suggested(), Some(8041));
+        assert!(findings[1].is_consistent());
+        assert_eq!(
+            suggested_updates(&doris, &findings),
+            [(PortKind::Http, 8041)]
+        );
+
+        // Backends that disagree leave the saved port alone
+        let mixed = [
+            backend("10.0.0.1", 8041, 8060),
+            backend("10.0.0.2", 8042, 8060),
+        ];
+        let findings = cross_check(&doris, &mixed, |_, port| port != 8040);
+        assert!(suggested_updates(&doris, &findings).is_empty());
+
+        assert_eq!(
+            port_collisions(&backend("10.0.0.1", 8060, 8060)),
+            ["webserver_port, brpc_port all use 8060"]
+        );
+    }
+}
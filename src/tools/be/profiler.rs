@@ -0,0 +1,284 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::be_http_client::{self, BeTarget};
+use crate::config::Config;
+use crate::config_loader::{Environment, process_detector};
+use crate::error::{CliError, Result};
+use crate::executor;
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::fe::FeProfilerTool;
+use crate::tools::{ExecutionResult, Impact, Tool};
+use crate::ui;
+
+/// Extra seconds granted to curl on top of the sampling duration
+const FETCH_SLACK_SECS: u64 = 30;
+
+/// Whether a `/pprof/profile` response is a gperftools CPU profile.
+///
+/// The binary header always contains zero words; the text or HTML error pages
+/// returned by builds without profiling support never do.
+pub fn is_cpu_profile(body: &[u8]) -> bool {
+    body.len() >= 16 && body.contains(&0)
+}
+
+/// Error page text with HTML tags stripped, on one line
+fn error_page_summary(body: &[u8]) -> String {
+    let text = String::from_utf8_lossy(body);
+    let mut plain = String::new();
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    let line = plain
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if line.is_empty() {
+        return "empty response".to_string();
+    }
+    ui::FormatHelper::truncate_string(&line, 160)
+}
+
+pub struct BeProfilerTool;
+
+impl Tool for BeProfilerTool {
+    fn name(&self) -> &str {
+        "be-profiler"
+    }
+
+    fn description(&self) -> &str {
+        "Collect a BE CPU profile via pprof (flame graph when tools are available)"
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        if config.stdout_only {
+            return Err(fs_utils::stdout_only_unsupported(
+                self.name(),
+                "binary CPU profile",
+            ));
+        }
+
+        let duration = FeProfilerTool::duration()?;
+        let selection = be_http_client::select_be_targets()?;
+
+        ui::print_info(&format!(
+            "Collecting {duration}s CPU profile from BE via /pprof/profile..."
+        ));
+        let results = be_http_client::request_bytes_on_selection(
+            &selection,
+            &format!("/pprof/profile?seconds={duration}"),
+            duration as u64 + FETCH_SLACK_SECS,
+        )?;
+
+        let timestamp = file_name::timestamp();
+        let mut saved = Vec::new();
+        let mut unsupported = Vec::new();
+
+        for (target, result) in results {
+            match result {
+                Ok(body) if is_cpu_profile(&body) => {
+                    saved.extend(save_pprof(config, &target, &body, &timestamp)?);
+                }
+                Ok(body) => {
+                    ui::print_warning(&format!(
+                        "BE {target} did not return a CPU profile: {}",
+                        error_page_summary(&body)
+                    ));
+                    ui::print_info(
+                        "This BE was likely built without CPU profiling support \
+                         (e.g. sanitizer builds), so nothing was saved.",
+                    );
+                    unsupported.push(target);
+                }
+                Err(e) => ui::print_error(&format!("Profiling BE {target} failed: {e}")),
+            }
+        }
+
+        if saved.is_empty() && !unsupported.is_empty() {
+            // perf can only attach to a process on this machine
+            match unsupported.iter().find(|t| t.is_local()) {
+                Some(target) => {
+                    ui::print_info(&format!(
+                        "Falling back to perf on the local BE process ({target})..."
+                    ));
+                    saved = profile_with_perf(config, duration, &timestamp)?;
+                }
+                None => ui::print_warning(&format!(
+                    "The perf fallback only profiles a BE on this machine; run cloud-cli on {} \
+                     to profile it with perf.",
+                    unsupported
+                        .iter()
+                        .map(|t| t.host.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            }
+        }
+        if saved.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No CPU profile was collected".to_string(),
+            ));
+        }
+
+        for path in &saved {
+            ui::print_info(&format!("Saved: {}", path.display()));
+        }
+        let output_path = saved
+            .iter()
+            .find(|p| p.extension().is_some_and(|e| e == "svg"))
+            .unwrap_or(&saved[0])
+            .clone();
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("BE CPU profile collected (duration: {duration}s)"),
+        ))
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
+    fn impact(&self) -> Impact {
+        Impact::ProcessAttach
+    }
+}
+
+/// Save the raw profile and, when pprof and flamegraph.pl are installed, an SVG
+fn save_pprof(
+    config: &Config,
+    target: &BeTarget,
+    body: &[u8],
+    timestamp: &str,
+) -> Result<Vec<PathBuf>> {
+    let base =
+        file_name::timestamped_at(&format!("be_cpu_{}", target.file_suffix()), timestamp, "");
+    let prof = fs_utils::write_artifact(config, &format!("{base}.prof"), body)?;
+    let mut saved = vec![prof.clone()];
+
+    let pprof = fs_utils::find_in_path("pprof").or_else(|| fs_utils::find_in_path("google-pprof"));
+    let flamegraph = fs_utils::find_in_path("flamegraph.pl");
+    let be_binary = local_be_binary();
+    match (pprof, flamegraph, be_binary) {
+        (Some(pprof), Some(flamegraph), Some(binary)) => {
+            let mut collapse = Command::new(pprof);
+            collapse.arg("--collapsed").arg(&binary).arg(&prof);
+            match executor::execute_command(&mut collapse, "pprof")
+                .and_then(|out| render_flamegraph(config, &flamegraph, &out.stdout, &base))
+            {
+                Ok(svg) => saved.push(svg),
+                Err(e) => ui::print_warning(&format!("Flame graph not generated: {e}")),
+            }
+        }
+        _ => ui::print_info(
+            "Tip: install pprof and flamegraph.pl (and keep lib/doris_be locally) to get an SVG.",
+        ),
+    }
+    Ok(saved)
+}
+
+fn local_be_binary() -> Option<PathBuf> {
+    let doris = crate::config_loader::load_config().ok()?;
+    let install_dir = doris.be_install_dir.unwrap_or(doris.install_dir);
+    Some(install_dir.join("lib").join("doris_be")).filter(|p| p.is_file())
+}
+
+/// Turn folded stacks into `<base>.svg`
+fn render_flamegraph(
+    config: &Config,
+    flamegraph: &Path,
+    folded: &[u8],
+    base: &str,
+) -> Result<PathBuf> {
+    let folded_path = fs_utils::write_artifact(config, &format!("{base}.folded"), folded)?;
+    let mut render = Command::new(flamegraph);
+    render.arg(&folded_path);
+    let svg = executor::execute_command(&mut render, "flamegraph.pl")?;
+    fs_utils::write_artifact(config, &format!("{base}.svg"), &svg.stdout)
+}
+
+/// Sample the local BE with `perf record` when pprof is unavailable
+fn profile_with_perf(config: &Config, duration: u32, timestamp: &str) -> Result<Vec<PathBuf>> {
+    let perf = fs_utils::find_in_path("perf").ok_or_else(|| {
+        CliError::ToolExecutionFailed(
+            "BE CPU profiling is unavailable and perf is not installed".to_string(),
+        )
+    })?;
+    let pid = process_detector::get_pid_by_env(Environment::BE).map_err(|_| {
+        CliError::ToolExecutionFailed(
+            "BE CPU profiling is unavailable and no local BE process was found for perf"
+                .to_string(),
+        )
+    })?;
+
+    config.ensure_output_dir()?;
+    let base = file_name::timestamped_at(&format!("be_perf_{pid}"), timestamp, "");
+    let data = config.artifact_path(&format!("{base}.data"));
+
+    let mut record = Command::new(&perf);
+    record
+        .args(["record", "-F", "99", "-g", "-p", &pid.to_string(), "-o"])
+        .arg(&data)
+        .args(["--", "sleep", &duration.to_string()]);
+    let partial = executor::PartialArtifact::track(&data);
+    executor::execute_command(&mut record, "perf record")?;
+    drop(partial);
+    let mut saved = vec![data.clone()];
+
+    let (Some(collapse), Some(flamegraph)) = (
+        fs_utils::find_in_path("stackcollapse-perf.pl"),
+        fs_utils::find_in_path("flamegraph.pl"),
+    ) else {
+        ui::print_info("Tip: install stackcollapse-perf.pl and flamegraph.pl to get an SVG.");
+        return Ok(saved);
+    };
+
+    let mut script = Command::new(&perf);
+    script.arg("script").arg("-i").arg(&data);
+    let stacks = executor::execute_command(&mut script, "perf script")?;
+    let stacks_path = fs_utils::write_artifact(config, &format!("{base}.stacks"), &stacks.stdout)?;
+
+    let mut fold = Command::new(collapse);
+    fold.arg(&stacks_path);
+    let folded = executor::execute_command(&mut fold, "stackcollapse-perf.pl")?;
+    saved.push(render_flamegraph(
+        config,
+        &flamegraph,
+        &folded.stdout,
+        &base,
+    )?);
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_error_pages() {
+        // gperftools legacy header: 0, 3, 0, period, 0 as native words
+        let mut profile = Vec::new();
+        for w in [0u64, 3, 0, 10000, 0] {
+            profile.extend_from_slice(&w.to_ne_bytes());
+        }
+        assert!(is_cpu_profile(&profile));
+
+        let html = b"<html><body><h1>Error</h1>\nCPU profiling is not available with address sanitizer builds.</body></html>";
+        assert!(!is_cpu_profile(html));
+        assert!(error_page_summary(html).starts_with("Error CPU profiling is not available"));
+        assert!(!is_cpu_profile(b""));
+    }
+}
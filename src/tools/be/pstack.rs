@@ -1,12 +1,132 @@
 use crate::config::Config;
 use crate::error::{CliError, Result};
 use crate::executor;
-use crate::tools::{ExecutionResult, Tool};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::{ExecutionResult, Impact, Tool};
+use crate::ui;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+const PTRACE_SCOPE: &str = "/proc/sys/kernel/yama/ptrace_scope";
+/// Top frames listed on the console; the saved file has all of them
+const CONSOLE_SUMMARY_ROWS: usize = 10;
+
+/// Ways to capture stacks, in the order they are tried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StackBackend {
+    EuStack,
+    Gdb,
+    /// Kernel-side stacks only, but needs neither ptrace nor a debugger
+    ProcStack,
+}
+
+impl StackBackend {
+    const ALL: [StackBackend; 3] = [
+        StackBackend::EuStack,
+        StackBackend::Gdb,
+        StackBackend::ProcStack,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            StackBackend::EuStack => "eu-stack",
+            StackBackend::Gdb => "gdb",
+            StackBackend::ProcStack => "/proc/<pid>/task/*/stack",
+        }
+    }
+
+    fn is_available(self) -> bool {
+        match self {
+            StackBackend::EuStack => fs_utils::find_in_path("eu-stack").is_some(),
+            StackBackend::Gdb => fs_utils::find_in_path("gdb").is_some(),
+            StackBackend::ProcStack => true,
+        }
+    }
+}
+
+/// Whether a backend failure means the kernel refused to let us attach
+pub(super) fn is_ptrace_denied(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    lower.contains("operation not permitted")
+        || lower.contains("permission denied")
+        || lower.contains("could not attach")
+}
+
+fn ptrace_denied_error(pid: u32) -> CliError {
+    let scope = fs::read_to_string(PTRACE_SCOPE)
+        .map(|s| format!(" (currently {})", s.trim()))
+        .unwrap_or_default();
+    CliError::ToolExecutionFailed(format!(
+        "Not permitted to attach to process {pid}. Run cloud-cli as root (or as the BE user), \
+         or relax kernel.yama.ptrace_scope{scope}: sysctl -w kernel.yama.ptrace_scope=0"
+    ))
+}
+
+/// Name of the function in a frame line from eu-stack, gdb or /proc stack
+fn frame_function(line: &str) -> Option<String> {
+    let line = line.trim();
+    if let Some(rest) = line.strip_prefix("[<") {
+        // /proc: `[<0>] futex_wait_queue_me+0xc4/0x120`
+        let symbol = rest.split_once("] ")?.1;
+        return Some(symbol.split('+').next().unwrap_or(symbol).to_string());
+    }
+    let rest = line.strip_prefix('#')?;
+    let mut words = rest.split_whitespace().skip(1).peekable();
+    // Skip the address; gdb adds `in`, eu-stack may add `- 1`
+    if words.peek().is_some_and(|w| w.starts_with("0x")) {
+        words.next();
+    }
+    while words.peek().is_some_and(|w| matches!(*w, "in" | "-" | "1")) {
+        words.next();
+    }
+    let symbol = words.next()?;
+    let symbol = symbol.split('@').next().unwrap_or(symbol);
+    (!symbol.is_empty()).then(|| symbol.to_string())
+}
+
+fn is_thread_header(line: &str) -> bool {
+    line.starts_with("TID ") || line.starts_with("Thread ")
+}
+
+/// Thread counts by top frame, most common first
+pub(super) fn summarize_top_frames(trace: &str) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut awaiting_top: Option<bool> = None;
+    for line in trace.lines() {
+        if is_thread_header(line) {
+            if awaiting_top == Some(true) {
+                *counts.entry("<no frames>".to_string()).or_default() += 1;
+            }
+            awaiting_top = Some(true);
+        } else if awaiting_top == Some(true)
+            && let Some(function) = frame_function(line)
+        {
+            *counts.entry(function).or_default() += 1;
+            awaiting_top = Some(false);
+        }
+    }
+    if awaiting_top == Some(true) {
+        *counts.entry("<no frames>".to_string()).or_default() += 1;
+    }
+
+    let mut summary: Vec<(String, usize)> = counts.into_iter().collect();
+    summary.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    summary
+}
+
+fn render_summary(summary: &[(String, usize)]) -> String {
+    let total: usize = summary.iter().map(|(_, n)| n).sum();
+    let mut out = format!("\n==== Threads by top frame ({total} threads) ====\n");
+    for (function, count) in summary {
+        out.push_str(&format!("{count:>6}  {function}\n"));
+    }
+    out
+}
+
 pub struct PstackTool;
 
 impl Tool for PstackTool {
@@ -18,45 +138,145 @@ impl Tool for PstackTool {
         "Generate process stack trace (.txt)"
     }
 
-    fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
-        config.ensure_output_dir()?;
-
-        let script_path = config.output_dir.join("ps.sh");
-        self.ensure_pstack_script(&script_path)?;
+    fn is_heavy(&self) -> bool {
+        true
+    }
 
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("pstack_{pid}_{timestamp}.txt");
-        let output_path = config.output_dir.join(filename);
+    fn impact(&self) -> Impact {
+        Impact::ProcessAttach
+    }
 
-        let mut command = Command::new("bash");
-        command
-            .arg(&script_path)
-            .arg(pid.to_string())
-            .current_dir(&config.output_dir);
+    fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
+        let mut denied = false;
+        let mut failures = Vec::new();
+        let mut captured = None;
+        for backend in StackBackend::ALL.into_iter().filter(|b| b.is_available()) {
+            match self.capture(backend, pid) {
+                Ok(trace) => {
+                    captured = Some((backend, trace));
+                    break;
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    denied |= is_ptrace_denied(&message);
+                    ui::print_warning(&format!("{} failed: {message}", backend.name()));
+                    failures.push(backend.name());
+                }
+            }
+        }
+        let Some((backend, trace)) = captured else {
+            return Err(if denied {
+                ptrace_denied_error(pid)
+            } else {
+                CliError::ToolExecutionFailed(format!(
+                    "Could not capture stacks of process {pid} (tried {})",
+                    failures.join(", ")
+                ))
+            });
+        };
 
-        let output = executor::execute_command(&mut command, self.name())?;
+        let summary = summarize_top_frames(&trace);
+        let threads: usize = summary.iter().map(|(_, n)| n).sum();
+        let mut content = format!(
+            "# pstack of process {pid}\n# backend: {}\n# captured at: {}\n\n",
+            backend.name(),
+            Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        content.push_str(&trace);
+        content.push_str(&render_summary(&summary));
+        let filename = file_name::timestamped(&format!("pstack_{pid}"), "txt");
+        let output_path = fs_utils::write_artifact(config, &filename, content.as_bytes())?;
 
-        // Write output to file
-        fs::write(&output_path, &output.stdout).map_err(CliError::IoError)?;
+        if !config.stdout_only {
+            ui::print_info(&format!("Top frames across {threads} thread(s):"));
+            for (function, count) in summary.iter().take(CONSOLE_SUMMARY_ROWS) {
+                ui::print_info(&format!("{count:>6}  {function}"));
+            }
+        }
 
-        Ok(ExecutionResult {
+        Ok(ExecutionResult::new(
             output_path,
-            message: "Process stack trace completed successfully".to_string(),
-        })
+            format!(
+                "Process stack trace completed successfully ({threads} threads via {})",
+                backend.name()
+            ),
+        ))
     }
 }
 
-impl PstackTool {
-    /// Ensures the pstack script exists at the specified path
-    fn ensure_pstack_script(&self, script_path: &PathBuf) -> Result<()> {
-        if script_path.exists() {
-            return Ok(());
+/// Kernel stacks of every thread, in the `TID n:` layout eu-stack uses
+fn read_proc_stacks(proc_root: &Path, pid: u32) -> Result<String> {
+    let task_dir = proc_root.join(pid.to_string()).join("task");
+    let mut tids: Vec<u32> = fs::read_dir(&task_dir)
+        .map_err(|e| {
+            CliError::ToolExecutionFailed(format!("Cannot read {}: {e}", task_dir.display()))
+        })?
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .collect();
+    tids.sort_unstable();
+
+    let mut out = String::new();
+    for tid in tids {
+        let path: PathBuf = task_dir.join(tid.to_string()).join("stack");
+        let stack = fs::read_to_string(&path).map_err(|e| {
+            CliError::ToolExecutionFailed(format!("Cannot read {}: {e}", path.display()))
+        })?;
+        out.push_str(&format!("TID {tid}:\n{stack}"));
+        if !stack.ends_with('\n') {
+            out.push('\n');
         }
+    }
+    Ok(out)
+}
 
-        // Create the directory if it doesn't exist
-        if let Some(parent) = script_path.parent() {
-            fs::create_dir_all(parent)?;
+impl PstackTool {
+    fn capture(&self, backend: StackBackend, pid: u32) -> Result<String> {
+        let trace = match backend {
+            StackBackend::EuStack => {
+                let mut command = Command::new("eu-stack");
+                command.arg("-p").arg(pid.to_string());
+                Self::run_backend(&mut command, backend)?
+            }
+            StackBackend::Gdb => {
+                // A fresh private directory each run: a fixed path under /tmp could
+                // be planted by another user and would then run as root
+                let work_dir = tempfile::Builder::new()
+                    .prefix("cloud-cli-pstack-")
+                    .tempdir()?;
+                let script_path = Self::write_pstack_script(work_dir.path())?;
+
+                let mut command = Command::new("bash");
+                command
+                    .arg(&script_path)
+                    .arg(pid.to_string())
+                    .current_dir(work_dir.path());
+                Self::run_backend(&mut command, backend)?
+            }
+            StackBackend::ProcStack => read_proc_stacks(Path::new("/proc"), pid)?,
+        };
+        Ok(trace)
+    }
+
+    /// Runs a debugger, treating output without any frame as a failure since
+    /// gdb exits 0 even when it could not attach
+    fn run_backend(command: &mut Command, backend: StackBackend) -> Result<String> {
+        let output = executor::execute_command(command, backend.name())?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if stdout.lines().any(|l| frame_function(l).is_some()) {
+            return Ok(stdout);
         }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(CliError::ToolExecutionFailed(format!(
+            "{} produced no stack frames: {}",
+            backend.name(),
+            stderr.trim()
+        )))
+    }
+
+    /// Writes the pstack script into `dir`, refusing to reuse an existing file
+    fn write_pstack_script(dir: &Path) -> Result<PathBuf> {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
 
         // Create the pstack script content
         let script_content = r#"#!/bin/bash
@@ -96,17 +316,95 @@ $GDB -quiet -nx /proc/$1/exe -p $1 <<<"$backtrace" |
     -e '/^Thread/p'
 "#;
 
-        fs::write(script_path, script_content)?;
+        let script_path = dir.join("ps.sh");
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o700)
+            .open(&script_path)?
+            .write_all(script_content.as_bytes())?;
+        Ok(script_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Make the script executable
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(script_path)?.permissions();
-            perms.set_mode(0o755);
-            fs::set_permissions(script_path, perms)?;
+    #[test]
+    fn summarizes_top_frames_from_each_backend() {
+        let eu_stack = "PID 4242 - process
+TID 4242:
+#0  0x00007f8b5e6c0d4d __GI___poll
+#1  0x000000000040113d - 1 main
+TID 4243:
+#0  0x00007f8b5e6a1f15 pthread_cond_wait@@GLIBC_2.3.2
+TID 4244:
+#0  0x00007f8b5e6a1f15 pthread_cond_wait@@GLIBC_2.3.2
+TID 4245:
+";
+        assert_eq!(
+            summarize_top_frames(eu_stack),
+            [
+                ("pthread_cond_wait".to_string(), 2),
+                ("<no frames>".to_string(), 1),
+                ("__GI___poll".to_string(), 1),
+            ]
+        );
+
+        let gdb = "Thread 2 (Thread 0x7f8b4d3ff700 (LWP 4243)):
+#0  0x00007f8b5e6a1f15 in pthread_cond_wait@@GLIBC_2.3.2 () from /lib64/libpthread.so.0
+#1  0x0000000002a1b2c3 in doris::ThreadPool::dispatch_thread() ()
+Thread 1 (Thread 0x7f8b5f0c1880 (LWP 4242)):
+#0  doris::Daemon::run (this=0x1) at daemon.cpp:12
+";
+        assert_eq!(
+            summarize_top_frames(gdb),
+            [
+                ("doris::Daemon::run".to_string(), 1),
+                ("pthread_cond_wait".to_string(), 1),
+            ]
+        );
+
+        let root = std::env::temp_dir().join(format!("cloud-cli-pstack-{}", std::process::id()));
+        for (tid, stack) in [
+            (
+                "7",
+                "[<0>] futex_wait_queue_me+0xc4/0x120\n[<0>] do_futex+0x1/0x2\n",
+            ),
+            ("8", "[<0>] ep_poll+0x2/0x3\n"),
+        ] {
+            let dir = root.join("42/task").join(tid);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("stack"), stack).unwrap();
         }
+        let trace = read_proc_stacks(&root, 42).unwrap();
+        assert!(trace.starts_with("TID 7:\n[<0>] futex_wait_queue_me"));
+        assert_eq!(
+            summarize_top_frames(&trace),
+            [
+                ("ep_poll".to_string(), 1),
+                ("futex_wait_queue_me".to_string(), 1),
+            ]
+        );
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(is_ptrace_denied(
+            "gdb failed: ptrace: Operation not permitted."
+        ));
+        assert!(!is_ptrace_denied("eu-stack failed: No such process"));
+    }
+
+    #[test]
+    fn pstack_script_is_private_and_never_reused() {
+        use std::os::unix::fs::PermissionsExt;
 
-        Ok(())
+        let dir = tempfile::tempdir().unwrap();
+        let script = PstackTool::write_pstack_script(dir.path()).unwrap();
+        assert_eq!(
+            fs::metadata(&script).unwrap().permissions().mode() & 0o777,
+            0o700
+        );
+        assert!(PstackTool::write_pstack_script(dir.path()).is_err());
     }
 }
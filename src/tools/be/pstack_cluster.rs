@@ -0,0 +1,397 @@
+use super::pstack::{is_ptrace_denied, summarize_top_frames};
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::executor;
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::mysql::ClusterInfo;
+use crate::tools::{ExecutionResult, Impact, Tool};
+use crate::ui;
+use crate::ui::InputHelper;
+use crate::ui::table::TableBuilder;
+use chrono::Utc;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const DEFAULT_PARALLEL_HOSTS: i64 = 4;
+const MAX_PARALLEL_HOSTS: i64 = 32;
+const CONNECT_TIMEOUT_SECS: u32 = 5;
+
+/// Runs on the BE host through the login shell, so it must stay POSIX sh.
+/// Failures print a `cloud-cli:` line on stderr that [`classify_failure`] knows.
+const REMOTE_SCRIPT: &str = r##"pid=$(pgrep -o -x doris_be 2>/dev/null || pidof -s doris_be 2>/dev/null)
+if [ -z "$pid" ]; then
+    echo "cloud-cli: doris_be is not running" >&2
+    exit 3
+fi
+limit=""
+if command -v timeout >/dev/null 2>&1; then limit="timeout 300"; fi
+echo "# doris_be pid: $pid"
+if command -v eu-stack >/dev/null 2>&1; then
+    echo "# backend: eu-stack"
+    $limit eu-stack -p "$pid"
+elif command -v gdb >/dev/null 2>&1; then
+    echo "# backend: gdb"
+    $limit gdb -quiet -nx -batch -p "$pid" -ex "thread apply all bt" | grep -E '^(#|Thread)'
+else
+    echo "cloud-cli: neither eu-stack nor gdb is installed" >&2
+    exit 4
+fi"##;
+
+/// Collects doris_be stacks from every alive BE over SSH
+pub struct ClusterPstackTool;
+
+#[derive(Debug, Clone)]
+struct SshOptions {
+    user: String,
+    key_path: Option<PathBuf>,
+}
+
+impl SshOptions {
+    fn command(&self, host: &str, remote: &str) -> Command {
+        let mut command = Command::new("ssh");
+        command
+            .arg("-o")
+            .arg("BatchMode=yes")
+            .arg("-o")
+            .arg(format!("ConnectTimeout={CONNECT_TIMEOUT_SECS}"))
+            .arg("-o")
+            .arg("ServerAliveInterval=15");
+        if let Some(key) = &self.key_path {
+            command.arg("-i").arg(key);
+        }
+        command.arg(format!("{}@{host}", self.user)).arg(remote);
+        command
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum HostOutcome {
+    Captured {
+        threads: usize,
+        top_frame: Option<String>,
+    },
+    Failed(String),
+}
+
+/// Short reason for a failed ssh or remote pstack run, from its error text
+fn classify_failure(message: &str) -> String {
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("doris_be is not running") {
+        "doris_be is not running".to_string()
+    } else if lower.contains("neither eu-stack nor gdb") {
+        "no eu-stack or gdb installed".to_string()
+    } else if lower.contains("permission denied (") || lower.contains("host key verification") {
+        format!("ssh authentication failed: {}", last_line(message))
+    } else if lower.contains("could not resolve hostname")
+        || lower.contains("connection refused")
+        || lower.contains("connection timed out")
+        || lower.contains("no route to host")
+    {
+        format!("ssh unreachable: {}", last_line(message))
+    } else if is_ptrace_denied(message) {
+        "permission denied attaching to doris_be (run as root or relax ptrace_scope)".to_string()
+    } else {
+        last_line(message).to_string()
+    }
+}
+
+fn last_line(message: &str) -> &str {
+    message
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .unwrap_or("unknown error")
+}
+
+/// Host as a file name; IPv6 colons are not welcome everywhere
+fn host_file_name(host: &str) -> String {
+    let safe: String = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{safe}.txt")
+}
+
+fn render_summary(hosts: &[String], outcomes: &[HostOutcome]) -> String {
+    let mut table = TableBuilder::new(["Host", "Status", "Threads", "Top frame"])
+        .max_width(3, 60)
+        .ascii(true);
+    for (host, outcome) in hosts.iter().zip(outcomes) {
+        match outcome {
+            HostOutcome::Captured { threads, top_frame } => table.push_row([
+                host.clone(),
+                "ok".to_string(),
+                threads.to_string(),
+                top_frame.clone().unwrap_or_default(),
+            ]),
+            HostOutcome::Failed(reason) => {
+                table.push_row([host.clone(), format!("failed: {reason}")])
+            }
+        }
+    }
+    table.render()
+}
+
+impl Tool for ClusterPstackTool {
+    fn name(&self) -> &str {
+        "pstack-cluster"
+    }
+
+    fn description(&self) -> &str {
+        "Collect doris_be stack traces from all BEs over SSH"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
+    fn impact(&self) -> Impact {
+        Impact::ProcessAttach
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        if fs_utils::find_in_path("ssh").is_none() {
+            return Err(CliError::ToolExecutionFailed(
+                "ssh is not installed on this host".to_string(),
+            ));
+        }
+        let info = ClusterInfo::load_from_file()?;
+        let hosts = match super::be_http_client::select_compute_group(&info)? {
+            Some(group) => info.list_be_hosts_in_group(&group),
+            None => info.list_be_hosts(),
+        };
+        if hosts.is_empty() {
+            return Err(CliError::ConfigError(
+                "No alive BE hosts found in clusters.toml".to_string(),
+            ));
+        }
+
+        let options = Self::prompt_ssh_options()?;
+        let parallel = InputHelper::prompt_number_with_default(
+            "Hosts to collect in parallel",
+            DEFAULT_PARALLEL_HOSTS,
+            1,
+            Some(MAX_PARALLEL_HOSTS),
+            Some("hosts"),
+        )? as usize;
+
+        let dir_name = file_name::timestamped("pstack_cluster", "");
+        let dir = config.artifact_path(&dir_name);
+        if !config.stdout_only {
+            fs::create_dir_all(&dir).map_err(|source| CliError::OutputWriteFailed {
+                path: dir.clone(),
+                source,
+            })?;
+        }
+
+        ui::print_info(&format!(
+            "Collecting stacks from {} BE host(s), {} at a time...",
+            hosts.len(),
+            parallel.min(hosts.len())
+        ));
+        let outcomes = Self::collect(config, &options, &hosts, &dir_name, parallel);
+
+        let summary = render_summary(&hosts, &outcomes);
+        fs_utils::write_artifact(
+            config,
+            &format!("{dir_name}/summary.txt"),
+            summary.as_bytes(),
+        )?;
+        if !config.stdout_only {
+            for line in summary.lines() {
+                ui::print_info(line);
+            }
+        }
+
+        let captured = outcomes
+            .iter()
+            .filter(|o| matches!(o, HostOutcome::Captured { .. }))
+            .count();
+        if captured == 0 {
+            return Err(CliError::ToolExecutionFailed(format!(
+                "No stacks captured from {} BE host(s); see the summary above",
+                hosts.len()
+            )));
+        }
+        let message = format!("Captured stacks from {captured}/{} BE host(s)", hosts.len());
+        if config.stdout_only {
+            Ok(ExecutionResult::console(message))
+        } else {
+            Ok(ExecutionResult::new(dir, message))
+        }
+    }
+}
+
+impl ClusterPstackTool {
+    fn prompt_ssh_options() -> Result<SshOptions> {
+        let default_user = std::env::var("USER").unwrap_or_else(|_| "root".to_string());
+        let user = ui::dialogs::input_text("SSH user", &default_user)?
+            .trim()
+            .to_string();
+        let default_key = dirs::home_dir()
+            .into_iter()
+            .flat_map(|home| ["id_ed25519", "id_rsa"].map(|k| home.join(".ssh").join(k)))
+            .find(|p| p.is_file())
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+        let key = ui::dialogs::input_text("SSH key path (empty for ssh defaults)", &default_key)?;
+        let key = key.trim();
+        if !key.is_empty() && !std::path::Path::new(key).is_file() {
+            return Err(CliError::InvalidInput(format!("SSH key not found: {key}")));
+        }
+        Ok(SshOptions {
+            user: if user.is_empty() { default_user } else { user },
+            key_path: (!key.is_empty()).then(|| PathBuf::from(key)),
+        })
+    }
+
+    /// Outcome per host, in `hosts` order, with at most `parallel` hosts in flight
+    fn collect(
+        config: &Config,
+        options: &SshOptions,
+        hosts: &[String],
+        dir_name: &str,
+        parallel: usize,
+    ) -> Vec<HostOutcome> {
+        let workers = parallel.clamp(1, hosts.len().max(1));
+        let next = AtomicUsize::new(0);
+        let mut outcomes: Vec<HostOutcome> = hosts
+            .iter()
+            .map(|_| HostOutcome::Failed("not attempted".to_string()))
+            .collect();
+
+        std::thread::scope(|s| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut local = Vec::new();
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::SeqCst);
+                            let Some(host) = hosts.get(idx) else {
+                                break;
+                            };
+                            let outcome = Self::collect_host(config, options, host, dir_name);
+                            match &outcome {
+                                HostOutcome::Captured { threads, .. } => {
+                                    ui::print_info(&format!("{host}: captured {threads} thread(s)"))
+                                }
+                                HostOutcome::Failed(reason) => {
+                                    ui::print_warning(&format!("{host}: {reason}"))
+                                }
+                            }
+                            local.push((idx, outcome));
+                        }
+                        local
+                    })
+                })
+                .collect();
+            for handle in handles {
+                if let Ok(local) = handle.join() {
+                    for (idx, outcome) in local {
+                        outcomes[idx] = outcome;
+                    }
+                }
+            }
+        });
+        outcomes
+    }
+
+    fn collect_host(
+        config: &Config,
+        options: &SshOptions,
+        host: &str,
+        dir_name: &str,
+    ) -> HostOutcome {
+        let mut precheck = options.command(host, "true");
+        if let Err(e) = executor::execute_command(&mut precheck, "ssh") {
+            return HostOutcome::Failed(classify_failure(&e.to_string()));
+        }
+
+        let mut command = options.command(host, REMOTE_SCRIPT);
+        let output = match executor::execute_command(&mut command, "ssh") {
+            Ok(output) => output,
+            Err(e) => return HostOutcome::Failed(classify_failure(&e.to_string())),
+        };
+        let trace = String::from_utf8_lossy(&output.stdout).to_string();
+        let summary = summarize_top_frames(&trace);
+        let threads: usize = summary.iter().map(|(_, n)| n).sum();
+        if threads == 0 {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return HostOutcome::Failed(classify_failure(&format!(
+                "no stack frames captured\n{stderr}"
+            )));
+        }
+
+        let file = host_file_name(host);
+        let content = format!(
+            "# pstack of doris_be on {host}\n# captured at: {}\n{trace}",
+            Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        if let Err(e) =
+            fs_utils::write_artifact(config, &format!("{dir_name}/{file}"), content.as_bytes())
+        {
+            return HostOutcome::Failed(format!("could not save stacks: {e}"));
+        }
+        HostOutcome::Captured {
+            threads,
+            top_frame: summary.into_iter().next().map(|(f, _)| f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_host_failures_and_renders_summary() {
+        assert_eq!(
+            classify_failure(
+                "ssh failed: ssh: connect to host 10.0.0.7 port 22: Connection refused"
+            ),
+            "ssh unreachable: ssh failed: ssh: connect to host 10.0.0.7 port 22: Connection refused"
+        );
+        assert!(
+            classify_failure("ssh failed: root@10.0.0.7: Permission denied (publickey).")
+                .starts_with("ssh authentication failed")
+        );
+        assert_eq!(
+            classify_failure("ssh failed: cloud-cli: doris_be is not running\n"),
+            "doris_be is not running"
+        );
+        assert_eq!(
+            classify_failure("ssh failed: cloud-cli: neither eu-stack nor gdb is installed"),
+            "no eu-stack or gdb installed"
+        );
+        assert!(
+            classify_failure("no stack frames captured\nptrace: Operation not permitted.")
+                .starts_with("permission denied attaching")
+        );
+        assert_eq!(host_file_name("fe80::1"), "fe80__1.txt");
+
+        let hosts = vec!["10.0.0.7".to_string(), "10.0.0.8".to_string()];
+        let outcomes = vec![
+            HostOutcome::Captured {
+                threads: 812,
+                top_frame: Some("pthread_cond_wait".to_string()),
+            },
+            HostOutcome::Failed("doris_be is not running".to_string()),
+        ];
+        let summary = render_summary(&hosts, &outcomes);
+        assert!(summary.contains("| 10.0.0.7 | ok "));
+        assert!(summary.contains("failed: doris_be is not running"));
+    }
+}
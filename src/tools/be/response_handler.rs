@@ -1,10 +1,9 @@
 use crate::config::Config;
 use crate::error::Result;
 use crate::tools::ExecutionResult;
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
 use crate::ui;
-use chrono::Utc;
-use std::fs;
-use std::path::PathBuf;
 
 /// Configuration for handling BE API responses
 pub struct BeResponseHandler<'a> {
@@ -33,10 +32,9 @@ impl<'a> BeResponseHandler<'a> {
                     println!("{output}");
                 }
 
-                Ok(ExecutionResult {
-                    output_path: PathBuf::from("console_output"),
-                    message: format!("Query completed for: {context}"),
-                })
+                Ok(ExecutionResult::console(format!(
+                    "Query completed for: {context}"
+                )))
             }
             Err(e) => {
                 ui::print_error(&format!("{}: {e}.", self.error_context));
@@ -66,17 +64,11 @@ impl<'a> BeResponseHandler<'a> {
                 if output.trim().is_empty() {
                     ui::print_warning(self.empty_warning);
 
-                    Ok(ExecutionResult {
-                        output_path: PathBuf::from("console_output"),
-                        message: "No data found".to_string(),
-                    })
+                    Ok(ExecutionResult::console("No data found"))
                 } else {
-                    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-
-                    let filename = format!("{file_prefix}_{timestamp}.txt");
-                    let output_path = config.output_dir.join(filename);
-
-                    fs::write(&output_path, &output)?;
+                    let filename = file_name::timestamped(file_prefix, "txt");
+                    let output_path =
+                        fs_utils::write_artifact(config, &filename, output.as_bytes())?;
 
                     println!("{}", summary_fn(&output));
 
@@ -86,10 +78,7 @@ impl<'a> BeResponseHandler<'a> {
                         output_path.display()
                     );
 
-                    Ok(ExecutionResult {
-                        output_path,
-                        message,
-                    })
+                    Ok(ExecutionResult::new(output_path, message))
                 }
             }
             Err(e) => {
@@ -0,0 +1,290 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::config_loader::{DorisConfig, Environment, regex_utils};
+use crate::error::{CliError, Result};
+use crate::tools::be::be_http_client;
+use crate::tools::mysql::MySQLTool;
+use crate::tools::mysql::parser::{parse_key_value_pairs, split_into_blocks};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+const MODES: &[&str] = &[
+    "Dump all effective settings",
+    "Look up a key (prefix match)",
+];
+
+/// Effective `key = value` line of a conf file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfEntry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// Settings that take effect, sorted by key; for duplicate keys the later line wins
+pub fn effective_entries(content: &str) -> Vec<ConfEntry> {
+    let mut entries = BTreeMap::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let (Some(key), Some(value)) = (
+            regex_utils::extract_key_from_line(line),
+            regex_utils::extract_value_from_line(line),
+        ) {
+            entries.insert(
+                key.clone(),
+                ConfEntry {
+                    key,
+                    value,
+                    line: idx + 1,
+                },
+            );
+        }
+    }
+    entries.into_values().collect()
+}
+
+/// Entries whose key starts with `prefix`
+pub fn lookup<'a>(entries: &'a [ConfEntry], prefix: &str) -> Vec<&'a ConfEntry> {
+    entries
+        .iter()
+        .filter(|e| e.key.starts_with(prefix))
+        .collect()
+}
+
+/// BE `/varz` lists one `key=value` per line
+pub fn parse_varz(body: &str) -> HashMap<String, String> {
+    body.lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .filter(|(k, _)| !k.is_empty() && !k.contains(['<', ' ']))
+        .collect()
+}
+
+/// Runtime FE settings, one vertical block per key
+pub const FRONTEND_CONFIG_SQL: &str = "ADMIN SHOW FRONTEND CONFIG \\G";
+
+/// One FE setting; `default` is only known on releases that report that column
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendConfigValue {
+    pub value: String,
+    pub default: Option<String>,
+}
+
+impl FrontendConfigValue {
+    pub fn is_non_default(&self) -> bool {
+        self.default.as_ref().is_some_and(|d| *d != self.value)
+    }
+}
+
+/// [`FRONTEND_CONFIG_SQL`] output by key
+pub fn parse_frontend_config(output: &str) -> HashMap<String, FrontendConfigValue> {
+    split_into_blocks(output)
+        .iter()
+        .map(|block| parse_key_value_pairs(block))
+        .filter_map(|mut fields| {
+            let key = fields.remove("Key").filter(|k| !k.is_empty())?;
+            let value = fields.remove("Value").unwrap_or_default();
+            let default = ["Default", "DefaultValue"]
+                .iter()
+                .find_map(|col| fields.remove(*col));
+            Some((key, FrontendConfigValue { value, default }))
+        })
+        .collect()
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches('"')
+        .split_whitespace()
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Runtime value when it differs from the file, meaning a restart is pending
+pub fn runtime_mismatch<'a>(
+    entry: &ConfEntry,
+    runtime: &'a HashMap<String, String>,
+) -> Option<&'a str> {
+    runtime
+        .get(&entry.key)
+        .filter(|live| normalize(live) != normalize(&entry.value))
+        .map(String::as_str)
+}
+
+/// View fe.conf or be.conf and compare it with the values the process runs with
+pub struct ConfFileInspectorTool {
+    pub env: Environment,
+}
+
+impl Tool for ConfFileInspectorTool {
+    fn name(&self) -> &str {
+        match self.env {
+            Environment::BE => "be-conf-inspector",
+            _ => "fe-conf-inspector",
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Show effective conf file settings and flag keys pending a restart"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let path = self.conf_path(&doris);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            CliError::ConfigError(format!("Failed to read {}: {e}", path.display()))
+        })?;
+        let entries = effective_entries(&content);
+
+        let shown: Vec<&ConfEntry> = if ui::dialogs::select_index("Inspect", MODES)? == 0 {
+            entries.iter().collect()
+        } else {
+            let prefix = ui::dialogs::input_text("Key", "")?;
+            lookup(&entries, prefix.trim())
+        };
+        if shown.is_empty() {
+            ui::print_warning("No matching keys in the conf file.");
+            return Ok(ExecutionResult::console("No matching keys"));
+        }
+
+        let runtime = self.runtime_values(&doris);
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let width = shown.iter().map(|e| e.key.len()).max().unwrap_or(0);
+        let mut pending = 0usize;
+        ui::print_info("");
+        for entry in &shown {
+            ui::print_info(&format!(
+                "{file_name}:{:<5} {:<width$} = {}",
+                entry.line, entry.key, entry.value
+            ));
+            if let Some(live) = runtime.as_ref().and_then(|r| runtime_mismatch(entry, r)) {
+                pending += 1;
+                ui::print_warning(&format!(
+                    "      runtime value is '{live}' (restart pending)"
+                ));
+            }
+        }
+
+        let mut message = format!("{} key(s) from {}", shown.len(), path.display());
+        if runtime.is_some() {
+            message.push_str(&format!(", {pending} differ from runtime"));
+        }
+        Ok(ExecutionResult::console(message))
+    }
+}
+
+impl ConfFileInspectorTool {
+    fn conf_path(&self, doris: &DorisConfig) -> PathBuf {
+        let (install_dir, file) = match self.env {
+            Environment::BE => (doris.be_install_dir.as_ref(), "be.conf"),
+            _ => (doris.fe_install_dir.as_ref(), "fe.conf"),
+        };
+        match install_dir {
+            Some(dir) => dir.join("conf").join(file),
+            None => doris.conf_dir.join(file),
+        }
+    }
+
+    /// Live values, or None (with a note) when the process cannot be asked
+    fn runtime_values(&self, doris: &DorisConfig) -> Option<HashMap<String, String>> {
+        if self.env != Environment::BE && doris.mysql.is_none() {
+            ui::print_info("No MySQL credentials; runtime comparison skipped.");
+            return None;
+        }
+        match runtime_values(self.env, doris) {
+            Ok(values) if !values.is_empty() => Some(values),
+            Ok(_) => None,
+            Err(e) => {
+                ui::print_info(&format!("Runtime comparison skipped: {e}"));
+                None
+            }
+        }
+    }
+}
+
+/// Values the process runs with: BE `/varz`, or `SHOW FRONTEND CONFIG` for FE
+pub fn runtime_values(env: Environment, doris: &DorisConfig) -> Result<HashMap<String, String>> {
+    match env {
+        Environment::BE => {
+            be_http_client::request_be_webserver_port("/varz", None).map(|body| parse_varz(&body))
+        }
+        _ => MySQLTool::query_sql_with_config(doris, FRONTEND_CONFIG_SQL).map(|output| {
+            parse_frontend_config(&output)
+                .into_iter()
+                .map(|(key, v)| (key, v.value))
+                .collect()
+        }),
+    }
+}
+
+/// Entries whose runtime value differs from the file, with that runtime value
+pub fn pending_restart<'a>(
+    entries: &'a [ConfEntry],
+    runtime: &'a HashMap<String, String>,
+) -> Vec<(&'a ConfEntry, &'a str)> {
+    entries
+        .iter()
+        .filter_map(|e| runtime_mismatch(e, runtime).map(|live| (e, live)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_lines_win_and_mismatches_are_flagged() {
+        let content = "# comment\n\
+            webserver_port = 8040\n\
+            \n\
+            mem_limit = 80%  # inline\n\
+            mem_limit = 90%\n\
+            enable_stream_load_record = \"true\"\n";
+        let entries = effective_entries(content);
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            ["enable_stream_load_record", "mem_limit", "webserver_port"]
+        );
+        assert_eq!(entries[1].value, "90%");
+        assert_eq!(entries[1].line, 5);
+        assert_eq!(lookup(&entries, "mem").len(), 1);
+
+        let runtime =
+            parse_varz("mem_limit=80%\nenable_stream_load_record=TRUE\nwebserver_port=8040\n");
+        assert_eq!(runtime_mismatch(&entries[1], &runtime), Some("80%"));
+        assert_eq!(runtime_mismatch(&entries[0], &runtime), None);
+        let pending = pending_restart(&entries, &runtime);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            (pending[0].0.key.as_str(), pending[0].1),
+            ("mem_limit", "80%")
+        );
+
+        let fe = parse_frontend_config(
+            "*************************** 1. row ***************************
+         Key: qe_max_connection
+       Value: 1024
+        Type: int
+DefaultValue: 1024
+*************************** 2. row ***************************
+         Key: edit_log_type
+       Value: bdb
+",
+        );
+        assert_eq!(fe["qe_max_connection"].value, "1024");
+        assert!(!fe["qe_max_connection"].is_non_default());
+        assert_eq!(fe["edit_log_type"].default, None);
+    }
+}
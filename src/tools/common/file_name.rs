@@ -0,0 +1,93 @@
+use chrono::Utc;
+
+/// UTC time stamped into artifact names, e.g. `20240115_102345`
+pub fn timestamp() -> String {
+    Utc::now().format("%Y%m%d_%H%M%S").to_string()
+}
+
+/// Whether `name` is a per-session run directory, `<date>_<time>[_<tag>]`
+pub fn is_run_dir_name(name: &str) -> bool {
+    let b = name.as_bytes();
+    let digits = |r: std::ops::Range<usize>| b[r].iter().all(u8::is_ascii_digit);
+    b.len() >= 15
+        && digits(0..8)
+        && b[8] == b'_'
+        && digits(9..15)
+        && b.get(15).is_none_or(|c| *c == b'_')
+}
+
+/// `<prefix>_<timestamp>.<extension>`, the name every tool gives a new artifact.
+/// An empty `extension` names a directory.
+pub fn timestamped(prefix: &str, extension: &str) -> String {
+    timestamped_at(prefix, &timestamp(), extension)
+}
+
+/// [`timestamped`] with a given timestamp, for artifacts written together
+pub fn timestamped_at(prefix: &str, timestamp: &str, extension: &str) -> String {
+    let name = match extension {
+        "" => format!("{prefix}_{timestamp}"),
+        ext => format!("{prefix}_{timestamp}.{ext}"),
+    };
+    sanitize(&name)
+}
+
+/// `name` with anything but ASCII letters, digits, `-`, `.` and `_` replaced by `_`,
+/// so it is a single safe path component on every filesystem
+pub fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `name` behind the non-empty `labels` (session tag, production profile),
+/// e.g. `SD-4821_prod-bj_jstack_1234_20240115_102345.log`
+pub fn labeled(labels: &[Option<&str>], name: &str) -> String {
+    let mut out = String::new();
+    for label in labels.iter().flatten() {
+        let label = sanitize(label.trim());
+        if !label.is_empty() {
+            out.push_str(&label);
+            out.push('_');
+        }
+    }
+    out.push_str(&sanitize(name));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_labeled_and_filesystem_safe() {
+        assert_eq!(
+            timestamped_at("be_vars_10.0.0.2:8040", "20240115_102345", "txt"),
+            "be_vars_10.0.0.2_8040_20240115_102345.txt"
+        );
+        assert_eq!(
+            timestamped_at("pstack_cluster", "20240115_102345", ""),
+            "pstack_cluster_20240115_102345"
+        );
+        assert_eq!(
+            labeled(&[Some("SD-4821"), Some("prod bj")], "jstack_1.log"),
+            "SD-4821_prod_bj_jstack_1.log"
+        );
+        assert_eq!(
+            labeled(&[Some("case/42 "), None, Some("")], "a.txt"),
+            "case_42_a.txt"
+        );
+        assert_eq!(labeled(&[], "序列.txt"), "__.txt");
+        assert_eq!(labeled(&[None], ""), "");
+
+        assert!(is_run_dir_name("20240115_102345"));
+        assert!(is_run_dir_name("20240115_102345_SD-4821"));
+        assert!(!is_run_dir_name("20240115_102345.log"));
+        assert!(!is_run_dir_name("jstack_20240115_102345"));
+    }
+}
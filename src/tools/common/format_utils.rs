@@ -3,8 +3,16 @@ pub fn format_bytes(bytes: u64, precision: usize, show_original: bool) -> String
     const KB: f64 = 1024.0;
     const MB: f64 = KB * 1024.0;
     const GB: f64 = MB * 1024.0;
+    const TB: f64 = GB * 1024.0;
 
-    if bytes >= GB as u64 {
+    if bytes >= TB as u64 {
+        let formatted = format!("{:.precision$} TB", bytes as f64 / TB);
+        if show_original {
+            format!("{} ({bytes} bytes)", formatted)
+        } else {
+            formatted
+        }
+    } else if bytes >= GB as u64 {
         let formatted = format!("{:.precision$} GB", bytes as f64 / GB);
         if show_original {
             format!("{} ({bytes} bytes)", formatted)
@@ -31,3 +39,38 @@ pub fn format_bytes(bytes: u64, precision: usize, show_original: bool) -> String
         format!("{} B", bytes)
     }
 }
+
+/// Quote a CSV field when it contains a separator, quote or newline
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Parse a Doris size string such as `1.234 GB`, `2.5TB` or `512` into bytes.
+///
+/// Units are binary (`KB` and `KiB` both mean 1024); unparseable input is 0.
+pub fn parse_size(input: &str) -> u64 {
+    let s = input.trim().replace(',', "");
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let Ok(num) = num.parse::<f64>() else {
+        return 0;
+    };
+
+    let unit = unit.trim().to_ascii_lowercase();
+    let exponent = match unit.trim_end_matches("ib").trim_end_matches('b') {
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        _ => 0,
+    };
+
+    (num * 1024f64.powi(exponent)) as u64
+}
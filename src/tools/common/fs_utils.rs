@@ -1,5 +1,7 @@
-use crate::error::Result;
+use crate::config::Config;
+use crate::error::{CliError, Result};
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 /// A generic utility to serialize a struct to a TOML file.
@@ -8,12 +10,25 @@ pub fn save_toml_to_file<T: serde::Serialize>(obj: &T, file_path: &Path) -> Resu
         crate::error::CliError::ConfigError(format!("Failed to serialize to TOML: {e}"))
     })?;
     ensure_dir_exists(file_path)?;
-    fs::write(file_path, toml_str).map_err(|e| {
+    write_atomic(file_path, toml_str.as_bytes()).map_err(|e| {
         crate::error::CliError::ConfigError(format!("Failed to write to file: {e}"))
     })?;
     Ok(())
 }
 
+/// Write through a sibling temp file and rename it over `path`, so readers
+/// never observe a partially written file.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".tmp.{}", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    if let Err(e) = fs::write(&tmp_path, bytes).and_then(|_| fs::rename(&tmp_path, path)) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
 /// Ensures that the directory for a given path exists, creating it if necessary.
 pub fn ensure_dir_exists(path: &Path) -> Result<()> {
     if let Some(parent) = path.parent()
@@ -26,6 +41,67 @@ pub fn ensure_dir_exists(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Write a tool artifact into the output directory.
+///
+/// In stdout-only mode the content is printed between delimiters instead and the
+/// console sentinel path is returned.
+pub fn write_artifact(config: &Config, file_name: &str, content: &[u8]) -> Result<PathBuf> {
+    if config.stdout_only {
+        let mut out = std::io::stdout().lock();
+        writeln!(out, "----- BEGIN {file_name} -----")?;
+        out.write_all(content)?;
+        if !content.ends_with(b"\n") {
+            writeln!(out)?;
+        }
+        writeln!(out, "----- END {file_name} -----")?;
+        return Ok(PathBuf::from(crate::tools::CONSOLE_OUTPUT));
+    }
+
+    config.ensure_output_dir()?;
+    let output_path = config.artifact_path(file_name);
+    fs::write(&output_path, content).map_err(|source| CliError::OutputWriteFailed {
+        path: output_path.clone(),
+        source,
+    })?;
+    Ok(output_path)
+}
+
+/// Size in bytes of a written artifact; `None` for directories or missing files
+pub fn output_size(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+}
+
+/// Error for tools whose binary artifacts cannot be printed in stdout-only mode
+pub fn stdout_only_unsupported(tool_name: &str, artifact: &str) -> CliError {
+    CliError::ToolExecutionFailed(format!(
+        "{tool_name} produces a {artifact} file and cannot run in stdout-only mode; \
+         unset CLOUD_CLI_STDOUT_ONLY or point OUTPUT_DIR at a writable directory"
+    ))
+}
+
+/// First executable named `cmd` on `PATH`
+pub fn find_in_path(cmd: &str) -> Option<PathBuf> {
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(cmd))
+            .find(|p| p.is_file())
+    })
+}
+
+/// Bytes available to this user on the filesystem holding `dir`
+pub fn available_space(dir: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let path = std::ffi::CString::new(dir.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
 /// Gets the path to the user's configuration directory for this application.
 pub fn get_user_config_dir() -> Result<std::path::PathBuf> {
     dirs::home_dir()
@@ -95,3 +171,26 @@ pub fn collect_fe_logs(dir: &Path) -> Result<Vec<PathBuf>> {
 pub fn collect_be_logs(dir: &Path) -> Result<Vec<PathBuf>> {
     collect_log_files(dir, "be.INFO")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stdout_only_mode_does_not_touch_output_dir() {
+        let dir = std::env::temp_dir().join(format!("cloud-cli-stdout-{}", std::process::id()));
+        let mut config = Config::default().with_output_dir(&dir);
+        config.stdout_only = true;
+
+        let path = write_artifact(&config, "histo.log", b"num  #instances\n").unwrap();
+        assert_eq!(path, PathBuf::from(crate::tools::CONSOLE_OUTPUT));
+        assert!(!dir.exists());
+
+        config.stdout_only = false;
+        let path = write_artifact(&config, "histo.log", b"num  #instances\n").unwrap();
+        assert_eq!(path, config.run_dir_path().join("histo.log"));
+        assert_eq!(path.parent().and_then(Path::parent), Some(dir.as_path()));
+        assert!(path.exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Mutex;
 
 static SELECTED_FE_HOST: OnceCell<Mutex<Option<String>>> = OnceCell::new();
 static SELECTED_BE_HOST: OnceCell<Mutex<Option<String>>> = OnceCell::new();
+static SELECTED_COMPUTE_GROUP: OnceCell<Mutex<Option<String>>> = OnceCell::new();
 
 fn storage(cell: &OnceCell<Mutex<Option<String>>>) -> &Mutex<Option<String>> {
     cell.get_or_init(|| Mutex::new(None))
@@ -19,6 +20,18 @@ pub fn set_selected_host(is_be: bool, host: String) {
     }
 }
 
+/// Drop the selection so tools fall back to the configured connection
+pub fn clear_selected_host(is_be: bool) {
+    let cell = if is_be {
+        &SELECTED_BE_HOST
+    } else {
+        &SELECTED_FE_HOST
+    };
+    if let Ok(mut guard) = storage(cell).lock() {
+        *guard = None;
+    }
+}
+
 pub fn get_selected_host(is_be: bool) -> Option<String> {
     let cell = if is_be {
         &SELECTED_BE_HOST
@@ -27,3 +40,17 @@ pub fn get_selected_host(is_be: bool) -> Option<String> {
     };
     storage(cell).lock().ok().and_then(|g| g.clone())
 }
+
+/// Compute group the selected BE host was picked from; `None` outside cloud mode
+pub fn set_selected_compute_group(group: Option<String>) {
+    if let Ok(mut guard) = storage(&SELECTED_COMPUTE_GROUP).lock() {
+        *guard = group;
+    }
+}
+
+pub fn get_selected_compute_group() -> Option<String> {
+    storage(&SELECTED_COMPUTE_GROUP)
+        .lock()
+        .ok()
+        .and_then(|g| g.clone())
+}
@@ -0,0 +1,208 @@
+use std::process::Command;
+
+use crate::config_loader::{self, DorisConfig};
+use crate::error::{CliError, Result};
+use crate::executor;
+use crate::tools::mysql::CredentialManager;
+
+/// Written by curl after the body so the status can be split off
+const STATUS_MARKER: &str = "\n__http_status__:";
+
+/// Scheme, basic auth and certificate policy for FE/BE HTTP calls, from the
+/// `https`, `http_insecure` and `http_auth_user`/`http_auth_password` settings
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HttpOptions {
+    pub https: bool,
+    /// Accept self-signed certificates (`curl --insecure`)
+    pub insecure: bool,
+    /// User and decrypted password sent as basic auth
+    pub auth: Option<(String, String)>,
+}
+
+/// Status and body of one answered request
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+impl HttpOptions {
+    /// Options stored in `doris`; a password that cannot be decrypted is an error
+    /// rather than a silent anonymous request
+    pub fn from_config(doris: &DorisConfig) -> Result<Self> {
+        let auth = match (&doris.http_auth_user, &doris.http_auth_password) {
+            (Some(user), password) if !user.is_empty() => {
+                let password = match password {
+                    Some(encrypted) => CredentialManager::load()?.decrypt_password(encrypted)?,
+                    None => String::new(),
+                };
+                Some((user.clone(), password))
+            }
+            _ => None,
+        };
+        Ok(Self {
+            https: doris.https,
+            insecure: doris.http_insecure,
+            auth,
+        })
+    }
+
+    /// Options of the current configuration
+    pub fn load() -> Result<Self> {
+        Self::from_config(&config_loader::load_config()?)
+    }
+
+    /// `http://` or `https://` URL of `path` on `host:port`
+    pub fn url(&self, host: &str, port: u16, path: &str) -> String {
+        let scheme = if self.https { "https" } else { "http" };
+        format!("{scheme}://{host}:{port}{path}")
+    }
+
+    /// `curl -sS` with the TLS flags; callers add the rest. With auth, curl reads
+    /// its config from stdin (`-K -`), which must then be [`Self::curl_config`]
+    pub fn curl(&self) -> Command {
+        let mut curl = Command::new("curl");
+        curl.arg("-sS");
+        if self.insecure {
+            curl.arg("--insecure");
+        }
+        if self.auth.is_some() {
+            curl.args(["-K", "-"]);
+        }
+        curl
+    }
+
+    /// curl config carrying the basic auth, so the password stays out of argv
+    pub fn curl_config(&self) -> Option<String> {
+        let (user, password) = self.auth.as_ref()?;
+        Some(format!(
+            "user = \"{}\"\n",
+            curl_quote(&format!("{user}:{password}"))
+        ))
+    }
+
+    /// GET `url`, giving up after `max_secs` when set.
+    ///
+    /// Connection failures are errors; 401 and 403 become
+    /// [`CliError::HttpAuthFailed`], other statuses are left to the caller.
+    pub fn get(&self, url: &str, max_secs: Option<u64>) -> Result<HttpResponse> {
+        let mut curl = self.curl();
+        if let Some(secs) = max_secs {
+            curl.args(["--max-time", &secs.to_string()]);
+        }
+        curl.args(["-w", &format!("{STATUS_MARKER}%{{http_code}}"), url]);
+        let output = match self.curl_config() {
+            Some(config) => {
+                executor::execute_command_with_input(&mut curl, "curl", config.as_bytes())?
+            }
+            None => executor::execute_command(&mut curl, "curl")?,
+        };
+        let response = split_status(output.stdout);
+        match response.status {
+            401 | 403 => Err(CliError::HttpAuthFailed {
+                url: redact_query(url),
+                status: response.status,
+                with_credentials: self.auth.is_some(),
+            }),
+            _ => Ok(response),
+        }
+    }
+}
+
+fn split_status(mut stdout: Vec<u8>) -> HttpResponse {
+    let marker = STATUS_MARKER.as_bytes();
+    let at = stdout
+        .windows(marker.len())
+        .rposition(|w| w == marker)
+        .unwrap_or(stdout.len());
+    let status = String::from_utf8_lossy(stdout.get(at + marker.len()..).unwrap_or_default())
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    stdout.truncate(at);
+    HttpResponse {
+        status,
+        body: stdout,
+    }
+}
+
+/// Escape `value` for a double-quoted curl config parameter
+fn curl_quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// `url` without its query string, which may carry tokens
+fn redact_query(url: &str) -> String {
+    url.split('?').next().unwrap_or(url).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_urls_and_splits_the_status_off_the_body() {
+        let plain = HttpOptions::default();
+        assert_eq!(plain.url("be1", 8040, "/memz"), "http://be1:8040/memz");
+        let tls = HttpOptions {
+            https: true,
+            ..Default::default()
+        };
+        assert_eq!(tls.url("fe1", 8030, "/metrics"), "https://fe1:8030/metrics");
+
+        let response = split_status(b"line 1\nline 2\n__http_status__:200".to_vec());
+        assert_eq!(response.status, 200);
+        assert_eq!(response.text(), "line 1\nline 2");
+        assert!(response.is_success());
+
+        let empty = split_status(b"\n__http_status__:401".to_vec());
+        assert_eq!((empty.status, empty.body.len()), (401, 0));
+        // No marker means curl wrote nothing useful
+        assert_eq!(split_status(b"garbage".to_vec()).status, 0);
+
+        assert_eq!(
+            redact_query("https://be1:8040/api/_load_error_log?file=x&token=y"),
+            "https://be1:8040/api/_load_error_log"
+        );
+    }
+
+    #[test]
+    fn credentials_go_through_stdin_not_argv() {
+        let options = HttpOptions {
+            auth: Some(("ops".to_string(), r#"p"w\d"#.to_string())),
+            ..Default::default()
+        };
+        let args: Vec<String> = options
+            .curl()
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(args, ["-sS", "-K", "-"]);
+        assert_eq!(
+            options.curl_config().as_deref(),
+            Some("user = \"ops:p\\\"w\\\\d\"\n")
+        );
+        assert_eq!(HttpOptions::default().curl_config(), None);
+    }
+}
@@ -1,12 +1,33 @@
 use crate::config::Config;
+use crate::core::interrupt;
 use crate::error::{CliError, Result};
 use crate::executor;
-use crate::tools::{ExecutionResult, Tool};
-use chrono::Utc;
+use crate::tools::common::{file_name, format_utils, fs_utils, retention};
+use crate::tools::{ExecutionResult, Impact, Tool};
+use crate::ui::{self, FormatHelper, InputHelper, InteractiveSelector};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
+
+/// Every HPROF file starts with "JAVA PROFILE 1.0.1" or "JAVA PROFILE 1.0.2"
+const HPROF_MAGIC: &[u8] = b"JAVA PROFILE 1.0.";
+
+/// `   1:   123456   12345678  java.lang.String` (JDK 8) or with a trailing
+/// ` (java.base@17.0.2)` module (JDK 9+)
+static HISTO_ROW_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*\d+:\s+(\d+)\s+(\d+)\s+(\S+)").unwrap());
+const HISTO_DIFF_TOP: usize = 30;
+const DEFAULT_DIFF_WAIT_SECS: u32 = 300;
+const MAX_DIFF_WAIT_SECS: u32 = 3600;
 
 pub struct JmapDumpTool;
 pub struct JmapHistoTool;
+pub struct JmapHistoDiffTool;
 
 impl Tool for JmapDumpTool {
     fn name(&self) -> &str {
@@ -17,30 +38,264 @@ impl Tool for JmapDumpTool {
         "Generate heap dump (.hprof)"
     }
 
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
+    fn impact(&self) -> Impact {
+        Impact::ProcessAttach
+    }
+
     fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
+        if config.stdout_only {
+            return Err(fs_utils::stdout_only_unsupported(
+                self.name(),
+                "binary .hprof",
+            ));
+        }
         config.ensure_output_dir()?;
+        check_free_space(&config.output_dir, pid)?;
+        let filename = file_name::timestamped(&format!("jmap_dump_{pid}"), "hprof");
+        let output_path = config.artifact_path(&filename);
+
+        let live = prompt_live_only()?;
+        let used = run_heap_dump(config, pid, &output_path, live, self.name())?;
+
+        let size = verify_hprof(&output_path).map_err(|reason| {
+            // A truncated dump only wastes the space a retry needs
+            let _ = fs::remove_file(&output_path);
+            write_failed(&output_path, format!("{reason}; the file was removed"))
+        })?;
+        let checksum = sha256(&output_path);
 
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("jmap_dump_{pid}_{timestamp}.hprof");
-        let output_path = config.output_dir.join(filename);
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "Heap dump completed successfully via {} ({}, timeout: {}s), {}, sha256 {}",
+                used.name(),
+                if live { "live objects" } else { "all objects" },
+                config.timeout_seconds,
+                format_utils::format_bytes(size, 2, false),
+                checksum.as_deref().unwrap_or("unavailable")
+            ),
+        ))
+    }
+}
 
-        let jmap_path = config.get_jmap_path();
-        let file_path = output_path.display();
-        let dump_arg = format!("live,file={file_path}");
+/// Output of jmap/jcmd when the JVM refuses the attach, e.g. after a JDK upgrade
+/// left jmap from another build, or when /tmp/.java_pid<pid> is unusable
+const ATTACH_ERRORS: &[&str] = &[
+    "AttachNotSupportedException",
+    "Unable to open socket file",
+    "well-known file is not secure",
+    "Unable to attach",
+];
 
-        let mut command = Command::new(&jmap_path);
-        command.args([format!("-dump:{dump_arg}"), pid.to_string()]);
+/// JDK command that writes the heap dump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpCommand {
+    Jmap,
+    Jcmd,
+}
 
-        executor::execute_command_with_timeout(&mut command, self.name(), config)?;
+impl DumpCommand {
+    pub fn name(self) -> &'static str {
+        match self {
+            DumpCommand::Jmap => "jmap",
+            DumpCommand::Jcmd => "jcmd",
+        }
+    }
 
-        Ok(ExecutionResult {
-            output_path,
-            message: format!(
-                "Heap dump completed successfully (timeout: {}s)",
-                config.timeout_seconds
+    fn path(self, config: &Config) -> PathBuf {
+        match self {
+            DumpCommand::Jmap => config.get_jmap_path(),
+            DumpCommand::Jcmd => config.get_jcmd_path(),
+        }
+    }
+}
+
+/// Arguments for dumping `pid` into `file`; `live` forces a full GC first
+pub fn dump_args(command: DumpCommand, pid: u32, file: &Path, live: bool) -> Vec<String> {
+    match command {
+        DumpCommand::Jmap => {
+            let scope = if live { "live," } else { "" };
+            vec![
+                format!("-dump:{scope}file={}", file.display()),
+                pid.to_string(),
+            ]
+        }
+        DumpCommand::Jcmd => {
+            let mut args = vec![pid.to_string(), "GC.heap_dump".to_string()];
+            if !live {
+                args.push("-all".to_string());
+            }
+            args.push(file.display().to_string());
+            args
+        }
+    }
+}
+
+pub fn is_attach_error(message: &str) -> bool {
+    ATTACH_ERRORS.iter().any(|e| message.contains(e))
+}
+
+fn prompt_live_only() -> Result<bool> {
+    let choice = ui::dialogs::select_index(
+        "Objects to include in the heap dump",
+        &[
+            "Live objects only (forces a full GC first)",
+            "All objects (no GC, larger file)",
+        ],
+    )?;
+    Ok(choice == 0)
+}
+
+/// Dump with jmap, or with jcmd when jmap is missing or cannot attach
+fn run_heap_dump(
+    config: &Config,
+    pid: u32,
+    output_path: &Path,
+    live: bool,
+    tool_name: &str,
+) -> Result<DumpCommand> {
+    let jcmd_available = config.get_jcmd_path().is_file();
+    if config.get_jmap_path().is_file() {
+        match run_dump_command(config, DumpCommand::Jmap, pid, output_path, live, tool_name) {
+            Ok(()) => return Ok(DumpCommand::Jmap),
+            Err(e) if jcmd_available && is_attach_error(&e.to_string()) => {
+                ui::print_warning("jmap could not attach to the process; retrying with jcmd");
+                let _ = fs::remove_file(output_path);
+            }
+            Err(e) => return Err(e),
+        }
+    } else if jcmd_available {
+        ui::print_info(&format!(
+            "jmap not found at {}; using jcmd",
+            config.get_jmap_path().display()
+        ));
+    } else {
+        return Err(CliError::ToolExecutionFailed(format!(
+            "Neither jmap nor jcmd found in {}. Set JDK_PATH to a full JDK.",
+            config.jdk_path.join("bin").display()
+        )));
+    }
+    run_dump_command(config, DumpCommand::Jcmd, pid, output_path, live, tool_name)?;
+    Ok(DumpCommand::Jcmd)
+}
+
+/// Run one dump command with its stderr kept, so a failure says why
+fn run_dump_command(
+    config: &Config,
+    dump: DumpCommand,
+    pid: u32,
+    output_path: &Path,
+    live: bool,
+    tool_name: &str,
+) -> Result<()> {
+    // jcmd resolves relative paths against the JVM's working directory
+    let file = std::path::absolute(output_path).unwrap_or_else(|_| output_path.to_path_buf());
+    // Unnamed and already unlinked, so nothing else can open or swap it
+    let mut stderr_file = tempfile::tempfile().map_err(CliError::IoError)?;
+
+    let mut command = Command::new(dump.path(config));
+    command
+        .args(dump_args(dump, pid, &file, live))
+        .stderr(stderr_file.try_clone().map_err(CliError::IoError)?);
+    let partial = executor::PartialArtifact::track(&file);
+    let result = executor::execute_command_with_timeout(&mut command, tool_name, config);
+    drop(partial);
+
+    let mut stderr = String::new();
+    let _ = stderr_file
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| stderr_file.read_to_string(&mut stderr));
+    match result {
+        Err(e @ CliError::Timeout { .. }) => {
+            if fs::remove_file(&file).is_ok() {
+                ui::print_warning(&format!("Removed incomplete {}", file.display()));
+            }
+            Err(e)
+        }
+        Err(CliError::ToolExecutionFailed(msg)) if !stderr.trim().is_empty() => Err(
+            CliError::ToolExecutionFailed(format!("{msg}: {}", stderr.trim())),
+        ),
+        other => other.map(|_| ()),
+    }
+}
+
+fn write_failed(path: &Path, reason: String) -> CliError {
+    CliError::OutputWriteFailed {
+        path: path.to_path_buf(),
+        source: io::Error::new(io::ErrorKind::StorageFull, reason),
+    }
+}
+
+/// Resident memory of `pid` from /proc; a live dump is rarely larger than this
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    parse_vm_rss(&status)
+}
+
+/// `VmRSS:   123456 kB` in bytes
+pub fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Refuse to start when the output filesystem cannot hold even half the RSS;
+/// warn when it holds less than the full RSS
+fn check_free_space(dir: &Path, pid: u32) -> Result<()> {
+    let (Some(free), Some(rss)) = (fs_utils::available_space(dir), process_rss_bytes(pid)) else {
+        return Ok(());
+    };
+    let fmt = |b| format_utils::format_bytes(b, 2, false);
+    if free < rss / 2 {
+        return Err(write_failed(
+            dir,
+            format!(
+                "only {} free, the heap dump of pid {pid} needs about {}",
+                fmt(free),
+                fmt(rss)
             ),
-        })
+        ));
+    }
+    if free < rss {
+        ui::print_warning(&format!(
+            "Only {} free for a process using {}; the dump may not fit.",
+            fmt(free),
+            fmt(rss)
+        ));
+    }
+    Ok(())
+}
+
+/// Size of a complete-looking HPROF file, or why it is not one
+pub fn verify_hprof(path: &Path) -> std::result::Result<u64, String> {
+    let size = fs::metadata(path)
+        .map_err(|e| format!("heap dump missing: {e}"))?
+        .len();
+    if size == 0 {
+        return Err("heap dump is empty".to_string());
     }
+    let mut header = vec![0u8; HPROF_MAGIC.len()];
+    fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .map_err(|_| format!("heap dump is truncated ({size} bytes)"))?;
+    if header != HPROF_MAGIC {
+        return Err("heap dump has no HPROF header".to_string());
+    }
+    Ok(size)
+}
+
+fn sha256(path: &Path) -> Option<String> {
+    let mut command = Command::new("sha256sum");
+    command.arg(path);
+    let output = executor::execute_command(&mut command, "sha256sum").ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
 }
 
 impl Tool for JmapHistoTool {
@@ -52,26 +307,392 @@ impl Tool for JmapHistoTool {
         "Generate histogram (.log)"
     }
 
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
+    fn impact(&self) -> Impact {
+        Impact::ProcessAttach
+    }
+
     fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
-        config.ensure_output_dir()?;
+        let output_path = take_histo(config, pid, self.name())?.0;
 
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("jmap_histo_{pid}_{timestamp}.log");
-        let output_path = config.output_dir.join(filename);
+        Ok(ExecutionResult::new(
+            output_path,
+            "Histogram completed successfully",
+        ))
+    }
+}
 
-        let jmap_path = config.get_jmap_path();
+/// Run `jmap -histo:live` and save it; returns the file and its content
+fn take_histo(config: &Config, pid: u32, tool_name: &str) -> Result<(PathBuf, String)> {
+    let filename = file_name::timestamped(&format!("jmap_histo_{pid}"), "log");
 
-        let mut command = Command::new(&jmap_path);
-        command.args(["-histo:live", &pid.to_string()]);
+    let jmap_path = config.get_jmap_path();
 
-        // Use regular execution for histogram as it's typically fast
-        let output = executor::execute_command(&mut command, self.name())?;
+    let mut command = Command::new(&jmap_path);
+    command.args(["-histo:live", &pid.to_string()]);
 
-        std::fs::write(&output_path, &output.stdout).map_err(CliError::IoError)?;
+    // Use regular execution for histogram as it's typically fast
+    let output = executor::execute_command(&mut command, tool_name)?;
 
-        Ok(ExecutionResult {
-            output_path,
-            message: "Histogram completed successfully".to_string(),
+    let output_path = fs_utils::write_artifact(config, &filename, &output.stdout)?;
+    Ok((
+        output_path,
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    ))
+}
+
+/// Instances and bytes per class, summed over classes loaded more than once
+pub fn parse_histo(content: &str) -> HashMap<String, (u64, u64)> {
+    let mut classes: HashMap<String, (u64, u64)> = HashMap::new();
+    for caps in content.lines().filter_map(|l| HISTO_ROW_RE.captures(l)) {
+        let (Ok(instances), Ok(bytes)) = (caps[1].parse::<u64>(), caps[2].parse::<u64>()) else {
+            continue;
+        };
+        let entry = classes.entry(caps[3].to_string()).or_default();
+        entry.0 += instances;
+        entry.1 += bytes;
+    }
+    classes
+}
+
+/// Change of one class between two histograms
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoDelta {
+    pub class: String,
+    pub instances_before: u64,
+    pub instances_after: u64,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl HistoDelta {
+    pub fn instance_growth(&self) -> i64 {
+        self.instances_after as i64 - self.instances_before as i64
+    }
+
+    pub fn byte_growth(&self) -> i64 {
+        self.bytes_after as i64 - self.bytes_before as i64
+    }
+}
+
+pub fn diff_histos(
+    before: &HashMap<String, (u64, u64)>,
+    after: &HashMap<String, (u64, u64)>,
+) -> Vec<HistoDelta> {
+    let mut classes: Vec<&String> = before.keys().chain(after.keys()).collect();
+    classes.sort();
+    classes.dedup();
+    classes
+        .into_iter()
+        .map(|class| {
+            let (ib, bb) = before.get(class).copied().unwrap_or_default();
+            let (ia, ba) = after.get(class).copied().unwrap_or_default();
+            HistoDelta {
+                class: class.clone(),
+                instances_before: ib,
+                instances_after: ia,
+                bytes_before: bb,
+                bytes_after: ba,
+            }
         })
+        .collect()
+}
+
+/// Classes with the largest positive growth by `key`
+pub fn top_growth(
+    deltas: &[HistoDelta],
+    key: impl Fn(&HistoDelta) -> i64,
+    limit: usize,
+) -> Vec<&HistoDelta> {
+    let mut growing: Vec<&HistoDelta> = deltas.iter().filter(|d| key(d) > 0).collect();
+    growing.sort_by_key(|d| std::cmp::Reverse(key(d)));
+    growing.truncate(limit);
+    growing
+}
+
+fn render_growth(title: &str, rows: &[&HistoDelta]) -> String {
+    let mut out = format!(
+        "{title}\n{:>14} {:>14} {:>14} {:>14}  {}\n{}\n",
+        "+Instances",
+        "Instances",
+        "+Bytes",
+        "Bytes",
+        "Class",
+        "-".repeat(110)
+    );
+    if rows.is_empty() {
+        out.push_str("  (no growth)\n");
+    }
+    for d in rows {
+        out.push_str(&format!(
+            "{:>+14} {:>14} {:>14} {:>14}  {}\n",
+            d.instance_growth(),
+            d.instances_after,
+            format!(
+                "{}{}",
+                if d.byte_growth() < 0 { "-" } else { "+" },
+                format_utils::format_bytes(d.byte_growth().unsigned_abs(), 1, false)
+            ),
+            format_utils::format_bytes(d.bytes_after, 1, false),
+            FormatHelper::truncate_string(&d.class, 50)
+        ));
+    }
+    out
+}
+
+pub fn render_histo_diff(before: &str, after: &str, deltas: &[HistoDelta]) -> String {
+    format!(
+        "Histogram diff\n  before: {before}\n  after:  {after}\n\n{}\n{}",
+        render_growth(
+            &format!("Top {HISTO_DIFF_TOP} classes by instance growth"),
+            &top_growth(deltas, HistoDelta::instance_growth, HISTO_DIFF_TOP)
+        ),
+        render_growth(
+            &format!("Top {HISTO_DIFF_TOP} classes by byte growth"),
+            &top_growth(deltas, HistoDelta::byte_growth, HISTO_DIFF_TOP)
+        )
+    )
+}
+
+/// Histogram files in the output and run directories, newest first
+fn existing_histos(config: &Config) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = retention::artifact_dirs(&config.output_dir)
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.contains("jmap_histo_") && n.ends_with(".log"))
+        })
+        .collect();
+    files.sort_by_key(|p| fs::metadata(p).and_then(|m| m.modified()).ok());
+    files.reverse();
+    files
+}
+
+fn pick_histo(files: &[PathBuf], title: &str) -> Result<PathBuf> {
+    let labels: Vec<String> = files
+        .iter()
+        .map(|p| {
+            p.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    let picked = InteractiveSelector::new(labels.clone(), title.to_string())
+        .select()?
+        .clone();
+    let pos = labels.iter().position(|l| *l == picked).unwrap_or(0);
+    Ok(files[pos].clone())
+}
+
+fn read_histo(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(CliError::IoError)
+}
+
+/// Sleep `secs`, returning false if the user pressed Ctrl-C
+fn wait_between_snapshots(secs: u32) -> bool {
+    interrupt::catching(|| interrupt::sleep_unless_interrupted(Duration::from_secs(secs.into())))
+}
+
+impl JmapHistoDiffTool {
+    fn snapshots(config: &Config, pid: u32, tool_name: &str) -> Result<[(PathBuf, String); 2]> {
+        let secs = InputHelper::prompt_duration_secs(
+            "Seconds between snapshots",
+            DEFAULT_DIFF_WAIT_SECS,
+            MAX_DIFF_WAIT_SECS,
+        )?;
+
+        let first = take_histo(config, pid, tool_name)?;
+        ui::print_info(&format!(
+            "First snapshot saved to {}; waiting {secs}s (Ctrl-C to stop)...",
+            first.0.display()
+        ));
+        if !wait_between_snapshots(secs) {
+            return Err(CliError::GracefulExit);
+        }
+        let second = take_histo(config, pid, tool_name)?;
+        Ok([first, second])
+    }
+
+    fn existing(config: &Config) -> Result<[(PathBuf, String); 2]> {
+        let files = existing_histos(config);
+        if files.len() < 2 {
+            return Err(CliError::ToolExecutionFailed(format!(
+                "Need two histogram files in {}, found {}",
+                config.output_dir.display(),
+                files.len()
+            )));
+        }
+        let before = pick_histo(&files, "Earlier histogram")?;
+        let after = pick_histo(&files, "Later histogram")?;
+        Ok([
+            (before.clone(), read_histo(&before)?),
+            (after.clone(), read_histo(&after)?),
+        ])
+    }
+}
+
+impl Tool for JmapHistoDiffTool {
+    fn name(&self) -> &str {
+        "jmap-histo-diff"
+    }
+
+    fn description(&self) -> &str {
+        "Diff two histograms to find growing classes"
+    }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
+    fn impact(&self) -> Impact {
+        Impact::ProcessAttach
+    }
+
+    fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
+        let mode = ui::dialogs::select_index(
+            "Histograms to compare",
+            &[
+                "Take two snapshots now",
+                "Pick two existing files from the output directory",
+            ],
+        )?;
+        let [(before_path, before), (after_path, after)] = if mode == 0 {
+            Self::snapshots(config, pid, self.name())?
+        } else {
+            Self::existing(config)?
+        };
+
+        let (before_classes, after_classes) = (parse_histo(&before), parse_histo(&after));
+        if before_classes.is_empty() || after_classes.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No class rows found; is this jmap -histo output?".to_string(),
+            ));
+        }
+        let deltas = diff_histos(&before_classes, &after_classes);
+        let report = render_histo_diff(
+            &before_path.display().to_string(),
+            &after_path.display().to_string(),
+            &deltas,
+        );
+        ui::print_info("");
+        ui::print_info(&report);
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("jmap_histo_diff", "txt"),
+            report.as_bytes(),
+        )?;
+        let growing = deltas.iter().filter(|d| d.instance_growth() > 0).count();
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("{growing} class(es) grew in instance count"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_hprof_header_and_rss() {
+        let dir = std::env::temp_dir().join(format!("cloud-cli-hprof-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let good = dir.join("good.hprof");
+        fs::write(&good, b"JAVA PROFILE 1.0.2\0\0\0\0\x08").unwrap();
+        assert_eq!(verify_hprof(&good), Ok(23));
+
+        let empty = dir.join("empty.hprof");
+        fs::write(&empty, b"").unwrap();
+        assert_eq!(verify_hprof(&empty).unwrap_err(), "heap dump is empty");
+
+        let short = dir.join("short.hprof");
+        fs::write(&short, b"JAVA PRO").unwrap();
+        assert!(verify_hprof(&short).unwrap_err().contains("truncated"));
+
+        let other = dir.join("other.hprof");
+        fs::write(&other, b"Dumping heap to /tmp/x.hprof ...").unwrap();
+        assert!(
+            verify_hprof(&other)
+                .unwrap_err()
+                .contains("no HPROF header")
+        );
+        fs::remove_dir_all(&dir).ok();
+
+        let status = "Name:\tjava\nVmPeak:\t 9000 kB\nVmRSS:\t  2048 kB\nThreads:\t12\n";
+        assert_eq!(parse_vm_rss(status), Some(2048 * 1024));
+    }
+
+    #[test]
+    fn dump_arguments_follow_the_object_scope() {
+        let file = Path::new("/out/jmap_dump_42.hprof");
+        assert_eq!(
+            dump_args(DumpCommand::Jmap, 42, file, true),
+            ["-dump:live,file=/out/jmap_dump_42.hprof", "42"]
+        );
+        assert_eq!(
+            dump_args(DumpCommand::Jmap, 42, file, false),
+            ["-dump:file=/out/jmap_dump_42.hprof", "42"]
+        );
+        assert_eq!(
+            dump_args(DumpCommand::Jcmd, 42, file, true),
+            ["42", "GC.heap_dump", "/out/jmap_dump_42.hprof"]
+        );
+        assert_eq!(
+            dump_args(DumpCommand::Jcmd, 42, file, false),
+            ["42", "GC.heap_dump", "-all", "/out/jmap_dump_42.hprof"]
+        );
+
+        assert!(is_attach_error(
+            "jmap failed with exit code: 1: com.sun.tools.attach.AttachNotSupportedException: \
+             Unable to open socket file /proc/42/root/tmp/.java_pid42"
+        ));
+        assert!(!is_attach_error("jmap failed with exit code: 1"));
+    }
+
+    #[test]
+    fn diffs_jdk8_and_jdk17_histograms() {
+        let jdk8 = " num     #instances         #bytes  class name
+----------------------------------------------
+   1:        100000        8000000  [C
+   2:         50000        1200000  java.lang.String
+   3:            10            640  org.apache.doris.Cache
+   4:             5            320  org.apache.doris.Cache
+Total        150015        9200960
+";
+        let jdk17 = " num     #instances         #bytes  class name (module)
+-------------------------------------------------------
+   1:        120000        9600000  [C (java.base@17.0.2)
+   2:         45000        1080000  java.lang.String (java.base@17.0.2)
+   3:          9000         576000  org.apache.doris.Cache
+   4:             1             16  org.apache.doris.New
+Total        174001       11256016
+";
+        let before = parse_histo(jdk8);
+        assert_eq!(before.len(), 3);
+        assert_eq!(before["org.apache.doris.Cache"], (15, 960));
+        let after = parse_histo(jdk17);
+        assert_eq!(after["[C"], (120000, 9600000));
+
+        let deltas = diff_histos(&before, &after);
+        let by_instances = top_growth(&deltas, HistoDelta::instance_growth, 30);
+        let names: Vec<&str> = by_instances.iter().map(|d| d.class.as_str()).collect();
+        assert_eq!(
+            names,
+            ["[C", "org.apache.doris.Cache", "org.apache.doris.New"]
+        );
+        assert_eq!(by_instances[1].instance_growth(), 8985);
+        let by_bytes = top_growth(&deltas, HistoDelta::byte_growth, 1);
+        assert_eq!(by_bytes[0].byte_growth(), 1_600_000);
+        assert!(render_histo_diff("a", "b", &deltas).contains("Top 30 classes by byte growth"));
     }
 }
@@ -0,0 +1,344 @@
+use crate::error::{CliError, Result};
+use console::Term;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// Bytes processed across all scan workers
+#[derive(Debug, Default)]
+pub struct ScanProgress {
+    processed: AtomicU64,
+    total: u64,
+}
+
+impl ScanProgress {
+    pub fn new(total: u64) -> Self {
+        Self {
+            processed: AtomicU64::new(0),
+            total,
+        }
+    }
+
+    pub fn add(&self, bytes: u64) {
+        self.processed.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn processed(&self) -> u64 {
+        self.processed.load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// Merged outcome of scanning a set of log files
+#[derive(Debug)]
+pub struct ScanOutcome<T> {
+    pub entries: Vec<T>,
+    pub failures: Vec<(PathBuf, String)>,
+}
+
+/// Entries reach the file a little before its mtime moves; keep files this close
+/// to the window so a slow flush never hides the oldest entries of the window
+const MTIME_SLACK: Duration = Duration::from_secs(10 * 60);
+
+/// Drop files last written before the last `recent` of log activity began.
+///
+/// Logs are append-only, so every entry of a file predates its mtime; a file
+/// whose mtime is older than the newest mtime minus `recent` cannot hold an
+/// entry inside a window anchored on the newest entry. Files without readable
+/// metadata are kept so the scan reports them.
+pub fn files_within_recent(files: &[PathBuf], recent: Duration) -> Vec<PathBuf> {
+    let with_mtime: Vec<(PathBuf, Option<SystemTime>)> = files
+        .iter()
+        .map(|p| (p.clone(), fs::metadata(p).and_then(|m| m.modified()).ok()))
+        .collect();
+    keep_recent(with_mtime, recent)
+}
+
+fn keep_recent(files: Vec<(PathBuf, Option<SystemTime>)>, recent: Duration) -> Vec<PathBuf> {
+    let Some(newest) = files.iter().filter_map(|(_, m)| *m).max() else {
+        return files.into_iter().map(|(p, _)| p).collect();
+    };
+    let cutoff = newest
+        .checked_sub(recent + MTIME_SLACK)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    files
+        .into_iter()
+        .filter(|(_, mtime)| mtime.is_none_or(|m| m >= cutoff))
+        .map(|(p, _)| p)
+        .collect()
+}
+
+/// [`files_within_recent`] for a window of `minutes`, telling the user what was skipped
+pub fn prefilter_recent_minutes(files: Vec<PathBuf>, minutes: i64) -> Vec<PathBuf> {
+    let recent = Duration::from_secs(minutes.max(0) as u64 * 60);
+    let kept = files_within_recent(&files, recent);
+    let skipped = files.len() - kept.len();
+    if skipped > 0 {
+        crate::ui::print_info(&format!(
+            "Skipping {skipped} file(s) last written before the last {minutes} min"
+        ));
+    }
+    kept
+}
+
+/// Default worker count: half of the available CPUs, at least one
+pub fn default_scan_workers() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| (n.get() / 2).max(1))
+        .unwrap_or(1)
+}
+
+/// Read `path` line by line, feeding each line to `on_line` and recording bytes read.
+pub fn for_each_line<F>(path: &Path, progress: &ScanProgress, mut on_line: F) -> Result<()>
+where
+    F: FnMut(&str),
+{
+    let f = fs::File::open(path).map_err(CliError::IoError)?;
+    let reader = BufReader::new(f);
+
+    for line_result in reader.lines() {
+        let line = line_result.map_err(CliError::IoError)?;
+        progress.add(line.len() as u64 + 1);
+        on_line(&line);
+    }
+
+    Ok(())
+}
+
+/// Scan files on a bounded pool of workers.
+///
+/// Each file is scanned by `scan` into its own Vec. Results are concatenated in input
+/// file order and then stable-sorted by `sort_key`, so the output does not depend on
+/// the worker count. IO errors and panics are reported per file without dropping
+/// results from the other files.
+pub fn scan_files_parallel<T, K, F, S>(
+    files: &[PathBuf],
+    max_workers: usize,
+    show_progress: bool,
+    scan: F,
+    sort_key: S,
+) -> ScanOutcome<T>
+where
+    T: Send,
+    K: Ord,
+    F: Fn(&Path, &ScanProgress) -> Result<Vec<T>> + Sync,
+    S: FnMut(&T) -> K,
+{
+    let total_bytes = files
+        .iter()
+        .filter_map(|p| fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum();
+    let progress = ScanProgress::new(total_bytes);
+    let workers = max_workers.clamp(1, files.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicBool::new(false);
+    let mut slots: Vec<Option<Result<Vec<T>>>> = (0..files.len()).map(|_| None).collect();
+
+    std::thread::scope(|s| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut local = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::SeqCst);
+                        if idx >= files.len() {
+                            break;
+                        }
+                        let path = &files[idx];
+                        let result =
+                            panic::catch_unwind(AssertUnwindSafe(|| scan(path, &progress)))
+                                .unwrap_or_else(|_| {
+                                    Err(CliError::ToolExecutionFailed(
+                                        "worker panicked while scanning".into(),
+                                    ))
+                                });
+                        local.push((idx, result));
+                    }
+                    local
+                })
+            })
+            .collect();
+
+        let reporter = show_progress.then(|| s.spawn(|| report_progress(&progress, &done)));
+
+        for handle in handles {
+            if let Ok(local) = handle.join() {
+                for (idx, result) in local {
+                    slots[idx] = Some(result);
+                }
+            }
+        }
+
+        done.store(true, Ordering::SeqCst);
+        if let Some(reporter) = reporter {
+            let _ = reporter.join();
+        }
+    });
+
+    let mut entries = Vec::new();
+    let mut failures = Vec::new();
+    for (path, slot) in files.iter().zip(slots) {
+        match slot {
+            Some(Ok(mut items)) => entries.append(&mut items),
+            Some(Err(e)) => failures.push((path.clone(), e.to_string())),
+            None => failures.push((path.clone(), "file was not scanned".to_string())),
+        }
+    }
+    entries.sort_by_key(sort_key);
+
+    ScanOutcome { entries, failures }
+}
+
+/// Warn about files that could not be scanned
+pub fn print_scan_failures(failures: &[(PathBuf, String)]) {
+    report_scan_failures(failures, &crate::ui::sink::ConsoleSink);
+}
+
+/// [`print_scan_failures`] into any sink
+pub fn report_scan_failures(
+    failures: &[(PathBuf, String)],
+    sink: &dyn crate::ui::sink::OutputSink,
+) {
+    for (path, err) in failures {
+        sink.warning(&format!("Skipped {}: {err}", path.display()));
+    }
+}
+
+fn report_progress(progress: &ScanProgress, done: &AtomicBool) {
+    let term = Term::stderr();
+    while !done.load(Ordering::SeqCst) {
+        let line = progress_line(progress);
+        let _ = term.clear_line();
+        let _ = term.write_str(&line);
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    let _ = term.clear_line();
+}
+
+fn progress_line(progress: &ScanProgress) -> String {
+    let processed = progress.processed().min(progress.total());
+    let pct = if progress.total() > 0 {
+        processed * 100 / progress.total()
+    } else {
+        100
+    };
+    format!(
+        "Scanning logs: {pct:>3}% ({} / {})",
+        super::format_utils::format_bytes(processed, 1, false),
+        super::format_utils::format_bytes(progress.total(), 1, false)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_logs(dir: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for f in 0..6 {
+            let path = dir.join(format!("fe.log.{f}"));
+            let mut content = String::new();
+            for i in 0..500 {
+                // Interleave timestamps across files so the merge actually reorders entries
+                content.push_str(&format!("{:06} file={f} line={i}\n", (i * 7 + f * 3) % 997));
+            }
+            fs::write(&path, content).unwrap();
+            files.push(path);
+        }
+        files
+    }
+
+    fn scan_numbered(path: &Path, progress: &ScanProgress) -> Result<Vec<(u32, String)>> {
+        let mut out = Vec::new();
+        for_each_line(path, progress, |line| {
+            if let Some(ts) = line.split(' ').next().and_then(|t| t.parse().ok()) {
+                out.push((ts, line.to_string()));
+            }
+        })?;
+        Ok(out)
+    }
+
+    #[test]
+    fn parallel_scan_matches_serial_scan() {
+        let dir = std::env::temp_dir().join(format!("cloud-cli-log-scan-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let files = write_logs(&dir);
+
+        let serial = scan_files_parallel(&files, 1, false, scan_numbered, |e| e.0);
+        let parallel = scan_files_parallel(&files, 4, false, scan_numbered, |e| e.0);
+
+        assert_eq!(serial.entries.len(), 3000);
+        assert_eq!(serial.entries, parallel.entries);
+        assert!(parallel.failures.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn files_older_than_the_window_are_skipped() {
+        let now = SystemTime::now();
+        let ago = |mins: u64| Some(now - Duration::from_secs(mins * 60));
+        let files = vec![
+            (PathBuf::from("fe.audit.log.20240501-1"), ago(600)),
+            (PathBuf::from("fe.audit.log.20240501-2"), ago(65)),
+            (PathBuf::from("fe.audit.log.20240501-3"), None),
+            (PathBuf::from("fe.audit.log"), ago(1)),
+        ];
+        let kept = keep_recent(files, Duration::from_secs(60 * 60));
+        assert_eq!(
+            kept,
+            [
+                PathBuf::from("fe.audit.log.20240501-2"),
+                PathBuf::from("fe.audit.log.20240501-3"),
+                PathBuf::from("fe.audit.log"),
+            ]
+        );
+    }
+
+    #[test]
+    fn per_file_failures_keep_other_results() {
+        let dir =
+            std::env::temp_dir().join(format!("cloud-cli-log-scan-fail-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut files = write_logs(&dir);
+        files.insert(2, dir.join("missing.log"));
+
+        let outcome = scan_files_parallel(
+            &files,
+            3,
+            false,
+            |path, progress| {
+                if path.ends_with("fe.log.4") {
+                    panic!("boom");
+                }
+                scan_numbered(path, progress)
+            },
+            |e| e.0,
+        );
+
+        assert_eq!(outcome.entries.len(), 2500);
+        assert_eq!(outcome.failures.len(), 2);
+        assert!(
+            outcome
+                .failures
+                .iter()
+                .any(|(p, _)| p.ends_with("missing.log"))
+        );
+        assert!(
+            outcome
+                .failures
+                .iter()
+                .any(|(p, e)| p.ends_with("fe.log.4") && e.contains("panicked"))
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
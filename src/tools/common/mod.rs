@@ -1,4 +1,11 @@
+pub mod conf_inspector;
+pub mod file_name;
 pub mod format_utils;
 pub mod fs_utils;
 pub mod host_selection;
+pub mod http;
 pub mod jmap;
+pub mod log_scan;
+pub mod progress;
+pub mod redact;
+pub mod retention;
@@ -0,0 +1,180 @@
+use crate::ui::Verbosity;
+use crate::ui::sink::OutputSink;
+use console::Term;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Items between summary lines when the animated bar is disabled
+const SUMMARY_EVERY: usize = 50;
+const BAR_TEMPLATE: &str =
+    "{prefix} [{bar:30.cyan}] {pos}/{len} {msg} elapsed {elapsed_precise} eta {eta}";
+
+#[derive(Debug, Default)]
+struct BatchState {
+    done: usize,
+    current: String,
+    failures: Vec<(String, String)>,
+}
+
+/// Progress of a batch of named items processed by several worker threads.
+///
+/// Renders an `indicatif` bar on stderr, or periodic summary lines when
+/// animation is disabled; quiet verbosity drops both and verbose prints one
+/// line per item instead. Failures are collected instead of printed so they
+/// do not interleave with the bar.
+#[derive(Debug)]
+pub struct BatchProgress {
+    label: String,
+    total: usize,
+    /// None when animation is disabled or stderr is not a terminal
+    bar: Option<ProgressBar>,
+    started: Instant,
+    state: Mutex<BatchState>,
+}
+
+impl BatchProgress {
+    pub fn new(label: &str, total: usize, animated: bool) -> Self {
+        let bar = (animated && Term::stderr().is_term()).then(|| {
+            let bar =
+                ProgressBar::with_draw_target(Some(total as u64), ProgressDrawTarget::stderr());
+            if let Ok(style) = ProgressStyle::with_template(BAR_TEMPLATE) {
+                bar.set_style(style.progress_chars("#-"));
+            }
+            bar.set_prefix(label.to_string());
+            bar
+        });
+        Self {
+            label: label.to_string(),
+            total,
+            bar,
+            started: Instant::now(),
+            state: Mutex::new(BatchState::default()),
+        }
+    }
+
+    /// Record one finished item; `error` is kept for the final summary
+    pub fn finish_item(&self, name: &str, error: Option<String>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.done += 1;
+        state.current = name.to_string();
+        if let Some(e) = error {
+            state.failures.push((name.to_string(), e));
+        }
+
+        // Render while holding the lock so workers never interleave output
+        let verbosity = crate::ui::verbosity();
+        if verbosity == Verbosity::Quiet {
+            return;
+        }
+        if verbosity >= Verbosity::Verbose {
+            let failed = state
+                .failures
+                .last()
+                .filter(|(failed, _)| *failed == state.current);
+            crate::ui::print_verbose(&format!(
+                "{} {}/{}: {}{}",
+                self.label,
+                state.done,
+                self.total,
+                state.current,
+                failed
+                    .map(|(_, e)| format!(" failed: {e}"))
+                    .unwrap_or_default()
+            ));
+        } else if let Some(bar) = &self.bar {
+            bar.set_message(crate::ui::FormatHelper::truncate_string(&state.current, 40));
+            bar.set_position(state.done as u64);
+        } else if state.done % SUMMARY_EVERY == 0 || state.done == self.total {
+            crate::ui::print_info(&format!(
+                "{}: {}/{} done, {} failed, elapsed {}",
+                self.label,
+                state.done,
+                self.total,
+                state.failures.len(),
+                format_duration(self.started.elapsed())
+            ));
+        }
+    }
+
+    /// Clear the bar and return the collected failures in completion order
+    pub fn finish(self) -> Vec<(String, String)> {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+        self.state
+            .into_inner()
+            .map(|s| s.failures)
+            .unwrap_or_default()
+    }
+}
+
+/// Format as mm:ss, or h:mm:ss past one hour
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Print collected failures as a two-column table
+pub fn print_failure_summary(failures: &[(String, String)]) {
+    report_failure_summary(failures, &crate::ui::sink::ConsoleSink);
+}
+
+/// [`print_failure_summary`] into any sink
+pub fn report_failure_summary(failures: &[(String, String)], sink: &dyn OutputSink) {
+    if failures.is_empty() {
+        return;
+    }
+    sink.warning(&format!("{} item(s) failed:", failures.len()));
+    let name_width = failures
+        .iter()
+        .map(|(n, _)| n.len())
+        .max()
+        .unwrap_or(0)
+        .clamp(10, 48);
+    sink.info(&format!("  {:<name_width$}  Error", "Item"));
+    sink.info(&format!("  {}", "-".repeat(name_width + 60)));
+    for (name, err) in failures {
+        sink.info(&format!(
+            "  {:<name_width$}  {}",
+            crate::ui::FormatHelper::truncate_string(name, name_width),
+            crate::ui::FormatHelper::truncate_string(err.lines().next().unwrap_or(""), 60)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_formatting() {
+        assert_eq!(format_duration(Duration::from_secs(75)), "01:15");
+        assert_eq!(format_duration(Duration::from_secs(3725)), "1:02:05");
+    }
+
+    #[test]
+    fn failures_are_collected_across_threads() {
+        let progress = BatchProgress::new("Tables", 40, false);
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let progress = &progress;
+                s.spawn(move || {
+                    for i in 0..10 {
+                        let err = (i == 3).then(|| format!("worker {t} failed"));
+                        progress.finish_item(&format!("db.t{t}_{i}"), err);
+                    }
+                });
+            }
+        });
+        let failures = progress.finish();
+        assert_eq!(failures.len(), 4);
+        assert!(failures.iter().all(|(name, _)| name.ends_with("_3")));
+    }
+}
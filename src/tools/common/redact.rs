@@ -0,0 +1,86 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Shown in place of a masked value
+pub const MASK: &str = "******";
+
+/// Argument names whose values are secrets: `-Dssl.keyStorePassword`, `--token`,
+/// `s3.secret_key` and such. Matched against the name with dashes stripped.
+static SENSITIVE_NAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(password|passwd|pwd|secret|token|credentials?|key)$")
+        .expect("valid sensitive name pattern")
+});
+
+/// Whether the argument `name` holds a secret
+pub fn is_sensitive(name: &str) -> bool {
+    let name = name.trim_start_matches('-');
+    let name = name.strip_prefix('D').unwrap_or(name);
+    SENSITIVE_NAME.is_match(name)
+}
+
+/// `command` with secret argument values replaced by [`MASK`].
+///
+/// Handles `name=value` (including `-Dname=value` and `--name=value`) and
+/// `--name value`. Spacing is kept, and masking an already masked command is a
+/// no-op, so redacted and raw commands can be compared after redacting both.
+pub fn redact_command(command: &str) -> String {
+    let mut out = Vec::new();
+    let mut mask_next = false;
+    for token in command.split(' ') {
+        if token.is_empty() {
+            out.push(String::new());
+            continue;
+        }
+        if std::mem::take(&mut mask_next) && !token.starts_with('-') {
+            out.push(MASK.to_string());
+            continue;
+        }
+        match token.split_once('=') {
+            Some((name, value)) if !value.is_empty() && is_sensitive(name) => {
+                out.push(format!("{name}={MASK}"));
+            }
+            Some(_) => out.push(token.to_string()),
+            None => {
+                mask_next = token.starts_with("--") && is_sensitive(token);
+                out.push(token.to_string());
+            }
+        }
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_secret_arguments_in_every_form() {
+        assert_eq!(
+            redact_command("java -Djavax.net.ssl.keyStorePassword=s3cr3t -Xmx8g"),
+            "java -Djavax.net.ssl.keyStorePassword=****** -Xmx8g"
+        );
+        assert_eq!(
+            redact_command("doris_be --password hunter2 --daemon"),
+            "doris_be --password ****** --daemon"
+        );
+        assert_eq!(
+            redact_command("doris_be --password=hunter2"),
+            "doris_be --password=******"
+        );
+        assert_eq!(
+            redact_command("java -Ds3.secret_key=abc -Dapi.token=t -Dfoo.pwd=p"),
+            "java -Ds3.secret_key=****** -Dapi.token=****** -Dfoo.pwd=******"
+        );
+
+        // Paths to key stores and flags without values are left alone
+        let plain = "java -Djavax.net.ssl.keyStore=/etc/ks.jks --password  -Xmx8g";
+        assert_eq!(redact_command(plain), plain);
+        assert_eq!(
+            redact_command("/opt/doris/fe/lib/doris-fe.jar"),
+            "/opt/doris/fe/lib/doris-fe.jar"
+        );
+
+        let once = redact_command("java -Dx.password=y");
+        assert_eq!(redact_command(&once), once);
+    }
+}
@@ -0,0 +1,416 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::tools::common::{file_name, format_utils};
+use crate::ui;
+
+/// File name prefixes written by cloud-cli tools; nothing else is ever deleted
+const MANAGED_PREFIXES: &[&str] = &[
+    "jmap_",
+    "jstack_",
+    "pstack_",
+    "memz_",
+    "mem_tracker_",
+    "routine_load_",
+    "pipeline_tasks_",
+    "be_cpu_",
+    "be_perf_",
+    "be_disk_usage_",
+    "be_out_tail_",
+    "be_vars_",
+    "be_compaction_",
+    "replica_health_",
+    "fe_gc_events_",
+    "fe_audit_queries_",
+    "fe_alter_jobs_",
+    "query_profile_",
+    "fe_config_drift_",
+    "fe_metrics_",
+    "tablet_lookup_",
+    "fe_editlog_",
+    "cluster_version_",
+    "fe_data_size_",
+    "fe_workload_groups_",
+    "doctor_",
+    "meta_service_",
+];
+/// Managed prefixes of the files each service's tools write
+const FE_PREFIXES: &[&str] = &[
+    "jmap_",
+    "jstack_",
+    "routine_load_",
+    "replica_health_",
+    "fe_gc_events_",
+    "fe_audit_queries_",
+    "fe_alter_jobs_",
+    "query_profile_",
+    "fe_config_drift_",
+    "fe_metrics_",
+    "tablet_lookup_",
+    "fe_editlog_",
+    "cluster_version_",
+    "fe_data_size_",
+    "fe_workload_groups_",
+    "meta_service_",
+    "doctor_",
+];
+const BE_PREFIXES: &[&str] = &[
+    "pstack_",
+    "jmap_",
+    "memz_",
+    "mem_tracker_",
+    "pipeline_tasks_",
+    "be_cpu_",
+    "be_perf_",
+    "be_disk_usage_",
+    "be_out_tail_",
+    "be_vars_",
+    "be_compaction_",
+    "doctor_",
+];
+/// Subdirectories owned entirely by cloud-cli
+const MANAGED_DIRS: &[&str] = &["table-info"];
+
+/// Set once the user has reviewed a dry-run listing in this session
+static DRY_RUN_REVIEWED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RetentionPolicy {
+    pub max_age: Option<Duration>,
+    pub max_total_bytes: Option<u64>,
+}
+
+impl RetentionPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_age: config
+                .max_output_age_days
+                .map(|d| Duration::from_secs(d * 24 * 3600)),
+            max_total_bytes: config.max_output_total_bytes,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_age.is_some() || self.max_total_bytes.is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Whether a top-level output file name was produced by a cloud-cli tool.
+///
+/// `profile_prefix` is the production profile stamp (`<profile>_`), if any.
+pub fn is_managed_name(name: &str, profile_prefix: &str) -> bool {
+    let name = name.strip_prefix(profile_prefix).unwrap_or(name);
+    MANAGED_PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+/// File name prefixes written by the tools of `service` ("FE" or "BE")
+pub fn service_prefixes(service: &str) -> &'static [&'static str] {
+    match service {
+        "BE" => BE_PREFIXES,
+        _ => FE_PREFIXES,
+    }
+}
+
+/// Files to delete: everything past `max_age`, then the oldest remaining ones
+/// until the total fits in `max_total_bytes`
+pub fn plan_cleanup(
+    mut files: Vec<OutputFile>,
+    policy: &RetentionPolicy,
+    now: SystemTime,
+) -> Vec<OutputFile> {
+    files.sort_by_key(|f| f.modified);
+    let (mut expired, mut kept): (Vec<_>, Vec<_>) = files.into_iter().partition(|f| {
+        policy
+            .max_age
+            .is_some_and(|max| now.duration_since(f.modified).is_ok_and(|age| age > max))
+    });
+
+    if let Some(limit) = policy.max_total_bytes {
+        let mut total: u64 = kept.iter().map(|f| f.size).sum();
+        let over = kept
+            .iter()
+            .take_while(|f| {
+                let exceeded = total > limit;
+                total = total.saturating_sub(f.size);
+                exceeded
+            })
+            .count();
+        expired.extend(kept.drain(..over));
+    }
+    expired
+}
+
+/// Regular files in `output_dir` that cloud-cli owns, including those in run
+/// directories and managed directories; symlinks are never followed
+pub fn collect_managed_files(output_dir: &Path, profile_prefix: &str) -> Vec<OutputFile> {
+    collect(output_dir, profile_prefix, false, None)
+}
+
+/// What retention may delete: each run directory as one entry (sized by its
+/// contents, dated by its newest file) except `keep`, plus managed files left
+/// by the flat layout
+pub fn collect_cleanup_units(
+    output_dir: &Path,
+    profile_prefix: &str,
+    keep: Option<&Path>,
+) -> Vec<OutputFile> {
+    collect(output_dir, profile_prefix, true, keep)
+}
+
+/// `output_dir` followed by every run directory in it, for tools that look
+/// for their own earlier artifacts
+pub fn artifact_dirs(output_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![output_dir.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(output_dir) {
+        dirs.extend(
+            entries
+                .flatten()
+                .filter(|e| is_run_dir(&e.path(), &e.file_name().to_string_lossy()))
+                .map(|e| e.path()),
+        );
+    }
+    dirs
+}
+
+fn is_run_dir(path: &Path, name: &str) -> bool {
+    file_name::is_run_dir_name(name) && path.symlink_metadata().is_ok_and(|m| m.is_dir())
+}
+
+fn collect(
+    output_dir: &Path,
+    profile_prefix: &str,
+    whole_run_dirs: bool,
+    keep: Option<&Path>,
+) -> Vec<OutputFile> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(output_dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Ok(meta) = entry.path().symlink_metadata() else {
+            continue;
+        };
+        if meta.is_dir() && file_name::is_run_dir_name(&name) {
+            if keep == Some(entry.path().as_path()) {
+                continue;
+            }
+            if whole_run_dirs {
+                files.extend(run_dir_unit(entry.path()));
+            } else {
+                collect_dir(&entry.path(), &mut files);
+            }
+        // Tools that write several files at once put them in a prefixed directory
+        } else if meta.is_dir()
+            && (MANAGED_DIRS.contains(&name.as_str()) || is_managed_name(&name, profile_prefix))
+        {
+            collect_dir(&entry.path(), &mut files);
+        } else if meta.is_file() && is_managed_name(&name, profile_prefix) {
+            files.extend(output_file(entry.path(), &meta));
+        }
+    }
+    files
+}
+
+fn run_dir_unit(dir: PathBuf) -> Option<OutputFile> {
+    let mut contents = Vec::new();
+    collect_dir(&dir, &mut contents);
+    let modified = match contents.iter().map(|f| f.modified).max() {
+        Some(newest) => newest,
+        None => dir.symlink_metadata().ok()?.modified().ok()?,
+    };
+    Some(OutputFile {
+        size: contents.iter().map(|f| f.size).sum(),
+        path: dir,
+        modified,
+    })
+}
+
+fn collect_dir(dir: &Path, files: &mut Vec<OutputFile>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.path().symlink_metadata() else {
+            continue;
+        };
+        if meta.is_dir() {
+            collect_dir(&entry.path(), files);
+        } else if meta.is_file() {
+            files.extend(output_file(entry.path(), &meta));
+        }
+    }
+}
+
+fn output_file(path: PathBuf, meta: &fs::Metadata) -> Option<OutputFile> {
+    Some(OutputFile {
+        path,
+        size: meta.len(),
+        modified: meta.modified().ok()?,
+    })
+}
+
+/// Apply `policy` to the output directory and report reclaimed space.
+///
+/// Run directories are removed whole, never the one this session writes to.
+/// The first run in a session lists the files and asks before deleting anything.
+pub fn enforce(config: &Config, policy: &RetentionPolicy) -> Result<()> {
+    if !policy.is_enabled() || !config.output_dir.is_dir() {
+        return Ok(());
+    }
+
+    let root = config.output_dir.canonicalize()?;
+    let profile_prefix = config.artifact_file_name("");
+    let current =
+        crate::config::current_run_dir(&config.output_dir).and_then(|dir| dir.canonicalize().ok());
+    let plan = plan_cleanup(
+        collect_cleanup_units(&root, &profile_prefix, current.as_deref()),
+        policy,
+        SystemTime::now(),
+    );
+    if plan.is_empty() {
+        return Ok(());
+    }
+
+    let planned_bytes: u64 = plan.iter().map(|f| f.size).sum();
+    if !DRY_RUN_REVIEWED.load(Ordering::Relaxed) {
+        ui::print_info(&format!(
+            "Output retention would remove {} file(s) or run directories, {}:",
+            plan.len(),
+            format_utils::format_bytes(planned_bytes, 2, false)
+        ));
+        for f in &plan {
+            ui::print_info(&format!(
+                "  {} ({})",
+                f.path.display(),
+                format_utils::format_bytes(f.size, 1, false)
+            ));
+        }
+        if !ui::ask_continue("Delete these outputs?")? {
+            ui::print_info("Nothing deleted.");
+            return Ok(());
+        }
+        DRY_RUN_REVIEWED.store(true, Ordering::Relaxed);
+    }
+
+    let mut removed = 0usize;
+    let mut reclaimed = 0u64;
+    for f in &plan {
+        // Defence in depth: only paths that still resolve inside output_dir
+        let inside = f.path.canonicalize().is_ok_and(|p| p.starts_with(&root));
+        if !inside {
+            continue;
+        }
+        let deleted = if f.path.is_dir() {
+            fs::remove_dir_all(&f.path)
+        } else {
+            fs::remove_file(&f.path)
+        };
+        if deleted.is_ok() {
+            removed += 1;
+            reclaimed += f.size;
+        }
+    }
+    ui::print_success(&format!(
+        "Removed {removed} old output file(s) or run directories, reclaimed {}",
+        format_utils::format_bytes(reclaimed, 2, false)
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, size: u64, age_days: u64, now: SystemTime) -> OutputFile {
+        OutputFile {
+            path: PathBuf::from(name),
+            size,
+            modified: now - Duration::from_secs(age_days * 24 * 3600),
+        }
+    }
+
+    #[test]
+    fn only_known_names_are_managed() {
+        assert!(is_managed_name("jmap_dump_123_20240101_000000.hprof", ""));
+        assert!(is_managed_name("prod-a_pstack_1_20240101.txt", "prod-a_"));
+        assert!(!is_managed_name("prod-a_pstack_1.txt", ""));
+        assert!(!is_managed_name("notes.txt", ""));
+        assert!(!is_managed_name("fe.conf", ""));
+    }
+
+    #[test]
+    fn run_directories_are_cleanup_units() {
+        let root = std::env::temp_dir().join(format!("cloud_cli_runs_{}", std::process::id()));
+        let old_run = root.join("20240101_000000_SD-1");
+        let current = root.join("20240102_000000");
+        for dir in [&old_run, &current, &root.join("table-info")] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(old_run.join("jstack_1.log"), [0u8; 100]).unwrap();
+        fs::write(old_run.join("commands.log"), [0u8; 20]).unwrap();
+        fs::write(current.join("pstack_1.txt"), [0u8; 5]).unwrap();
+        fs::write(root.join("memz_1.html"), [0u8; 7]).unwrap();
+        fs::write(root.join("notes.txt"), [0u8; 9]).unwrap();
+
+        let mut units = collect_cleanup_units(&root, "", Some(&current));
+        units.sort_by(|a, b| a.path.cmp(&b.path));
+        let found: Vec<(PathBuf, u64)> = units.into_iter().map(|u| (u.path, u.size)).collect();
+        assert_eq!(
+            found,
+            [(old_run.clone(), 120), (root.join("memz_1.html"), 7)]
+        );
+
+        // The viewer still sees the files inside every run directory
+        assert_eq!(collect_managed_files(&root, "").len(), 4);
+        assert_eq!(artifact_dirs(&root).len(), 3);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn age_then_size_limit_oldest_first() {
+        let now = SystemTime::now();
+        let files = vec![
+            file("new", 100, 0, now),
+            file("old", 100, 10, now),
+            file("mid", 300, 3, now),
+            file("mid2", 200, 2, now),
+        ];
+
+        let by_age = RetentionPolicy {
+            max_age: Some(Duration::from_secs(7 * 24 * 3600)),
+            max_total_bytes: None,
+        };
+        let plan = plan_cleanup(files.clone(), &by_age, now);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].path, PathBuf::from("old"));
+
+        let both = RetentionPolicy {
+            max_total_bytes: Some(250),
+            ..by_age
+        };
+        let names: Vec<_> = plan_cleanup(files, &both, now)
+            .into_iter()
+            .map(|f| f.path)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                PathBuf::from("old"),
+                PathBuf::from("mid"),
+                PathBuf::from("mid2")
+            ]
+        );
+    }
+}
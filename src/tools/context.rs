@@ -0,0 +1,198 @@
+use chrono::NaiveDateTime;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::config_loader::Environment;
+use crate::error::{CliError, Result};
+use crate::tools::fe::routine_load::log_source;
+use crate::ui::sink::{BufferedSink, ConsoleSink, OutputSink};
+use crate::ui::{InputHelper, TimeRange};
+
+/// Inputs a tool would otherwise ask for mid-run, and where its status lines go.
+///
+/// The CLI uses [`InteractiveContext`]; programs embedding the collectors pass a
+/// [`PresetContext`] with everything resolved up front.
+pub trait ToolContext {
+    fn sink(&self) -> &dyn OutputSink;
+
+    /// Whether prompts and progress animations may be used
+    fn is_interactive(&self) -> bool;
+
+    /// FE log directory for the log analyzers
+    fn log_dir(&self, config: &Config) -> Result<PathBuf>;
+
+    /// Routine load job to analyze
+    fn job_id(&self) -> Result<String>;
+
+    fn database(&self, config: &Config) -> Result<String>;
+
+    /// Window to analyze within the `available` log data
+    fn time_range(
+        &self,
+        default_minutes: i64,
+        available: (NaiveDateTime, NaiveDateTime),
+    ) -> Result<TimeRange>;
+
+    /// One of `options`; contexts that cannot ask take `default`
+    fn choose(&self, prompt: &str, options: &[&str], default: usize) -> Result<usize>;
+}
+
+/// Terminal prompts and `ui::print_*`, exactly what the menus do
+pub struct InteractiveContext;
+
+impl ToolContext for InteractiveContext {
+    fn sink(&self) -> &dyn OutputSink {
+        &ConsoleSink
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn log_dir(&self, config: &Config) -> Result<PathBuf> {
+        log_source::resolve_log_dir(config)
+    }
+
+    fn job_id(&self) -> Result<String> {
+        log_source::resolve_job_id()
+    }
+
+    fn database(&self, config: &Config) -> Result<String> {
+        crate::tools::fe::table_info::browser::select_database(config)
+            .map_err(|e| CliError::ToolExecutionFailed(e.to_string()))
+    }
+
+    fn time_range(
+        &self,
+        default_minutes: i64,
+        available: (NaiveDateTime, NaiveDateTime),
+    ) -> Result<TimeRange> {
+        InputHelper::prompt_time_range(default_minutes, available)
+    }
+
+    fn choose(&self, prompt: &str, options: &[&str], _default: usize) -> Result<usize> {
+        crate::ui::dialogs::select_index(prompt, options)
+    }
+}
+
+/// Inputs resolved by the caller; a missing one is an error rather than a prompt.
+///
+/// Status lines are kept in `sink` for the caller to read with
+/// [`BufferedSink::take`].
+#[derive(Debug, Default)]
+pub struct PresetContext {
+    /// Defaults to `offline_log_dir`, then the live FE log directory
+    pub log_dir: Option<PathBuf>,
+    pub job_id: Option<String>,
+    pub database: Option<String>,
+    /// Defaults to the analyzer's own recent-minutes window
+    pub time_range: Option<TimeRange>,
+    pub sink: BufferedSink,
+}
+
+impl PresetContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_log_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.log_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_job_id(mut self, job_id: impl Into<String>) -> Self {
+        self.job_id = Some(job_id.into());
+        self
+    }
+
+    pub fn with_database(mut self, database: impl Into<String>) -> Self {
+        self.database = Some(database.into());
+        self
+    }
+
+    pub fn with_time_range(mut self, range: TimeRange) -> Self {
+        self.time_range = Some(range);
+        self
+    }
+}
+
+fn missing(input: &str) -> CliError {
+    CliError::InvalidInput(format!("{input} was not provided to the tool context"))
+}
+
+impl ToolContext for PresetContext {
+    fn sink(&self) -> &dyn OutputSink {
+        &self.sink
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn log_dir(&self, config: &Config) -> Result<PathBuf> {
+        match self.log_dir.as_ref().or(config.offline_log_dir.as_ref()) {
+            Some(dir) => Ok(dir.clone()),
+            None => Ok(crate::config_loader::load_config()?
+                .service_paths(Environment::FE)
+                .log_dir),
+        }
+    }
+
+    fn job_id(&self) -> Result<String> {
+        self.job_id.clone().ok_or_else(|| missing("Job ID"))
+    }
+
+    fn database(&self, _config: &Config) -> Result<String> {
+        self.database.clone().ok_or_else(|| missing("Database"))
+    }
+
+    fn time_range(
+        &self,
+        default_minutes: i64,
+        _available: (NaiveDateTime, NaiveDateTime),
+    ) -> Result<TimeRange> {
+        Ok(self
+            .time_range
+            .unwrap_or(TimeRange::Recent(default_minutes)))
+    }
+
+    fn choose(&self, _prompt: &str, _options: &[&str], default: usize) -> Result<usize> {
+        Ok(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MessageType;
+
+    #[test]
+    fn preset_context_answers_without_prompting() {
+        let ctx = PresetContext::new().with_job_id("10086");
+        assert_eq!(ctx.job_id().unwrap(), "10086");
+        assert!(matches!(
+            ctx.database(&Config::default()),
+            Err(CliError::InvalidInput(_))
+        ));
+
+        let now = chrono::Local::now().naive_local();
+        assert!(matches!(
+            ctx.time_range(30, (now, now)).unwrap(),
+            TimeRange::Recent(30)
+        ));
+        assert_eq!(
+            ctx.choose("Resume?", &["Resume", "Start over"], 0).unwrap(),
+            0
+        );
+
+        ctx.sink().warning("Skipped fe.log.1: permission denied");
+        assert_eq!(
+            ctx.sink.take(),
+            [(
+                MessageType::Warning,
+                "Skipped fe.log.1: permission denied".to_string()
+            )]
+        );
+        assert!(ctx.sink.take().is_empty());
+    }
+}
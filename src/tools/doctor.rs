@@ -0,0 +1,639 @@
+use chrono::{DateTime, Local, NaiveDateTime};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::Config;
+use crate::config_loader::process_detector::{self, ProcessDetectionResult};
+use crate::config_loader::{DorisConfig, Environment};
+use crate::error::Result;
+use crate::tools::be::be_http_client::{self, BeTarget};
+use crate::tools::be::port_check::{self, PortFinding};
+use crate::tools::common::fs_utils;
+use crate::tools::common::{conf_inspector, file_name};
+use crate::tools::mysql::{ClusterInfo, MySQLTool};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+/// Per-connection timeout; keeps a full run well under 20s on a broken host
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// Log timestamps further than this from the file mtime count as skewed
+const MAX_SKEW_SECS: i64 = 300;
+/// Bytes read from the end of a log when looking for its last timestamp
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not applicable on this host, e.g. BE checks without a BE
+    Skip,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// One-line remediation shown for failures
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    pub(crate) fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    pub(crate) fn fail(name: &str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub(crate) fn skip(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Skip,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn line(&self) -> String {
+        let tag = match self.status {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "SKIP",
+        };
+        format!("[{tag}] {:<28} {}", self.name, self.detail)
+    }
+}
+
+/// Runs connectivity and environment checks and reports pass/fail for each
+pub struct DoctorTool;
+
+impl Tool for DoctorTool {
+    fn name(&self) -> &str {
+        "doctor"
+    }
+
+    fn description(&self) -> &str {
+        "Check processes, credentials, ports and tooling on this host"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let (results, port_findings) = run_checks(config);
+        print_results(&results);
+        if let Err(e) = port_check::offer_updates(&port_findings) {
+            ui::print_warning(&format!("BE ports not saved: {e}"));
+        }
+
+        let (passed, failed) = count_status(&results);
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("doctor", "txt"),
+            render_report("cloud-cli doctor report", &results).as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("Doctor finished: {passed} passed, {failed} failed"),
+        ))
+    }
+}
+
+pub(crate) fn print_results(results: &[CheckResult]) {
+    ui::print_info("");
+    for r in results {
+        match r.status {
+            CheckStatus::Pass => ui::print_success(&r.line()),
+            CheckStatus::Fail => ui::print_error(&r.line()),
+            CheckStatus::Skip => ui::print_info(&r.line()),
+        }
+        if let Some(hint) = &r.hint {
+            ui::print_info(&format!("       hint: {hint}"));
+        }
+    }
+}
+
+/// (passed, failed)
+pub(crate) fn count_status(results: &[CheckResult]) -> (usize, usize) {
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    (count(CheckStatus::Pass), count(CheckStatus::Fail))
+}
+
+/// Check results, plus the BE port findings behind the port cross-check lines
+fn run_checks(config: &Config) -> (Vec<CheckResult>, Vec<PortFinding>) {
+    let processes = process_detector::detect_all_processes().unwrap_or_default();
+    let doris = crate::config_loader::load_config().ok();
+
+    let mut results = vec![check_processes(&processes)];
+    results.extend(check_conf_files(&processes));
+    results.extend(check_conf_applied(&processes, doris.as_ref()));
+    results.extend(check_mysql(doris.as_ref()));
+    results.push(check_fe_http(doris.as_ref()));
+    results.extend(check_be_webservers(&processes));
+    let port_findings = match port_check::run(doris.as_ref()) {
+        Some((checks, findings)) => {
+            results.extend(checks);
+            findings
+        }
+        None => {
+            results.push(CheckResult::skip(
+                "BE port cross-check",
+                "no known backends (run FE > fe-list to collect cluster info)",
+            ));
+            Vec::new()
+        }
+    };
+    results.push(check_output_dir(config));
+    results.push(check_jdk_tools(config, &processes));
+    results.extend(check_clock_skew(&processes, doris.as_ref()));
+    results.extend(crate::tools::meta_service::check_meta_service(
+        doris.as_ref(),
+    ));
+    (results, port_findings)
+}
+
+pub(crate) fn render_report(title: &str, results: &[CheckResult]) -> String {
+    let mut out = format!("{title} ({})\n\n", Local::now().format("%Y-%m-%d %H:%M:%S"));
+    for r in results {
+        out.push_str(&r.line());
+        out.push('\n');
+        if let Some(hint) = &r.hint {
+            out.push_str(&format!("       hint: {hint}\n"));
+        }
+    }
+    out
+}
+
+fn process_of(
+    processes: &[ProcessDetectionResult],
+    env: Environment,
+) -> Option<&ProcessDetectionResult> {
+    processes.iter().find(|p| p.environment == env)
+}
+
+fn check_processes(processes: &[ProcessDetectionResult]) -> CheckResult {
+    const NAME: &str = "Doris processes";
+    if processes.is_empty() {
+        return CheckResult::fail(
+            NAME,
+            "no FE or BE process found",
+            "Start FE/BE, or run as a user that can see them in `ps`",
+        );
+    }
+    let found: Vec<String> = processes
+        .iter()
+        .map(|p| format!("{} pid {}", p.environment, p.pid))
+        .collect();
+    CheckResult::pass(NAME, found.join(", "))
+}
+
+fn check_conf_files(processes: &[ProcessDetectionResult]) -> Vec<CheckResult> {
+    processes
+        .iter()
+        .map(|p| {
+            let file = match p.environment {
+                Environment::BE => "be.conf",
+                _ => "fe.conf",
+            };
+            let name = format!("{} conf readable", p.environment);
+            let path = p.doris_home.join("conf").join(file);
+            match fs::File::open(&path) {
+                Ok(_) => CheckResult::pass(&name, path.display().to_string()),
+                Err(e) => CheckResult::fail(
+                    &name,
+                    format!("{}: {e}", path.display()),
+                    "Run cloud-cli as the Doris user or grant read access to the conf dir",
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Keys named in the detail of a stale conf check before the rest are counted
+const MAX_LISTED_KEYS: usize = 5;
+
+/// Conf files edited after the process started; their changes wait for a restart
+fn check_conf_applied(
+    processes: &[ProcessDetectionResult],
+    doris: Option<&DorisConfig>,
+) -> Vec<CheckResult> {
+    processes
+        .iter()
+        .filter_map(|p| {
+            let file = match p.environment {
+                Environment::BE => "be.conf",
+                _ => "fe.conf",
+            };
+            let name = format!("{} conf applied", p.environment);
+            let path = p.doris_home.join("conf").join(file);
+            // An unreadable file is already reported by the readable check
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            let Some(started) = process_detector::get_process_start_time(p.pid) else {
+                return Some(CheckResult::skip(&name, "process start time unknown"));
+            };
+            let pending = || {
+                let content = fs::read_to_string(&path).ok()?;
+                let runtime = conf_inspector::runtime_values(p.environment, doris?).ok()?;
+                let entries = conf_inspector::effective_entries(&content);
+                let pending = conf_inspector::pending_restart(&entries, &runtime)
+                    .into_iter()
+                    .map(|(e, live)| format!("{} (file {}, live {live})", e.key, e.value))
+                    .collect();
+                Some(pending)
+            };
+            Some(conf_applied_result(
+                &name,
+                file,
+                p.environment,
+                modified,
+                started,
+                pending,
+            ))
+        })
+        .collect()
+}
+
+/// `pending` lists the keys whose live value differs from the file; it is only
+/// asked for when the file is newer than the process, and None means unknown
+fn conf_applied_result(
+    name: &str,
+    file: &str,
+    env: Environment,
+    modified: SystemTime,
+    started: SystemTime,
+    pending: impl FnOnce() -> Option<Vec<String>>,
+) -> CheckResult {
+    let at = |t: SystemTime| DateTime::<Local>::from(t).format("%Y-%m-%d %H:%M:%S");
+    if modified <= started {
+        return CheckResult::pass(
+            name,
+            format!("{file} unchanged since {env} started {}", at(started)),
+        );
+    }
+    let stale = format!(
+        "{file} modified {} after {env} started {}",
+        at(modified),
+        at(started)
+    );
+    let hint = format!(
+        "Config file modified after the process started; restart {env} to apply file-based changes"
+    );
+    match pending() {
+        Some(keys) if keys.is_empty() => CheckResult::pass(
+            name,
+            format!("{stale}, but every key matches the live value"),
+        ),
+        Some(keys) => {
+            let mut listed = keys[..keys.len().min(MAX_LISTED_KEYS)].join(", ");
+            if keys.len() > MAX_LISTED_KEYS {
+                listed.push_str(&format!(" and {} more", keys.len() - MAX_LISTED_KEYS));
+            }
+            CheckResult::fail(
+                name,
+                format!("{stale}; {} key(s) differ from live: {listed}", keys.len()),
+                hint,
+            )
+        }
+        None => CheckResult::fail(name, format!("{stale}; live values unavailable"), hint),
+    }
+}
+
+fn tcp_reachable(host: &str, port: u16) -> std::result::Result<(), String> {
+    tcp_connect_latency(host, port).map(|_| ())
+}
+
+/// Time to the first successful TCP connect across the host's addresses
+pub(crate) fn tcp_connect_latency(host: &str, port: u16) -> std::result::Result<Duration, String> {
+    let addrs = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("cannot resolve {host}: {e}"))?;
+    let mut last_err = format!("no address for {host}");
+    for addr in addrs {
+        let started = Instant::now();
+        match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+            Ok(_) => return Ok(started.elapsed()),
+            Err(e) => last_err = e.to_string(),
+        }
+    }
+    Err(last_err)
+}
+
+fn check_mysql(doris: Option<&DorisConfig>) -> Vec<CheckResult> {
+    const CREDS: &str = "MySQL credentials";
+    const CONN: &str = "MySQL connection";
+    let Some((doris, mysql)) = doris.and_then(|d| d.mysql.as_ref().map(|m| (d, m))) else {
+        return vec![
+            CheckResult::fail(
+                CREDS,
+                "not configured",
+                "Restart cloud-cli on an FE/BE host and accept the credential prompt",
+            ),
+            CheckResult::skip(CONN, "no credentials"),
+        ];
+    };
+
+    let creds = CheckResult::pass(CREDS, format!("user '{}'", mysql.user));
+    let (host, port) = match MySQLTool::get_connection_params(Some(mysql)) {
+        Ok(params) => params,
+        Err(e) => {
+            return vec![
+                creds,
+                CheckResult::fail(CONN, e.to_string(), "Set MYSQL_HOST and MYSQL_PORT"),
+            ];
+        }
+    };
+
+    // Probe the port first so an unreachable FE does not hang the mysql client
+    let conn = if let Err(e) = tcp_reachable(&host, port) {
+        CheckResult::fail(
+            CONN,
+            format!("{host}:{port} unreachable: {e}"),
+            "Check that FE is running and query_port is open; override with MYSQL_HOST/MYSQL_PORT",
+        )
+    } else {
+        match MySQLTool::query_sql_with_config(doris, "SELECT 1") {
+            Ok(_) => CheckResult::pass(CONN, format!("SELECT 1 on {host}:{port}")),
+            Err(e) => CheckResult::fail(
+                CONN,
+                e.to_string(),
+                "Re-enter credentials via Settings or verify the user's privileges",
+            ),
+        }
+    };
+    vec![creds, conn]
+}
+
+fn check_fe_http(doris: Option<&DorisConfig>) -> CheckResult {
+    const NAME: &str = "FE HTTP port";
+    let host = doris
+        .and_then(|d| d.mysql.as_ref())
+        .and_then(|m| m.host.clone())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = doris.and_then(|d| d.http_port).unwrap_or(8030);
+    match tcp_reachable(&host, port) {
+        Ok(()) => CheckResult::pass(NAME, format!("{host}:{port}")),
+        Err(e) => CheckResult::fail(
+            NAME,
+            format!("{host}:{port} unreachable: {e}"),
+            "Check http_port in fe.conf and that no firewall blocks it",
+        ),
+    }
+}
+
+/// Backends from clusters.toml, else the first-responder candidates when a BE runs here
+fn be_targets(processes: &[ProcessDetectionResult]) -> Vec<BeTarget> {
+    if let Ok(info) = ClusterInfo::load_from_file()
+        && !info.backends.is_empty()
+    {
+        return info
+            .backends
+            .iter()
+            .map(|b| BeTarget {
+                host: b.host.clone(),
+                port: b.http_port,
+            })
+            .collect();
+    }
+    if process_of(processes, Environment::BE).is_none() {
+        return Vec::new();
+    }
+    be_http_client::candidate_targets().unwrap_or_default()
+}
+
+fn check_be_webservers(processes: &[ProcessDetectionResult]) -> Vec<CheckResult> {
+    let targets = be_targets(processes);
+    if targets.is_empty() {
+        return vec![CheckResult::skip(
+            "BE webserver ports",
+            "no known backends (run FE > fe-list to collect cluster info)",
+        )];
+    }
+
+    // Probe in parallel so many dead backends still finish within one timeout
+    std::thread::scope(|s| {
+        let handles: Vec<_> = targets
+            .iter()
+            .map(|t| s.spawn(move || (t, tcp_reachable(&t.host, t.port))))
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().ok())
+            .map(|(t, reachable)| {
+                let name = format!("BE webserver {t}");
+                match reachable {
+                    Ok(()) => CheckResult::pass(&name, "reachable"),
+                    Err(e) => CheckResult::fail(
+                        &name,
+                        e,
+                        "Check that BE is alive and webserver_port in be.conf is open",
+                    ),
+                }
+            })
+            .collect()
+    })
+}
+
+fn check_output_dir(config: &Config) -> CheckResult {
+    const NAME: &str = "Output dir writable";
+    let dir = &config.output_dir;
+    let probe = dir.join(format!(".cloud-cli-doctor-{}", std::process::id()));
+    let result = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b"ok"))
+        .and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => CheckResult::pass(NAME, dir.display().to_string()),
+        Err(e) => CheckResult::fail(
+            NAME,
+            format!("{}: {e}", dir.display()),
+            "Point OUTPUT_DIR at a writable directory",
+        ),
+    }
+}
+
+fn check_jdk_tools(config: &Config, processes: &[ProcessDetectionResult]) -> CheckResult {
+    const NAME: &str = "JDK tools";
+    if process_of(processes, Environment::FE).is_none() && !config.jdk_path.exists() {
+        return CheckResult::skip(NAME, "no FE process and no JDK configured");
+    }
+    // Heap dumps fall back to jcmd when jmap is missing
+    let dump_tool = [config.get_jmap_path(), config.get_jcmd_path()]
+        .into_iter()
+        .find(|p| p.is_file());
+    let mut missing = Vec::new();
+    if dump_tool.is_none() {
+        missing.push(format!("{} (or jcmd)", config.get_jmap_path().display()));
+    }
+    if !config.get_jstack_path().is_file() {
+        missing.push(config.get_jstack_path().display().to_string());
+    }
+    if missing.is_empty() {
+        CheckResult::pass(
+            NAME,
+            format!(
+                "{}, jstack in {}",
+                dump_tool
+                    .as_deref()
+                    .and_then(Path::file_name)
+                    .map_or_default(|n| n.to_string_lossy().into_owned()),
+                config.jdk_path.display()
+            ),
+        )
+    } else {
+        CheckResult::fail(
+            NAME,
+            format!("missing {}", missing.join(", ")),
+            "Set JDK_PATH to a full JDK (not a JRE) matching the FE's Java version",
+        )
+    }
+}
+
+/// Timestamp at the start of an FE (`2024-05-01 10:00:00,123`) or BE glog
+/// (`I20240501 10:00:00.123456`) log line
+pub fn log_line_timestamp(line: &str) -> Option<NaiveDateTime> {
+    if let Some(ts) = line.get(..19)
+        && let Ok(t) = NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S")
+    {
+        return Some(t);
+    }
+    let ts = line.get(1..18)?;
+    if !line.starts_with(['I', 'W', 'E', 'F']) {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(ts, "%Y%m%d %H:%M:%S").ok()
+}
+
+/// Seconds the last logged timestamp is ahead of (positive) or behind the file mtime
+pub fn log_skew_secs(last_logged: NaiveDateTime, modified: NaiveDateTime) -> i64 {
+    (last_logged - modified).num_seconds()
+}
+
+fn last_log_timestamp(path: &Path) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let mut file = fs::File::open(path).ok()?;
+    let meta = file.metadata().ok()?;
+    let modified = DateTime::<Local>::from(meta.modified().ok()?).naive_local();
+    file.seek(SeekFrom::Start(meta.len().saturating_sub(LOG_TAIL_BYTES)))
+        .ok()?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).ok()?;
+    let text = String::from_utf8_lossy(&buf);
+    let last = text.lines().rev().find_map(log_line_timestamp)?;
+    Some((last, modified))
+}
+
+fn check_clock_skew(
+    processes: &[ProcessDetectionResult],
+    doris: Option<&DorisConfig>,
+) -> Vec<CheckResult> {
+    let mut logs: Vec<(Environment, PathBuf)> = processes
+        .iter()
+        .map(|p| {
+            let file = match p.environment {
+                Environment::BE => "be.INFO",
+                _ => "fe.log",
+            };
+            (p.environment, p.doris_home.join("log").join(file))
+        })
+        .collect();
+    if logs.is_empty()
+        && let Some(d) = doris
+    {
+        for (env, file) in [(Environment::FE, "fe.log"), (Environment::BE, "be.INFO")] {
+            logs.push((env, d.service_paths(env).log_dir.join(file)));
+        }
+        logs.retain(|(_, p)| p.is_file());
+    }
+    if logs.is_empty() {
+        return vec![CheckResult::skip("Clock skew", "no FE/BE log found")];
+    }
+
+    logs.into_iter()
+        .map(|(env, path)| {
+            let name = format!("{env} log clock skew");
+            match last_log_timestamp(&path) {
+                None => CheckResult::skip(&name, format!("no timestamp in {}", path.display())),
+                Some((last, modified)) => {
+                    let skew = log_skew_secs(last, modified);
+                    if skew.abs() > MAX_SKEW_SECS {
+                        CheckResult::fail(
+                            &name,
+                            format!("log time differs from system time by {skew}s"),
+                            "Check NTP sync and that the Doris process uses the host timezone (TZ)",
+                        )
+                    } else {
+                        CheckResult::pass(&name, format!("{skew}s"))
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fe_and_be_log_timestamps() {
+        let fe =
+            log_line_timestamp("2024-05-01 10:00:00,123 INFO (main|1) [Env.init():1] ok").unwrap();
+        let be = log_line_timestamp("I20240501 09:50:00.123456 12345 storage_engine.cpp:100] ok")
+            .unwrap();
+        assert_eq!(fe.to_string(), "2024-05-01 10:00:00");
+        assert_eq!(be.to_string(), "2024-05-01 09:50:00");
+        assert_eq!(log_skew_secs(be, fe), -600);
+
+        assert!(log_line_timestamp("\tat org.apache.doris.Foo.bar(Foo.java:1)").is_none());
+        assert!(log_line_timestamp("Info about startup").is_none());
+    }
+
+    #[test]
+    fn conf_edited_after_start_needs_restart() {
+        let started = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let later = started + Duration::from_secs(600);
+        let check = |modified, pending: Option<Vec<String>>| {
+            conf_applied_result(
+                "BE conf applied",
+                "be.conf",
+                Environment::BE,
+                modified,
+                started,
+                || pending,
+            )
+        };
+
+        let r = check(started - Duration::from_secs(60), None);
+        assert_eq!(r.status, CheckStatus::Pass);
+        assert!(r.detail.contains("unchanged"));
+
+        let keys: Vec<String> = (0..7)
+            .map(|i| format!("key_{i} (file 1, live 0)"))
+            .collect();
+        let r = check(later, Some(keys));
+        assert_eq!(r.status, CheckStatus::Fail);
+        assert!(r.detail.contains("7 key(s) differ"));
+        assert!(r.detail.ends_with("and 2 more"));
+        assert!(r.hint.unwrap().contains("restart BE"));
+
+        assert_eq!(check(later, Some(Vec::new())).status, CheckStatus::Pass);
+        let r = check(later, None);
+        assert_eq!(r.status, CheckStatus::Fail);
+        assert!(r.detail.contains("live values unavailable"));
+    }
+}
@@ -0,0 +1,311 @@
+use chrono::{Local, NaiveDateTime};
+use console::style;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::core::interrupt;
+use crate::error::Result;
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::fe::table_info::browser;
+use crate::tools::mysql::MySQLTool;
+use crate::tools::mysql::parser::{RowFields, parse_key_value_pairs, split_into_blocks};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::{FormatHelper, InteractiveSelector};
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlterKind {
+    Column,
+    Rollup,
+}
+
+impl AlterKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            AlterKind::Column => "COLUMN",
+            AlterKind::Rollup => "ROLLUP",
+        }
+    }
+}
+
+/// One row of `SHOW ALTER TABLE COLUMN|ROLLUP`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlterJob {
+    pub kind: AlterKind,
+    pub job_id: String,
+    pub table: String,
+    pub state: String,
+    pub progress: Option<String>,
+    pub create_time: String,
+    pub transaction_id: String,
+    pub msg: Option<String>,
+}
+
+impl AlterJob {
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state.as_str(), "FINISHED" | "CANCELLED")
+    }
+
+    /// Hours since CreateTime, which FE reports in its local time
+    pub fn running_hours(&self, now: NaiveDateTime) -> Option<f64> {
+        let created = self.create_time.get(..19)?;
+        let created = NaiveDateTime::parse_from_str(created, "%Y-%m-%d %H:%M:%S").ok()?;
+        Some((now - created).num_seconds() as f64 / 3600.0)
+    }
+
+    /// Why the job deserves attention: cancelled, or still active for longer than `warn_hours`
+    pub fn attention(&self, now: NaiveDateTime, warn_hours: u64) -> Option<String> {
+        if self.state == "CANCELLED" {
+            return Some(format!(
+                "cancelled: {}",
+                self.msg.as_deref().unwrap_or("no message")
+            ));
+        }
+        if self.is_finished() {
+            return None;
+        }
+        self.running_hours(now)
+            .filter(|h| *h > warn_hours as f64)
+            .map(|h| format!("{} for {h:.1}h", self.state))
+    }
+
+    fn label(&self) -> String {
+        format!(
+            "{} {} on {} ({}, {})",
+            self.kind.keyword(),
+            self.job_id,
+            self.table,
+            self.state,
+            self.progress.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+fn field(fields: &RowFields, key: &str) -> Option<String> {
+    fields
+        .value(key)
+        .filter(|v| !v.is_empty() && *v != "N/A")
+        .map(str::to_string)
+}
+
+pub fn parse_alter_jobs(output: &str, kind: AlterKind) -> Vec<AlterJob> {
+    split_into_blocks(output)
+        .iter()
+        .map(|block| parse_key_value_pairs(block))
+        .filter(|fields| fields.contains_key("JobId"))
+        .map(|fields| AlterJob {
+            kind,
+            job_id: fields["JobId"].clone(),
+            table: field(&fields, "TableName").unwrap_or_default(),
+            state: field(&fields, "State").unwrap_or_else(|| "UNKNOWN".to_string()),
+            progress: field(&fields, "Progress"),
+            create_time: field(&fields, "CreateTime").unwrap_or_default(),
+            transaction_id: field(&fields, "TransactionId").unwrap_or_else(|| "-".to_string()),
+            msg: field(&fields, "Msg"),
+        })
+        .collect()
+}
+
+fn query_jobs(doris: &DorisConfig, database: &str, kind: AlterKind) -> Result<Vec<AlterJob>> {
+    let sql = format!("SHOW ALTER TABLE {} FROM `{database}` \\G", kind.keyword());
+    let output = MySQLTool::query_sql_with_config(doris, &sql)?;
+    Ok(parse_alter_jobs(&output, kind))
+}
+
+/// Schema change and rollup jobs of a database, with a watch mode for upgrades
+pub struct FeAlterJobTool;
+
+impl Tool for FeAlterJobTool {
+    fn name(&self) -> &str {
+        "fe-alter-jobs"
+    }
+
+    fn description(&self) -> &str {
+        "Monitor schema change and rollup jobs of a database"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let database = browser::select_database(config)?;
+
+        ui::print_info(&format!("Querying alter jobs in {database}..."));
+        let mut jobs = query_jobs(&doris, &database, AlterKind::Column)?;
+        jobs.extend(query_jobs(&doris, &database, AlterKind::Rollup)?);
+        if jobs.is_empty() {
+            return Ok(ExecutionResult::console(format!(
+                "No schema change or rollup jobs in {database}"
+            )));
+        }
+
+        let warn_hours = config.alter_job_warn_after_hours();
+        let now = Local::now().naive_local();
+        let lines = render_jobs(&jobs, now, warn_hours);
+        ui::print_info("");
+        for (line, flagged) in &lines {
+            if *flagged {
+                ui::print_info(&style(line).red().to_string());
+            } else {
+                ui::print_info(line);
+            }
+        }
+        let report: String = lines.iter().map(|(l, _)| format!("{l}\n")).collect();
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped(&format!("fe_alter_jobs_{database}"), "txt"),
+            report.as_bytes(),
+        )?;
+
+        let active: Vec<&AlterJob> = jobs.iter().filter(|j| !j.is_finished()).collect();
+        let flagged = lines.iter().filter(|(_, f)| *f).count();
+        if !active.is_empty()
+            && ui::dialogs::select_index("Next", &["Watch a running job", "Done"])? == 0
+        {
+            let labels: Vec<String> = active.iter().map(|j| j.label()).collect();
+            let picked = InteractiveSelector::new(labels.clone(), "Job to watch".to_string())
+                .select()?
+                .clone();
+            if let Some(pos) = labels.iter().position(|l| *l == picked) {
+                watch_job(&doris, &database, active[pos])?;
+            }
+        }
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "{} alter job(s) in {database}, {} active, {flagged} need attention",
+                jobs.len(),
+                active.len()
+            ),
+        ))
+    }
+}
+
+/// Table lines, each paired with whether it is highlighted
+fn render_jobs(jobs: &[AlterJob], now: NaiveDateTime, warn_hours: u64) -> Vec<(String, bool)> {
+    let mut lines = vec![
+        (
+            format!(
+                "{:<7} {:<14} {:<28} {:<12} {:<10} {:<20} {:>14}",
+                "Type", "JobId", "Table", "State", "Progress", "CreateTime", "TransactionId"
+            ),
+            false,
+        ),
+        ("-".repeat(111), false),
+    ];
+    for job in jobs {
+        lines.push((
+            format!(
+                "{:<7} {:<14} {:<28} {:<12} {:<10} {:<20} {:>14}",
+                job.kind.keyword(),
+                job.job_id,
+                FormatHelper::truncate_string(&job.table, 28),
+                job.state,
+                job.progress.as_deref().unwrap_or("-"),
+                job.create_time,
+                job.transaction_id
+            ),
+            false,
+        ));
+        if let Some(reason) = job.attention(now, warn_hours) {
+            lines.push((format!("        ^ {reason}"), true));
+        }
+    }
+    lines
+}
+
+/// Poll `job` and print state/progress changes until it finishes or Ctrl-C is pressed
+fn watch_job(doris: &DorisConfig, database: &str, job: &AlterJob) -> Result<()> {
+    ui::print_info(&format!(
+        "Watching job {} every {}s; press Ctrl-C to stop.",
+        job.job_id,
+        WATCH_INTERVAL.as_secs()
+    ));
+    interrupt::catching(|| {
+        let mut last = (job.state.clone(), job.progress.clone());
+        loop {
+            if !interrupt::sleep_unless_interrupted(WATCH_INTERVAL) {
+                ui::print_info("Stopped watching.");
+                return Ok(());
+            }
+
+            let Some(current) = query_jobs(doris, database, job.kind)?
+                .into_iter()
+                .find(|j| j.job_id == job.job_id)
+            else {
+                ui::print_warning(&format!("Job {} is no longer listed.", job.job_id));
+                return Ok(());
+            };
+            let now = (current.state.clone(), current.progress.clone());
+            if now != last {
+                ui::print_info(&format!(
+                    "{} {} -> {} {}",
+                    Local::now().format("%H:%M:%S"),
+                    current.job_id,
+                    current.state,
+                    current.progress.as_deref().unwrap_or("")
+                ));
+                last = now;
+            }
+            if current.is_finished() {
+                match current.msg.as_deref() {
+                    Some(msg) if current.state == "CANCELLED" => {
+                        ui::print_error(&format!("Job cancelled: {msg}"))
+                    }
+                    _ => ui::print_success(&format!("Job {} {}", current.job_id, current.state)),
+                }
+                return Ok(());
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_jobs_and_flags_long_running_or_cancelled() {
+        let output = "*************************** 1. row ***************************
+        JobId: 17001
+    TableName: orders
+   CreateTime: 2024-05-01 02:00:00.123
+   FinishTime: NULL
+    IndexName: orders
+TransactionId: 5001
+        State: RUNNING
+          Msg:
+     Progress: 12/40
+      Timeout: 86400
+*************************** 2. row ***************************
+        JobId: 17002
+    TableName: items
+   CreateTime: 2024-05-01 09:00:00
+TransactionId: 5002
+        State: CANCELLED
+          Msg: Create replicas failed. Error: disk full
+     Progress: N/A
+";
+        let jobs = parse_alter_jobs(output, AlterKind::Column);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].progress.as_deref(), Some("12/40"));
+        assert_eq!(jobs[0].msg, None);
+        assert_eq!(jobs[1].progress, None);
+
+        let now =
+            NaiveDateTime::parse_from_str("2024-05-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(
+            jobs[0].attention(now, 6).as_deref(),
+            Some("RUNNING for 8.0h")
+        );
+        assert_eq!(jobs[0].attention(now, 12), None);
+        assert!(jobs[1].attention(now, 6).unwrap().contains("disk full"));
+    }
+}
@@ -0,0 +1,421 @@
+use chrono::{Duration, NaiveDateTime};
+use console::style;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::config_loader::Environment;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::format_utils::{self, csv_field};
+use crate::tools::common::fs_utils;
+use crate::tools::common::log_scan::{self, ScanProgress};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::{FormatHelper, InputHelper, MAX_RECENT_MINUTES};
+
+const TOP_USERS: usize = 10;
+
+// Field names differ between releases; the first alias present in the layout wins
+const KEY_QUERY_ID: &[&str] = &["QueryId"];
+const KEY_USER: &[&str] = &["User"];
+const KEY_DB: &[&str] = &["Db"];
+const KEY_TIME: &[&str] = &["Time(ms)", "Time"];
+const KEY_SCAN_BYTES: &[&str] = &["ScanBytes"];
+const KEY_STATE: &[&str] = &["State"];
+const KEY_ERROR_CODE: &[&str] = &["ErrorCode"];
+const KEY_STMT: &[&str] = &["Stmt"];
+
+/// One query parsed from fe.audit.log
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub timestamp: NaiveDateTime,
+    pub query_id: String,
+    pub user: String,
+    pub db: String,
+    pub time_ms: u64,
+    pub scan_bytes: u64,
+    pub state: String,
+    pub error_code: String,
+    pub stmt: String,
+}
+
+impl AuditEntry {
+    pub fn is_failure(&self) -> bool {
+        !matches!(self.state.as_str(), "EOF" | "OK" | "")
+    }
+}
+
+/// Field keys of an audit log, detected from its first entry.
+///
+/// Only these keys start a new field, so a `|` inside a statement is kept as text.
+#[derive(Debug, Clone)]
+pub struct AuditLayout {
+    keys: Vec<String>,
+}
+
+impl AuditLayout {
+    pub fn detect(line: &str) -> Option<Self> {
+        let (_, body) = split_prefix(line)?;
+        let keys: Vec<String> = body
+            .split('|')
+            .filter_map(|seg| seg.split_once('='))
+            .map(|(k, _)| k.to_string())
+            .collect();
+        keys.iter()
+            .any(|k| KEY_QUERY_ID.contains(&k.as_str()))
+            .then_some(Self { keys })
+    }
+
+    fn is_key(&self, key: &str) -> bool {
+        self.keys.iter().any(|k| k == key)
+    }
+
+    pub fn parse(&self, line: &str) -> Option<AuditEntry> {
+        let (timestamp, body) = split_prefix(line)?;
+
+        let mut fields: HashMap<&str, String> = HashMap::new();
+        let mut last: Option<&str> = None;
+        for seg in body.split('|') {
+            match seg.split_once('=') {
+                Some((k, v)) if self.is_key(k) => {
+                    fields.insert(k, v.to_string());
+                    last = Some(k);
+                }
+                _ => {
+                    if let Some(k) = last
+                        && let Some(v) = fields.get_mut(k)
+                    {
+                        v.push('|');
+                        v.push_str(seg);
+                    }
+                }
+            }
+        }
+
+        let text = |aliases: &[&str]| {
+            aliases
+                .iter()
+                .find_map(|a| fields.get(a))
+                .map(|v| v.trim().to_string())
+                .unwrap_or_default()
+        };
+        let number = |aliases: &[&str]| text(aliases).parse::<u64>().unwrap_or(0);
+
+        Some(AuditEntry {
+            timestamp,
+            query_id: text(KEY_QUERY_ID),
+            user: text(KEY_USER),
+            db: text(KEY_DB),
+            time_ms: number(KEY_TIME),
+            scan_bytes: number(KEY_SCAN_BYTES),
+            state: text(KEY_STATE),
+            error_code: text(KEY_ERROR_CODE),
+            stmt: text(KEY_STMT),
+        })
+    }
+}
+
+/// Split `2024-01-15 10:23:45,123 [query] |k=v|...` into timestamp and field body
+fn split_prefix(line: &str) -> Option<(NaiveDateTime, &str)> {
+    let ts = line.get(..23)?;
+    let timestamp = NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S,%3f").ok()?;
+    let body = &line[line.find('|')? + 1..];
+    Some((timestamp, body))
+}
+
+pub struct FeAuditLogAnalyzer;
+
+impl Tool for FeAuditLogAnalyzer {
+    fn name(&self) -> &str {
+        "fe-audit-log"
+    }
+
+    fn description(&self) -> &str {
+        "Analyze slow and failed queries from fe.audit.log"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let log_dir = doris.service_paths(Environment::FE).log_dir;
+
+        let files = match fs_utils::collect_log_files(&log_dir, "fe.audit.log") {
+            Ok(files) => files,
+            Err(_) => {
+                ui::print_warning(&format!("No audit logs found in {}", log_dir.display()));
+                return Err(CliError::GracefulExit);
+            }
+        };
+
+        let minutes = InputHelper::prompt_number_with_default(
+            "Analyze recent minutes",
+            60,
+            1,
+            Some(MAX_RECENT_MINUTES),
+            Some("minutes"),
+        )?;
+        let top_n = InputHelper::prompt_number_with_default(
+            "Show top N slowest queries",
+            20,
+            1,
+            Some(1000),
+            Some("queries"),
+        )? as usize;
+
+        let files = log_scan::prefilter_recent_minutes(files, minutes);
+        ui::print_info(&format!(
+            "Analyzing {} audit log file(s) in {} (last {minutes} min)...",
+            files.len(),
+            log_dir.display()
+        ));
+
+        let outcome = log_scan::scan_files_parallel(
+            &files,
+            config.log_scan_workers(),
+            !config.no_progress_animation,
+            scan_audit_file,
+            |e| e.timestamp,
+        );
+        log_scan::print_scan_failures(&outcome.failures);
+
+        if outcome.entries.is_empty() {
+            return Err(CliError::ToolExecutionFailed(format!(
+                "No audit entries found in {}",
+                log_dir.display()
+            )));
+        }
+
+        let latest = outcome.entries.iter().map(|e| e.timestamp).max();
+        let clock_note = latest.and_then(ui::check_log_clock_skew);
+        let entries = filter_entries_by_window(outcome.entries, minutes);
+        display_summary(
+            &entries,
+            top_n,
+            config.audit_stmt_len(),
+            clock_note.as_deref(),
+        );
+
+        let output_path = write_entries_csv(config, &entries)?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("Audit log analysis completed ({} queries)", entries.len()),
+        ))
+    }
+}
+
+fn scan_audit_file(path: &Path, progress: &ScanProgress) -> Result<Vec<AuditEntry>> {
+    let f = fs::File::open(path).map_err(CliError::IoError)?;
+    let reader = BufReader::new(f);
+    let mut layout: Option<AuditLayout> = None;
+    let mut out = Vec::new();
+
+    for line in reader.lines() {
+        let Ok(line) = line else { continue };
+        progress.add(line.len() as u64 + 1);
+
+        if layout.is_none() {
+            layout = AuditLayout::detect(&line);
+        }
+        if let Some(entry) = layout.as_ref().and_then(|l| l.parse(&line)) {
+            out.push(entry);
+        } else if let Some(prev) = out.last_mut() {
+            // Multi-line statements continue on lines without a timestamp prefix
+            prev.stmt.push('\n');
+            prev.stmt.push_str(&line);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Keep entries (sorted by time) in the last `minutes`, anchored on the newest entry
+fn filter_entries_by_window(mut entries: Vec<AuditEntry>, minutes: i64) -> Vec<AuditEntry> {
+    if let Some(latest) = entries.last().map(|e| e.timestamp) {
+        let window_start = latest - Duration::minutes(minutes);
+        entries.retain(|e| e.timestamp >= window_start);
+    }
+    entries
+}
+
+/// Per-user (query count, total ms), ordered by total time descending
+fn top_users(entries: &[AuditEntry]) -> Vec<(String, usize, u64)> {
+    let mut by_user: HashMap<&str, (usize, u64)> = HashMap::new();
+    for e in entries {
+        let v = by_user.entry(e.user.as_str()).or_default();
+        v.0 += 1;
+        v.1 += e.time_ms;
+    }
+    let mut users: Vec<(String, usize, u64)> = by_user
+        .into_iter()
+        .map(|(u, (c, t))| (u.to_string(), c, t))
+        .collect();
+    users.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+    users
+}
+
+/// Failure counts keyed by "State ErrorCode"
+fn failures_by_state(entries: &[AuditEntry]) -> BTreeMap<String, usize> {
+    let mut out = BTreeMap::new();
+    for e in entries.iter().filter(|e| e.is_failure()) {
+        let key = if e.error_code.is_empty() {
+            e.state.clone()
+        } else {
+            format!("{} (ErrorCode={})", e.state, e.error_code)
+        };
+        *out.entry(key).or_insert(0) += 1;
+    }
+    out
+}
+
+fn display_summary(
+    entries: &[AuditEntry],
+    top_n: usize,
+    stmt_len: usize,
+    clock_note: Option<&str>,
+) {
+    ui::print_info("");
+    ui::print_info("Audit Log Summary");
+    ui::print_info(&"-".repeat(60));
+    if let Some(note) = clock_note {
+        ui::print_info(note);
+    }
+    ui::print_info(&format!("Queries: {}", entries.len()));
+    ui::print_info(&format!(
+        "Failed: {}",
+        entries.iter().filter(|e| e.is_failure()).count()
+    ));
+
+    let mut slowest: Vec<&AuditEntry> = entries.iter().collect();
+    slowest.sort_by_key(|e| std::cmp::Reverse(e.time_ms));
+
+    ui::print_info("");
+    ui::print_info(&format!("Top {top_n} slowest queries"));
+    ui::print_info(&format!(
+        "{:>10} {:>10} {:<16} {:<16} {:<6} Stmt",
+        "Time(ms)", "Scan", "User", "Db", "State"
+    ));
+    ui::print_info(&"-".repeat(70 + stmt_len));
+    for e in slowest.iter().take(top_n) {
+        let line = format!(
+            "{:>10} {:>10} {:<16} {:<16} {:<6} {}",
+            e.time_ms,
+            format_utils::format_bytes(e.scan_bytes, 1, false),
+            FormatHelper::truncate_string(&e.user, 16),
+            FormatHelper::truncate_string(&e.db, 16),
+            e.state,
+            FormatHelper::truncate_string(&e.stmt.replace('\n', " "), stmt_len)
+        );
+        if e.is_failure() {
+            ui::print_info(&style(line).red().to_string());
+        } else {
+            ui::print_info(&line);
+        }
+    }
+
+    ui::print_info("");
+    ui::print_info("Top users by total query time");
+    ui::print_info(&format!(
+        "{:<24} {:>10} {:>14} {:>10}",
+        "User", "Queries", "Total(ms)", "Avg(ms)"
+    ));
+    ui::print_info(&"-".repeat(61));
+    for (user, count, total) in top_users(entries).iter().take(TOP_USERS) {
+        ui::print_info(&format!(
+            "{:<24} {:>10} {:>14} {:>10}",
+            FormatHelper::truncate_string(user, 24),
+            count,
+            total,
+            total / *count as u64
+        ));
+    }
+
+    let failures = failures_by_state(entries);
+    if failures.is_empty() {
+        return;
+    }
+    ui::print_info("");
+    ui::print_warning("Failures by state:");
+    for (state, count) in &failures {
+        println!("  {}", style(format!("{state}: {count}")).red());
+    }
+}
+
+fn write_entries_csv(config: &Config, entries: &[AuditEntry]) -> Result<PathBuf> {
+    let file_name = file_name::timestamped("fe_audit_queries", "csv");
+
+    let mut content =
+        String::from("timestamp,query_id,user,db,time_ms,scan_bytes,state,error_code,stmt\n");
+    for e in entries {
+        content.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            e.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+            csv_field(&e.query_id),
+            csv_field(&e.user),
+            csv_field(&e.db),
+            e.time_ms,
+            e.scan_bytes,
+            csv_field(&e.state),
+            csv_field(&e.error_code),
+            csv_field(&e.stmt)
+        ));
+    }
+    fs_utils::write_artifact(config, &file_name, content.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_LAYOUT: &str = "2023-06-01 12:00:01,100 [query] |Client=10.0.0.1:5432|User=root|Db=default_cluster:sales|State=EOF|Time=1532|ScanBytes=2048|ScanRows=10|ReturnRows=1|StmtId=7|QueryId=a1b2|IsQuery=true|feIp=10.0.0.2|Stmt=select a | b from t|Digest=";
+    const NEW_LAYOUT: &str = "2024-03-10 08:15:30,250 [query] |QueryId=c3d4|Timestamp=2024-03-10 08:15:28.000|Client=10.0.0.1:5432|User=etl|FeIp=10.0.0.2|Ctl=internal|Db=logs|State=ERR|ErrorCode=1105|ErrorMessage=timeout|Time(ms)=30001|ScanBytes=0|ScanRows=0|ReturnRows=0|StmtId=9|Stmt=insert into t select * from s";
+
+    #[test]
+    fn parses_old_and_new_field_orders() {
+        let old = AuditLayout::detect(OLD_LAYOUT).unwrap();
+        let e = old.parse(OLD_LAYOUT).unwrap();
+        assert_eq!(e.query_id, "a1b2");
+        assert_eq!(e.db, "default_cluster:sales");
+        assert_eq!(e.time_ms, 1532);
+        assert_eq!(e.stmt, "select a | b from t");
+        assert!(!e.is_failure());
+
+        let new = AuditLayout::detect(NEW_LAYOUT).unwrap();
+        let e = new.parse(NEW_LAYOUT).unwrap();
+        assert_eq!((e.user.as_str(), e.time_ms), ("etl", 30001));
+        assert_eq!(e.error_code, "1105");
+        assert!(e.is_failure());
+
+        assert!(AuditLayout::detect("not an audit line").is_none());
+    }
+
+    #[test]
+    fn aggregates_users_and_failures() {
+        let layout = AuditLayout::detect(NEW_LAYOUT).unwrap();
+        let failed = layout.parse(NEW_LAYOUT).unwrap();
+        let ok = AuditEntry {
+            user: "root".to_string(),
+            state: "EOF".to_string(),
+            error_code: String::new(),
+            time_ms: 10,
+            ..failed.clone()
+        };
+        let entries = vec![ok.clone(), failed.clone(), ok, failed];
+
+        let users = top_users(&entries);
+        assert_eq!(users[0], ("etl".to_string(), 2, 60002));
+        assert_eq!(users[1], ("root".to_string(), 2, 20));
+
+        let failures = failures_by_state(&entries);
+        assert_eq!(failures.get("ERR (ErrorCode=1105)"), Some(&2));
+        assert_eq!(failures.len(), 1);
+
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,319 @@
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::error::{CliError, Result};
+use crate::tools::be::be_http_client::{self, BeTarget};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::mysql::{ClusterInfo, MySQLTool};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::table::TableBuilder;
+use console::style;
+
+/// Endpoints tried in order; older builds only answer the second
+const BE_VERSION_PATHS: &[&str] = &["/api/be_version_info", "/version"];
+const BE_VERSION_TIMEOUT_SECS: u64 = 3;
+
+/// Version and build details of one FE or BE
+#[derive(Debug, Clone, Default, PartialEq)]
+struct NodeVersion {
+    role: &'static str,
+    host: String,
+    alive: bool,
+    version: String,
+    build_hash: Option<String>,
+    build_time: Option<String>,
+}
+
+/// Build details from `/api/be_version_info`; any missing field stays None
+#[derive(Debug, Clone, Default, PartialEq)]
+struct BuildInfo {
+    version: Option<String>,
+    hash: Option<String>,
+    time: Option<String>,
+}
+
+/// First string under `key` anywhere in `value`
+fn find_string(value: &serde_json::Value, key: &str) -> Option<String> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .or_else(|| map.values().find_map(|v| find_string(v, key))),
+        serde_json::Value::Array(items) => items.iter().find_map(|v| find_string(v, key)),
+        _ => None,
+    }
+}
+
+fn parse_be_version_info(body: &str) -> Option<BuildInfo> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    let info = BuildInfo {
+        version: find_string(&json, "dorisBuildVersion"),
+        // `git://host/path@8f1a2b3c...`; only the hash is interesting
+        hash: find_string(&json, "dorisBuildHash")
+            .map(|h| h.rsplit('@').next().unwrap_or(&h).trim().to_string()),
+        time: find_string(&json, "dorisBuildTime"),
+    };
+    (info != BuildInfo::default()).then_some(info)
+}
+
+/// Commit suffix of a release string such as `doris-2.1.4-rc01-8f1a2b3c4d`
+fn hash_from_version(version: &str) -> Option<String> {
+    let last = version.rsplit('-').next()?;
+    (last.len() >= 7 && last.chars().all(|c| c.is_ascii_hexdigit())).then(|| last.to_string())
+}
+
+/// Most common version among alive nodes; ties go to the one seen first,
+/// which puts frontends ahead of backends
+fn majority_version(nodes: &[NodeVersion]) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for node in nodes.iter().filter(|n| n.alive && !n.version.is_empty()) {
+        match counts.iter_mut().find(|(v, _)| *v == node.version) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((&node.version, 1)),
+        }
+    }
+    counts
+        .iter()
+        .rev()
+        .max_by_key(|(_, n)| *n)
+        .map(|(v, _)| v.to_string())
+}
+
+/// Matrix lines, flagged when a node runs something other than the majority
+fn render_matrix(nodes: &[NodeVersion], majority: Option<&str>) -> Vec<(String, bool)> {
+    let mut table = TableBuilder::new(["Role", "Host", "Version", "Build hash", "Build time"])
+        .max_width(2, 40)
+        .max_width(4, 32);
+    let mut flags = Vec::new();
+    for node in nodes {
+        let differs = majority.is_some_and(|m| node.version != m);
+        let version = match (node.alive, differs) {
+            (false, _) => format!("{} (down)", node.version),
+            (true, true) => format!("{} (!)", node.version),
+            (true, false) => node.version.clone(),
+        };
+        table.push_row([
+            node.role.to_string(),
+            node.host.clone(),
+            version,
+            node.build_hash.clone().unwrap_or_else(|| "-".to_string()),
+            node.build_time.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+        flags.push(differs);
+    }
+    // Three frame lines precede the first row
+    table
+        .render()
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let flagged = i.checked_sub(3).and_then(|r| flags.get(r)).copied();
+            (line.to_string(), flagged.unwrap_or(false))
+        })
+        .collect()
+}
+
+fn cluster_info(doris: &DorisConfig) -> Result<ClusterInfo> {
+    match MySQLTool.query_cluster_info(doris) {
+        Ok(info) => Ok(info),
+        Err(e @ (CliError::MySQLAccessDenied(_) | CliError::MySQLPrivilegeDenied { .. })) => Err(e),
+        Err(e) => {
+            ui::print_warning(&format!(
+                "Live cluster query failed ({e}); using clusters.toml"
+            ));
+            ClusterInfo::load_from_file()
+        }
+    }
+}
+
+/// Version matrix of every FE and BE, with BE build details over HTTP
+pub struct ClusterVersionTool;
+
+impl Tool for ClusterVersionTool {
+    fn name(&self) -> &str {
+        "cluster-version"
+    }
+
+    fn description(&self) -> &str {
+        "FE/BE versions and build info; flags nodes off the majority version"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let info = cluster_info(&doris)?;
+        if info.frontends.is_empty() && info.backends.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No frontends or backends found in the cluster info".to_string(),
+            ));
+        }
+
+        let mut nodes: Vec<NodeVersion> = info
+            .frontends
+            .iter()
+            .map(|fe| NodeVersion {
+                role: if fe.is_master { "FE*" } else { "FE" },
+                host: format!("{}:{}", fe.host, fe.http_port),
+                alive: fe.alive,
+                version: fe.version.clone(),
+                build_hash: hash_from_version(&fe.version),
+                build_time: None,
+            })
+            .collect();
+
+        let mut no_endpoint = Vec::new();
+        for be in &info.backends {
+            let target = BeTarget {
+                host: be.host.clone(),
+                port: be.http_port,
+            };
+            let mut node = NodeVersion {
+                role: "BE",
+                host: target.to_string(),
+                alive: be.alive,
+                version: be.version.clone(),
+                build_hash: hash_from_version(&be.version),
+                build_time: None,
+            };
+            if be.alive {
+                ui::print_info(&format!("Reading build info of BE {target}..."));
+                let build = BE_VERSION_PATHS.iter().find_map(|path| {
+                    be_http_client::request_be_target_bytes(&target, path, BE_VERSION_TIMEOUT_SECS)
+                        .ok()
+                        .and_then(|body| parse_be_version_info(&String::from_utf8_lossy(&body)))
+                });
+                match build {
+                    Some(build) => {
+                        node.build_hash = build.hash.or(node.build_hash);
+                        node.build_time = build.time;
+                        if node.version.is_empty()
+                            && let Some(version) = build.version
+                        {
+                            node.version = version;
+                        }
+                    }
+                    None => no_endpoint.push(target.to_string()),
+                }
+            }
+            nodes.push(node);
+        }
+        let majority = majority_version(&nodes);
+        let lines = render_matrix(&nodes, majority.as_deref());
+        let differing = lines.iter().filter(|(_, flagged)| *flagged).count();
+
+        let mut report = format!(
+            "Cluster versions: {} FE, {} BE, majority {}\n\n",
+            info.frontends.len(),
+            info.backends.len(),
+            majority.as_deref().unwrap_or("-")
+        );
+        for (line, _) in &lines {
+            report.push_str(line);
+            report.push('\n');
+        }
+        report.push_str("\n* master FE, (!) differs from the majority version\n");
+        if !no_endpoint.is_empty() {
+            report.push_str(&format!(
+                "No build info endpoint on: {}\n",
+                no_endpoint.join(", ")
+            ));
+        }
+
+        if !config.stdout_only {
+            ui::print_info("");
+            for (line, flagged) in &lines {
+                if *flagged {
+                    println!("{}", style(line).red());
+                } else {
+                    println!("{line}");
+                }
+            }
+            if differing == 0 {
+                ui::print_success("All nodes run the same version.");
+            } else {
+                ui::print_warning(&format!(
+                    "{differing} node(s) differ from the majority version {}; \
+                     check for an unfinished upgrade",
+                    majority.as_deref().unwrap_or("-")
+                ));
+            }
+            if !no_endpoint.is_empty() {
+                ui::print_info(&format!(
+                    "Build info unavailable on {} BE(s) without a version endpoint",
+                    no_endpoint.len()
+                ));
+            }
+        }
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("cluster_version", "txt"),
+            report.as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "{} node(s) checked, {differing} off the majority version",
+                nodes.len()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_build_info_and_flags_minority_versions() {
+        let body = r#"{"msg":"success","code":0,"data":{"beVersionInfo":{
+            "dorisBuildVersionPrefix":"doris","dorisBuildVersion":"doris-2.1.4-rc01",
+            "dorisBuildHash":"git://vm-12@8f1a2b3c4d5e6f","dorisBuildTime":"Mon, 10 Jun 2024 08:00:00 CST"}},"count":0}"#;
+        assert_eq!(
+            parse_be_version_info(body),
+            Some(BuildInfo {
+                version: Some("doris-2.1.4-rc01".to_string()),
+                hash: Some("8f1a2b3c4d5e6f".to_string()),
+                time: Some("Mon, 10 Jun 2024 08:00:00 CST".to_string()),
+            })
+        );
+        assert_eq!(parse_be_version_info("<html>404</html>"), None);
+        assert_eq!(parse_be_version_info(r#"{"msg":"not found"}"#), None);
+        assert_eq!(
+            hash_from_version("doris-2.1.4-rc01-8f1a2b3c4d").as_deref(),
+            Some("8f1a2b3c4d")
+        );
+        assert_eq!(hash_from_version("doris-3.0.2"), None);
+
+        let node = |role, host: &str, version: &str, alive| NodeVersion {
+            role,
+            host: host.to_string(),
+            alive,
+            version: version.to_string(),
+            ..Default::default()
+        };
+        let nodes = vec![
+            node("FE*", "10.0.0.1:8030", "doris-2.1.4", true),
+            node("BE", "10.0.0.2:8040", "doris-2.1.4", true),
+            node("BE", "10.0.0.3:8040", "doris-2.1.3", true),
+            node("BE", "10.0.0.4:8040", "doris-2.1.3", false),
+        ];
+        let majority = majority_version(&nodes);
+        assert_eq!(majority.as_deref(), Some("doris-2.1.4"));
+        let lines = render_matrix(&nodes, majority.as_deref());
+        let flagged: Vec<&str> = lines
+            .iter()
+            .filter(|(_, f)| *f)
+            .map(|(l, _)| l.as_str())
+            .collect();
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged[0].contains("10.0.0.3:8040") && flagged[0].contains("doris-2.1.3 (!)"));
+        assert!(flagged[1].contains("(down)"));
+    }
+}
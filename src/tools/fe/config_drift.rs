@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::error::{CliError, Result};
+use crate::tools::common::format_utils::csv_field;
+use crate::tools::common::fs_utils;
+use crate::tools::common::{conf_inspector, file_name};
+use crate::tools::mysql::{ClusterInfo, Frontend, MySQLTool};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui::{self, FormatHelper};
+
+const VALUE_WIDTH: usize = 24;
+
+/// Config of one frontend: key -> (value, non-default)
+pub type FeConfig = BTreeMap<String, (String, bool)>;
+
+/// [`FeConfig`] of one node from its `ADMIN SHOW FRONTEND CONFIG` output
+pub fn fe_config(output: &str) -> FeConfig {
+    conf_inspector::parse_frontend_config(output)
+        .into_iter()
+        .map(|(key, v)| {
+            let non_default = v.is_non_default();
+            (key, (v.value, non_default))
+        })
+        .collect()
+}
+
+/// A key worth reporting, with the value each node has (None: key missing there)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriftEntry {
+    pub key: String,
+    pub values: Vec<Option<String>>,
+    pub differs: bool,
+    pub non_default: bool,
+}
+
+/// Keys whose values differ between `configs` or that are non-default on any node
+pub fn compare_configs(configs: &[FeConfig]) -> Vec<DriftEntry> {
+    let keys: BTreeSet<&String> = configs.iter().flat_map(|c| c.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let entries: Vec<Option<&(String, bool)>> =
+                configs.iter().map(|c| c.get(key)).collect();
+            let values: Vec<Option<String>> =
+                entries.iter().map(|e| e.map(|(v, _)| v.clone())).collect();
+            let differs = values.windows(2).any(|w| w[0] != w[1]);
+            let non_default = entries.iter().flatten().any(|(_, nd)| *nd);
+            (differs || non_default).then(|| DriftEntry {
+                key: key.clone(),
+                values,
+                differs,
+                non_default,
+            })
+        })
+        .collect()
+}
+
+fn reason(entry: &DriftEntry) -> &'static str {
+    match (entry.differs, entry.non_default) {
+        (true, true) => "differs,non-default",
+        (true, false) => "differs",
+        _ => "non-default",
+    }
+}
+
+fn render_table(nodes: &[String], drift: &[DriftEntry]) -> String {
+    let key_width = drift
+        .iter()
+        .map(|d| d.key.len())
+        .max()
+        .unwrap_or(3)
+        .clamp(3, 48);
+    let mut out = format!("{:<key_width$}  {:<19}", "Key", "Reason");
+    for node in nodes {
+        out.push_str(&format!(
+            "  {:<VALUE_WIDTH$}",
+            FormatHelper::truncate_string(node, VALUE_WIDTH)
+        ));
+    }
+    out.push('\n');
+    out.push_str(&"-".repeat(key_width + 21 + nodes.len() * (VALUE_WIDTH + 2)));
+    out.push('\n');
+    for entry in drift {
+        out.push_str(&format!(
+            "{:<key_width$}  {:<19}",
+            FormatHelper::truncate_string(&entry.key, key_width),
+            reason(entry)
+        ));
+        for value in &entry.values {
+            let shown = value.as_deref().unwrap_or("(missing)");
+            out.push_str(&format!(
+                "  {:<VALUE_WIDTH$}",
+                FormatHelper::truncate_string(shown, VALUE_WIDTH)
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_csv(nodes: &[String], drift: &[DriftEntry]) -> String {
+    let mut out = String::from("key,reason");
+    for node in nodes {
+        out.push(',');
+        out.push_str(&csv_field(node));
+    }
+    out.push('\n');
+    for entry in drift {
+        out.push_str(&csv_field(&entry.key));
+        out.push(',');
+        out.push_str(&csv_field(reason(entry)));
+        for value in &entry.values {
+            out.push(',');
+            out.push_str(&csv_field(value.as_deref().unwrap_or("")));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn frontends(doris: &DorisConfig) -> Result<Vec<Frontend>> {
+    let cached = ClusterInfo::load_from_file()
+        .map(|info| info.frontends)
+        .unwrap_or_default();
+    if !cached.is_empty() {
+        return Ok(cached);
+    }
+    Ok(MySQLTool.query_cluster_info(doris)?.frontends)
+}
+
+/// Compares `ADMIN SHOW FRONTEND CONFIG` across all frontends
+pub struct FeConfigDriftTool;
+
+impl Tool for FeConfigDriftTool {
+    fn name(&self) -> &str {
+        "fe-config-drift"
+    }
+
+    fn description(&self) -> &str {
+        "Compare FE configs across all frontends"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let frontends = frontends(&doris)?;
+        if frontends.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No frontends found in the cluster info".to_string(),
+            ));
+        }
+
+        let mut nodes = Vec::new();
+        let mut configs = Vec::new();
+        let mut unreachable = Vec::new();
+        for fe in &frontends {
+            let label = format!("{}:{}", fe.host, fe.query_port);
+            if !fe.alive {
+                unreachable.push(format!("{label} (not alive)"));
+                continue;
+            }
+            ui::print_info(&format!("Reading config of {label}..."));
+            match MySQLTool::query_sql_on_host(
+                &doris,
+                &fe.host,
+                fe.query_port,
+                conf_inspector::FRONTEND_CONFIG_SQL,
+            ) {
+                Ok(output) => {
+                    nodes.push(label);
+                    configs.push(fe_config(&output));
+                }
+                // Credentials are shared; no point asking every node
+                Err(
+                    e @ (CliError::MySQLAccessDenied(_) | CliError::MySQLPrivilegeDenied { .. }),
+                ) => {
+                    return Err(e);
+                }
+                Err(e) => unreachable.push(format!("{label} ({e})")),
+            }
+        }
+        if configs.is_empty() {
+            return Err(CliError::ToolExecutionFailed(format!(
+                "No frontend could be reached: {}",
+                unreachable.join("; ")
+            )));
+        }
+
+        let drift = compare_configs(&configs);
+        let differing = drift.iter().filter(|d| d.differs).count();
+        let table = render_table(&nodes, &drift);
+        let master = frontends
+            .iter()
+            .find(|fe| fe.is_master)
+            .map(|fe| format!(", master {}:{}", fe.host, fe.query_port))
+            .unwrap_or_default();
+        let mut report = format!("Compared {} frontend(s){master}\n\n{table}", nodes.len());
+        if !unreachable.is_empty() {
+            report.push_str("\nUnreachable:\n");
+            for node in &unreachable {
+                report.push_str(&format!("  {node}\n"));
+            }
+        }
+
+        ui::print_info("");
+        ui::print_info(&format!("Compared {} frontend(s){master}", nodes.len()));
+        if drift.is_empty() {
+            ui::print_success("All reachable frontends share the same config.");
+        } else {
+            ui::print_info(&table);
+        }
+        for node in &unreachable {
+            ui::print_warning(&format!("Unreachable: {node}"));
+        }
+        let timestamp = file_name::timestamp();
+        fs_utils::write_artifact(
+            config,
+            &file_name::timestamped_at("fe_config_drift", &timestamp, "csv"),
+            render_csv(&nodes, &drift).as_bytes(),
+        )?;
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped_at("fe_config_drift", &timestamp, "txt"),
+            report.as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "{differing} key(s) differ across {} frontend(s), {} non-default",
+                nodes.len(),
+                drift.iter().filter(|d| d.non_default).count()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_differing_and_non_default_keys() {
+        let fe1 = fe_config(
+            "*************************** 1. row ***************************
+        Key: max_bytes_per_broker_scanner
+      Value: 536870912
+       Type: long
+  IsMutable: true
+ MasterOnly: true
+    Comment:
+*************************** 2. row ***************************
+        Key: edit_log_type
+      Value: bdb
+       Type: String
+  IsMutable: false
+ MasterOnly: false
+    Comment:
+*************************** 3. row ***************************
+        Key: audit_log_dir
+      Value: /opt/doris/fe/log
+       Type: String
+  IsMutable: false
+ MasterOnly: false
+    Comment:
+",
+        );
+        let fe2 = fe_config(
+            "*************************** 1. row ***************************
+         Key: max_bytes_per_broker_scanner
+       Value: 1073741824
+DefaultValue: 536870912
+*************************** 2. row ***************************
+         Key: edit_log_type
+       Value: bdb
+DefaultValue: bdb
+*************************** 3. row ***************************
+         Key: qe_max_connection
+       Value: 4096
+DefaultValue: 1024
+",
+        );
+        assert_eq!(fe1.len(), 3);
+        assert_eq!(fe1["audit_log_dir"].0, "/opt/doris/fe/log");
+
+        let drift = compare_configs(&[fe1, fe2]);
+        let keys: Vec<&str> = drift.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "audit_log_dir",
+                "max_bytes_per_broker_scanner",
+                "qe_max_connection"
+            ]
+        );
+        assert_eq!(drift[0].values[1], None);
+        assert_eq!(reason(&drift[1]), "differs,non-default");
+
+        let csv = render_csv(&["fe1:9030".into(), "fe2:9030".into()], &drift);
+        assert!(csv.starts_with("key,reason,fe1:9030,fe2:9030\n"));
+        assert!(csv.contains("\nqe_max_connection,\"differs,non-default\",,4096\n"));
+    }
+}
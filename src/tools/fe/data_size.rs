@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+
+use super::replica_health::ProcTable;
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::format_utils::{csv_field, format_bytes, parse_size};
+use crate::tools::common::fs_utils;
+use crate::tools::mysql::MySQLTool;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::table::{Align, TableBuilder};
+
+const TOP_TABLES: usize = 10;
+
+/// Rows SHOW DATA appends after the tables
+const SUMMARY_ROWS: &[&str] = &["Total", "Quota", "Left"];
+
+/// One table from SHOW DATA; sizes cover all replicas
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TableSize {
+    name: String,
+    size: u64,
+    replica_count: u64,
+    remote_size: Option<u64>,
+}
+
+/// A database's SHOW DATA result
+#[derive(Debug, Clone, Default, PartialEq)]
+struct DbSize {
+    name: String,
+    tables: Vec<TableSize>,
+    /// Data and replica quota as printed, on releases that report them
+    data_quota: Option<String>,
+    replica_quota: Option<String>,
+    /// Replicas per tablet from `/statistic`, for the single-copy estimate
+    copies: Option<f64>,
+}
+
+impl DbSize {
+    fn size(&self) -> u64 {
+        self.tables.iter().map(|t| t.size).sum()
+    }
+
+    fn replica_count(&self) -> u64 {
+        self.tables.iter().map(|t| t.replica_count).sum()
+    }
+}
+
+fn parse_show_data(db: &str, output: &str) -> DbSize {
+    let table = ProcTable::parse(output);
+    let mut out = DbSize {
+        name: db.to_string(),
+        ..Default::default()
+    };
+    for row in &table.rows {
+        let name = table.value(row, &["TableName"]).unwrap_or_default();
+        let size = table.value(row, &["Size", "DataSize"]).unwrap_or_default();
+        let replicas = table
+            .value(row, &["ReplicaCount"])
+            .unwrap_or_default()
+            .trim();
+        match name {
+            "Quota" => {
+                out.data_quota = Some(size.to_string()).filter(|s| !s.is_empty());
+                out.replica_quota = Some(replicas.to_string()).filter(|s| !s.is_empty());
+            }
+            _ if name.is_empty() || SUMMARY_ROWS.contains(&name) => {}
+            _ => out.tables.push(TableSize {
+                name: name.to_string(),
+                size: parse_size(size),
+                replica_count: replicas.parse().unwrap_or(0),
+                remote_size: table.value(row, &["RemoteSize"]).map(parse_size),
+            }),
+        }
+    }
+    out.tables
+        .sort_by(|a, b| b.size.cmp(&a.size).then(a.name.cmp(&b.name)));
+    out
+}
+
+/// Replicas per tablet of each database in `SHOW PROC '/statistic'`
+fn parse_copies(statistic: &str) -> HashMap<String, f64> {
+    let table = ProcTable::parse(statistic);
+    table
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let name = table.value(row, &["DbName"])?;
+            let tablets: f64 = table.value(row, &["TabletNum"])?.parse().ok()?;
+            let replicas: f64 = table.value(row, &["ReplicaNum"])?.parse().ok()?;
+            (tablets > 0.0).then(|| (name.to_string(), replicas / tablets))
+        })
+        .collect()
+}
+
+/// Total size over total single-copy size, weighting each database by its size
+fn cluster_copy_ratio(dbs: &[DbSize]) -> Option<f64> {
+    let (mut total, mut single) = (0.0, 0.0);
+    for db in dbs {
+        let copies = db.copies.filter(|c| *c > 0.0)?;
+        total += db.size() as f64;
+        single += db.size() as f64 / copies;
+    }
+    (single > 0.0).then(|| total / single)
+}
+
+fn render_report(dbs: &[DbSize]) -> String {
+    let quotas = dbs.iter().any(|d| d.data_quota.is_some());
+    let mut headers = vec!["Database", "Size", "Single copy", "Replicas", "Tables"];
+    if quotas {
+        headers.extend(["Data quota", "Replica quota"]);
+    }
+    let mut summary = TableBuilder::new(headers)
+        .max_width(0, 40)
+        .align(1, Align::Right)
+        .align(2, Align::Right)
+        .align(3, Align::Right)
+        .align(4, Align::Right)
+        .empty_message("(no databases)");
+    for db in dbs {
+        let mut row = vec![
+            db.name.clone(),
+            format_bytes(db.size(), 2, false),
+            db.copies
+                .filter(|c| *c > 0.0)
+                .map(|c| format_bytes((db.size() as f64 / c) as u64, 2, false))
+                .unwrap_or_else(|| "-".to_string()),
+            db.replica_count().to_string(),
+            db.tables.len().to_string(),
+        ];
+        if quotas {
+            row.push(db.data_quota.clone().unwrap_or_else(|| "-".to_string()));
+            row.push(db.replica_quota.clone().unwrap_or_else(|| "-".to_string()));
+        }
+        summary.push_row(row);
+    }
+
+    let total: u64 = dbs.iter().map(DbSize::size).sum();
+    let mut out = summary.render();
+    out.push_str(&format!(
+        "Cluster total: {} in {} database(s)",
+        format_bytes(total, 2, false),
+        dbs.len()
+    ));
+    match cluster_copy_ratio(dbs) {
+        Some(ratio) => out.push_str(&format!(
+            ", {ratio:.2}x a single copy ({})\n",
+            format_bytes((total as f64 / ratio) as u64, 2, false)
+        )),
+        None => out.push('\n'),
+    }
+
+    for db in dbs.iter().filter(|d| !d.tables.is_empty()) {
+        let mut top = TableBuilder::new(["Table", "Size", "Replicas", "Remote"])
+            .max_width(0, 48)
+            .align(1, Align::Right)
+            .align(2, Align::Right)
+            .align(3, Align::Right);
+        for t in db.tables.iter().take(TOP_TABLES) {
+            top.push_row([
+                t.name.clone(),
+                format_bytes(t.size, 2, false),
+                t.replica_count.to_string(),
+                t.remote_size
+                    .map(|s| format_bytes(s, 2, false))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]);
+        }
+        let shown = db.tables.len().min(TOP_TABLES);
+        out.push_str(&format!(
+            "\nLargest tables in {} ({shown} of {}):\n",
+            db.name,
+            db.tables.len()
+        ));
+        out.push_str(&top.render());
+    }
+    out
+}
+
+fn render_csv(dbs: &[DbSize]) -> String {
+    let quotas = dbs.iter().any(|d| d.data_quota.is_some());
+    let mut out = String::from("database,table,size_bytes,replica_count,remote_size_bytes");
+    if quotas {
+        out.push_str(",db_data_quota,db_replica_quota");
+    }
+    out.push('\n');
+    for db in dbs {
+        for t in &db.tables {
+            out.push_str(&format!(
+                "{},{},{},{},{}",
+                csv_field(&db.name),
+                csv_field(&t.name),
+                t.size,
+                t.replica_count,
+                t.remote_size.map(|s| s.to_string()).unwrap_or_default()
+            ));
+            if quotas {
+                out.push_str(&format!(
+                    ",{},{}",
+                    db.data_quota
+                        .as_deref()
+                        .map(|q| parse_size(q).to_string())
+                        .unwrap_or_default(),
+                    db.replica_quota.as_deref().unwrap_or_default()
+                ));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Database and table sizes from SHOW DATA
+pub struct FeDataSizeTool;
+
+impl Tool for FeDataSizeTool {
+    fn name(&self) -> &str {
+        "fe-data-size"
+    }
+
+    fn description(&self) -> &str {
+        "Database sizes with their largest tables from SHOW DATA"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let databases = MySQLTool::list_databases(&doris)?;
+        if databases.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No user databases found".to_string(),
+            ));
+        }
+
+        let mut options = vec!["All databases".to_string()];
+        options.extend(databases.iter().cloned());
+        let choice = ui::InteractiveSelector::new(options, "Report sizes for".to_string())
+            .select()?
+            .clone();
+        let selected: Vec<&String> = if choice == "All databases" {
+            databases.iter().collect()
+        } else {
+            databases.iter().filter(|d| **d == choice).collect()
+        };
+
+        // Replication factors are optional: without them only the raw size is shown
+        let copies = MySQLTool::query_sql_with_config(&doris, "SHOW PROC '/statistic';")
+            .map(|out| parse_copies(&out))
+            .unwrap_or_default();
+
+        let mut dbs = Vec::new();
+        for (i, db) in selected.iter().enumerate() {
+            ui::print_info(&format!("[{}/{}] SHOW DATA in {db}", i + 1, selected.len()));
+            match MySQLTool::query_sql_with_config(&doris, &format!("USE `{db}`; SHOW DATA;")) {
+                Ok(output) => {
+                    let mut size = parse_show_data(db, &output);
+                    size.copies = copies.get(db.as_str()).copied();
+                    dbs.push(size);
+                }
+                Err(e) => ui::print_warning(&format!("SHOW DATA failed for {db}: {e}")),
+            }
+        }
+        if dbs.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "SHOW DATA returned nothing for the selected databases".to_string(),
+            ));
+        }
+        dbs.sort_by(|a, b| b.size().cmp(&a.size()).then(a.name.cmp(&b.name)));
+
+        if !config.stdout_only {
+            ui::print_info("");
+            for line in render_report(&dbs).lines() {
+                ui::print_info(line);
+            }
+        }
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("fe_data_size", "csv"),
+            render_csv(&dbs).as_bytes(),
+        )?;
+
+        let total: u64 = dbs.iter().map(DbSize::size).sum();
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "{} in {} database(s), {} table(s)",
+                format_bytes(total, 2, false),
+                dbs.len(),
+                dbs.iter().map(|d| d.tables.len()).sum::<usize>()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHOW_DATA: &str = "TableName\tSize\tReplicaCount\tRemoteSize
+orders\t1.500 TB\t3000\t0.000
+dim_user\t512.000 MB\t30\t0.000
+events\t2.25TB\t600\t1.000 GB
+Total\t3.750 TB\t3630\t1.000 GB
+Quota\t1024.000 TB\t1073741824\t
+Left\t1020.250 TB\t1073738194\t
+";
+
+    const STATISTIC: &str =
+        "DbId\tDbName\tTableNum\tPartitionNum\tIndexNum\tTabletNum\tReplicaNum\tUnhealthyTabletNum
+10002\tsales\t3\t40\t40\t1210\t3630\t0
+Total\t1\t3\t40\t40\t1210\t3630\t0
+";
+
+    #[test]
+    fn parses_tb_sizes_quotas_and_copy_ratio() {
+        let tb = 1024u64.pow(4);
+        assert_eq!(parse_size("1.500 TB"), tb * 3 / 2);
+        assert_eq!(parse_size("2.25TB"), tb * 9 / 4);
+        assert_eq!(parse_size("1 KiB"), 1024);
+        assert_eq!(parse_size("512"), 512);
+        assert_eq!(parse_size("n/a"), 0);
+
+        let mut db = parse_show_data("sales", SHOW_DATA);
+        let names: Vec<&str> = db.tables.iter().map(|t| t.name.as_str()).collect();
+        // Summary rows are not tables, and tables come largest first
+        assert_eq!(names, vec!["events", "orders", "dim_user"]);
+        assert_eq!(db.tables[0].remote_size, Some(1024u64.pow(3)));
+        assert_eq!(db.data_quota.as_deref(), Some("1024.000 TB"));
+        assert_eq!(db.replica_quota.as_deref(), Some("1073741824"));
+        assert_eq!(db.replica_count(), 3630);
+
+        db.copies = parse_copies(STATISTIC).get("sales").copied();
+        assert_eq!(db.copies, Some(3.0));
+        let dbs = vec![db];
+        assert_eq!(cluster_copy_ratio(&dbs), Some(3.0));
+
+        let report = render_report(&dbs);
+        assert!(report.contains("3.00x a single copy"));
+        assert!(report.contains("Data quota"));
+        let csv = render_csv(&dbs);
+        assert_eq!(
+            csv.lines().next(),
+            Some(
+                "database,table,size_bytes,replica_count,remote_size_bytes,db_data_quota,db_replica_quota"
+            )
+        );
+        assert_eq!(csv.lines().count(), 4);
+    }
+}
@@ -0,0 +1,443 @@
+use console::style;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::replica_health::ProcTable;
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::error::Result;
+use crate::tools::common::{file_name, format_utils, fs_utils};
+use crate::tools::mysql::MySQLTool;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+/// Gap between the two SHOW FRONTENDS samples used to tell growing lag from jitter
+const SAMPLE_GAP: Duration = Duration::from_secs(5);
+/// Replay lag below this many journals is normal replay jitter
+const LAG_NOISE: u64 = 100;
+const CHECKPOINT_WARN_HOURS: u64 = 24;
+
+/// Journal progress of one frontend from SHOW FRONTENDS
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeJournal {
+    pub host: String,
+    pub role: String,
+    pub is_master: bool,
+    pub replayed: Option<u64>,
+    /// The FE this session is connected to
+    pub current: bool,
+}
+
+fn yes(value: Option<&str>) -> bool {
+    value.is_some_and(|v| v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("yes"))
+}
+
+pub fn parse_frontend_journals(output: &str) -> Vec<FeJournal> {
+    let table = ProcTable::parse(output);
+    table
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(FeJournal {
+                host: table.value(row, &["Host", "IP"])?.to_string(),
+                role: table.value(row, &["Role"]).unwrap_or("").to_string(),
+                is_master: yes(table.value(row, &["IsMaster"])),
+                replayed: table
+                    .value(row, &["ReplayedJournalId"])
+                    .and_then(|v| v.parse().ok()),
+                current: yes(table.value(row, &["CurrentConnected"])),
+            })
+        })
+        .collect()
+}
+
+/// Latest journal id from `/journals`, or from the `/bdbje` database list
+/// (each database is named after its first journal id)
+pub fn parse_current_journal(output: &str) -> Option<u64> {
+    let table = ProcTable::parse(output);
+    let ids = table.rows.iter().filter_map(|row| {
+        if let Some(id) = table.value(row, &["CurrentJournalId", "MaxJournalId", "JournalId"]) {
+            return id.parse::<u64>().ok();
+        }
+        let first: u64 = table.value(row, &["DbNames", "DbName"])?.parse().ok()?;
+        let count: u64 = table.value(row, &["JournalNumber"])?.parse().ok()?;
+        Some((first + count).saturating_sub(1))
+    });
+    ids.max()
+}
+
+/// What the local meta directory shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetaDirState {
+    pub bdb_bytes: Option<u64>,
+    /// Journal id and write time of the newest `image.<id>`
+    pub image: Option<(u64, SystemTime)>,
+    /// `role=` from `image/ROLE`, for when SQL is unavailable
+    pub role: Option<String>,
+}
+
+fn dir_size(dir: &Path) -> Option<u64> {
+    let mut total = 0;
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.is_dir() {
+            total += dir_size(&entry.path()).unwrap_or(0);
+        } else {
+            total += meta.len();
+        }
+    }
+    Some(total)
+}
+
+pub fn inspect_meta_dir(meta_dir: &Path) -> MetaDirState {
+    let image_dir = meta_dir.join("image");
+    let image = fs::read_dir(&image_dir).ok().and_then(|entries| {
+        entries
+            .flatten()
+            .filter_map(|e| {
+                let id = e
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("image.")?
+                    .parse()
+                    .ok()?;
+                Some((id, e.metadata().ok()?.modified().ok()?))
+            })
+            .max_by_key(|(id, _)| *id)
+    });
+    let role = fs::read_to_string(image_dir.join("ROLE"))
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("role=").map(str::to_string))
+        });
+    MetaDirState {
+        bdb_bytes: dir_size(&meta_dir.join("bdb")),
+        image,
+        role,
+    }
+}
+
+/// `meta_dir` from fe.conf, else `doris-meta` under the FE install dir
+fn meta_dir(doris: &DorisConfig) -> PathBuf {
+    doris.meta_dir.clone().unwrap_or_else(|| {
+        doris
+            .fe_install_dir
+            .as_ref()
+            .unwrap_or(&doris.install_dir)
+            .join("doris-meta")
+    })
+}
+
+/// Inputs of one editlog health report
+#[derive(Debug, Clone, Default)]
+pub struct EditlogSample {
+    pub current_journal: Option<u64>,
+    /// First and second SHOW FRONTENDS samples; empty when SQL is unavailable
+    pub first: Vec<FeJournal>,
+    pub second: Vec<FeJournal>,
+    pub meta_dir: PathBuf,
+    pub local: MetaDirState,
+}
+
+impl EditlogSample {
+    fn master_replayed(frontends: &[FeJournal]) -> Option<u64> {
+        frontends.iter().find(|f| f.is_master)?.replayed
+    }
+
+    /// Latest journal id known: the proc result or the master's replay position
+    fn latest(&self, frontends: &[FeJournal]) -> Option<u64> {
+        self.current_journal
+            .into_iter()
+            .chain(Self::master_replayed(frontends))
+            .max()
+    }
+
+    fn lag(&self, frontends: &[FeJournal], host: &str) -> Option<u64> {
+        let fe = frontends.iter().find(|f| f.host == host)?;
+        Some(self.latest(frontends)?.saturating_sub(fe.replayed?))
+    }
+
+    /// Role of the local FE: SHOW FRONTENDS of the connected node, else `image/ROLE`
+    fn local_role(&self) -> Option<String> {
+        self.second
+            .iter()
+            .find(|f| f.current)
+            .map(|f| {
+                if f.is_master {
+                    "MASTER".to_string()
+                } else {
+                    f.role.clone()
+                }
+            })
+            .or_else(|| self.local.role.clone())
+    }
+}
+
+/// Report lines, each paired with whether it is a warning
+pub fn render_report(sample: &EditlogSample, now: SystemTime) -> Vec<(String, bool)> {
+    let mut lines = Vec::new();
+    let latest = sample.latest(&sample.second);
+    lines.push((
+        format!(
+            "Current journal id: {}",
+            latest.map_or_else(|| "unavailable".to_string(), |id| id.to_string())
+        ),
+        false,
+    ));
+
+    let local_role = sample.local_role();
+    let is_master = local_role.as_deref() == Some("MASTER");
+    lines.push((
+        format!(
+            "Local FE role: {}",
+            local_role.as_deref().unwrap_or("unknown")
+        ),
+        false,
+    ));
+    if local_role.as_deref() == Some("OBSERVER") {
+        lines.push((
+            "  Observers replay the master's journal and never checkpoint; the local image \
+             is the last one pushed by the master."
+                .to_string(),
+            false,
+        ));
+    }
+
+    if !sample.second.is_empty() {
+        lines.push((String::new(), false));
+        lines.push((
+            format!(
+                "{:<24} {:<10} {:>18} {:>10} {:>10}",
+                "Host", "Role", "ReplayedJournalId", "Lag", "Trend"
+            ),
+            false,
+        ));
+        for fe in &sample.second {
+            let role = if fe.is_master { "MASTER" } else { &fe.role };
+            let before = sample.lag(&sample.first, &fe.host);
+            let now_lag = sample.lag(&sample.second, &fe.host);
+            let growing =
+                matches!((before, now_lag), (Some(b), Some(n)) if n > b && n >= LAG_NOISE);
+            let trend = match (before, now_lag) {
+                _ if fe.is_master => "-".to_string(),
+                (Some(b), Some(n)) => format!("{:+}", n as i64 - b as i64),
+                _ => "?".to_string(),
+            };
+            lines.push((
+                format!(
+                    "{:<24} {:<10} {:>18} {:>10} {:>10}",
+                    ui::FormatHelper::truncate_string(&fe.host, 24),
+                    role,
+                    fe.replayed
+                        .map_or_else(|| "-".to_string(), |id| id.to_string()),
+                    if fe.is_master {
+                        "-".to_string()
+                    } else {
+                        now_lag.map_or_else(|| "?".to_string(), |l| l.to_string())
+                    },
+                    trend
+                ),
+                growing,
+            ));
+            if growing {
+                lines.push((
+                    format!(
+                        "  ^ replay lag grew over {}s; check this FE's disk and log for replay errors",
+                        SAMPLE_GAP.as_secs()
+                    ),
+                    true,
+                ));
+            }
+        }
+    }
+
+    lines.push((String::new(), false));
+    lines.push((format!("Meta dir: {}", sample.meta_dir.display()), false));
+    lines.push((
+        format!(
+            "  bdb size: {}",
+            sample.local.bdb_bytes.map_or_else(
+                || "unavailable".to_string(),
+                |b| format_utils::format_bytes(b, 2, false)
+            )
+        ),
+        false,
+    ));
+    match sample.local.image {
+        Some((image_id, written)) => {
+            let age = now.duration_since(written).unwrap_or_default();
+            let hours = age.as_secs() / 3600;
+            lines.push((
+                format!(
+                    "  Latest image: image.{image_id}, {hours}h {}m old",
+                    age.as_secs() % 3600 / 60
+                ),
+                false,
+            ));
+            if let Some(latest) = latest {
+                lines.push((
+                    format!(
+                        "  Journals since checkpoint: {}",
+                        latest.saturating_sub(image_id)
+                    ),
+                    false,
+                ));
+            }
+            if hours >= CHECKPOINT_WARN_HOURS {
+                let hint = if is_master {
+                    "checkpoints may be failing; check fe.log for Checkpoint errors"
+                } else {
+                    "checkpoints run on the master and are pushed here; check the master first"
+                };
+                lines.push((
+                    format!("  ^ no checkpoint for over {CHECKPOINT_WARN_HOURS}h: {hint}"),
+                    true,
+                ));
+            }
+        }
+        None => lines.push(("  No image found".to_string(), false)),
+    }
+    lines
+}
+
+fn query(doris: &DorisConfig, sql: &str) -> Option<String> {
+    MySQLTool::query_sql_with_config(doris, sql).ok()
+}
+
+/// Tool to check FE editlog growth, replay lag and checkpoint age
+pub struct FeEditlogTool;
+
+impl Tool for FeEditlogTool {
+    fn name(&self) -> &str {
+        "fe-editlog"
+    }
+
+    fn description(&self) -> &str {
+        "Check editlog replay lag, checkpoint age and bdb size"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let meta_dir = meta_dir(&doris);
+
+        ui::print_info("Sampling journal progress...");
+        let first = query(&doris, "SHOW FRONTENDS;")
+            .map(|o| parse_frontend_journals(&o))
+            .unwrap_or_default();
+        let current_journal = ["SHOW PROC '/journals';", "SHOW PROC '/bdbje';"]
+            .iter()
+            .find_map(|sql| query(&doris, sql).and_then(|o| parse_current_journal(&o)));
+        let second = if first.is_empty() {
+            ui::print_warning("SQL is unavailable; reporting from the local meta dir only.");
+            Vec::new()
+        } else {
+            std::thread::sleep(SAMPLE_GAP);
+            query(&doris, "SHOW FRONTENDS;")
+                .map(|o| parse_frontend_journals(&o))
+                .unwrap_or_else(|| first.clone())
+        };
+
+        let sample = EditlogSample {
+            current_journal,
+            first,
+            second,
+            local: inspect_meta_dir(&meta_dir),
+            meta_dir,
+        };
+        let lines = render_report(&sample, SystemTime::now());
+
+        ui::print_info("");
+        for (line, flagged) in &lines {
+            if *flagged {
+                ui::print_info(&style(line).red().to_string());
+            } else {
+                ui::print_info(line);
+            }
+        }
+        let report: String = lines.iter().map(|(l, _)| format!("{l}\n")).collect();
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("fe_editlog", "txt"),
+            report.as_bytes(),
+        )?;
+
+        let warnings = lines.iter().filter(|(_, f)| *f).count();
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("Editlog check completed, {warnings} warning(s)"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_growing_lag_and_stale_checkpoint() {
+        let frontends = |follower: u64| {
+            format!(
+                "Name\tHost\tRole\tIsMaster\tReplayedJournalId\tCurrentConnected\n\
+fe1\t10.0.0.1\tFOLLOWER\ttrue\t50000\tNo\n\
+fe2\t10.0.0.2\tFOLLOWER\tfalse\t{follower}\tNo\n\
+fe3\t10.0.0.3\tOBSERVER\tfalse\t49990\tYes\n"
+            )
+        };
+        let first = parse_frontend_journals(&frontends(49800));
+        assert_eq!(first.len(), 3);
+        assert!(first[0].is_master && first[2].current);
+
+        let bdbje = "DbNames\tJournalNumber\tComment\n1\t40000\t\n40001\t10050\t\n";
+        assert_eq!(parse_current_journal(bdbje), Some(50050));
+
+        let root = std::env::temp_dir().join(format!("cloud-cli-editlog-{}", std::process::id()));
+        fs::create_dir_all(root.join("image")).unwrap();
+        fs::create_dir_all(root.join("bdb")).unwrap();
+        fs::write(root.join("image/image.30000"), b"old").unwrap();
+        fs::write(root.join("image/image.45000"), b"new").unwrap();
+        fs::write(
+            root.join("image/ROLE"),
+            "#comment\nrole=OBSERVER\nname=fe3\n",
+        )
+        .unwrap();
+        fs::write(root.join("bdb/00000001.jdb"), vec![0u8; 2048]).unwrap();
+        let local = inspect_meta_dir(&root);
+        assert_eq!(local.bdb_bytes, Some(2048));
+        assert_eq!(local.role.as_deref(), Some("OBSERVER"));
+        let (image_id, written) = local.image.unwrap();
+        assert_eq!(image_id, 45000);
+        let _ = fs::remove_dir_all(&root);
+
+        let sample = EditlogSample {
+            current_journal: parse_current_journal(bdbje),
+            first,
+            second: parse_frontend_journals(&frontends(49700)),
+            meta_dir: root.clone(),
+            local,
+        };
+        let lines = render_report(&sample, written + Duration::from_secs(30 * 3600));
+        let flagged: Vec<&str> = lines
+            .iter()
+            .filter(|(_, f)| *f)
+            .map(|(l, _)| l.as_str())
+            .collect();
+        assert_eq!(flagged.len(), 3);
+        assert!(flagged[0].starts_with("10.0.0.2"));
+        assert!(flagged[2].contains("pushed here"));
+        assert!(lines.iter().any(|(l, _)| l == "Local FE role: OBSERVER"));
+        assert!(
+            lines
+                .iter()
+                .any(|(l, _)| l == "  Journals since checkpoint: 5050")
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use chrono::{Duration, NaiveDateTime};
+use console::style;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::config_loader::Environment;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::common::log_scan::{self, ScanProgress};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::{InputHelper, MAX_RECENT_MINUTES};
+
+const LONG_PAUSE_MS: f64 = 1000.0;
+
+/// A single stop-the-world pause parsed from an FE GC log
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcEvent {
+    pub timestamp: NaiveDateTime,
+    pub kind: String,
+    pub full: bool,
+    pub pause_ms: f64,
+}
+
+/// Parser for JDK8 (`-XX:+PrintGCDateStamps`) and unified (`-Xlog:gc`) GC logs
+pub struct GcLogParser {
+    re_jdk8: Regex,
+    re_jdk8_secs: Regex,
+    re_unified: Regex,
+}
+
+impl GcLogParser {
+    pub fn new() -> Self {
+        Self {
+            re_jdk8: Regex::new(
+                r"^(?P<ts>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\.\d{3})\S*: (?:[\d.]+: )?\[(?P<kind>Full GC|GC)[ (]",
+            )
+            .unwrap(),
+            re_jdk8_secs: Regex::new(r", (?P<secs>\d+\.\d+) secs\]").unwrap(),
+            re_unified: Regex::new(
+                r"^\[(?P<ts>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\.\d{3})[^\]]*\].*GC\(\d+\) (?P<kind>Pause [A-Za-z]+(?: [A-Za-z]+)*).*?(?P<ms>\d+(?:\.\d+)?)ms\s*$",
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn parse_line(&self, line: &str) -> Option<GcEvent> {
+        if let Some(caps) = self.re_unified.captures(line) {
+            let timestamp = parse_gc_timestamp(&caps["ts"])?;
+            let kind = caps["kind"].to_string();
+            let full = kind.starts_with("Pause Full");
+            let pause_ms = caps["ms"].parse().ok()?;
+            return Some(GcEvent {
+                timestamp,
+                kind,
+                full,
+                pause_ms,
+            });
+        }
+
+        let caps = self.re_jdk8.captures(line)?;
+        let timestamp = parse_gc_timestamp(&caps["ts"])?;
+        let full = &caps["kind"] == "Full GC";
+        // The outermost pause time is the last "secs]" on the line
+        let secs: f64 = self
+            .re_jdk8_secs
+            .captures_iter(line)
+            .last()?
+            .name("secs")?
+            .as_str()
+            .parse()
+            .ok()?;
+
+        Some(GcEvent {
+            timestamp,
+            kind: if full { "Full GC" } else { "Young GC" }.to_string(),
+            full,
+            pause_ms: secs * 1000.0,
+        })
+    }
+}
+
+impl Default for GcLogParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_gc_timestamp(ts: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S%.3f").ok()
+}
+
+pub struct FeGcAnalyzerTool;
+
+impl Tool for FeGcAnalyzerTool {
+    fn name(&self) -> &str {
+        "fe-gc-analyzer"
+    }
+
+    fn description(&self) -> &str {
+        "Analyze GC pauses from FE GC logs"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let log_dir = doris.service_paths(Environment::FE).log_dir;
+
+        let files = match fs_utils::collect_log_files(&log_dir, "fe.gc.log") {
+            Ok(files) => files,
+            Err(_) => {
+                ui::print_warning(&format!("No GC logs found in {}", log_dir.display()));
+                ui::print_info("Tips: GC logging may be disabled in JAVA_OPTS of fe.conf.");
+                return Err(CliError::GracefulExit);
+            }
+        };
+
+        let minutes = InputHelper::prompt_number_with_default(
+            "Analyze recent minutes",
+            60,
+            1,
+            Some(MAX_RECENT_MINUTES),
+            Some("minutes"),
+        )?;
+
+        let files = log_scan::prefilter_recent_minutes(files, minutes);
+        ui::print_info(&format!(
+            "Analyzing {} GC log file(s) in {} (last {minutes} min)...",
+            files.len(),
+            log_dir.display()
+        ));
+
+        let parser = GcLogParser::new();
+        let outcome = log_scan::scan_files_parallel(
+            &files,
+            config.log_scan_workers(),
+            !config.no_progress_animation,
+            |path, progress| scan_gc_file(&parser, path, progress),
+            |e| e.timestamp,
+        );
+        log_scan::print_scan_failures(&outcome.failures);
+        let events = outcome.entries;
+
+        if events.is_empty() {
+            return Err(CliError::ToolExecutionFailed(format!(
+                "No GC pause events with date stamps found in {}",
+                log_dir.display()
+            )));
+        }
+
+        let clock_note = events
+            .iter()
+            .map(|e| e.timestamp)
+            .max()
+            .and_then(ui::check_log_clock_skew);
+        let events = filter_events_by_window(events, minutes);
+        display_gc_summary(&events, clock_note.as_deref());
+
+        let output_path = write_events_csv(config, &events)?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("GC analysis completed ({} pause events)", events.len()),
+        ))
+    }
+}
+
+fn scan_gc_file(
+    parser: &GcLogParser,
+    path: &Path,
+    progress: &ScanProgress,
+) -> Result<Vec<GcEvent>> {
+    let f = fs::File::open(path).map_err(CliError::IoError)?;
+    let reader = BufReader::new(f);
+    let mut out = Vec::new();
+
+    for line in reader.lines() {
+        // GC logs may contain partial writes after rotation; skip unreadable lines
+        let Ok(line) = line else { continue };
+        progress.add(line.len() as u64 + 1);
+        if let Some(event) = parser.parse_line(&line) {
+            out.push(event);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Keep events (sorted by time) in the last `minutes`, anchored on the newest event
+fn filter_events_by_window(mut events: Vec<GcEvent>, minutes: i64) -> Vec<GcEvent> {
+    if let Some(latest) = events.last().map(|e| e.timestamp) {
+        let window_start = latest - Duration::minutes(minutes);
+        events.retain(|e| e.timestamp >= window_start);
+    }
+    events
+}
+
+/// Longest run of back-to-back Full GCs
+fn max_consecutive_full_gcs(events: &[GcEvent]) -> usize {
+    let mut max_run = 0;
+    let mut run = 0;
+    for e in events {
+        if e.full {
+            run += 1;
+            max_run = max_run.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    max_run
+}
+
+fn display_gc_summary(events: &[GcEvent], clock_note: Option<&str>) {
+    let total_ms: f64 = events.iter().map(|e| e.pause_ms).sum();
+    let max_ms = events.iter().map(|e| e.pause_ms).fold(0.0, f64::max);
+    let full_count = events.iter().filter(|e| e.full).count();
+
+    ui::print_info("");
+    ui::print_info("GC Pause Summary");
+    ui::print_info(&"-".repeat(60));
+    if let Some(note) = clock_note {
+        ui::print_info(note);
+    }
+    ui::print_info(&format!("Pause events: {}", events.len()));
+    ui::print_info(&format!("Full GCs: {full_count}"));
+    ui::print_info(&format!("Total pause: {total_ms:.1} ms"));
+    ui::print_info(&format!("Max pause: {max_ms:.1} ms"));
+
+    let mut per_minute: BTreeMap<String, (usize, f64)> = BTreeMap::new();
+    for e in events {
+        let entry = per_minute
+            .entry(e.timestamp.format("%m-%d %H:%M").to_string())
+            .or_insert((0, 0.0));
+        entry.0 += 1;
+        entry.1 += e.pause_ms;
+    }
+
+    ui::print_info("");
+    ui::print_info("Per-minute pauses (ascending time)");
+    ui::print_info(&"-".repeat(60));
+    let max_minute_ms = per_minute.values().map(|v| v.1).fold(0.0, f64::max);
+    for (minute, (count, ms)) in &per_minute {
+        let bar_len = if max_minute_ms > 0.0 {
+            ((ms / max_minute_ms) * 30.0).ceil() as usize
+        } else {
+            0
+        };
+        ui::print_info(&format!(
+            "{minute} count={count:<4} pause={ms:>10.1} ms {}",
+            "#".repeat(bar_len)
+        ));
+    }
+
+    let long_pauses: Vec<&GcEvent> = events
+        .iter()
+        .filter(|e| e.pause_ms > LONG_PAUSE_MS)
+        .collect();
+    let consecutive_full = max_consecutive_full_gcs(events);
+
+    if long_pauses.is_empty() && consecutive_full < 2 {
+        return;
+    }
+
+    ui::print_info("");
+    ui::print_warning("Suspicious patterns:");
+    if consecutive_full >= 2 {
+        println!(
+            "  {}",
+            style(format!("{consecutive_full} consecutive Full GCs detected")).red()
+        );
+    }
+    for e in long_pauses.iter().take(10) {
+        println!(
+            "  {}",
+            style(format!(
+                "{} {} paused {:.1} ms",
+                e.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+                e.kind,
+                e.pause_ms
+            ))
+            .red()
+        );
+    }
+    if long_pauses.len() > 10 {
+        ui::print_info(&format!(
+            "  ... and {} more pauses over 1s",
+            long_pauses.len() - 10
+        ));
+    }
+}
+
+fn write_events_csv(config: &Config, events: &[GcEvent]) -> Result<PathBuf> {
+    let file_name = file_name::timestamped("fe_gc_events", "csv");
+
+    let mut content = String::from("timestamp,kind,full_gc,pause_ms\n");
+    for e in events {
+        content.push_str(&format!(
+            "{},{},{},{:.3}\n",
+            e.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+            e.kind,
+            e.full,
+            e.pause_ms
+        ));
+    }
+    fs_utils::write_artifact(config, &file_name, content.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_jdk8_young_and_full_gc() {
+        let parser = GcLogParser::new();
+
+        let young = "2024-01-15T10:23:45.123+0800: 12345.678: [GC (Allocation Failure) [PSYoungGen: 1024K->512K(2048K)] 4096K->3584K(8192K), 0.0123456 secs] [Times: user=0.02 sys=0.00, real=0.01 secs]";
+        let e = parser.parse_line(young).unwrap();
+        assert!(!e.full);
+        assert!((e.pause_ms - 12.3456).abs() < 1e-6);
+
+        let full = "2024-01-15T10:24:00.001+0800: 12360.000: [Full GC (Ergonomics) [PSYoungGen: 512K->0K(2048K)] [ParOldGen: 6000K->5000K(6144K)] 6512K->5000K(8192K), [Metaspace: 100K->100K(1024K)], 1.5000000 secs] [Times: user=3.00 sys=0.01, real=1.50 secs]";
+        let e = parser.parse_line(full).unwrap();
+        assert!(e.full);
+        assert!((e.pause_ms - 1500.0).abs() < 1e-6);
+
+        let g1 = "2024-01-15T10:25:00.000+0800: 12420.000: [GC pause (G1 Evacuation Pause) (young), 0.0052330 secs]";
+        assert!(parser.parse_line(g1).is_some());
+    }
+
+    #[test]
+    fn parses_unified_logging_pauses_only() {
+        let parser = GcLogParser::new();
+
+        let young = "[2024-01-15T10:23:45.123+0800][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) 512M->128M(2048M) 12.345ms";
+        let e = parser.parse_line(young).unwrap();
+        assert_eq!(e.kind, "Pause Young");
+        assert!(!e.full);
+        assert!((e.pause_ms - 12.345).abs() < 1e-6);
+
+        let full = "[2024-01-15T10:23:50.000+0800][12.345s][info][gc] GC(13) Pause Full (G1 Compaction Pause) 2000M->1500M(2048M) 2345.678ms";
+        assert!(parser.parse_line(full).unwrap().full);
+
+        let start = "[2024-01-15T10:23:45.100+0800][info][gc,start] GC(12) Pause Young (Normal) (G1 Evacuation Pause)";
+        assert!(parser.parse_line(start).is_none());
+        let concurrent =
+            "[2024-01-15T10:23:46.000+0800][info][gc] GC(14) Concurrent Mark Cycle 55.123ms";
+        assert!(parser.parse_line(concurrent).is_none());
+    }
+
+    #[test]
+    fn counts_consecutive_full_gcs() {
+        let ts = parse_gc_timestamp("2024-01-15T10:00:00.000").unwrap();
+        let ev = |full| GcEvent {
+            timestamp: ts,
+            kind: String::new(),
+            full,
+            pause_ms: 1.0,
+        };
+        let events = vec![ev(true), ev(false), ev(true), ev(true), ev(true), ev(false)];
+        assert_eq!(max_consecutive_full_gcs(&events), 3);
+    }
+}
@@ -1,8 +1,12 @@
+use super::thread_dump;
 use crate::config::Config;
 use crate::error::{CliError, Result};
 use crate::executor;
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::common::jmap::is_attach_error;
 use crate::tools::{ExecutionResult, Tool};
-use chrono::Utc;
+use crate::ui;
 use std::process::Command;
 
 pub struct JstackTool;
@@ -17,24 +21,55 @@ impl Tool for JstackTool {
     }
 
     fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
-        config.ensure_output_dir()?;
+        let filename = file_name::timestamped(&format!("jstack_{pid}"), "log");
 
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("jstack_{pid}_{timestamp}.log");
-        let output_path = config.output_dir.join(filename);
+        let mut content = thread_dump_output(config, pid, self.name())?;
+        let digest = thread_dump::digest(&String::from_utf8_lossy(&content));
+        digest.print();
+        content.extend_from_slice(digest.render().as_bytes());
 
-        let jstack_path = config.get_jstack_path();
+        let output_path = fs_utils::write_artifact(config, &filename, &content)?;
 
+        let message = if digest.deadlocks.is_empty() {
+            "Thread stack trace completed successfully".to_string()
+        } else {
+            format!(
+                "Thread stack trace completed; {} Java-level deadlock(s) found",
+                digest.deadlocks.len()
+            )
+        };
+        Ok(ExecutionResult::new(output_path, message))
+    }
+}
+
+/// Dump with jstack, or with `jcmd <pid> Thread.print` when jstack is missing or cannot attach
+fn thread_dump_output(config: &Config, pid: u32, tool_name: &str) -> Result<Vec<u8>> {
+    let jcmd_path = config.get_jcmd_path();
+    let jstack_path = config.get_jstack_path();
+    if jstack_path.is_file() || !jcmd_path.is_file() {
         let mut command = Command::new(&jstack_path);
         command.args([&pid.to_string()]);
+        match executor::execute_command(&mut command, tool_name) {
+            Ok(output) => return Ok(output.stdout),
+            Err(e) if jcmd_path.is_file() && is_attach_error(&e.to_string()) => {
+                ui::print_warning("jstack could not attach to the process; retrying with jcmd");
+            }
+            Err(e) => return Err(e),
+        }
+    } else {
+        ui::print_info(&format!(
+            "jstack not found at {}; using jcmd",
+            jstack_path.display()
+        ));
+    }
 
-        let output = executor::execute_command(&mut command, self.name())?;
-
-        std::fs::write(&output_path, &output.stdout).map_err(CliError::IoError)?;
-
-        Ok(ExecutionResult {
-            output_path,
-            message: "Thread stack trace completed successfully".to_string(),
-        })
+    let mut command = Command::new(&jcmd_path);
+    command.args([&pid.to_string(), "Thread.print"]);
+    let output = executor::execute_command(&mut command, "jcmd")?;
+    if output.stdout.is_empty() {
+        return Err(CliError::ToolExecutionFailed(
+            "jcmd Thread.print returned no output".to_string(),
+        ));
     }
+    Ok(output.stdout)
 }
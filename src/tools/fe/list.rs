@@ -1,8 +1,76 @@
 use crate::config::Config;
+use crate::config_loader;
 use crate::error::{CliError, Result};
-use crate::tools::Tool;
+use crate::tools::common::host_selection;
+use crate::tools::mysql::{ClusterInfo, Frontend, MySQLTool};
+use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
-use std::collections::BTreeSet;
+use crate::ui::table::TableBuilder;
+
+const REFRESH_ITEM: &str = "Refresh (re-run SHOW FRONTENDS)";
+const CLEAR_ITEM: &str = "Use the configured connection (clear selection)";
+
+pub fn set_selected_fe_host(host: String) {
+    host_selection::set_selected_host(false, host);
+}
+
+pub fn get_selected_fe_host() -> Option<String> {
+    host_selection::get_selected_host(false)
+}
+
+/// Re-query the cluster and save clusters.toml; falls back to the saved file
+/// when there are no credentials or the query fails
+fn refresh_cluster_info() -> Result<ClusterInfo> {
+    let doris = config_loader::load_config()?;
+    if doris.mysql.is_some() {
+        ui::print_info("Refreshing frontends via SHOW FRONTENDS...");
+        match MySQLTool.query_cluster_info(&doris) {
+            Ok(info) => {
+                if let Err(e) = info.save_to_file() {
+                    ui::print_warning(&format!("Refreshed cluster info not saved: {e}"));
+                }
+                return Ok(info);
+            }
+            Err(e) => ui::print_warning(&format!("Refresh failed, using saved cluster info: {e}")),
+        }
+    }
+    ClusterInfo::load_from_file()
+}
+
+fn render_frontends(frontends: &[Frontend], selected: Option<&str>) -> String {
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let mut table = TableBuilder::new(["", "Name", "Host", "Role", "Master", "Alive", "Version"])
+        .max_width(1, 40)
+        .max_width(6, 40)
+        .empty_message("No frontends in the cluster info");
+    for fe in frontends {
+        let marker = if selected == Some(fe.host.as_str()) {
+            "*"
+        } else {
+            ""
+        };
+        table.push_row([
+            marker.to_string(),
+            fe.name.clone(),
+            format!("{}:{}", fe.host, fe.query_port),
+            fe.role.clone(),
+            yes_no(fe.is_master).to_string(),
+            yes_no(fe.alive).to_string(),
+            fe.version.clone(),
+        ]);
+    }
+    table.render()
+}
+
+/// Menu label of one selectable (alive) frontend
+fn item_label(fe: &Frontend) -> String {
+    let role = if fe.is_master {
+        "MASTER"
+    } else {
+        fe.role.as_str()
+    };
+    format!("{}:{} ({role})", fe.host, fe.query_port)
+}
 
 pub struct FeListTool;
 
@@ -12,42 +80,116 @@ impl Tool for FeListTool {
     }
 
     fn description(&self) -> &str {
-        "List and select a FE host (IP) for this session"
+        "List frontends and select the active FE target for this session"
     }
 
     fn requires_pid(&self) -> bool {
         false
     }
 
-    fn execute(&self, _config: &Config, _pid: u32) -> Result<crate::tools::ExecutionResult> {
-        let info = crate::tools::mysql::ClusterInfo::load_from_file()?;
-        let mut hosts: BTreeSet<String> = BTreeSet::new();
-        for fe in info.frontends.iter().filter(|f| f.alive) {
-            if !fe.host.is_empty() {
-                hosts.insert(fe.host.clone());
+    fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let mut info = refresh_cluster_info()?;
+        loop {
+            let alive: Vec<&Frontend> = info
+                .frontends
+                .iter()
+                .filter(|fe| fe.alive && !fe.host.is_empty())
+                .collect();
+            let selected = get_selected_fe_host();
+            if let Some(host) = &selected
+                && !alive.iter().any(|fe| fe.host == *host)
+            {
+                ui::print_warning(&format!(
+                    "Active FE target {host} is no longer alive; using the configured connection"
+                ));
+                host_selection::clear_selected_host(false);
+                continue;
+            }
+
+            ui::print_info("");
+            for line in render_frontends(&info.frontends, selected.as_deref()).lines() {
+                ui::print_info(line);
+            }
+            if alive.is_empty() {
+                return Err(CliError::ConfigError(
+                    "No alive FE hosts found in clusters.toml".to_string(),
+                ));
+            }
+
+            let mut items: Vec<String> = alive.iter().map(|fe| item_label(fe)).collect();
+            items.push(REFRESH_ITEM.to_string());
+            if selected.is_some() {
+                items.push(CLEAR_ITEM.to_string());
+            }
+            let default = selected
+                .as_ref()
+                .and_then(|host| alive.iter().position(|fe| fe.host == *host))
+                .unwrap_or(0);
+
+            let selection =
+                dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                    .with_prompt("Select Frontend (FE) target")
+                    .items(&items)
+                    .default(default)
+                    .interact()
+                    .map_err(|e| CliError::prompt_failed("FE selection failed", e))?;
+
+            match alive.get(selection) {
+                Some(fe) => {
+                    let host = fe.host.clone();
+                    set_selected_fe_host(host.clone());
+                    ui::print_success(&format!("Selected FE host: {}", item_label(fe)));
+                    return Ok(ExecutionResult::console(format!(
+                        "FE target set to {host} for this session"
+                    )));
+                }
+                None if items[selection] == REFRESH_ITEM => {
+                    info = refresh_cluster_info()?;
+                }
+                None => {
+                    host_selection::clear_selected_host(false);
+                    ui::print_success("FE target cleared");
+                    return Ok(ExecutionResult::console(
+                        "FE target reset to the configured connection",
+                    ));
+                }
             }
         }
-        if hosts.is_empty() {
-            return Err(CliError::ConfigError(
-                "No FE hosts found in clusters.toml".to_string(),
-            ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fe(host: &str, role: &str, is_master: bool, alive: bool) -> Frontend {
+        Frontend {
+            name: format!("fe_{host}"),
+            host: host.to_string(),
+            edit_log_port: 9010,
+            http_port: 8030,
+            query_port: 9030,
+            rpc_port: 9020,
+            role: role.to_string(),
+            is_master,
+            cluster_id: "1".to_string(),
+            alive,
+            version: "doris-2.1.4".to_string(),
         }
-        let items: Vec<String> = hosts.iter().cloned().collect();
-
-        let selection = dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
-            .with_prompt("Select Frontend (FE) host")
-            .items(&items)
-            .default(0)
-            .interact()
-            .map_err(|e| CliError::InvalidInput(format!("FE selection failed: {e}")))?;
-
-        let host = items[selection].clone();
-        crate::tools::common::host_selection::set_selected_host(false, host.clone());
-        ui::print_success(&format!("Selected FE host: {host}"));
-
-        Ok(crate::tools::ExecutionResult {
-            output_path: std::path::PathBuf::from("console_output"),
-            message: "FE target updated for this session".to_string(),
-        })
+    }
+
+    #[test]
+    fn lists_every_frontend_and_marks_the_target() {
+        let frontends = [
+            fe("10.0.0.1", "FOLLOWER", true, true),
+            fe("10.0.0.2", "OBSERVER", false, false),
+        ];
+        let table = render_frontends(&frontends, Some("10.0.0.1"));
+        let rows: Vec<&str> = table.lines().filter(|l| l.contains("fe_")).collect();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].contains('*') && rows[0].contains("10.0.0.1:9030"));
+        assert!(rows[1].contains("OBSERVER") && !rows[1].contains('*'));
+        assert_eq!(item_label(&frontends[0]), "10.0.0.1:9030 (MASTER)");
+        assert_eq!(item_label(&frontends[1]), "10.0.0.2:9030 (OBSERVER)");
     }
 }
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::error::{CliError, Result};
+use crate::tools::common::http::HttpOptions;
+use crate::tools::common::{file_name, format_utils, fs_utils, retention};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+const METRICS_PATH: &str = "/metrics";
+const HTTP_TIMEOUT_SECS: u64 = 10;
+const SNAPSHOT_PREFIX: &str = "fe_metrics";
+const SNAPSHOT_EXT: &str = "prom";
+
+/// Curated metric name prefixes, grouped for display
+const GROUPS: &[(&str, &[&str])] = &[
+    ("Thread pools", &["doris_fe_thread_pool"]),
+    ("Query latency", &["doris_fe_query_latency_ms"]),
+    (
+        "Edit log",
+        &["doris_fe_editlog_write_latency_ms", "doris_fe_edit_log"],
+    ),
+    ("Transactions", &["doris_fe_txn"]),
+];
+
+/// Curated samples keyed by `name{labels}`, in a stable order
+pub type Snapshot = BTreeMap<String, f64>;
+
+fn group_of(name: &str) -> Option<usize> {
+    GROUPS
+        .iter()
+        .position(|(_, prefixes)| prefixes.iter().any(|p| name.starts_with(p)))
+}
+
+/// Curated samples of a Prometheus text exposition
+pub fn parse_metrics(body: &str) -> Snapshot {
+    body.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|line| {
+            // `name{a="b"} 1.5` or `name 1.5`; labels may contain spaces
+            let split = match line.rfind('}') {
+                Some(end) => end + 1,
+                None => line.find(char::is_whitespace)?,
+            };
+            let (key, rest) = line.split_at(split);
+            let value: f64 = rest.split_whitespace().next()?.parse().ok()?;
+            let name = key.split('{').next().unwrap_or(key);
+            group_of(name).map(|_| (key.trim().to_string(), value))
+        })
+        .collect()
+}
+
+/// Value with the unit its metric name implies
+pub fn humanize(key: &str, value: f64) -> String {
+    let name = key.split('{').next().unwrap_or(key);
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    if name.ends_with("_ms") {
+        if value.abs() >= 1000.0 {
+            format!("{:.2}s", value / 1000.0)
+        } else {
+            format!("{value:.1}ms")
+        }
+    } else if name.ends_with("_bytes") {
+        let sign = if value < 0.0 { "-" } else { "" };
+        format!(
+            "{sign}{}",
+            format_utils::format_bytes(value.abs() as u64, 1, false)
+        )
+    } else if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.3}")
+    }
+}
+
+/// Grouped table of `current`, with a delta column when `previous` is given
+pub fn render_metrics(current: &Snapshot, previous: Option<(&Snapshot, u64)>) -> String {
+    let width = current
+        .keys()
+        .map(|k| k.len())
+        .max()
+        .unwrap_or(6)
+        .clamp(6, 90);
+    let mut out = String::new();
+    if let Some((_, secs)) = previous {
+        out.push_str(&format!(
+            "Deltas against the snapshot taken {secs}s earlier\n\n"
+        ));
+    }
+    for (idx, (title, _)) in GROUPS.iter().enumerate() {
+        let rows: Vec<(&String, &f64)> = current
+            .iter()
+            .filter(|(k, _)| group_of(k.split('{').next().unwrap_or(k)) == Some(idx))
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("{title}\n"));
+        out.push_str(&format!("  {:<width$} {:>14}", "Metric", "Value"));
+        if previous.is_some() {
+            out.push_str(&format!(" {:>14}", "Delta"));
+        }
+        out.push('\n');
+        for (key, value) in rows {
+            out.push_str(&format!("  {key:<width$} {:>14}", humanize(key, *value)));
+            if let Some((prev, _)) = previous {
+                let delta = match prev.get(key) {
+                    Some(before) if value - before != 0.0 => {
+                        let d = humanize(key, value - before);
+                        if d.starts_with('-') {
+                            d
+                        } else {
+                            format!("+{d}")
+                        }
+                    }
+                    Some(_) => "0".to_string(),
+                    None => "new".to_string(),
+                };
+                out.push_str(&format!(" {delta:>14}"));
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    if out.trim().is_empty() {
+        out.push_str("No thread pool, latency, edit log or transaction metrics found.\n");
+    }
+    out
+}
+
+/// Newest earlier snapshot in the output or a run directory and its age in seconds
+fn previous_snapshot(config: &Config) -> Option<(PathBuf, u64)> {
+    let (path, modified) = retention::artifact_dirs(&config.output_dir)
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            name.contains(&format!("{SNAPSHOT_PREFIX}_"))
+                && name.ends_with(&format!(".{SNAPSHOT_EXT}"))
+        })
+        .filter_map(|e| Some((e.path(), e.metadata().ok()?.modified().ok()?)))
+        .max_by_key(|(_, modified)| *modified)?;
+    let age = SystemTime::now().duration_since(modified).ok()?.as_secs();
+    Some((path, age))
+}
+
+fn fetch_metrics(doris: &DorisConfig, host: &str, port: u16) -> Result<String> {
+    let options = HttpOptions::from_config(doris)?;
+    let url = options.url(host, port, METRICS_PATH);
+    let cannot_fetch = || {
+        CliError::ToolExecutionFailed(format!(
+            "Cannot fetch metrics from FE HTTP at {host}:{port}. Check http_port in fe.conf and that the FE is running."
+        ))
+    };
+    let response = options
+        .get(&url, Some(HTTP_TIMEOUT_SECS))
+        .map_err(|e| match e {
+            CliError::HttpAuthFailed { .. } => e,
+            _ => cannot_fetch(),
+        })?;
+    if !response.is_success() {
+        return Err(cannot_fetch());
+    }
+    Ok(response.text())
+}
+
+/// Snapshot of FE thread pool, latency, edit log and transaction metrics
+pub struct FeMetricsTool;
+
+impl Tool for FeMetricsTool {
+    fn name(&self) -> &str {
+        "fe-metrics"
+    }
+
+    fn description(&self) -> &str {
+        "Snapshot FE thread pool and queue metrics"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let (host, port) = super::query_profile::fe_http_endpoint(&doris);
+
+        ui::print_info(&format!("Fetching metrics from {host}:{port}..."));
+        let body = fetch_metrics(&doris, &host, port)?;
+        let current = parse_metrics(&body);
+
+        // Read the previous snapshot before this one is written next to it
+        let previous = previous_snapshot(config).and_then(|(path, age)| {
+            let prev = parse_metrics(&fs::read_to_string(&path).ok()?);
+            (!prev.is_empty()).then_some((prev, age))
+        });
+        let report = render_metrics(&current, previous.as_ref().map(|(p, age)| (p, *age)));
+        ui::print_info("");
+        ui::print_info(&report);
+
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped(SNAPSHOT_PREFIX, SNAPSHOT_EXT),
+            body.as_bytes(),
+        )?;
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "{} FE metric(s) captured{}",
+                current.len(),
+                if previous.is_some() {
+                    ", compared with the previous snapshot"
+                } else {
+                    ""
+                }
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_groups_and_diffs_prometheus_text() {
+        let before = r#"# HELP doris_fe_thread_pool thread pool metric
+# TYPE doris_fe_thread_pool gauge
+doris_fe_thread_pool{name="mysql-nio-pool", type="active_thread_num"} 12
+doris_fe_thread_pool{name="mysql-nio-pool", type="task_in_queue"} 0
+doris_fe_query_latency_ms{quantile="0.99"} 850.5
+doris_fe_editlog_write_latency_ms{quantile="0.99"} 4
+doris_fe_txn_counter{type="begin"} 100
+jvm_heap_size_bytes{type="used"} 123456789
+"#;
+        let prev = parse_metrics(before);
+        assert_eq!(prev.len(), 5);
+        assert_eq!(
+            prev[r#"doris_fe_thread_pool{name="mysql-nio-pool", type="active_thread_num"}"#],
+            12.0
+        );
+
+        let after = before
+            .replace("} 12\n", "} 64\n")
+            .replace("850.5", "2350")
+            .replace("task_in_queue\"} 0", "task_in_queue\"} 37");
+        let current = parse_metrics(&after);
+        let table = render_metrics(&current, Some((&prev, 60)));
+        assert!(table.starts_with("Deltas against the snapshot taken 60s earlier"));
+        assert!(table.contains("Thread pools\n"));
+        assert!(table.contains("+52"));
+        assert!(table.contains("+37"));
+        assert!(table.contains("2.35s"));
+        assert!(table.contains("+1.50s"));
+        assert!(!table.contains("jvm_heap"));
+
+        assert_eq!(humanize("doris_fe_query_latency_ms", 12.34), "12.3ms");
+        assert_eq!(humanize("x_bytes", 2048.0), "2.0 KB");
+        assert_eq!(humanize("doris_fe_txn_counter", 7.0), "7");
+    }
+}
@@ -1,13 +1,40 @@
+mod alter_job;
+mod audit_log;
+mod cluster_version;
+mod config_drift;
+mod data_size;
+mod editlog;
+mod gc_analyzer;
 mod jmap;
 mod jstack;
 mod list;
+mod metrics;
 mod profiler;
+mod query_profile;
+mod replica_health;
 pub mod routine_load;
+mod sql_console;
 pub mod table_info;
+mod tablet_lookup;
+mod thread_dump;
+mod workload_group;
 
-pub use jmap::{JmapDumpTool, JmapHistoTool};
+pub use alter_job::FeAlterJobTool;
+pub use audit_log::FeAuditLogAnalyzer;
+pub use cluster_version::ClusterVersionTool;
+pub use config_drift::FeConfigDriftTool;
+pub use data_size::FeDataSizeTool;
+pub use editlog::FeEditlogTool;
+pub use gc_analyzer::FeGcAnalyzerTool;
+pub use jmap::{JmapDumpTool, JmapHistoDiffTool, JmapHistoTool};
 pub use jstack::JstackTool;
 pub use list::FeListTool;
+pub use metrics::FeMetricsTool;
 pub use profiler::FeProfilerTool;
+pub use query_profile::FeQueryProfileTool;
+pub use replica_health::FeReplicaHealthTool;
 pub use routine_load::{RoutineLoadJobLister, get_routine_load_tools};
+pub use sql_console::FeSqlConsoleTool;
 pub use table_info::{FeTableInfoTool, TableIdentity, TableInfoReport};
+pub use tablet_lookup::FeTabletLookupTool;
+pub use workload_group::FeWorkloadGroupTool;
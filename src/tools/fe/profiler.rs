@@ -1,42 +1,28 @@
 use crate::config::Config;
+use crate::core::version::DorisVersion;
 use crate::error::{CliError, Result};
 use crate::executor;
-use crate::tools::{ExecutionResult, Tool};
-use crate::ui;
-use dialoguer::Input;
-use std::env;
+use crate::tools::{ExecutionResult, Impact, Tool};
+use crate::ui::InputHelper;
 use std::process::Command;
 
+/// async-profiler runs are capped to keep the FE responsive
+const MAX_PROFILE_SECONDS: u32 = 300;
+
 pub struct FeProfilerTool;
 
 impl FeProfilerTool {
     /// Prompt user for profile duration and return the duration value
     /// This method can be called before tool execution to get user input
     pub fn prompt_duration() -> Result<u32> {
-        let input: String = Input::with_theme(&dialoguer::theme::ColorfulTheme::default())
-            .with_prompt("Enter collection duration in seconds")
-            .with_initial_text("10")
-            .interact_text()
-            .map_err(|e| CliError::InvalidInput(format!("Duration input failed: {e}")))?;
-
-        let duration_str = if input.trim().is_empty() {
-            "10"
-        } else {
-            input.trim()
-        };
-
-        match duration_str.parse::<u32>() {
-            Ok(val) if val > 0 && val <= 300 => Ok(val),
-            Ok(_) => {
-                ui::print_warning("Duration must be between 1 and 300 seconds!");
-                ui::print_info("Hint: Enter a number between 1-300, e.g., 25");
-                Err(CliError::GracefulExit)
-            }
-            Err(_) => {
-                ui::print_warning("Please enter a valid number!");
-                ui::print_info("Hint: Enter a number between 1-300, e.g., 25");
-                Err(CliError::GracefulExit)
-            }
+        InputHelper::prompt_duration_secs("Collection duration", 10, MAX_PROFILE_SECONDS)
+    }
+
+    /// Duration from `PROFILE_SECONDS` when set, otherwise asked for
+    pub fn duration() -> Result<u32> {
+        match InputHelper::duration_secs_from_env("PROFILE_SECONDS", 10, MAX_PROFILE_SECONDS) {
+            Some(secs) => Ok(secs),
+            None => Self::prompt_duration(),
         }
     }
 
@@ -67,10 +53,7 @@ impl FeProfilerTool {
 
         let message = format!("Flame graph generated successfully (duration: {duration}s).");
 
-        Ok(ExecutionResult {
-            output_path: std::path::PathBuf::new(),
-            message,
-        })
+        Ok(ExecutionResult::new(std::path::PathBuf::new(), message))
     }
 }
 
@@ -84,19 +67,23 @@ impl Tool for FeProfilerTool {
     }
 
     fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
-        let profile_seconds = if env::var("PROFILE_SECONDS").is_ok() {
-            env::var("PROFILE_SECONDS")
-                .unwrap()
-                .parse::<u32>()
-                .unwrap_or(10)
-        } else {
-            Self::prompt_duration()?
-        };
-
-        self.execute_with_duration(config, profile_seconds)
+        self.execute_with_duration(config, Self::duration()?)
     }
 
     fn requires_pid(&self) -> bool {
         false // FE profiler doesn't need PID as it uses profile_fe.sh script
     }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
+    fn impact(&self) -> Impact {
+        Impact::WritesInstallDir
+    }
+
+    fn min_version(&self) -> Option<DorisVersion> {
+        // profile_fe.sh ships with 2.1.4
+        Some(DorisVersion::new(2, 1, 4))
+    }
 }
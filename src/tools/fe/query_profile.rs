@@ -0,0 +1,418 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::error::{CliError, Result};
+use crate::tools::common::fs_utils;
+use crate::tools::common::http::HttpOptions;
+use crate::tools::mysql::CredentialManager;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui::{self, FormatHelper, InteractiveSelector};
+
+const QUERY_INFO_PATH: &str = "/rest/v2/manager/query/query_info?is_all_node=false";
+const PROFILE_PATH: &str = "/api/profile?query_id=";
+const HTTP_TIMEOUT_SECS: u64 = 30;
+const ENTER_ID: &str = "[Enter a query ID]";
+const MAX_LISTED: usize = 30;
+const TOP_OPERATORS: usize = 5;
+/// Summary keys echoed to the console, in profile order
+const SUMMARY_KEYS: &[&str] = &[
+    "Profile ID",
+    "Task Type",
+    "Start Time",
+    "Total",
+    "Task State",
+    "User",
+    "Default Db",
+    "Sql Statement",
+];
+
+/// `OLAP_SCAN_OPERATOR (id=0. nereids_id=12. table name = t)` (2.1/3.x) or
+/// `VNewOlapScanNode (id=0):(Active: 1.2ms, ...)` (2.0)
+static OPERATOR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*([A-Za-z_]+)\s*\(id=(-?\d+)").unwrap());
+static ACTIVE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\(Active:\s*([^,)]+)").unwrap());
+/// Merged profiles report `avg .., max .., min ..`; the max is the critical path
+static EXEC_TIME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"-\s*ExecTime:\s*(?:avg\s*[^,]+,\s*max\s*)?([^,\s]+)").unwrap());
+static DURATION_PART_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d+(?:\.\d+)?)\s*(ns|us|ms|sec|s|min|m|hour|h)").unwrap());
+
+/// Doris profile durations such as `1sec23ms`, `15.933ms`, `2m3s` or `120.5us`
+pub fn parse_profile_duration(raw: &str) -> Option<Duration> {
+    let mut nanos = 0f64;
+    let mut matched = false;
+    for caps in DURATION_PART_RE.captures_iter(raw) {
+        let value: f64 = caps[1].parse().ok()?;
+        let scale = match &caps[2] {
+            "ns" => 1.0,
+            "us" => 1e3,
+            "ms" => 1e6,
+            "sec" | "s" => 1e9,
+            "min" | "m" => 60e9,
+            _ => 3600e9,
+        };
+        nanos += value * scale;
+        matched = true;
+    }
+    matched.then(|| Duration::from_nanos(nanos as u64))
+}
+
+/// `- Key: value` lines of the top-level `Summary:` section
+pub fn profile_summary(profile: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut in_summary = false;
+    for line in profile.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with(' ') && !trimmed.is_empty() {
+            // Unindented lines are section headers
+            if in_summary {
+                break;
+            }
+            in_summary = trimmed == "Summary:";
+            continue;
+        }
+        if in_summary && let Some((key, value)) = trimmed.trim_start_matches("- ").split_once(':') {
+            fields.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    fields
+}
+
+/// Slowest operators as (name, id, time); repeated operators keep their largest time
+pub fn top_operators(profile: &str, limit: usize) -> Vec<(String, String, Duration)> {
+    let mut times: HashMap<(String, String), Duration> = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+    for line in profile.lines() {
+        if let Some(caps) = OPERATOR_RE.captures(line) {
+            let key = (caps[1].to_string(), caps[2].to_string());
+            if let Some(d) = ACTIVE_RE
+                .captures(line)
+                .and_then(|c| parse_profile_duration(&c[1]))
+            {
+                let entry = times.entry(key.clone()).or_default();
+                *entry = (*entry).max(d);
+            }
+            current = Some(key);
+            continue;
+        }
+        if let (Some(key), Some(caps)) = (&current, EXEC_TIME_RE.captures(line))
+            && let Some(d) = parse_profile_duration(&caps[1])
+        {
+            let entry = times.entry(key.clone()).or_default();
+            *entry = (*entry).max(d);
+        }
+    }
+    let mut ops: Vec<(String, String, Duration)> =
+        times.into_iter().map(|((n, id), d)| (n, id, d)).collect();
+    ops.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(&b.1)));
+    ops.truncate(limit);
+    ops
+}
+
+/// A finished query from the FE manager API
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentQuery {
+    pub query_id: String,
+    pub duration: String,
+    pub sql: String,
+}
+
+/// Rows of `query_info`; column titles differ by release and UI language, so
+/// columns are found by title with positional fallbacks
+pub fn parse_query_info(body: &str) -> Vec<RecentQuery> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+    let data = &value["data"];
+    let names: Vec<String> = data["column_names"]
+        .as_array()
+        .map(|cols| {
+            cols.iter()
+                .map(|c| c.as_str().unwrap_or_default().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let column = |aliases: &[&str], fallback: usize| {
+        names
+            .iter()
+            .position(|n| aliases.iter().any(|a| n.eq_ignore_ascii_case(a)))
+            .unwrap_or(fallback)
+    };
+    let id_col = column(&["Query ID", "QueryId"], 0);
+    let sql_col = column(&["Sql", "Stmt"], 4);
+    let duration_col = column(&["Execution Duration", "执行时长", "Duration"], 8);
+
+    let cell = |row: &serde_json::Value, idx: usize| match &row[idx] {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    };
+    data["rows"]
+        .as_array()
+        .map(|rows| {
+            rows.iter()
+                .map(|row| RecentQuery {
+                    query_id: cell(row, id_col),
+                    duration: cell(row, duration_col),
+                    sql: cell(row, sql_col)
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                })
+                .filter(|q| !q.query_id.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Profile text from `/api/profile`, which wraps it in JSON on most releases
+pub fn extract_profile(body: &str) -> Result<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Ok(body.to_string());
+    };
+    if let Some(profile) = value["data"]["profile"].as_str() {
+        return Ok(profile.to_string());
+    }
+    if let Some(profile) = value["data"].as_str().filter(|_| value["code"] == 0) {
+        return Ok(profile.to_string());
+    }
+    let msg = value["data"]
+        .as_str()
+        .or(value["msg"].as_str())
+        .unwrap_or("no profile in response");
+    Err(CliError::ToolExecutionFailed(format!(
+        "FE returned no profile: {msg}"
+    )))
+}
+
+/// FE HTTP host and port: the FE picked via `fe-list`, else the MySQL host if
+/// set, else local, on `http_port`
+pub(super) fn fe_http_endpoint(doris: &DorisConfig) -> (String, u16) {
+    if let Some(fe) = crate::tools::mysql::selected_frontend() {
+        return (fe.host, fe.http_port);
+    }
+    let host = doris
+        .mysql
+        .as_ref()
+        .and_then(|m| m.host.clone())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    (host, doris.http_port.unwrap_or(8030))
+}
+
+/// GET `path` from the FE HTTP port; without `http_auth_user` the stored
+/// MySQL user's basic auth is sent
+fn fe_get(doris: &DorisConfig, path: &str) -> Result<String> {
+    let mut options = HttpOptions::from_config(doris)?;
+    if options.auth.is_none() {
+        let mysql = doris.mysql.as_ref().ok_or_else(|| {
+            CliError::ConfigError("MySQL credentials not found in config".to_string())
+        })?;
+        let password = CredentialManager::load()?.decrypt_password(&mysql.password)?;
+        options.auth = Some((mysql.user.clone(), password));
+    }
+    let (host, port) = fe_http_endpoint(doris);
+
+    let url = options.url(&host, port, path);
+    let response = options
+        .get(&url, Some(HTTP_TIMEOUT_SECS))
+        .map_err(|e| match e {
+            CliError::HttpAuthFailed { .. } => e,
+            _ => CliError::ToolExecutionFailed(format!(
+                "Cannot reach FE HTTP at {host}:{port}. Check http_port in fe.conf and that the FE is running."
+            )),
+        })?;
+    match response.status {
+        200 => Ok(response.text()),
+        other => Err(CliError::ToolExecutionFailed(format!(
+            "FE HTTP {path} returned status {other}"
+        ))),
+    }
+}
+
+/// Fetches a query profile from the FE HTTP API
+pub struct FeQueryProfileTool;
+
+impl FeQueryProfileTool {
+    fn prompt_query_id(doris: &DorisConfig) -> Result<String> {
+        let recent: Vec<RecentQuery> = fe_get(doris, QUERY_INFO_PATH)
+            .map(|body| parse_query_info(&body))
+            .unwrap_or_default()
+            .into_iter()
+            .take(MAX_LISTED)
+            .collect();
+        if !recent.is_empty() {
+            let mut labels: Vec<String> = recent
+                .iter()
+                .map(|q| {
+                    format!(
+                        "{:<34} {:>10}  {}",
+                        q.query_id,
+                        q.duration,
+                        FormatHelper::truncate_string(&q.sql, 60)
+                    )
+                })
+                .collect();
+            labels.push(ENTER_ID.to_string());
+            let picked = InteractiveSelector::new(labels.clone(), "Recent queries".to_string())
+                .select()?
+                .clone();
+            if let Some(pos) = labels.iter().position(|l| *l == picked)
+                && pos < recent.len()
+            {
+                return Ok(recent[pos].query_id.clone());
+            }
+        }
+        let id = ui::dialogs::input_text("Query ID", "")?;
+        let id = id.trim();
+        if id.is_empty() {
+            return Err(CliError::InvalidInput("No query ID given".to_string()));
+        }
+        Ok(id.to_string())
+    }
+}
+
+impl Tool for FeQueryProfileTool {
+    fn name(&self) -> &str {
+        "fe-query-profile"
+    }
+
+    fn description(&self) -> &str {
+        "Download a query profile from the FE HTTP API"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+        let query_id = Self::prompt_query_id(&doris)?;
+
+        ui::print_info(&format!("Fetching profile of {query_id}..."));
+        let profile = extract_profile(&fe_get(&doris, &format!("{PROFILE_PATH}{query_id}"))?)?;
+        if profile.trim().is_empty() {
+            return Err(CliError::ToolExecutionFailed(format!(
+                "Profile of {query_id} is empty; profiles need enable_profile=true or a slow enough query"
+            )));
+        }
+        // Query IDs are `hi-lo` hex pairs; keep the file name safe regardless
+        let safe_id: String = query_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let output_path = fs_utils::write_artifact(
+            config,
+            &format!("query_profile_{safe_id}.txt"),
+            profile.as_bytes(),
+        )?;
+
+        ui::print_info("");
+        for (key, value) in profile_summary(&profile)
+            .iter()
+            .filter(|(k, _)| SUMMARY_KEYS.contains(&k.as_str()))
+        {
+            ui::print_info(&format!(
+                "{:<14} {}",
+                format!("{key}:"),
+                FormatHelper::truncate_string(value, 100)
+            ));
+        }
+        let top = top_operators(&profile, TOP_OPERATORS);
+        if !top.is_empty() {
+            ui::print_info("");
+            ui::print_info(&format!("Top {} operators by time:", top.len()));
+            for (name, id, time) in &top {
+                ui::print_info(&format!(
+                    "  {:>10.3}ms  {name} (id={id})",
+                    time.as_secs_f64() * 1000.0
+                ));
+            }
+        }
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("Profile of {query_id} saved"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profile_summary_and_slowest_operators() {
+        assert_eq!(
+            parse_profile_duration("1sec23ms"),
+            Some(Duration::from_millis(1023))
+        );
+        assert_eq!(
+            parse_profile_duration("2m3s"),
+            Some(Duration::from_secs(123))
+        );
+        assert_eq!(
+            parse_profile_duration("120.5us"),
+            Some(Duration::from_nanos(120_500))
+        );
+        assert_eq!(parse_profile_duration("N/A"), None);
+
+        let profile = "Summary:
+  - Profile ID: 5e1c7b1f2a3d4e5f-9a8b7c6d5e4f3a2b
+  - Total: 1sec23ms
+  - Task State: OK
+  - Sql Statement: select count(*) from t
+Execution Summary:
+  - Workload Group: normal
+MergedProfile:
+  Fragments:
+    Fragment 0:
+      Pipeline 0(instance_num=1):
+        RESULT_SINK_OPERATOR (id=0):
+          - ExecTime: avg 1.2ms, max 1.5ms, min 1.0ms
+        AGGREGATION_OPERATOR (id=2. nereids_id=30):
+          CommonCounters:
+            - ExecTime: avg 300.1ms, max 812.4ms, min 20ms
+    Fragment 1:
+      Pipeline 0(instance_num=4):
+        OLAP_SCAN_OPERATOR (id=1. nereids_id=12. table name = t(t)):
+          - ExecTime: avg 90ms, max 95.5ms, min 80ms
+VNewOlapScanNode (id=7):(Active: 2sec, % non-child: 10.00%)
+";
+        let summary = profile_summary(profile);
+        assert_eq!(summary.len(), 4);
+        assert_eq!(summary[1], ("Total".to_string(), "1sec23ms".to_string()));
+
+        let top = top_operators(profile, 3);
+        let names: Vec<&str> = top.iter().map(|(n, _, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "VNewOlapScanNode",
+                "AGGREGATION_OPERATOR",
+                "OLAP_SCAN_OPERATOR"
+            ]
+        );
+        assert_eq!(top[1].2, Duration::from_micros(812_400));
+
+        let info = r#"{"msg":"success","code":0,"data":{"column_names":["Query ID","FE Node","Query User","Execution Database","Sql","Query Type","Start Time","End Time","Execution Duration","Status"],"rows":[["a1-b2","fe1","root","db","select\n 1","Query","t0","t1","12ms","FINISHED"]]}}"#;
+        let queries = parse_query_info(info);
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].sql, "select 1");
+        assert_eq!(queries[0].duration, "12ms");
+
+        let wrapped = r#"{"msg":"success","code":0,"data":{"profile":"Summary:\n  - Total: 1s"}}"#;
+        assert!(extract_profile(wrapped).unwrap().starts_with("Summary:"));
+        let missing = r#"{"msg":"Bad Request","code":403,"data":"query id x not found"}"#;
+        assert!(extract_profile(missing).is_err());
+    }
+}
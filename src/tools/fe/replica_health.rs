@@ -0,0 +1,348 @@
+use console::style;
+use std::collections::BTreeMap;
+
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::mysql::MySQLTool;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+const MAX_PROBLEM_TABLETS: usize = 50;
+
+// Column names differ between releases; the first present alias wins
+const COL_UNHEALTHY: &[&str] = &["UnhealthyTabletNum"];
+const COL_INCONSISTENT: &[&str] = &["InconsistentTabletNum", "InconsistentNum"];
+const COL_VERSION_MISSING: &[&str] = &["VersionIncompleteNum", "VersionMissingNum"];
+
+/// Header plus rows of a tab-separated PROC result
+#[derive(Debug, Default)]
+pub struct ProcTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ProcTable {
+    /// Parse mysql batch output (first line is the header)
+    pub fn parse(output: &str) -> Self {
+        let mut lines = output.lines().filter(|l| !l.trim().is_empty());
+        let Some(header) = lines.next() else {
+            return Self::default();
+        };
+        let headers = header.split('\t').map(|h| h.trim().to_string()).collect();
+        let rows = lines
+            .map(|l| l.split('\t').map(|c| c.trim().to_string()).collect())
+            .collect();
+        Self { headers, rows }
+    }
+
+    pub(super) fn column(&self, aliases: &[&str]) -> Option<usize> {
+        aliases
+            .iter()
+            .find_map(|a| self.headers.iter().position(|h| h.eq_ignore_ascii_case(a)))
+    }
+
+    pub(super) fn value<'a>(&self, row: &'a [String], aliases: &[&str]) -> Option<&'a str> {
+        self.column(aliases)
+            .and_then(|i| row.get(i))
+            .map(String::as_str)
+    }
+
+    fn count(&self, row: &[String], aliases: &[&str]) -> u64 {
+        self.value(row, aliases)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0)
+    }
+}
+
+/// Tablet health counters of one database
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DbReplicaHealth {
+    pub db_id: String,
+    pub db_name: String,
+    pub tablets: u64,
+    pub unhealthy: u64,
+    pub inconsistent: u64,
+    pub version_missing: u64,
+}
+
+impl DbReplicaHealth {
+    pub fn has_problems(&self) -> bool {
+        self.unhealthy + self.inconsistent + self.version_missing > 0
+    }
+}
+
+/// Merge `/statistic` and `/cluster_health/tablet_health` output into per-database health
+pub fn parse_health(statistic: &str, tablet_health: &str) -> Vec<DbReplicaHealth> {
+    let mut by_db: BTreeMap<String, DbReplicaHealth> = BTreeMap::new();
+
+    let stat = ProcTable::parse(statistic);
+    for row in &stat.rows {
+        let Some(db_id) = db_id_of(&stat, row) else {
+            continue;
+        };
+        let entry = by_db
+            .entry(db_id.clone())
+            .or_insert_with(|| DbReplicaHealth {
+                db_id,
+                ..Default::default()
+            });
+        entry.db_name = stat.value(row, &["DbName"]).unwrap_or("").to_string();
+        entry.tablets = stat.count(row, &["TabletNum"]);
+        entry.unhealthy = stat.count(row, COL_UNHEALTHY);
+        entry.inconsistent = stat.count(row, COL_INCONSISTENT);
+    }
+
+    let health = ProcTable::parse(tablet_health);
+    for row in &health.rows {
+        let Some(db_id) = db_id_of(&health, row) else {
+            continue;
+        };
+        let entry = by_db
+            .entry(db_id.clone())
+            .or_insert_with(|| DbReplicaHealth {
+                db_id,
+                ..Default::default()
+            });
+        if entry.db_name.is_empty() {
+            entry.db_name = health.value(row, &["DbName"]).unwrap_or("").to_string();
+        }
+        entry.version_missing = health.count(row, COL_VERSION_MISSING);
+        // 3.x reports inconsistency only in tablet_health
+        entry.inconsistent = entry.inconsistent.max(health.count(row, COL_INCONSISTENT));
+    }
+
+    by_db.into_values().collect()
+}
+
+/// Skip the trailing "Total" row and anything without a numeric DbId
+fn db_id_of(table: &ProcTable, row: &[String]) -> Option<String> {
+    table
+        .value(row, &["DbId"])
+        .filter(|id| id.parse::<u64>().is_ok())
+        .map(str::to_string)
+}
+
+/// Parse a drill-down PROC row into (status, tablet id) pairs.
+/// Every `<Status>Tablets` column holds a list like `[10001, 10002]`.
+pub fn parse_problem_tablets(output: &str) -> Vec<(String, String)> {
+    let table = ProcTable::parse(output);
+    let mut out = Vec::new();
+    for row in &table.rows {
+        for (idx, header) in table.headers.iter().enumerate() {
+            let Some(status) = header.strip_suffix("Tablets") else {
+                continue;
+            };
+            let Some(cell) = row.get(idx) else {
+                continue;
+            };
+            for id in cell
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty() && id.parse::<u64>().is_ok())
+            {
+                out.push((status.to_string(), id.to_string()));
+            }
+        }
+    }
+    out
+}
+
+pub struct FeReplicaHealthTool;
+
+impl Tool for FeReplicaHealthTool {
+    fn name(&self) -> &str {
+        "fe-replica-health"
+    }
+
+    fn description(&self) -> &str {
+        "Summarize unhealthy/inconsistent tablets per database"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+
+        ui::print_info("Querying tablet statistics...");
+        let statistic = MySQLTool::query_sql_with_config(&doris, "SHOW PROC '/statistic';")?;
+        // Older releases do not have tablet_health; fall back to /statistic only
+        let tablet_health = match MySQLTool::query_sql_with_config(
+            &doris,
+            "SHOW PROC '/cluster_health/tablet_health';",
+        ) {
+            Ok(output) => Some(output),
+            Err(e @ (CliError::MySQLAccessDenied(_) | CliError::MySQLPrivilegeDenied { .. })) => {
+                return Err(e);
+            }
+            Err(e) => {
+                ui::print_warning(&format!(
+                    "tablet_health unavailable, counts below come from /statistic only: {e}"
+                ));
+                None
+            }
+        };
+
+        let dbs = parse_health(&statistic, tablet_health.as_deref().unwrap_or_default());
+        if dbs.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No database statistics returned by SHOW PROC '/statistic'".to_string(),
+            ));
+        }
+        display_summary(&dbs);
+
+        let problems: Vec<&DbReplicaHealth> = dbs.iter().filter(|d| d.has_problems()).collect();
+        if problems.is_empty() {
+            let message = if tablet_health.is_some() {
+                "All tablets are healthy"
+            } else {
+                "No unhealthy tablets in /statistic; tablet_health could not be checked"
+            };
+            return Ok(ExecutionResult::console(message));
+        }
+
+        let report = build_problem_report(&doris, &problems);
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("replica_health", "txt"),
+            report.as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "Replica health check completed, {} database(s) with problems",
+                problems.len()
+            ),
+        ))
+    }
+}
+
+fn display_summary(dbs: &[DbReplicaHealth]) {
+    ui::print_info("");
+    ui::print_info(&format!(
+        "{:<32} {:>10} {:>10} {:>13} {:>16}",
+        "Database", "Tablets", "Unhealthy", "Inconsistent", "VersionMissing"
+    ));
+    ui::print_info(&"-".repeat(85));
+
+    let mut total = DbReplicaHealth::default();
+    for db in dbs {
+        let line = format!(
+            "{:<32} {:>10} {:>10} {:>13} {:>16}",
+            ui::FormatHelper::truncate_string(&db.db_name, 32),
+            db.tablets,
+            db.unhealthy,
+            db.inconsistent,
+            db.version_missing
+        );
+        if db.has_problems() {
+            ui::print_info(&style(line).red().to_string());
+        } else {
+            ui::print_info(&line);
+        }
+        total.tablets += db.tablets;
+        total.unhealthy += db.unhealthy;
+        total.inconsistent += db.inconsistent;
+        total.version_missing += db.version_missing;
+    }
+
+    ui::print_info(&"-".repeat(85));
+    ui::print_info(&format!(
+        "{:<32} {:>10} {:>10} {:>13} {:>16}",
+        "Total", total.tablets, total.unhealthy, total.inconsistent, total.version_missing
+    ));
+}
+
+fn build_problem_report(doris: &DorisConfig, problems: &[&DbReplicaHealth]) -> String {
+    let mut out = String::from("Replica health problems\n");
+    for db in problems {
+        out.push_str(&format!(
+            "\n== {} (DbId {}): unhealthy={} inconsistent={} version_missing={}\n",
+            db.db_name, db.db_id, db.unhealthy, db.inconsistent, db.version_missing
+        ));
+
+        let mut tablets = Vec::new();
+        for proc_path in [
+            format!("/statistic/{}", db.db_id),
+            format!("/cluster_health/tablet_health/{}", db.db_id),
+        ] {
+            match MySQLTool::query_sql_with_config(doris, &format!("SHOW PROC '{proc_path}';")) {
+                Ok(output) => tablets.extend(parse_problem_tablets(&output)),
+                Err(e) => out.push_str(&format!("  ({proc_path} unavailable: {e})\n")),
+            }
+        }
+        tablets.sort();
+        tablets.dedup();
+
+        let shown = tablets.len().min(MAX_PROBLEM_TABLETS);
+        for (status, id) in &tablets[..shown] {
+            out.push_str(&format!("  {id:<20} {status}\n"));
+        }
+        if tablets.len() > shown {
+            out.push_str(&format!(
+                "  ... {} more not listed\n",
+                tablets.len() - shown
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATISTIC_V2: &str = "DbId\tDbName\tTableNum\tPartitionNum\tIndexNum\tTabletNum\tReplicaNum\tUnhealthyTabletNum\tInconsistentTabletNum\tCloningTabletNum\tBadTabletNum
+10002\tsales\t12\t40\t40\t960\t2880\t3\t0\t0\t0
+10005\tlogs\t3\t10\t10\t300\t900\t0\t0\t0\t0
+Total\t2\t15\t50\t50\t1260\t3780\t3\t0\t0\t0
+";
+
+    const TABLET_HEALTH_V3: &str = "DbId\tDbName\tTabletNum\tHealthyNum\tReplicaMissingNum\tVersionIncompleteNum\tReplicaRelocatingNum\tInconsistentNum\tCloningNum
+10002\tsales\t960\t955\t2\t1\t0\t0\t0
+10005\tlogs\t300\t298\t0\t0\t0\t2\t0
+Total\t2\t1260\t1253\t2\t1\t0\t2\t0
+";
+
+    #[test]
+    fn merges_statistic_and_tablet_health_layouts() {
+        let dbs = parse_health(STATISTIC_V2, TABLET_HEALTH_V3);
+        assert_eq!(dbs.len(), 2);
+
+        let sales = dbs.iter().find(|d| d.db_name == "sales").unwrap();
+        assert_eq!((sales.unhealthy, sales.version_missing), (3, 1));
+        let logs = dbs.iter().find(|d| d.db_name == "logs").unwrap();
+        assert_eq!(logs.inconsistent, 2);
+        assert!(logs.has_problems());
+
+        // Without tablet_health only the /statistic counters are used
+        let dbs = parse_health(STATISTIC_V2, "");
+        assert!(
+            !dbs.iter()
+                .find(|d| d.db_name == "logs")
+                .unwrap()
+                .has_problems()
+        );
+    }
+
+    #[test]
+    fn parses_problem_tablet_lists() {
+        let output = "UnhealthyTablets\tInconsistentTablets\tCloningTablets\tBadTablets
+[10101, 10103]\t[]\t[]\t[10201]
+";
+        assert_eq!(
+            parse_problem_tablets(output),
+            vec![
+                ("Unhealthy".to_string(), "10101".to_string()),
+                ("Unhealthy".to_string(), "10103".to_string()),
+                ("Bad".to_string(), "10201".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use super::job_manager::RoutineLoadJobManager;
+use super::models::RoutineLoadJob;
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::common::http::HttpOptions;
+use crate::tools::fe::routine_load::messages as ErrMsg;
+use crate::tools::mysql::MySQLTool;
+use crate::tools::mysql::parser::{RowFields, parse_key_value_pairs, split_into_blocks};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::FormatHelper;
+
+/// Lines of the newest error log shown inline
+const ERROR_LOG_PREVIEW_LINES: usize = 20;
+const ERROR_LOG_FETCH_SECS: u64 = 10;
+/// Column names used for the task error message across Doris versions
+const TASK_ERROR_FIELDS: &[&str] = &["ErrorMsg", "OtherMsg", "Msg"];
+
+/// One row of `SHOW ROUTINE LOAD TASK`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutineLoadTask {
+    pub task_id: String,
+    pub be_id: String,
+    pub state: String,
+    pub error: Option<String>,
+}
+
+/// Value of `key` unless NULL or empty
+fn present(fields: &RowFields, key: &str) -> Option<String> {
+    fields
+        .value(key)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+pub fn parse_task_output(output: &str) -> Vec<RoutineLoadTask> {
+    split_into_blocks(output)
+        .iter()
+        .map(|block| parse_key_value_pairs(block))
+        .filter(|fields| fields.contains_key("TaskId"))
+        .map(|fields| RoutineLoadTask {
+            task_id: fields["TaskId"].clone(),
+            be_id: present(&fields, "BeId").unwrap_or_else(|| "-".to_string()),
+            state: present(&fields, "TxnStatus")
+                .or_else(|| present(&fields, "State"))
+                .unwrap_or_else(|| "UNKNOWN".to_string()),
+            error: TASK_ERROR_FIELDS.iter().find_map(|k| present(&fields, k)),
+        })
+        .collect()
+}
+
+/// Distinct task error messages with their counts, most frequent first
+pub fn aggregate_task_errors(tasks: &[RoutineLoadTask]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for error in tasks.iter().filter_map(|t| t.error.as_ref()) {
+        match counts.iter_mut().find(|(msg, _)| msg == error) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((error.clone(), 1)),
+        }
+    }
+    counts.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    counts
+}
+
+/// The last URL in the job's `ErrorLogUrls`, which Doris appends in time order
+pub fn newest_error_log_url(urls: &str) -> Option<&str> {
+    urls.split([',', ' ', '\n'])
+        .map(str::trim)
+        .rfind(|u| u.starts_with("http://") || u.starts_with("https://"))
+}
+
+pub struct RoutineLoadErrorChecker;
+
+impl Tool for RoutineLoadErrorChecker {
+    fn name(&self) -> &str {
+        "routine_load_error_checker"
+    }
+
+    fn description(&self) -> &str {
+        "Check job errors with a per-task drill-down and the newest error log"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let job_manager = RoutineLoadJobManager;
+        let job_id = job_manager
+            .get_current_job_id()
+            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))?;
+
+        let job = job_manager.fetch_current_job(&job_id)?;
+        print_job_state(&job);
+
+        let error_rows = job.statistic.as_ref().map_or(0, |s| s.error_rows);
+        if error_rows == 0 {
+            return Ok(ExecutionResult::console(format!(
+                "Job {job_id} has no error rows"
+            )));
+        }
+
+        let tasks = self.query_tasks(&job)?;
+        print_tasks(&tasks);
+
+        let saved = match job.error_log_urls.as_deref().and_then(newest_error_log_url) {
+            Some(url) => self.fetch_error_log(config, &job, url)?,
+            None => None,
+        };
+        let message = format!(
+            "Job {job_id}: {error_rows} error row(s) across {} task(s)",
+            tasks.len()
+        );
+        Ok(match saved {
+            Some(path) => ExecutionResult::new(path, message),
+            None => ExecutionResult::console(message),
+        })
+    }
+}
+
+impl RoutineLoadErrorChecker {
+    fn query_tasks(&self, job: &RoutineLoadJob) -> Result<Vec<RoutineLoadTask>> {
+        let database = RoutineLoadJobManager
+            .get_last_database()
+            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))?;
+        let doris = crate::config_loader::load_config()?;
+        let sql = format!(
+            "USE `{database}`; SHOW ROUTINE LOAD TASK WHERE JobName = '{}' \\G",
+            job.name.replace('\'', "\\'")
+        );
+        let output = MySQLTool::query_sql_with_config(&doris, &sql)?;
+        Ok(parse_task_output(&output))
+    }
+
+    /// Download the newest error log, print its head and save it in full.
+    ///
+    /// Network failures only print the URL so the rest of the report stands.
+    fn fetch_error_log(
+        &self,
+        config: &Config,
+        job: &RoutineLoadJob,
+        url: &str,
+    ) -> Result<Option<std::path::PathBuf>> {
+        ui::print_info("");
+        ui::print_info("Newest error log:");
+        // The URL comes from the FE with its own scheme; only auth and TLS flags apply
+        let body = match HttpOptions::load()
+            .and_then(|options| options.get(url, Some(ERROR_LOG_FETCH_SECS)))
+        {
+            Ok(response) if !response.body.is_empty() => response.body,
+            Ok(_) => {
+                ui::print_warning(&format!("Error log is empty; open it manually: {url}"));
+                return Ok(None);
+            }
+            Err(e) => {
+                ui::print_warning(&format!("Could not fetch the error log ({e})"));
+                ui::print_info(&format!("URL: {url}"));
+                return Ok(None);
+            }
+        };
+
+        let text = String::from_utf8_lossy(&body);
+        for line in text.lines().take(ERROR_LOG_PREVIEW_LINES) {
+            ui::print_info(&format!("  {}", FormatHelper::truncate_string(line, 200)));
+        }
+        let total = text.lines().count();
+        if total > ERROR_LOG_PREVIEW_LINES {
+            ui::print_info(&format!(
+                "  ... {} more line(s)",
+                total - ERROR_LOG_PREVIEW_LINES
+            ));
+        }
+        let path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped(&format!("routine_load_error_log_{}", job.id), "txt"),
+            &body,
+        )?;
+        Ok(Some(path))
+    }
+}
+
+pub(super) fn print_job_state(job: &RoutineLoadJob) {
+    ui::print_info("");
+    ui::print_info(&format!("Job {} ({}): {}", job.name, job.id, job.state));
+    if let Some(stat) = &job.statistic {
+        ui::print_info(&format!(
+            "  Loaded rows: {} | Error rows: {} | Aborted tasks: {}",
+            FormatHelper::fmt_int(stat.loaded_rows),
+            FormatHelper::fmt_int(stat.error_rows),
+            FormatHelper::fmt_int(stat.aborted_task_num)
+        ));
+    }
+    if let Some(msg) = job.other_msg.as_deref().filter(|m| !m.is_empty()) {
+        ui::print_info(&format!("  OtherMsg: {msg}"));
+    }
+    if job.state == "PAUSED"
+        && let Some(pause_time) = &job.pause_time
+    {
+        ui::print_warning(&format!("  Paused since {pause_time}"));
+    }
+}
+
+fn print_tasks(tasks: &[RoutineLoadTask]) {
+    ui::print_info("");
+    ui::print_info("Tasks:");
+    if tasks.is_empty() {
+        ui::print_info("  (no running tasks)");
+        return;
+    }
+    ui::print_info(&format!(
+        "  {:<36} {:>10} {:<12} Last error",
+        "TaskId", "BeId", "State"
+    ));
+    for task in tasks {
+        ui::print_info(&format!(
+            "  {:<36} {:>10} {:<12} {}",
+            task.task_id,
+            task.be_id,
+            task.state,
+            task.error
+                .as_deref()
+                .map(|e| FormatHelper::truncate_string(e, 80))
+                .unwrap_or_else(|| "-".to_string())
+        ));
+    }
+
+    let errors = aggregate_task_errors(tasks);
+    if !errors.is_empty() {
+        ui::print_info("");
+        ui::print_info("Task errors by message:");
+        for (msg, count) in errors {
+            ui::print_info(&format!("  {count:>5} × {msg}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_aggregates_task_errors() {
+        let output = "*************************** 1. row ***************************
+              TaskId: 8b3b6d6e-1
+               TxnId: 101
+           TxnStatus: ABORTED
+               JobId: 12001
+                BeId: 10002
+            ErrorMsg: too many filtered rows
+*************************** 2. row ***************************
+              TaskId: 8b3b6d6e-2
+           TxnStatus: COMMITTED
+                BeId: 10003
+            ErrorMsg: NULL
+*************************** 3. row ***************************
+              TaskId: 8b3b6d6e-3
+           TxnStatus: ABORTED
+                BeId: 10002
+            ErrorMsg: too many filtered rows
+";
+        let tasks = parse_task_output(output);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].be_id, "10002");
+        assert_eq!(tasks[1].error, None);
+        assert_eq!(
+            aggregate_task_errors(&tasks),
+            [("too many filtered rows".to_string(), 2)]
+        );
+
+        assert_eq!(
+            newest_error_log_url(
+                "http://be1:8040/api/_load_error_log?file=a, http://be2:8040/api/_load_error_log?file=b"
+            ),
+            Some("http://be2:8040/api/_load_error_log?file=b")
+        );
+        assert_eq!(newest_error_log_url("NULL"), None);
+    }
+}
@@ -0,0 +1,187 @@
+use super::error_checker::print_job_state;
+use super::job_manager::RoutineLoadJobManager;
+use super::models::RoutineLoadJob;
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::{file_name, fs_utils};
+use crate::tools::fe::routine_load::messages as ErrMsg;
+use crate::tools::mysql::MySQLTool;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+/// What can be done to the selected job from the menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobAction {
+    Pause,
+    Resume,
+    ShowCreate,
+}
+
+impl JobAction {
+    fn label(self) -> &'static str {
+        match self {
+            JobAction::Pause => "PAUSE ROUTINE LOAD",
+            JobAction::Resume => "RESUME ROUTINE LOAD",
+            JobAction::ShowCreate => "SHOW CREATE ROUTINE LOAD",
+        }
+    }
+
+    fn changes_state(self) -> bool {
+        !matches!(self, JobAction::ShowCreate)
+    }
+
+    pub fn statement(self, database: &str, job_name: &str) -> String {
+        let verb = match self {
+            JobAction::Pause => "PAUSE",
+            JobAction::Resume => "RESUME",
+            JobAction::ShowCreate => "SHOW CREATE",
+        };
+        format!("USE `{database}`; {verb} ROUTINE LOAD FOR `{job_name}`")
+    }
+}
+
+/// Actions that make sense in `state`; pause and resume are left out in read-only mode
+pub fn available_actions(state: &str, read_only: bool) -> Vec<JobAction> {
+    let mut actions = Vec::new();
+    if !read_only {
+        match state {
+            "RUNNING" | "NEED_SCHEDULE" => actions.push(JobAction::Pause),
+            "PAUSED" => actions.push(JobAction::Resume),
+            _ => {}
+        }
+    }
+    actions.push(JobAction::ShowCreate);
+    actions
+}
+
+/// `CreateStmt` from the raw (-N -B -r) output of SHOW CREATE ROUTINE LOAD,
+/// whose columns are JobId, JobName and CreateStmt
+pub fn create_stmt_from_raw(raw: &str) -> Option<&str> {
+    raw.splitn(3, '\t')
+        .nth(2)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+pub struct RoutineLoadJobControlTool;
+
+impl Tool for RoutineLoadJobControlTool {
+    fn name(&self) -> &str {
+        "routine_load_job_control"
+    }
+
+    fn description(&self) -> &str {
+        "Pause or resume the selected job, or show its CREATE statement"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let job_manager = RoutineLoadJobManager;
+        let job_id = job_manager
+            .get_current_job_id()
+            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))?;
+        let database = job_manager
+            .get_last_database()
+            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))?;
+
+        let job = job_manager.fetch_current_job(&job_id)?;
+        print_job_state(&job);
+
+        let actions = available_actions(&job.state, config.is_read_only());
+        if config.is_read_only() {
+            ui::print_info("Read-only mode: pause and resume are not available.");
+        }
+        let labels: Vec<&str> = actions.iter().map(|a| a.label()).collect();
+        let action = actions[ui::dialogs::select_index("Action", &labels)?];
+
+        let doris = crate::config_loader::load_config()?;
+        if !action.changes_state() {
+            return show_create(config, &doris, &database, &job);
+        }
+
+        if !confirm_job_name(&job.name, action)? {
+            return Ok(ExecutionResult::console(
+                "Cancelled: the typed name did not match the job",
+            ));
+        }
+        MySQLTool::query_sql_with_config(&doris, &action.statement(&database, &job.name))?;
+
+        let updated = job_manager.fetch_current_job(&job_id)?;
+        print_job_state(&updated);
+        Ok(ExecutionResult::console(format!(
+            "Job {}: {} -> {}",
+            job.name, job.state, updated.state
+        )))
+    }
+}
+
+/// Typed confirmation: the job name, exactly
+fn confirm_job_name(job_name: &str, action: JobAction) -> Result<bool> {
+    ui::print_warning(&format!(
+        "{} changes the state of job '{job_name}' on the cluster.",
+        action.label()
+    ));
+    let typed = ui::dialogs::input_text("Type the job name to confirm", "")?;
+    Ok(ui::tool_executor::confirmation_matches(job_name, &typed))
+}
+
+fn show_create(
+    config: &Config,
+    doris: &crate::config_loader::DorisConfig,
+    database: &str,
+    job: &RoutineLoadJob,
+) -> Result<ExecutionResult> {
+    let sql = JobAction::ShowCreate.statement(database, &job.name);
+    let raw = MySQLTool::query_sql_raw_with_config(doris, &sql)?;
+    let stmt = create_stmt_from_raw(&raw).ok_or_else(|| {
+        CliError::ToolExecutionFailed(format!("SHOW CREATE returned nothing for {}", job.name))
+    })?;
+
+    ui::print_info("");
+    for line in stmt.lines() {
+        ui::print_info(line);
+    }
+    let path = fs_utils::write_artifact(
+        config,
+        &file_name::timestamped(&format!("routine_load_create_{}", job.id), "sql"),
+        format!("{stmt}\n").as_bytes(),
+    )?;
+    Ok(ExecutionResult::new(
+        path,
+        format!("CREATE statement of job {}", job.name),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offers_state_changes_only_where_they_apply() {
+        assert_eq!(
+            available_actions("RUNNING", false),
+            [JobAction::Pause, JobAction::ShowCreate]
+        );
+        assert_eq!(
+            available_actions("PAUSED", false),
+            [JobAction::Resume, JobAction::ShowCreate]
+        );
+        assert_eq!(available_actions("STOPPED", false), [JobAction::ShowCreate]);
+        assert_eq!(available_actions("PAUSED", true), [JobAction::ShowCreate]);
+
+        assert_eq!(
+            JobAction::Pause.statement("db", "kafka_job"),
+            "USE `db`; PAUSE ROUTINE LOAD FOR `kafka_job`"
+        );
+
+        let raw = "10086\tkafka_job\tCREATE ROUTINE LOAD db.kafka_job ON t\nPROPERTIES (...)\n";
+        assert_eq!(
+            create_stmt_from_raw(raw),
+            Some("CREATE ROUTINE LOAD db.kafka_job ON t\nPROPERTIES (...)")
+        );
+        assert_eq!(create_stmt_from_raw(""), None);
+    }
+}
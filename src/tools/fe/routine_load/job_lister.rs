@@ -4,9 +4,10 @@ use crate::config::Config;
 use crate::config_loader;
 use crate::error::{CliError, Result};
 use crate::tools::common::fs_utils::ensure_dir_exists;
-use crate::tools::mysql::MySQLTool;
+use crate::tools::mysql::{self, FeTarget, MySQLTool};
 use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
+use crate::ui::table::{Align, TableBuilder};
 use crate::ui::{InputHelper, InteractiveSelector};
 use crate::ui::{NoJobsNextAction, show_no_jobs_recovery_menu, show_unknown_db_recovery_menu};
 use chrono::Utc;
@@ -31,25 +32,28 @@ impl Tool for RoutineLoadJobLister {
     }
 
     fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let target = mysql::choose_fe_target()?;
         // Retry loop: allow reselecting database if no jobs found
         let mut database = self.prompt_database_name()?;
         loop {
-            match self.query_routine_load_jobs(&database) {
+            match self.query_routine_load_jobs(&database, &target) {
                 Ok(jobs) => {
                     self.display_jobs(&jobs)?;
                     let selected_job = self.prompt_job_selection(&jobs)?;
                     self.save_selected_job(selected_job, &database)?;
-                    let report =
-                        self.generate_selection_report(selected_job, &config.output_dir)?;
+                    let report = self.generate_selection_report(selected_job, &config.run_dir())?;
                     ui::print_info("");
                     ui::print_info(&report);
-                    return Ok(ExecutionResult {
-                        output_path: config.output_dir.clone(),
-                        message: format!(
-                            "Job ID '{}' selected and saved in memory",
-                            selected_job.id
-                        ),
-                    });
+                    ui::hints::show_hints(
+                        config,
+                        &[ui::hints::Finding::RoutineLoadJobState(
+                            selected_job.state.clone(),
+                        )],
+                    );
+                    return Ok(ExecutionResult::new(
+                        config.run_dir(),
+                        format!("Job ID '{}' selected and saved in memory", selected_job.id),
+                    ));
                 }
                 Err(CliError::ToolExecutionFailed(msg))
                     if msg.contains("No Routine Load jobs found in database") =>
@@ -61,7 +65,7 @@ impl Tool for RoutineLoadJobLister {
                         NoJobsNextAction::BackToMenu => return Err(CliError::GracefulExit),
                     }
                 }
-                Err(CliError::ToolExecutionFailed(msg)) if msg.contains("Unknown database") => {
+                Err(CliError::MySQLUnknownDatabase) => {
                     match show_unknown_db_recovery_menu(&database)? {
                         NoJobsNextAction::ChooseAnotherDatabase => {
                             database = self.prompt_database_name()?;
@@ -101,11 +105,16 @@ impl RoutineLoadJobLister {
         InputHelper::prompt_non_empty("Database name")
     }
 
-    fn query_routine_load_jobs(&self, database: &str) -> Result<Vec<RoutineLoadJob>> {
+    fn query_routine_load_jobs(
+        &self,
+        database: &str,
+        target: &FeTarget,
+    ) -> Result<Vec<RoutineLoadJob>> {
         let doris_config = config_loader::load_config()?;
 
         let sql = format!("USE `{}`; SHOW ALL ROUTINE LOAD \\G", database);
-        let output = MySQLTool::query_sql_with_config(&doris_config, &sql)?;
+        let (output, served_by) = MySQLTool::query_sql_on_target(&doris_config, target, &sql)?;
+        ui::print_info(&format!("Served by FE {served_by}"));
 
         let job_manager = RoutineLoadJobManager;
         let jobs = job_manager.parse_routine_load_output(&output)?;
@@ -253,7 +262,7 @@ impl RoutineLoadJobLister {
             rows.push((part, prog, lag_v));
         }
 
-        rows.sort_by(|a, b| b.2.cmp(&a.2));
+        rows.sort_by_key(|r| std::cmp::Reverse(r.2));
         rows
     }
 
@@ -269,41 +278,32 @@ impl RoutineLoadJobLister {
             .filter(|(_, _, lag_v)| *lag_v > 0)
             .cloned()
             .collect();
-        let total = nonzero.len();
-        let mut out = String::new();
+        let section = |rows: &[(String, Option<String>, i64)]| {
+            let mut table = TableBuilder::new(["Partition", "Progress", "Lag"])
+                .max_width(0, 40)
+                .max_width(1, 24)
+                .align(1, Align::Right)
+                .align(2, Align::Right)
+                .empty_message("(no data)");
+            for (part, prog, lag_v) in rows {
+                table.push_row([
+                    part.clone(),
+                    prog.clone().unwrap_or_else(|| "N/A".to_string()),
+                    lag_v.to_string(),
+                ]);
+            }
+            table.render()
+        };
 
         // Top section (largest lag first)
-        out.push_str("Top by lag:\n");
-        out.push_str("┌─────────────┬─────────────┬─────────────┐\n");
-        out.push_str("│  Partition  │   Progress  │     Lag     │\n");
-        out.push_str("├─────────────┼─────────────┼─────────────┤\n");
-        // sort desc
-        nonzero.sort_by(|a, b| b.2.cmp(&a.2));
-        let mut printed = 0usize;
-        for (part, prog, lag_v) in nonzero.iter().take(top_n) {
-            let prog_s = prog.as_deref().unwrap_or("N/A");
-            out.push_str(&format!("│ {part:>11} │ {prog_s:>11} │ {lag_v:>11} │\n"));
-            printed += 1;
-        }
-        if printed == 0 {
-            out.push_str("│               (no data)               │\n");
-        }
-        out.push_str("└─────────────┴─────────────┴─────────────┘\n");
-
-        // Bottom section (smallest lag last); avoid overlap with top
-        out.push_str("Bottom by lag:\n");
-        out.push_str("┌─────────────┬─────────────┬─────────────┐\n");
-        out.push_str("│  Partition  │   Progress  │     Lag     │\n");
-        out.push_str("├─────────────┼─────────────┼─────────────┤\n");
-        // sort asc
-        nonzero.sort_by(|a, b| a.2.cmp(&b.2));
-        let start = 0usize; // beginning for smallest
-        let end = bottom_n.min(total);
-        for (part, prog, lag_v) in nonzero.iter().skip(start).take(end) {
-            let prog_s = prog.as_deref().unwrap_or("N/A");
-            out.push_str(&format!("│ {part:>11} │ {prog_s:>11} │ {lag_v:>11} │\n"));
-        }
-        out.push_str("└─────────────┴─────────────┴─────────────┘\n");
+        nonzero.sort_by_key(|r| std::cmp::Reverse(r.2));
+        let top: Vec<_> = nonzero.iter().take(top_n).cloned().collect();
+        let mut out = format!("Top by lag:\n{}", section(&top));
+
+        // Bottom section (smallest lag first)
+        nonzero.sort_by_key(|r| r.2);
+        let bottom: Vec<_> = nonzero.iter().take(bottom_n).cloned().collect();
+        out.push_str(&format!("Bottom by lag:\n{}", section(&bottom)));
 
         out
     }
@@ -328,3 +328,29 @@ impl RoutineLoadJobLister {
         Ok(file_path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::table::display_width;
+
+    #[test]
+    fn partition_overview_stays_aligned_with_long_names() {
+        let rows = vec![
+            (
+                "p_20240101_region_cn_north_1_very_long_partition_name".to_string(),
+                Some("12345678".to_string()),
+                9000,
+            ),
+            ("7".to_string(), None, 12),
+            ("3".to_string(), Some("1".to_string()), 0),
+        ];
+        let out = RoutineLoadJobLister.format_partitions_overview_nonzero_top_bottom(&rows, 5, 5);
+        let table_lines: Vec<&str> = out.lines().filter(|l| !l.ends_with(':')).collect();
+        let width = display_width(table_lines[0]);
+        assert!(table_lines.iter().all(|l| display_width(l) == width));
+        assert!(out.contains("p_20240101_region_cn_north_1_very"));
+        assert!(!out.contains("very_long_partition_name"));
+        assert!(!out.contains("│ 3 "));
+    }
+}
@@ -1,5 +1,7 @@
+use super::messages::NO_JOB_ID;
 use super::models::{JobStatistic, RoutineLoadJob, RoutineLoadState};
 use crate::error::{CliError, Result};
+use crate::tools::mysql::MySQLTool;
 use crate::tools::mysql::parser::{parse_key_value_pairs, split_into_blocks};
 use once_cell::sync::Lazy;
 use serde_json;
@@ -89,6 +91,24 @@ impl RoutineLoadJobManager {
         self.with_state_readonly(|state| Ok(state.job_cache.clone()))
     }
 
+    /// Query the remembered job again so callers see its live state
+    pub fn fetch_current_job(&self, job_id: &str) -> Result<RoutineLoadJob> {
+        let (Some(name), Some(database)) = (self.get_current_job_name(), self.get_last_database())
+        else {
+            return Err(CliError::InvalidInput(NO_JOB_ID.into()));
+        };
+
+        let doris = crate::config_loader::load_config()?;
+        let sql = format!("USE `{database}`; SHOW ALL ROUTINE LOAD FOR `{name}` \\G");
+        let output = MySQLTool::query_sql_with_config(&doris, &sql)?;
+        self.parse_routine_load_output(&output)?
+            .into_iter()
+            .find(|j| j.id == job_id)
+            .ok_or_else(|| {
+                CliError::ToolExecutionFailed(format!("Job {job_id} not found in {database}"))
+            })
+    }
+
     pub fn parse_routine_load_output(&self, output: &str) -> Result<Vec<RoutineLoadJob>> {
         let blocks = split_into_blocks(output);
         let mut jobs = Vec::new();
@@ -109,35 +129,15 @@ impl RoutineLoadJobManager {
             return Ok(None);
         }
 
-        let statistic = if let Some(stat_str) = fields.get("Statistic") {
-            if stat_str != "NULL" {
-                Some(self.parse_statistic(stat_str)?)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        let progress = if let Some(prog_str) = fields.get("Progress") {
-            if prog_str != "NULL" {
-                Some(self.parse_progress(prog_str)?)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        let lag = if let Some(lag_str) = fields.get("Lag") {
-            if lag_str != "NULL" {
-                Some(self.parse_lag(lag_str)?)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let statistic = fields
+            .value("Statistic")
+            .map(|s| self.parse_statistic(s))
+            .transpose()?;
+        let progress = fields
+            .value("Progress")
+            .map(|s| self.parse_progress(s))
+            .transpose()?;
+        let lag = fields.value("Lag").map(|s| self.parse_lag(s)).transpose()?;
 
         let job = RoutineLoadJob {
             id: fields.get("Id").unwrap().clone(),
@@ -149,15 +149,15 @@ impl RoutineLoadJobManager {
             db_name: fields.get("DbName").unwrap_or(&"".to_string()).clone(),
             table_name: fields.get("TableName").unwrap_or(&"".to_string()).clone(),
             create_time: fields.get("CreateTime").unwrap_or(&"".to_string()).clone(),
-            pause_time: fields.get("PauseTime").filter(|&s| s != "NULL").cloned(),
-            end_time: fields.get("EndTime").filter(|&s| s != "NULL").cloned(),
+            pause_time: fields.value("PauseTime").map(str::to_string),
+            end_time: fields.value("EndTime").map(str::to_string),
             current_task_num: fields.get("CurrentTaskNum").cloned(),
             data_source_type: fields.get("DataSourceType").cloned(),
             statistic,
             progress,
             lag,
-            error_log_urls: fields.get("ErrorLogUrls").cloned(),
-            other_msg: fields.get("OtherMsg").cloned(),
+            error_log_urls: fields.value("ErrorLogUrls").map(str::to_string),
+            other_msg: fields.value("OtherMsg").map(str::to_string),
         };
 
         Ok(Some(job))
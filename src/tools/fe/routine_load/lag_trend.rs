@@ -0,0 +1,397 @@
+use chrono::{DateTime, Local};
+use console::Term;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+use super::job_manager::RoutineLoadJobManager;
+use super::models::RoutineLoadJob;
+use crate::config::Config;
+use crate::core::interrupt;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::format_utils::csv_field;
+use crate::tools::common::fs_utils;
+use crate::tools::fe::routine_load::messages as ErrMsg;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::FormatHelper;
+use crate::ui::table::{Align, TableBuilder};
+
+const MAX_INTERVAL_SECS: u32 = 300;
+const MAX_WATCH_MINUTES: i64 = 24 * 60;
+/// Growing partitions listed on the console; the CSV has all of them
+const SHOWN_PARTITIONS: usize = 20;
+
+/// Lag of one job at one point in time
+#[derive(Debug, Clone, PartialEq)]
+struct LagSample {
+    at: DateTime<Local>,
+    state: String,
+    loaded_rows: u64,
+    lag: BTreeMap<String, i64>,
+}
+
+impl LagSample {
+    fn from_job(at: DateTime<Local>, job: &RoutineLoadJob) -> Self {
+        Self {
+            at,
+            state: job.state.clone(),
+            loaded_rows: job.statistic.as_ref().map_or(0, |s| s.loaded_rows),
+            lag: job
+                .lag
+                .as_ref()
+                .map(|l| l.iter().map(|(k, v)| (k.clone(), *v)).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn total_lag(&self) -> i64 {
+        self.lag.values().sum()
+    }
+}
+
+/// Lag change of one partition between the first and last sample
+#[derive(Debug, Clone, PartialEq)]
+struct PartitionGrowth {
+    partition: String,
+    first: i64,
+    last: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct LagTrend {
+    elapsed_secs: i64,
+    first_total: i64,
+    last_total: i64,
+    /// loadedRows per second over the observation
+    consumed_per_sec: f64,
+    /// Seconds until lag reaches zero at the observed net drain; `None` unless lag shrinks
+    catch_up_secs: Option<f64>,
+    /// Partitions whose lag grew, fastest growing first
+    growing: Vec<PartitionGrowth>,
+    /// First sample not in RUNNING state, if the job left it
+    left_running: Option<(DateTime<Local>, String)>,
+}
+
+impl LagTrend {
+    fn delta(&self) -> i64 {
+        self.last_total - self.first_total
+    }
+}
+
+fn analyze(samples: &[LagSample]) -> Option<LagTrend> {
+    let (first, last) = (samples.first()?, samples.last()?);
+    let elapsed_secs = (last.at - first.at).num_seconds();
+    let (first_total, last_total) = (first.total_lag(), last.total_lag());
+    let per_sec = |delta: f64| {
+        if elapsed_secs > 0 {
+            delta / elapsed_secs as f64
+        } else {
+            0.0
+        }
+    };
+    let drain_per_sec = per_sec((first_total - last_total) as f64);
+    let catch_up_secs = (drain_per_sec > 0.0).then(|| last_total as f64 / drain_per_sec);
+
+    let mut growing: Vec<PartitionGrowth> = last
+        .lag
+        .iter()
+        .filter_map(|(partition, &lag)| {
+            let before = *first.lag.get(partition)?;
+            (lag > before).then(|| PartitionGrowth {
+                partition: partition.clone(),
+                first: before,
+                last: lag,
+            })
+        })
+        .collect();
+    growing.sort_by_key(|p| std::cmp::Reverse(p.last - p.first));
+
+    Some(LagTrend {
+        elapsed_secs,
+        first_total,
+        last_total,
+        consumed_per_sec: per_sec(last.loaded_rows.saturating_sub(first.loaded_rows) as f64),
+        catch_up_secs,
+        growing,
+        left_running: samples
+            .iter()
+            .find(|s| s.state != "RUNNING")
+            .map(|s| (s.at, s.state.clone())),
+    })
+}
+
+/// `2h 05m`, `12m 30s` or `45s`
+fn format_eta(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, _) => format!("{h}h {m:02}m"),
+    }
+}
+
+fn render_report(trend: &LagTrend, samples: usize) -> String {
+    let mut out = format!("Samples: {samples} over {}s\n", trend.elapsed_secs.max(0));
+    if let Some((at, state)) = &trend.left_running {
+        out.push_str(&format!(
+            "[!] Job left RUNNING at {} (state {state}); lag after that point is not being consumed\n",
+            at.format("%H:%M:%S")
+        ));
+    }
+    out.push_str(&format!(
+        "Total lag: {} -> {} ({})\n",
+        FormatHelper::fmt_int(trend.first_total.max(0) as u64),
+        FormatHelper::fmt_int(trend.last_total.max(0) as u64),
+        FormatHelper::fmt_signed(trend.delta())
+    ));
+    out.push_str(&format!(
+        "Consumption: {:.1} rows/s\n",
+        trend.consumed_per_sec
+    ));
+    out.push_str(&match trend.catch_up_secs {
+        Some(secs) => format!(
+            "Estimated catch-up: {} at the current rate\n",
+            format_eta(secs)
+        ),
+        None if trend.last_total <= 0 => "Estimated catch-up: no lag left\n".to_string(),
+        None => "Estimated catch-up: never at the current rate; lag is not shrinking\n".to_string(),
+    });
+
+    if trend.growing.is_empty() {
+        out.push_str("No partition's lag grew during the observation.\n");
+        return out;
+    }
+    out.push_str(&format!(
+        "\nPartitions with growing lag ({} of {}):\n",
+        trend.growing.len().min(SHOWN_PARTITIONS),
+        trend.growing.len()
+    ));
+    let mut table = TableBuilder::new(["Partition", "First", "Last", "Growth"])
+        .max_width(0, 40)
+        .align(1, Align::Right)
+        .align(2, Align::Right)
+        .align(3, Align::Right);
+    for p in trend.growing.iter().take(SHOWN_PARTITIONS) {
+        table.push_row([
+            p.partition.clone(),
+            p.first.to_string(),
+            p.last.to_string(),
+            FormatHelper::fmt_signed(p.last - p.first),
+        ]);
+    }
+    out.push_str(&table.render());
+    out
+}
+
+/// One row per sample with a lag column per partition seen in any sample
+fn render_csv(samples: &[LagSample]) -> String {
+    let partitions: BTreeSet<&String> = samples.iter().flat_map(|s| s.lag.keys()).collect();
+    let mut out = String::from("timestamp,state,loaded_rows,total_lag");
+    for p in &partitions {
+        out.push_str(&format!(",{}", csv_field(&format!("lag_{p}"))));
+    }
+    out.push('\n');
+    for s in samples {
+        out.push_str(&format!(
+            "{},{},{},{}",
+            s.at.format("%Y-%m-%d %H:%M:%S"),
+            csv_field(&s.state),
+            s.loaded_rows,
+            s.total_lag()
+        ));
+        for p in &partitions {
+            out.push(',');
+            if let Some(lag) = s.lag.get(*p) {
+                out.push_str(&lag.to_string());
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Query the job each `interval` until `duration` passes or Ctrl-C
+fn sample(job_id: &str, interval: Duration, duration: Duration) -> Vec<LagSample> {
+    let term = Term::stdout();
+    let live = term.is_term() && !ui::plain_output();
+    let started = Instant::now();
+    let mut samples: Vec<LagSample> = Vec::new();
+    interrupt::catching(|| {
+        loop {
+            let at = Local::now();
+            let line = match RoutineLoadJobManager.fetch_current_job(job_id) {
+                Ok(job) => {
+                    let sample = LagSample::from_job(at, &job);
+                    let line = format!(
+                        "{}  {}  total lag {}  ({} samples)",
+                        at.format("%H:%M:%S"),
+                        sample.state,
+                        FormatHelper::fmt_int(sample.total_lag().max(0) as u64),
+                        samples.len() + 1
+                    );
+                    samples.push(sample);
+                    line
+                }
+                Err(_) if interrupt::interrupted() => break,
+                Err(e) => format!("{}  query failed: {e}", at.format("%H:%M:%S")),
+            };
+            if live {
+                let _ = term.clear_line();
+                let _ = term.write_str(&line);
+            } else {
+                ui::print_info(&line);
+            }
+            if started.elapsed() + interval > duration
+                || !interrupt::sleep_unless_interrupted(interval)
+            {
+                break;
+            }
+        }
+    });
+    if live {
+        let _ = term.write_line("");
+    }
+    if interrupt::interrupted() {
+        ui::print_info("Stopped sampling; keeping the samples taken so far.");
+    }
+    samples
+}
+
+/// Samples SHOW ROUTINE LOAD over time and reports whether the Kafka lag shrinks
+pub struct RoutineLoadLagTrendTool;
+
+impl Tool for RoutineLoadLagTrendTool {
+    fn name(&self) -> &str {
+        "routine_load_lag_trend"
+    }
+
+    fn description(&self) -> &str {
+        "Sample job lag over time and report whether it is catching up"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let job_id = RoutineLoadJobManager
+            .get_current_job_id()
+            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))?;
+        let interval =
+            ui::InputHelper::prompt_duration_secs("Sample interval", 30, MAX_INTERVAL_SECS)?;
+        let minutes = ui::InputHelper::prompt_number_with_default(
+            "Observation duration",
+            10,
+            1,
+            Some(MAX_WATCH_MINUTES),
+            Some("minutes"),
+        )?;
+
+        ui::print_info(&format!(
+            "Sampling job {job_id} every {interval}s for {minutes} minute(s); press Ctrl-C to stop early."
+        ));
+        let samples = sample(
+            &job_id,
+            Duration::from_secs(u64::from(interval)),
+            Duration::from_secs(minutes as u64 * 60),
+        );
+        let trend = match analyze(&samples) {
+            Some(trend) if samples.len() >= 2 => trend,
+            _ => {
+                return Err(CliError::ToolExecutionFailed(format!(
+                    "Need at least 2 samples of job {job_id} for a trend, got {}",
+                    samples.len()
+                )));
+            }
+        };
+
+        let report = render_report(&trend, samples.len());
+        ui::print_info("");
+        for line in report.lines() {
+            if line.starts_with("[!]") {
+                ui::print_warning(line.trim_start_matches("[!]").trim());
+            } else {
+                ui::print_info(line);
+            }
+        }
+
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped(&format!("routine_load_lag_{job_id}"), "csv"),
+            render_csv(&samples).as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "Lag of job {job_id} {} by {} over {} sample(s)",
+                if trend.delta() > 0 { "grew" } else { "shrank" },
+                FormatHelper::fmt_int(trend.delta().unsigned_abs()),
+                samples.len()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample(secs: i64, state: &str, loaded: u64, lag: &[(&str, i64)]) -> LagSample {
+        LagSample {
+            at: Local.timestamp_opt(1_714_557_600 + secs, 0).unwrap(),
+            state: state.to_string(),
+            loaded_rows: loaded,
+            lag: lag.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn shrinking_lag_gets_a_catch_up_estimate() {
+        let samples = [
+            sample(0, "RUNNING", 0, &[("0", 600), ("1", 400)]),
+            sample(60, "RUNNING", 3_000, &[("0", 300), ("1", 450)]),
+            sample(100, "RUNNING", 5_000, &[("0", 100), ("1", 400)]),
+        ];
+        let trend = analyze(&samples).unwrap();
+        assert_eq!(trend.delta(), -500);
+        assert_eq!(trend.consumed_per_sec, 50.0);
+        // 500 drained in 100s leaves 500 at 5/s
+        assert_eq!(trend.catch_up_secs, Some(100.0));
+        assert!(trend.growing.is_empty());
+        assert_eq!(trend.left_running, None);
+        assert_eq!(format_eta(100.0), "1m 40s");
+        assert_eq!(format_eta(7_500.0), "2h 05m");
+
+        let csv = render_csv(&samples);
+        assert!(csv.starts_with("timestamp,state,loaded_rows,total_lag,lag_0,lag_1\n"));
+        assert!(
+            csv.lines()
+                .nth(3)
+                .unwrap()
+                .ends_with(",RUNNING,5000,500,100,400")
+        );
+    }
+
+    #[test]
+    fn growing_partitions_and_state_change_are_reported() {
+        let samples = [
+            sample(0, "RUNNING", 0, &[("0", 10), ("1", 10), ("2", 10)]),
+            sample(30, "PAUSED", 100, &[("0", 50), ("1", 20), ("2", 5)]),
+        ];
+        let trend = analyze(&samples).unwrap();
+        assert_eq!(trend.catch_up_secs, None);
+        let growing: Vec<&str> = trend.growing.iter().map(|p| p.partition.as_str()).collect();
+        assert_eq!(growing, ["0", "1"]);
+        assert_eq!(
+            trend.left_running.as_ref().map(|s| s.1.as_str()),
+            Some("PAUSED")
+        );
+
+        let report = render_report(&trend, samples.len());
+        assert!(report.contains("[!] Job left RUNNING"));
+        assert!(report.contains("lag is not shrinking"));
+    }
+}
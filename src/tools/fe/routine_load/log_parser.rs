@@ -1,19 +1,10 @@
-use crate::error::{CliError, Result};
+pub use super::models::LogCommitEntry;
+use crate::error::Result;
+use crate::tools::common::log_scan::{ScanProgress, for_each_line};
 use chrono::NaiveDateTime;
 use regex::Regex;
-use std::fs;
-use std::io::{BufRead, BufReader};
 use std::path::Path;
 
-#[derive(Debug, Clone, Default)]
-pub struct LogCommitEntry {
-    pub timestamp: NaiveDateTime,
-    pub loaded_rows: Option<u64>,
-    pub received_bytes: Option<u64>,
-    pub task_execution_ms: Option<u64>,
-    pub transaction_id: Option<String>,
-}
-
 pub struct FeLogParser {
     re_ts: Regex,
     re_fields: Regex,
@@ -70,19 +61,13 @@ pub fn scan_file(
     parser: &FeLogParser,
     path: &Path,
     job_id: &str,
-    out: &mut Vec<LogCommitEntry>,
-) -> Result<()> {
-    let f = fs::File::open(path).map_err(CliError::IoError)?;
-
-    let reader = BufReader::new(f);
-
-    for line_result in reader.lines() {
-        let line = line_result.map_err(CliError::IoError)?;
-
-        if let Some(entry) = parser.parse_line(&line, job_id) {
+    progress: &ScanProgress,
+) -> Result<Vec<LogCommitEntry>> {
+    let mut out = Vec::new();
+    for_each_line(path, progress, |line| {
+        if let Some(entry) = parser.parse_line(line, job_id) {
             out.push(entry);
         }
-    }
-
-    Ok(())
+    })?;
+    Ok(out)
 }
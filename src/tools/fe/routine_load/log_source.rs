@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use super::job_manager::RoutineLoadJobManager;
+use crate::config::Config;
+use crate::config_loader::Environment;
+use crate::error::{CliError, Result};
+use crate::tools::common::fs_utils;
+use crate::ui;
+use crate::ui::InputHelper;
+
+const LOG_SOURCES: &[&str] = &["Live FE log directory", "Copied log directory (offline)"];
+
+/// FE log directory for the analyzers: `offline_log_dir` when configured,
+/// otherwise the live FE or a copied bundle picked at the prompt.
+///
+/// Offline directories never touch process detection; they only need `fe.log*` files.
+pub fn resolve_log_dir(config: &Config) -> Result<PathBuf> {
+    if let Some(dir) = &config.offline_log_dir {
+        fs_utils::collect_fe_logs(dir)?;
+        ui::print_info(&format!("Using offline FE logs in {}", dir.display()));
+        return Ok(dir.clone());
+    }
+
+    if ui::dialogs::select_index("FE log source", LOG_SOURCES)? == 0 {
+        let doris = crate::config_loader::load_config()?;
+        return Ok(doris.service_paths(Environment::FE).log_dir);
+    }
+    let dir = PathBuf::from(ui::dialogs::input_text("Log directory path", "")?.trim());
+    fs_utils::collect_fe_logs(&dir)?;
+    Ok(dir)
+}
+
+/// Job selected with "Get Job ID", or one typed in when analyzing offline
+pub fn resolve_job_id() -> Result<String> {
+    if let Some(job_id) = RoutineLoadJobManager.get_current_job_id() {
+        return Ok(job_id);
+    }
+    ui::print_info("No job selected; enter the Job ID to look for in the logs.");
+    let job_id = InputHelper::prompt_non_empty("Job ID")?.trim().to_string();
+    if !job_id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(CliError::InvalidInput(format!(
+            "Job ID must be numeric, got '{job_id}'"
+        )));
+    }
+    Ok(job_id)
+}
@@ -1,26 +1,39 @@
+mod error_checker;
+mod job_control;
 mod job_lister;
 mod job_manager;
+mod lag_trend;
 mod log_parser;
+pub(crate) mod log_source;
 mod models;
 mod performance_analyzer;
+mod snapshot;
 mod traffic_monitor;
 
 pub mod messages {
     pub const NO_JOB_ID: &str = "No Job ID in memory. Run 'Get Job ID' first.";
 }
 
+pub use error_checker::RoutineLoadErrorChecker;
+pub use job_control::RoutineLoadJobControlTool;
 pub use job_lister::RoutineLoadJobLister;
 pub use job_manager::RoutineLoadJobManager;
+pub use lag_trend::RoutineLoadLagTrendTool;
 pub use models::*;
-pub use performance_analyzer::RoutineLoadPerformanceAnalyzer;
-pub use traffic_monitor::RoutineLoadTrafficMonitor;
+pub use performance_analyzer::{PerformanceReport, RoutineLoadPerformanceAnalyzer};
+pub use snapshot::RoutineLoadSnapshotTool;
+pub use traffic_monitor::{MinuteTraffic, RoutineLoadTrafficMonitor, TrafficReport};
 
-/// Routine Load tool index enum to avoid hardcoded indices
+/// Position of each Routine Load tool in the FE tool list of `ToolRegistry`
 #[derive(Debug, Clone, Copy)]
 pub enum RoutineLoadToolIndex {
-    JobLister = 5,
-    PerformanceAnalyzer = 6,
-    TrafficMonitor = 7,
+    JobLister = 6,
+    PerformanceAnalyzer = 7,
+    TrafficMonitor = 8,
+    SnapshotCompare = 9,
+    ErrorChecker = 10,
+    LagTrend = 11,
+    JobControl = 12,
 }
 
 impl RoutineLoadToolIndex {
@@ -38,5 +51,37 @@ pub fn get_routine_load_tools() -> Vec<Box<dyn crate::tools::Tool>> {
         Box::new(RoutineLoadJobLister),
         Box::new(RoutineLoadPerformanceAnalyzer),
         Box::new(RoutineLoadTrafficMonitor),
+        Box::new(RoutineLoadSnapshotTool),
+        Box::new(RoutineLoadErrorChecker),
+        Box::new(RoutineLoadLagTrendTool),
+        Box::new(RoutineLoadJobControlTool),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indices_point_at_the_registered_tools() {
+        let registry = crate::tools::ToolRegistry::new();
+        let name =
+            |index: RoutineLoadToolIndex| index.get_tool(registry.fe_tools()).map(|t| t.name());
+        assert_eq!(
+            name(RoutineLoadToolIndex::JobLister),
+            Some("routine_load_job_lister")
+        );
+        assert_eq!(
+            name(RoutineLoadToolIndex::ErrorChecker),
+            Some("routine_load_error_checker")
+        );
+        assert_eq!(
+            name(RoutineLoadToolIndex::LagTrend),
+            Some("routine_load_lag_trend")
+        );
+        assert_eq!(
+            name(RoutineLoadToolIndex::JobControl),
+            Some("routine_load_job_control")
+        );
+    }
+}
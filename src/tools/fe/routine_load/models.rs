@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Routine Load job information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoutineLoadJob {
     pub id: String,
     pub name: String,
@@ -21,7 +22,7 @@ pub struct RoutineLoadJob {
 }
 
 /// Job statistics information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobStatistic {
     pub received_bytes: u64,
     pub loaded_rows: u64,
@@ -1,18 +1,30 @@
-use chrono::Duration;
+use chrono::NaiveDateTime;
 use std::collections::HashMap;
 
-use super::job_manager::RoutineLoadJobManager;
 use super::log_parser::{FeLogParser, LogCommitEntry, scan_file};
 use crate::config::Config;
 use crate::error::{CliError, Result};
-use crate::tools::common::fs_utils;
-use crate::tools::fe::routine_load::messages as ErrMsg;
+use crate::tools::common::{fs_utils, log_scan};
+use crate::tools::context::{InteractiveContext, ToolContext};
 use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
-use crate::ui::{FormatHelper, InputHelper};
+use crate::ui::sink::OutputSink;
+use crate::ui::table::{Align, TableBuilder};
+use crate::ui::{FormatHelper, describe_range};
 
 pub struct RoutineLoadPerformanceAnalyzer;
 
+/// Commits of one job inside the analyzed window, oldest first and without
+/// the duplicates several FE log lines produce for the same commit
+#[derive(Debug, Clone)]
+pub struct PerformanceReport {
+    pub job_id: String,
+    pub range: (NaiveDateTime, NaiveDateTime),
+    /// Set when the newest log entry is far from the local clock
+    pub clock_note: Option<String>,
+    pub commits: Vec<LogCommitEntry>,
+}
+
 impl Tool for RoutineLoadPerformanceAnalyzer {
     fn name(&self) -> &str {
         "routine_load_performance_analyzer"
@@ -24,56 +36,83 @@ impl Tool for RoutineLoadPerformanceAnalyzer {
         false
     }
 
-    fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
-        let job_manager = RoutineLoadJobManager;
-        let job_id = job_manager
-            .get_current_job_id()
-            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))?;
+    fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
+        self.execute_with_ctx(config, pid, &InteractiveContext)
+    }
 
-        let doris = crate::config_loader::load_config()?;
-        let log_dir = doris.log_dir;
+    fn execute_with_ctx(
+        &self,
+        config: &Config,
+        _pid: u32,
+        ctx: &dyn ToolContext,
+    ) -> Result<ExecutionResult> {
+        let report = self.analyze(config, ctx)?;
+        let out = ctx.sink();
+
+        out.info("");
+        out.info(&format!(
+            "Analyzed range: {}",
+            describe_range(report.range.0, report.range.1)
+        ));
+        if let Some(note) = &report.clock_note {
+            out.info(note);
+        }
+        self.display_performance_results(&report.commits, out);
+
+        Ok(ExecutionResult::console("Performance analysis completed"))
+    }
+}
 
-        let minutes = self.prompt_time_window()?;
+impl RoutineLoadPerformanceAnalyzer {
+    /// Scan the FE logs for the job's commits in the window `ctx` picks
+    pub fn analyze(&self, config: &Config, ctx: &dyn ToolContext) -> Result<PerformanceReport> {
+        let job_id = ctx.job_id()?;
+        let log_dir = ctx.log_dir(config)?;
 
-        ui::print_info(&format!(
-            "Analyzing FE logs in {} for job {} (last {} min)...",
+        ctx.sink().info(&format!(
+            "Scanning FE logs in {} for job {}...",
             log_dir.display(),
-            job_id,
-            minutes
+            job_id
         ));
 
-        let entries = self.collect_and_parse_logs(&log_dir, &job_id)?;
-
-        let filtered_entries = self.filter_entries_by_time_window(entries, minutes)?;
+        let entries = self.collect_and_parse_logs(config, ctx, &log_dir, &job_id)?;
 
-        let deduplicated_entries = self.deduplicate_entries(filtered_entries)?;
+        let latest = entries.iter().map(|e| e.timestamp).max().unwrap();
+        let clock_note = ui::report_log_clock_skew(latest, ctx.sink());
+        let first = entries.iter().map(|e| e.timestamp).min().unwrap();
+        let range = ctx.time_range(30, (first, latest))?.bounds(latest);
 
-        self.display_performance_results(&deduplicated_entries)?;
+        let filtered_entries = self.filter_entries_by_time_window(entries, range)?;
+        let mut commits = self.deduplicate_entries(filtered_entries)?;
+        commits.sort_by_key(|e| e.timestamp);
 
-        Ok(ExecutionResult {
-            output_path: std::path::PathBuf::from("console_output"),
-            message: "Performance analysis completed".into(),
+        Ok(PerformanceReport {
+            job_id,
+            range,
+            clock_note,
+            commits,
         })
     }
-}
-
-impl RoutineLoadPerformanceAnalyzer {
-    fn prompt_time_window(&self) -> Result<i64> {
-        InputHelper::prompt_number_with_default("Analyze recent minutes", 30, 1)
-    }
 
     fn collect_and_parse_logs(
         &self,
+        config: &Config,
+        ctx: &dyn ToolContext,
         log_dir: &std::path::Path,
         job_id: &str,
     ) -> Result<Vec<LogCommitEntry>> {
         let files = fs_utils::collect_fe_logs(log_dir)?;
         let parser = FeLogParser::new();
-        let mut entries: Vec<LogCommitEntry> = Vec::new();
 
-        for path in files {
-            scan_file(&parser, &path, job_id, &mut entries)?;
-        }
+        let outcome = log_scan::scan_files_parallel(
+            &files,
+            config.log_scan_workers(),
+            !config.no_progress_animation && ctx.is_interactive(),
+            |path, progress| scan_file(&parser, path, job_id, progress),
+            |e| e.timestamp,
+        );
+        log_scan::report_scan_failures(&outcome.failures, ctx.sink());
+        let entries = outcome.entries;
 
         if entries.is_empty() {
             return Err(CliError::ToolExecutionFailed(
@@ -87,17 +126,15 @@ impl RoutineLoadPerformanceAnalyzer {
     fn filter_entries_by_time_window(
         &self,
         mut entries: Vec<LogCommitEntry>,
-        minutes: i64,
+        (start, end): (NaiveDateTime, NaiveDateTime),
     ) -> Result<Vec<LogCommitEntry>> {
-        // Use latest timestamp from logs as reference to avoid timezone/clock inconsistencies
-        let latest_ts = entries.iter().map(|e| e.timestamp).max().unwrap();
-        let window_start = latest_ts - Duration::minutes(minutes);
-        entries.retain(|e| e.timestamp >= window_start);
+        entries.retain(|e| e.timestamp >= start && e.timestamp <= end);
 
         if entries.is_empty() {
-            return Err(CliError::ToolExecutionFailed(
-                "No matching commit entries found in FE logs".into(),
-            ));
+            return Err(CliError::ToolExecutionFailed(format!(
+                "No commit entries between {}",
+                describe_range(start, end)
+            )));
         }
 
         Ok(entries)
@@ -136,17 +173,13 @@ impl RoutineLoadPerformanceAnalyzer {
         Ok(deduped)
     }
 
-    fn display_performance_results(&self, entries: &[LogCommitEntry]) -> Result<()> {
-        // Collect rows
-        let mut sorted_entries = entries.to_vec();
-        sorted_entries.sort_by_key(|e| e.timestamp);
-
+    fn display_performance_results(&self, sorted_entries: &[LogCommitEntry], out: &dyn OutputSink) {
         let headers = ["Time", "ms", "loadedRows", "receivedBytes", "txnId"];
 
         let mut rows: Vec<[String; 5]> = Vec::with_capacity(sorted_entries.len());
         let mut stats = PerformanceStats::new();
 
-        for entry in &sorted_entries {
+        for entry in sorted_entries {
             let time_str = entry.timestamp.format("%H:%M:%S").to_string();
             let ms = entry.task_execution_ms.unwrap_or(0);
             let rows_val = entry.loaded_rows.unwrap_or(0);
@@ -163,81 +196,24 @@ impl RoutineLoadPerformanceAnalyzer {
             stats.update(entry);
         }
 
-        // Compute column widths
-        let mut widths = [0usize; 5];
-        for i in 0..5 {
-            widths[i] = headers[i].len();
-        }
-        for row in &rows {
-            for i in 0..5 {
-                widths[i] = widths[i].max(row[i].len());
-            }
-        }
-
-        ui::print_info("");
-        ui::print_info("Per-commit stats");
-        self.print_table(&headers, &rows, &widths)?;
+        out.info("");
+        out.info("Per-commit stats");
+        self.print_table(&headers, &rows, out);
 
-        stats.display_summary();
-        Ok(())
+        stats.display_summary(out);
     }
 
-    fn print_table(
-        &self,
-        headers: &[&str; 5],
-        rows: &[[String; 5]],
-        widths: &[usize; 5],
-    ) -> Result<()> {
-        // Separator line
-        let sep = {
-            let mut s = String::new();
-            for (idx, w) in widths.iter().enumerate() {
-                if idx > 0 {
-                    s.push('+');
-                }
-                s.push_str(&"-".repeat(*w + 2));
-            }
-            s
-        };
-
-        // Header
-        ui::print_info(&sep);
-        let header_line = format!(
-            " {:<w0$} | {:>w1$} | {:>w2$} | {:>w3$} | {:<w4$}",
-            headers[0],
-            headers[1],
-            headers[2],
-            headers[3],
-            headers[4],
-            w0 = widths[0],
-            w1 = widths[1],
-            w2 = widths[2],
-            w3 = widths[3],
-            w4 = widths[4]
-        );
-        ui::print_info(&header_line);
-        ui::print_info(&sep);
-
-        // Rows
+    fn print_table(&self, headers: &[&str; 5], rows: &[[String; 5]], out: &dyn OutputSink) {
+        let mut table = TableBuilder::new(*headers)
+            .align(1, Align::Right)
+            .align(2, Align::Right)
+            .align(3, Align::Right);
         for row in rows {
-            let line = format!(
-                " {:<w0$} | {:>w1$} | {:>w2$} | {:>w3$} | {:<w4$}",
-                row[0],
-                row[1],
-                row[2],
-                row[3],
-                row[4],
-                w0 = widths[0],
-                w1 = widths[1],
-                w2 = widths[2],
-                w3 = widths[3],
-                w4 = widths[4]
-            );
-            ui::print_info(&line);
+            table.push_row(row.iter().cloned());
+        }
+        for line in table.render().lines() {
+            out.info(line);
         }
-        ui::print_info(&sep);
-
-        Ok(())
     }
 }
 
@@ -288,9 +264,9 @@ impl PerformanceStats {
         self.max_bytes = self.max_bytes.max(bytes);
     }
 
-    fn display_summary(&self) {
+    fn display_summary(&self, out: &dyn OutputSink) {
         if self.count > 0 {
-            ui::print_info(&format!(
+            out.info(&format!(
                 "count={}  avg_ms={}  min_ms={}  max_ms={}",
                 self.count,
                 self.sum_ms / self.count as u128,
@@ -301,7 +277,7 @@ impl PerformanceStats {
                 },
                 self.max_ms
             ));
-            ui::print_info(&format!(
+            out.info(&format!(
                 "          avg_rows={}  min_rows={}  max_rows={}",
                 FormatHelper::fmt_int_u128(self.sum_rows / self.count as u128),
                 FormatHelper::fmt_int(if self.min_rows == u64::MAX {
@@ -311,7 +287,7 @@ impl PerformanceStats {
                 }),
                 FormatHelper::fmt_int(self.max_rows)
             ));
-            ui::print_info(&format!(
+            out.info(&format!(
                 "          avg_bytes={}  min_bytes={}  max_bytes={}",
                 FormatHelper::fmt_int_u128(self.sum_bytes / self.count as u128),
                 FormatHelper::fmt_int(if self.min_bytes == u64::MAX {
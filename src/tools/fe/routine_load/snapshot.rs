@@ -0,0 +1,408 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::job_manager::RoutineLoadJobManager;
+use super::models::{JobStatistic, RoutineLoadJob};
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::fs_utils;
+use crate::tools::fe::routine_load::messages as ErrMsg;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::FormatHelper;
+
+const SNAPSHOT_DIR: &str = "routine_load_snapshots";
+const RETENTION_DAYS: i64 = 7;
+const FILE_TS_FORMAT: &str = "%Y%m%d_%H%M%S";
+const LIVE: &str = "Live (query now)";
+
+/// Parsed job state captured at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSnapshot {
+    #[serde(with = "rfc3339")]
+    pub taken_at: DateTime<Utc>,
+    pub job: RoutineLoadJob,
+}
+
+impl JobSnapshot {
+    fn file_name(&self) -> String {
+        format!(
+            "{}_{}.json",
+            self.job.id,
+            self.taken_at.format(FILE_TS_FORMAT)
+        )
+    }
+}
+
+/// Stored as RFC 3339 text, like `last_detected` in the config file
+mod rfc3339 {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(t: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&t.to_rfc3339())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<DateTime<Utc>, D::Error> {
+        let text = String::deserialize(d)?;
+        DateTime::parse_from_rfc3339(&text)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Change of one counter between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricDelta {
+    pub name: &'static str,
+    pub before: u64,
+    pub after: u64,
+    pub delta: i64,
+    pub per_sec: f64,
+}
+
+/// Lag change of one partition; `None` when the partition is missing on one side
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionLagDelta {
+    pub partition: String,
+    pub before: Option<i64>,
+    pub after: Option<i64>,
+    pub delta: i64,
+    pub per_sec: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotDiff {
+    pub elapsed_secs: i64,
+    pub metrics: Vec<MetricDelta>,
+    pub partitions: Vec<PartitionLagDelta>,
+}
+
+fn rate(delta: i64, secs: i64) -> f64 {
+    if secs <= 0 {
+        0.0
+    } else {
+        delta as f64 / secs as f64
+    }
+}
+
+/// Compared statistic counters; zero when the job reported no statistic
+fn counters(stat: Option<&JobStatistic>) -> [(&'static str, u64); 4] {
+    let get = |f: fn(&JobStatistic) -> u64| stat.map(f).unwrap_or(0);
+    [
+        ("loadedRows", get(|s| s.loaded_rows)),
+        ("receivedBytes", get(|s| s.received_bytes)),
+        ("errorRows", get(|s| s.error_rows)),
+        ("committedTaskNum", get(|s| s.committed_task_num)),
+    ]
+}
+
+/// Deltas of the statistic counters and per-partition lag, with per-second rates
+pub fn compare_snapshots(before: &JobSnapshot, after: &JobSnapshot) -> SnapshotDiff {
+    let elapsed_secs = (after.taken_at - before.taken_at).num_seconds();
+    let metrics = counters(before.job.statistic.as_ref())
+        .into_iter()
+        .zip(counters(after.job.statistic.as_ref()))
+        .map(|((name, b), (_, a))| {
+            let delta = a as i64 - b as i64;
+            MetricDelta {
+                name,
+                before: b,
+                after: a,
+                delta,
+                per_sec: rate(delta, elapsed_secs),
+            }
+        })
+        .collect();
+
+    let before_lag = before.job.lag.clone().unwrap_or_default();
+    let after_lag = after.job.lag.clone().unwrap_or_default();
+    let keys: BTreeSet<&String> = before_lag.keys().chain(after_lag.keys()).collect();
+    let mut partitions: Vec<PartitionLagDelta> = keys
+        .into_iter()
+        .map(|k| {
+            let b = before_lag.get(k).copied();
+            let a = after_lag.get(k).copied();
+            let delta = a.unwrap_or(0) - b.unwrap_or(0);
+            PartitionLagDelta {
+                partition: k.clone(),
+                before: b,
+                after: a,
+                delta,
+                per_sec: rate(delta, elapsed_secs),
+            }
+        })
+        .collect();
+    partitions.sort_by_key(|p| std::cmp::Reverse(p.delta.abs()));
+
+    SnapshotDiff {
+        elapsed_secs,
+        metrics,
+        partitions,
+    }
+}
+
+/// Whether a `<job_id>_<timestamp>.json` file is past the retention window.
+/// Names that do not carry a timestamp are kept.
+pub fn is_expired(file_name: &str, now: DateTime<Utc>) -> bool {
+    file_name
+        .strip_suffix(".json")
+        .and_then(|stem| stem.split_once('_'))
+        .and_then(|(_, ts)| NaiveDateTime::parse_from_str(ts, FILE_TS_FORMAT).ok())
+        .is_some_and(|t| now - t.and_utc() > Duration::days(RETENTION_DAYS))
+}
+
+pub struct RoutineLoadSnapshotTool;
+
+impl Tool for RoutineLoadSnapshotTool {
+    fn name(&self) -> &str {
+        "routine_load_snapshot_compare"
+    }
+
+    fn description(&self) -> &str {
+        "Snapshot job statistics and compare two points in time"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let job_manager = RoutineLoadJobManager;
+        let job_id = job_manager
+            .get_current_job_id()
+            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))?;
+
+        let dir = snapshot_dir()?;
+        prune_expired(&dir);
+
+        let choice = ui::dialogs::select_index(
+            "Snapshot & Compare",
+            &["Take snapshot", "Compare snapshots"],
+        )?;
+        if choice == 0 {
+            let snapshot = self.query_live(&job_id)?;
+            let path = dir.join(snapshot.file_name());
+            fs_utils::ensure_dir_exists(&path)?;
+            let json = serde_json::to_string_pretty(&snapshot)
+                .map_err(|e| CliError::ToolExecutionFailed(format!("Serialize failed: {e}")))?;
+            fs::write(&path, json)?;
+            return Ok(ExecutionResult::new(
+                path,
+                format!("Snapshot of job {job_id} saved"),
+            ));
+        }
+
+        let snapshots = load_snapshots(&dir, &job_id);
+        if snapshots.is_empty() {
+            return Err(CliError::InvalidInput(format!(
+                "No snapshots for job {job_id}. Take a snapshot first."
+            )));
+        }
+
+        let labels: Vec<String> = snapshots.iter().map(snapshot_label).collect();
+        let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+        let before_idx = ui::dialogs::select_index("Baseline snapshot", &label_refs)?;
+
+        let mut after_labels: Vec<&str> = label_refs[before_idx + 1..].to_vec();
+        after_labels.push(LIVE);
+        let after_idx = ui::dialogs::select_index("Compare with", &after_labels)?;
+        let after = if after_labels[after_idx] == LIVE {
+            self.query_live(&job_id)?
+        } else {
+            snapshots[before_idx + 1 + after_idx].clone()
+        };
+
+        let diff = compare_snapshots(&snapshots[before_idx], &after);
+        print_diff(&diff);
+
+        Ok(ExecutionResult::console(format!(
+            "Compared job {job_id} over {}s",
+            diff.elapsed_secs.max(0)
+        )))
+    }
+}
+
+impl RoutineLoadSnapshotTool {
+    fn query_live(&self, job_id: &str) -> Result<JobSnapshot> {
+        Ok(JobSnapshot {
+            taken_at: Utc::now(),
+            job: RoutineLoadJobManager.fetch_current_job(job_id)?,
+        })
+    }
+}
+
+fn snapshot_dir() -> Result<PathBuf> {
+    Ok(fs_utils::get_user_config_dir()?.join(SNAPSHOT_DIR))
+}
+
+fn prune_expired(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let now = Utc::now();
+    for entry in entries.flatten() {
+        if is_expired(&entry.file_name().to_string_lossy(), now) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Snapshots of `job_id`, oldest first
+fn load_snapshots(dir: &Path, job_id: &str) -> Vec<JobSnapshot> {
+    let prefix = format!("{job_id}_");
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<JobSnapshot> = entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|s| serde_json::from_str(&s).ok())
+        .collect();
+    snapshots.sort_by_key(|s| s.taken_at);
+    snapshots
+}
+
+fn snapshot_label(s: &JobSnapshot) -> String {
+    let loaded = s
+        .job
+        .statistic
+        .as_ref()
+        .map(|st| st.loaded_rows)
+        .unwrap_or(0);
+    format!(
+        "{} UTC  {}  loadedRows={}",
+        s.taken_at.format("%Y-%m-%d %H:%M:%S"),
+        s.job.state,
+        FormatHelper::fmt_int(loaded)
+    )
+}
+
+fn print_diff(diff: &SnapshotDiff) {
+    ui::print_info("");
+    ui::print_info(&format!("Elapsed: {}s", diff.elapsed_secs));
+    ui::print_info(&format!(
+        "{:<18} {:>18} {:>18} {:>16} {:>14}",
+        "Metric", "Before", "After", "Delta", "Per sec"
+    ));
+    ui::print_info(&"-".repeat(88));
+    for m in &diff.metrics {
+        ui::print_info(&format!(
+            "{:<18} {:>18} {:>18} {:>16} {:>14.2}",
+            m.name,
+            FormatHelper::fmt_int(m.before),
+            FormatHelper::fmt_int(m.after),
+            FormatHelper::fmt_signed(m.delta),
+            m.per_sec
+        ));
+    }
+
+    if diff.partitions.is_empty() {
+        return;
+    }
+    const SHOWN: usize = 30;
+    ui::print_info("");
+    ui::print_info(&format!(
+        "Partition lag change (largest first, {} of {}):",
+        diff.partitions.len().min(SHOWN),
+        diff.partitions.len()
+    ));
+    ui::print_info(&format!(
+        "{:<12} {:>14} {:>14} {:>14} {:>12}",
+        "Partition", "Before", "After", "Delta", "Per sec"
+    ));
+    ui::print_info(&"-".repeat(70));
+    let cell = |v: Option<i64>| v.map(|l| l.to_string()).unwrap_or_else(|| "-".into());
+    for p in diff.partitions.iter().take(SHOWN) {
+        ui::print_info(&format!(
+            "{:<12} {:>14} {:>14} {:>14} {:>12.2}",
+            p.partition,
+            cell(p.before),
+            cell(p.after),
+            FormatHelper::fmt_signed(p.delta),
+            p.per_sec
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn snapshot(at: &str, loaded: u64, lag: &[(&str, i64)]) -> JobSnapshot {
+        JobSnapshot {
+            taken_at: NaiveDateTime::parse_from_str(at, "%Y-%m-%d %H:%M:%S")
+                .unwrap()
+                .and_utc(),
+            job: RoutineLoadJob {
+                id: "10086".into(),
+                name: "orders_load".into(),
+                state: "RUNNING".into(),
+                db_name: "db".into(),
+                table_name: "orders".into(),
+                create_time: String::new(),
+                pause_time: None,
+                end_time: None,
+                current_task_num: None,
+                data_source_type: Some("KAFKA".into()),
+                statistic: Some(JobStatistic {
+                    received_bytes: loaded * 100,
+                    loaded_rows: loaded,
+                    error_rows: 0,
+                    committed_task_num: loaded / 1000,
+                    load_rows_rate: 0,
+                    aborted_task_num: 0,
+                    total_rows: loaded,
+                    unselected_rows: 0,
+                    received_bytes_rate: 0,
+                    task_execute_time_ms: 0,
+                }),
+                progress: None,
+                lag: Some(
+                    lag.iter()
+                        .map(|(k, v)| (k.to_string(), *v))
+                        .collect::<HashMap<_, _>>(),
+                ),
+                error_log_urls: None,
+                other_msg: None,
+            },
+        }
+    }
+
+    #[test]
+    fn compares_counters_and_partition_lag() {
+        let before = snapshot("2024-05-01 10:00:00", 10_000, &[("0", 100), ("1", 50)]);
+        let after = snapshot("2024-05-01 10:01:40", 60_000, &[("0", 20), ("2", 5)]);
+        let diff = compare_snapshots(&before, &after);
+
+        assert_eq!(diff.elapsed_secs, 100);
+        let loaded = &diff.metrics[0];
+        assert_eq!((loaded.name, loaded.delta), ("loadedRows", 50_000));
+        assert_eq!(loaded.per_sec, 500.0);
+
+        assert_eq!(diff.partitions[0].partition, "0");
+        assert_eq!(diff.partitions[0].delta, -80);
+        assert_eq!(diff.partitions[0].per_sec, -0.8);
+        let added = diff.partitions.iter().find(|p| p.partition == "2").unwrap();
+        assert_eq!((added.before, added.after), (None, Some(5)));
+
+        let json = serde_json::to_string(&after).unwrap();
+        let back: JobSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.file_name(), "10086_20240501_100140.json");
+    }
+
+    #[test]
+    fn expires_snapshots_after_retention() {
+        let now = NaiveDateTime::parse_from_str("2024-05-10 00:00:00", "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc();
+        assert!(is_expired("10086_20240501_100000.json", now));
+        assert!(!is_expired("10086_20240505_100000.json", now));
+        assert!(!is_expired("notes.txt", now));
+    }
+}
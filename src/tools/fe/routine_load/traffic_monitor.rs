@@ -1,86 +1,127 @@
-use chrono::Duration;
-use std::collections::BTreeMap;
+use chrono::{Duration, NaiveDateTime, Timelike};
 
-use super::job_manager::RoutineLoadJobManager;
 use super::log_parser::{FeLogParser, LogCommitEntry, scan_file};
 use crate::config::Config;
 use crate::error::{CliError, Result};
-use crate::tools::common::fs_utils;
-use crate::tools::fe::routine_load::messages as ErrMsg;
+use crate::tools::common::{file_name, fs_utils, log_scan};
+use crate::tools::context::{InteractiveContext, ToolContext};
 use crate::tools::{ExecutionResult, Tool};
 use crate::ui;
-use crate::ui::InputHelper;
+use crate::ui::describe_range;
+use crate::ui::sink::OutputSink;
 
 pub struct RoutineLoadTrafficMonitor;
 
+/// Per-minute traffic of one job over the analyzed window
+#[derive(Debug, Clone)]
+pub struct TrafficReport {
+    pub job_id: String,
+    pub range: (NaiveDateTime, NaiveDateTime),
+    /// Set when the newest log entry is far from the local clock
+    pub clock_note: Option<String>,
+    /// Every minute of the window, idle ones included
+    pub per_minute: Vec<MinuteTraffic>,
+}
+
 impl Tool for RoutineLoadTrafficMonitor {
     fn name(&self) -> &str {
         "routine_load_traffic_monitor"
     }
     fn description(&self) -> &str {
-        "Aggregate per-minute loadedRows from FE logs"
+        "Per-minute loadedRows and receivedBytes from FE logs, with CSV export"
     }
     fn requires_pid(&self) -> bool {
         false
     }
 
-    fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
-        let job_id = self.get_job_id()?;
-        let log_dir = self.get_log_directory()?;
+    fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult> {
+        self.execute_with_ctx(config, pid, &InteractiveContext)
+    }
 
-        let minutes = self.prompt_time_window()?;
+    fn execute_with_ctx(
+        &self,
+        config: &Config,
+        _pid: u32,
+        ctx: &dyn ToolContext,
+    ) -> Result<ExecutionResult> {
+        let report = self.analyze(config, ctx)?;
+        let out = ctx.sink();
 
-        ui::print_info(&format!(
-            "Analyzing traffic in {log_dir} for job {job_id} (last {minutes} min)...",
-            log_dir = log_dir.display(),
-            job_id = job_id,
-            minutes = minutes
+        out.info("");
+        out.info(&format!(
+            "Analyzed range: {}",
+            describe_range(report.range.0, report.range.1)
         ));
+        if let Some(note) = &report.clock_note {
+            out.info(note);
+        }
+        self.display_traffic_results(&report.per_minute, out);
 
-        let entries = self.collect_and_parse_logs(&log_dir, &job_id)?;
-
-        let filtered_entries = self.filter_entries_by_time_window(entries, minutes)?;
-
-        let per_minute_data = self.aggregate_per_minute(filtered_entries);
-
-        self.display_traffic_results(&per_minute_data)?;
+        let file_name =
+            file_name::timestamped(&format!("routine_load_traffic_{}", report.job_id), "csv");
+        let path = fs_utils::write_artifact(
+            config,
+            &file_name,
+            render_csv(&report.per_minute).as_bytes(),
+        )?;
 
-        Ok(ExecutionResult {
-            output_path: std::path::PathBuf::from("console_output"),
-            message: "Traffic monitor completed".into(),
-        })
+        Ok(ExecutionResult::new(
+            path,
+            format!(
+                "Traffic monitor completed ({} minutes)",
+                report.per_minute.len()
+            ),
+        ))
     }
 }
 
 impl RoutineLoadTrafficMonitor {
-    fn get_job_id(&self) -> Result<String> {
-        let job_manager = RoutineLoadJobManager;
-        job_manager
-            .get_current_job_id()
-            .ok_or_else(|| CliError::InvalidInput(ErrMsg::NO_JOB_ID.into()))
-    }
+    /// Scan the FE logs and bucket the job's commits per minute of the window `ctx` picks
+    pub fn analyze(&self, config: &Config, ctx: &dyn ToolContext) -> Result<TrafficReport> {
+        let job_id = ctx.job_id()?;
+        let log_dir = ctx.log_dir(config)?;
 
-    fn get_log_directory(&self) -> Result<std::path::PathBuf> {
-        let doris = crate::config_loader::load_config()?;
-        Ok(doris.log_dir)
-    }
+        ctx.sink().info(&format!(
+            "Scanning traffic in {} for job {job_id}...",
+            log_dir.display()
+        ));
+
+        let entries = self.collect_and_parse_logs(config, ctx, &log_dir, &job_id)?;
+
+        let latest = entries.iter().map(|e| e.timestamp).max().unwrap();
+        let clock_note = ui::report_log_clock_skew(latest, ctx.sink());
+        let first = entries.iter().map(|e| e.timestamp).min().unwrap();
+        let range = ctx.time_range(60, (first, latest))?.bounds(latest);
 
-    fn prompt_time_window(&self) -> Result<i64> {
-        InputHelper::prompt_number_with_default("Analyze recent minutes", 60, 1)
+        let filtered_entries = self.filter_entries_by_time_window(entries, range)?;
+
+        Ok(TrafficReport {
+            job_id,
+            range,
+            clock_note,
+            per_minute: aggregate_per_minute(&filtered_entries, range),
+        })
     }
 
     fn collect_and_parse_logs(
         &self,
+        config: &Config,
+        ctx: &dyn ToolContext,
         log_dir: &std::path::Path,
         job_id: &str,
     ) -> Result<Vec<LogCommitEntry>> {
         let files = fs_utils::collect_fe_logs(log_dir)?;
         let parser = FeLogParser::new();
-        let mut entries: Vec<LogCommitEntry> = Vec::new();
 
-        for path in files {
-            scan_file(&parser, &path, job_id, &mut entries)?;
-        }
+        let outcome = log_scan::scan_files_parallel(
+            &files,
+            config.log_scan_workers(),
+            !config.no_progress_animation && ctx.is_interactive(),
+            |path, progress| scan_file(&parser, path, job_id, progress),
+            |e| e.timestamp,
+        );
+        log_scan::report_scan_failures(&outcome.failures, ctx.sink());
+        let entries = outcome.entries;
 
         if entries.is_empty() {
             return Err(CliError::ToolExecutionFailed(
@@ -94,57 +135,192 @@ impl RoutineLoadTrafficMonitor {
     fn filter_entries_by_time_window(
         &self,
         mut entries: Vec<LogCommitEntry>,
-        minutes: i64,
+        (start, end): (NaiveDateTime, NaiveDateTime),
     ) -> Result<Vec<LogCommitEntry>> {
-        let latest_ts = entries.iter().map(|e| e.timestamp).max().unwrap();
-        let window_start = latest_ts - Duration::minutes(minutes);
-        entries.retain(|e| e.timestamp >= window_start);
+        entries.retain(|e| e.timestamp >= start && e.timestamp <= end);
 
         if entries.is_empty() {
-            return Err(CliError::ToolExecutionFailed(
-                "No entries in selected window".into(),
-            ));
+            return Err(CliError::ToolExecutionFailed(format!(
+                "No entries between {}",
+                describe_range(start, end)
+            )));
         }
 
         Ok(entries)
     }
 
-    fn aggregate_per_minute(&self, entries: Vec<LogCommitEntry>) -> BTreeMap<String, u128> {
-        let mut per_minute: BTreeMap<String, u128> = BTreeMap::new();
+    fn display_traffic_results(&self, per_minute: &[MinuteTraffic], out: &dyn OutputSink) {
+        // Room left after the "[i] " marker and the row label
+        let width = console::Term::stdout()
+            .size_checked()
+            .map_or(80, |(_, cols)| cols as usize)
+            .saturating_sub(16)
+            .max(10);
+        let rows: Vec<u128> = per_minute.iter().map(|m| m.rows).collect();
+        let bytes: Vec<u128> = per_minute.iter().map(|m| m.bytes).collect();
+        let per_column = per_minute.len().div_ceil(width).max(1);
 
-        for entry in entries {
-            let rows = entry.loaded_rows.unwrap_or(0) as u128;
-            let key = entry.timestamp.format("%H:%M").to_string();
-            *per_minute.entry(key).or_insert(0) += rows;
+        out.info(&format!("loadedRows    {}", render_sparkline(&rows, width)));
+        out.info(&format!(
+            "receivedBytes {}",
+            render_sparkline(&bytes, width)
+        ));
+        if let (Some(first), Some(last)) = (per_minute.first(), per_minute.last()) {
+            out.info(&format!(
+                "{} to {}, {per_column} minute(s) per column",
+                first.minute.format(MINUTE_FORMAT),
+                last.minute.format(MINUTE_FORMAT)
+            ));
         }
 
-        per_minute
+        let total_rows: u128 = rows.iter().sum();
+        let idle = per_minute.iter().filter(|m| m.commits == 0).count();
+        out.info(&"-".repeat(40));
+        out.info(&format!("Total minutes: {}", per_minute.len()));
+        out.info(&format!("Minutes without commits: {idle}"));
+        out.info(&format!("Total loadedRows: {total_rows}"));
+        out.info(&format!(
+            "Average per minute: {}",
+            total_rows / per_minute.len().max(1) as u128
+        ));
+        if let Some(peak) = per_minute.iter().max_by_key(|m| m.rows) {
+            out.info(&format!(
+                "Peak minute: {} loadedRows={}",
+                peak.minute.format(MINUTE_FORMAT),
+                peak.rows
+            ));
+        }
     }
+}
 
-    fn display_traffic_results(&self, per_minute_data: &BTreeMap<String, u128>) -> Result<()> {
-        ui::print_info("");
-        ui::print_info("Per-minute loadedRows (ascending time)");
-        ui::print_info(&"-".repeat(40));
+const MINUTE_FORMAT: &str = "%Y-%m-%d %H:%M";
 
-        for (minute, rows) in per_minute_data.iter() {
-            ui::print_info(&format!("{minute} loadedRows={rows}"));
-        }
+/// Commits landing in one minute of the analyzed window
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinuteTraffic {
+    pub minute: NaiveDateTime,
+    pub rows: u128,
+    pub bytes: u128,
+    pub commits: usize,
+}
+
+fn truncate_to_minute(ts: NaiveDateTime) -> NaiveDateTime {
+    ts.with_second(0)
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(ts)
+}
+
+/// Every minute of `start..=end`, with explicit zero rows for minutes without commits
+fn aggregate_per_minute(
+    entries: &[LogCommitEntry],
+    (start, end): (NaiveDateTime, NaiveDateTime),
+) -> Vec<MinuteTraffic> {
+    let start = truncate_to_minute(start);
+    let minutes = (truncate_to_minute(end) - start).num_minutes().max(0) as usize + 1;
+    let mut per_minute: Vec<MinuteTraffic> = (0..minutes)
+        .map(|i| MinuteTraffic {
+            minute: start + Duration::minutes(i as i64),
+            rows: 0,
+            bytes: 0,
+            commits: 0,
+        })
+        .collect();
+
+    for entry in entries {
+        let offset = (truncate_to_minute(entry.timestamp) - start).num_minutes();
+        let Some(slot) = usize::try_from(offset)
+            .ok()
+            .and_then(|i| per_minute.get_mut(i))
+        else {
+            continue;
+        };
+        slot.rows += u128::from(entry.loaded_rows.unwrap_or(0));
+        slot.bytes += u128::from(entry.received_bytes.unwrap_or(0));
+        slot.commits += 1;
+    }
+    per_minute
+}
 
-        let total_rows: u128 = per_minute_data.values().sum();
-        ui::print_info(&"-".repeat(40));
-        ui::print_info(&format!(
-            "Total minutes: {count}",
-            count = per_minute_data.len()
+fn render_csv(per_minute: &[MinuteTraffic]) -> String {
+    let mut csv = String::from("minute,loaded_rows,received_bytes,commits\n");
+    for m in per_minute {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            m.minute.format(MINUTE_FORMAT),
+            m.rows,
+            m.bytes,
+            m.commits
         ));
-        ui::print_info(&format!("Total loadedRows: {total_rows}"));
+    }
+    csv
+}
 
-        let avg_rows = if !per_minute_data.is_empty() {
-            total_rows / per_minute_data.len() as u128
-        } else {
-            0
+/// One character per column, each the peak of the minutes it covers.
+///
+/// Columns without traffic stay blank so gaps stand out; any traffic at all
+/// gets at least the lowest bar.
+fn render_sparkline(values: &[u128], width: usize) -> String {
+    let levels: &[char] = if ui::plain_output() {
+        &['.', ':', '-', '=', '+', '*', '#', '@']
+    } else {
+        &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█']
+    };
+    let per_column = values.len().div_ceil(width.max(1)).max(1);
+    let peaks: Vec<u128> = values
+        .chunks(per_column)
+        .map(|c| c.iter().copied().max().unwrap_or(0))
+        .collect();
+    let max = peaks.iter().copied().max().unwrap_or(0);
+    peaks
+        .into_iter()
+        .map(|v| {
+            if v == 0 {
+                ' '
+            } else {
+                let top = levels.len() as u128 - 1;
+                levels[(v * top / max) as usize]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(raw: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn zero_fills_idle_minutes_and_shows_gaps() {
+        let entry = |ts: &str, rows: u64| LogCommitEntry {
+            timestamp: at(ts),
+            loaded_rows: Some(rows),
+            received_bytes: Some(rows * 100),
+            task_execution_ms: None,
+            transaction_id: None,
         };
-        ui::print_info(&format!("Average per minute: {avg_rows}"));
+        let entries = [
+            entry("2024-05-01 23:58:10", 10),
+            entry("2024-05-01 23:58:50", 30),
+            entry("2024-05-02 00:01:05", 5),
+        ];
+        let window = (at("2024-05-01 23:57:30"), at("2024-05-02 00:01:59"));
+        let per_minute = aggregate_per_minute(&entries, window);
+        let rows: Vec<u128> = per_minute.iter().map(|m| m.rows).collect();
+        assert_eq!(rows, [0, 40, 0, 0, 5]);
+        assert_eq!(per_minute[1].commits, 2);
+
+        let csv = render_csv(&per_minute);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "minute,loaded_rows,received_bytes,commits");
+        assert_eq!(lines[2], "2024-05-01 23:58,40,4000,2");
+        assert_eq!(lines[3], "2024-05-01 23:59,0,0,0");
+        assert_eq!(lines.len(), 6);
 
-        Ok(())
+        assert_eq!(render_sparkline(&rows, 80), " █  ▁");
+        // Two minutes per column, each column showing its busier minute
+        assert_eq!(render_sparkline(&rows, 3), "█ ▁");
     }
 }
@@ -0,0 +1,469 @@
+use dialoguer::History;
+use dialoguer::theme::ColorfulTheme;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::replica_health::ProcTable;
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::tools::common::{file_name, fs_utils};
+use crate::tools::mysql::MySQLTool;
+use crate::tools::mysql::parser::{parse_key_value, parse_key_value_pairs, split_into_blocks};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::table::{TableBuilder, display_width};
+
+const HISTORY_SIZE: usize = 100;
+const MAX_CELL_WIDTH: usize = 60;
+/// Terminal height assumed when it cannot be read
+const DEFAULT_SCREEN_ROWS: usize = 24;
+
+/// Statements typed in the console this session, newest first
+static HISTORY: Lazy<Mutex<StatementHistory>> =
+    Lazy::new(|| Mutex::new(StatementHistory::default()));
+
+/// Whole statements flattened to one line, so up-arrow brings back a complete statement
+/// instead of one line of it
+#[derive(Debug, Default)]
+struct StatementHistory {
+    entries: VecDeque<String>,
+}
+
+impl StatementHistory {
+    fn record(&mut self, statement: &str) {
+        let line = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() || self.entries.front() == Some(&line) {
+            return;
+        }
+        self.entries.push_front(line);
+        self.entries.truncate(HISTORY_SIZE);
+    }
+}
+
+impl History<String> for StatementHistory {
+    fn read(&self, pos: usize) -> Option<String> {
+        self.entries.get(pos).cloned()
+    }
+
+    // Single lines are not kept; complete statements go in through `record`
+    fn write(&mut self, _val: &String) {}
+}
+
+/// Statement types the console runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementKind {
+    Select,
+    Show,
+    Describe,
+    Explain,
+}
+
+/// A statement that passed [`check_statement`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleStatement {
+    pub kind: StatementKind,
+    /// Statement text without its terminator
+    pub sql: String,
+    /// Ended with `\G`
+    pub vertical: bool,
+}
+
+impl ConsoleStatement {
+    fn to_query(&self) -> String {
+        if self.vertical {
+            format!("{} \\G", self.sql)
+        } else {
+            self.sql.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Semicolon,
+    /// `\G`
+    Vertical,
+    Other,
+}
+
+/// Tokens of `sql` with comments, quoted strings and quoted identifiers skipped,
+/// each token paired with its byte offset
+fn tokenize(sql: &str) -> Vec<(usize, Token)> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.char_indices().peekable();
+    while let Some((at, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '\'' | '"' | '`' => {
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' && c != '`' {
+                        chars.next();
+                    } else if next == c {
+                        break;
+                    }
+                }
+                tokens.push((at, Token::Other));
+            }
+            '#' => {
+                for (_, next) in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '-' if sql[at..].starts_with("--")
+                && sql[at + 2..].chars().next().is_none_or(char::is_whitespace) =>
+            {
+                for (_, next) in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if sql[at..].starts_with("/*") => {
+                chars.next();
+                let mut prev = ' ';
+                for (_, next) in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            ';' => tokens.push((at, Token::Semicolon)),
+            '\\' if matches!(chars.peek(), Some((_, 'G' | 'g'))) => {
+                chars.next();
+                tokens.push((at, Token::Vertical));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = at + c.len_utf8();
+                while let Some(&(i, next)) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    end = i + next.len_utf8();
+                    chars.next();
+                }
+                tokens.push((at, Token::Word(sql[at..end].to_ascii_uppercase())));
+            }
+            _ => tokens.push((at, Token::Other)),
+        }
+    }
+    tokens
+}
+
+/// Whether `buffer` holds a statement ended by `;` or `\G`
+fn is_complete(buffer: &str) -> bool {
+    matches!(
+        tokenize(buffer).last(),
+        Some((_, Token::Semicolon | Token::Vertical))
+    )
+}
+
+fn refused(reason: impl Into<String>) -> CliError {
+    CliError::InvalidInput(reason.into())
+}
+
+/// One read-only statement from `input`, judged by its statement type rather than
+/// its first characters. `SELECT ... INTO OUTFILE`, several statements and anything
+/// but SELECT, SHOW, DESC and EXPLAIN are refused.
+pub fn check_statement(input: &str) -> Result<ConsoleStatement> {
+    let mut tokens = tokenize(input);
+    let mut vertical = false;
+    let mut end = input.len();
+    while let Some((at, last)) = tokens.last() {
+        match last {
+            Token::Semicolon => {}
+            Token::Vertical if !vertical => vertical = true,
+            _ => break,
+        }
+        end = *at;
+        tokens.pop();
+    }
+    if tokens
+        .iter()
+        .any(|(_, t)| matches!(t, Token::Semicolon | Token::Vertical))
+    {
+        return Err(refused("run one statement at a time"));
+    }
+
+    let words: Vec<&str> = tokens
+        .iter()
+        .filter_map(|(_, t)| match t {
+            Token::Word(w) => Some(w.as_str()),
+            _ => None,
+        })
+        .collect();
+    let Some(first) = words.first() else {
+        return Err(refused("empty statement"));
+    };
+    let kind = match *first {
+        "SELECT" | "WITH" => StatementKind::Select,
+        "SHOW" => StatementKind::Show,
+        "DESC" | "DESCRIBE" => StatementKind::Describe,
+        "EXPLAIN" => StatementKind::Explain,
+        other => {
+            return Err(refused(format!(
+                "{other} statements are not allowed; the console runs SELECT, SHOW, DESC and EXPLAIN"
+            )));
+        }
+    };
+    if kind == StatementKind::Select {
+        // WITH may front an INSERT, and SELECT ... INTO OUTFILE exports data
+        if let Some(word) = words
+            .iter()
+            .find(|w| ["INTO", "OUTFILE", "INSERT", "UPDATE", "DELETE"].contains(w))
+        {
+            return Err(refused(format!(
+                "{word} is not allowed in the console; it only reads"
+            )));
+        }
+        if !words.contains(&"SELECT") {
+            return Err(refused("WITH must be followed by a SELECT"));
+        }
+    }
+
+    Ok(ConsoleStatement {
+        kind,
+        sql: input[..end].trim().to_string(),
+        vertical,
+    })
+}
+
+/// `\G` rows with their fields aligned, in the order the server sent them
+fn render_vertical(output: &str) -> (String, usize) {
+    let blocks = split_into_blocks(output);
+    let mut out = String::new();
+    for block in &blocks {
+        let fields = parse_key_value_pairs(block);
+        let mut keys: Vec<String> = Vec::new();
+        for (key, _) in block.lines().filter_map(parse_key_value) {
+            if fields.contains_key(&key) && !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        let width = keys.iter().map(|k| display_width(k)).max().unwrap_or(0);
+        if let Some(separator) = block.lines().find(|l| l.contains("***")) {
+            out.push_str(separator.trim());
+            out.push('\n');
+        }
+        for key in keys {
+            let pad = " ".repeat(width - display_width(&key));
+            let value = fields[&key].replace('\n', &format!("\n{}", " ".repeat(width + 2)));
+            out.push_str(&format!("{pad}{key}: {value}\n"));
+        }
+    }
+    (out, blocks.len())
+}
+
+/// Tab-separated output as a table when it fits `columns`, as is otherwise
+fn render_tabular(output: &str, columns: usize) -> (String, usize) {
+    let table = ProcTable::parse(output);
+    let rows = table.rows.len();
+    if table.headers.is_empty() {
+        return (String::new(), 0);
+    }
+    if table.rows.iter().any(|r| r.len() != table.headers.len()) {
+        return (output.to_string(), rows);
+    }
+
+    let mut builder = TableBuilder::new(table.headers.iter().cloned());
+    for i in 0..table.headers.len() {
+        builder = builder.max_width(i, MAX_CELL_WIDTH);
+    }
+    for row in &table.rows {
+        builder.push_row(row.iter().cloned());
+    }
+    let rendered = builder.render();
+    if rendered.lines().any(|l| display_width(l) > columns) {
+        let mut raw = output.to_string();
+        raw.push_str("(too wide for a table; end the statement with \\G for one field per line)\n");
+        return (raw, rows);
+    }
+    (rendered, rows)
+}
+
+/// Result text and row count, picked by the shape of the output
+fn render_result(output: &str, columns: usize) -> (String, usize) {
+    if output.contains("***************************") {
+        render_vertical(output)
+    } else {
+        render_tabular(output, columns)
+    }
+}
+
+fn screen_size() -> (usize, usize) {
+    console::Term::stdout()
+        .size_checked()
+        .map(|(rows, cols)| (rows as usize, cols as usize))
+        .unwrap_or((DEFAULT_SCREEN_ROWS, 120))
+}
+
+/// Lines up to one ending with `;` or `\G`; `None` when the user leaves the console
+fn read_statement() -> Result<Option<String>> {
+    let mut buffer = String::new();
+    loop {
+        let prompt = if buffer.is_empty() { "sql>" } else { "  ->" };
+        let mut history = HISTORY.lock().unwrap_or_else(|e| e.into_inner());
+        let line: String = dialoguer::Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .allow_empty(true)
+            .history_with(&mut *history)
+            .interact_text()
+            .map_err(|e| CliError::prompt_failed("SQL input failed", e))?;
+        drop(history);
+
+        let trimmed = line.trim();
+        if buffer.is_empty() {
+            if trimmed.is_empty() || ["exit", "quit", "\\q"].contains(&trimmed) {
+                return Ok(None);
+            }
+        } else if trimmed.is_empty() {
+            return Ok(Some(buffer));
+        }
+        if !buffer.is_empty() {
+            buffer.push('\n');
+        }
+        buffer.push_str(&line);
+        if is_complete(&buffer) {
+            return Ok(Some(buffer));
+        }
+    }
+}
+
+/// Ad-hoc SELECT/SHOW/DESC/EXPLAIN against the configured FE
+pub struct FeSqlConsoleTool;
+
+impl Tool for FeSqlConsoleTool {
+    fn name(&self) -> &str {
+        "fe-sql-console"
+    }
+
+    fn description(&self) -> &str {
+        "Run read-only SQL (SELECT, SHOW, DESC, EXPLAIN) without leaving the tool"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        ui::terminal::require_terminal("The SQL console")?;
+        let doris = crate::config_loader::load_config()?;
+        ui::print_info(
+            "End statements with ';' or '\\G'. Up-arrow recalls earlier statements; an empty line or 'exit' leaves.",
+        );
+
+        let mut executed = 0;
+        let mut saved: Option<PathBuf> = None;
+        while let Some(input) = read_statement()? {
+            HISTORY
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .record(&input);
+            let statement = match check_statement(&input) {
+                Ok(statement) => statement,
+                Err(e) => {
+                    ui::print_error(&format!("Refused: {e}"));
+                    continue;
+                }
+            };
+            // The client's read-only guard still applies to what gets through here
+            let output = match MySQLTool::query_sql_with_config(&doris, &statement.to_query()) {
+                Ok(output) => output,
+                Err(e) => {
+                    ui::print_error(&format!("Query failed: {e}"));
+                    continue;
+                }
+            };
+            executed += 1;
+
+            let (rows, columns) = screen_size();
+            let (text, count) = render_result(&output, columns);
+            println!();
+            print!("{text}");
+            ui::print_info(&format!("{count} row(s)"));
+
+            if text.lines().count() + 2 > rows
+                && ui::ask_continue("Output is longer than one screen. Save it to a file?")?
+            {
+                let path = fs_utils::write_artifact(
+                    config,
+                    &file_name::timestamped("sql_console", "txt"),
+                    format!("{}\n\n{text}", statement.to_query()).as_bytes(),
+                )?;
+                ui::print_success(&format!("Saved to {}", path.display()));
+                saved = Some(path);
+            }
+        }
+
+        let message = format!("{executed} statement(s) run in the SQL console");
+        Ok(match saved {
+            Some(path) => ExecutionResult::new(path, message),
+            None => ExecutionResult::console(message),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_reads_by_statement_type() {
+        let s = check_statement("select * from t where note = 'a;b' \\G").unwrap();
+        assert_eq!(s.kind, StatementKind::Select);
+        assert_eq!(s.sql, "select * from t where note = 'a;b'");
+        assert!(s.vertical);
+        assert_eq!(s.to_query(), "select * from t where note = 'a;b' \\G");
+
+        let s = check_statement("/* hint */ SHOW BACKENDS;").unwrap();
+        assert_eq!((s.kind, s.vertical), (StatementKind::Show, false));
+        assert_eq!(
+            check_statement("-- why\nDESC db.t;").unwrap().kind,
+            StatementKind::Describe
+        );
+        assert_eq!(
+            check_statement("WITH x AS (SELECT 1) SELECT * FROM x")
+                .unwrap()
+                .kind,
+            StatementKind::Select
+        );
+
+        for refused in [
+            "SELECT 1; DROP TABLE t",
+            "select * from t into outfile 's3://bucket/x'",
+            "WITH x AS (SELECT 1) INSERT INTO t SELECT * FROM x",
+            "/* SELECT */ DELETE FROM t",
+            "ADMIN SET FRONTEND CONFIG ('a' = 'b')",
+            "  ;",
+        ] {
+            assert!(check_statement(refused).is_err(), "{refused}");
+        }
+
+        assert!(is_complete("SELECT 1\n;"));
+        assert!(!is_complete("SELECT ';'"));
+        assert!(!is_complete("SELECT 1 -- ;"));
+
+        let mut history = StatementHistory::default();
+        history.record("SELECT 1\nFROM t;");
+        history.record("SELECT 1 FROM t;");
+        history.record("SHOW FRONTENDS;");
+        assert_eq!(history.read(0).as_deref(), Some("SHOW FRONTENDS;"));
+        assert_eq!(history.read(1).as_deref(), Some("SELECT 1 FROM t;"));
+        assert_eq!(history.read(2), None);
+
+        let (table, rows) = render_result("Host\tAlive\n10.0.0.1\ttrue\n", 120);
+        assert_eq!(rows, 1);
+        assert!(table.contains("10.0.0.1"));
+        let vertical = "*************************** 1. row ***************************\n\
+                 Host: 10.0.0.1\n\
+            ErrMsg: \n";
+        let (text, rows) = render_result(vertical, 120);
+        assert_eq!(rows, 1);
+        assert!(text.contains("  Host: 10.0.0.1\nErrMsg: \n"));
+    }
+}
@@ -0,0 +1,485 @@
+use super::{BUCKET_SKEW_THRESHOLD, BucketCount, PartitionStat, TableInfoReport, TableModel};
+use crate::tools::common::format_utils::format_bytes;
+use chrono::{Datelike, Months, NaiveDate};
+
+const MB: u64 = 1024 * 1024;
+const GB: u64 = 1024 * MB;
+
+/// Buckets this small mean too many tablets for the data they hold
+pub const BUCKET_TOO_SMALL_BYTES: u64 = 100 * MB;
+/// Buckets above this compact and balance slowly
+pub const BUCKET_TOO_LARGE_BYTES: u64 = 10 * GB;
+/// Random bucketing loses bucket pruning beyond this table size
+pub const LARGE_TABLE_BYTES: u64 = 100 * GB;
+/// Partitions this many times the median partition size stand out
+pub const OVERSIZED_FACTOR: u64 = 10;
+/// Share of partition names that must parse as dates before gaps are reported
+const DATE_NAMED_SHARE: f64 = 0.8;
+/// Partitions named as examples in a finding
+const EXAMPLES: usize = 3;
+
+fn listed(items: Vec<String>) -> String {
+    let total = items.len();
+    let mut shown: Vec<String> = items.into_iter().take(EXAMPLES).collect();
+    if total > EXAMPLES {
+        shown.push(format!("+{} more", total - EXAMPLES));
+    }
+    shown.join(", ")
+}
+
+fn examples(parts: &[&PartitionStat]) -> String {
+    listed(
+        parts
+            .iter()
+            .map(|p| {
+                format!(
+                    "{} {}x{}",
+                    p.name,
+                    p.buckets,
+                    format_bytes(p.avg_bucket_size_bytes.unwrap_or(0), 1, false)
+                )
+            })
+            .collect(),
+    )
+}
+
+fn sized(parts: &[&PartitionStat]) -> String {
+    listed(
+        parts
+            .iter()
+            .map(|p| format!("{} {}", p.name, format_bytes(p.size_bytes, 1, false)))
+            .collect(),
+    )
+}
+
+/// What makes one partition stand out, see [`partition_anomalies`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PartitionAnomaly {
+    /// Zero rows but data on disk: delete tombstones or garbage not compacted away
+    pub empty_with_data: bool,
+    /// Over [`OVERSIZED_FACTOR`] times the median partition size
+    pub oversized: bool,
+    /// Date-named partition that follows missing days or months
+    pub after_gap: bool,
+}
+
+/// Marks and their meaning, shown under the partition table
+pub const ANOMALY_LEGEND: [(&str, &str); 3] = [
+    ("*", "zero rows but holds data"),
+    ("!", "over 10x the median partition size"),
+    ("~", "follows a gap in the date sequence"),
+];
+
+impl PartitionAnomaly {
+    pub fn marks(&self) -> String {
+        [self.empty_with_data, self.oversized, self.after_gap]
+            .iter()
+            .zip(ANOMALY_LEGEND)
+            .filter(|(set, _)| **set)
+            .map(|(_, (mark, _))| mark)
+            .collect()
+    }
+}
+
+/// Median partition size, ignoring empty partitions
+fn median_size(parts: &[PartitionStat]) -> Option<u64> {
+    let mut sizes: Vec<u64> = parts
+        .iter()
+        .map(|p| p.size_bytes)
+        .filter(|&s| s > 0)
+        .collect();
+    if sizes.len() < 3 {
+        return None;
+    }
+    sizes.sort_unstable();
+    Some(sizes[sizes.len() / 2])
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DateUnit {
+    Day,
+    Month,
+}
+
+/// Date in a partition name such as `p20240115`, `p2024_01_15` or `p202401`
+fn partition_date(name: &str) -> Option<(DateUnit, NaiveDate)> {
+    let digits: String = name.chars().filter(char::is_ascii_digit).collect();
+    match digits.len() {
+        8 => NaiveDate::parse_from_str(&digits, "%Y%m%d")
+            .ok()
+            .map(|d| (DateUnit::Day, d)),
+        6 => NaiveDate::parse_from_str(&format!("{digits}01"), "%Y%m%d")
+            .ok()
+            .map(|d| (DateUnit::Month, d)),
+        _ => None,
+    }
+}
+
+/// Days or months missing between consecutive date-named partitions
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateGap {
+    pub first_missing: NaiveDate,
+    pub missing: i64,
+    /// Index of the partition right after the gap
+    pub before: usize,
+    pub unit_name: &'static str,
+}
+
+/// Gaps in the date sequence of range partitions. Only looked for when at least
+/// 80% of the names are dates of one unit, so list partitions are left alone.
+pub fn date_gaps(parts: &[PartitionStat]) -> Vec<DateGap> {
+    let dated: Vec<(usize, DateUnit, NaiveDate)> = parts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, p)| partition_date(&p.name).map(|(unit, d)| (i, unit, d)))
+        .collect();
+    let Some(unit) = [DateUnit::Day, DateUnit::Month]
+        .into_iter()
+        .max_by_key(|u| dated.iter().filter(|(_, unit, _)| unit == u).count())
+    else {
+        return Vec::new();
+    };
+    let mut dated: Vec<(usize, NaiveDate)> = dated
+        .into_iter()
+        .filter(|(_, u, _)| *u == unit)
+        .map(|(i, _, d)| (i, d))
+        .collect();
+    if dated.len() < 3 || (dated.len() as f64) < parts.len() as f64 * DATE_NAMED_SHARE {
+        return Vec::new();
+    }
+    dated.sort_by_key(|(_, d)| *d);
+    dated.dedup_by_key(|(_, d)| *d);
+
+    let month_index = |d: NaiveDate| i64::from(d.year()) * 12 + i64::from(d.month0());
+    dated
+        .windows(2)
+        .filter_map(|w| {
+            let ((_, prev), (index, next)) = (w[0], w[1]);
+            let (missing, first_missing, unit_name) = match unit {
+                DateUnit::Day => ((next - prev).num_days() - 1, prev.succ_opt()?, "day"),
+                DateUnit::Month => (
+                    month_index(next) - month_index(prev) - 1,
+                    prev.checked_add_months(Months::new(1))?,
+                    "month",
+                ),
+            };
+            (missing > 0).then_some(DateGap {
+                first_missing,
+                missing,
+                before: index,
+                unit_name,
+            })
+        })
+        .collect()
+}
+
+/// Per partition, in order: empty-with-data, oversized and after-gap marks.
+///
+/// Doris 2.x reports no row counts, so the empty check only runs when some
+/// partition of the table has rows.
+pub fn partition_anomalies(parts: &[PartitionStat]) -> Vec<PartitionAnomaly> {
+    let rows_known = parts.iter().any(|p| p.rows > 0);
+    let median = median_size(parts);
+    let mut out: Vec<PartitionAnomaly> = parts
+        .iter()
+        .map(|p| PartitionAnomaly {
+            empty_with_data: rows_known && p.rows == 0 && p.size_bytes > 0,
+            oversized: median.is_some_and(|m| p.size_bytes > m * OVERSIZED_FACTOR),
+            after_gap: false,
+        })
+        .collect();
+    for gap in date_gaps(parts) {
+        out[gap.before].after_gap = true;
+    }
+    out
+}
+
+fn partition_findings(parts: &[PartitionStat]) -> Vec<String> {
+    let mut out = Vec::new();
+    let anomalies = partition_anomalies(parts);
+    let flagged = |pick: fn(&PartitionAnomaly) -> bool| -> Vec<&PartitionStat> {
+        parts
+            .iter()
+            .zip(&anomalies)
+            .filter(|(_, a)| pick(a))
+            .map(|(p, _)| p)
+            .collect()
+    };
+
+    let empty = flagged(|a| a.empty_with_data);
+    if !empty.is_empty() {
+        out.push(format!(
+            "{} partition(s) have zero rows but hold data ({}); likely delete tombstones or garbage awaiting compaction",
+            empty.len(),
+            sized(&empty)
+        ));
+    }
+
+    let oversized = flagged(|a| a.oversized);
+    if let (false, Some(median)) = (oversized.is_empty(), median_size(parts)) {
+        out.push(format!(
+            "{} partition(s) are over {OVERSIZED_FACTOR}x the median partition size of {} ({}); check for skewed loads or too coarse partitioning",
+            oversized.len(),
+            format_bytes(median, 1, false),
+            sized(&oversized)
+        ));
+    }
+
+    let gaps = date_gaps(parts);
+    if let Some(first) = gaps.first() {
+        let missing: i64 = gaps.iter().map(|g| g.missing).sum();
+        out.push(format!(
+            "Date partitions skip {missing} {}(s) in {} gap(s) ({}); check dynamic partition creation or dropped partitions",
+            first.unit_name,
+            gaps.len(),
+            listed(
+                gaps.iter()
+                    .map(|g| format!("{} before {}", g.first_missing, parts[g.before].name))
+                    .collect()
+            )
+        ));
+    }
+    out
+}
+
+/// Bucket sizing, partition anomaly and table design findings, aiming for
+/// 1-10 GB per bucket
+pub fn recommendations(report: &TableInfoReport) -> Vec<String> {
+    let mut out = Vec::new();
+
+    // A single bucket cannot shrink further, and empty partitions say nothing yet
+    let too_small: Vec<&PartitionStat> = report
+        .partitions
+        .iter()
+        .filter(|p| p.buckets > 1 && p.size_bytes > 0)
+        .filter(|p| {
+            p.avg_bucket_size_bytes
+                .is_some_and(|b| b < BUCKET_TOO_SMALL_BYTES)
+        })
+        .collect();
+    if !too_small.is_empty() {
+        out.push(format!(
+            "{} partition(s) have buckets under 100 MB ({}); lower the bucket count toward 1-10 GB per bucket",
+            too_small.len(),
+            examples(&too_small)
+        ));
+    }
+
+    let too_large: Vec<&PartitionStat> = report
+        .partitions
+        .iter()
+        .filter(|p| {
+            p.avg_bucket_size_bytes
+                .is_some_and(|b| b > BUCKET_TOO_LARGE_BYTES)
+        })
+        .collect();
+    if !too_large.is_empty() {
+        out.push(format!(
+            "{} partition(s) have buckets over 10 GB ({}); raise the bucket count",
+            too_large.len(),
+            examples(&too_large)
+        ));
+    }
+
+    let total: u64 = report.partitions.iter().map(|p| p.size_bytes).sum();
+    if report.bucketing_key.is_none() && total > LARGE_TABLE_BYTES {
+        out.push(format!(
+            "RANDOM bucketing on a {} table; point and range queries cannot prune buckets, consider HASH on a filter column",
+            format_bytes(total, 1, false)
+        ));
+    }
+
+    if matches!(report.bucket, BucketCount::Auto) {
+        let tiny = report
+            .partitions
+            .iter()
+            .filter(|p| p.size_bytes < BUCKET_TOO_SMALL_BYTES)
+            .count();
+        if tiny > 0 && tiny * 2 >= report.partitions.len() && report.partitions.len() > 1 {
+            out.push(format!(
+                "AUTO buckets with {tiny} of {} partitions under 100 MB; use a small fixed bucket count or coarser partitions",
+                report.partitions.len()
+            ));
+        }
+    }
+
+    out.extend(partition_findings(&report.partitions));
+
+    if matches!(report.model, TableModel::UniqueKey) && report.merge_on_write != Some(true) {
+        out.push(
+            "Unique Key table without merge-on-write; enable \"enable_unique_key_merge_on_write\" for faster queries (requires recreating the table)"
+                .to_string(),
+        );
+    }
+
+    out
+}
+
+/// How rows are spread over buckets, as written in the DDL
+pub fn bucketing_clause(report: &TableInfoReport) -> String {
+    match &report.bucketing_key {
+        Some(cols) => format!("HASH({})", cols.join(", ")),
+        None => "RANDOM".to_string(),
+    }
+}
+
+/// Partitions whose largest tablet is over [`BUCKET_SKEW_THRESHOLD`] times
+/// the median, with the bucketing that put the rows there
+pub fn bucket_skew_warning(report: &TableInfoReport) -> Option<String> {
+    let skewed: Vec<String> = report
+        .bucket_skew
+        .iter()
+        .filter(|s| s.is_skewed())
+        .map(|s| s.partition.clone())
+        .collect();
+    if skewed.is_empty() {
+        return None;
+    }
+    let advice = match report.bucketing_key {
+        Some(_) => {
+            "a few key values hold most rows; a higher-cardinality column spreads them evenly"
+        }
+        None => {
+            "random bucketing writes a load batch to few tablets, so uneven batches leave uneven tablets"
+        }
+    };
+    Some(format!(
+        "max tablet over {BUCKET_SKEW_THRESHOLD:.0}x the median in {} under DISTRIBUTED BY {}; {advice}",
+        listed(skewed),
+        bucketing_clause(report)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::fe::table_info::{PartitionBucketSkew, TableIdentity};
+
+    fn partition(name: &str, size_bytes: u64, buckets: u32) -> PartitionStat {
+        PartitionStat {
+            name: name.to_string(),
+            size_bytes,
+            rows: 0,
+            buckets,
+            avg_bucket_size_bytes: (buckets > 0).then(|| size_bytes / buckets as u64),
+        }
+    }
+
+    #[test]
+    fn flags_bucket_sizes_and_table_design() {
+        let mut report = TableInfoReport {
+            ident: TableIdentity {
+                schema: "db".into(),
+                name: "t".into(),
+            },
+            model: TableModel::UniqueKey,
+            key_columns: vec!["id".into()],
+            bucketing_key: Some(vec!["id".into()]),
+            bucket: BucketCount::Fixed(16),
+            merge_on_write: Some(true),
+            indexes: vec![],
+            columns: vec![],
+            partitions: vec![
+                partition("p1", 16 * 50 * MB, 16),
+                partition("p2", 16 * 2 * GB, 16),
+                partition("p3", 0, 16),
+            ],
+            tablet_distribution: vec![],
+            recommendations: vec![],
+            properties: Default::default(),
+            rollups: vec![],
+            async_mvs: None,
+            bucket_skew: vec![],
+        };
+        let found = recommendations(&report);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("1 partition(s) have buckets under 100 MB (p1 16x50.0 MB)"));
+
+        report.partitions = vec![partition("p1", 12 * 20 * GB, 12)];
+        report.bucketing_key = None;
+        report.merge_on_write = Some(false);
+        let found = recommendations(&report);
+        assert_eq!(found.len(), 3);
+        assert!(found[0].contains("over 10 GB"));
+        assert!(found[1].starts_with("RANDOM bucketing"));
+        assert!(found[2].contains("merge-on-write"));
+
+        report = TableInfoReport {
+            model: TableModel::DuplicateKey,
+            bucket: BucketCount::Auto,
+            partitions: vec![partition("p1", MB, 1), partition("p2", 2 * MB, 1)],
+            ..report
+        };
+        let found = recommendations(&report);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("AUTO buckets with 2 of 2 partitions"));
+
+        let skew = |partition: &str, max_bytes: u64| PartitionBucketSkew {
+            partition: partition.into(),
+            tablets: 16,
+            max_bytes,
+            median_bytes: GB,
+            min_bytes: GB / 2,
+        };
+        report.bucketing_key = Some(vec!["user_id".into()]);
+        report.bucket_skew = vec![skew("p1", 2 * GB)];
+        assert_eq!(bucket_skew_warning(&report), None);
+        report.bucket_skew.push(skew("p2", 5 * GB));
+        assert!(bucket_skew_warning(&report).unwrap().starts_with(
+            "max tablet over 3x the median in p2 under DISTRIBUTED BY HASH(user_id);"
+        ));
+    }
+
+    #[test]
+    fn flags_empty_oversized_and_missing_date_partitions() {
+        let with_rows = |name: &str, size_bytes: u64, rows: u64| PartitionStat {
+            rows,
+            ..partition(name, size_bytes, 1)
+        };
+        let parts = vec![
+            with_rows("p20240101", GB, 10),
+            with_rows("p20240102", GB, 10),
+            with_rows("p20240105", 20 * GB, 10),
+            with_rows("p20240106", MB, 0),
+            with_rows("p_other", 0, 0),
+        ];
+        let anomalies = partition_anomalies(&parts);
+        let marks: Vec<String> = anomalies.iter().map(PartitionAnomaly::marks).collect();
+        assert_eq!(marks, ["", "", "!~", "*", ""]);
+
+        let gaps = date_gaps(&parts);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].missing, 2);
+        assert_eq!(gaps[0].first_missing.to_string(), "2024-01-03");
+
+        let found = partition_findings(&parts);
+        assert_eq!(found.len(), 3);
+        assert!(found[0].starts_with("1 partition(s) have zero rows but hold data (p20240106"));
+        assert!(found[1].contains("over 10x the median partition size of 1.0 GB"));
+        assert!(found[2].starts_with(
+            "Date partitions skip 2 day(s) in 1 gap(s) (2024-01-03 before p20240105)"
+        ));
+
+        // Under 80% date names: list partitions are not checked for gaps
+        let list = vec![
+            with_rows("p202401", GB, 1),
+            with_rows("p202404", GB, 1),
+            with_rows("p_bj", GB, 1),
+            with_rows("p_sh", GB, 1),
+        ];
+        assert!(date_gaps(&list).is_empty());
+        let months = &list[..2];
+        assert!(date_gaps(months).is_empty(), "needs three dated partitions");
+        let months = [
+            list[0].clone(),
+            list[1].clone(),
+            with_rows("p202405", GB, 1),
+        ];
+        assert_eq!(date_gaps(&months)[0].missing, 2);
+        assert_eq!(date_gaps(&months)[0].unit_name, "month");
+
+        // No row counts on Doris 2.x, so nothing is empty-with-data
+        let v2 = vec![partition("p1", GB, 1), partition("p2", GB, 1)];
+        assert!(partition_anomalies(&v2).iter().all(|a| !a.empty_with_data));
+    }
+}
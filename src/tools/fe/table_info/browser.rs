@@ -1,45 +1,109 @@
 use anyhow::Result;
 
+use crate::ui::table::{Align, TableBuilder};
 use crate::ui::{InteractiveSelector, print_error, print_info};
 
 use super::{FeTableInfoTool, TableIdentity};
+use crate::tools::context::InteractiveContext;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub fn run_interactive(config: &crate::config::Config) -> Result<()> {
     loop {
         match select_database_or_bulk(config)? {
             DatabaseSelection::Single(db) => match select_table_or_bulk(config, &db)? {
                 TableSelection::Single(ident) => {
-                    let report = FeTableInfoTool::collect_one(config, &ident)?;
+                    let report = match prompt_analysis_depth()? {
+                        AnalysisDepth::Basic => FeTableInfoTool::collect_one(config, &ident)?,
+                        AnalysisDepth::Deep => {
+                            print_info("Collecting tablet distribution (SHOW TABLETS)...");
+                            let mut report = FeTableInfoTool::collect_one_deep(config, &ident)?;
+                            if prompt_bucket_skew()? {
+                                FeTableInfoTool::collect_bucket_skew(config, &mut report)?;
+                            }
+                            report
+                        }
+                    };
                     render_brief(&report);
+                    show_report_hints(config, &report);
+                    let format = prompt_report_format()?;
+                    if let Ok(files) = save_reports(config, &[report], SaveMode::Single, format) {
+                        for f in files {
+                            print_info(&format!("Saved: {}", f.display()));
+                        }
+                    }
                 }
                 TableSelection::AllInDb(db_name) => {
                     let total = FeTableInfoTool::list_tables(config, Some(&db_name))?.len();
-                    let conc = FeTableInfoTool::suggest_concurrency(total);
-                    let reports = FeTableInfoTool::collect_all_in_db(config, &db_name, conc)?;
-                    if let Ok(files) = save_reports_txt(config, &reports, SaveMode::PerDatabase) {
+                    let conc = prompt_concurrency(config, total)?;
+                    let outcome = FeTableInfoTool::collect_all_in_db(
+                        config,
+                        &db_name,
+                        conc,
+                        &InteractiveContext,
+                    )?;
+                    if let Ok(files) = save_reports(
+                        config,
+                        &outcome.reports,
+                        SaveMode::PerDatabase,
+                        prompt_report_format()?,
+                    ) {
                         for f in files {
                             print_info(&format!("Saved: {}", f.display()));
                         }
                     }
-                    render_batch_summary(&db_name, reports.len());
+                    render_batch_summary(&db_name, &outcome, None);
+                }
+                TableSelection::Many(idents) => {
+                    let conc = prompt_concurrency(config, idents.len())?;
+                    let outcome = FeTableInfoTool::collect_many(
+                        config,
+                        &idents,
+                        conc,
+                        None,
+                        &InteractiveContext,
+                    )?;
+                    if let Ok(files) = save_reports(
+                        config,
+                        &outcome.reports,
+                        SaveMode::Selected,
+                        prompt_report_format()?,
+                    ) {
+                        for f in files {
+                            print_info(&format!("Saved: {}", f.display()));
+                        }
+                    }
+                    render_batch_summary(&db, &outcome, None);
                 }
             },
             DatabaseSelection::AllDbs => {
                 print_info("Scanning all databases and tables...");
-                let all_tables = FeTableInfoTool::list_tables(config, None)?;
+                let listing =
+                    FeTableInfoTool::list_tables_per_database(config, 16, &InteractiveContext)?;
+                let all_tables = &listing.tables;
+                print_info(&format!("Found {} tables", all_tables.len()));
                 let conc = if all_tables.is_empty() {
                     16
                 } else {
-                    FeTableInfoTool::suggest_concurrency(all_tables.len())
+                    prompt_concurrency(config, all_tables.len())?
                 };
-                print_info(&format!("Found {} tables, starting...", all_tables.len()));
-                let reports = FeTableInfoTool::collect_many(config, &all_tables, conc)?;
-                if let Ok(files) = save_reports_txt(config, &reports, SaveMode::SingleFile) {
+                let outcome = FeTableInfoTool::collect_resumable(
+                    config,
+                    super::ALL_DATABASES_SCOPE,
+                    all_tables,
+                    conc,
+                    &InteractiveContext,
+                )?;
+                if let Ok(files) = save_reports(
+                    config,
+                    &outcome.reports,
+                    SaveMode::SingleFile,
+                    prompt_report_format()?,
+                ) {
                     print_info(&format!("Saved: {}", files[0].display()));
                 }
-                render_batch_summary("<all_dbs>", reports.len());
+                render_batch_summary("<all_dbs>", &outcome, Some(&listing));
             }
         }
 
@@ -111,11 +175,44 @@ fn select_database_or_bulk(config: &crate::config::Config) -> Result<DatabaseSel
     }
 }
 
+enum AnalysisDepth {
+    Basic,
+    Deep,
+}
+
+fn prompt_analysis_depth() -> Result<AnalysisDepth> {
+    let items = vec![
+        "Basic report".to_string(),
+        "Deep analysis (tablet distribution per BE, slower on large tables)".to_string(),
+    ];
+    let selector = InteractiveSelector::new(items, "Select analysis depth".to_string());
+    let sel = selector.select()?;
+    if sel.starts_with("Deep") {
+        Ok(AnalysisDepth::Deep)
+    } else {
+        Ok(AnalysisDepth::Basic)
+    }
+}
+
+/// Opt-in, since it runs one SHOW TABLETS per partition
+fn prompt_bucket_skew() -> Result<bool> {
+    Ok(dialoguer::Confirm::new()
+        .with_prompt(format!(
+            "Also check bucket skew in the {} largest partitions (one SHOW TABLETS each)?",
+            super::BUCKET_SKEW_PARTITIONS
+        ))
+        .default(false)
+        .interact()?)
+}
+
 enum TableSelection {
     Single(TableIdentity),
+    Many(Vec<TableIdentity>),
     AllInDb(String),
 }
 
+const PICK_SEVERAL: &str = "[Pick several tables]";
+
 fn select_table_or_bulk(config: &crate::config::Config, database: &str) -> Result<TableSelection> {
     let tables = FeTableInfoTool::list_tables(config, Some(database))?;
     let names: Vec<String> = tables
@@ -124,12 +221,30 @@ fn select_table_or_bulk(config: &crate::config::Config, database: &str) -> Resul
         .map(|t| t.name)
         .collect();
 
+    let mut options = names.clone();
+    if names.len() > 1 {
+        options.push(PICK_SEVERAL.to_string());
+    }
+
     match create_string_selector(
-        names,
+        options,
         format!("Select a table in {}", database),
         true,
         "[All tables in this DB]",
     )? {
+        SelectionResult::Single(name) if name == PICK_SEVERAL => {
+            let selector = InteractiveSelector::new(names, format!("Select tables in {database}"))
+                .with_page_size(30);
+            let idents = selector
+                .select_multi()?
+                .into_iter()
+                .map(|name| TableIdentity {
+                    schema: database.to_string(),
+                    name: name.clone(),
+                })
+                .collect();
+            Ok(TableSelection::Many(idents))
+        }
         SelectionResult::Single(name) => Ok(TableSelection::Single(TableIdentity {
             schema: database.to_string(),
             name,
@@ -157,6 +272,61 @@ fn generate_report_content(report: &super::TableInfoReport) -> String {
     out.push_str(&"-".repeat(80));
     out.push('\n');
 
+    for (label, value) in summary_fields(report) {
+        out.push_str(&format!("  {:<18} {}\n", format!("{label}:"), value));
+    }
+
+    out.push('\n');
+    out.push_str("Properties:\n");
+    for (label, value) in property_fields(&report.properties) {
+        out.push_str(&format!("  {:<18} {}\n", format!("{label}:"), value));
+    }
+
+    if !report.rollups.is_empty() {
+        out.push('\n');
+        out.push_str("Rollups / Sync MVs:\n");
+        out.push_str(&build_rollups_table(&report.rollups));
+        out.push_str(&format!(
+            "Every load also writes these {} index(es)\n",
+            report.rollups.len()
+        ));
+    }
+
+    out.push('\n');
+    out.push_str("Partitions:\n");
+    out.push_str(&build_partitions_table(&report.partitions));
+    out.push_str(&format!("Total partitions: {}\n", report.partitions.len()));
+    if !report.tablet_distribution.is_empty() {
+        out.push('\n');
+        out.push_str("Tablet Distribution:\n");
+        out.push_str(&build_tablet_distribution_table(
+            &report.tablet_distribution,
+        ));
+        let (ratio, flag) = skew_summary(report);
+        let flag = flag.map(|f| format!("  [!] {f}")).unwrap_or_default();
+        out.push_str(&format!("Skew ratio (max/min): {ratio}{flag}\n"));
+    }
+    if !report.bucket_skew.is_empty() {
+        out.push('\n');
+        out.push_str(&format!("{}:\n", bucket_skew_heading(report)));
+        out.push_str(&build_bucket_skew_table(report));
+        if let Some(warning) = super::analysis::bucket_skew_warning(report) {
+            out.push_str(&format!("  [!] {warning}\n"));
+        }
+    }
+    if !report.recommendations.is_empty() {
+        out.push('\n');
+        out.push_str("Findings:\n");
+        for finding in &report.recommendations {
+            out.push_str(&format!("  [!] {finding}\n"));
+        }
+    }
+    out.push_str(&"=".repeat(80));
+    out
+}
+
+/// Labelled overview values shared by the text and Markdown reports
+pub(super) fn summary_fields(report: &super::TableInfoReport) -> Vec<(&'static str, String)> {
     let model = format!("{:?}", report.model);
     let keys = if report.key_columns.is_empty() {
         "-".to_string()
@@ -176,13 +346,6 @@ fn generate_report_content(report: &super::TableInfoReport) -> String {
         .merge_on_write
         .map(|v| if v { "Yes" } else { "No" })
         .unwrap_or("-");
-
-    out.push_str(&format!("  {:<18} {}\n", "Table Type:", model));
-    out.push_str(&format!("  {:<18} {}\n", "Key Columns:", keys));
-    out.push_str(&format!("  {:<18} {}\n", "Bucketing Key:", bucket_key));
-    out.push_str(&format!("  {:<18} {}\n", "Bucket Count:", bucket_str));
-    out.push_str(&format!("  {:<18} {}\n", "Merge-on-Write:", mow));
-
     let indexes_line = if report.indexes.is_empty() {
         "None".to_string()
     } else {
@@ -199,82 +362,270 @@ fn generate_report_content(report: &super::TableInfoReport) -> String {
             .collect::<Vec<_>>()
             .join(", ")
     };
-    out.push_str(&format!("  {:<18} {}\n", "Indexes:", indexes_line));
 
-    out.push('\n');
-    out.push_str("Partitions:\n");
-    out.push_str(&build_partitions_table(&report.partitions));
-    out.push_str(&format!("Total partitions: {}\n", report.partitions.len()));
-    out.push_str(&"=".repeat(80));
-    out
+    let mut fields = vec![
+        ("Table Type", model),
+        ("Key Columns", keys),
+        ("Bucketing Key", bucket_key),
+        ("Bucket Count", bucket_str),
+        ("Merge-on-Write", mow.to_string()),
+        ("Indexes", indexes_line),
+    ];
+    if let Some(mvs) = report.async_mvs.as_ref().filter(|m| !m.is_empty()) {
+        fields.push(("Async MVs", mvs.join(", ")));
+    }
+    fields
+}
+
+pub(super) fn property_fields(props: &super::TableProperties) -> Vec<(&'static str, String)> {
+    let replication = match (&props.replication_allocation, props.replication_num) {
+        (Some(alloc), _) => alloc.clone(),
+        (None, Some(n)) => n.to_string(),
+        (None, None) => "-".to_string(),
+    };
+    let dynamic = match props.dynamic_partition_enabled {
+        Some(true) => format!(
+            "Enabled ({})",
+            props.dynamic_partition_time_unit.as_deref().unwrap_or("-")
+        ),
+        Some(false) => "Disabled".to_string(),
+        None => "-".to_string(),
+    };
+    vec![
+        ("Replication", replication),
+        (
+            "Storage Policy",
+            props.storage_policy.as_deref().unwrap_or("-").to_string(),
+        ),
+        ("Dynamic Partition", dynamic),
+        (
+            "Compression",
+            props.compression.as_deref().unwrap_or("-").to_string(),
+        ),
+    ]
+}
+
+/// `max/min` text of the tablet skew and the warning appended when it is too high
+pub(super) fn skew_summary(report: &super::TableInfoReport) -> (String, Option<String>) {
+    let ratio = match report.tablet_skew_ratio() {
+        Some(r) if r.is_infinite() => "inf (a BE holds no data)".to_string(),
+        Some(r) => format!("{r:.2}x"),
+        None => "-".to_string(),
+    };
+    let flag = report.is_tablet_skewed().then(|| {
+        format!(
+            "skewed: max/min BE data ratio exceeds {:.0}x",
+            super::TABLET_SKEW_THRESHOLD
+        )
+    });
+    (ratio, flag)
+}
+
+/// Section title naming the bucketing key, shared by the text and Markdown reports
+pub(super) fn bucket_skew_heading(report: &super::TableInfoReport) -> String {
+    format!(
+        "Bucket Skew (largest {} partitions, DISTRIBUTED BY {})",
+        report.bucket_skew.len(),
+        super::analysis::bucketing_clause(report)
+    )
 }
 
-fn render_batch_summary(scope: &str, total: usize) {
+/// Partition, tablets, max, median, min and max/median of each checked partition
+pub(super) fn bucket_skew_rows(report: &super::TableInfoReport) -> Vec<Vec<String>> {
+    use crate::tools::common::format_utils::format_bytes;
+    report
+        .bucket_skew
+        .iter()
+        .map(|s| {
+            let ratio = s.ratio();
+            vec![
+                s.partition.clone(),
+                s.tablets.to_string(),
+                format_bytes(s.max_bytes, 3, false),
+                format_bytes(s.median_bytes, 3, false),
+                format_bytes(s.min_bytes, 3, false),
+                match (ratio.is_infinite(), s.is_skewed()) {
+                    (true, _) => "inf [!]".to_string(),
+                    (false, true) => format!("{ratio:.2}x [!]"),
+                    (false, false) => format!("{ratio:.2}x"),
+                },
+            ]
+        })
+        .collect()
+}
+
+fn show_report_hints(config: &crate::config::Config, report: &super::TableInfoReport) {
+    let max_avg = report
+        .partitions
+        .iter()
+        .filter_map(|p| p.avg_bucket_size_bytes)
+        .max();
+    if let Some(bytes) = max_avg {
+        crate::ui::hints::show_hints(
+            config,
+            &[crate::ui::hints::Finding::MaxAvgBucketSize(bytes)],
+        );
+    }
+}
+
+/// Worker threads for a batch of `total` tables: the suggestion, or what the
+/// user enters instead, capped by `table_info_max_concurrency`
+fn prompt_concurrency(config: &crate::config::Config, total: usize) -> Result<usize> {
+    let cap = config.table_info_max_concurrency();
+    let suggested = FeTableInfoTool::suggest_concurrency(total, cap);
+    let threads = crate::ui::InputHelper::prompt_number_with_default(
+        "Worker threads (Enter keeps the suggestion)",
+        suggested as i64,
+        1,
+        Some(cap as i64),
+        None,
+    )?;
+    Ok(threads as usize)
+}
+
+fn render_batch_summary(
+    scope: &str,
+    outcome: &super::BatchOutcome,
+    listing: Option<&super::TableListing>,
+) {
+    let reports = &outcome.reports;
     print_info("");
     print_info(&"=".repeat(80));
     print_info(&format!("Batch collection completed for {}", scope));
-    print_info(&format!("Collected tables: {}", total));
+    if outcome.resumed > 0 {
+        print_info(&format!(
+            "Collected tables: {} ({} new, {} resumed from checkpoint)",
+            reports.len(),
+            outcome.collected,
+            outcome.resumed
+        ));
+    } else {
+        print_info(&format!("Collected tables: {}", reports.len()));
+    }
+    if let Some(l) = listing {
+        print_info(&format!(
+            "Enumerated {} database(s) and {} table(s), skipped {} database(s)",
+            l.databases,
+            l.tables.len(),
+            l.skipped.len()
+        ));
+    }
+    if outcome.throttle.pauses > 0 {
+        print_info(&format!(
+            "Throttled: paused {} time(s), {:.1}s in total, because the FE answered slowly",
+            outcome.throttle.pauses,
+            outcome.throttle.paused.as_secs_f64()
+        ));
+    }
+    let with_mvs = reports.iter().filter(|r| !r.rollups.is_empty()).count();
+    if with_mvs > 0 {
+        print_info(&format!(
+            "Tables with rollups/sync MVs: {with_mvs} ({} index(es) in total)",
+            reports.iter().map(|r| r.rollups.len()).sum::<usize>()
+        ));
+    }
+    let flagged = flagged_per_database(reports);
+    if !flagged.is_empty() {
+        print_info("Tables with findings:");
+        for (db, (count, total)) in flagged {
+            print_info(&format!("  {db:<30} {count} of {total}"));
+        }
+    }
     print_info(&"=".repeat(80));
 }
 
+/// Per database: tables with at least one finding, and tables collected
+fn flagged_per_database(
+    reports: &[super::TableInfoReport],
+) -> std::collections::BTreeMap<&str, (usize, usize)> {
+    let mut per_db = std::collections::BTreeMap::new();
+    for r in reports {
+        let entry = per_db.entry(r.ident.schema.as_str()).or_insert((0, 0));
+        entry.1 += 1;
+        if !r.recommendations.is_empty() {
+            entry.0 += 1;
+        }
+    }
+    per_db.retain(|_, (flagged, _)| *flagged > 0);
+    per_db
+}
+
 fn build_partitions_table(parts: &[super::PartitionStat]) -> String {
-    let w_part = 18usize;
-    let w_size = 10usize;
-    let w_rows = 12usize;
-    let w_buck = 8usize;
-
-    let mut s = String::new();
-    let top = format!(
-        "┌{}┬{}┬{}┬{}┐\n",
-        "─".repeat(w_part + 2),
-        "─".repeat(w_size + 2),
-        "─".repeat(w_rows + 2),
-        "─".repeat(w_buck + 2)
-    );
-    let mid = format!(
-        "├{}┼{}┼{}┼{}┤\n",
-        "─".repeat(w_part + 2),
-        "─".repeat(w_size + 2),
-        "─".repeat(w_rows + 2),
-        "─".repeat(w_buck + 2)
-    );
-    let bot = format!(
-        "└{}┴{}┴{}┴{}┘\n",
-        "─".repeat(w_part + 2),
-        "─".repeat(w_size + 2),
-        "─".repeat(w_rows + 2),
-        "─".repeat(w_buck + 2)
-    );
-
-    s.push_str(&top);
-    s.push_str(&format!(
-        "│ {:<w_part$} │ {:>w_size$} │ {:>w_rows$} │ {:>w_buck$} │\n",
-        "Partition",
-        "Size",
-        "Rows",
-        "Buckets",
-        w_part = w_part,
-        w_size = w_size,
-        w_rows = w_rows,
-        w_buck = w_buck
-    ));
-    s.push_str(&mid);
-    for p in parts.iter() {
-        let size = crate::tools::common::format_utils::format_bytes(p.size_bytes, 3, false);
-        s.push_str(&format!(
-            "│ {:<w_part$} │ {:>w_size$} │ {:>w_rows$} │ {:>w_buck$} │\n",
-            truncate(&p.name, w_part),
-            size,
-            p.rows,
-            p.buckets,
-            w_part = w_part,
-            w_size = w_size,
-            w_rows = w_rows,
-            w_buck = w_buck
-        ));
+    let anomalies = super::analysis::partition_anomalies(parts);
+    let mut table = TableBuilder::new(["Partition", "Size", "Rows", "Buckets"])
+        .max_width(0, 40)
+        .align(1, Align::Right)
+        .align(2, Align::Right)
+        .align(3, Align::Right);
+    for (p, anomaly) in parts.iter().zip(&anomalies) {
+        let marks = anomaly.marks();
+        table.push_row([
+            if marks.is_empty() {
+                p.name.clone()
+            } else {
+                format!("{marks} {}", p.name)
+            },
+            crate::tools::common::format_utils::format_bytes(p.size_bytes, 3, false),
+            p.rows.to_string(),
+            p.buckets.to_string(),
+        ]);
+    }
+    let mut out = table.render();
+    let legend: Vec<String> = super::analysis::ANOMALY_LEGEND
+        .iter()
+        .filter(|(mark, _)| anomalies.iter().any(|a| a.marks().contains(mark)))
+        .map(|(mark, meaning)| format!("{mark} {meaning}"))
+        .collect();
+    if !legend.is_empty() {
+        out.push_str(&format!("Marks: {}\n", legend.join("; ")));
+    }
+    out
+}
+
+fn build_rollups_table(rollups: &[super::RollupInfo]) -> String {
+    let mut table = TableBuilder::new(["Name", "Keys", "Key Columns", "Columns"])
+        .max_width(0, 32)
+        .max_width(2, 30)
+        .max_width(3, 50);
+    for r in rollups {
+        table.push_row([
+            r.name.clone(),
+            r.keys_type.clone(),
+            r.key_columns.join(", "),
+            r.columns.join(", "),
+        ]);
     }
-    s.push_str(&bot);
-    s
+    table.render()
+}
+
+fn build_tablet_distribution_table(stats: &[super::BackendTabletStat]) -> String {
+    let mut table = TableBuilder::new(["Backend", "Tablets", "Data", "Share"])
+        .max_width(0, 24)
+        .align(1, Align::Right)
+        .align(2, Align::Right)
+        .align(3, Align::Right);
+    for b in stats {
+        table.push_row([
+            b.backend_id.clone(),
+            b.tablet_count.to_string(),
+            crate::tools::common::format_utils::format_bytes(b.data_bytes, 3, false),
+            format!("{:.1}%", b.percentage),
+        ]);
+    }
+    table.render()
+}
+
+fn build_bucket_skew_table(report: &super::TableInfoReport) -> String {
+    let mut table =
+        TableBuilder::new(["Partition", "Tablets", "Max", "Median", "Min", "Max/Median"])
+            .max_width(0, 40);
+    for col in 1..6 {
+        table = table.align(col, Align::Right);
+    }
+    for row in bucket_skew_rows(report) {
+        table.push_row(row);
+    }
+    table.render()
 }
 
 enum NextAction {
@@ -302,31 +653,117 @@ fn prompt_next_action() -> Result<NextAction> {
 
 #[derive(Debug, Clone)]
 enum SaveMode {
+    /// The table picked on its own, saved as `<db>.<table>.<ext>`
+    Single,
     SingleFile,
     PerDatabase,
+    /// A hand-picked subset of one database, kept apart from its full report
+    Selected,
+}
+
+/// File format of saved table reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+    Text,
+    /// Pipe tables that render in ticketing systems
+    Markdown,
+}
+
+impl ReportFormat {
+    const ALL: [ReportFormat; 2] = [ReportFormat::Text, ReportFormat::Markdown];
+
+    fn label(self) -> &'static str {
+        match self {
+            ReportFormat::Text => "Text (.txt)",
+            ReportFormat::Markdown => "Markdown (.md)",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Text => "txt",
+            ReportFormat::Markdown => "md",
+        }
+    }
+
+    fn render(self, report: &super::TableInfoReport) -> String {
+        match self {
+            ReportFormat::Text => generate_report_content(report),
+            ReportFormat::Markdown => super::markdown::generate_report_content_md(report),
+        }
+    }
+}
+
+/// Index into [`ReportFormat::ALL`] of the format picked last in this session
+static LAST_FORMAT: AtomicUsize = AtomicUsize::new(0);
+
+fn prompt_report_format() -> Result<ReportFormat> {
+    let labels: Vec<&str> = ReportFormat::ALL.iter().map(|f| f.label()).collect();
+    let index = crate::ui::dialogs::select_index_with_default(
+        "Save report as",
+        &labels,
+        LAST_FORMAT.load(Ordering::Relaxed),
+    )?;
+    LAST_FORMAT.store(index, Ordering::Relaxed);
+    Ok(ReportFormat::ALL[index])
+}
+
+/// Reports one after another, behind the profile stamp
+fn combined_content(
+    config: &crate::config::Config,
+    reports: &[&super::TableInfoReport],
+    format: ReportFormat,
+) -> String {
+    let mut content = report_stamp(config);
+    for r in reports {
+        content.push_str(&format.render(r));
+        content.push('\n');
+        match format {
+            ReportFormat::Text => content.push_str(&"-".repeat(80)),
+            ReportFormat::Markdown => content.push_str("\n---"),
+        }
+        content.push('\n');
+    }
+    content
 }
 
-fn save_reports_txt(
+fn save_reports(
     config: &crate::config::Config,
     reports: &[super::TableInfoReport],
     mode: SaveMode,
+    format: ReportFormat,
 ) -> anyhow::Result<Vec<PathBuf>> {
-    let base_dir: PathBuf = config.output_dir.join("table-info");
+    let base_dir: PathBuf = config.run_dir().join("table-info");
     config.ensure_output_dir()?;
+    let ext = format.extension();
+    let write = |name: String, group: &[&super::TableInfoReport]| -> anyhow::Result<PathBuf> {
+        let file_path = base_dir.join(config.artifact_file_name(&name));
+        crate::tools::common::fs_utils::ensure_dir_exists(&file_path)?;
+        fs::write(&file_path, combined_content(config, group, format))?;
+        Ok(file_path)
+    };
+    let all: Vec<&super::TableInfoReport> = reports.iter().collect();
 
     match mode {
-        SaveMode::SingleFile => {
-            let file_path = base_dir.join("all_databases_table_info.txt");
-            crate::tools::common::fs_utils::ensure_dir_exists(&file_path)?;
-            let mut content = String::new();
-            for r in reports {
-                content.push_str(&generate_report_content(r));
-                content.push('\n');
-                content.push_str(&"-".repeat(80));
-                content.push('\n');
-            }
-            fs::write(&file_path, content)?;
-            Ok(vec![file_path])
+        SaveMode::Single => {
+            let name = reports.first().map_or_else(
+                || "table".to_string(),
+                |r| format!("{}.{}", r.ident.schema, r.ident.name),
+            );
+            Ok(vec![write(format!("{name}.{ext}"), &all)?])
+        }
+        SaveMode::SingleFile => Ok(vec![write(
+            format!("all_databases_table_info.{ext}"),
+            &all,
+        )?]),
+        SaveMode::Selected => {
+            let db_name = reports
+                .first()
+                .map_or("tables", |r| r.ident.schema.as_str());
+            Ok(vec![write(
+                format!("{db_name}_selected_tables.{ext}"),
+                &all,
+            )?])
         }
         SaveMode::PerDatabase => {
             let mut db_groups: std::collections::HashMap<String, Vec<&super::TableInfoReport>> =
@@ -340,27 +777,17 @@ fn save_reports_txt(
 
             let mut files: Vec<PathBuf> = Vec::with_capacity(db_groups.len());
             for (db_name, db_reports) in db_groups {
-                let file_path = base_dir.join(format!("{}.txt", db_name));
-                crate::tools::common::fs_utils::ensure_dir_exists(&file_path)?;
-                let mut content = String::new();
-                for r in db_reports {
-                    content.push_str(&generate_report_content(r));
-                    content.push('\n');
-                    content.push_str(&"-".repeat(80));
-                    content.push('\n');
-                }
-                fs::write(&file_path, content)?;
-                files.push(file_path);
+                files.push(write(format!("{db_name}.{ext}"), &db_reports)?);
             }
             Ok(files)
         }
     }
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max.saturating_sub(1)])
-    }
+/// Header line identifying the production profile a saved report came from
+fn report_stamp(config: &crate::config::Config) -> String {
+    config
+        .production_profile()
+        .map(|p| format!("Profile: {p} (production)\n"))
+        .unwrap_or_default()
 }
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::{TableIdentity, TableInfoReport};
+
+/// Finished reports of a batch scan, one JSON line each, so a dropped session
+/// keeps everything collected so far and a re-run can skip it
+pub struct Checkpoint {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+fn ident_key(ident: &TableIdentity) -> String {
+    format!("{}.{}", ident.schema, ident.name)
+}
+
+impl Checkpoint {
+    /// Checkpoint file of a scan scope such as `db_sales` or `all_databases`.
+    /// It must outlive the session, so it stays outside the run directory.
+    pub fn path_for(config: &crate::config::Config, scope: &str) -> PathBuf {
+        let safe: String = scope
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        config
+            .output_dir
+            .join("table-info")
+            .join(config.artifact_file_name(&format!(".checkpoint_{safe}.jsonl")))
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        crate::tools::common::fs_utils::ensure_dir_exists(path)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        // Terminate a line cut off by a crash so the next report starts cleanly
+        if file.metadata()?.len() > 0 {
+            let mut last = [0u8; 1];
+            let mut reader = File::open(path)?;
+            reader.seek(SeekFrom::End(-1))?;
+            reader.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                file.write_all(b"\n")?;
+            }
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Reports recorded so far; a line cut off by a crash is ignored
+    pub fn load(path: &Path) -> Vec<TableInfoReport> {
+        let Ok(file) = File::open(path) else {
+            return Vec::new();
+        };
+        BufReader::new(file)
+            .lines()
+            .map_while(|l| l.ok())
+            .filter_map(|line| serde_json::from_str(&line).ok())
+            .collect()
+    }
+
+    pub fn append(&self, report: &TableInfoReport) -> Result<()> {
+        let mut line = serde_json::to_string(report)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn remove(self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Checkpointed reports that belong to `idents`, and the idents still to collect
+pub fn split_completed(
+    recorded: Vec<TableInfoReport>,
+    idents: &[TableIdentity],
+) -> (Vec<TableInfoReport>, Vec<TableIdentity>) {
+    let wanted: HashSet<String> = idents.iter().map(ident_key).collect();
+    let mut seen = HashSet::new();
+    let done: Vec<TableInfoReport> = recorded
+        .into_iter()
+        .filter(|r| {
+            let key = ident_key(&r.ident);
+            wanted.contains(&key) && seen.insert(key)
+        })
+        .collect();
+    let remaining = idents
+        .iter()
+        .filter(|i| !seen.contains(&ident_key(i)))
+        .cloned()
+        .collect();
+    (done, remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::fe::table_info::{BucketCount, TableModel, TableProperties};
+
+    fn report(schema: &str, name: &str) -> TableInfoReport {
+        TableInfoReport {
+            ident: TableIdentity {
+                schema: schema.to_string(),
+                name: name.to_string(),
+            },
+            model: TableModel::DuplicateKey,
+            key_columns: vec!["id".to_string()],
+            bucketing_key: None,
+            bucket: BucketCount::Auto,
+            merge_on_write: None,
+            indexes: Vec::new(),
+            columns: Vec::new(),
+            partitions: Vec::new(),
+            tablet_distribution: Vec::new(),
+            recommendations: Vec::new(),
+            properties: TableProperties::default(),
+            rollups: Vec::new(),
+            async_mvs: None,
+            bucket_skew: vec![],
+        }
+    }
+
+    #[test]
+    fn resumes_from_appended_reports() {
+        let dir = std::env::temp_dir().join(format!("cloud-cli-checkpoint-{}", std::process::id()));
+        let path = dir.join(".checkpoint_db_sales.jsonl");
+        let checkpoint = Checkpoint::open(&path).unwrap();
+        checkpoint.append(&report("sales", "orders")).unwrap();
+        checkpoint.append(&report("sales", "orders")).unwrap();
+        checkpoint.append(&report("logs", "events")).unwrap();
+        drop(checkpoint);
+        // Interrupted mid-write
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"ident":{"schema":"sales","#).unwrap();
+
+        let recorded = Checkpoint::load(&path);
+        assert_eq!(recorded.len(), 3);
+        Checkpoint::open(&path)
+            .unwrap()
+            .append(&report("sales", "refunds"))
+            .unwrap();
+        assert_eq!(Checkpoint::load(&path).len(), 4);
+
+        let idents: Vec<TableIdentity> = ["orders", "items"]
+            .iter()
+            .map(|n| report("sales", n).ident)
+            .collect();
+        let (done, remaining) = split_completed(recorded, &idents);
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].ident.name, "orders");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].name, "items");
+
+        Checkpoint::open(&path).unwrap().remove();
+        assert!(!path.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
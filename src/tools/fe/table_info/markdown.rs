@@ -0,0 +1,255 @@
+use crate::tools::common::format_utils::format_bytes;
+use crate::ui::table::truncate_to_width;
+
+use super::browser::{
+    bucket_skew_heading, bucket_skew_rows, property_fields, skew_summary, summary_fields,
+};
+use super::{TableInfoReport, analysis};
+
+/// Cells longer than this are cut, so long index or MV definitions keep the table readable
+const MAX_CELL_WIDTH: usize = 120;
+
+/// Markdown report of one table: overview and properties as bullet lists,
+/// columns, indexes and partitions as pipe tables
+pub fn generate_report_content_md(report: &TableInfoReport) -> String {
+    let mut out = format!(
+        "## {}.{}\n\n",
+        escape_text(&report.ident.schema),
+        escape_text(&report.ident.name)
+    );
+    for (label, value) in summary_fields(report) {
+        out.push_str(&format!("- **{label}:** {}\n", escape_text(&value)));
+    }
+
+    out.push_str("\n### Properties\n\n");
+    for (label, value) in property_fields(&report.properties) {
+        out.push_str(&format!("- **{label}:** {}\n", escape_text(&value)));
+    }
+
+    if !report.columns.is_empty() {
+        out.push_str("\n### Columns\n\n");
+        let rows = report.columns.iter().map(|c| {
+            vec![
+                c.name.clone(),
+                c.data_type.clone(),
+                yes_no(c.nullable),
+                yes_no(c.is_key),
+            ]
+        });
+        out.push_str(&table(
+            &["Column", "Type", "Nullable", "Key"],
+            &[false; 4],
+            rows,
+        ));
+    }
+
+    if !report.indexes.is_empty() {
+        out.push_str("\n### Indexes\n\n");
+        let rows = report
+            .indexes
+            .iter()
+            .map(|i| vec![i.name.clone(), i.index_type.clone(), i.columns.join(", ")]);
+        out.push_str(&table(&["Name", "Type", "Columns"], &[false; 3], rows));
+    }
+
+    if !report.rollups.is_empty() {
+        out.push_str("\n### Rollups / Sync MVs\n\n");
+        let rows = report.rollups.iter().map(|r| {
+            vec![
+                r.name.clone(),
+                r.keys_type.clone(),
+                r.key_columns.join(", "),
+                r.columns.join(", "),
+            ]
+        });
+        out.push_str(&table(
+            &["Name", "Keys", "Key Columns", "Columns"],
+            &[false; 4],
+            rows,
+        ));
+        out.push_str(&format!(
+            "\nEvery load also writes these {} index(es).\n",
+            report.rollups.len()
+        ));
+    }
+
+    out.push_str("\n### Partitions\n\n");
+    let anomalies = analysis::partition_anomalies(&report.partitions);
+    let rows = report.partitions.iter().zip(&anomalies).map(|(p, a)| {
+        let marks = a.marks();
+        vec![
+            if marks.is_empty() {
+                p.name.clone()
+            } else {
+                format!("{marks} {}", p.name)
+            },
+            format_bytes(p.size_bytes, 3, false),
+            p.rows.to_string(),
+            p.buckets.to_string(),
+        ]
+    });
+    out.push_str(&table(
+        &["Partition", "Size", "Rows", "Buckets"],
+        &[false, true, true, true],
+        rows,
+    ));
+    out.push_str(&format!(
+        "\nTotal partitions: {}\n",
+        report.partitions.len()
+    ));
+    let legend: Vec<String> = analysis::ANOMALY_LEGEND
+        .iter()
+        .filter(|(mark, _)| anomalies.iter().any(|a| a.marks().contains(mark)))
+        .map(|(mark, meaning)| format!("`{mark}` {meaning}"))
+        .collect();
+    if !legend.is_empty() {
+        out.push_str(&format!("Marks: {}\n", legend.join("; ")));
+    }
+
+    if !report.tablet_distribution.is_empty() {
+        out.push_str("\n### Tablet Distribution\n\n");
+        let rows = report.tablet_distribution.iter().map(|b| {
+            vec![
+                b.backend_id.clone(),
+                b.tablet_count.to_string(),
+                format_bytes(b.data_bytes, 3, false),
+                format!("{:.1}%", b.percentage),
+            ]
+        });
+        out.push_str(&table(
+            &["Backend", "Tablets", "Data", "Share"],
+            &[false, true, true, true],
+            rows,
+        ));
+        let (ratio, flag) = skew_summary(report);
+        out.push_str(&format!("\nSkew ratio (max/min): {ratio}\n"));
+        if let Some(flag) = flag {
+            out.push_str(&format!("\n> **Warning:** {flag}\n"));
+        }
+    }
+
+    if !report.bucket_skew.is_empty() {
+        out.push_str(&format!(
+            "\n### {}\n\n",
+            escape_text(&bucket_skew_heading(report))
+        ));
+        out.push_str(&table(
+            &["Partition", "Tablets", "Max", "Median", "Min", "Max/Median"],
+            &[false, true, true, true, true, true],
+            bucket_skew_rows(report).into_iter(),
+        ));
+        if let Some(warning) = analysis::bucket_skew_warning(report) {
+            out.push_str(&format!("\n> **Warning:** {}\n", escape_text(&warning)));
+        }
+    }
+
+    if !report.recommendations.is_empty() {
+        out.push_str("\n### Findings\n\n");
+        for finding in &report.recommendations {
+            out.push_str(&format!("- {}\n", escape_text(finding)));
+        }
+    }
+    out
+}
+
+fn yes_no(v: bool) -> String {
+    if v { "Yes" } else { "No" }.to_string()
+}
+
+/// Pipe table; cells are cut to [`MAX_CELL_WIDTH`] before escaping
+fn table(
+    headers: &[&str],
+    right_aligned: &[bool],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> String {
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let mut out = line(headers.iter().map(|h| h.to_string()).collect());
+    out.push_str(&line(
+        right_aligned
+            .iter()
+            .map(|&right| if right { "---:" } else { "---" }.to_string())
+            .collect(),
+    ));
+    for row in rows {
+        out.push_str(&line(row.iter().map(|c| cell(c)).collect()));
+    }
+    out
+}
+
+fn cell(value: &str) -> String {
+    let flat = value.replace(['\r', '\n'], " ");
+    escape_text(&truncate_to_width(&flat, MAX_CELL_WIDTH, "..."))
+}
+
+/// Keep `|` from ending a table cell and `<`/`*`/`_` from turning into markup
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '|' | '\\' | '*' | '_' | '`' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::fe::table_info::{
+        BucketCount, ColumnDef, IndexInfo, PartitionStat, TableIdentity, TableModel,
+    };
+
+    #[test]
+    fn renders_escaped_pipe_tables() {
+        let report = TableInfoReport {
+            ident: TableIdentity {
+                schema: "db".into(),
+                name: "orders".into(),
+            },
+            model: TableModel::DuplicateKey,
+            key_columns: vec!["id".into()],
+            bucketing_key: Some(vec!["id".into()]),
+            bucket: BucketCount::Fixed(8),
+            merge_on_write: None,
+            indexes: vec![IndexInfo {
+                name: "idx_note".into(),
+                columns: vec!["a|b".repeat(100)],
+                index_type: "INVERTED".into(),
+            }],
+            columns: vec![ColumnDef {
+                name: "id".into(),
+                data_type: "BIGINT".into(),
+                nullable: false,
+                is_key: true,
+            }],
+            partitions: vec![PartitionStat {
+                name: "p1".into(),
+                size_bytes: 1024,
+                rows: 10,
+                buckets: 8,
+                avg_bucket_size_bytes: Some(128),
+            }],
+            tablet_distribution: vec![],
+            recommendations: vec![],
+            properties: Default::default(),
+            rollups: vec![],
+            async_mvs: None,
+            bucket_skew: vec![],
+        };
+        let md = generate_report_content_md(&report);
+
+        assert!(md.starts_with("## db.orders\n\n- **Table Type:** DuplicateKey\n"));
+        assert!(md.contains("| Column | Type | Nullable | Key |\n| --- | --- | --- | --- |\n"));
+        assert!(md.contains("| id | BIGINT | No | Yes |\n"));
+        assert!(
+            md.contains("| Partition | Size | Rows | Buckets |\n| --- | ---: | ---: | ---: |\n")
+        );
+
+        let index_row = md.lines().find(|l| l.starts_with("| idx\\_note")).unwrap();
+        assert!(index_row.contains("a\\|b"));
+        assert!(index_row.ends_with("... |"));
+        // Every unescaped pipe is a cell border
+        assert_eq!(index_row.replace("\\|", "").matches('|').count(), 4);
+    }
+}
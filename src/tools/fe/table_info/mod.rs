@@ -6,10 +6,21 @@ use std::sync::{
     atomic::{AtomicUsize, Ordering},
 };
 use std::thread;
+use std::time::Instant;
 
+use crate::tools::common::progress::{BatchProgress, report_failure_summary};
+use crate::tools::context::ToolContext;
+use checkpoint::Checkpoint;
+use throttle::Throttle;
+pub use throttle::ThrottleSummary;
+
+mod analysis;
 pub mod browser;
+mod checkpoint;
+mod markdown;
 mod ops;
 pub mod sql;
+mod throttle;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableIdentity {
@@ -39,6 +50,45 @@ pub struct PartitionStat {
     pub avg_bucket_size_bytes: Option<u64>,
 }
 
+/// Tablet replicas and data held by one backend for a single table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendTabletStat {
+    pub backend_id: String,
+    pub tablet_count: usize,
+    pub data_bytes: u64,
+    pub percentage: f64,
+}
+
+/// Tablet sizes within one partition, from `SHOW TABLETS ... PARTITION`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartitionBucketSkew {
+    pub partition: String,
+    pub tablets: usize,
+    pub max_bytes: u64,
+    pub median_bytes: u64,
+    pub min_bytes: u64,
+}
+
+/// Max/median tablet size ratio above which the bucketing key is reported as skewed
+pub const BUCKET_SKEW_THRESHOLD: f64 = 3.0;
+/// Largest partitions whose tablets the bucket skew check lists
+pub const BUCKET_SKEW_PARTITIONS: usize = 5;
+
+impl PartitionBucketSkew {
+    /// Infinity when most tablets are empty but some hold data
+    pub fn ratio(&self) -> f64 {
+        match (self.max_bytes, self.median_bytes) {
+            (0, _) => 1.0,
+            (_, 0) => f64::INFINITY,
+            (max, median) => max as f64 / median as f64,
+        }
+    }
+
+    pub fn is_skewed(&self) -> bool {
+        self.ratio() > BUCKET_SKEW_THRESHOLD
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableStatsFromPartitions {
     pub partitions: Vec<PartitionStat>,
@@ -60,6 +110,24 @@ pub struct IndexInfo {
     pub index_type: String,
 }
 
+/// A rollup or sync materialized view: an extra index every load writes to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollupInfo {
+    pub name: String,
+    /// `IndexKeysType` of `DESC ALL`, e.g. DUP_KEYS or AGG_KEYS
+    pub keys_type: String,
+    pub columns: Vec<String>,
+    pub key_columns: Vec<String>,
+}
+
+/// Materialized views built on one table
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterializedViews {
+    pub rollups: Vec<RollupInfo>,
+    /// `schema.name` of async MVs reading the table; None when the FE cannot list them
+    pub async_mvs: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BucketingSpec {
     Hash {
@@ -71,12 +139,25 @@ pub enum BucketingSpec {
     },
 }
 
+/// Storage-related entries of the PROPERTIES block
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableProperties {
+    /// Explicit `replication_num`, else the sum over `replication_allocation` tags
+    pub replication_num: Option<u32>,
+    pub replication_allocation: Option<String>,
+    pub storage_policy: Option<String>,
+    pub dynamic_partition_enabled: Option<bool>,
+    pub dynamic_partition_time_unit: Option<String>,
+    pub compression: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateTableParsed {
     pub model: TableModel,
     pub key_columns: Vec<String>,
     pub bucketing: BucketingSpec,
     pub merge_on_write: Option<bool>,
+    pub properties: TableProperties,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +171,93 @@ pub struct TableInfoReport {
     pub indexes: Vec<IndexInfo>,
     pub columns: Vec<ColumnDef>,
     pub partitions: Vec<PartitionStat>,
+    /// Per-backend tablet data, only filled by the deep analysis path
+    #[serde(default)]
+    pub tablet_distribution: Vec<BackendTabletStat>,
+    /// Findings from the bucket sizing and table design checks
+    #[serde(default)]
+    pub recommendations: Vec<String>,
+    #[serde(default)]
+    pub properties: TableProperties,
+    /// Rollups and sync MVs from `DESC <table> ALL`
+    #[serde(default)]
+    pub rollups: Vec<RollupInfo>,
+    #[serde(default)]
+    pub async_mvs: Option<Vec<String>>,
+    /// Tablet sizes of the largest partitions, only filled on request by the deep path
+    #[serde(default)]
+    pub bucket_skew: Vec<PartitionBucketSkew>,
+}
+
+/// Checkpoint scope of the all-database scan
+pub const ALL_DATABASES_SCOPE: &str = "all_databases";
+
+/// Max/min backend data ratio above which a table is reported as skewed
+pub const TABLET_SKEW_THRESHOLD: f64 = 2.0;
+
+impl TableInfoReport {
+    /// Ratio between the largest and smallest backend data size.
+    ///
+    /// Returns None without a distribution from at least two backends, and infinity
+    /// when some backend holds tablets of this table but no data.
+    pub fn tablet_skew_ratio(&self) -> Option<f64> {
+        if self.tablet_distribution.len() < 2 {
+            return None;
+        }
+        let max = self
+            .tablet_distribution
+            .iter()
+            .map(|b| b.data_bytes)
+            .max()?;
+        let min = self
+            .tablet_distribution
+            .iter()
+            .map(|b| b.data_bytes)
+            .min()?;
+        match (max, min) {
+            (0, _) => Some(1.0),
+            (_, 0) => Some(f64::INFINITY),
+            _ => Some(max as f64 / min as f64),
+        }
+    }
+
+    pub fn is_tablet_skewed(&self) -> bool {
+        self.tablet_skew_ratio()
+            .is_some_and(|r| r > TABLET_SKEW_THRESHOLD)
+    }
+}
+
+/// Tables found by a per-database scan, with the databases that could not be listed
+#[derive(Debug, Clone, Default)]
+pub struct TableListing {
+    pub tables: Vec<TableIdentity>,
+    pub databases: usize,
+    pub skipped: Vec<String>,
+}
+
+/// Reports of a batch scan, split by whether this run or an earlier one collected them
+#[derive(Debug, Clone, Default)]
+pub struct BatchOutcome {
+    pub reports: Vec<TableInfoReport>,
+    pub collected: usize,
+    pub resumed: usize,
+    pub throttle: ThrottleSummary,
+}
+
+/// Map raw "schema\ttable" lines (mysql -N -B -r -A) into identities
+fn parse_table_list(raw: &str) -> Vec<TableIdentity> {
+    raw.lines()
+        .filter_map(|line| {
+            let mut parts = line.trim().split('\t');
+            match (parts.next(), parts.next()) {
+                (Some(s), Some(t)) if !s.is_empty() => Some(TableIdentity {
+                    schema: s.to_string(),
+                    name: t.to_string(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
 }
 
 pub struct FeTableInfoTool;
@@ -107,23 +275,90 @@ impl FeTableInfoTool {
         // Load doris config to pass mysql credentials
         let client = Self::create_client(cfg)?;
         let rs = sql::query_table_list(&client, schema)?;
+        Ok(parse_table_list(&rs.0))
+    }
 
-        // Map raw lines "schema\ttable" into identities (since raw mode -N -B -r -A)
-        let mut out = Vec::new();
-        for line in rs.0.lines() {
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            let mut parts = trimmed.split('\t');
-            if let (Some(s), Some(t)) = (parts.next(), parts.next()) {
-                out.push(TableIdentity {
-                    schema: s.to_string(),
-                    name: t.to_string(),
-                });
-            }
+    /// List tables database by database on a bounded worker pool.
+    ///
+    /// One information_schema query over every table times out on very large
+    /// clusters; a database whose listing fails is skipped instead of aborting.
+    pub fn list_tables_per_database(
+        cfg: &crate::config::Config,
+        concurrency: usize,
+        ctx: &dyn ToolContext,
+    ) -> Result<TableListing> {
+        let databases = Self::list_databases(cfg)?;
+        if databases.is_empty() {
+            return Ok(TableListing::default());
         }
-        Ok(out)
+
+        let doris_cfg = crate::config_loader::load_config()?.with_app_config(cfg);
+        let total = databases.len();
+        let worker_count = concurrency.max(1).min(Self::suggest_concurrency(
+            total,
+            cfg.table_info_max_concurrency(),
+        ));
+        let shared_dbs: Arc<Vec<String>> = Arc::new(databases);
+        let results: Arc<Mutex<Vec<Option<Vec<TableIdentity>>>>> =
+            Arc::new(Mutex::new(vec![None; total]));
+        let next_index = Arc::new(AtomicUsize::new(0));
+        let progress = Arc::new(BatchProgress::new(
+            "Listing databases",
+            total,
+            !cfg.no_progress_animation && ctx.is_interactive(),
+        ));
+
+        let mut handles = Vec::with_capacity(worker_count);
+        for _ in 0..worker_count {
+            let doris_cfg_cloned = doris_cfg.clone();
+            let shared_dbs_cloned = Arc::clone(&shared_dbs);
+            let results_cloned = Arc::clone(&results);
+            let next_index_cloned = Arc::clone(&next_index);
+            let progress_cloned = Arc::clone(&progress);
+
+            let handle = thread::spawn(move || {
+                let client = sql::MySqlExecutor::from_config(doris_cfg_cloned);
+                loop {
+                    let idx = next_index_cloned.fetch_add(1, Ordering::SeqCst);
+                    if idx >= shared_dbs_cloned.len() {
+                        break;
+                    }
+                    let db = &shared_dbs_cloned[idx];
+                    match sql::query_table_list(&client, Some(db)) {
+                        Ok(rs) => {
+                            if let Ok(mut guard) = results_cloned.lock() {
+                                guard[idx] = Some(parse_table_list(&rs.0));
+                            }
+                            progress_cloned.finish_item(db, None);
+                        }
+                        Err(e) => progress_cloned.finish_item(db, Some(e.to_string())),
+                    }
+                }
+            });
+            handles.push(handle);
+        }
+
+        for h in handles {
+            let _ = h.join();
+        }
+
+        let failures = Arc::try_unwrap(progress)
+            .map(BatchProgress::finish)
+            .unwrap_or_default();
+        if !failures.is_empty() {
+            ctx.sink().warning(&format!(
+                "Skipped {} database(s) whose table listing failed:",
+                failures.len()
+            ));
+            report_failure_summary(&failures, ctx.sink());
+        }
+
+        let per_db = results.lock().unwrap().clone();
+        Ok(TableListing {
+            databases: total,
+            skipped: failures.into_iter().map(|(db, _)| db).collect(),
+            tables: per_db.into_iter().flatten().flatten().collect(),
+        })
     }
 
     pub fn list_databases(cfg: &crate::config::Config) -> anyhow::Result<Vec<String>> {
@@ -149,31 +384,79 @@ impl FeTableInfoTool {
         ident: &TableIdentity,
     ) -> Result<TableInfoReport> {
         let client = Self::create_client(cfg)?;
-        let (create, parts, cols, idxs) = ops::fetch_and_parse_all(&client, ident)?;
-        let report = assemble_report(ident, &create, &parts, &cols, &idxs);
+        let (create, parts, cols, idxs, mvs) = ops::fetch_and_parse_all(&client, ident)?;
+        let report = assemble_report(ident, &create, &parts, &cols, &idxs, mvs);
         Ok(report)
     }
 
+    /// Collect the report plus per-backend tablet distribution (runs SHOW TABLETS)
+    pub fn collect_one_deep(
+        cfg: &crate::config::Config,
+        ident: &TableIdentity,
+    ) -> Result<TableInfoReport> {
+        let client = Self::create_client(cfg)?;
+        let (create, parts, cols, idxs, mvs) = ops::fetch_and_parse_all(&client, ident)?;
+        let mut report = assemble_report(ident, &create, &parts, &cols, &idxs, mvs);
+        let tablets_rs = sql::query_tablets(&client, ident)?;
+        report.tablet_distribution = ops::parse_tablet_distribution(&tablets_rs);
+        Ok(report)
+    }
+
+    /// Tablet sizes of the [`BUCKET_SKEW_PARTITIONS`] largest partitions, one
+    /// `SHOW TABLETS ... PARTITION` each
+    pub fn collect_bucket_skew(
+        cfg: &crate::config::Config,
+        report: &mut TableInfoReport,
+    ) -> Result<()> {
+        let client = Self::create_client(cfg)?;
+        let mut largest: Vec<&PartitionStat> = report
+            .partitions
+            .iter()
+            .filter(|p| p.size_bytes > 0)
+            .collect();
+        largest.sort_by_key(|p| std::cmp::Reverse(p.size_bytes));
+
+        let mut skew = Vec::new();
+        for p in largest.into_iter().take(BUCKET_SKEW_PARTITIONS) {
+            let rows = sql::query_partition_tablets(&client, &report.ident, &p.name)?;
+            skew.extend(ops::parse_bucket_skew(&p.name, &rows));
+        }
+        report.bucket_skew = skew;
+        Ok(())
+    }
+
+    /// Collect `idents` on up to `concurrency` workers.
+    ///
+    /// Workers pause between tables while most recent ones are slow, so a busy
+    /// FE is not pushed further; the outcome reports how long they paused.
     fn collect_many(
         cfg: &crate::config::Config,
         idents: &[TableIdentity],
         concurrency: usize,
-    ) -> Result<Vec<TableInfoReport>> {
+        checkpoint: Option<&Arc<Checkpoint>>,
+        ctx: &dyn ToolContext,
+    ) -> Result<BatchOutcome> {
         if idents.is_empty() {
-            return Ok(Vec::new());
+            return Ok(BatchOutcome::default());
         }
 
         let doris_cfg = crate::config_loader::load_config()?.with_app_config(cfg);
-        let worker_count = concurrency
-            .max(1)
-            .min(Self::suggest_concurrency(idents.len()));
+        let worker_count = concurrency.max(1).min(Self::suggest_concurrency(
+            idents.len(),
+            cfg.table_info_max_concurrency(),
+        ));
 
         let total = idents.len();
         let shared_idents: Arc<Vec<TableIdentity>> = Arc::new(idents.to_vec());
         let results: Arc<Mutex<Vec<Option<TableInfoReport>>>> =
             Arc::new(Mutex::new(vec![None; total]));
         let next_index = Arc::new(AtomicUsize::new(0));
-        let progress = Arc::new(AtomicUsize::new(0));
+        let throttle = Arc::new(Throttle::default());
+        let progress = Arc::new(BatchProgress::new(
+            "Collecting tables",
+            total,
+            !cfg.no_progress_animation && ctx.is_interactive(),
+        ));
 
         let mut handles = Vec::with_capacity(worker_count);
         for _ in 0..worker_count {
@@ -182,6 +465,8 @@ impl FeTableInfoTool {
             let results_cloned = Arc::clone(&results);
             let next_index_cloned = Arc::clone(&next_index);
             let progress_cloned = Arc::clone(&progress);
+            let checkpoint_cloned = checkpoint.cloned();
+            let throttle_cloned = Arc::clone(&throttle);
 
             let handle = thread::spawn(move || {
                 let client = sql::MySqlExecutor::from_config(doris_cfg_cloned);
@@ -191,29 +476,29 @@ impl FeTableInfoTool {
                         break;
                     }
                     let ident = &shared_idents_cloned[idx];
+                    let started = Instant::now();
                     let res = ops::fetch_and_parse_all(&client, ident).map(
-                        |(create, parts, cols, idxs)| {
-                            assemble_report(ident, &create, &parts, &cols, &idxs)
+                        |(create, parts, cols, idxs, mvs)| {
+                            assemble_report(ident, &create, &parts, &cols, &idxs, mvs)
                         },
                     );
+                    let full_name = format!("{}.{}", ident.schema, ident.name);
                     match res {
                         Ok(rep) => {
+                            // A failed checkpoint write only costs this table on resume
+                            if let Some(cp) = &checkpoint_cloned {
+                                let _ = cp.append(&rep);
+                            }
                             if let Ok(mut guard) = results_cloned.lock() {
                                 guard[idx] = Some(rep);
                             }
+                            progress_cloned.finish_item(&full_name, None);
                         }
-                        Err(e) => {
-                            crate::ui::print_error(&format!(
-                                "Collect failed for {}.{}: {}",
-                                ident.schema, ident.name, e
-                            ));
-                        }
+                        Err(e) => progress_cloned.finish_item(&full_name, Some(e.to_string())),
+                    }
+                    if let Some(pause) = throttle_cloned.record(started.elapsed()) {
+                        thread::sleep(pause);
                     }
-                    let done = progress_cloned.fetch_add(1, Ordering::SeqCst) + 1;
-                    crate::ui::print_info(&format!(
-                        "Process: {}/{} {}.{}",
-                        done, total, ident.schema, ident.name
-                    ));
                 }
             });
             handles.push(handle);
@@ -223,6 +508,11 @@ impl FeTableInfoTool {
             let _ = h.join();
         }
 
+        // All workers have joined, so this is the last reference
+        if let Ok(progress) = Arc::try_unwrap(progress) {
+            report_failure_summary(&progress.finish(), ctx.sink());
+        }
+
         let reports: Vec<TableInfoReport> = results
             .lock()
             .unwrap()
@@ -230,33 +520,110 @@ impl FeTableInfoTool {
             .into_iter()
             .flatten()
             .collect();
-        Ok(reports)
+        Ok(BatchOutcome {
+            collected: reports.len(),
+            reports,
+            resumed: 0,
+            throttle: throttle.summary(),
+        })
+    }
+
+    /// Collect `idents`, recording each finished report under `scope` as it completes.
+    ///
+    /// When an earlier run of the same scope left a checkpoint, the user may resume
+    /// and skip the tables it already holds. The checkpoint is removed once every
+    /// table is collected and kept otherwise, so a re-run retries only the rest.
+    pub fn collect_resumable(
+        cfg: &crate::config::Config,
+        scope: &str,
+        idents: &[TableIdentity],
+        concurrency: usize,
+        ctx: &dyn ToolContext,
+    ) -> Result<BatchOutcome> {
+        let path = Checkpoint::path_for(cfg, scope);
+        let (mut done, remaining) = checkpoint::split_completed(Checkpoint::load(&path), idents);
+        let remaining = if done.is_empty() {
+            remaining
+        } else {
+            let choice = ctx.choose(
+                &format!(
+                    "A previous run already collected {} of {} table(s)",
+                    done.len(),
+                    idents.len()
+                ),
+                &["Resume and skip completed tables", "Start over"],
+                0,
+            )?;
+            if choice == 0 {
+                remaining
+            } else {
+                done.clear();
+                let _ = std::fs::remove_file(&path);
+                idents.to_vec()
+            }
+        };
+
+        let checkpoint = Arc::new(Checkpoint::open(&path)?);
+        let batch = Self::collect_many(cfg, &remaining, concurrency, Some(&checkpoint), ctx)?;
+        let resumed = done.len();
+        if resumed + batch.collected >= idents.len() {
+            if let Ok(cp) = Arc::try_unwrap(checkpoint) {
+                cp.remove();
+            }
+        } else {
+            ctx.sink().info(&format!(
+                "Checkpoint kept at {}; run the same scan again to retry {} table(s)",
+                checkpoint.path().display(),
+                idents.len() - resumed - batch.collected
+            ));
+        }
+
+        done.extend(batch.reports);
+        Ok(BatchOutcome {
+            reports: done,
+            collected: batch.collected,
+            resumed,
+            throttle: batch.throttle,
+        })
     }
 
     pub fn collect_all_in_db(
         cfg: &crate::config::Config,
         db: &str,
         concurrency: usize,
-    ) -> Result<Vec<TableInfoReport>> {
+        ctx: &dyn ToolContext,
+    ) -> Result<BatchOutcome> {
         let tables = Self::list_tables(cfg, Some(db))?;
         let idents: Vec<TableIdentity> = tables.into_iter().filter(|t| t.schema == db).collect();
-        Self::collect_many(cfg, &idents, concurrency)
+        Self::collect_resumable(cfg, &format!("db_{db}"), &idents, concurrency, ctx)
+    }
+
+    /// Every table of the database `ctx` names or lets the user pick
+    pub fn collect_database(
+        cfg: &crate::config::Config,
+        concurrency: usize,
+        ctx: &dyn ToolContext,
+    ) -> Result<BatchOutcome> {
+        let db = ctx.database(cfg)?;
+        Self::collect_all_in_db(cfg, &db, concurrency, ctx)
     }
 
     pub fn collect_all_in_all_dbs(
         cfg: &crate::config::Config,
         concurrency: usize,
-    ) -> Result<Vec<TableInfoReport>> {
-        // One shot: list all tables across all databases to avoid double scanning
-        let idents: Vec<TableIdentity> = Self::list_tables(cfg, None)?;
-        Self::collect_many(cfg, &idents, concurrency)
+        ctx: &dyn ToolContext,
+    ) -> Result<BatchOutcome> {
+        let listing = Self::list_tables_per_database(cfg, concurrency, ctx)?;
+        Self::collect_resumable(cfg, ALL_DATABASES_SCOPE, &listing.tables, concurrency, ctx)
     }
 
-    pub fn suggest_concurrency(total_tables: usize) -> usize {
+    /// Power of two up to `total_tables`, never above `hard_cap`
+    /// (the `table_info_max_concurrency` setting)
+    pub fn suggest_concurrency(total_tables: usize, hard_cap: usize) -> usize {
         if total_tables <= 1 {
             return 1;
         }
-        let hard_cap = 32usize;
+        let hard_cap = hard_cap.max(1);
         let mut c = 2usize;
         while c < total_tables && c < hard_cap {
             c = c.saturating_mul(2);
@@ -272,6 +639,7 @@ fn assemble_report(
     parts: &TableStatsFromPartitions,
     cols: &[ColumnDef],
     idxs: &[IndexInfo],
+    mvs: MaterializedViews,
 ) -> TableInfoReport {
     let (final_bucket, bucketing_key) = match &create.bucketing {
         BucketingSpec::Hash { columns, buckets } => (buckets.clone(), Some(columns.clone())),
@@ -283,7 +651,7 @@ fn assemble_report(
         _ => None,
     };
 
-    TableInfoReport {
+    let mut report = TableInfoReport {
         ident: ident.clone(),
         model: create.model.clone(),
         key_columns: create.key_columns.clone(),
@@ -293,32 +661,30 @@ fn assemble_report(
         indexes: idxs.to_vec(),
         columns: cols.to_vec(),
         partitions: parts.partitions.clone(),
-    }
+        tablet_distribution: Vec::new(),
+        recommendations: Vec::new(),
+        properties: create.properties.clone(),
+        rollups: mvs.rollups,
+        async_mvs: mvs.async_mvs,
+        bucket_skew: Vec::new(),
+    };
+    report.recommendations = analysis::recommendations(&report);
+    report
 }
 
-pub(crate) fn parse_size(input: &str) -> u64 {
-    let s = input.trim();
-    if s.is_empty() {
-        return 0;
-    }
-
-    let parts: Vec<&str> = s.split_whitespace().collect();
-    if parts.is_empty() {
-        return 0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggested_concurrency_doubles_up_to_the_cap() {
+        assert_eq!(FeTableInfoTool::suggest_concurrency(0, 32), 1);
+        assert_eq!(FeTableInfoTool::suggest_concurrency(1, 32), 1);
+        assert_eq!(FeTableInfoTool::suggest_concurrency(3, 32), 3);
+        assert_eq!(FeTableInfoTool::suggest_concurrency(20, 32), 20);
+        assert_eq!(FeTableInfoTool::suggest_concurrency(500, 32), 32);
+        // The configured cap wins over the doubling, even when it is not a power of two
+        assert_eq!(FeTableInfoTool::suggest_concurrency(500, 6), 6);
+        assert_eq!(FeTableInfoTool::suggest_concurrency(500, 0), 1);
     }
-
-    let num = parts[0].parse::<f64>().unwrap_or(0.0);
-    let unit = parts
-        .get(1)
-        .map(|u| u.to_ascii_lowercase())
-        .unwrap_or_else(|| "b".to_string());
-
-    let factor = match unit.as_str() {
-        "kb" => 1024.0,
-        "mb" => 1024.0 * 1024.0,
-        "gb" => 1024.0 * 1024.0 * 1024.0,
-        _ => 1.0,
-    };
-
-    (num * factor) as u64
 }
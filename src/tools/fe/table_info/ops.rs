@@ -1,11 +1,28 @@
 use anyhow::Result;
 use regex::Regex;
 
-use super::{ColumnDef, CreateTableParsed, IndexInfo, TableIdentity, TableStatsFromPartitions};
+use crate::tools::common::format_utils;
+
+use super::{
+    BackendTabletStat, ColumnDef, CreateTableParsed, IndexInfo, MaterializedViews,
+    PartitionBucketSkew, RollupInfo, TableIdentity, TableProperties, TableStatsFromPartitions,
+};
 
 const V2_MIN_COLS: usize = 15; // up to DataSize index (14)
 const V3_MIN_COLS: usize = 22;
 
+// SHOW TABLETS: TabletId, ReplicaId, BackendId, SchemaHash, Version, LstSuccessVersion,
+// LstFailedVersion, LstFailedTime, LocalDataSize, ...
+const TABLET_ID_IDX: usize = 0;
+const TABLET_BACKEND_IDX: usize = 2;
+const TABLET_LOCAL_SIZE_IDX: usize = 8;
+
+// DESC <table> ALL: IndexName, IndexKeysType, Field, Type, InternalType, Null, Key, ...
+// IndexName and IndexKeysType are only set on the first row of each index
+const DESC_KEYS_TYPE_IDX: usize = 1;
+const DESC_FIELD_IDX: usize = 2;
+const DESC_KEY_IDX: usize = 6;
+
 fn parse_column_list(input: &str) -> Vec<String> {
     input
         .split(',')
@@ -25,24 +42,96 @@ fn parse_bucket_count(buckets: &str) -> super::BucketCount {
     }
 }
 
-pub fn fetch_and_parse_all(
-    exec: &super::sql::MySqlExecutor,
-    ident: &TableIdentity,
-) -> Result<(
+/// Everything one table's report is assembled from
+pub type FetchedTable = (
     CreateTableParsed,
     TableStatsFromPartitions,
     Vec<ColumnDef>,
     Vec<IndexInfo>,
-)> {
+    MaterializedViews,
+);
+
+pub fn fetch_and_parse_all(
+    exec: &super::sql::MySqlExecutor,
+    ident: &TableIdentity,
+) -> Result<FetchedTable> {
     let create_rs = super::sql::query_show_create(exec, ident)?;
     let parts_rs = super::sql::query_partitions(exec, ident)?;
+    let desc_rs = super::sql::query_desc_all(exec, ident)?;
 
     let create = parse_create_table(create_rs.0.as_str())?;
     let parts = parse_partitions(&parts_rs)?;
     let cols: Vec<ColumnDef> = Vec::new();
     let idxs = parse_indexes_from_create(create_rs.0.as_str());
+    let mvs = MaterializedViews {
+        rollups: parse_rollups(&desc_rs, &ident.name),
+        async_mvs: super::sql::query_async_mvs(exec, ident)
+            .map(|rs| parse_async_mv_refs(&rs, ident)),
+    };
 
-    Ok((create, parts, cols, idxs))
+    Ok((create, parts, cols, idxs, mvs))
+}
+
+/// Rollups and sync MVs from `DESC <table> ALL`; the base index (named after
+/// the table) is left out
+pub fn parse_rollups(rows: &super::sql::ResultSet, table: &str) -> Vec<RollupInfo> {
+    let mut indexes: Vec<RollupInfo> = Vec::new();
+    for line in rows.0.lines() {
+        let cols: Vec<&str> = line.split('\t').map(str::trim).collect();
+        let name = cols[0];
+        if !name.is_empty() {
+            indexes.push(RollupInfo {
+                name: name.to_string(),
+                keys_type: cols.get(DESC_KEYS_TYPE_IDX).unwrap_or(&"").to_string(),
+                columns: Vec::new(),
+                key_columns: Vec::new(),
+            });
+        }
+        let (Some(index), Some(field)) = (indexes.last_mut(), cols.get(DESC_FIELD_IDX)) else {
+            continue;
+        };
+        if field.is_empty() {
+            continue;
+        }
+        index.columns.push(field.to_string());
+        if cols
+            .get(DESC_KEY_IDX)
+            .is_some_and(|k| k.eq_ignore_ascii_case("true") || k.eq_ignore_ascii_case("yes"))
+        {
+            index.key_columns.push(field.to_string());
+        }
+    }
+    indexes.retain(|i| i.name != table);
+    indexes
+}
+
+/// `schema.name` of each async MV whose definition reads `ident`.
+///
+/// The definition is matched on the qualified table name, or on the bare name
+/// when the MV lives in the same database.
+pub fn parse_async_mv_refs(rows: &super::sql::ResultSet, ident: &TableIdentity) -> Vec<String> {
+    let word = |name: &str| format!(r"(?:^|[^\w.]){}\b", regex::escape(name));
+    let qualified = Regex::new(&format!(
+        r"(?i)\b{}\.{}\b",
+        regex::escape(&ident.schema),
+        regex::escape(&ident.name)
+    ));
+    let bare = Regex::new(&format!("(?i){}", word(&ident.name)));
+    let (Ok(qualified), Ok(bare)) = (qualified, bare) else {
+        return Vec::new();
+    };
+
+    rows.0
+        .lines()
+        .filter_map(|line| {
+            let mut cols = line.splitn(3, '\t');
+            let (schema, name, definition) = (cols.next()?, cols.next()?, cols.next()?);
+            let definition = definition.replace('`', "");
+            let reads = qualified.is_match(&definition)
+                || (schema == ident.schema && bare.is_match(&definition));
+            reads.then(|| format!("{schema}.{name}"))
+        })
+        .collect()
 }
 
 pub fn parse_create_table(raw_sql: &str) -> Result<CreateTableParsed> {
@@ -108,6 +197,50 @@ pub fn parse_create_table(raw_sql: &str) -> Result<CreateTableParsed> {
         key_columns,
         bucketing,
         merge_on_write: mow,
+        properties: parse_table_properties(raw_sql)?,
+    })
+}
+
+/// `"key" = "value"` of the trailing PROPERTIES block; None without one
+fn property(block: &str, key: &str) -> Result<Option<String>> {
+    let re = Regex::new(&format!(r#""{}"\s*=\s*"(?P<v>[^"]*)""#, regex::escape(key)))?;
+    Ok(re
+        .captures(block)
+        .map(|c| c["v"].trim().to_string())
+        .filter(|v| !v.is_empty()))
+}
+
+pub fn parse_table_properties(raw_sql: &str) -> Result<TableProperties> {
+    // Column comments may look like properties; only the PROPERTIES block counts
+    let Some(start) = Regex::new(r"(?i)\bPROPERTIES\s*\(")?
+        .find_iter(raw_sql)
+        .last()
+        .map(|m| m.end())
+    else {
+        return Ok(TableProperties::default());
+    };
+    let block = &raw_sql[start..];
+
+    let replication_allocation = property(block, "replication_allocation")?;
+    // "tag.location.default: 1, tag.location.group_a: 2"
+    let allocated = replication_allocation.as_deref().and_then(|alloc| {
+        alloc
+            .split(',')
+            .map(|tag| tag.rsplit(':').next()?.trim().parse::<u32>().ok())
+            .sum::<Option<u32>>()
+    });
+    let replication_num = property(block, "replication_num")?
+        .and_then(|v| v.parse().ok())
+        .or(allocated);
+
+    Ok(TableProperties {
+        replication_num,
+        replication_allocation,
+        storage_policy: property(block, "storage_policy")?,
+        dynamic_partition_enabled: property(block, "dynamic_partition.enable")?
+            .map(|v| v.eq_ignore_ascii_case("true")),
+        dynamic_partition_time_unit: property(block, "dynamic_partition.time_unit")?,
+        compression: property(block, "compression")?,
     })
 }
 
@@ -156,7 +289,7 @@ pub fn parse_partitions(rows: &super::sql::ResultSet) -> Result<TableStatsFromPa
             .and_then(|s| s.trim().parse::<u64>().ok())
             .unwrap_or(0);
 
-        let size_bytes = super::parse_size(data_size);
+        let size_bytes = format_utils::parse_size(data_size);
         let avg_bucket_sz = if buckets > 0 {
             Some(size_bytes / buckets as u64)
         } else {
@@ -189,6 +322,80 @@ pub fn parse_partitions(rows: &super::sql::ResultSet) -> Result<TableStatsFromPa
     })
 }
 
+/// Aggregate SHOW TABLETS rows (one per replica) into per-backend totals, largest first
+pub fn parse_tablet_distribution(rows: &super::sql::ResultSet) -> Vec<BackendTabletStat> {
+    let mut per_backend: std::collections::BTreeMap<String, (usize, u64)> =
+        std::collections::BTreeMap::new();
+
+    for line in rows.0.lines() {
+        let cols: Vec<&str> = line.trim_end().split('\t').collect();
+        if cols.len() <= TABLET_LOCAL_SIZE_IDX {
+            continue;
+        }
+        let backend_id = cols[TABLET_BACKEND_IDX].trim();
+        if backend_id.is_empty() || backend_id.parse::<u64>().is_err() {
+            continue;
+        }
+        let bytes = format_utils::parse_size(cols[TABLET_LOCAL_SIZE_IDX]);
+        let entry = per_backend.entry(backend_id.to_string()).or_default();
+        entry.0 += 1;
+        entry.1 += bytes;
+    }
+
+    let total_bytes: u64 = per_backend.values().map(|(_, b)| *b).sum();
+    let mut stats: Vec<BackendTabletStat> = per_backend
+        .into_iter()
+        .map(
+            |(backend_id, (tablet_count, data_bytes))| BackendTabletStat {
+                backend_id,
+                tablet_count,
+                data_bytes,
+                percentage: if total_bytes > 0 {
+                    data_bytes as f64 * 100.0 / total_bytes as f64
+                } else {
+                    0.0
+                },
+            },
+        )
+        .collect();
+    stats.sort_by_key(|b| std::cmp::Reverse(b.data_bytes));
+    stats
+}
+
+/// Max/median/min tablet size of one partition from its SHOW TABLETS rows.
+///
+/// A tablet counts once at the size of its largest replica, so a replica
+/// still catching up does not pass for a small bucket.
+pub fn parse_bucket_skew(
+    partition: &str,
+    rows: &super::sql::ResultSet,
+) -> Option<PartitionBucketSkew> {
+    let mut per_tablet: std::collections::HashMap<&str, u64> = std::collections::HashMap::new();
+    for line in rows.0.lines() {
+        let cols: Vec<&str> = line.trim_end().split('\t').collect();
+        if cols.len() <= TABLET_LOCAL_SIZE_IDX {
+            continue;
+        }
+        let tablet_id = cols[TABLET_ID_IDX].trim();
+        if tablet_id.parse::<u64>().is_err() {
+            continue;
+        }
+        let bytes = format_utils::parse_size(cols[TABLET_LOCAL_SIZE_IDX]);
+        let entry = per_tablet.entry(tablet_id).or_default();
+        *entry = (*entry).max(bytes);
+    }
+
+    let mut sizes: Vec<u64> = per_tablet.into_values().collect();
+    sizes.sort_unstable();
+    Some(PartitionBucketSkew {
+        partition: partition.to_string(),
+        tablets: sizes.len(),
+        max_bytes: *sizes.last()?,
+        median_bytes: sizes[sizes.len() / 2],
+        min_bytes: sizes[0],
+    })
+}
+
 pub fn parse_indexes_from_create(ddl: &str) -> Vec<IndexInfo> {
     let mut result: Vec<IndexInfo> = Vec::new();
 
@@ -235,3 +442,195 @@ pub fn parse_indexes_from_create(ddl: &str) -> Vec<IndexInfo> {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tablet_row(tablet: u64, backend: u64, bytes: u64) -> String {
+        format!(
+            "{tablet}\t{}\t{backend}\t1\t2\t2\t-1\tNULL\t{bytes}\t0\t10\tNORMAL",
+            tablet + 1
+        )
+    }
+
+    #[test]
+    fn tablet_distribution_aggregates_per_backend() {
+        let rows = [
+            tablet_row(100, 10001, 3000),
+            tablet_row(102, 10002, 500),
+            tablet_row(104, 10001, 3000),
+            tablet_row(106, 10002, 500),
+        ]
+        .join("\n");
+        let stats = parse_tablet_distribution(&super::super::sql::ResultSet(rows));
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].backend_id, "10001");
+        assert_eq!(stats[0].tablet_count, 2);
+        assert_eq!(stats[0].data_bytes, 6000);
+        assert!((stats[0].percentage - 85.714).abs() < 0.01);
+        assert_eq!(stats[1].data_bytes, 1000);
+
+        let report = super::super::TableInfoReport {
+            ident: TableIdentity {
+                schema: "db".into(),
+                name: "t".into(),
+            },
+            model: super::super::TableModel::DuplicateKey,
+            key_columns: vec![],
+            bucketing_key: None,
+            bucket: super::super::BucketCount::Auto,
+            merge_on_write: None,
+            indexes: vec![],
+            columns: vec![],
+            partitions: vec![],
+            tablet_distribution: stats,
+            recommendations: vec![],
+            properties: Default::default(),
+            rollups: vec![],
+            async_mvs: None,
+            bucket_skew: vec![],
+        };
+        assert_eq!(report.tablet_skew_ratio(), Some(6.0));
+        assert!(report.is_tablet_skewed());
+    }
+
+    #[test]
+    fn bucket_skew_takes_the_largest_replica_of_each_tablet() {
+        let rows = [
+            tablet_row(100, 10001, 100),
+            tablet_row(100, 10002, 80),
+            tablet_row(102, 10001, 120),
+            tablet_row(104, 10002, 900),
+        ]
+        .join("\n");
+        let skew = parse_bucket_skew("p1", &super::super::sql::ResultSet(rows)).unwrap();
+
+        assert_eq!(skew.tablets, 3);
+        assert_eq!(
+            (skew.max_bytes, skew.median_bytes, skew.min_bytes),
+            (900, 120, 100)
+        );
+        assert!(skew.is_skewed());
+        assert!(parse_bucket_skew("p1", &super::super::sql::ResultSet(String::new())).is_none());
+    }
+
+    #[test]
+    fn parses_rollups_and_async_mv_references() {
+        let row = |cols: &[&str]| {
+            let mut all = cols.to_vec();
+            all.resize(12, "");
+            all.join("\t")
+        };
+        let desc = [
+            row(&[
+                "orders", "DUP_KEYS", "dt", "DATE", "DATE", "Yes", "true", "NULL", "", "true",
+            ]),
+            row(&[
+                "", "", "user_id", "BIGINT", "BIGINT", "Yes", "true", "NULL", "", "true",
+            ]),
+            row(&[
+                "", "", "amount", "DECIMAL", "DECIMAL", "Yes", "false", "NULL", "NONE", "true",
+            ]),
+            row(&[]),
+            row(&[
+                "r_user", "AGG_KEYS", "user_id", "BIGINT", "BIGINT", "Yes", "true", "NULL", "",
+                "true",
+            ]),
+            row(&[
+                "", "", "amount", "DECIMAL", "DECIMAL", "Yes", "false", "NULL", "SUM", "true",
+            ]),
+        ]
+        .join("\n");
+        let rollups = parse_rollups(&super::super::sql::ResultSet(desc), "orders");
+        assert_eq!(
+            rollups,
+            [RollupInfo {
+                name: "r_user".into(),
+                keys_type: "AGG_KEYS".into(),
+                columns: vec!["user_id".into(), "amount".into()],
+                key_columns: vec!["user_id".into()],
+            }]
+        );
+
+        let ident = TableIdentity {
+            schema: "sales".into(),
+            name: "orders".into(),
+        };
+        let mvs = [
+            "sales\tmv_daily\tSELECT dt, sum(amount) FROM `orders` GROUP BY dt",
+            "bi\tmv_cross\tSELECT * FROM `sales`.`orders` o JOIN users u ON o.user_id = u.id",
+            "bi\tmv_other\tSELECT * FROM bi.orders",
+            "sales\tmv_prefix\tSELECT * FROM orders_archive",
+        ]
+        .join("\n");
+        assert_eq!(
+            parse_async_mv_refs(&super::super::sql::ResultSet(mvs), &ident),
+            ["sales.mv_daily", "bi.mv_cross"]
+        );
+    }
+
+    #[test]
+    fn parses_properties_from_show_create() {
+        // 2.x: explicit replication_num, dynamic partitions, cooldown policy
+        let v2 = r#"CREATE TABLE `events` (
+  `dt` date NULL,
+  `id` bigint(20) NULL COMMENT '"compression" = "ZSTD"'
+) ENGINE=OLAP
+DUPLICATE KEY(`dt`, `id`)
+PARTITION BY RANGE(`dt`)
+(PARTITION p20240501 VALUES [('2024-05-01'), ('2024-05-02')))
+DISTRIBUTED BY HASH(`id`) BUCKETS 8
+PROPERTIES (
+"replication_num" = "3",
+"dynamic_partition.enable" = "true",
+"dynamic_partition.time_unit" = "DAY",
+"dynamic_partition.start" = "-7",
+"storage_policy" = "cold_s3",
+"in_memory" = "false",
+"storage_format" = "V2"
+);"#;
+        let props = parse_create_table(v2).unwrap().properties;
+        assert_eq!(props.replication_num, Some(3));
+        assert_eq!(props.replication_allocation, None);
+        assert_eq!(props.storage_policy.as_deref(), Some("cold_s3"));
+        assert_eq!(props.dynamic_partition_enabled, Some(true));
+        assert_eq!(props.dynamic_partition_time_unit.as_deref(), Some("DAY"));
+        assert_eq!(props.compression, None);
+
+        // 3.x: tag-based allocation across resource groups
+        let v3 = r#"CREATE TABLE `orders` (
+  `id` bigint NOT NULL,
+  `v` varchar(64) NULL
+) ENGINE=OLAP
+UNIQUE KEY(`id`)
+DISTRIBUTED BY HASH(`id`) BUCKETS AUTO
+PROPERTIES (
+"replication_allocation" = "tag.location.default: 2, tag.location.group_b: 1",
+"min_load_replica_num" = "-1",
+"is_being_synced" = "false",
+"storage_medium" = "hdd",
+"storage_format" = "V2",
+"inverted_index_storage_format" = "V2",
+"compression" = "ZSTD",
+"enable_unique_key_merge_on_write" = "true",
+"dynamic_partition.enable" = "false"
+);"#;
+        let props = parse_create_table(v3).unwrap().properties;
+        assert_eq!(props.replication_num, Some(3));
+        assert_eq!(
+            props.replication_allocation.as_deref(),
+            Some("tag.location.default: 2, tag.location.group_b: 1")
+        );
+        assert_eq!(props.compression.as_deref(), Some("ZSTD"));
+        assert_eq!(props.dynamic_partition_enabled, Some(false));
+        assert_eq!(props.storage_policy, None);
+
+        let bare = "CREATE TABLE `t` (\n  `k` int NULL\n) ENGINE=OLAP\nDUPLICATE KEY(`k`)\nDISTRIBUTED BY RANDOM BUCKETS 1;";
+        assert_eq!(
+            parse_create_table(bare).unwrap().properties,
+            TableProperties::default()
+        );
+    }
+}
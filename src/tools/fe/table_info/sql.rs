@@ -1,4 +1,5 @@
 use anyhow::Result;
+use std::sync::OnceLock;
 
 // Minimal ResultSet abstraction wrapping raw mysql output (-N -B -r -A)
 #[derive(Debug, Clone)]
@@ -6,15 +7,24 @@ pub struct ResultSet(pub String);
 
 pub struct MySqlExecutor {
     doris: crate::config_loader::DorisConfig,
+    /// Whether `information_schema.materialized_views` answered; decided by the first query
+    mv_view_available: OnceLock<bool>,
 }
 
 impl MySqlExecutor {
     pub fn from_config(doris: crate::config_loader::DorisConfig) -> Self {
-        Self { doris }
+        Self {
+            doris,
+            mv_view_available: OnceLock::new(),
+        }
     }
 
     pub fn query(&self, sql: &str) -> Result<ResultSet> {
-        let output = crate::tools::mysql::MySQLTool::query_sql_raw_with_config(&self.doris, sql)?;
+        let output = crate::tools::mysql::with_retry(
+            &crate::tools::mysql::RetryPolicy::default(),
+            "table info query",
+            || crate::tools::mysql::MySQLTool::query_sql_raw_with_config(&self.doris, sql),
+        )?;
         Ok(ResultSet(output))
     }
 }
@@ -53,3 +63,58 @@ pub fn query_partitions(exec: &MySqlExecutor, ident: &super::TableIdentity) -> R
     );
     exec.query(&sql)
 }
+
+pub fn query_tablets(exec: &MySqlExecutor, ident: &super::TableIdentity) -> Result<ResultSet> {
+    let sql = format!(
+        "SHOW TABLETS FROM `{}`.`{}`;",
+        ident.schema.replace("`", "``"),
+        ident.name.replace("`", "``")
+    );
+    exec.query(&sql)
+}
+
+/// SHOW TABLETS limited to one partition, for the bucket skew check
+pub fn query_partition_tablets(
+    exec: &MySqlExecutor,
+    ident: &super::TableIdentity,
+    partition: &str,
+) -> Result<ResultSet> {
+    let sql = format!(
+        "SHOW TABLETS FROM `{}`.`{}` PARTITION (`{}`);",
+        ident.schema.replace("`", "``"),
+        ident.name.replace("`", "``"),
+        partition.replace("`", "``")
+    );
+    exec.query(&sql)
+}
+
+/// `DESC <table> ALL`: the base index followed by every rollup and sync MV
+pub fn query_desc_all(exec: &MySqlExecutor, ident: &super::TableIdentity) -> Result<ResultSet> {
+    let sql = format!(
+        "DESC `{}`.`{}` ALL;",
+        ident.schema.replace("`", "``"),
+        ident.name.replace("`", "``")
+    );
+    exec.query(&sql)
+}
+
+/// Async materialized views whose definition mentions the table name, as
+/// "schema\tname\tdefinition" lines; None when the FE has no such view
+pub fn query_async_mvs(exec: &MySqlExecutor, ident: &super::TableIdentity) -> Option<ResultSet> {
+    if exec.mv_view_available.get() == Some(&false) {
+        return None;
+    }
+    let sql = format!(
+        "SELECT TABLE_SCHEMA, TABLE_NAME, REPLACE(REPLACE(VIEW_DEFINITION, '\\n', ' '), '\\t', ' ') \
+        FROM information_schema.materialized_views WHERE VIEW_DEFINITION LIKE '%{}%';",
+        ident
+            .name
+            .replace('\\', "\\\\")
+            .replace('\'', "''")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    let result = exec.query(&sql).ok();
+    let _ = exec.mv_view_available.set(result.is_some());
+    result
+}
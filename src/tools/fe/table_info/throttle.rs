@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Table collections looked at when deciding whether the FE is struggling
+const WINDOW: usize = 16;
+/// Fetching one table's metadata (a handful of SHOW statements) slower than
+/// this counts as slow
+const SLOW_TABLE: Duration = Duration::from_secs(3);
+/// Share of slow collections in the window that starts throttling
+const SLOW_SHARE: f64 = 0.5;
+const MIN_PAUSE: Duration = Duration::from_millis(500);
+const MAX_PAUSE: Duration = Duration::from_secs(5);
+
+/// How much a batch was slowed down to spare the FE
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThrottleSummary {
+    pub pauses: usize,
+    pub paused: Duration,
+}
+
+/// Pause before the next task when too many recent collections ran slow, or
+/// `None`. The pause is the average recent duration, so a busy FE gets
+/// roughly half the load for as long as it stays slow.
+fn pause_for(recent: &VecDeque<Duration>) -> Option<Duration> {
+    // Decide on a full window only; the first tables often warm caches
+    if recent.len() < WINDOW {
+        return None;
+    }
+    let slow = recent.iter().filter(|d| **d > SLOW_TABLE).count();
+    if (slow as f64) <= recent.len() as f64 * SLOW_SHARE {
+        return None;
+    }
+    let mean = recent.iter().sum::<Duration>() / recent.len() as u32;
+    Some(mean.clamp(MIN_PAUSE, MAX_PAUSE))
+}
+
+/// Shared by the workers of one batch
+#[derive(Default)]
+pub struct Throttle {
+    recent: Mutex<VecDeque<Duration>>,
+    summary: Mutex<ThrottleSummary>,
+}
+
+impl Throttle {
+    /// Record how long one table took; returns how long the worker should sleep
+    pub fn record(&self, elapsed: Duration) -> Option<Duration> {
+        let pause = {
+            let mut recent = self.recent.lock().ok()?;
+            recent.push_back(elapsed);
+            while recent.len() > WINDOW {
+                recent.pop_front();
+            }
+            pause_for(&recent)?
+        };
+        if let Ok(mut summary) = self.summary.lock() {
+            summary.pauses += 1;
+            summary.paused += pause;
+        }
+        Some(pause)
+    }
+
+    pub fn summary(&self) -> ThrottleSummary {
+        self.summary.lock().map(|s| *s).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttles_only_while_most_recent_tables_are_slow() {
+        let secs = |s: u64| Duration::from_secs(s);
+        let throttle = Throttle::default();
+        // Too few samples to judge, however slow
+        for _ in 0..WINDOW - 1 {
+            assert_eq!(throttle.record(secs(10)), None);
+        }
+        assert_eq!(throttle.record(secs(10)), Some(MAX_PAUSE));
+
+        let window = |slow: usize, each: u64| -> VecDeque<Duration> {
+            (0..WINDOW)
+                .map(|i| {
+                    if i < slow {
+                        secs(each)
+                    } else {
+                        Duration::from_millis(200)
+                    }
+                })
+                .collect()
+        };
+        assert_eq!(pause_for(&window(WINDOW / 2, 4)), None);
+        let pause = pause_for(&window(WINDOW / 2 + 1, 4)).unwrap();
+        assert!(pause > MIN_PAUSE && pause < secs(4));
+        assert_eq!(pause_for(&window(0, 0)), None);
+
+        // Fast tables push the slow ones out of the window again
+        for _ in 0..WINDOW {
+            throttle.record(Duration::from_millis(100));
+        }
+        assert_eq!(throttle.record(Duration::from_millis(100)), None);
+        let summary = throttle.summary();
+        assert!(summary.pauses >= 1 && summary.paused >= MAX_PAUSE);
+    }
+}
@@ -0,0 +1,343 @@
+use console::style;
+use std::collections::HashMap;
+
+use super::replica_health::ProcTable;
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::mysql::{ClusterInfo, MySQLTool};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+/// Where a tablet lives, from `SHOW TABLET <id>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabletLocation {
+    pub db: String,
+    pub table: String,
+    pub partition: String,
+    pub index: String,
+    pub detail_cmd: String,
+}
+
+/// One row of the DetailCmd PROC result
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaInfo {
+    pub replica_id: String,
+    pub backend_id: String,
+    pub version: i64,
+    pub last_failed_version: i64,
+    pub state: String,
+    pub is_bad: bool,
+    pub path_hash: String,
+}
+
+impl ReplicaInfo {
+    /// Reasons this replica needs attention; `max_version` is the highest among its peers
+    pub fn problems(&self, max_version: i64) -> Vec<&'static str> {
+        let mut problems = Vec::new();
+        if self.is_bad {
+            problems.push("BAD");
+        }
+        if self.version < max_version || self.last_failed_version > 0 {
+            problems.push("MISSING_VERSION");
+        }
+        if !self.state.is_empty() && !self.state.eq_ignore_ascii_case("NORMAL") {
+            problems.push("NOT_NORMAL");
+        }
+        problems
+    }
+}
+
+/// Numeric tablet ids from a comma or whitespace separated list; anything else is
+/// returned separately so it can be reported as not found
+pub fn parse_tablet_ids(input: &str) -> (Vec<String>, Vec<String>) {
+    let mut ids = Vec::new();
+    let mut invalid = Vec::new();
+    for token in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        let target = if token.chars().all(|c| c.is_ascii_digit()) {
+            &mut ids
+        } else {
+            &mut invalid
+        };
+        if !target.iter().any(|t| t == token) {
+            target.push(token.to_string());
+        }
+    }
+    (ids, invalid)
+}
+
+/// `SHOW TABLET` output; None when the FE does not know the tablet
+pub fn parse_show_tablet(output: &str) -> Option<TabletLocation> {
+    let table = ProcTable::parse(output);
+    let row = table.rows.first()?;
+    let field = |name: &str| {
+        table
+            .value(row, &[name])
+            .filter(|v| !v.is_empty() && !v.eq_ignore_ascii_case("NULL"))
+            .map(str::to_string)
+    };
+    let detail_cmd = field("DetailCmd")?;
+    Some(TabletLocation {
+        db: field("DbName")?,
+        table: field("TableName")?,
+        partition: field("PartitionName").unwrap_or_default(),
+        index: field("IndexName").unwrap_or_default(),
+        detail_cmd,
+    })
+}
+
+/// Replica rows of `SHOW PROC '/dbs/.../<tablet>'`
+pub fn parse_replicas(output: &str) -> Vec<ReplicaInfo> {
+    let table = ProcTable::parse(output);
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let text = |aliases: &[&str]| table.value(row, aliases).unwrap_or("").to_string();
+            let number = |aliases: &[&str]| {
+                table
+                    .value(row, aliases)
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(-1)
+            };
+            ReplicaInfo {
+                replica_id: text(&["ReplicaId"]),
+                backend_id: text(&["BackendId"]),
+                version: number(&["Version"]),
+                last_failed_version: number(&["LstFailedVersion"]),
+                state: text(&["State"]),
+                is_bad: text(&["IsBad"]).eq_ignore_ascii_case("true"),
+                path_hash: text(&["PathHash"]),
+            }
+        })
+        .collect()
+}
+
+/// DetailCmd is run verbatim, so only accept the PROC lookup FE generates
+fn is_proc_command(cmd: &str) -> bool {
+    let cmd = cmd.trim();
+    let cmd = cmd.strip_suffix(';').unwrap_or(cmd);
+    cmd.to_ascii_uppercase().starts_with("SHOW PROC '/DBS/") && !cmd.contains(';')
+}
+
+fn render_tablet(
+    id: &str,
+    location: &TabletLocation,
+    replicas: &[ReplicaInfo],
+    hosts: &HashMap<String, String>,
+) -> (String, usize) {
+    let mut out = format!(
+        "Tablet {id}: {}.{} partition {} index {}\n",
+        location.db, location.table, location.partition, location.index
+    );
+    if replicas.is_empty() {
+        out.push_str("  No replicas returned by the detail command\n");
+        return (out, 0);
+    }
+    let max_version = replicas.iter().map(|r| r.version).max().unwrap_or(-1);
+    out.push_str(&format!(
+        "  {:<14} {:<24} {:>10} {:>12} {:<10} {:<22} Problems\n",
+        "ReplicaId", "Backend", "Version", "LstFailed", "State", "PathHash"
+    ));
+    let mut flagged = 0;
+    for replica in replicas {
+        let backend = match hosts.get(&replica.backend_id) {
+            Some(host) => format!("{} ({host})", replica.backend_id),
+            None => replica.backend_id.clone(),
+        };
+        let problems = replica.problems(max_version);
+        if !problems.is_empty() {
+            flagged += 1;
+        }
+        out.push_str(&format!(
+            "  {:<14} {:<24} {:>10} {:>12} {:<10} {:<22} {}\n",
+            replica.replica_id,
+            ui::FormatHelper::truncate_string(&backend, 24),
+            replica.version,
+            replica.last_failed_version,
+            replica.state,
+            replica.path_hash,
+            if problems.is_empty() {
+                "-".to_string()
+            } else {
+                problems.join(",")
+            }
+        ));
+    }
+    (out, flagged)
+}
+
+fn backend_hosts() -> HashMap<String, String> {
+    ClusterInfo::load_from_file()
+        .map(|info| {
+            info.backends
+                .into_iter()
+                .map(|be| (be.backend_id, be.host))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn lookup(doris: &DorisConfig, id: &str) -> Result<Option<(TabletLocation, Vec<ReplicaInfo>)>> {
+    let output = match MySQLTool::query_sql_with_config(doris, &format!("SHOW TABLET {id};")) {
+        Ok(output) => output,
+        // Some releases reject unknown ids instead of returning a NULL row
+        Err(CliError::ToolExecutionFailed(msg))
+            if ["not exist", "not found"]
+                .iter()
+                .any(|m| msg.to_ascii_lowercase().contains(m)) =>
+        {
+            return Ok(None);
+        }
+        Err(e) => return Err(e),
+    };
+    let Some(location) = parse_show_tablet(&output) else {
+        return Ok(None);
+    };
+    if !is_proc_command(&location.detail_cmd) {
+        return Err(CliError::ToolExecutionFailed(format!(
+            "Unexpected DetailCmd for tablet {id}: {}",
+            location.detail_cmd
+        )));
+    }
+    let replicas = parse_replicas(&MySQLTool::query_sql_with_config(
+        doris,
+        &location.detail_cmd,
+    )?);
+    Ok(Some((location, replicas)))
+}
+
+/// Resolve tablet ids to table/partition and list their replicas
+pub struct FeTabletLookupTool;
+
+impl Tool for FeTabletLookupTool {
+    fn name(&self) -> &str {
+        "fe-tablet-lookup"
+    }
+
+    fn description(&self) -> &str {
+        "Resolve tablet ids to table, partition and replicas"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let input = ui::dialogs::input_text("Tablet id(s), comma separated", "")?;
+        let (ids, invalid) = parse_tablet_ids(&input);
+        if ids.is_empty() && invalid.is_empty() {
+            return Err(CliError::InvalidInput("No tablet id given".to_string()));
+        }
+
+        let doris = crate::config_loader::load_config()?;
+        let hosts = backend_hosts();
+        let mut report = String::new();
+        let mut found = 0;
+        let mut flagged = 0;
+        for id in &invalid {
+            ui::print_warning(&format!("Tablet {id} not found"));
+            report.push_str(&format!("Tablet {id}: not found\n\n"));
+        }
+        for id in &ids {
+            ui::print_info(&format!("Looking up tablet {id}..."));
+            match lookup(&doris, id) {
+                Ok(Some((location, replicas))) => {
+                    let (text, bad) = render_tablet(id, &location, &replicas, &hosts);
+                    found += 1;
+                    flagged += bad;
+                    ui::print_info("");
+                    ui::print_info(&text);
+                    if bad > 0 {
+                        ui::print_info(
+                            &style(format!("{bad} replica(s) of tablet {id} need attention"))
+                                .red()
+                                .to_string(),
+                        );
+                    }
+                    report.push_str(&text);
+                }
+                Ok(None) => {
+                    ui::print_warning(&format!("Tablet {id} not found"));
+                    report.push_str(&format!("Tablet {id}: not found\n"));
+                }
+                // Credentials are shared; the remaining ids would fail the same way
+                Err(
+                    e @ (CliError::MySQLAccessDenied(_) | CliError::MySQLPrivilegeDenied { .. }),
+                ) => {
+                    return Err(e);
+                }
+                Err(e) => {
+                    ui::print_warning(&format!("Tablet {id}: {e}"));
+                    report.push_str(&format!("Tablet {id}: lookup failed: {e}\n"));
+                }
+            }
+            report.push('\n');
+        }
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("tablet_lookup", "txt"),
+            report.as_bytes(),
+        )?;
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "{found} of {} tablet(s) found, {flagged} replica(s) flagged",
+                ids.len() + invalid.len()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_tablet_and_flags_replicas() {
+        let (ids, invalid) = parse_tablet_ids(" 10101, 10103 10101,abc;1 ,");
+        assert_eq!(ids, ["10101", "10103"]);
+        assert_eq!(invalid, ["abc;1"]);
+
+        let show = "DbName\tTableName\tPartitionName\tIndexName\tDbId\tTableId\tPartitionId\tIndexId\tIsSync\tOrder\tQueryHits\tDetailCmd
+sales\torders\tp20240501\torders\t10002\t10005\t10004\t10006\ttrue\t0\t0\tSHOW PROC '/dbs/10002/10005/partitions/10004/10006/10101';
+";
+        let location = parse_show_tablet(show).unwrap();
+        assert_eq!(
+            (location.db.as_str(), location.table.as_str()),
+            ("sales", "orders")
+        );
+        assert!(is_proc_command(&location.detail_cmd));
+        assert!(!is_proc_command("SHOW PROC '/dbs/1'; DROP TABLE t;"));
+
+        let missing = "DbName\tTableName\tPartitionName\tIndexName\tDbId\tTableId\tPartitionId\tIndexId\tIsSync\tOrder\tQueryHits\tDetailCmd
+NULL\tNULL\tNULL\tNULL\t-1\t-1\t-1\t-1\tfalse\t-1\t0\tNULL
+";
+        assert_eq!(parse_show_tablet(missing), None);
+        assert_eq!(parse_show_tablet(""), None);
+
+        let replicas = parse_replicas(
+            "ReplicaId\tBackendId\tVersion\tLstSuccessVersion\tLstFailedVersion\tLstFailedTime\tSchemaHash\tLocalDataSize\tRemoteDataSize\tRowCount\tState\tIsBad\tIsUserDrop\tVersionCount\tPathHash
+20001\t10001\t15\t15\t-1\tNULL\t1234\t100\t0\t10\tNORMAL\tfalse\tfalse\t3\t-887766
+20002\t10002\t12\t12\t13\t2024-05-01 02:00:00\t1234\t90\t0\t9\tNORMAL\tfalse\tfalse\t3\t445566
+20003\t10003\t15\t15\t-1\tNULL\t1234\t100\t0\t10\tNORMAL\ttrue\tfalse\t3\t778899
+",
+        );
+        assert_eq!(replicas.len(), 3);
+        assert!(replicas[0].problems(15).is_empty());
+        assert_eq!(replicas[1].problems(15), ["MISSING_VERSION"]);
+        assert_eq!(replicas[2].problems(15), ["BAD"]);
+
+        let hosts = HashMap::from([("10001".to_string(), "be1".to_string())]);
+        let (text, flagged) = render_tablet("10101", &location, &replicas, &hosts);
+        assert_eq!(flagged, 2);
+        assert!(text.starts_with("Tablet 10101: sales.orders partition p20240501"));
+        assert!(text.contains("10001 (be1)"));
+    }
+}
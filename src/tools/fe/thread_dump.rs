@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ui;
+
+/// Frames from the top of a stack that make up its prefix
+const STACK_PREFIX_FRAMES: usize = 5;
+/// Most common stack prefixes and contended locks listed in the digest
+const TOP_N: usize = 10;
+
+/// One thread of a HotSpot jstack or jcmd Thread.print dump
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ThreadInfo {
+    name: String,
+    /// `java.lang.Thread.State`; None for VM and GC threads
+    state: Option<String>,
+    /// `at ...` frames, topmost first
+    frames: Vec<String>,
+    /// Lock address from `- waiting to lock <0x...>`
+    waiting_to_lock: Option<String>,
+    /// Lock addresses from `- locked <0x...>`
+    locked: Vec<String>,
+}
+
+/// Threads and deadlocks read from a dump, with the class of each lock seen
+#[derive(Debug, Default)]
+struct ThreadDump {
+    threads: Vec<ThreadInfo>,
+    /// Names of the threads in each "Found one Java-level deadlock" section
+    deadlocks: Vec<Vec<String>>,
+    lock_classes: HashMap<String, String>,
+}
+
+/// Threads waiting for the same monitor
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockGroup {
+    pub lock: String,
+    /// Class of the locked object, e.g. `java.lang.Object`
+    pub class: String,
+    pub holder: Option<String>,
+    pub waiters: Vec<String>,
+}
+
+/// Quoted thread name at the start of a header line
+fn quoted_name(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix('"')?;
+    rest.rfind('"').map(|end| &rest[..end])
+}
+
+/// `<0x000000076b2a1234> (a java.lang.Object)` into address and class
+fn lock_ref(rest: &str) -> Option<(String, String)> {
+    let start = rest.find('<')?;
+    let end = start + rest[start..].find('>')?;
+    let class = rest[end + 1..]
+        .trim()
+        .trim_start_matches("(a ")
+        .trim_end_matches(')')
+        .to_string();
+    Some((rest[start + 1..end].to_string(), class))
+}
+
+/// Parse a dump; the jcmd `<pid>:` line and date header are skipped like any
+/// other line outside a thread
+fn parse(content: &str) -> ThreadDump {
+    let mut dump = ThreadDump::default();
+    // Whether indented lines belong to the last thread header
+    let mut in_thread = false;
+    let mut in_deadlock = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Found one Java-level deadlock") {
+            in_deadlock = true;
+            in_thread = false;
+            dump.deadlocks.push(Vec::new());
+            continue;
+        }
+        if in_deadlock {
+            if trimmed.starts_with("Java stack information") || trimmed.starts_with("Found ") {
+                in_deadlock = false;
+            } else if let Some(name) = line.strip_suffix(':').and_then(quoted_name)
+                && let Some(names) = dump.deadlocks.last_mut()
+                && !names.iter().any(|n| n == name)
+            {
+                names.push(name.to_string());
+            }
+            continue;
+        }
+
+        // Headers carry tid= or nid=; the `"name":` lines of the deadlock stacks do not
+        if line.starts_with('"') && (line.contains(" tid=") || line.contains(" nid=")) {
+            if let Some(name) = quoted_name(line) {
+                dump.threads.push(ThreadInfo {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                in_thread = true;
+            }
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            // `JNI global refs`, `Found 1 deadlock.` and the like end the thread list
+            in_thread = in_thread && trimmed.is_empty();
+            continue;
+        }
+        let Some(thread) = dump.threads.last_mut().filter(|_| in_thread) else {
+            continue;
+        };
+        if let Some(state) = trimmed.strip_prefix("java.lang.Thread.State:") {
+            thread.state = state.split_whitespace().next().map(str::to_string);
+        } else if let Some(frame) = trimmed.strip_prefix("at ") {
+            thread.frames.push(frame.to_string());
+        } else if let Some(rest) = trimmed.strip_prefix("- waiting to lock")
+            && let Some((lock, class)) = lock_ref(rest)
+        {
+            dump.lock_classes.insert(lock.clone(), class);
+            thread.waiting_to_lock = Some(lock);
+        } else if let Some(rest) = trimmed.strip_prefix("- locked")
+            && let Some((lock, class)) = lock_ref(rest)
+        {
+            dump.lock_classes.insert(lock.clone(), class);
+            thread.locked.push(lock);
+        }
+    }
+    dump
+}
+
+/// What the digest reports about one dump
+#[derive(Debug, Default)]
+pub struct ThreadDigest {
+    pub total: usize,
+    /// Threads without a Java state (VM, GC, compiler) count as `VM`
+    pub by_state: BTreeMap<String, usize>,
+    pub deadlocks: Vec<Vec<String>>,
+    /// Top frames shared by several threads, most common first
+    pub top_stacks: Vec<(Vec<String>, usize)>,
+    /// Monitors with waiting threads, most waiters first
+    pub lock_groups: Vec<LockGroup>,
+}
+
+pub fn digest(content: &str) -> ThreadDigest {
+    let dump = parse(content);
+
+    let mut by_state = BTreeMap::new();
+    for t in &dump.threads {
+        let state = t.state.clone().unwrap_or_else(|| "VM".to_string());
+        *by_state.entry(state).or_insert(0) += 1;
+    }
+
+    let mut stacks: HashMap<Vec<String>, usize> = HashMap::new();
+    for t in dump.threads.iter().filter(|t| !t.frames.is_empty()) {
+        let prefix = t.frames.iter().take(STACK_PREFIX_FRAMES).cloned().collect();
+        *stacks.entry(prefix).or_insert(0) += 1;
+    }
+    let mut top_stacks: Vec<(Vec<String>, usize)> = stacks.into_iter().collect();
+    top_stacks.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_stacks.truncate(TOP_N);
+
+    let mut waiters: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for t in &dump.threads {
+        if let Some(lock) = &t.waiting_to_lock {
+            waiters.entry(lock).or_default().push(t.name.clone());
+        }
+    }
+    let mut lock_groups: Vec<LockGroup> = waiters
+        .into_iter()
+        .map(|(lock, waiters)| LockGroup {
+            lock: lock.to_string(),
+            class: dump.lock_classes.get(lock).cloned().unwrap_or_default(),
+            holder: dump
+                .threads
+                .iter()
+                .find(|t| t.locked.iter().any(|l| l == lock))
+                .map(|t| t.name.clone()),
+            waiters,
+        })
+        .collect();
+    lock_groups.sort_by_key(|g| std::cmp::Reverse(g.waiters.len()));
+    lock_groups.truncate(TOP_N);
+
+    ThreadDigest {
+        total: dump.threads.len(),
+        by_state,
+        deadlocks: dump.deadlocks,
+        top_stacks,
+        lock_groups,
+    }
+}
+
+impl ThreadDigest {
+    /// Plain text appended to the dump file
+    pub fn render(&self) -> String {
+        let mut out = String::from("\n===== cloud-cli thread digest =====\n");
+        let states: Vec<String> = self
+            .by_state
+            .iter()
+            .map(|(state, n)| format!("{state} {n}"))
+            .collect();
+        out.push_str(&format!(
+            "Threads: {} ({})\n",
+            self.total,
+            states.join(", ")
+        ));
+
+        if self.deadlocks.is_empty() {
+            out.push_str("Deadlocks: none\n");
+        }
+        for names in &self.deadlocks {
+            out.push_str(&format!("DEADLOCK between: {}\n", names.join(", ")));
+        }
+
+        if !self.lock_groups.is_empty() {
+            out.push_str("\nThreads blocked on the same lock:\n");
+            for g in &self.lock_groups {
+                let holder = g.holder.as_deref().unwrap_or("unknown");
+                out.push_str(&format!(
+                    "  <{}> ({}) held by \"{holder}\", {} waiting: {}\n",
+                    g.lock,
+                    g.class,
+                    g.waiters.len(),
+                    g.waiters.join(", ")
+                ));
+            }
+        }
+
+        if !self.top_stacks.is_empty() {
+            out.push_str(&format!(
+                "\nMost common stacks (top {STACK_PREFIX_FRAMES} frames):\n"
+            ));
+            for (frames, count) in &self.top_stacks {
+                out.push_str(&format!("  {count} thread(s)\n"));
+                for frame in frames {
+                    out.push_str(&format!("      at {frame}\n"));
+                }
+            }
+        }
+        out
+    }
+
+    /// Console version: deadlocks in red, blocked threads as warnings
+    pub fn print(&self) {
+        let states: Vec<String> = self
+            .by_state
+            .iter()
+            .map(|(state, n)| format!("{state} {n}"))
+            .collect();
+        ui::print_info(&format!("Threads: {} ({})", self.total, states.join(", ")));
+        for names in &self.deadlocks {
+            ui::print_error(&format!(
+                "Java-level deadlock between: {}",
+                names.join(", ")
+            ));
+        }
+        for g in self.lock_groups.iter().filter(|g| g.waiters.len() > 1) {
+            ui::print_warning(&format!(
+                "{} threads wait for <{}> ({}) held by {}",
+                g.waiters.len(),
+                g.lock,
+                g.class,
+                g.holder.as_deref().unwrap_or("an unknown thread")
+            ));
+        }
+        if let Some((frames, count)) = self.top_stacks.first() {
+            ui::print_info(&format!(
+                "Most common stack ({count} threads): {}",
+                frames.first().map(String::as_str).unwrap_or_default()
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JCMD_DUMP: &str = r#"12345:
+2026-10-15 10:00:00
+Full thread dump OpenJDK 64-Bit Server VM (17.0.8+7 mixed mode, sharing):
+
+"worker-1" #21 daemon prio=5 os_prio=0 cpu=10.00ms elapsed=5.00s tid=0x00007f00 nid=0x5a03 waiting for monitor entry  [0x00007f01]
+   java.lang.Thread.State: BLOCKED (on object monitor)
+	at org.apache.doris.catalog.Database.readLock(Database.java:120)
+	- waiting to lock <0x00000000c1> (a java.lang.Object)
+	at org.apache.doris.qe.StmtExecutor.execute(StmtExecutor.java:500)
+
+"worker-2" #22 daemon prio=5 os_prio=0 cpu=10.00ms elapsed=5.00s tid=0x00007f02 nid=0x5a04 waiting for monitor entry  [0x00007f03]
+   java.lang.Thread.State: BLOCKED (on object monitor)
+	at org.apache.doris.catalog.Database.readLock(Database.java:120)
+	- waiting to lock <0x00000000c1> (a java.lang.Object)
+	at org.apache.doris.qe.StmtExecutor.execute(StmtExecutor.java:500)
+
+"checkpointer" #30 daemon prio=5 os_prio=0 tid=0x00007f04 nid=0x5a05 runnable  [0x00007f05]
+   java.lang.Thread.State: RUNNABLE
+	at org.apache.doris.master.Checkpoint.doCheckpoint(Checkpoint.java:90)
+	- locked <0x00000000c1> (a java.lang.Object)
+	- waiting to lock <0x00000000c2> (a java.util.HashMap)
+
+"editlog" #31 daemon prio=5 os_prio=0 tid=0x00007f06 nid=0x5a06 waiting for monitor entry  [0x00007f07]
+   java.lang.Thread.State: BLOCKED (on object monitor)
+	at org.apache.doris.persist.EditLog.logEdit(EditLog.java:40)
+	- locked <0x00000000c2> (a java.util.HashMap)
+
+"VM Thread" os_prio=0 cpu=50.00ms elapsed=5.00s tid=0x00007f08 nid=0x5a07 runnable
+
+JNI global refs: 20, weak refs: 0
+
+
+Found one Java-level deadlock:
+=============================
+"checkpointer":
+  waiting to lock monitor 0x00007e00 (object 0x00000000c2, a java.util.HashMap),
+  which is held by "editlog"
+"editlog":
+  waiting to lock monitor 0x00007e01 (object 0x00000000c1, a java.lang.Object),
+  which is held by "checkpointer"
+
+Java stack information for the threads listed above:
+===================================================
+"checkpointer":
+	at org.apache.doris.master.Checkpoint.doCheckpoint(Checkpoint.java:90)
+
+Found 1 deadlock.
+"#;
+
+    #[test]
+    fn digests_states_deadlocks_and_lock_contention() {
+        let d = digest(JCMD_DUMP);
+
+        assert_eq!(d.total, 5);
+        assert_eq!(d.by_state.get("BLOCKED"), Some(&3));
+        assert_eq!(d.by_state.get("RUNNABLE"), Some(&1));
+        assert_eq!(d.by_state.get("VM"), Some(&1));
+        assert_eq!(d.deadlocks, [vec!["checkpointer", "editlog"]]);
+
+        assert_eq!(d.top_stacks[0].1, 2);
+        assert!(d.top_stacks[0].0[0].starts_with("org.apache.doris.catalog.Database.readLock"));
+
+        let g = &d.lock_groups[0];
+        assert_eq!(g.lock, "0x00000000c1");
+        assert_eq!(g.class, "java.lang.Object");
+        assert_eq!(g.holder.as_deref(), Some("checkpointer"));
+        assert_eq!(g.waiters, ["worker-1", "worker-2"]);
+
+        // A jstack dump has no `<pid>:` line and parses the same
+        let jstack = JCMD_DUMP.split_once('\n').unwrap().1;
+        assert_eq!(digest(jstack).total, 5);
+        assert!(
+            d.render()
+                .contains("DEADLOCK between: checkpointer, editlog\n")
+        );
+    }
+}
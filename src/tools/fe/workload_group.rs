@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+
+use super::replica_health::ProcTable;
+use crate::config::Config;
+use crate::core::version::DorisVersion;
+use crate::error::{CliError, Result};
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::mysql::MySQLTool;
+use crate::tools::mysql::retry;
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+use crate::ui::table::{Align, TableBuilder};
+
+/// Share of a limit at which a group is reported as close to it
+const NEAR_LIMIT: f64 = 0.9;
+
+/// Doris stores "no limit" for concurrency and queue size as Integer.MAX_VALUE
+const UNLIMITED: u64 = i32::MAX as u64;
+
+const SHOW_GROUPS: &str = "SHOW WORKLOAD GROUPS;";
+const SCHEMA_GROUPS: &str = "SELECT * FROM information_schema.workload_groups;";
+const ACTIVE_QUERIES: &str = "SELECT WORKLOAD_GROUP_ID, QUERY_STATUS, COUNT(*) AS QUERY_NUM \
+     FROM information_schema.active_queries GROUP BY WORKLOAD_GROUP_ID, QUERY_STATUS;";
+const RESOURCE_USAGE: &str = "SELECT WORKLOAD_GROUP_ID, AVG(CPU_USAGE_PERCENT) AS CPU_USAGE_PERCENT \
+     FROM information_schema.workload_group_resource_usage GROUP BY WORKLOAD_GROUP_ID;";
+
+/// Limits and live counters of one workload group
+#[derive(Debug, Clone, Default, PartialEq)]
+struct WorkloadGroup {
+    id: String,
+    name: String,
+    cpu_share: String,
+    cpu_hard_limit: String,
+    memory_limit: String,
+    /// `None` when unlimited
+    max_concurrency: Option<u64>,
+    max_queue_size: Option<u64>,
+    running: Option<u64>,
+    queued: Option<u64>,
+    /// Average over the backends, in percent
+    cpu_usage: Option<f64>,
+}
+
+impl WorkloadGroup {
+    /// Why the group needs a look: queued queries or a limit almost reached
+    fn findings(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        let queued = self.queued.unwrap_or(0);
+        if queued > 0 {
+            let of = self
+                .max_queue_size
+                .map(|max| format!(" of {max}"))
+                .unwrap_or_default();
+            out.push(format!("{queued}{of} queries queued"));
+        }
+        if let (Some(running), Some(max)) = (self.running, self.max_concurrency)
+            && max > 0
+            && running as f64 >= max as f64 * NEAR_LIMIT
+        {
+            out.push(format!("{running} of {max} concurrent queries running"));
+        }
+        if let (Some(usage), Some(limit)) = (self.cpu_usage, percent(&self.cpu_hard_limit))
+            && usage >= limit * NEAR_LIMIT
+        {
+            out.push(format!("CPU at {usage:.1}% of a {limit}% hard limit"));
+        }
+        out
+    }
+}
+
+/// A positive percentage such as `30%`; `-1` and empty values are unset
+fn percent(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .ok()
+        .filter(|p| *p > 0.0)
+}
+
+fn limit(value: Option<&str>) -> Option<u64> {
+    value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|v| *v > 0 && *v < UNLIMITED)
+}
+
+/// Groups from `SHOW WORKLOAD GROUPS` or `information_schema.workload_groups`.
+///
+/// 2.0 prints one `Id, Name, Item, Value` row per property; later releases
+/// print one row per group, so the narrow form is pivoted first.
+fn parse_groups(output: &str) -> Vec<WorkloadGroup> {
+    let table = pivot_items(ProcTable::parse(output));
+    table
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let name = table.value(row, &["Name"])?.to_string();
+            let text = |aliases: &[&str]| table.value(row, aliases).unwrap_or("").to_string();
+            let count = |aliases: &[&str]| {
+                table
+                    .value(row, aliases)
+                    .and_then(|v| v.trim().parse().ok())
+            };
+            Some(WorkloadGroup {
+                id: text(&["Id"]),
+                name,
+                cpu_share: text(&["cpu_share"]),
+                cpu_hard_limit: text(&["cpu_hard_limit"]),
+                memory_limit: text(&["memory_limit"]),
+                max_concurrency: limit(table.value(row, &["max_concurrency"])),
+                max_queue_size: limit(table.value(row, &["max_queue_size"])),
+                running: count(&["running_query_num"]),
+                queued: count(&["waiting_query_num"]),
+                cpu_usage: None,
+            })
+        })
+        .collect()
+}
+
+fn pivot_items(table: ProcTable) -> ProcTable {
+    let (Some(item), Some(value)) = (table.column(&["Item"]), table.column(&["Value"])) else {
+        return table;
+    };
+    let mut headers = vec!["Id".to_string(), "Name".to_string()];
+    let mut groups: Vec<(String, String, HashMap<String, String>)> = Vec::new();
+    for row in &table.rows {
+        let id = table.value(row, &["Id"]).unwrap_or_default().to_string();
+        let name = table.value(row, &["Name"]).unwrap_or_default().to_string();
+        let (Some(key), Some(val)) = (row.get(item), row.get(value)) else {
+            continue;
+        };
+        if !headers.contains(key) {
+            headers.push(key.clone());
+        }
+        match groups.iter_mut().find(|g| g.1 == name) {
+            Some(group) => {
+                group.2.insert(key.clone(), val.clone());
+            }
+            None => groups.push((id, name, HashMap::from([(key.clone(), val.clone())]))),
+        }
+    }
+    let rows = groups
+        .into_iter()
+        .map(|(id, name, values)| {
+            let mut row = vec![id, name];
+            row.extend(
+                headers[2..]
+                    .iter()
+                    .map(|h| values.get(h).cloned().unwrap_or_default()),
+            );
+            row
+        })
+        .collect();
+    ProcTable { headers, rows }
+}
+
+/// Running and queued query counts per group id from `active_queries`
+fn parse_queue(output: &str) -> HashMap<String, (u64, u64)> {
+    let table = ProcTable::parse(output);
+    let mut out: HashMap<String, (u64, u64)> = HashMap::new();
+    for row in &table.rows {
+        let Some(id) = table.value(row, &["WORKLOAD_GROUP_ID"]) else {
+            continue;
+        };
+        let num: u64 = table
+            .value(row, &["QUERY_NUM"])
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        let entry = out.entry(id.to_string()).or_default();
+        match table.value(row, &["QUERY_STATUS"]) {
+            Some(status) if status.eq_ignore_ascii_case("QUEUED") => entry.1 += num,
+            _ => entry.0 += num,
+        }
+    }
+    out
+}
+
+fn parse_usage(output: &str) -> HashMap<String, f64> {
+    let table = ProcTable::parse(output);
+    table
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let id = table.value(row, &["WORKLOAD_GROUP_ID"])?;
+            let usage = table.value(row, &["CPU_USAGE_PERCENT"])?.parse().ok()?;
+            Some((id.to_string(), usage))
+        })
+        .collect()
+}
+
+/// Fill the counters the group listing lacks from the optional sources;
+/// `queue` is `None` when `active_queries` could not be read
+fn merge(
+    groups: &mut [WorkloadGroup],
+    queue: Option<&HashMap<String, (u64, u64)>>,
+    usage: &HashMap<String, f64>,
+) {
+    for group in groups {
+        if let Some(queue) = queue
+            && group.running.is_none()
+            && group.queued.is_none()
+        {
+            let (running, queued) = queue.get(&group.id).copied().unwrap_or_default();
+            group.running = Some(running);
+            group.queued = Some(queued);
+        }
+        group.cpu_usage = usage.get(&group.id).copied();
+    }
+}
+
+fn render_report(groups: &[WorkloadGroup]) -> String {
+    let dash = || "-".to_string();
+    let number = |v: Option<u64>| v.map(|n| n.to_string()).unwrap_or_else(dash);
+    let text = |v: &str| {
+        if v.is_empty() || v == "-1" {
+            dash()
+        } else {
+            v.to_string()
+        }
+    };
+    let mut table = TableBuilder::new([
+        "Group",
+        "CPU share",
+        "CPU hard limit",
+        "Memory limit",
+        "Max concurrency",
+        "Max queue",
+        "Running",
+        "Queued",
+        "CPU usage",
+    ])
+    .max_width(0, 32);
+    for column in 1..9 {
+        table = table.align(column, Align::Right);
+    }
+    for g in groups {
+        table.push_row([
+            g.name.clone(),
+            text(&g.cpu_share),
+            text(&g.cpu_hard_limit),
+            text(&g.memory_limit),
+            g.max_concurrency
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "unlimited".to_string()),
+            g.max_queue_size
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "unlimited".to_string()),
+            number(g.running),
+            number(g.queued),
+            g.cpu_usage.map(|u| format!("{u:.1}%")).unwrap_or_else(dash),
+        ]);
+    }
+
+    let mut out = table.render();
+    let flagged: Vec<(&WorkloadGroup, Vec<String>)> = groups
+        .iter()
+        .map(|g| (g, g.findings()))
+        .filter(|(_, f)| !f.is_empty())
+        .collect();
+    out.push('\n');
+    if flagged.is_empty() {
+        out.push_str("No group has queued queries or is near its limits.\n");
+    } else {
+        out.push_str("Groups needing attention:\n");
+        for (group, findings) in flagged {
+            out.push_str(&format!("  - {}: {}\n", group.name, findings.join("; ")));
+        }
+    }
+    out
+}
+
+/// SQL errors mean the release lacks the statement or table; connection and
+/// credential failures are real errors
+fn unsupported(err: &CliError) -> bool {
+    matches!(err, CliError::ToolExecutionFailed(_)) && !retry::is_retryable(err)
+}
+
+/// Shows workload group limits and query queue usage
+pub struct FeWorkloadGroupTool;
+
+impl Tool for FeWorkloadGroupTool {
+    fn name(&self) -> &str {
+        "fe-workload-groups"
+    }
+
+    fn description(&self) -> &str {
+        "Show workload group limits, running/queued queries and groups near their limits"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config()?;
+
+        ui::print_info("Querying workload groups...");
+        let show = MySQLTool::query_sql_with_config(&doris, SHOW_GROUPS);
+        let schema = MySQLTool::query_sql_with_config(&doris, SCHEMA_GROUPS);
+        let (show, schema) = match (show, schema) {
+            (Err(e), _) | (_, Err(e)) if !unsupported(&e) => return Err(e),
+            (Err(_), Err(_)) => {
+                return Ok(ExecutionResult::console(
+                    "Workload groups are not supported on this version",
+                ));
+            }
+            (show, schema) => (show.unwrap_or_default(), schema.unwrap_or_default()),
+        };
+
+        // Prefer the typed schema table for limits, but only SHOW has the FE queue counters
+        let shown = parse_groups(&show);
+        let mut groups = parse_groups(&schema);
+        if groups.is_empty() {
+            groups = shown.clone();
+        }
+        for group in &mut groups {
+            if let Some(s) = shown.iter().find(|s| s.name == group.name) {
+                group.running = s.running;
+                group.queued = s.queued;
+            }
+        }
+        if groups.is_empty() {
+            return Err(CliError::ToolExecutionFailed(
+                "No workload groups returned".to_string(),
+            ));
+        }
+
+        // Queue and usage tables arrived in later releases; without them the columns stay empty
+        let active = MySQLTool::query_sql_with_config(&doris, ACTIVE_QUERIES).ok();
+        let usage = MySQLTool::query_sql_with_config(&doris, RESOURCE_USAGE).unwrap_or_default();
+        let queue = active.as_deref().map(parse_queue);
+        merge(&mut groups, queue.as_ref(), &parse_usage(&usage));
+        let active = active.unwrap_or_default();
+
+        let report = render_report(&groups);
+        if !config.stdout_only {
+            ui::print_info("");
+            for line in report.lines() {
+                ui::print_info(line);
+            }
+        }
+
+        let mut content = report;
+        for (title, raw) in [
+            (SHOW_GROUPS, &show),
+            (SCHEMA_GROUPS, &schema),
+            (ACTIVE_QUERIES, &active),
+            (RESOURCE_USAGE, &usage),
+        ] {
+            content.push_str(&format!("\n==== {title}\n"));
+            content.push_str(if raw.trim().is_empty() {
+                "(not available)\n"
+            } else {
+                raw
+            });
+        }
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("fe_workload_groups", "txt"),
+            content.as_bytes(),
+        )?;
+
+        let flagged = groups.iter().filter(|g| !g.findings().is_empty()).count();
+        Ok(ExecutionResult::new(
+            output_path,
+            format!(
+                "{} workload group(s), {flagged} needing attention",
+                groups.len()
+            ),
+        ))
+    }
+
+    fn min_version(&self) -> Option<DorisVersion> {
+        // Workload groups were introduced in 2.0
+        Some(DorisVersion::new(2, 0, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_both_listing_forms_and_flags_busy_groups() {
+        let wide = "Id\tName\tcpu_share\tmemory_limit\tmax_concurrency\tmax_queue_size\t\
+                    cpu_hard_limit\trunning_query_num\twaiting_query_num\n\
+                    1\tnormal\t1024\t30%\t2147483647\t0\t-1\t3\t0\n\
+                    2\tetl\t512\t20%\t10\t50\t40%\t10\t4\n";
+        let groups = parse_groups(wide);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].max_concurrency, None);
+        assert!(groups[0].findings().is_empty());
+        assert_eq!(groups[1].max_queue_size, Some(50));
+        assert_eq!(groups[1].findings().len(), 2);
+
+        let narrow = "Id\tName\tItem\tValue\n\
+                      1\tnormal\tcpu_share\t10\n\
+                      1\tnormal\tmemory_limit\t30%\n\
+                      7\tadhoc\tmax_concurrency\t5\n";
+        let groups = parse_groups(narrow);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].memory_limit, "30%");
+        assert_eq!(groups[1].id, "7");
+        assert_eq!(groups[1].max_concurrency, Some(5));
+
+        let mut groups = groups;
+        let queue = parse_queue(
+            "WORKLOAD_GROUP_ID\tQUERY_STATUS\tQUERY_NUM\n7\tRUNNING\t5\n7\tQUEUED\t2\n",
+        );
+        let usage = parse_usage("WORKLOAD_GROUP_ID\tCPU_USAGE_PERCENT\n1\t12.5\n");
+        merge(&mut groups, Some(&queue), &usage);
+        assert_eq!((groups[0].running, groups[0].queued), (Some(0), Some(0)));
+        assert_eq!(groups[0].cpu_usage, Some(12.5));
+        assert_eq!(groups[1].findings().len(), 2);
+
+        let report = render_report(&groups);
+        assert!(report.contains("adhoc: 2 queries queued; 5 of 5 concurrent queries running"));
+    }
+}
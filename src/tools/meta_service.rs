@@ -0,0 +1,236 @@
+use std::process::Command;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::config_loader::DorisConfig;
+use crate::core::version::DorisVersion;
+use crate::error::Result;
+use crate::executor;
+use crate::tools::common::file_name;
+use crate::tools::common::fs_utils;
+use crate::tools::doctor::{self, CheckResult, CheckStatus};
+use crate::tools::{ExecutionResult, Tool};
+use crate::ui;
+
+/// brpc builtin pages, tried in order; either one proves the service answers HTTP
+const BRPC_STATUS_PAGES: &[&str] = &["/status", "/vars"];
+const FE_HEALTH_PATH: &str = "/api/health";
+const HTTP_PROBE_SECS: u64 = 2;
+const NOT_CLOUD: &str = "not a cloud deployment (meta_service_endpoint not set)";
+
+/// Split `meta_service_endpoint` into host/port pairs and the entries that do not parse
+pub fn parse_endpoints(raw: &str) -> (Vec<(String, u16)>, Vec<String>) {
+    let mut endpoints = Vec::new();
+    let mut invalid = Vec::new();
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry
+            .rsplit_once(':')
+            .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)))
+        {
+            Some((host, port)) if !host.is_empty() => endpoints.push((host.to_string(), port)),
+            _ => invalid.push(entry.to_string()),
+        }
+    }
+    (endpoints, invalid)
+}
+
+/// HTTP status code of a GET, or None when nothing answered
+fn http_status(host: &str, port: u16, path: &str) -> Option<u16> {
+    let url = format!("http://{host}:{port}{path}");
+    let mut curl_cmd = Command::new("curl");
+    curl_cmd.args([
+        "-sS",
+        "-o",
+        "/dev/null",
+        "-w",
+        "%{http_code}",
+        "--max-time",
+        &HTTP_PROBE_SECS.to_string(),
+        &url,
+    ]);
+    let output = executor::execute_command(&mut curl_cmd, "curl").ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()
+        .filter(|code| *code != 0)
+}
+
+fn format_latency(latency: Duration) -> String {
+    format!("{:.1} ms", latency.as_secs_f64() * 1000.0)
+}
+
+fn check_endpoint(host: &str, port: u16) -> CheckResult {
+    let name = format!("Meta service {host}:{port}");
+    let latency = match doctor::tcp_connect_latency(host, port) {
+        Ok(latency) => latency,
+        Err(e) => {
+            return CheckResult::fail(
+                &name,
+                format!("unreachable: {e}"),
+                "Check that meta-service is running and brpc_listen_port is open",
+            );
+        }
+    };
+    let page = BRPC_STATUS_PAGES
+        .iter()
+        .find_map(|path| match http_status(host, port, path) {
+            Some(200) => Some(format!("{path} 200")),
+            _ => None,
+        })
+        .unwrap_or_else(|| "no brpc status page".to_string());
+    CheckResult::pass(
+        &name,
+        format!("tcp connect {}, {page}", format_latency(latency)),
+    )
+}
+
+fn check_fe_cloud_http(doris: &DorisConfig, port: u16) -> CheckResult {
+    const NAME: &str = "FE cloud HTTP port";
+    let host = doris
+        .mysql
+        .as_ref()
+        .and_then(|m| m.host.clone())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let latency = match doctor::tcp_connect_latency(&host, port) {
+        Ok(latency) => latency,
+        Err(e) => {
+            return CheckResult::fail(
+                NAME,
+                format!("{host}:{port} unreachable: {e}"),
+                "Check cloud_http_port in fe.conf and that the FE is running",
+            );
+        }
+    };
+    match http_status(&host, port, FE_HEALTH_PATH) {
+        Some(200) => CheckResult::pass(
+            NAME,
+            format!(
+                "{host}:{port}{FE_HEALTH_PATH} 200 ({})",
+                format_latency(latency)
+            ),
+        ),
+        other => CheckResult::fail(
+            NAME,
+            format!(
+                "{host}:{port}{FE_HEALTH_PATH} returned {}",
+                other.map_or("no response".to_string(), |c| c.to_string())
+            ),
+            "FE is up but not healthy; check fe.log for meta-service connection errors",
+        ),
+    }
+}
+
+/// Meta-service and FE cloud port checks; a single skip when this is not a cloud deployment
+pub fn check_meta_service(doris: Option<&DorisConfig>) -> Vec<CheckResult> {
+    const NAME: &str = "Meta service";
+    let Some(doris) = doris else {
+        return vec![CheckResult::skip(NAME, "no Doris configuration loaded")];
+    };
+    let Some(raw) = doris
+        .meta_service_endpoint
+        .as_deref()
+        .filter(|r| !r.trim().is_empty())
+    else {
+        return vec![CheckResult::skip(NAME, NOT_CLOUD)];
+    };
+
+    let (endpoints, invalid) = parse_endpoints(raw);
+    let mut results: Vec<CheckResult> = invalid
+        .iter()
+        .map(|entry| {
+            CheckResult::fail(
+                NAME,
+                format!("invalid endpoint '{entry}'"),
+                "meta_service_endpoint must be a comma-separated host:port list",
+            )
+        })
+        .collect();
+
+    // Probe in parallel, as with BE webservers, so dead endpoints cost one timeout
+    results.extend(std::thread::scope(|s| {
+        let handles: Vec<_> = endpoints
+            .iter()
+            .map(|(host, port)| s.spawn(move || check_endpoint(host, *port)))
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().ok())
+            .collect::<Vec<_>>()
+    }));
+
+    if let Some(port) = doris.cloud_http_port {
+        results.push(check_fe_cloud_http(doris, port));
+    }
+    results
+}
+
+/// Checks reachability and latency of the cloud-mode meta-service endpoints
+pub struct MetaServiceCheckTool;
+
+impl Tool for MetaServiceCheckTool {
+    fn name(&self) -> &str {
+        "meta-service-check"
+    }
+
+    fn description(&self) -> &str {
+        "Check reachability and latency of cloud meta-service endpoints"
+    }
+
+    fn requires_pid(&self) -> bool {
+        false
+    }
+
+    fn min_version(&self) -> Option<DorisVersion> {
+        // Storage-compute separation arrived in 3.0
+        Some(DorisVersion::new(3, 0, 0))
+    }
+
+    fn execute(&self, config: &Config, _pid: u32) -> Result<ExecutionResult> {
+        let doris = crate::config_loader::load_config().ok();
+        let results = check_meta_service(doris.as_ref());
+
+        if results.iter().all(|r| r.status == CheckStatus::Skip) {
+            let reason = results
+                .first()
+                .map_or(NOT_CLOUD, |r| r.detail.as_str())
+                .to_string();
+            ui::print_info(&format!("Meta service check skipped: {reason}"));
+            return Ok(ExecutionResult::console(format!("Skipped: {reason}")));
+        }
+
+        doctor::print_results(&results);
+        let (passed, failed) = doctor::count_status(&results);
+        let output_path = fs_utils::write_artifact(
+            config,
+            &file_name::timestamped("meta_service", "txt"),
+            doctor::render_report("cloud-cli meta-service report", &results).as_bytes(),
+        )?;
+
+        Ok(ExecutionResult::new(
+            output_path,
+            format!("Meta service check: {passed} passed, {failed} failed"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_endpoint_list_and_flags_bad_entries() {
+        let (endpoints, invalid) = parse_endpoints(" 10.0.0.1:5000, ms-2:5000 ,bad, :5000,");
+        assert_eq!(
+            endpoints,
+            [("10.0.0.1".to_string(), 5000), ("ms-2".to_string(), 5000)]
+        );
+        assert_eq!(invalid, ["bad", ":5000"]);
+
+        let doris = DorisConfig::default();
+        let results = check_meta_service(Some(&doris));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, CheckStatus::Skip);
+        assert_eq!(results[0].detail, NOT_CLOUD);
+    }
+}
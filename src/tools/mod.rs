@@ -1,17 +1,88 @@
 pub mod be;
 pub mod common;
+pub mod context;
+pub mod doctor;
 pub mod fe;
+pub mod meta_service;
 pub mod mysql;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::core::version::DorisVersion;
+use crate::error::{CliError, Result};
+use crate::tools::common::{format_utils, fs_utils, progress};
+use crate::tools::context::ToolContext;
+use chrono::{DateTime, Utc};
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// `ExecutionResult::output_path` sentinel for output that only went to the console
+pub const CONSOLE_OUTPUT: &str = "console_output";
 
 /// Result of executing a tool
 #[derive(Debug)]
 pub struct ExecutionResult {
     pub output_path: PathBuf,
     pub message: String,
+    pub started_at: DateTime<Utc>,
+    /// Wall-clock run time, filled in by the executor
+    pub duration: Duration,
+    pub output_size_bytes: Option<u64>,
+}
+
+impl ExecutionResult {
+    pub fn new(output_path: PathBuf, message: impl Into<String>) -> Self {
+        let output_size_bytes = fs_utils::output_size(&output_path);
+        Self {
+            output_path,
+            message: message.into(),
+            started_at: Utc::now(),
+            duration: Duration::ZERO,
+            output_size_bytes,
+        }
+    }
+
+    /// Result of a tool that only printed to the console
+    pub fn console(message: impl Into<String>) -> Self {
+        Self::new(PathBuf::from(CONSOLE_OUTPUT), message)
+    }
+
+    pub fn with_timing(mut self, started_at: DateTime<Utc>, duration: Duration) -> Self {
+        self.started_at = started_at;
+        self.duration = duration;
+        self
+    }
+
+    /// One-line summary such as "completed in 12.4s, wrote 356.00 MB to <path>"
+    pub fn summary(&self) -> String {
+        let elapsed = if self.duration.as_secs() >= 60 {
+            progress::format_duration(self.duration)
+        } else {
+            format!("{:.1}s", self.duration.as_secs_f64())
+        };
+        match self.output_size_bytes {
+            Some(size) if !self.is_console_output() => format!(
+                "completed in {elapsed}, wrote {} to {}",
+                format_utils::format_bytes(size, 2, false),
+                self.output_path.display()
+            ),
+            _ => format!("completed in {elapsed}"),
+        }
+    }
+
+    pub fn is_console_output(&self) -> bool {
+        self.output_path.as_os_str() == CONSOLE_OUTPUT
+    }
+}
+
+/// What a tool touches besides `output_dir`; read-only mode only runs [`Impact::ReadOnly`] tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Impact {
+    /// Queries and collects, writing only into `output_dir`
+    ReadOnly,
+    /// Attaches to or pauses the process: debuggers, profilers, heap dumps
+    ProcessAttach,
+    /// Runs scripts from the Doris install dir that write their results there
+    WritesInstallDir,
 }
 
 /// Trait for diagnostic tools that can be executed against processes
@@ -22,10 +93,44 @@ pub trait Tool {
 
     fn execute(&self, config: &Config, pid: u32) -> Result<ExecutionResult>;
 
+    /// Run with inputs and status lines going through `ctx` instead of the terminal.
+    ///
+    /// Tools that have not been moved onto the context still prompt, so they
+    /// refuse to run under a non-interactive one.
+    fn execute_with_ctx(
+        &self,
+        config: &Config,
+        pid: u32,
+        ctx: &dyn ToolContext,
+    ) -> Result<ExecutionResult> {
+        if !ctx.is_interactive() {
+            return Err(CliError::InvalidInput(format!(
+                "{} needs the interactive UI",
+                self.name()
+            )));
+        }
+        self.execute(config, pid)
+    }
+
     /// Indicates whether the tool requires a process PID to execute.
     fn requires_pid(&self) -> bool {
         true
     }
+
+    /// Whether the tool is expensive for the target process (pauses, attaches, full GC).
+    fn is_heavy(&self) -> bool {
+        false
+    }
+
+    /// Impact on the host and process, checked before running in read-only mode.
+    fn impact(&self) -> Impact {
+        Impact::ReadOnly
+    }
+
+    /// Oldest Doris release the tool works with; None when any version will do.
+    fn min_version(&self) -> Option<DorisVersion> {
+        None
+    }
 }
 
 /// Registry for all available diagnostic tools
@@ -43,14 +148,26 @@ impl Default for ToolRegistry {
 impl ToolRegistry {
     /// Creates a new tool registry with all available tools
     pub fn new() -> Self {
+        use crate::config_loader::Environment;
         use crate::tools::be::{
-            BeListTool, BeVarsTool, MemzGlobalTool, MemzTool, PipelineTasksTool, PstackTool,
+            BeCompactionTool, BeCrashInspectorTool, BeDiskUsageTool, BeListTool, BeProfilerTool,
+            BeSnapshotTool, BeVarsTool, ClusterPstackTool, MemTrackerTool, MemzGlobalTool,
+            MemzTool, MemzWatchTool, PipelineTasksTool, PstackTool,
         };
-        use crate::tools::be::{JmapDumpTool as BeJmapDumpTool, JmapHistoTool as BeJmapHistoTool};
+        use crate::tools::be::{
+            JmapDumpTool as BeJmapDumpTool, JmapHistoDiffTool as BeJmapHistoDiffTool,
+            JmapHistoTool as BeJmapHistoTool,
+        };
+        use crate::tools::common::conf_inspector::ConfFileInspectorTool;
         use crate::tools::fe::routine_load::get_routine_load_tools;
         use crate::tools::fe::{
-            FeListTool, FeProfilerTool, JmapDumpTool, JmapHistoTool, JstackTool,
+            ClusterVersionTool, FeAlterJobTool, FeAuditLogAnalyzer, FeConfigDriftTool,
+            FeDataSizeTool, FeEditlogTool, FeGcAnalyzerTool, FeListTool, FeMetricsTool,
+            FeProfilerTool, FeQueryProfileTool, FeReplicaHealthTool, FeSqlConsoleTool,
+            FeTabletLookupTool, FeWorkloadGroupTool, JmapDumpTool, JmapHistoDiffTool,
+            JmapHistoTool, JstackTool,
         };
+        use crate::tools::meta_service::MetaServiceCheckTool;
 
         let mut registry = Self {
             fe_tools: Vec::new(),
@@ -61,21 +178,52 @@ impl ToolRegistry {
         registry.fe_tools.push(Box::new(FeListTool));
         registry.fe_tools.push(Box::new(JmapDumpTool));
         registry.fe_tools.push(Box::new(JmapHistoTool));
+        registry.fe_tools.push(Box::new(JmapHistoDiffTool));
         registry.fe_tools.push(Box::new(JstackTool));
         registry.fe_tools.push(Box::new(FeProfilerTool));
 
         // Register Routine Load tools
         registry.fe_tools.extend(get_routine_load_tools());
 
+        registry.fe_tools.push(Box::new(FeGcAnalyzerTool));
+        registry.fe_tools.push(Box::new(FeReplicaHealthTool));
+        registry.fe_tools.push(Box::new(FeAuditLogAnalyzer));
+        registry.fe_tools.push(Box::new(ConfFileInspectorTool {
+            env: Environment::FE,
+        }));
+        registry.fe_tools.push(Box::new(MetaServiceCheckTool));
+        registry.fe_tools.push(Box::new(FeAlterJobTool));
+        registry.fe_tools.push(Box::new(FeQueryProfileTool));
+        registry.fe_tools.push(Box::new(FeConfigDriftTool));
+        registry.fe_tools.push(Box::new(FeMetricsTool));
+        registry.fe_tools.push(Box::new(FeTabletLookupTool));
+        registry.fe_tools.push(Box::new(FeEditlogTool));
+        registry.fe_tools.push(Box::new(ClusterVersionTool));
+        registry.fe_tools.push(Box::new(FeDataSizeTool));
+        registry.fe_tools.push(Box::new(FeWorkloadGroupTool));
+        registry.fe_tools.push(Box::new(FeSqlConsoleTool));
+
         // Register BE tools
         registry.be_tools.push(Box::new(BeListTool));
+        registry.be_tools.push(Box::new(BeSnapshotTool));
         registry.be_tools.push(Box::new(PstackTool));
+        registry.be_tools.push(Box::new(ClusterPstackTool));
         registry.be_tools.push(Box::new(BeVarsTool));
         registry.be_tools.push(Box::new(BeJmapDumpTool));
         registry.be_tools.push(Box::new(BeJmapHistoTool));
+        registry.be_tools.push(Box::new(BeJmapHistoDiffTool));
         registry.be_tools.push(Box::new(PipelineTasksTool));
         registry.be_tools.push(Box::new(MemzTool));
         registry.be_tools.push(Box::new(MemzGlobalTool));
+        registry.be_tools.push(Box::new(MemzWatchTool));
+        registry.be_tools.push(Box::new(MemTrackerTool));
+        registry.be_tools.push(Box::new(BeCrashInspectorTool));
+        registry.be_tools.push(Box::new(BeDiskUsageTool));
+        registry.be_tools.push(Box::new(BeProfilerTool));
+        registry.be_tools.push(Box::new(ConfFileInspectorTool {
+            env: Environment::BE,
+        }));
+        registry.be_tools.push(Box::new(BeCompactionTool));
 
         registry
     }
@@ -88,3 +236,27 @@ impl ToolRegistry {
         &self.be_tools
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_includes_size_only_for_files() {
+        let path = std::env::temp_dir().join(format!("cloud_cli_summary_{}", std::process::id()));
+        std::fs::write(&path, vec![0u8; 2048]).unwrap();
+        let result = ExecutionResult::new(path.clone(), "done")
+            .with_timing(Utc::now(), Duration::from_millis(12_400));
+        assert_eq!(result.output_size_bytes, Some(2048));
+        assert_eq!(
+            result.summary(),
+            format!("completed in 12.4s, wrote 2.00 KB to {}", path.display())
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        let console =
+            ExecutionResult::console("done").with_timing(Utc::now(), Duration::from_secs(75));
+        assert_eq!(console.output_size_bytes, None);
+        assert_eq!(console.summary(), "completed in 01:15");
+    }
+}
@@ -1,10 +1,44 @@
-use crate::config_loader::Environment;
 use crate::config_loader::process_detector;
+use crate::config_loader::{Environment, MySQLConfig};
 use crate::error::{CliError, Result};
+use crate::tools::mysql::retry::{self, CONNECT_FAILED, CONNECTION_LOST, RetryPolicy};
 use std::process::Command;
 
 pub struct MySQLTool;
 
+/// Statement keywords that only read; `ADMIN SHOW ...` is checked separately
+const READ_STATEMENTS: &[&str] = &[
+    "SELECT", "SHOW", "DESC", "DESCRIBE", "EXPLAIN", "USE", "WITH", "HELP",
+];
+
+/// First statement of `query` that is not a pure read, for read-only mode
+fn first_writing_statement(query: &str) -> Option<&str> {
+    query
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .find(|statement| {
+            let mut words = statement.split_whitespace().map(str::to_ascii_uppercase);
+            match words.next().as_deref() {
+                Some("ADMIN") => words.next().as_deref() != Some("SHOW"),
+                Some(first) => !READ_STATEMENTS.contains(&first),
+                None => false,
+            }
+        })
+}
+
+/// The FE's reason when the user is logged in but not allowed to run a statement,
+/// e.g. `ERROR 1227 (42000): Access denied; you need (at least one of) the LOAD privilege(s)`
+fn missing_privilege(stderr: &str) -> Option<&str> {
+    let line = stderr
+        .lines()
+        .find(|l| l.contains("ERROR 1227") || l.contains("Access denied; you need"))?;
+    let detail = line
+        .find("Access denied;")
+        .map_or(line, |start| &line[start..]);
+    Some(detail.trim())
+}
+
 /// Output mode for mysql CLI
 #[derive(Copy, Clone)]
 enum OutputMode {
@@ -24,22 +58,31 @@ impl MySQLTool {
         &self,
         config: &crate::config_loader::DorisConfig,
     ) -> Result<crate::tools::mysql::ClusterInfo> {
-        let frontends_output =
-            Self::query_sql_with_config(config, "SHOW FRONTENDS \\G").map_err(|e| {
-                crate::error::CliError::ConfigError(format!("Failed to query frontends: {e}"))
-            })?;
+        self.query_cluster_info_with_policy(config, &RetryPolicy::default())
+    }
+
+    /// `query_cluster_info` retrying connection failures under `policy`
+    pub fn query_cluster_info_with_policy(
+        &self,
+        config: &crate::config_loader::DorisConfig,
+        policy: &RetryPolicy,
+    ) -> Result<crate::tools::mysql::ClusterInfo> {
+        let query = |sql: &str, what: &str| {
+            retry::with_retry(policy, sql, || Self::query_sql_with_config(config, sql)).map_err(
+                |e| match e {
+                    // Callers tell bad credentials apart from a broken cluster
+                    CliError::MySQLAccessDenied(_) | CliError::MySQLPrivilegeDenied { .. } => e,
+                    _ => CliError::ConfigError(format!("Failed to query {what}: {e}")),
+                },
+            )
+        };
+        let frontends_output = query("SHOW FRONTENDS \\G", "frontends")?;
         let frontends = crate::tools::mysql::parse_frontends(&frontends_output);
 
-        let backends_output =
-            Self::query_sql_with_config(config, "SHOW BACKENDS \\G").map_err(|e| {
-                crate::error::CliError::ConfigError(format!("Failed to query backends: {e}"))
-            })?;
+        let backends_output = query("SHOW BACKENDS \\G", "backends")?;
         let backends = crate::tools::mysql::parse_backends(&backends_output);
 
-        Ok(crate::tools::mysql::ClusterInfo {
-            frontends,
-            backends,
-        })
+        Ok(crate::tools::mysql::ClusterInfo::new(frontends, backends))
     }
 
     /// Executes a MySQL query using credentials from the configuration (standard output mode).
@@ -47,7 +90,36 @@ impl MySQLTool {
         config: &crate::config_loader::DorisConfig,
         query: &str,
     ) -> Result<String> {
-        Self::execute_query_with_config(config, query, OutputMode::Standard)
+        Self::execute_query_with_config(config, query, OutputMode::Standard, None)
+    }
+
+    /// Executes a query (standard output mode) against a specific FE instead of
+    /// the configured connection, still using the stored credentials.
+    pub fn query_sql_on_host(
+        config: &crate::config_loader::DorisConfig,
+        host: &str,
+        port: u16,
+        query: &str,
+    ) -> Result<String> {
+        Self::execute_query_with_config(config, query, OutputMode::Standard, Some((host, port)))
+    }
+
+    /// Executes a query (standard output mode) on `target`, returning the output
+    /// and the `host:port` of the FE that served it.
+    pub fn query_sql_on_target(
+        config: &crate::config_loader::DorisConfig,
+        target: &crate::tools::mysql::FeTarget,
+        query: &str,
+    ) -> Result<(String, String)> {
+        let frontends = crate::tools::mysql::ClusterInfo::load_from_file()
+            .map(|info| info.frontends)
+            .unwrap_or_default();
+        let (host, port) = match target.resolve(&frontends)? {
+            Some(endpoint) => endpoint,
+            None => Self::get_connection_params(config.mysql.as_ref())?,
+        };
+        let output = Self::query_sql_on_host(config, &host, port, query)?;
+        Ok((output, format!("{host}:{port}")))
     }
 
     /// Executes a MySQL query and returns raw output without headers or formatting (-N -B -r -A)
@@ -55,7 +127,7 @@ impl MySQLTool {
         config: &crate::config_loader::DorisConfig,
         query: &str,
     ) -> Result<String> {
-        Self::execute_query_with_config(config, query, OutputMode::Raw)
+        Self::execute_query_with_config(config, query, OutputMode::Raw, None)
     }
 
     /// Shared implementation for executing a query with selected output mode
@@ -63,33 +135,52 @@ impl MySQLTool {
         config: &crate::config_loader::DorisConfig,
         query: &str,
         mode: OutputMode,
+        target: Option<(&str, u16)>,
     ) -> Result<String> {
+        let read_only = crate::config::read_only_from_env().unwrap_or(config.is_read_only());
+        if read_only && let Some(statement) = first_writing_statement(query) {
+            return Err(CliError::ReadOnlyBlocked(format!(
+                "refusing to run `{}`; only SELECT, SHOW and similar statements are allowed",
+                crate::ui::FormatHelper::truncate_string(statement, 60)
+            )));
+        }
+
         let mysql_cfg = config.mysql.as_ref().ok_or_else(|| {
             CliError::ConfigError("MySQL credentials not found in config".to_string())
         })?;
 
-        let cred_mgr = crate::tools::mysql::CredentialManager::new()?;
+        let cred_mgr = crate::tools::mysql::CredentialManager::load()?;
         let user = &mysql_cfg.user;
         let password = cred_mgr.decrypt_password(&mysql_cfg.password)?;
-        let (host, port) = Self::get_connection_params()?;
+        let (host, port) = match target {
+            Some((host, port)) => (host.to_string(), port),
+            None => Self::get_connection_params(Some(mysql_cfg))?,
+        };
 
         let output = Self::run_mysql_command(&host, port, user, &password, query, mode)?;
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
+            crate::ui::print_debug(&format!("mysql at {host}:{port} failed: {}", stderr.trim()));
             if stderr.contains("Access denied for user") || stderr.contains("ERROR 1045") {
                 Err(CliError::MySQLAccessDenied(
                     "Access denied. Please update MySQL credentials.".into(),
                 ))
+            } else if let Some(detail) = missing_privilege(&stderr) {
+                Err(CliError::MySQLPrivilegeDenied {
+                    detail: format!("user '{user}': {detail}"),
+                })
             } else if stderr.contains("Unknown database") || stderr.contains("ERROR 1049") {
-                Err(CliError::ToolExecutionFailed(
-                    "Unknown database. Please verify the database name.".into(),
-                ))
+                Err(CliError::MySQLUnknownDatabase)
             } else if stderr.contains("Can't connect")
                 || stderr.contains("Connection refused")
                 || stderr.contains("ERROR 2003")
             {
                 Err(CliError::ToolExecutionFailed(format!(
-                    "Cannot connect to MySQL at {host}:{port}. Check host/port and service status."
+                    "{CONNECT_FAILED} at {host}:{port}. Check host/port and service status."
+                )))
+            } else if stderr.contains("Lost connection") || stderr.contains("ERROR 2013") {
+                Err(CliError::ToolExecutionFailed(format!(
+                    "{CONNECTION_LOST} at {host}:{port} during the query."
                 )))
             } else {
                 Err(CliError::ToolExecutionFailed(
@@ -136,14 +227,23 @@ impl MySQLTool {
         // Prevent mysql from prompting for a password interactively
         command.stdin(std::process::Stdio::null());
 
-        command
-            .output()
-            .map_err(|e| CliError::ToolExecutionFailed(format!("Failed to execute mysql: {e}")))
+        let started_at = chrono::Utc::now();
+        let timer = std::time::Instant::now();
+        let output = crate::executor::output_tracked(&mut command, "mysql");
+        crate::executor::log_command(
+            &command,
+            started_at,
+            timer.elapsed(),
+            output.as_ref().ok().map(|o| o.status),
+        );
+        output.map_err(|e| CliError::ToolExecutionFailed(format!("Failed to execute mysql: {e}")))
     }
 
     /// Lists databases (excluding system databases) using raw mysql output
     pub fn list_databases(config: &crate::config_loader::DorisConfig) -> Result<Vec<String>> {
-        let output = Self::query_sql_raw_with_config(config, "SHOW DATABASES;")?;
+        let output = retry::with_retry(&RetryPolicy::default(), "SHOW DATABASES", || {
+            Self::query_sql_raw_with_config(config, "SHOW DATABASES;")
+        })?;
         let mut dbs: Vec<String> = output
             .lines()
             .map(|s| s.trim().to_string())
@@ -171,21 +271,134 @@ impl MySQLTool {
     }
 
     /// Gets the connection parameters for MySQL, with a clear priority:
-    pub fn get_connection_params() -> Result<(String, u16)> {
-        if let Some((host, port)) = std::env::var("MYSQL_HOST")
-            .ok()
-            .and_then(|h| std::env::var("MYSQL_PORT").ok().map(|p| (h, p)))
-            .and_then(|(h, p_str)| p_str.parse::<u16>().ok().map(|p| (h, p)))
-        {
-            return Ok((host, port));
-        }
+    /// MYSQL_HOST/MYSQL_PORT env vars, then the FE picked via `fe-list`, then the
+    /// host/port stored in `[mysql]`, then the local FE query_port, then 127.0.0.1:9030.
+    pub fn get_connection_params(mysql: Option<&MySQLConfig>) -> Result<(String, u16)> {
+        resolve_connection_params(
+            |name| std::env::var(name).ok(),
+            || crate::tools::mysql::selected_frontend().map(|fe| (fe.host, fe.query_port)),
+            mysql,
+            || crate::config_loader::load_config().map(|config| config.query_port),
+        )
+    }
+}
 
-        let config = crate::config_loader::load_config()?;
-        if let Some(port) = config.query_port {
-            return Ok(("127.0.0.1".to_string(), port));
-        }
+/// [`MySQLTool::get_connection_params`] with each source looked up only when reached
+fn resolve_connection_params(
+    env_var: impl Fn(&str) -> Option<String>,
+    selected_frontend: impl FnOnce() -> Option<(String, u16)>,
+    mysql: Option<&MySQLConfig>,
+    local_query_port: impl FnOnce() -> Result<Option<u16>>,
+) -> Result<(String, u16)> {
+    if let Some(host) = env_var("MYSQL_HOST")
+        && let Some(port) = env_var("MYSQL_PORT").and_then(|p| p.parse::<u16>().ok())
+    {
+        return Ok((host, port));
+    }
+
+    if let Some(endpoint) = selected_frontend() {
+        return Ok(endpoint);
+    }
+
+    if let Some(host) = mysql.and_then(|m| m.host.clone()) {
+        let port = mysql.and_then(|m| m.port).unwrap_or(9030);
+        return Ok((host, port));
+    }
+
+    if let Some(port) = local_query_port()? {
+        return Ok(("127.0.0.1".to_string(), port));
+    }
+
+    // Fallback to default value.
+    Ok(("127.0.0.1".to_string(), 9030))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_mode_only_lets_reads_through() {
+        assert_eq!(first_writing_statement("SHOW FRONTENDS \\G"), None);
+        assert_eq!(
+            first_writing_statement("USE `db`; select count(*) from t;"),
+            None
+        );
+        assert_eq!(first_writing_statement("ADMIN SHOW FRONTEND CONFIG"), None);
+        assert_eq!(
+            first_writing_statement("USE `db`; ALTER TABLE t SET (\"x\" = \"1\")"),
+            Some("ALTER TABLE t SET (\"x\" = \"1\")")
+        );
+        assert_eq!(
+            first_writing_statement("ADMIN SET FRONTEND CONFIG (\"a\" = \"b\")"),
+            Some("ADMIN SET FRONTEND CONFIG (\"a\" = \"b\")")
+        );
+        assert!(first_writing_statement("PAUSE ROUTINE LOAD FOR db.job").is_some());
+    }
+
+    #[test]
+    fn missing_privileges_are_told_apart_from_other_errors() {
+        let stderr = "ERROR 1227 (42000) at line 1: errCode = 2, detailMessage = Access denied; \
+                      you need (at least one of) the LOAD privilege(s) for this operation\n";
+        assert_eq!(
+            missing_privilege(stderr),
+            Some(
+                "Access denied; you need (at least one of) the LOAD privilege(s) for this operation"
+            )
+        );
+        assert_eq!(
+            missing_privilege("ERROR 1045 (28000): Access denied for user 'u'@'h'"),
+            None
+        );
+    }
+
+    #[test]
+    fn connection_params_follow_their_precedence() {
+        let stored = MySQLConfig {
+            user: "root".to_string(),
+            password: String::new(),
+            host: Some("10.0.0.3".to_string()),
+            port: None,
+        };
+        let env = |name: &str| match name {
+            "MYSQL_HOST" => Some("10.0.0.1".to_string()),
+            "MYSQL_PORT" => Some("9131".to_string()),
+            _ => None,
+        };
+        let no_env = |_: &str| None;
+        let selected = || Some(("10.0.0.2".to_string(), 9230));
+        let resolve = |env: &dyn Fn(&str) -> Option<String>,
+                       selected: Option<(String, u16)>,
+                       mysql: Option<&MySQLConfig>,
+                       local: Option<u16>| {
+            resolve_connection_params(env, || selected, mysql, || Ok(local)).unwrap()
+        };
 
-        // Fallback to default value.
-        Ok(("127.0.0.1".to_string(), 9030))
+        assert_eq!(
+            resolve(&env, selected(), Some(&stored), Some(9330)),
+            ("10.0.0.1".to_string(), 9131)
+        );
+        // A half-set or unparsable env pair is ignored
+        let host_only = |name: &str| (name == "MYSQL_HOST").then(|| "10.0.0.1".to_string());
+        assert_eq!(
+            resolve(&host_only, selected(), None, None),
+            ("10.0.0.2".to_string(), 9230)
+        );
+        assert_eq!(
+            resolve(&no_env, selected(), Some(&stored), Some(9330)),
+            ("10.0.0.2".to_string(), 9230)
+        );
+        assert_eq!(
+            resolve(&no_env, None, Some(&stored), Some(9330)),
+            ("10.0.0.3".to_string(), 9030)
+        );
+        assert_eq!(
+            resolve(&no_env, None, None, Some(9330)),
+            ("127.0.0.1".to_string(), 9330)
+        );
+        assert_eq!(
+            resolve(&no_env, None, None, None),
+            ("127.0.0.1".to_string(), 9030)
+        );
     }
 }
@@ -1,7 +1,8 @@
 use super::parser::{parse_key_value_pairs, split_into_blocks};
 use crate::error::Result;
-use crate::tools::common::fs_utils;
+use crate::tools::common::{format_utils, fs_utils};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 // Macro definitions for parsing MySQL output fields
@@ -100,6 +101,15 @@ pub struct Backend {
     pub status: String,
     pub node_role: String,
     pub tag: Option<String>,
+    // Capacity fields are absent in clusters.toml written by older versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_used_capacity: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avail_capacity: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_capacity: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used_pct: Option<f64>,
 }
 
 impl Backend {
@@ -122,6 +132,11 @@ impl Backend {
         // Extract Tag information
         let tag = fields.get("Tag").map(|s| Self::parse_tag_info(s.trim()));
 
+        let capacity = |key: &str| fields.get(key).map(|v| format_utils::parse_size(v));
+        let used_pct = fields
+            .get("UsedPct")
+            .and_then(|v| v.trim().trim_end_matches('%').trim().parse().ok());
+
         Some(Backend {
             backend_id,
             host,
@@ -134,9 +149,24 @@ impl Backend {
             status,
             node_role,
             tag: tag.flatten(),
+            data_used_capacity: capacity("DataUsedCapacity"),
+            avail_capacity: capacity("AvailCapacity"),
+            total_capacity: capacity("TotalCapacity"),
+            used_pct,
         })
     }
 
+    /// Compute group name and id from the cloud Tag, if the backend has one
+    pub fn compute_group(&self) -> Option<(String, Option<String>)> {
+        let tag: serde_json::Value = serde_json::from_str(self.tag.as_deref()?).ok()?;
+        let name = tag.get("cloud_cluster_name")?.as_str()?.to_string();
+        let id = tag
+            .get("cloud_cluster_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        Some((name, id))
+    }
+
     /// Parse Tag information and extract cloud cluster information
     fn parse_tag_info(tag_str: &str) -> Option<String> {
         if tag_str.is_empty() || tag_str == "{}" {
@@ -183,24 +213,71 @@ impl Backend {
     }
 }
 
+/// Backends of one cloud compute group (`cloud_cluster_name` in their Tag)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComputeGroup {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cluster_id: Option<String>,
+    pub backend_ids: Vec<String>,
+}
+
 /// Holds information about the entire Doris cluster.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClusterInfo {
     pub frontends: Vec<Frontend>,
     pub backends: Vec<Backend>,
+    // Empty outside cloud mode; older versions neither write nor need it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compute_groups: Vec<ComputeGroup>,
 }
 
 impl ClusterInfo {
+    pub fn new(frontends: Vec<Frontend>, backends: Vec<Backend>) -> Self {
+        let compute_groups = Self::group_backends(&backends);
+        Self {
+            frontends,
+            backends,
+            compute_groups,
+        }
+    }
+
     pub fn load_from_file() -> Result<Self> {
         let config_dir = fs_utils::get_user_config_dir()?;
         let file_path = config_dir.join("clusters.toml");
         let content = fs_utils::read_file_content(&file_path)?;
-        let info: ClusterInfo = toml::from_str(&content).map_err(|e| {
+        Self::parse_toml(&content)
+    }
+
+    /// Parse clusters.toml, regrouping backends when an older version wrote it
+    pub fn parse_toml(content: &str) -> Result<Self> {
+        let mut info: ClusterInfo = toml::from_str(content).map_err(|e| {
             crate::error::CliError::ConfigError(format!("Failed to parse clusters.toml: {e}"))
         })?;
+        if info.compute_groups.is_empty() {
+            info.compute_groups = Self::group_backends(&info.backends);
+        }
         Ok(info)
     }
 
+    /// Compute groups by name; backends without a cloud Tag are left out
+    pub fn group_backends(backends: &[Backend]) -> Vec<ComputeGroup> {
+        let mut groups: BTreeMap<String, ComputeGroup> = BTreeMap::new();
+        for be in backends {
+            let Some((name, cluster_id)) = be.compute_group() else {
+                continue;
+            };
+            let group = groups.entry(name.clone()).or_insert_with(|| ComputeGroup {
+                name,
+                cluster_id: None,
+                backend_ids: Vec::new(),
+            });
+            group.cluster_id = group.cluster_id.take().or(cluster_id);
+            group.backend_ids.push(be.backend_id.clone());
+        }
+        groups.into_values().collect()
+    }
+
     pub fn list_be_hosts(&self) -> Vec<String> {
         self.backends
             .iter()
@@ -209,6 +286,24 @@ impl ClusterInfo {
             .collect()
     }
 
+    /// Alive backends of the compute group `name`
+    pub fn backends_in_group(&self, name: &str) -> Vec<&Backend> {
+        let Some(group) = self.compute_groups.iter().find(|g| g.name == name) else {
+            return Vec::new();
+        };
+        self.backends
+            .iter()
+            .filter(|b| b.alive && group.backend_ids.contains(&b.backend_id))
+            .collect()
+    }
+
+    pub fn list_be_hosts_in_group(&self, name: &str) -> Vec<String> {
+        self.backends_in_group(name)
+            .into_iter()
+            .map(|b| b.host.clone())
+            .collect()
+    }
+
     pub fn save_to_file(&self) -> Result<PathBuf> {
         self.validate()?;
         let config_dir = fs_utils::get_user_config_dir()?;
@@ -361,4 +456,49 @@ HeartbeatFailureCounter: 0
         assert!(be.tag.is_some());
         assert!(be.tag.unwrap().contains("location"));
     }
+
+    #[test]
+    fn groups_cloud_backends_and_reads_old_files() {
+        let block = |id: &str, host: &str, tag: &str| {
+            format!(
+                "BackendId: {id}\nHost: {host}\nHeartbeatPort: 9050\nBePort: 9060\n\
+                 HttpPort: 8040\nBrpcPort: 8060\nAlive: true\nVersion: doris-3.0.2\n\
+                 Status: {{}}\nNodeRole: mix\nTag: {tag}\n"
+            )
+        };
+        let backends: Vec<Backend> = [
+            block(
+                "1",
+                "10.0.0.1",
+                r#"{"cloud_cluster_name" : "etl", "cloud_cluster_id" : "c1"}"#,
+            ),
+            block(
+                "2",
+                "10.0.0.2",
+                r#"{"compute_group_name" : "adhoc", "location" : "default"}"#,
+            ),
+            block("3", "10.0.0.3", r#"{"cloud_cluster_name" : "etl"}"#),
+            block("4", "10.0.0.4", r#"{"location" : "default"}"#),
+        ]
+        .iter()
+        .filter_map(|b| Backend::parse_from_block(b))
+        .collect();
+        let info = ClusterInfo::new(Vec::new(), backends);
+        let names: Vec<&str> = info
+            .compute_groups
+            .iter()
+            .map(|g| g.name.as_str())
+            .collect();
+        assert_eq!(names, ["adhoc", "etl"]);
+        assert_eq!(info.compute_groups[1].cluster_id.as_deref(), Some("c1"));
+        assert_eq!(info.list_be_hosts_in_group("etl"), ["10.0.0.1", "10.0.0.3"]);
+        assert!(info.list_be_hosts_in_group("missing").is_empty());
+
+        let mut old = info.clone();
+        old.compute_groups.clear();
+        let content = toml::to_string(&old).unwrap();
+        assert!(!content.contains("compute_groups"));
+        let reloaded = ClusterInfo::parse_toml(&content).unwrap();
+        assert_eq!(reloaded.compute_groups, info.compute_groups);
+    }
 }
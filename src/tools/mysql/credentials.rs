@@ -1,30 +1,46 @@
+use crate::config::Config;
 use crate::config_loader::{DorisConfig, MySQLConfig};
 use crate::error::{CliError, Result};
-use crate::tools::mysql::MySQLTool;
+use crate::tools::mysql::{ClusterInfo, MySQLTool};
 
+use super::key_store::{self, KeySource, RawKey, keyring};
 use aes_gcm::aead::rand_core::RngCore;
 use aes_gcm::aead::{Aead, KeyInit, OsRng};
 use aes_gcm::{Aes256Gcm, Key, Nonce};
 use base64::{Engine as _, engine::general_purpose};
 use dialoguer::{Input, Password};
-use std::fs;
-use std::io::{Read, Write};
-use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 
 type Aes256GcmKey = aes_gcm::Key<Aes256Gcm>;
 
+/// Remote FE host and query port
+pub type FeEndpoint = (String, u16);
+
 const CONFIG_DIR: &str = ".config/cloud-cli";
 const KEY_FILE: &str = "key";
+const KEYRING_DESCRIPTION: &str = "cloud-cli:credential-key";
 
 pub struct CredentialManager {
-    key: Aes256GcmKey,
+    /// Candidate keys in decryption order; the first one encrypts
+    keys: Vec<(KeySource, Aes256GcmKey)>,
+    /// No key existed anywhere when the manager was created
+    generated: bool,
 }
 
 impl CredentialManager {
-    pub fn new() -> Result<Self> {
-        let key = Self::load_or_generate_key()?;
-        Ok(Self { key })
+    /// Load the keys, generating one if none exists. The key file is the durable
+    /// copy; `credential_keyring` opts into also keeping the key in the user keyring.
+    pub fn new(config: &Config) -> Result<Self> {
+        Self::load_or_generate_keys(config.credential_keyring)
+    }
+
+    /// Load the existing keys for decryption, without migrating or generating one
+    pub fn load() -> Result<Self> {
+        let keys = Self::existing_keys(&Self::get_key_path()?)?;
+        Ok(Self {
+            generated: keys.is_empty(),
+            keys,
+        })
     }
 
     pub fn prompt_for_credentials(&self) -> Result<(String, String)> {
@@ -39,18 +55,49 @@ impl CredentialManager {
         Ok((user, password))
     }
 
+    /// Prompts for the FE host/port, defaulting to the master FE from clusters.toml.
+    pub fn prompt_for_endpoint(&self) -> Result<FeEndpoint> {
+        let (default_host, default_port) = ClusterInfo::load_from_file()
+            .ok()
+            .and_then(|info| info.frontends.into_iter().find(|fe| fe.is_master))
+            .map(|fe| (fe.host, fe.query_port))
+            .filter(|(host, port)| !host.is_empty() && *port != 0)
+            .unwrap_or_else(|| ("127.0.0.1".to_string(), 9030));
+
+        crate::ui::print_info("No local FE process detected. Enter the FE to connect to.");
+        let host: String = Input::new()
+            .with_prompt("FE host")
+            .default(default_host)
+            .interact()?;
+        let port: u16 = Input::new()
+            .with_prompt("FE query port")
+            .default(default_port)
+            .interact()?;
+        Ok((host, port))
+    }
+
     /// Prompts for credentials and verifies them, handling passwordless scenarios.
-    pub fn prompt_credentials_with_connection_test(&self) -> Result<(String, String)> {
+    /// When no FE runs locally, also prompts for the remote FE endpoint.
+    pub fn prompt_credentials_with_connection_test(
+        &self,
+    ) -> Result<(String, String, Option<FeEndpoint>)> {
+        let endpoint = match MySQLTool::detect_fe_process() {
+            Ok(_) => None,
+            Err(_) => Some(self.prompt_for_endpoint()?),
+        };
+
         let max_retries = 3;
         for _ in 0..max_retries {
             let (user, password) = self.prompt_for_credentials()?;
 
-            match self.test_connection(&user, &password) {
+            match self.test_connection(&user, &password, endpoint.as_ref()) {
                 Ok(_) => {
-                    if password.is_empty() || self.test_connection(&user, "").is_err() {
-                        return Ok((user, password));
+                    if password.is_empty()
+                        || self.test_connection(&user, "", endpoint.as_ref()).is_err()
+                    {
+                        return Ok((user, password, endpoint));
                     } else {
-                        return Ok((user, "".to_string()));
+                        return Ok((user, "".to_string(), endpoint));
                     }
                 }
                 Err(CliError::MySQLAccessDenied(_)) => {
@@ -70,11 +117,18 @@ impl CredentialManager {
     }
 
     /// Helper function to test a MySQL connection with specific credentials.
-    fn test_connection(&self, user: &str, password: &str) -> Result<()> {
+    fn test_connection(
+        &self,
+        user: &str,
+        password: &str,
+        endpoint: Option<&FeEndpoint>,
+    ) -> Result<()> {
         let config = DorisConfig {
             mysql: Some(MySQLConfig {
                 user: user.to_string(),
                 password: self.encrypt_password(password)?,
+                host: endpoint.map(|(h, _)| h.clone()),
+                port: endpoint.map(|(_, p)| *p),
             }),
             ..Default::default()
         };
@@ -86,9 +140,12 @@ impl CredentialManager {
         Ok(MySQLConfig {
             user: user.to_string(),
             password: encrypted_password,
+            host: None,
+            port: None,
         })
     }
 
+    /// Decrypt with the keyring key first, then the legacy key file
     pub fn decrypt_password(&self, encrypted: &str) -> Result<String> {
         if encrypted.is_empty() {
             return Ok(String::new());
@@ -101,33 +158,154 @@ impl CredentialManager {
         }
         let (nonce_bytes, ciphertext) = combined.split_at(12);
         let nonce = Nonce::from_slice(nonce_bytes);
-        let cipher = Aes256Gcm::new(&self.key);
-        let plaintext = cipher
-            .decrypt(nonce, ciphertext)
-            .map_err(|e| std::io::Error::other(format!("Decryption failed: {e}")))?;
+
+        let plaintext = self
+            .keys
+            .iter()
+            .find_map(|(_, key)| Aes256Gcm::new(key).decrypt(nonce, ciphertext).ok())
+            .ok_or_else(|| {
+                let reason = if self.generated {
+                    "no credential key was found in the keyring or the key file"
+                } else {
+                    "it was encrypted with a different key"
+                };
+                CliError::MySQLAccessDenied(format!(
+                    "Stored MySQL password cannot be decrypted ({reason}). Please re-enter MySQL credentials."
+                ))
+            })?;
         let s = String::from_utf8(plaintext)
             .map_err(|e| std::io::Error::other(format!("UTF8 decode failed: {e}")))?;
         Ok(s)
     }
 
-    fn load_or_generate_key() -> Result<Aes256GcmKey> {
+    /// Manager holding a fresh key for the active backend.
+    ///
+    /// The key is only kept in memory until `store_key`, so values re-encrypted
+    /// with it can be persisted before the old key is replaced.
+    pub fn rotated(&self) -> Self {
+        let raw = key_store::generate_key();
+        Self {
+            keys: vec![(self.active_source(), *Key::<Aes256Gcm>::from_slice(&raw))],
+            generated: false,
+        }
+    }
+
+    /// Write the active key to its backend, replacing the key stored there
+    pub fn store_key(&self) -> Result<()> {
+        let (source, key) = self
+            .keys
+            .first()
+            .ok_or_else(|| std::io::Error::other("No credential key loaded"))?;
+        let raw: RawKey = (*key).into();
         let key_path = Self::get_key_path()?;
-        if key_path.exists() {
-            let mut buf = [0u8; 32];
-            let mut f = fs::File::open(&key_path)?;
-            f.read_exact(&mut buf)?;
-            Ok(*Key::<Aes256Gcm>::from_slice(&buf))
-        } else {
-            let mut buf = [0u8; 32];
-            OsRng.fill_bytes(&mut buf);
-            if let Some(parent) = key_path.parent() {
-                fs::create_dir_all(parent)?;
+        if *source == KeySource::Keyring {
+            keyring::store(KEYRING_DESCRIPTION, &raw)?;
+        }
+        // The key file stays in step with the keyring unless its removal was confirmed
+        if *source == KeySource::File || key_path.exists() {
+            key_store::write_key_file(&key_path, &raw)?;
+        }
+        Ok(())
+    }
+
+    pub fn active_source(&self) -> KeySource {
+        self.keys
+            .first()
+            .map(|(source, _)| *source)
+            .unwrap_or(KeySource::File)
+    }
+
+    /// Keyring key first, then a file key that differs from it
+    fn existing_keys(key_path: &std::path::Path) -> Result<Vec<(KeySource, Aes256GcmKey)>> {
+        let as_key = |raw: &RawKey| *Key::<Aes256Gcm>::from_slice(raw);
+        let keyring_key = keyring::read(KEYRING_DESCRIPTION);
+        let file_key = key_store::read_key_file(key_path)?;
+
+        let mut keys = Vec::new();
+        if let Some(raw) = keyring_key {
+            keys.push((KeySource::Keyring, as_key(&raw)));
+        }
+        if let Some(raw) = file_key
+            && keyring_key != Some(raw)
+        {
+            keys.push((KeySource::File, as_key(&raw)));
+        }
+        Ok(keys)
+    }
+
+    fn load_or_generate_keys(use_keyring: bool) -> Result<Self> {
+        let key_path = Self::get_key_path()?;
+        let use_keyring = use_keyring && keyring::available(KEYRING_DESCRIPTION);
+        let mut keys = Self::existing_keys(&key_path)?;
+
+        if use_keyring && let [(KeySource::File, key)] = keys.as_slice() {
+            let raw: RawKey = (*key).into();
+            Self::migrate_to_keyring(&key_path, &raw);
+            if keyring::read(KEYRING_DESCRIPTION).is_some() {
+                keys[0].0 = KeySource::Keyring;
             }
-            let mut f = fs::File::create(&key_path)?;
-            f.write_all(&buf)?;
-            fs::set_permissions(&key_path, fs::Permissions::from_mode(0o600))?;
-            Ok(*Key::<Aes256Gcm>::from_slice(&buf))
         }
+        if !keys.is_empty() {
+            return Ok(Self {
+                keys,
+                generated: false,
+            });
+        }
+
+        let raw = key_store::generate_key();
+        key_store::write_key_file(&key_path, &raw)?;
+        let source = if use_keyring && keyring::store(KEYRING_DESCRIPTION, &raw).is_ok() {
+            KeySource::Keyring
+        } else {
+            KeySource::File
+        };
+        Ok(Self {
+            keys: vec![(source, *Key::<Aes256Gcm>::from_slice(&raw))],
+            generated: true,
+        })
+    }
+
+    /// Copy the file key into the keyring and verify it reads back. The file is
+    /// only shredded when the user confirms, since the keyring does not survive a reboot.
+    fn migrate_to_keyring(key_path: &std::path::Path, raw: &RawKey) {
+        if let Err(e) = keyring::store(KEYRING_DESCRIPTION, raw) {
+            crate::ui::print_warning(&format!("Keyring unavailable, keeping key file: {e}"));
+            return;
+        }
+        if keyring::read(KEYRING_DESCRIPTION).as_ref() != Some(raw) {
+            keyring::remove(KEYRING_DESCRIPTION);
+            crate::ui::print_warning("Keyring read-back failed, keeping key file");
+            return;
+        }
+        crate::ui::print_info("Credential key copied into the user keyring.");
+        if !Self::confirm_key_file_removal(key_path) {
+            return;
+        }
+        match key_store::shred_file(key_path) {
+            Ok(()) => crate::ui::print_info(&format!(
+                "{} removed; stored credentials must be re-entered after a reboot.",
+                key_path.display()
+            )),
+            Err(e) => crate::ui::print_warning(&format!(
+                "Key copied to the keyring but {} could not be removed: {e}",
+                key_path.display()
+            )),
+        }
+    }
+
+    /// Defaults to keeping the file; without a terminal nothing is asked or removed
+    fn confirm_key_file_removal(key_path: &std::path::Path) -> bool {
+        if !console::Term::stdout().is_term() {
+            return false;
+        }
+        dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "Remove {}? The keyring copy is lost on reboot, and stored passwords with it",
+                key_path.display()
+            ))
+            .default(false)
+            .interact()
+            .unwrap_or(false)
     }
 
     fn get_config_dir() -> Result<PathBuf> {
@@ -140,8 +318,12 @@ impl CredentialManager {
         Ok(Self::get_config_dir()?.join(KEY_FILE))
     }
 
-    fn encrypt_password(&self, password: &str) -> Result<String> {
-        let cipher = Aes256Gcm::new(&self.key);
+    pub fn encrypt_password(&self, password: &str) -> Result<String> {
+        let (_, key) = self
+            .keys
+            .first()
+            .ok_or_else(|| std::io::Error::other("No credential key loaded"))?;
+        let cipher = Aes256Gcm::new(key);
         let mut nonce_bytes = [0u8; 12];
         OsRng.fill_bytes(&mut nonce_bytes);
         let nonce = Nonce::from_slice(&nonce_bytes);
@@ -159,9 +341,19 @@ impl CredentialManager {
 mod tests {
     use super::*;
 
+    fn file_manager() -> CredentialManager {
+        CredentialManager {
+            keys: vec![(
+                KeySource::File,
+                *Key::<Aes256Gcm>::from_slice(&key_store::generate_key()),
+            )],
+            generated: false,
+        }
+    }
+
     #[test]
     fn test_encrypt_decrypt_password() {
-        let mgr = CredentialManager::new().unwrap();
+        let mgr = file_manager();
         let password = "test123!@#";
         let encrypted = mgr.encrypt_password(password).unwrap();
         let decrypted = mgr.decrypt_password(&encrypted).unwrap();
@@ -170,10 +362,46 @@ mod tests {
 
     #[test]
     fn test_encrypt_decrypt_empty_password() {
-        let mgr = CredentialManager::new().unwrap();
+        let mgr = file_manager();
         let password = "";
         let encrypted = mgr.encrypt_password(password).unwrap();
         let decrypted = mgr.decrypt_password(&encrypted).unwrap();
         assert_eq!(password, decrypted);
     }
+
+    #[test]
+    fn decrypt_falls_back_to_legacy_key() {
+        let key = |raw: RawKey| *Key::<Aes256Gcm>::from_slice(&raw);
+        let legacy = CredentialManager {
+            keys: vec![(KeySource::File, key(key_store::generate_key()))],
+            generated: false,
+        };
+        let encrypted = legacy.encrypt_password("secret").unwrap();
+
+        let mut both = CredentialManager {
+            keys: vec![
+                (KeySource::Keyring, key(key_store::generate_key())),
+                legacy.keys[0],
+            ],
+            generated: false,
+        };
+        assert_eq!(both.decrypt_password(&encrypted).unwrap(), "secret");
+
+        both.keys.truncate(1);
+        assert!(matches!(
+            both.decrypt_password(&encrypted),
+            Err(CliError::MySQLAccessDenied(_))
+        ));
+    }
+
+    #[test]
+    fn rotated_key_stays_in_memory_until_stored() {
+        let mgr = file_manager();
+        let rotated = mgr.rotated();
+        assert_eq!(rotated.active_source(), KeySource::File);
+
+        let encrypted = rotated.encrypt_password("secret").unwrap();
+        assert_eq!(rotated.decrypt_password(&encrypted).unwrap(), "secret");
+        assert!(mgr.decrypt_password(&encrypted).is_err());
+    }
 }
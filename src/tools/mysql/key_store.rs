@@ -0,0 +1,163 @@
+use crate::error::Result;
+
+use aes_gcm::aead::OsRng;
+use aes_gcm::aead::rand_core::RngCore;
+use std::fs;
+use std::io::{Read, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::Path;
+
+pub const KEY_LEN: usize = 32;
+
+pub type RawKey = [u8; KEY_LEN];
+
+/// Where the active credential key is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    /// Linux kernel keyring (kept in kernel memory, cleared on reboot)
+    Keyring,
+    /// Legacy key file under ~/.config/cloud-cli
+    File,
+}
+
+pub fn generate_key() -> RawKey {
+    let mut buf = [0u8; KEY_LEN];
+    OsRng.fill_bytes(&mut buf);
+    buf
+}
+
+/// Read the key file, tightening its permissions to 0600 if they are looser
+pub fn read_key_file(path: &Path) -> Result<Option<RawKey>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mode = fs::metadata(path)?.permissions().mode();
+    if mode & 0o077 != 0 {
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        crate::ui::print_warning(&format!(
+            "Key file {} was accessible to other users; permissions reset to 0600",
+            path.display()
+        ));
+    }
+
+    let mut buf = [0u8; KEY_LEN];
+    fs::File::open(path)?.read_exact(&mut buf)?;
+    Ok(Some(buf))
+}
+
+/// Write the key file with 0600 from creation so it is never briefly world-readable
+pub fn write_key_file(path: &Path, key: &RawKey) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut f = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    f.write_all(key)?;
+    f.sync_all()?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+/// Overwrite the file with random bytes before removing it
+pub fn shred_file(path: &Path) -> Result<()> {
+    let len = fs::metadata(path)?.len() as usize;
+    let mut noise = vec![0u8; len.max(KEY_LEN)];
+    OsRng.fill_bytes(&mut noise);
+    let mut f = fs::OpenOptions::new().write(true).open(path)?;
+    f.write_all(&noise)?;
+    f.sync_all()?;
+    drop(f);
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub mod keyring {
+    //! Kernel key retention service through the `keyring` crate (keyutils backend).
+
+    use super::{KEY_LEN, RawKey};
+    use ::keyring::{Entry, Error};
+
+    const SERVICE: &str = "cloud-cli";
+
+    fn entry(description: &str) -> Option<Entry> {
+        Entry::new_with_target(description, SERVICE, description).ok()
+    }
+
+    /// Whether the keyring can be used (may be blocked by seccomp in containers)
+    pub fn available(description: &str) -> bool {
+        entry(description).is_some_and(|e| matches!(e.get_secret(), Ok(_) | Err(Error::NoEntry)))
+    }
+
+    pub fn read(description: &str) -> Option<RawKey> {
+        let secret = entry(description)?.get_secret().ok()?;
+        <[u8; KEY_LEN]>::try_from(secret.as_slice()).ok()
+    }
+
+    /// Add or replace the key stored under `description`
+    pub fn store(description: &str, key: &RawKey) -> std::io::Result<()> {
+        entry(description)
+            .ok_or_else(|| std::io::Error::other("keyring entry could not be created"))?
+            .set_secret(key)
+            .map_err(std::io::Error::other)
+    }
+
+    pub fn remove(description: &str) {
+        if let Some(e) = entry(description) {
+            let _ = e.delete_credential();
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub mod keyring {
+    use super::RawKey;
+
+    pub fn available(_description: &str) -> bool {
+        false
+    }
+
+    pub fn read(_description: &str) -> Option<RawKey> {
+        None
+    }
+
+    pub fn store(_description: &str, _key: &RawKey) -> std::io::Result<()> {
+        Err(std::io::Error::other("keyring is only supported on Linux"))
+    }
+
+    pub fn remove(_description: &str) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_file_is_private_and_shredded() {
+        let dir = std::env::temp_dir().join(format!("cloud-cli-key-{}", std::process::id()));
+        let path = dir.join("key");
+        let key = generate_key();
+
+        write_key_file(&path, &key).unwrap();
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(read_key_file(&path).unwrap(), Some(key));
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+
+        shred_file(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(read_key_file(&path).unwrap(), None);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,12 +1,17 @@
 mod client;
 mod cluster;
 mod credentials;
+pub mod key_store;
 pub mod parser;
+pub mod retry;
+mod target;
 
 pub use client::MySQLTool;
-pub use cluster::{Backend, ClusterInfo, Frontend};
+pub use cluster::{Backend, ClusterInfo, ComputeGroup, Frontend};
 pub use credentials::CredentialManager;
 pub use parser::{parse_backends, parse_frontends};
+pub use retry::{RetryPolicy, with_retry};
+pub use target::{FeTarget, choose_fe_target, selected_frontend};
 
 /// System databases to hide from selection
 pub const SYSTEM_DATABASES: &[&str] = &["__internal_schema", "mysql", "information_schema"];
@@ -1,5 +1,6 @@
 use super::cluster::{Backend, ClusterInfo, Frontend};
 use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
 
 /// Parse frontends from MySQL output
 pub fn parse_frontends(output: &str) -> Vec<Frontend> {
@@ -36,22 +37,90 @@ pub fn split_into_blocks(output: &str) -> Vec<String> {
     blocks
 }
 
-/// Parse key-value pairs from a block of text
-pub fn parse_key_value_pairs(block: &str) -> HashMap<String, String> {
-    let mut fields = HashMap::new();
+/// Fields of one `\G` row, by column name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowFields(HashMap<String, String>);
 
-    for line in block.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.contains("***************************") {
-            continue;
-        }
+impl RowFields {
+    /// Trimmed value of `key`; None when the column is missing or SQL NULL.
+    /// Empty values stay `Some("")`.
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(|v| v.trim()).filter(|v| *v != "NULL")
+    }
+}
+
+impl Deref for RowFields {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for RowFields {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Byte offset of the colon ending a `Key:` prefix, if the line has one
+fn key_colon(line: &str) -> Option<usize> {
+    let start = line.len() - line.trim_start().len();
+    let colon = start + line[start..].find(':')?;
+    let key = &line[start..colon];
+    let is_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let rest = &line[colon + 1..];
+    (is_key && (rest.is_empty() || rest.starts_with(' '))).then_some(colon)
+}
 
-        if let Some((key, value)) = parse_key_value(line) {
-            fields.insert(key, value);
+/// Parse key-value pairs from a block of `\G` output.
+///
+/// The mysql client right-aligns column names, so their colons share one
+/// column. When most key-looking lines agree on it, only lines with the colon
+/// there start a new field; anything else (wrapped JSON, multi-line error
+/// reasons) continues the previous value.
+pub fn parse_key_value_pairs(block: &str) -> RowFields {
+    let lines: Vec<&str> = block
+        .lines()
+        .filter(|l| !l.contains("***************************"))
+        .collect();
+
+    let mut columns: HashMap<usize, usize> = HashMap::new();
+    for colon in lines.iter().filter_map(|l| key_colon(l)) {
+        *columns.entry(colon).or_default() += 1;
+    }
+    let key_lines: usize = columns.values().sum();
+    let aligned = columns
+        .iter()
+        .max_by_key(|(colon, count)| (**count, std::cmp::Reverse(**colon)))
+        .filter(|(_, count)| **count > 1 && **count * 2 > key_lines)
+        .map(|(colon, _)| *colon);
+
+    let mut fields = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+    for line in lines {
+        let starts_key = match (key_colon(line), aligned) {
+            (Some(colon), Some(column)) => colon == column,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if starts_key && let Some((key, value)) = parse_key_value(line) {
+            if let Some((key, value)) = current.replace((key, value)) {
+                fields.insert(key, value.trim().to_string());
+            }
+        } else if let Some((_, value)) = current.as_mut()
+            && !line.trim().is_empty()
+        {
+            value.push('\n');
+            value.push_str(line.trim_end());
         }
     }
+    if let Some((key, value)) = current {
+        fields.insert(key, value.trim().to_string());
+    }
 
-    fields
+    RowFields(fields)
 }
 
 /// Parse a single key-value line
@@ -113,4 +182,55 @@ mod tests {
         assert!(blocks[1].contains("192.168.0.2"));
         assert!(blocks[1].contains("OBSERVER"));
     }
+
+    #[test]
+    fn multi_line_values_and_null() {
+        let output = r#"
+*************************** 1. row ***************************
+                  Id: 41005
+                Name: orders_load
+           PauseTime: NULL
+             EndTime: 
+               State: PAUSED
+      DataSourceType: KAFKA
+        ErrorLogUrls: 
+ReasonOfStateChanged: ErrorReason{code=errCode = 100, msg='be 10003 abort task, task id: 9b2c5a1e-0f1d-4c9e-8a31-2f6f4d1e0b7a job id: 41005 with reason: [DATA_QUALITY_ERROR]too many filtered rows
+	0#  doris::Status doris::Status::Error<true>(int, std::basic_string_view<char, std::char_traits<char> >) at /home/zcp/repo_center/doris_release/doris/be/src/common/status.h:377
+	1#  doris::vectorized::VOlapTableSink::send(doris::RuntimeState*, doris::vectorized::Block*) at be/src/vec/sink/vtablet_sink.cpp:1320
+reason: column(user_id) values is null while columns is not nullable. src line [{"user_id":null,"amount":3}]; '}
+            Progress: {"0":"1024","1":"2048"}
+            OtherMsg: 
+"#;
+        let fields = parse_key_value_pairs(&split_into_blocks(output)[0]);
+        assert_eq!(fields.value("Id"), Some("41005"));
+        assert_eq!(fields.value("PauseTime"), None);
+        assert_eq!(fields.value("EndTime"), Some(""));
+        assert_eq!(fields.value("Missing"), None);
+        let reason = fields.value("ReasonOfStateChanged").unwrap();
+        assert!(reason.starts_with("ErrorReason{code=errCode = 100"));
+        assert!(reason.contains("\n\t1#  doris::vectorized::VOlapTableSink::send"));
+        assert!(reason.ends_with("src line [{\"user_id\":null,\"amount\":3}]; '}"));
+        assert!(!fields.contains_key("reason"));
+        assert_eq!(fields.value("Progress"), Some(r#"{"0":"1024","1":"2048"}"#));
+
+        let task = r#"
+*************************** 1. row ***************************
+              TaskId: 9b2c5a1e0f1d4c9e-8a312f6f4d1e0b7a
+               TxnId: 88121
+           TxnStatus: ABORTED
+               JobId: 41005
+            ErrorMsg: errCode = 2, detailMessage = failed to send batch
+detailMessage: [INTERNAL_ERROR]tablet writer write failed, tablet_id=10087, txn_id=88121
+                BeId: 10003
+"#;
+        let fields = parse_key_value_pairs(task);
+        assert_eq!(
+            fields.value("ErrorMsg"),
+            Some(
+                "errCode = 2, detailMessage = failed to send batch\n\
+                 detailMessage: [INTERNAL_ERROR]tablet writer write failed, tablet_id=10087, txn_id=88121"
+            )
+        );
+        assert_eq!(fields.value("BeId"), Some("10003"));
+    }
 }
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use crate::error::{CliError, Result};
+
+/// Prefixes of the connection-level failures `MySQLTool` reports; an FE that
+/// is starting up or failing over produces these for a few seconds
+pub(crate) const CONNECT_FAILED: &str = "Cannot connect to MySQL";
+pub(crate) const CONNECTION_LOST: &str = "Lost connection to MySQL";
+
+/// Exponential backoff with jitter for MySQL queries
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Foreground queries: about 3.5s of retries before giving up
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(4),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Background collection can afford to wait out an FE restart
+    pub fn background() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based), before jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    fn jittered(&self, retry: u32) -> Duration {
+        // Up to +50% so background and foreground callers do not retry in lockstep
+        let base = self.backoff(retry);
+        let jitter_ms = rand::random_range(0..=base.as_millis() as u64 / 2);
+        base + Duration::from_millis(jitter_ms)
+    }
+}
+
+/// Whether retrying `err` can help: connection failures and timeouts, never
+/// bad credentials, missing databases or SQL errors
+pub fn is_retryable(err: &CliError) -> bool {
+    match err {
+        CliError::ToolExecutionFailed(msg) => {
+            msg.starts_with(CONNECT_FAILED) || msg.starts_with(CONNECTION_LOST)
+        }
+        CliError::Timeout { .. } => true,
+        _ => false,
+    }
+}
+
+/// Run `op` under `policy`; `what` names the operation in the final error
+pub fn with_retry<T>(
+    policy: &RetryPolicy,
+    what: &str,
+    mut op: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if !is_retryable(&e) => return Err(e),
+            Err(e) if attempt >= policy.max_attempts => {
+                let last = match e {
+                    CliError::ToolExecutionFailed(msg) => msg,
+                    other => other.to_string(),
+                };
+                return Err(CliError::ToolExecutionFailed(format!(
+                    "{what} failed after {attempt} attempt(s): {last}"
+                )));
+            }
+            Err(e) => {
+                let delay = policy.jittered(attempt);
+                crate::ui::print_debug(&format!(
+                    "{what}: attempt {attempt}/{} failed ({e}); retrying in {:.1}s",
+                    policy.max_attempts,
+                    delay.as_secs_f64()
+                ));
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_only_transient_errors_and_reports_attempts() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(1));
+        assert_eq!(policy.backoff(2), Duration::from_millis(2));
+        assert_eq!(policy.backoff(9), Duration::from_millis(2));
+
+        let mut calls = 0;
+        let value = with_retry(&policy, "SHOW FRONTENDS", || {
+            calls += 1;
+            if calls < 3 {
+                Err(CliError::ToolExecutionFailed(format!(
+                    "{CONNECT_FAILED} at fe1:9030. Check host/port and service status."
+                )))
+            } else {
+                Ok(calls)
+            }
+        })
+        .unwrap();
+        assert_eq!(value, 3);
+
+        let mut calls = 0;
+        let err = with_retry::<()>(&policy, "SHOW FRONTENDS", || {
+            calls += 1;
+            Err(CliError::ToolExecutionFailed(format!(
+                "{CONNECTION_LOST} at fe1:9030"
+            )))
+        })
+        .unwrap_err();
+        assert_eq!(calls, 3);
+        assert!(err.to_string().contains("failed after 3 attempt(s)"));
+        assert!(
+            err.to_string()
+                .contains("Lost connection to MySQL at fe1:9030")
+        );
+
+        let mut calls = 0;
+        let err = with_retry::<()>(&policy, "SHOW DATABASES", || {
+            calls += 1;
+            Err(CliError::MySQLAccessDenied("denied".into()))
+        })
+        .unwrap_err();
+        assert_eq!(calls, 1);
+        assert!(matches!(err, CliError::MySQLAccessDenied(_)));
+        assert!(!is_retryable(&CliError::MySQLUnknownDatabase));
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+
+use crate::error::{CliError, Result};
+use crate::tools::common::host_selection;
+use crate::tools::mysql::{ClusterInfo, Frontend};
+
+/// Which frontend a query is sent to
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum FeTarget {
+    /// The configured connection: the FE picked via `fe-list`, else the `[mysql]`
+    /// host if set, else the local FE
+    #[default]
+    Local,
+    /// Whichever alive FE currently reports itself as master
+    Master,
+    Host {
+        host: String,
+        port: u16,
+    },
+}
+
+impl fmt::Display for FeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeTarget::Local => write!(f, "local FE"),
+            FeTarget::Master => write!(f, "master FE"),
+            FeTarget::Host { host, port } => write!(f, "{host}:{port}"),
+        }
+    }
+}
+
+impl FeTarget {
+    /// Explicit host and port, or None for the configured connection
+    pub fn resolve(&self, frontends: &[Frontend]) -> Result<Option<(String, u16)>> {
+        match self {
+            FeTarget::Local => Ok(None),
+            FeTarget::Host { host, port } => Ok(Some((host.clone(), *port))),
+            FeTarget::Master => frontends
+                .iter()
+                .find(|fe| fe.is_master && fe.alive)
+                .map(|fe| Some((fe.host.clone(), fe.query_port)))
+                .ok_or_else(|| {
+                    CliError::ToolExecutionFailed(
+                        "No alive master FE in the cluster info; refresh it or pick a host"
+                            .to_string(),
+                    )
+                }),
+        }
+    }
+}
+
+/// The FE picked via `fe-list`, looked up in the saved cluster info
+pub fn selected_frontend() -> Option<Frontend> {
+    let host = host_selection::get_selected_host(false)?;
+    ClusterInfo::load_from_file()
+        .ok()?
+        .frontends
+        .into_iter()
+        .find(|fe| fe.host == host)
+}
+
+/// Menu entries for `frontends`: local first, then master, then each alive FE
+fn target_options(frontends: &[Frontend]) -> Vec<(String, FeTarget)> {
+    let mut options = vec![(
+        "Local FE (configured connection)".to_string(),
+        FeTarget::Local,
+    )];
+    if let Some(master) = frontends.iter().find(|fe| fe.is_master && fe.alive) {
+        options.push((
+            format!("Master ({}:{})", master.host, master.query_port),
+            FeTarget::Master,
+        ));
+    }
+    for fe in frontends.iter().filter(|fe| fe.alive) {
+        let role = if fe.is_master {
+            "MASTER"
+        } else {
+            fe.role.as_str()
+        };
+        options.push((
+            format!("{}:{} ({role})", fe.host, fe.query_port),
+            FeTarget::Host {
+                host: fe.host.clone(),
+                port: fe.query_port,
+            },
+        ));
+    }
+    options
+}
+
+/// Ask which FE to query when the cluster has more than one; single-FE clusters
+/// and missing cluster info use the local FE without asking. The host picked
+/// via `fe-list` is pre-selected.
+pub fn choose_fe_target() -> Result<FeTarget> {
+    let frontends = ClusterInfo::load_from_file()
+        .map(|info| info.frontends)
+        .unwrap_or_default();
+    if frontends.iter().filter(|fe| fe.alive).count() <= 1 {
+        return Ok(FeTarget::Local);
+    }
+
+    let mut options = target_options(&frontends);
+    let default = host_selection::get_selected_host(false)
+        .and_then(|host| {
+            options
+                .iter()
+                .position(|(_, t)| matches!(t, FeTarget::Host { host: h, .. } if *h == host))
+        })
+        .unwrap_or(0);
+    let labels: Vec<&str> = options.iter().map(|(label, _)| label.as_str()).collect();
+    let index = dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+        .with_prompt("Query which FE?")
+        .items(&labels)
+        .default(default)
+        .interact()
+        .map_err(|e| CliError::prompt_failed("FE selection failed", e))?;
+    Ok(options.swap_remove(index).1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fe(host: &str, role: &str, is_master: bool, alive: bool) -> Frontend {
+        Frontend {
+            name: host.to_string(),
+            host: host.to_string(),
+            edit_log_port: 9010,
+            http_port: 8030,
+            query_port: 9030,
+            rpc_port: 9020,
+            role: role.to_string(),
+            is_master,
+            cluster_id: "1".to_string(),
+            alive,
+            version: "2.1".to_string(),
+        }
+    }
+
+    #[test]
+    fn resolves_master_and_lists_alive_frontends() {
+        let frontends = [
+            fe("fe1", "FOLLOWER", false, true),
+            fe("fe2", "FOLLOWER", true, true),
+            fe("fe3", "OBSERVER", false, false),
+        ];
+        assert_eq!(FeTarget::Local.resolve(&frontends).unwrap(), None);
+        assert_eq!(
+            FeTarget::Master.resolve(&frontends).unwrap(),
+            Some(("fe2".to_string(), 9030))
+        );
+        assert!(FeTarget::Master.resolve(&frontends[..1]).is_err());
+
+        let labels: Vec<String> = target_options(&frontends)
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Local FE (configured connection)",
+                "Master (fe2:9030)",
+                "fe1:9030 (FOLLOWER)",
+                "fe2:9030 (MASTER)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+
+use crate::config::Config;
+use crate::config_loader;
+use crate::error::{CliError, Result};
+use crate::tools::Tool;
+use crate::ui::table::TableBuilder;
+use crate::ui::tool_executor::execute_tool_once;
+use crate::ui::{InteractiveSelector, print_error, print_info, print_success, print_warning};
+
+const PICK_TOOLS: &str = "Pick tools...";
+
+/// How one tool of a bundle run ended
+#[derive(Debug, Clone, PartialEq)]
+enum StepOutcome {
+    Done {
+        message: String,
+        output: Option<String>,
+    },
+    Skipped,
+    Failed(String),
+}
+
+/// Configured bundles naming at least one of `tools`; the others belong to
+/// the other service
+fn bundles_for_service<'a>(
+    bundles: &'a BTreeMap<String, Vec<String>>,
+    tools: &[Box<dyn Tool>],
+) -> Vec<(&'a str, &'a [String])> {
+    bundles
+        .iter()
+        .filter(|(_, names)| {
+            names
+                .iter()
+                .any(|n| tools.iter().any(|t| t.name() == n.trim()))
+        })
+        .map(|(name, names)| (name.as_str(), names.as_slice()))
+        .collect()
+}
+
+/// Registry indices of `names` in bundle order, and the names with no such tool
+fn resolve_bundle(tools: &[Box<dyn Tool>], names: &[String]) -> (Vec<usize>, Vec<String>) {
+    let mut indices = Vec::new();
+    let mut missing = Vec::new();
+    for name in names {
+        match tools.iter().position(|t| t.name() == name.trim()) {
+            Some(index) if !indices.contains(&index) => indices.push(index),
+            Some(_) => {}
+            None => missing.push(name.clone()),
+        }
+    }
+    (indices, missing)
+}
+
+/// Tools picked from the registry, in registry order
+fn pick_tools(tools: &[Box<dyn Tool>], service: &str) -> Result<Vec<usize>> {
+    let labels: Vec<String> = tools
+        .iter()
+        .map(|t| format!("{} - {}", t.name(), t.description()))
+        .collect();
+    let selector = InteractiveSelector::new(
+        labels.clone(),
+        format!("Select {service} tools to run back-to-back"),
+    );
+    let picked = selector.select_multi()?;
+    Ok(labels
+        .iter()
+        .enumerate()
+        .filter(|(_, label)| picked.contains(label))
+        .map(|(i, _)| i)
+        .collect())
+}
+
+fn run_steps(
+    config: &Config,
+    tools: &[Box<dyn Tool>],
+    indices: &[usize],
+    service: &str,
+) -> Vec<(String, StepOutcome)> {
+    let mut results = Vec::new();
+    for (step, &index) in indices.iter().enumerate() {
+        let tool = &*tools[index];
+        print_info("");
+        print_info(&format!("[{}/{}] {}", step + 1, indices.len(), tool.name()));
+        let outcome = match execute_tool_once(config, tool, service) {
+            Ok(Some(result)) => StepOutcome::Done {
+                output: (!result.is_console_output())
+                    .then(|| result.output_path.display().to_string()),
+                message: result.message,
+            },
+            Ok(None) | Err(CliError::GracefulExit) => {
+                print_warning(&format!("{} skipped", tool.name()));
+                StepOutcome::Skipped
+            }
+            Err(e) => {
+                print_error(&format!("{} failed: {e}", tool.name()));
+                StepOutcome::Failed(e.to_string())
+            }
+        };
+        results.push((tool.name().to_string(), outcome));
+    }
+    results
+}
+
+fn render_summary(results: &[(String, StepOutcome)]) -> String {
+    let mut table = TableBuilder::new(["Tool", "Result", "Output"])
+        .max_width(1, 60)
+        .max_width(2, 72);
+    for (name, outcome) in results {
+        let (result, output) = match outcome {
+            StepOutcome::Done { message, output } => (
+                format!("ok: {message}"),
+                output.clone().unwrap_or_else(|| "(console)".to_string()),
+            ),
+            StepOutcome::Skipped => ("skipped".to_string(), "-".to_string()),
+            StepOutcome::Failed(error) => (format!("failed: {error}"), "-".to_string()),
+        };
+        table.push_row([name.clone(), result, output]);
+    }
+    table.render()
+}
+
+/// Run a configured bundle or a hand-picked set of `service` tools one after
+/// another, continuing past failures, then print one summary.
+///
+/// Bundles live under `[bundles]` in config.toml, e.g.
+/// `"standard FE triage" = ["jstack", "jmap-histo", "fe-metrics"]`.
+pub fn run_diagnostic_bundle(
+    config: &Config,
+    tools: &[Box<dyn Tool>],
+    service: &str,
+) -> Result<()> {
+    let bundles = config_loader::load_config()
+        .map(|doris| doris.bundles)
+        .unwrap_or_default();
+    let available = bundles_for_service(&bundles, tools);
+
+    let choice = if available.is_empty() {
+        print_info(&format!(
+            "No {service} bundles configured; add a [bundles] table to config.toml to keep one."
+        ));
+        available.len()
+    } else {
+        let mut options: Vec<String> = available
+            .iter()
+            .map(|(name, names)| format!("{name} ({})", names.join(", ")))
+            .collect();
+        options.push(PICK_TOOLS.to_string());
+        let options: Vec<&str> = options.iter().map(String::as_str).collect();
+        crate::ui::dialogs::select_index("Run which bundle?", &options)?
+    };
+
+    let indices = match available.get(choice) {
+        Some((bundle, names)) => {
+            let (indices, missing) = resolve_bundle(tools, names);
+            for name in missing {
+                print_warning(&format!(
+                    "Bundle '{bundle}': no {service} tool named '{name}', skipping it"
+                ));
+            }
+            indices
+        }
+        None => pick_tools(tools, service)?,
+    };
+
+    let results = run_steps(config, tools, &indices, service);
+    let failed = results
+        .iter()
+        .filter(|(_, o)| matches!(o, StepOutcome::Failed(_)))
+        .count();
+    let skipped = results
+        .iter()
+        .filter(|(_, o)| *o == StepOutcome::Skipped)
+        .count();
+
+    print_info("");
+    let done = results.len() - failed - skipped;
+    let totals = format!("Bundle finished: {done} ok, {failed} failed, {skipped} skipped");
+    if failed == 0 {
+        print_success(&totals);
+    } else {
+        print_warning(&totals);
+    }
+    for line in render_summary(&results).lines() {
+        print_info(line);
+    }
+    if !config.stdout_only && done > 0 {
+        print_info(&format!(
+            "Outputs of this run: {}",
+            config.run_dir_path().display()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ExecutionResult;
+
+    struct Named(&'static str);
+
+    impl Tool for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+        fn description(&self) -> &str {
+            "test tool"
+        }
+        fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn bundles_resolve_against_the_service_registry() {
+        let tools: Vec<Box<dyn Tool>> = vec![
+            Box::new(Named("fe-list")),
+            Box::new(Named("jstack")),
+            Box::new(Named("fe-metrics")),
+        ];
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let bundles = BTreeMap::from([
+            (
+                "standard FE triage".to_string(),
+                names(&["jstack", "memz", "fe-metrics", "jstack"]),
+            ),
+            ("BE only".to_string(), names(&["memz", "pipeline-tasks"])),
+        ]);
+
+        let available = bundles_for_service(&bundles, &tools);
+        assert_eq!(available.len(), 1);
+        assert_eq!(available[0].0, "standard FE triage");
+
+        let (indices, missing) = resolve_bundle(&tools, available[0].1);
+        assert_eq!(indices, vec![1, 2]);
+        assert_eq!(missing, vec!["memz".to_string()]);
+
+        let summary = render_summary(&[
+            (
+                "jstack".to_string(),
+                StepOutcome::Done {
+                    message: "Thread dump written".to_string(),
+                    output: Some("/tmp/jstack_1.log".to_string()),
+                },
+            ),
+            (
+                "fe-metrics".to_string(),
+                StepOutcome::Failed("timed out".to_string()),
+            ),
+        ]);
+        assert!(summary.contains("/tmp/jstack_1.log"));
+        assert!(summary.contains("failed: timed out"));
+    }
+}
@@ -25,7 +25,7 @@ pub fn show_no_jobs_recovery_menu(database: &str) -> Result<NoJobsNextAction> {
         .items(&options)
         .default(0)
         .interact()
-        .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+        .map_err(CliError::from)?;
 
     let action = match selection {
         0 => NoJobsNextAction::ChooseAnotherDatabase,
@@ -47,7 +47,7 @@ pub fn show_unknown_db_recovery_menu(database: &str) -> Result<NoJobsNextAction>
         .items(&options)
         .default(0)
         .interact()
-        .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+        .map_err(CliError::from)?;
 
     let action = match selection {
         0 => NoJobsNextAction::ChooseAnotherDatabase,
@@ -59,12 +59,17 @@ pub fn show_unknown_db_recovery_menu(database: &str) -> Result<NoJobsNextAction>
 
 // Generic prompt helpers for reuse across UI modules
 pub fn select_index(prompt: &str, options: &[&str]) -> Result<usize> {
+    select_index_with_default(prompt, options, 0)
+}
+
+/// [`select_index`] starting on `default`, e.g. the choice made last time
+pub fn select_index_with_default(prompt: &str, options: &[&str], default: usize) -> Result<usize> {
     let selection = Select::new()
         .with_prompt(prompt)
         .items(options)
-        .default(0)
+        .default(default.min(options.len().saturating_sub(1)))
         .interact()
-        .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+        .map_err(CliError::from)?;
     Ok(selection)
 }
 
@@ -73,6 +78,6 @@ pub fn input_text(prompt: &str, initial: &str) -> Result<String> {
         .with_prompt(prompt)
         .with_initial_text(initial.to_string())
         .interact_text()
-        .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+        .map_err(CliError::from)?;
     Ok(text)
 }
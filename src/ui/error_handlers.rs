@@ -1,6 +1,7 @@
 use crate::config::Config;
 use crate::config_loader;
-use crate::error::{self, Result};
+use crate::error::{self, CliError, Result};
+use crate::tools::fe::routine_load::messages::NO_JOB_ID;
 use crate::ui::{print_error, print_info, print_success, print_warning};
 
 pub fn handle_tool_execution_error(
@@ -13,7 +14,7 @@ pub fn handle_tool_execution_error(
 
     if service_name == "FE"
         && tool_name.contains("routine_load")
-        && error.to_string().contains("No Job ID in memory")
+        && matches!(error, CliError::InvalidInput(msg) if msg == NO_JOB_ID)
     {
         print_warning("Routine Load tool execution failed: No Job ID selected.");
         print_error(&format!("Error: {error}"));
@@ -52,6 +53,34 @@ pub fn handle_tool_execution_error(
         };
     }
 
+    // Disk filled up or the output was truncated: freeing space is the usual fix
+    if let CliError::OutputWriteFailed { path, .. } = error {
+        print_warning("Output could not be written completely.");
+        print_error(&format!("Error: {error}"));
+        print_info(&format!(
+            "Free disk space on the filesystem holding {} and retry, or pick another output directory.",
+            path.display()
+        ));
+
+        let options = ["Retry", "Fix output directory and retry", "Return to menu"];
+        return match crate::ui::dialogs::select_index("Choose an option", &options)? {
+            0 => Ok(Some(config.clone())),
+            1 => fix_output_directory(config),
+            _ => Ok(None),
+        };
+    }
+
+    // Logged in, but the user may not run this statement: credentials are not the fix
+    if matches!(error, CliError::MySQLPrivilegeDenied { .. }) {
+        print_warning("The MySQL user is not allowed to run this statement.");
+        print_error(&format!("Error: {error}"));
+        print_info("Ask an admin to GRANT the privilege named above to this user, then retry.");
+
+        let options = ["Return to menu"];
+        let _ = crate::ui::dialogs::select_index("Choose an option", &options)?;
+        return Ok(None);
+    }
+
     // FE profiler script missing: show simple guidance
     if service_name == "FE" && is_fe_profiler_script_missing(tool_name, error) {
         print_warning("FE profiler script missing.");
@@ -76,7 +105,7 @@ pub fn handle_tool_execution_error(
         labels.push("Fix JDK path and retry");
         actions.push(fix_jdk_path as ActionFn);
     }
-    if is_output_dir_invalid(config, error) {
+    if is_output_dir_invalid(config) {
         labels.push("Fix output directory and retry");
         actions.push(fix_output_directory as ActionFn);
     }
@@ -104,10 +133,13 @@ fn fix_jdk_path(config: &Config) -> Result<Option<Config>> {
     }
 
     let jmap_path = new_path.join("bin/jmap");
+    let jcmd_path = new_path.join("bin/jcmd");
     let jstack_path = new_path.join("bin/jstack");
 
-    if !jmap_path.exists() || !jstack_path.exists() {
-        print_error("Required JDK tools (jmap/jstack) not found in the specified path");
+    if (!jmap_path.exists() && !jcmd_path.exists()) || !jstack_path.exists() {
+        print_error(
+            "Required JDK tools (jmap or jcmd, and jstack) not found in the specified path",
+        );
         return Ok(None);
     }
 
@@ -154,28 +186,28 @@ fn persist_updated_config(config: &Config) -> Result<()> {
 }
 
 fn is_be_connectivity_error(error: &error::CliError) -> bool {
-    let s = error.to_string();
-    s.contains("Could not connect to any BE http port")
+    matches!(error, CliError::BeUnreachable { .. })
 }
 
 fn is_fe_profiler_script_missing(tool_name: &str, error: &error::CliError) -> bool {
-    tool_name.contains("fe-profiler") && error.to_string().contains("profile_fe.sh not found")
+    tool_name.contains("fe-profiler")
+        && matches!(error, CliError::ConfigError(msg) if msg.starts_with("profile_fe.sh not found"))
 }
 
 fn is_jdk_missing(config: &Config, error: &error::CliError) -> bool {
-    let s = error.to_string();
-    if s.contains("JDK path does not exist") || s.contains("jmap") || s.contains("jstack") {
-        return true;
+    match error {
+        CliError::ConfigError(msg) if msg.starts_with("JDK path does not exist") => return true,
+        CliError::ToolExecutionFailed(msg) if msg.contains("jmap") || msg.contains("jstack") => {
+            return true;
+        }
+        _ => {}
     }
     let jmap = config.jdk_path.join("bin/jmap");
+    let jcmd = config.get_jcmd_path();
     let jstack = config.jdk_path.join("bin/jstack");
-    !jmap.exists() || !jstack.exists()
+    (!jmap.exists() && !jcmd.exists()) || !jstack.exists()
 }
 
-fn is_output_dir_invalid(config: &Config, error: &error::CliError) -> bool {
-    let s = error.to_string();
-    if s.contains("Cannot create directory") || s.contains("Output dir input failed") {
-        return true;
-    }
+fn is_output_dir_invalid(config: &Config) -> bool {
     !config.output_dir.exists()
 }
@@ -0,0 +1,132 @@
+use crate::config::Config;
+use console::style;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use super::INFO;
+
+const LARGE_BUCKET_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+const HIGH_RESIDENT_BYTES: u64 = 32 * 1024 * 1024 * 1024;
+
+/// Key findings a tool exposes after producing its result
+#[derive(Debug, Clone, PartialEq)]
+pub enum Finding {
+    /// Largest average bucket (tablet) size across a table's partitions
+    MaxAvgBucketSize(u64),
+    /// State of the Routine Load job the user selected
+    RoutineLoadJobState(String),
+    /// Jemalloc resident memory reported by /memz
+    MemzResident(u64),
+}
+
+/// Rule mapping a finding condition to a one-line hint
+pub struct HintRule {
+    pub id: &'static str,
+    pub matches: fn(&Finding) -> bool,
+    pub text: &'static str,
+}
+
+static RULES: &[HintRule] = &[
+    HintRule {
+        id: "large-bucket-size",
+        matches: |f| matches!(f, Finding::MaxAvgBucketSize(b) if *b > LARGE_BUCKET_BYTES),
+        text: "Average bucket size exceeds 10 GB; run the deep table analysis to check tablet distribution for skew or raise the bucket count",
+    },
+    HintRule {
+        id: "paused-routine-load",
+        matches: |f| matches!(f, Finding::RoutineLoadJobState(s) if s == "PAUSED"),
+        text: "This job is PAUSED; check ReasonOfStateChanged and ErrorLogUrls in SHOW ROUTINE LOAD to find the cause",
+    },
+    HintRule {
+        id: "high-resident-memory",
+        matches: |f| matches!(f, Finding::MemzResident(b) if *b > HIGH_RESIDENT_BYTES),
+        text: "Resident memory is high; collect a heap profile to see which allocations dominate",
+    },
+];
+
+/// Tracks which hints were already shown in this session
+#[derive(Default)]
+pub struct HintSession {
+    shown: HashSet<&'static str>,
+}
+
+impl HintSession {
+    /// Returns true the first time a hint id is seen and records it
+    pub fn mark_shown(&mut self, id: &'static str) -> bool {
+        self.shown.insert(id)
+    }
+}
+
+static SESSION: Lazy<Mutex<HintSession>> = Lazy::new(|| Mutex::new(HintSession::default()));
+
+/// Evaluate the rules table against a set of findings
+pub fn matching_rules(findings: &[Finding]) -> Vec<&'static HintRule> {
+    RULES
+        .iter()
+        .filter(|rule| findings.iter().any(|f| (rule.matches)(f)))
+        .collect()
+}
+
+/// Hints to display now, skipping any already shown in `session`
+pub fn pending_hints(session: &mut HintSession, findings: &[Finding]) -> Vec<&'static str> {
+    matching_rules(findings)
+        .into_iter()
+        .filter(|rule| session.mark_shown(rule.id))
+        .map(|rule| rule.text)
+        .collect()
+}
+
+/// Print hints for the given findings unless hints are disabled in settings
+pub fn show_hints(config: &Config, findings: &[Finding]) {
+    if config.no_hints || findings.is_empty() {
+        return;
+    }
+
+    let hints = match SESSION.lock() {
+        Ok(mut session) => pending_hints(&mut session, findings),
+        Err(_) => return,
+    };
+
+    for hint in hints {
+        println!("{}", style(format!("{INFO} Hint: {hint}")).dim());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_match_only_their_conditions() {
+        let findings = vec![
+            Finding::MaxAvgBucketSize(LARGE_BUCKET_BYTES + 1),
+            Finding::RoutineLoadJobState("RUNNING".to_string()),
+            Finding::MemzResident(1024),
+        ];
+        let ids: Vec<&str> = matching_rules(&findings).iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["large-bucket-size"]);
+
+        let findings = vec![
+            Finding::RoutineLoadJobState("PAUSED".to_string()),
+            Finding::MemzResident(HIGH_RESIDENT_BYTES + 1),
+        ];
+        let ids: Vec<&str> = matching_rules(&findings).iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["paused-routine-load", "high-resident-memory"]);
+
+        assert!(matching_rules(&[Finding::MaxAvgBucketSize(LARGE_BUCKET_BYTES)]).is_empty());
+    }
+
+    #[test]
+    fn hints_are_shown_once_per_session() {
+        let mut session = HintSession::default();
+        let findings = vec![Finding::RoutineLoadJobState("PAUSED".to_string())];
+
+        assert_eq!(pending_hints(&mut session, &findings).len(), 1);
+        assert!(pending_hints(&mut session, &findings).is_empty());
+
+        let other = vec![Finding::MemzResident(HIGH_RESIDENT_BYTES * 2)];
+        assert_eq!(pending_hints(&mut session, &other).len(), 1);
+        assert!(pending_hints(&mut session, &other).is_empty());
+    }
+}
@@ -20,18 +20,29 @@ struct Menu<T> {
 
 impl<T: Copy> Menu<T> {
     fn show(&self) -> Result<T> {
+        self.show_with_context(&[])
+    }
+
+    /// [`Menu::show`] with `context` lines kept under the step header
+    fn show_with_context(&self, context: &[String]) -> Result<T> {
         let items: Vec<String> = self
             .options
             .iter()
             .map(|o| format_menu_item(&o.key, &o.name, &o.description))
             .collect();
 
-        let selection = show_interactive_menu(self.step, &self.title, &items)?;
+        let selection = show_interactive_menu(self.step, &self.title, context, &items)?;
         Ok(self.options[selection].action)
     }
 }
 
-fn show_interactive_menu(step: u8, title: &str, items: &[String]) -> Result<usize> {
+fn show_interactive_menu(
+    step: u8,
+    title: &str,
+    context: &[String],
+    items: &[String],
+) -> Result<usize> {
+    ui::terminal::require_terminal("Cannot show the menu")?;
     let term = Term::stdout();
     let mut selection = 0;
 
@@ -42,6 +53,9 @@ fn show_interactive_menu(step: u8, title: &str, items: &[String]) -> Result<usiz
     } else {
         ui::print_info("");
     }
+    for line in context {
+        ui::print_info(&style(line).dim().to_string());
+    }
 
     term.hide_cursor()?;
 
@@ -57,7 +71,7 @@ fn show_interactive_menu(step: u8, title: &str, items: &[String]) -> Result<usiz
     }
 
     loop {
-        let key = term.read_key()?;
+        let key = term.read_key().map_err(dialoguer::Error::IO)?;
 
         match key {
             Key::Enter => {
@@ -112,9 +126,25 @@ fn show_interactive_menu(step: u8, title: &str, items: &[String]) -> Result<usiz
 pub enum MainMenuAction {
     Fe,
     Be,
+    Doctor,
+    Settings,
     Exit,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum SettingsAction {
+    RekeyCredentials,
+    CleanOutputs,
+    ExportConfig,
+    ImportConfig,
+    RestoreConfig,
+    ShowConfig,
+    SessionTag,
+    HttpEndpoints,
+    Verbosity,
+    Back,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum FeToolAction {
     FeList,
@@ -123,6 +153,22 @@ pub enum FeToolAction {
     FeProfiler,
     TableInfo,
     RoutineLoad,
+    GcAnalyzer,
+    ReplicaHealth,
+    AuditLog,
+    ConfInspector,
+    MetaService,
+    AlterJobs,
+    QueryProfile,
+    ConfigDrift,
+    Metrics,
+    TabletLookup,
+    Editlog,
+    ClusterVersion,
+    DataSize,
+    WorkloadGroups,
+    SqlConsole,
+    Bundle,
     Back,
 }
 
@@ -131,6 +177,10 @@ pub enum RoutineLoadAction {
     GetJobId,
     Performance,
     Traffic,
+    SnapshotCompare,
+    ErrorChecker,
+    LagTrend,
+    ManageJob,
     Back,
 }
 
@@ -152,8 +202,20 @@ pub fn show_main_menu() -> Result<MainMenuAction> {
                 description: "Backend operations".to_string(),
             },
             MenuOption {
-                action: MainMenuAction::Exit,
+                action: MainMenuAction::Doctor,
                 key: "[3]".to_string(),
+                name: "Doctor".to_string(),
+                description: "Check connectivity and tooling on this host".to_string(),
+            },
+            MenuOption {
+                action: MainMenuAction::Settings,
+                key: "[4]".to_string(),
+                name: "Settings".to_string(),
+                description: "Credential and configuration maintenance".to_string(),
+            },
+            MenuOption {
+                action: MainMenuAction::Exit,
+                key: "[5]".to_string(),
                 name: "Exit".to_string(),
                 description: "Exit the application".to_string(),
             },
@@ -162,7 +224,78 @@ pub fn show_main_menu() -> Result<MainMenuAction> {
     menu.show()
 }
 
-pub fn show_fe_tools_menu() -> Result<FeToolAction> {
+pub fn show_settings_menu() -> Result<SettingsAction> {
+    let menu = Menu {
+        step: 2,
+        title: "Settings".to_string(),
+        options: vec![
+            MenuOption {
+                action: SettingsAction::RekeyCredentials,
+                key: "[1]".to_string(),
+                name: "rekey-credentials".to_string(),
+                description: "Re-encrypt the stored MySQL password with a new key".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::CleanOutputs,
+                key: "[2]".to_string(),
+                name: "clean-outputs".to_string(),
+                description: "Clean old outputs from the output directory".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::ExportConfig,
+                key: "[3]".to_string(),
+                name: "export-config".to_string(),
+                description: "Write this config for provisioning other hosts".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::ImportConfig,
+                key: "[4]".to_string(),
+                name: "import-config".to_string(),
+                description: "Merge an exported config into this one".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::RestoreConfig,
+                key: "[5]".to_string(),
+                name: "restore-config".to_string(),
+                description: "Restore configuration from backup".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::ShowConfig,
+                key: "[6]".to_string(),
+                name: "show-config".to_string(),
+                description: "Show effective configuration and where each value came from"
+                    .to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::SessionTag,
+                key: "[7]".to_string(),
+                name: "session-tag".to_string(),
+                description: "Tag every output file with a ticket or case ID".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::HttpEndpoints,
+                key: "[8]".to_string(),
+                name: "http-endpoints".to_string(),
+                description: "HTTPS and basic auth for FE/BE HTTP endpoints".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::Verbosity,
+                key: "[9]".to_string(),
+                name: "verbosity".to_string(),
+                description: "Quiet, normal, verbose or debug output for this session".to_string(),
+            },
+            MenuOption {
+                action: SettingsAction::Back,
+                key: "[10]".to_string(),
+                name: "Back".to_string(),
+                description: "Return to main menu".to_string(),
+            },
+        ],
+    };
+    menu.show()
+}
+
+pub fn show_fe_tools_menu(context: &[String]) -> Result<FeToolAction> {
     let menu = Menu {
         step: 2,
         title: "Select FE tool".to_string(),
@@ -171,7 +304,7 @@ pub fn show_fe_tools_menu() -> Result<FeToolAction> {
                 action: FeToolAction::FeList,
                 key: "[1]".to_string(),
                 name: "fe-list".to_string(),
-                description: "List and select FE host (IP)".to_string(),
+                description: "List frontends and pick the active FE target".to_string(),
             },
             MenuOption {
                 action: FeToolAction::Jmap,
@@ -206,14 +339,110 @@ pub fn show_fe_tools_menu() -> Result<FeToolAction> {
                 description: "Routine Load management tools".to_string(),
             },
             MenuOption {
-                action: FeToolAction::Back,
+                action: FeToolAction::GcAnalyzer,
                 key: "[7]".to_string(),
+                name: "gc-analyzer".to_string(),
+                description: "Analyze GC pauses from FE GC logs".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::ReplicaHealth,
+                key: "[8]".to_string(),
+                name: "replica-health".to_string(),
+                description: "Check unhealthy/inconsistent tablet replicas".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::AuditLog,
+                key: "[9]".to_string(),
+                name: "audit-log".to_string(),
+                description: "Slow and failed queries from fe.audit.log".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::ConfInspector,
+                key: "[10]".to_string(),
+                name: "fe-conf".to_string(),
+                description: "View fe.conf and compare it with the running FE".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::MetaService,
+                key: "[11]".to_string(),
+                name: "meta-service".to_string(),
+                description: "Check cloud meta-service endpoints".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::AlterJobs,
+                key: "[12]".to_string(),
+                name: "alter-jobs".to_string(),
+                description: "Monitor schema change and rollup jobs".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::QueryProfile,
+                key: "[13]".to_string(),
+                name: "query-profile".to_string(),
+                description: "Download and summarize a query profile".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::ConfigDrift,
+                key: "[14]".to_string(),
+                name: "config-drift".to_string(),
+                description: "Compare FE configs across frontends".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::Metrics,
+                key: "[15]".to_string(),
+                name: "metrics".to_string(),
+                description: "Snapshot thread pool and queue metrics".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::TabletLookup,
+                key: "[16]".to_string(),
+                name: "tablet-lookup".to_string(),
+                description: "Resolve tablet ids to table, partition and replicas".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::Editlog,
+                key: "[17]".to_string(),
+                name: "fe-editlog".to_string(),
+                description: "Editlog replay lag, checkpoint age and bdb size".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::ClusterVersion,
+                key: "[18]".to_string(),
+                name: "cluster-version".to_string(),
+                description: "FE/BE version matrix with build hash and time".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::DataSize,
+                key: "[19]".to_string(),
+                name: "fe-data-size".to_string(),
+                description: "Database sizes and largest tables from SHOW DATA".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::WorkloadGroups,
+                key: "[20]".to_string(),
+                name: "fe-workload-groups".to_string(),
+                description: "Workload group limits with running and queued queries".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::SqlConsole,
+                key: "[21]".to_string(),
+                name: "fe-sql-console".to_string(),
+                description: "Run one-off SELECT/SHOW/DESC/EXPLAIN statements".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::Bundle,
+                key: "[22]".to_string(),
+                name: "diagnostic-bundle".to_string(),
+                description: "Run several tools back-to-back with one summary".to_string(),
+            },
+            MenuOption {
+                action: FeToolAction::Back,
+                key: "[23]".to_string(),
                 name: "← Back".to_string(),
                 description: "Return to main menu".to_string(),
             },
         ],
     };
-    menu.show()
+    menu.show_with_context(context)
 }
 
 pub fn show_routine_load_menu() -> Result<RoutineLoadAction> {
@@ -240,8 +469,32 @@ pub fn show_routine_load_menu() -> Result<RoutineLoadAction> {
                 description: "Aggregate per-minute loadedRows from FE logs".to_string(),
             },
             MenuOption {
-                action: RoutineLoadAction::Back,
+                action: RoutineLoadAction::SnapshotCompare,
                 key: "[4]".to_string(),
+                name: "Snapshot & Compare".to_string(),
+                description: "Save job statistics and diff two points in time".to_string(),
+            },
+            MenuOption {
+                action: RoutineLoadAction::ErrorChecker,
+                key: "[5]".to_string(),
+                name: "Error Checker".to_string(),
+                description: "Job errors with per-task drill-down and error log".to_string(),
+            },
+            MenuOption {
+                action: RoutineLoadAction::LagTrend,
+                key: "[6]".to_string(),
+                name: "Lag Trend".to_string(),
+                description: "Sample Kafka lag over time and estimate catch-up".to_string(),
+            },
+            MenuOption {
+                action: RoutineLoadAction::ManageJob,
+                key: "[7]".to_string(),
+                name: "Manage Job".to_string(),
+                description: "Pause/resume the selected job or show its CREATE".to_string(),
+            },
+            MenuOption {
+                action: RoutineLoadAction::Back,
+                key: "[8]".to_string(),
                 name: "← Back to FE Tools".to_string(),
                 description: "Return to FE tools menu".to_string(),
             },
@@ -254,6 +507,7 @@ pub fn show_routine_load_menu() -> Result<RoutineLoadAction> {
 pub enum JmapAction {
     Dump,
     Histo,
+    HistoDiff,
     Back,
 }
 
@@ -275,8 +529,14 @@ pub fn show_jmap_menu() -> Result<JmapAction> {
                 description: "Generate histogram (.log)".to_string(),
             },
             MenuOption {
-                action: JmapAction::Back,
+                action: JmapAction::HistoDiff,
                 key: "[3]".to_string(),
+                name: "Histo diff".to_string(),
+                description: "Compare two histograms to spot growing classes".to_string(),
+            },
+            MenuOption {
+                action: JmapAction::Back,
+                key: "[4]".to_string(),
                 name: "← Back to FE Tools".to_string(),
                 description: "Return to FE tools menu".to_string(),
             },
@@ -288,15 +548,23 @@ pub fn show_jmap_menu() -> Result<JmapAction> {
 #[derive(Debug, Clone, Copy)]
 pub enum BeToolAction {
     BeList,
+    Snapshot,
     Pstack,
     BeVars,
     Jmap,
     PipelineTasks,
     Memz,
+    CrashInspector,
+    DiskUsage,
+    Profiler,
+    ConfInspector,
+    Compaction,
+    ClusterPstack,
+    Bundle,
     Back,
 }
 
-pub fn show_be_tools_menu() -> Result<BeToolAction> {
+pub fn show_be_tools_menu(context: &[String]) -> Result<BeToolAction> {
     let menu = Menu {
         step: 2,
         title: "Select BE tool".to_string(),
@@ -308,50 +576,100 @@ pub fn show_be_tools_menu() -> Result<BeToolAction> {
                 description: "List and select BE host (IP)".to_string(),
             },
             MenuOption {
-                action: BeToolAction::Pstack,
+                action: BeToolAction::Snapshot,
                 key: "[2]".to_string(),
+                name: "be-snapshot".to_string(),
+                description: "Running queries, memory and CPU threads at this moment".to_string(),
+            },
+            MenuOption {
+                action: BeToolAction::Pstack,
+                key: "[3]".to_string(),
                 name: "pstack".to_string(),
                 description: "Generate thread stack trace (.log)".to_string(),
             },
             MenuOption {
                 action: BeToolAction::Jmap,
-                key: "[3]".to_string(),
+                key: "[4]".to_string(),
                 name: "jmap".to_string(),
                 description: "Java heap tools (dump/histo)".to_string(),
             },
             MenuOption {
                 action: BeToolAction::BeVars,
-                key: "[4]".to_string(),
+                key: "[5]".to_string(),
                 name: "be-vars".to_string(),
                 description: "Query BE variables via HTTP".to_string(),
             },
             MenuOption {
                 action: BeToolAction::PipelineTasks,
-                key: "[5]".to_string(),
+                key: "[6]".to_string(),
                 name: "pipeline-tasks".to_string(),
                 description: "Collect pipeline tasks from BE".to_string(),
             },
             MenuOption {
                 action: BeToolAction::Memz,
-                key: "[6]".to_string(),
+                key: "[7]".to_string(),
                 name: "memz".to_string(),
                 description: "Memory tracker tools (current/global)".to_string(),
             },
+            MenuOption {
+                action: BeToolAction::CrashInspector,
+                key: "[8]".to_string(),
+                name: "be-crash-inspector".to_string(),
+                description: "Inspect be.out, core files and minidumps".to_string(),
+            },
+            MenuOption {
+                action: BeToolAction::DiskUsage,
+                key: "[9]".to_string(),
+                name: "be-disk-usage".to_string(),
+                description: "Per-BE disk usage sorted by used percentage".to_string(),
+            },
+            MenuOption {
+                action: BeToolAction::Profiler,
+                key: "[10]".to_string(),
+                name: "be-profiler".to_string(),
+                description: "CPU profile / flame graph via BE pprof".to_string(),
+            },
+            MenuOption {
+                action: BeToolAction::ConfInspector,
+                key: "[11]".to_string(),
+                name: "be-conf".to_string(),
+                description: "View be.conf and compare it with the running BE".to_string(),
+            },
+            MenuOption {
+                action: BeToolAction::Compaction,
+                key: "[12]".to_string(),
+                name: "be-compaction".to_string(),
+                description: "Running compactions and top tablets by score".to_string(),
+            },
+            MenuOption {
+                action: BeToolAction::ClusterPstack,
+                key: "[13]".to_string(),
+                name: "pstack-cluster".to_string(),
+                description: "Stack traces from every BE over SSH".to_string(),
+            },
+            MenuOption {
+                action: BeToolAction::Bundle,
+                key: "[14]".to_string(),
+                name: "diagnostic-bundle".to_string(),
+                description: "Run several tools back-to-back with one summary".to_string(),
+            },
             MenuOption {
                 action: BeToolAction::Back,
-                key: "[7]".to_string(),
+                key: "[15]".to_string(),
                 name: "← Back".to_string(),
                 description: "Return to main menu".to_string(),
             },
         ],
     };
-    menu.show()
+    menu.show_with_context(context)
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum MemzAction {
     Current,
     Global,
+    TrackerBreakdown,
+    Watch,
     Back,
 }
 
@@ -373,8 +691,20 @@ pub fn show_memz_menu() -> Result<MemzAction> {
                 description: "Show memory tracker (global)".to_string(),
             },
             MenuOption {
-                action: MemzAction::Back,
+                action: MemzAction::TrackerBreakdown,
                 key: "[3]".to_string(),
+                name: "Tracker breakdown".to_string(),
+                description: "Top trackers by consumption from /mem_tracker".to_string(),
+            },
+            MenuOption {
+                action: MemzAction::Watch,
+                key: "[4]".to_string(),
+                name: "Watch".to_string(),
+                description: "Sample /memz over time and report growth per minute".to_string(),
+            },
+            MenuOption {
+                action: MemzAction::Back,
+                key: "[5]".to_string(),
                 name: "← Back to BE Tools".to_string(),
                 description: "Return to BE tools menu".to_string(),
             },
@@ -386,14 +716,21 @@ pub fn show_memz_menu() -> Result<MemzAction> {
 #[derive(Debug, Clone, Copy)]
 pub enum PostExecutionAction {
     Continue,
+    ViewPrevious,
     BackToMain,
     Exit,
 }
 
-pub fn show_post_execution_menu(tool_name: &str) -> Result<PostExecutionAction> {
+pub fn show_post_execution_menu(
+    tool_name: &str,
+    summary: Option<&str>,
+) -> Result<PostExecutionAction> {
     let menu = Menu {
         step: 4,
-        title: format!("{tool_name} completed - What's next?"),
+        title: format!(
+            "{tool_name} {} - What's next?",
+            summary.unwrap_or("completed")
+        ),
         options: vec![
             MenuOption {
                 action: PostExecutionAction::Continue,
@@ -402,14 +739,20 @@ pub fn show_post_execution_menu(tool_name: &str) -> Result<PostExecutionAction>
                 description: "Run another tool".to_string(),
             },
             MenuOption {
-                action: PostExecutionAction::BackToMain,
+                action: PostExecutionAction::ViewPrevious,
                 key: "[2]".to_string(),
+                name: "View previous results".to_string(),
+                description: "Re-display the summary of an earlier output file".to_string(),
+            },
+            MenuOption {
+                action: PostExecutionAction::BackToMain,
+                key: "[3]".to_string(),
                 name: "← Back to Main".to_string(),
                 description: "Return to service selection".to_string(),
             },
             MenuOption {
                 action: PostExecutionAction::Exit,
-                key: "[3]".to_string(),
+                key: "[4]".to_string(),
                 name: "Exit".to_string(),
                 description: "Exit the application".to_string(),
             },
@@ -426,6 +769,6 @@ pub fn ask_continue(prompt: &str) -> Result<bool> {
         .items(&options)
         .default(0)
         .interact()
-        .map_err(|e| CliError::InvalidInput(format!("Continue selection failed: {e}")))?;
+        .map_err(|e| CliError::prompt_failed("Continue selection failed", e))?;
     Ok(selection == 0)
 }
@@ -1,10 +1,21 @@
 use console::{Term, style};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 
+use crate::config::Config;
+use crate::tools::common::redact::redact_command;
+
+pub mod bundle;
 pub mod dialogs;
 pub mod error_handlers;
+pub mod hints;
 pub mod menu;
+pub mod results_viewer;
 pub mod selector;
 pub mod service_handlers;
+pub mod sink;
+pub mod table;
+pub mod terminal;
 pub mod tool_executor;
 pub mod utils;
 
@@ -22,45 +33,186 @@ pub static WARNING: &str = "[*] ";
 pub static INFO: &str = "[i] ";
 pub static PROCESS: &str = "[>] ";
 pub static SEARCH: &str = "[?] ";
+pub static DEBUG: &str = "[d] ";
+
+/// Set once per config change; read by everything that prints
+static PLAIN_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Apply the plain output decision for the rest of the process.
+///
+/// Plain mode comes from `plain_output`, `--plain`, `CLOUD_CLI_PLAIN`, `NO_COLOR` or
+/// stdout not being a terminal. It turns off `console` styling globally, which also
+/// covers the selectors and dialoguer prompts, and makes [`plain_text`] swap box
+/// drawing for ASCII.
+pub fn configure_output(config: &Config) {
+    let plain = config.plain_output
+        || crate::config::plain_requested()
+        || !std::io::IsTerminal::is_terminal(&std::io::stdout());
+    PLAIN_OUTPUT.store(plain, Ordering::Relaxed);
+    console::set_colors_enabled(!plain);
+    console::set_colors_enabled_stderr(!plain);
+}
+
+pub fn plain_output() -> bool {
+    PLAIN_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// How much the CLI prints; each level includes everything below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Results, warnings and errors only; progress lines are dropped
+    Quiet,
+    Normal,
+    /// Per-item progress of batches
+    Verbose,
+    /// Also [`print_debug`] output: retries, probe attempts, stderr of failed commands
+    Debug,
+}
+
+impl Verbosity {
+    pub const ALL: [Verbosity; 4] = [
+        Verbosity::Quiet,
+        Verbosity::Normal,
+        Verbosity::Verbose,
+        Verbosity::Debug,
+    ];
+
+    /// A level name or its number, 0 (quiet) to 3 (debug)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "quiet" | "0" => Some(Self::Quiet),
+            "normal" | "1" => Some(Self::Normal),
+            "verbose" | "2" => Some(Self::Verbose),
+            "debug" | "3" => Some(Self::Debug),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Quiet => "quiet",
+            Self::Normal => "normal",
+            Self::Verbose => "verbose",
+            Self::Debug => "debug",
+        }
+    }
+}
+
+const VERBOSITY_UNSET: u8 = u8::MAX;
 
-enum MessageType {
+/// Session verbosity; read from the environment on first use
+static VERBOSITY: AtomicU8 = AtomicU8::new(VERBOSITY_UNSET);
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        VERBOSITY_UNSET => {
+            let level = crate::config::verbosity_requested().unwrap_or(Verbosity::Normal);
+            VERBOSITY.store(level as u8, Ordering::Relaxed);
+            level
+        }
+        n => Verbosity::ALL[n as usize],
+    }
+}
+
+/// Replace the verbosity for the rest of the session (settings menu)
+pub fn set_verbosity(level: Verbosity) {
+    VERBOSITY.store(level as u8, Ordering::Relaxed);
+}
+
+fn ascii_fallback(c: char) -> char {
+    match c {
+        '┌' | '┬' | '┐' | '├' | '┼' | '┤' | '└' | '┴' | '┘' => '+',
+        '─' | '═' => '-',
+        '│' => '|',
+        '←' => '<',
+        '→' => '>',
+        '↑' => '^',
+        '↓' => 'v',
+        other => other,
+    }
+}
+
+/// `text` for the console: unchanged normally, ASCII tables and arrows in plain mode
+pub fn plain_text(text: &str) -> Cow<'_, str> {
+    if plain_output() {
+        to_ascii(text)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn to_ascii(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.chars().map(ascii_fallback).collect())
+    }
+}
+
+/// Kind of a status line, see [`sink::OutputSink`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageType {
     Success,
     Error,
     Warning,
     Info,
 }
 
-fn print_message(level: MessageType, message: &str) {
-    match level {
-        MessageType::Success => {
-            println!("{}", style(format!("{SUCCESS} {message}")).green().bold())
-        }
-        MessageType::Error => eprintln!("{}", style(format!("{ERROR} {message}")).red().bold()),
-        MessageType::Warning => {
-            println!("{}", style(format!("{WARNING} {message}")).yellow().bold())
-        }
-        MessageType::Info => println!("{}", style(format!("{INFO} {message}")).blue()),
-    }
+pub(crate) fn print_message(level: MessageType, message: &str) {
+    let message = plain_text(message);
+    let (to_stderr, line) = match level {
+        MessageType::Success => (false, style(format!("{SUCCESS} {message}")).green().bold()),
+        MessageType::Error => (true, style(format!("{ERROR} {message}")).red().bold()),
+        MessageType::Warning => (false, style(format!("{WARNING} {message}")).yellow().bold()),
+        MessageType::Info => (false, style(format!("{INFO} {message}")).blue()),
+    };
+    terminal::write_line(to_stderr, &line.to_string());
 }
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub fn print_header() {
+    print_header_for(None, None);
+}
+
+/// Print the header; a production profile switches it to a red banner naming the profile.
+/// An active session tag is shown in the subtitle.
+pub fn print_header_for(production_profile: Option<&str>, session_tag: Option<&str>) {
     let term = Term::stdout();
     // Fallback width if terminal size can't be determined
     let width = term.size_checked().map(|s| s.1 as usize).unwrap_or(80);
 
-    let title = "SelectDB CLI Tools for Apache Doris";
-    let version_info = format!("Version {VERSION}");
+    let (title, subtitle) = header_lines(production_profile, session_tag);
 
     println!();
-    println!("{}", style("─".repeat(width)).dim());
-    println!("{:^width$}", style(title).cyan().bold());
-    println!("{:^width$}", style(version_info).dim());
-    println!("{}", style("─".repeat(width)).dim());
+    let rule = plain_text("─").repeat(width);
+    println!("{}", style(&rule).dim());
+    if production_profile.is_some() {
+        println!("{:^width$}", style(title).red().bold());
+        println!("{:^width$}", style(subtitle).red());
+    } else {
+        println!("{:^width$}", style(title).cyan().bold());
+        println!("{:^width$}", style(subtitle).dim());
+    }
+    println!("{}", style(&rule).dim());
     println!();
 }
 
+fn header_lines(production_profile: Option<&str>, session_tag: Option<&str>) -> (String, String) {
+    let title = "SelectDB CLI Tools for Apache Doris".to_string();
+    let version_info = match session_tag {
+        Some(tag) => format!("Version {VERSION} - tag {tag} on every output file"),
+        None => format!("Version {VERSION}"),
+    };
+    match production_profile {
+        Some(profile) => (
+            format!("{title} [PRODUCTION: {profile}]"),
+            format!("{version_info} - strict mode: heavy tools require confirmation"),
+        ),
+        None => (title, version_info),
+    }
+}
+
 pub fn print_success(message: &str) {
     print_message(MessageType::Success, message);
 }
@@ -77,6 +229,21 @@ pub fn print_info(message: &str) {
     print_message(MessageType::Info, message);
 }
 
+/// Info line shown from [`Verbosity::Verbose`] up, for per-item detail
+pub fn print_verbose(message: &str) {
+    if verbosity() >= Verbosity::Verbose {
+        print_message(MessageType::Info, message);
+    }
+}
+
+/// Diagnostic line on stderr, shown only at [`Verbosity::Debug`]
+pub fn print_debug(message: &str) {
+    if verbosity() >= Verbosity::Debug {
+        let line = style(format!("{DEBUG} {}", plain_text(message))).dim();
+        terminal::write_line(true, &line.to_string());
+    }
+}
+
 pub fn print_step(step: u8, message: &str) {
     println!();
     println!(
@@ -97,7 +264,7 @@ pub fn print_process_info(pid: u32, command: &str) {
     println!(
         "  {} Command: {}",
         style(">").blue(),
-        style(truncate_command(command, 60)).dim()
+        style(truncate_command(&redact_command(command), 60)).dim()
     );
 }
 
@@ -112,19 +279,77 @@ pub fn print_goodbye() {
     println!();
 }
 
+/// `command` cut to `max_len` columns, with `...` appended when shortened
 pub fn truncate_command(command: &str, max_len: usize) -> String {
-    if command.len() <= max_len {
+    if table::display_width(command) <= max_len {
         command.to_string()
     } else {
-        format!("{}...", &command[..max_len])
+        format!("{}...", table::truncate_to_width(command, max_len, ""))
     }
 }
 
 pub fn format_menu_item(icon: &str, title: &str, description: &str) -> String {
     format!(
         "{} {} - {}",
-        style(icon).blue(),
-        style(title).bold(),
-        style(description).dim()
+        style(plain_text(icon)).blue(),
+        style(plain_text(title)).bold(),
+        style(plain_text(description)).dim()
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_names_production_profile() {
+        let (title, subtitle) = header_lines(Some("prod-bj"), None);
+        assert!(title.ends_with("[PRODUCTION: prod-bj]"));
+        assert!(subtitle.contains("strict mode"));
+
+        let (title, subtitle) = header_lines(None, None);
+        assert!(!title.contains("PRODUCTION"));
+        assert_eq!(subtitle, format!("Version {VERSION}"));
+
+        let (_, subtitle) = header_lines(Some("prod-bj"), Some("SD-4821"));
+        assert!(subtitle.contains("tag SD-4821"));
+        assert!(subtitle.contains("strict mode"));
+    }
+
+    #[test]
+    fn verbosity_parses_names_and_numbers_in_order() {
+        assert_eq!(Verbosity::parse(" Debug "), Some(Verbosity::Debug));
+        assert_eq!(Verbosity::parse("0"), Some(Verbosity::Quiet));
+        assert_eq!(Verbosity::parse("loud"), None);
+        for level in Verbosity::ALL {
+            assert_eq!(Verbosity::parse(level.as_str()), Some(level));
+            assert_eq!(Verbosity::ALL[level as usize], level);
+        }
+        assert!(Verbosity::Quiet < Verbosity::Normal && Verbosity::Verbose < Verbosity::Debug);
+    }
+
+    #[test]
+    fn ascii_fallback_keeps_table_alignment() {
+        let table = "┌────┬──┐\n│ ab │ ← │\n└────┴──┘";
+        assert_eq!(to_ascii(table), "+----+--+\n| ab | < |\n+----+--+");
+        assert!(matches!(to_ascii("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn truncation_never_splits_multibyte_chars() {
+        // "订" is three bytes and two columns wide, "🚀" four bytes and two columns
+        let comment = "订单表 comment 🚀 done";
+        for max in 0..=table::display_width(comment) + 1 {
+            let cut = FormatHelper::truncate_string(comment, max);
+            assert!(table::display_width(&cut) <= max, "{max}: {cut}");
+            let command = truncate_command(comment, max);
+            assert!(table::display_width(command.trim_end_matches("...")) <= max);
+        }
+        assert_eq!(FormatHelper::truncate_string("订单表", 5), "订...");
+        assert_eq!(FormatHelper::truncate_string("订单表", 6), "订单表");
+        assert_eq!(truncate_command("订单表abc", 3), "订...");
+        assert_eq!(truncate_command("ab🚀cd", 3), "ab...");
+        assert_eq!(truncate_command("ab🚀cd", 4), "ab🚀...");
+        assert_eq!(truncate_command("/opt/doris/be", 60), "/opt/doris/be");
+    }
+}
@@ -0,0 +1,183 @@
+use console::{Key, Term};
+use std::fs;
+use std::io::Read;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::tools::common::format_utils;
+use crate::tools::common::retention::{self, OutputFile};
+use crate::ui::{self, InteractiveSelector};
+
+/// Files offered in the picker, newest first
+const MAX_LISTED: usize = 50;
+/// Bytes inspected when deciding whether a file is text
+const SNIFF_BYTES: usize = 8192;
+
+/// Output files of `service`'s tools, newest first
+pub fn recent_outputs(config: &Config, service: &str) -> Vec<OutputFile> {
+    let profile_prefix = config.artifact_file_name("");
+    let prefixes = retention::service_prefixes(service);
+    let mut files: Vec<OutputFile> =
+        retention::collect_managed_files(&config.output_dir, &profile_prefix)
+            .into_iter()
+            .filter(|f| {
+                prefixes
+                    .iter()
+                    .any(|p| stripped_name(f, &profile_prefix).starts_with(p))
+            })
+            .collect();
+    files.sort_by_key(|f| std::cmp::Reverse(f.modified));
+    files.truncate(MAX_LISTED);
+    files
+}
+
+fn stripped_name(file: &OutputFile, profile_prefix: &str) -> String {
+    let name = file
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match name.strip_prefix(profile_prefix) {
+        Some(rest) => rest.to_string(),
+        None => name,
+    }
+}
+
+/// Let the user pick an earlier output file and show its summary again
+pub fn view_previous_results(config: &Config, service: &str) -> Result<()> {
+    let files = recent_outputs(config, service);
+    if files.is_empty() {
+        ui::print_info(&format!(
+            "No previous {service} results in {}",
+            config.output_dir.display()
+        ));
+        return Ok(());
+    }
+
+    let selector = InteractiveSelector::new(files, format!("Previous {service} results"));
+    let file = selector.select()?;
+
+    ui::print_info("");
+    ui::print_info(&format!(
+        "{} ({})",
+        file.path.display(),
+        format_utils::format_bytes(file.size, 2, false)
+    ));
+    // Sniff before reading in full; heap dumps can be many gigabytes
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    fs::File::open(&file.path)?
+        .take(SNIFF_BYTES as u64)
+        .read_to_end(&mut head)?;
+    if head.contains(&0) {
+        ui::print_info("Binary file; open it with the matching tool (e.g. a heap analyzer).");
+        return Ok(());
+    }
+
+    let bytes = fs::read(&file.path)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let name = stripped_name(file, &config.artifact_file_name(""));
+    if name.starts_with("memz_") {
+        let (table, _) = crate::tools::be::extract_memory_metrics(&text);
+        println!("{}", ui::plain_text(&table));
+    } else if name.starts_with("pstack_") {
+        ui::print_info(&format!("{} thread(s)", count_pstack_threads(&text)));
+        show_head(&text);
+    } else if name.starts_with("jstack_") {
+        let (total, states) = jstack_thread_states(&text);
+        ui::print_info(&format!("{total} thread(s)"));
+        for (state, count) in states {
+            ui::print_info(&format!("  {count:>6}  {state}"));
+        }
+        show_head(&text);
+    } else {
+        show_paged(&text)?;
+    }
+    Ok(())
+}
+
+/// `Thread 12 (Thread 0x7f.. (LWP 1234)):` headers written by gdb/pstack
+pub fn count_pstack_threads(text: &str) -> usize {
+    text.lines()
+        .filter(|l| l.starts_with("Thread ") && l.contains('('))
+        .count()
+}
+
+/// Thread count and `java.lang.Thread.State` breakdown of a jstack dump, most common first
+pub fn jstack_thread_states(text: &str) -> (usize, Vec<(String, usize)>) {
+    let total = text.lines().filter(|l| l.starts_with('"')).count();
+    let mut states: Vec<(String, usize)> = Vec::new();
+    for state in text.lines().filter_map(|l| {
+        l.trim()
+            .strip_prefix("java.lang.Thread.State:")
+            .and_then(|s| s.split_whitespace().next())
+    }) {
+        match states.iter_mut().find(|(s, _)| s == state) {
+            Some((_, n)) => *n += 1,
+            None => states.push((state.to_string(), 1)),
+        }
+    }
+    states.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    (total, states)
+}
+
+/// Lines that fit on one screen, leaving room for the prompt
+fn page_height() -> usize {
+    let (rows, _) = Term::stdout().size();
+    (rows as usize).saturating_sub(2).max(5)
+}
+
+fn show_head(text: &str) {
+    println!();
+    for line in text.lines().take(page_height()) {
+        println!("{line}");
+    }
+}
+
+fn show_paged(text: &str) -> Result<()> {
+    if !crate::ui::terminal::is_interactive() {
+        // No keys to page with; print it all
+        text.lines().for_each(|line| println!("{line}"));
+        return Ok(());
+    }
+    let term = Term::stdout();
+    let lines: Vec<&str> = text.lines().collect();
+    let mut pages = lines.chunks(page_height()).peekable();
+    while let Some(page) = pages.next() {
+        for line in page {
+            println!("{line}");
+        }
+        if pages.peek().is_none() {
+            break;
+        }
+        term.write_str("-- more (Enter/Space: next page, q: stop) --")?;
+        let key = term.read_key().map_err(dialoguer::Error::IO)?;
+        term.clear_line()?;
+        if matches!(key, Key::Char('q') | Key::Escape) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_threads_in_stack_dumps() {
+        let pstack = "Thread 2 (Thread 0x7f01 (LWP 101)):\n#0  0x1 in poll ()\n\
+            Thread 1 (Thread 0x7f00 (LWP 100)):\n#0  0x2 in main ()\n";
+        assert_eq!(count_pstack_threads(pstack), 2);
+
+        let jstack = "\"main\" #1 prio=5 os_prio=0\n   java.lang.Thread.State: RUNNABLE\n\n\
+            \"worker-1\" #20 daemon\n   java.lang.Thread.State: WAITING (parking)\n\n\
+            \"worker-2\" #21 daemon\n   java.lang.Thread.State: WAITING (parking)\n\n\
+            \"VM Thread\" os_prio=0\n";
+        let (total, states) = jstack_thread_states(jstack);
+        assert_eq!(total, 4);
+        assert_eq!(
+            states,
+            [("WAITING".to_string(), 2), ("RUNNABLE".to_string(), 1)]
+        );
+    }
+}
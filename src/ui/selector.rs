@@ -10,6 +10,7 @@ pub struct InteractiveSelector<T> {
     items: Vec<T>,
     title: String,
     page_size: usize,
+    default_index: usize,
 }
 
 impl<T> InteractiveSelector<T> {
@@ -18,9 +19,16 @@ impl<T> InteractiveSelector<T> {
             items,
             title,
             page_size: 30,
+            default_index: 0,
         }
     }
 
+    /// Item highlighted when the selector opens
+    pub fn with_default(mut self, index: usize) -> Self {
+        self.default_index = index;
+        self
+    }
+
     pub fn with_page_size(mut self, page_size: usize) -> Self {
         self.page_size = page_size.max(1);
         self
@@ -33,131 +41,319 @@ impl<T> InteractiveSelector<T> {
         if self.items.is_empty() {
             return Err(CliError::InvalidInput("No items to select from".into()));
         }
+        crate::ui::terminal::require_terminal(&format!("Cannot select from '{}'", self.title))?;
 
         let term = Term::stdout();
-        let mut selection: usize = 0;
+        let labels: Vec<String> = self.items.iter().map(|i| self.format_item(i)).collect();
+        let mut view = FilterView::new(labels.len());
+        view.selection = self.default_index.min(labels.len() - 1);
         let mut last_drawn_lines: usize;
 
         let header_lines = 2usize;
         crate::ui::print_info("");
         crate::ui::print_info(&self.title.to_string());
-        crate::ui::print_info("Use ↑/↓, ←/→, 1-9, Enter");
+        crate::ui::print_info("Use ↑/↓, ←/→, 1-9, / to filter, Esc to clear, Enter");
 
         term.hide_cursor()
             .map_err(|e| CliError::InvalidInput(e.to_string()))?;
 
-        last_drawn_lines = self.render_selection_list(&term, selection)?;
+        last_drawn_lines = self.render_selection_list(&term, &labels, &view, None)?;
 
         loop {
-            match term
-                .read_key()
-                .map_err(|e| CliError::InvalidInput(e.to_string()))?
-            {
+            let key = term.read_key().map_err(dialoguer::Error::IO)?;
+            let visible = view.visible.len();
+            let page_size = self.page_size.min(visible).max(1);
+            match key {
                 Key::Enter => {
-                    term.show_cursor()
-                        .map_err(|e| CliError::InvalidInput(e.to_string()))?;
-                    term.clear_last_lines(last_drawn_lines + header_lines + 1)
-                        .ok();
-                    break;
-                }
-                Key::ArrowUp => {
-                    selection = if selection == 0 {
-                        self.items.len() - 1
-                    } else {
-                        selection - 1
-                    };
-                }
-                Key::ArrowDown => {
-                    selection = if selection + 1 >= self.items.len() {
-                        0
-                    } else {
-                        selection + 1
-                    };
-                }
-                Key::ArrowLeft => {
-                    if !self.items.is_empty() {
-                        let page_size = self.page_size.min(self.items.len()).max(1);
-                        let current_page = selection / page_size;
-                        if current_page > 0 {
-                            selection = (current_page - 1) * page_size;
-                        }
+                    if let Some(&index) = view.visible.get(view.selection) {
+                        term.show_cursor()
+                            .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+                        term.clear_last_lines(last_drawn_lines + header_lines + 1)
+                            .ok();
+                        return Ok(&self.items[index]);
                     }
                 }
-                Key::ArrowRight => {
-                    if !self.items.is_empty() {
-                        let page_size = self.page_size.min(self.items.len()).max(1);
-                        let total_pages = self.items.len().div_ceil(page_size);
-                        let current_page = selection / page_size;
-                        if current_page + 1 < total_pages {
-                            selection = (current_page + 1) * page_size;
-                            if selection >= self.items.len() {
-                                selection = self.items.len() - 1;
-                            }
-                        }
-                    }
+                Key::Escape => view.clear(labels.len()),
+                Key::Backspace if view.editing => {
+                    view.query.pop();
+                    view.refilter(&labels);
                 }
-                Key::Char(c) => {
-                    if let Some(d) = c.to_digit(10) {
-                        let page_size = self.page_size.min(self.items.len()).max(1);
-                        let current_page = selection / page_size;
-                        let page_start = current_page * page_size;
-                        let idx_in_page = d.saturating_sub(1) as usize;
-                        let target = page_start + idx_in_page;
-                        if target < self.items.len() {
-                            selection = target;
-                        }
-                    }
+                Key::Char(c) if view.editing => {
+                    view.query.push(c);
+                    view.refilter(&labels);
                 }
-                _ => {}
+                Key::Char('/') => view.editing = true,
+                _ if visible == 0 => {}
+                key => view.navigate(&key, page_size),
             }
 
             term.clear_last_lines(last_drawn_lines).ok();
-            last_drawn_lines = self.render_selection_list(&term, selection)?;
+            last_drawn_lines = self.render_selection_list(&term, &labels, &view, None)?;
         }
-
-        Ok(&self.items[selection])
     }
 
-    fn render_selection_list(&self, term: &Term, selection: usize) -> Result<usize>
+    /// Pick one or more items: Space toggles, `a` toggles the whole page, Enter confirms.
+    ///
+    /// Items are returned in their original order; Enter is ignored until at
+    /// least one item is checked.
+    pub fn select_multi(&self) -> Result<Vec<&T>>
     where
         Self: ItemFormatter<T>,
     {
-        let total = self.items.len();
+        if self.items.is_empty() {
+            return Err(CliError::InvalidInput("No items to select from".into()));
+        }
+        crate::ui::terminal::require_terminal(&format!("Cannot select from '{}'", self.title))?;
+
+        let term = Term::stdout();
+        let labels: Vec<String> = self.items.iter().map(|i| self.format_item(i)).collect();
+        let mut view = FilterView::new(labels.len());
+        view.selection = self.default_index.min(labels.len() - 1);
+        let mut checked = vec![false; labels.len()];
+
+        let header_lines = 2usize;
+        crate::ui::print_info("");
+        crate::ui::print_info(&self.title.to_string());
+        crate::ui::print_info(
+            "Use ↑/↓, ←/→, 1-9, / to filter, Space to toggle, a for the whole page, Enter",
+        );
+
+        term.hide_cursor()
+            .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+
+        let mut last_drawn_lines =
+            self.render_selection_list(&term, &labels, &view, Some(&checked))?;
+
+        loop {
+            let key = term.read_key().map_err(dialoguer::Error::IO)?;
+            let visible = view.visible.len();
+            let page_size = self.page_size.min(visible).max(1);
+            match key {
+                Key::Enter => {
+                    if checked.contains(&true) {
+                        term.show_cursor()
+                            .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+                        term.clear_last_lines(last_drawn_lines + header_lines + 1)
+                            .ok();
+                        return Ok(self
+                            .items
+                            .iter()
+                            .zip(&checked)
+                            .filter(|(_, c)| **c)
+                            .map(|(item, _)| item)
+                            .collect());
+                    }
+                }
+                Key::Escape => view.clear(labels.len()),
+                Key::Backspace if view.editing => {
+                    view.query.pop();
+                    view.refilter(&labels);
+                }
+                Key::Char(c) if view.editing => {
+                    view.query.push(c);
+                    view.refilter(&labels);
+                }
+                Key::Char('/') => view.editing = true,
+                _ if visible == 0 => {}
+                Key::Char(' ') => {
+                    let index = view.visible[view.selection];
+                    checked[index] = !checked[index];
+                }
+                Key::Char('a') => {
+                    let start = view.selection / page_size * page_size;
+                    let end = (start + page_size).min(visible);
+                    toggle_all(&mut checked, &view.visible[start..end]);
+                }
+                key => view.navigate(&key, page_size),
+            }
+
+            term.clear_last_lines(last_drawn_lines).ok();
+            last_drawn_lines = self.render_selection_list(&term, &labels, &view, Some(&checked))?;
+        }
+    }
+
+    /// Draw the current page; `checked` adds `[x]` markers in multi-select mode
+    fn render_selection_list(
+        &self,
+        term: &Term,
+        labels: &[String],
+        view: &FilterView,
+        checked: Option<&[bool]>,
+    ) -> Result<usize> {
+        let total = view.visible.len();
         let page_size = self.page_size.min(total).max(1);
-        let total_pages = total.div_ceil(page_size);
-        let current_page = selection / page_size;
+        let total_pages = total.div_ceil(page_size).max(1);
+        let current_page = view.selection / page_size;
         let start = current_page * page_size;
         let end = (start + page_size).min(total);
 
         let mut lines_drawn = 0usize;
+        let count = if view.is_filtered() {
+            format!("{total} of {} (filtered)", labels.len())
+        } else {
+            format!("{total} items")
+        };
+        let picked = match checked {
+            Some(c) => match c.iter().filter(|c| **c).count() {
+                0 => ", none selected yet".to_string(),
+                n => format!(", {n} selected"),
+            },
+            None => String::new(),
+        };
         let page_title = format!(
-            "Page {}/{}  ({} items)",
+            "Page {}/{}  ({count}{picked})",
             current_page + 1,
-            total_pages,
-            total
+            total_pages
         );
         term.clear_line()?;
         term.write_line(&page_title)
             .map_err(|e| CliError::InvalidInput(e.to_string()))?;
         lines_drawn += 1;
 
-        for (i, item) in self.items[start..end].iter().enumerate() {
-            let global_index = start + i;
+        if view.editing || view.is_filtered() {
+            let cursor = if view.editing { "_" } else { "" };
+            let line = format!("Filter: /{}{cursor}", view.query);
             term.clear_line()?;
-            let arrow = if global_index == selection {
+            term.write_line(&style(line).yellow().to_string())
+                .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+            lines_drawn += 1;
+        }
+
+        for (pos, &index) in view.visible[start..end].iter().enumerate() {
+            let visible_index = start + pos;
+            term.clear_line()?;
+            let arrow = if visible_index == view.selection {
                 style(">").cyan().bold().to_string()
             } else {
                 " ".to_string()
             };
-            let line = format!("{arrow} {}. {}", global_index + 1, self.format_item(item));
+            let marker = match checked {
+                Some(c) if c[index] => "[x] ",
+                Some(_) => "[ ] ",
+                None => "",
+            };
+            let line = format!(
+                "{arrow} {marker}{}. {}",
+                visible_index + 1,
+                crate::ui::plain_text(&labels[index])
+            );
             term.write_line(&line)
                 .map_err(|e| CliError::InvalidInput(e.to_string()))?;
             lines_drawn += 1;
         }
+
+        if total == 0 {
+            term.clear_line()?;
+            term.write_line(&style("  No matching items").dim().to_string())
+                .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+            lines_drawn += 1;
+        }
         Ok(lines_drawn)
     }
 }
 
+/// Filter state of a selector: the query and the item indices it matches
+struct FilterView {
+    query: String,
+    editing: bool,
+    visible: Vec<usize>,
+    /// Position within `visible`
+    selection: usize,
+}
+
+impl FilterView {
+    fn new(total: usize) -> Self {
+        Self {
+            query: String::new(),
+            editing: false,
+            visible: (0..total).collect(),
+            selection: 0,
+        }
+    }
+
+    fn clear(&mut self, total: usize) {
+        *self = Self::new(total);
+    }
+
+    fn refilter(&mut self, labels: &[String]) {
+        self.visible = filter_indices(labels, &self.query);
+        self.selection = 0;
+    }
+
+    fn is_filtered(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Arrow keys and digit shortcuts shared by single and multi select
+    fn navigate(&mut self, key: &Key, page_size: usize) {
+        let visible = self.visible.len();
+        match key {
+            Key::ArrowUp => {
+                self.editing = false;
+                self.selection = if self.selection == 0 {
+                    visible - 1
+                } else {
+                    self.selection - 1
+                };
+            }
+            Key::ArrowDown => {
+                self.editing = false;
+                self.selection = if self.selection + 1 >= visible {
+                    0
+                } else {
+                    self.selection + 1
+                };
+            }
+            Key::ArrowLeft => {
+                self.editing = false;
+                let current_page = self.selection / page_size;
+                if current_page > 0 {
+                    self.selection = (current_page - 1) * page_size;
+                }
+            }
+            Key::ArrowRight => {
+                self.editing = false;
+                let total_pages = visible.div_ceil(page_size);
+                let current_page = self.selection / page_size;
+                if current_page + 1 < total_pages {
+                    self.selection = ((current_page + 1) * page_size).min(visible - 1);
+                }
+            }
+            Key::Char(c) => {
+                if let Some(d) = c.to_digit(10) {
+                    let current_page = self.selection / page_size;
+                    let page_start = current_page * page_size;
+                    let idx_in_page = d.saturating_sub(1) as usize;
+                    let target = page_start + idx_in_page;
+                    if target < visible {
+                        self.selection = target;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Check every item in `page`, or uncheck them all if they already are
+fn toggle_all(checked: &mut [bool], page: &[usize]) {
+    let all_checked = page.iter().all(|&i| checked[i]);
+    for &i in page {
+        checked[i] = !all_checked;
+    }
+}
+
+/// Indices of labels containing `query`, case-insensitively
+fn filter_indices(labels: &[String], query: &str) -> Vec<usize> {
+    let needle = query.to_lowercase();
+    labels
+        .iter()
+        .enumerate()
+        .filter(|(_, label)| label.to_lowercase().contains(&needle))
+        .map(|(i, _)| i)
+        .collect()
+}
+
 impl ItemFormatter<String> for InteractiveSelector<String> {
     fn format_item(&self, item: &String) -> String {
         item.clone()
@@ -172,3 +368,49 @@ impl ItemFormatter<crate::tools::fe::routine_load::RoutineLoadJob>
         format!("{} - {} ({})", job.id, name, job.state)
     }
 }
+
+impl ItemFormatter<crate::tools::common::retention::OutputFile>
+    for InteractiveSelector<crate::tools::common::retention::OutputFile>
+{
+    fn format_item(&self, file: &crate::tools::common::retention::OutputFile) -> String {
+        let name = file
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let modified = chrono::DateTime::<chrono::Local>::from(file.modified);
+        format!(
+            "{} {:>10}  {}",
+            modified.format("%Y-%m-%d %H:%M:%S"),
+            crate::tools::common::format_utils::format_bytes(file.size, 1, false),
+            name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_matches_case_insensitive_substring() {
+        let labels: Vec<String> = ["orders", "ORDER_items", "customers", "t_order_2024"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(filter_indices(&labels, "order"), vec![0, 1, 3]);
+        assert_eq!(filter_indices(&labels, "Cust"), vec![2]);
+        assert!(filter_indices(&labels, "nope").is_empty());
+        assert_eq!(filter_indices(&labels, "").len(), labels.len());
+    }
+
+    #[test]
+    fn page_toggle_checks_then_clears() {
+        let mut checked = vec![true, false, false, false];
+        toggle_all(&mut checked, &[0, 1, 2]);
+        assert_eq!(checked, [true, true, true, false]);
+        toggle_all(&mut checked, &[0, 1, 2]);
+        assert_eq!(checked, [false, false, false, false]);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::config::Config;
+use crate::config_loader::{DorisConfig, Environment};
 use crate::error::{self, Result};
 use crate::tools::Tool;
 use crate::ui::*;
+use chrono::{DateTime, Utc};
 
 fn index_by_name(tools: &[Box<dyn Tool>], name: &str) -> Option<usize> {
     tools.iter().position(|t| t.name() == name)
@@ -14,20 +16,43 @@ fn run_tool_with_post(
     service: &str,
 ) -> Result<Option<()>> {
     let tool = &*tools[index];
-    if let Err(e) = crate::execute_tool_enhanced(config, tool, service) {
-        match e {
-            error::CliError::GracefulExit => {}
-            _ => print_error(&format!("Tool execution failed: {e}")),
+    let summary = match crate::execute_tool_enhanced(config, tool, service) {
+        Ok(result) => result.map(|r| r.summary()),
+        Err(e) => {
+            match e {
+                error::CliError::GracefulExit => {}
+                _ => print_error(&format!("Tool execution failed: {e}")),
+            }
+            return Ok(Some(()));
         }
-        return Ok(Some(()));
-    }
+    };
 
-    match show_post_execution_menu(tool.name())? {
-        PostExecutionAction::Continue => Ok(Some(())),
+    match post_execution_action(config, tool.name(), summary.as_deref(), service)? {
         PostExecutionAction::BackToMain => Err(error::CliError::GracefulExit),
-        PostExecutionAction::Exit => {
-            crate::ui::print_goodbye();
-            std::process::exit(0);
+        _ => Ok(Some(())),
+    }
+}
+
+/// Show the post-execution menu until the user leaves it; viewing results loops back
+fn post_execution_action(
+    config: &Config,
+    tool_name: &str,
+    summary: Option<&str>,
+    service: &str,
+) -> Result<PostExecutionAction> {
+    loop {
+        match show_post_execution_menu(tool_name, summary)? {
+            PostExecutionAction::ViewPrevious => {
+                match crate::ui::results_viewer::view_previous_results(config, service) {
+                    Ok(()) | Err(error::CliError::GracefulExit) => {}
+                    Err(e) => print_error(&format!("Could not show previous results: {e}")),
+                }
+            }
+            PostExecutionAction::Exit => {
+                crate::ui::print_goodbye();
+                std::process::exit(0);
+            }
+            action => return Ok(action),
         }
     }
 }
@@ -66,11 +91,55 @@ fn run_jmap_submenu_by_names(
                     _ => continue,
                 }
             }
+            crate::ui::JmapAction::HistoDiff => {
+                match run_tool_by_name(config, tools, "jmap-histo-diff", service) {
+                    Err(error::CliError::GracefulExit) => return Ok(None),
+                    _ => continue,
+                }
+            }
             crate::ui::JmapAction::Back => return Ok(Some(())),
         }
     }
 }
 
+/// Rough age such as `5m ago`, for the menu context line
+fn format_age(secs: i64) -> String {
+    match secs.max(0) {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86400),
+    }
+}
+
+/// Lines under the FE/BE menu header naming the install the tools act on, so
+/// FE analysis on a mixed host is not run against the BE log_dir unnoticed
+fn context_lines(doris: &DorisConfig, env: Environment, now: DateTime<Utc>) -> Vec<String> {
+    let paths = doris.service_paths(env);
+    let pid = resolve_service_pid(doris, &env.to_string())
+        .map(|pid| format!("PID {pid}"))
+        .unwrap_or_else(|_| format!("no local {env} process"));
+    let detected = doris
+        .last_detected
+        .map(|at| format!("detected {}", format_age((now - at).num_seconds())))
+        .unwrap_or_else(|| "never detected".to_string());
+    vec![
+        format!(
+            "  {env} context: {} environment, {pid}, {detected}",
+            doris.environment
+        ),
+        format!("  install_dir: {}", paths.install_dir.display()),
+        format!("  log_dir:     {}", paths.log_dir.display()),
+    ]
+}
+
+/// Context for the `env` menu, reloaded so it follows detection between tools
+fn service_context(env: Environment) -> Vec<String> {
+    crate::config_loader::load_config()
+        .map(|doris| context_lines(&doris, env, Utc::now()))
+        .unwrap_or_default()
+}
+
 /// Generic loop for handling a service type (FE or BE).
 pub fn handle_service_loop(
     config: &Config,
@@ -87,9 +156,12 @@ pub fn handle_service_loop(
 /// Handle FE service loop with nested menu structure
 pub fn handle_fe_service_loop(config: &Config, tools: &[Box<dyn Tool>]) -> Result<()> {
     loop {
-        match crate::ui::show_fe_tools_menu()? {
+        match crate::ui::show_fe_tools_menu(&service_context(Environment::FE))? {
             crate::ui::FeToolAction::FeList => {
-                run_tool_by_name(config, tools, "fe-list", "FE").ok();
+                match run_tool_by_name(config, tools, "fe-list", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
             }
             crate::ui::FeToolAction::Jmap => {
                 match run_jmap_submenu_by_names(config, tools, "jmap-dump", "jmap-histo", "FE") {
@@ -122,6 +194,102 @@ pub fn handle_fe_service_loop(config: &Config, tools: &[Box<dyn Tool>]) -> Resul
                     }
                 }
             }
+            crate::ui::FeToolAction::GcAnalyzer => {
+                match run_tool_by_name(config, tools, "fe-gc-analyzer", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::ReplicaHealth => {
+                match run_tool_by_name(config, tools, "fe-replica-health", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::AuditLog => {
+                match run_tool_by_name(config, tools, "fe-audit-log", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::ConfInspector => {
+                match run_tool_by_name(config, tools, "fe-conf-inspector", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::MetaService => {
+                match run_tool_by_name(config, tools, "meta-service-check", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::AlterJobs => {
+                match run_tool_by_name(config, tools, "fe-alter-jobs", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::QueryProfile => {
+                match run_tool_by_name(config, tools, "fe-query-profile", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::ConfigDrift => {
+                match run_tool_by_name(config, tools, "fe-config-drift", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::Metrics => {
+                match run_tool_by_name(config, tools, "fe-metrics", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::TabletLookup => {
+                match run_tool_by_name(config, tools, "fe-tablet-lookup", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::Editlog => {
+                match run_tool_by_name(config, tools, "fe-editlog", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::ClusterVersion => {
+                match run_tool_by_name(config, tools, "cluster-version", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::DataSize => {
+                match run_tool_by_name(config, tools, "fe-data-size", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::WorkloadGroups => {
+                match run_tool_by_name(config, tools, "fe-workload-groups", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::SqlConsole => {
+                match run_tool_by_name(config, tools, "fe-sql-console", "FE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::FeToolAction::Bundle => {
+                match crate::ui::bundle::run_diagnostic_bundle(config, tools, "FE") {
+                    Ok(()) | Err(error::CliError::GracefulExit) => {}
+                    Err(e) => print_error(&format!("Diagnostic bundle failed: {e}")),
+                }
+            }
             crate::ui::FeToolAction::Back => return Ok(()),
         }
     }
@@ -147,6 +315,26 @@ pub fn handle_routine_load_loop(config: &Config, tools: &[Box<dyn Tool>]) -> Res
                 tools,
                 crate::tools::fe::routine_load::RoutineLoadToolIndex::TrafficMonitor,
             )?,
+            crate::ui::RoutineLoadAction::SnapshotCompare => execute_routine_load_tool(
+                config,
+                tools,
+                crate::tools::fe::routine_load::RoutineLoadToolIndex::SnapshotCompare,
+            )?,
+            crate::ui::RoutineLoadAction::ErrorChecker => execute_routine_load_tool(
+                config,
+                tools,
+                crate::tools::fe::routine_load::RoutineLoadToolIndex::ErrorChecker,
+            )?,
+            crate::ui::RoutineLoadAction::LagTrend => execute_routine_load_tool(
+                config,
+                tools,
+                crate::tools::fe::routine_load::RoutineLoadToolIndex::LagTrend,
+            )?,
+            crate::ui::RoutineLoadAction::ManageJob => execute_routine_load_tool(
+                config,
+                tools,
+                crate::tools::fe::routine_load::RoutineLoadToolIndex::JobControl,
+            )?,
             crate::ui::RoutineLoadAction::Back => return Ok(()),
         }
     }
@@ -164,33 +352,38 @@ fn execute_routine_load_tool(
         ))
     })?;
 
-    if let Err(e) = crate::execute_tool_enhanced(config, tool, "FE") {
-        match e {
-            error::CliError::GracefulExit => { /* Do nothing, just loop again */ }
-            _ => print_error(&format!("Tool execution failed: {e}")),
-        }
-        return Ok(());
-    }
-    match crate::ui::show_post_execution_menu(tool.name())? {
-        crate::ui::PostExecutionAction::Continue => Ok(()),
-        crate::ui::PostExecutionAction::BackToMain => Err(error::CliError::GracefulExit),
-        crate::ui::PostExecutionAction::Exit => {
-            crate::ui::print_goodbye();
-            std::process::exit(0);
+    let summary = match crate::execute_tool_enhanced(config, tool, "FE") {
+        Ok(result) => result.map(|r| r.summary()),
+        Err(e) => {
+            match e {
+                error::CliError::GracefulExit => { /* Do nothing, just loop again */ }
+                _ => print_error(&format!("Tool execution failed: {e}")),
+            }
+            return Ok(());
         }
+    };
+    match post_execution_action(config, tool.name(), summary.as_deref(), "FE")? {
+        PostExecutionAction::BackToMain => Err(error::CliError::GracefulExit),
+        _ => Ok(()),
     }
 }
 
 /// Handle BE service loop (original logic)
 pub fn handle_be_service_loop(config: &Config, tools: &[Box<dyn Tool>]) -> Result<()> {
     loop {
-        match crate::ui::show_be_tools_menu()? {
+        match crate::ui::show_be_tools_menu(&service_context(Environment::BE))? {
             crate::ui::BeToolAction::BeList => {
                 match run_tool_by_name(config, tools, "be-list", "BE") {
                     Err(error::CliError::GracefulExit) => return Ok(()),
                     _ => continue,
                 }
             }
+            crate::ui::BeToolAction::Snapshot => {
+                match run_tool_by_name(config, tools, "be-snapshot", "BE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
             crate::ui::BeToolAction::Pstack => {
                 match run_tool_by_name(config, tools, "pstack", "BE") {
                     Err(error::CliError::GracefulExit) => return Ok(()),
@@ -229,10 +422,104 @@ pub fn handle_be_service_loop(config: &Config, tools: &[Box<dyn Tool>]) -> Resul
                             _ => continue,
                         }
                     }
+                    crate::ui::MemzAction::TrackerBreakdown => {
+                        match run_tool_by_name(config, tools, "mem-tracker", "BE") {
+                            Err(error::CliError::GracefulExit) => return Ok(()),
+                            _ => continue,
+                        }
+                    }
+                    crate::ui::MemzAction::Watch => {
+                        match run_tool_by_name(config, tools, "memz-watch", "BE") {
+                            Err(error::CliError::GracefulExit) => return Ok(()),
+                            _ => continue,
+                        }
+                    }
                     crate::ui::MemzAction::Back => break,
                 }
             },
+            crate::ui::BeToolAction::CrashInspector => {
+                match run_tool_by_name(config, tools, "be-crash-inspector", "BE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::BeToolAction::DiskUsage => {
+                match run_tool_by_name(config, tools, "be-disk-usage", "BE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::BeToolAction::Profiler => {
+                match run_tool_by_name(config, tools, "be-profiler", "BE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::BeToolAction::ConfInspector => {
+                match run_tool_by_name(config, tools, "be-conf-inspector", "BE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::BeToolAction::Compaction => {
+                match run_tool_by_name(config, tools, "be-compaction", "BE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::BeToolAction::ClusterPstack => {
+                match run_tool_by_name(config, tools, "pstack-cluster", "BE") {
+                    Err(error::CliError::GracefulExit) => return Ok(()),
+                    _ => continue,
+                }
+            }
+            crate::ui::BeToolAction::Bundle => {
+                match crate::ui::bundle::run_diagnostic_bundle(config, tools, "BE") {
+                    Ok(()) | Err(error::CliError::GracefulExit) => {}
+                    Err(e) => print_error(&format!("Diagnostic bundle failed: {e}")),
+                }
+            }
             crate::ui::BeToolAction::Back => return Ok(()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn mixed_context_follows_the_selected_service() {
+        let now = Utc::now();
+        let doris = DorisConfig {
+            environment: Environment::Mixed,
+            install_dir: PathBuf::from("/opt/doris/be"),
+            log_dir: PathBuf::from("/opt/doris/be/log"),
+            fe_install_dir: Some(PathBuf::from("/opt/doris/fe")),
+            be_install_dir: Some(PathBuf::from("/opt/doris/be")),
+            fe_process_pid: Some(100),
+            be_process_pid: Some(200),
+            last_detected: Some(now - chrono::Duration::minutes(5)),
+            ..Default::default()
+        };
+
+        let fe = context_lines(&doris, Environment::FE, now);
+        assert_eq!(
+            fe[0],
+            "  FE context: FE + BE environment, PID 100, detected 5m ago"
+        );
+        assert_eq!(fe[2], "  log_dir:     /opt/doris/fe/log");
+
+        let be = context_lines(&doris, Environment::BE, now);
+        assert!(be[0].contains("PID 200"));
+        assert_eq!(be[1], "  install_dir: /opt/doris/be");
+
+        let fe_only = DorisConfig {
+            environment: Environment::FE,
+            last_detected: None,
+            ..doris
+        };
+        assert!(context_lines(&fe_only, Environment::BE, now)[0].ends_with("never detected"));
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Mutex;
+
+use super::MessageType;
+
+/// Where status lines of a tool go: the terminal for the CLI, a buffer when
+/// cloud-cli runs inside another program
+pub trait OutputSink: Send + Sync {
+    fn message(&self, level: MessageType, text: &str);
+
+    fn info(&self, text: &str) {
+        self.message(MessageType::Info, text);
+    }
+
+    fn success(&self, text: &str) {
+        self.message(MessageType::Success, text);
+    }
+
+    fn warning(&self, text: &str) {
+        self.message(MessageType::Warning, text);
+    }
+
+    fn error(&self, text: &str) {
+        self.message(MessageType::Error, text);
+    }
+}
+
+/// Prints like `ui::print_*`, which is what the menus use
+pub struct ConsoleSink;
+
+impl OutputSink for ConsoleSink {
+    fn message(&self, level: MessageType, text: &str) {
+        super::print_message(level, text);
+    }
+}
+
+/// Keeps every line for the embedding program to log or show
+#[derive(Debug, Default)]
+pub struct BufferedSink {
+    lines: Mutex<Vec<(MessageType, String)>>,
+}
+
+impl BufferedSink {
+    /// Lines so far, oldest first; the buffer is left empty
+    pub fn take(&self) -> Vec<(MessageType, String)> {
+        self.lines
+            .lock()
+            .map(|mut lines| std::mem::take(&mut *lines))
+            .unwrap_or_default()
+    }
+}
+
+impl OutputSink for BufferedSink {
+    fn message(&self, level: MessageType, text: &str) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.push((level, text.to_string()));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Horizontal alignment of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// Box-drawn table sized by display width, so CJK text and emoji line up.
+///
+/// Columns grow to fit their widest cell unless capped with [`max_width`];
+/// capped cells are cut with an ellipsis.
+///
+/// [`max_width`]: TableBuilder::max_width
+#[derive(Debug, Clone)]
+pub struct TableBuilder {
+    headers: Vec<String>,
+    aligns: Vec<Align>,
+    max_widths: Vec<Option<usize>>,
+    rows: Vec<Vec<String>>,
+    ascii: bool,
+    empty_message: Option<String>,
+}
+
+/// Columns a string occupies in a terminal
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// `s` cut to at most `max` columns, ending in `ellipsis` when shortened.
+///
+/// Cuts fall on char boundaries; a wide char that would straddle the limit
+/// is dropped whole.
+pub fn truncate_to_width(s: &str, max: usize, ellipsis: &str) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    let budget = max.saturating_sub(display_width(ellipsis));
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if max >= display_width(ellipsis) {
+        out.push_str(ellipsis);
+    }
+    out
+}
+
+fn pad(s: &str, width: usize, align: Align) -> String {
+    let gap = width.saturating_sub(display_width(s));
+    match align {
+        Align::Left => format!("{s}{}", " ".repeat(gap)),
+        Align::Right => format!("{}{s}", " ".repeat(gap)),
+        Align::Center => {
+            let left = gap / 2;
+            format!("{}{s}{}", " ".repeat(left), " ".repeat(gap - left))
+        }
+    }
+}
+
+impl TableBuilder {
+    /// ASCII mode follows plain output unless set with [`ascii`](Self::ascii)
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        let columns = headers.len();
+        Self {
+            headers,
+            aligns: vec![Align::Left; columns],
+            max_widths: vec![None; columns],
+            rows: Vec::new(),
+            ascii: super::plain_output(),
+            empty_message: None,
+        }
+    }
+
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if let Some(a) = self.aligns.get_mut(column) {
+            *a = align;
+        }
+        self
+    }
+
+    pub fn max_width(mut self, column: usize, width: usize) -> Self {
+        if let Some(w) = self.max_widths.get_mut(column) {
+            *w = Some(width);
+        }
+        self
+    }
+
+    /// `+`, `-` and `|` frames with `...` ellipses instead of box drawing
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Line spanning the table body when there are no rows
+    pub fn empty_message(mut self, message: impl Into<String>) -> Self {
+        self.empty_message = Some(message.into());
+        self
+    }
+
+    /// Missing cells are left blank and extra cells are dropped
+    pub fn push_row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut row: Vec<String> = cells.into_iter().map(Into::into).collect();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+    }
+
+    pub fn row<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.push_row(cells);
+        self
+    }
+
+    fn ellipsis(&self) -> &'static str {
+        if self.ascii { "..." } else { "…" }
+    }
+
+    fn cells(&self, row: &[String]) -> Vec<String> {
+        row.iter()
+            .zip(&self.max_widths)
+            .map(|(cell, max)| match max {
+                Some(max) => truncate_to_width(cell, *max, self.ellipsis()),
+                None => cell.clone(),
+            })
+            .collect()
+    }
+
+    /// Rendered table, one line per `\n`
+    pub fn render(&self) -> String {
+        let headers = self.cells(&self.headers);
+        let rows: Vec<Vec<String>> = self.rows.iter().map(|r| self.cells(r)).collect();
+        let mut widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(display_width(cell));
+            }
+        }
+        // Widen the last column if the empty message would not fit
+        let inner = |widths: &[usize]| widths.iter().map(|w| w + 3).sum::<usize>() - 1;
+        let body = inner(&widths);
+        if rows.is_empty()
+            && let Some(message) = &self.empty_message
+            && let Some(last) = widths.last_mut()
+        {
+            *last += (display_width(message) + 2).saturating_sub(body);
+        }
+
+        let (h, v) = if self.ascii {
+            ("-", "|")
+        } else {
+            ("─", "│")
+        };
+        let rule = |l: &str, m: &str, r: &str| {
+            let (l, m, r) = if self.ascii {
+                ("+", "+", "+")
+            } else {
+                (l, m, r)
+            };
+            let segments: Vec<String> = widths.iter().map(|w| h.repeat(w + 2)).collect();
+            format!("{l}{}{r}\n", segments.join(m))
+        };
+        let line = |cells: &[String], aligns: &[Align]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .zip(aligns)
+                .map(|((cell, w), align)| pad(cell, *w, *align))
+                .collect();
+            format!("{v} {} {v}\n", padded.join(&format!(" {v} ")))
+        };
+
+        let mut out = rule("┌", "┬", "┐");
+        out.push_str(&line(&headers, &vec![Align::Left; headers.len()]));
+        out.push_str(&rule("├", "┼", "┤"));
+        for row in &rows {
+            out.push_str(&line(row, &self.aligns));
+        }
+        if rows.is_empty()
+            && let Some(message) = &self.empty_message
+        {
+            let body = pad(message, inner(&widths), Align::Center);
+            out.push_str(&format!("{v}{body}{v}\n"));
+        }
+        out.push_str(&rule("└", "┴", "┘"));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widths(table: &str) -> Vec<usize> {
+        table.lines().map(display_width).collect()
+    }
+
+    #[test]
+    fn aligns_cjk_and_truncates_long_cells() {
+        let table = TableBuilder::new(["Table", "Rows"])
+            .align(1, Align::Right)
+            .max_width(0, 12)
+            .ascii(false)
+            .row(["订单明细表", "1200"])
+            .row(["orders", "7"])
+            .row(["p_20240101_very_long_partition_name", "3"])
+            .render();
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(widths(&table).iter().all(|w| *w == widths(&table)[0]));
+        assert_eq!(lines[3], "│ 订单明细表   │ 1200 │");
+        assert_eq!(lines[5], "│ p_20240101_… │    3 │");
+
+        let ascii = TableBuilder::new(["Partition", "Lag"])
+            .ascii(true)
+            .max_width(0, 8)
+            .empty_message("(no data)")
+            .render();
+        assert!(ascii.is_ascii());
+        assert!(widths(&ascii).iter().all(|w| *w == widths(&ascii)[0]));
+        assert!(ascii.lines().nth(3).unwrap().contains("(no data)"));
+        assert_eq!(truncate_to_width("abcdefghij", 8, "..."), "abcde...");
+    }
+}
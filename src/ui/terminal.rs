@@ -0,0 +1,92 @@
+use std::io::{self, IsTerminal, Write};
+
+use crate::error::{CliError, Result};
+
+/// Shown when the menus cannot run because stdin or stdout is not a terminal
+const NO_TERMINAL_HINT: &str = "cloud-cli is menu driven and needs a terminal on stdin and stdout. \
+     Run it without pipes or redirects (use `ssh -t` for remote sessions); \
+     to keep a transcript, use `script -c cloud-cli` instead of `| tee`";
+
+/// Both stdin and stdout are terminals, so key reads and cursor movement work
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Fail with [`CliError::NoTerminal`] instead of waiting on a key that can never come
+pub fn require_terminal(what: &str) -> Result<()> {
+    if is_interactive() {
+        Ok(())
+    } else {
+        Err(CliError::NoTerminal(format!("{what}: {NO_TERMINAL_HINT}")))
+    }
+}
+
+/// The reader of our output went away: a closed pipe or a hung-up terminal
+pub fn is_closed_output(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+    ) || err.raw_os_error() == Some(libc::EIO)
+}
+
+/// Nobody is reading anymore: stop child tools and leave without a message
+pub fn exit_on_closed_output() -> ! {
+    let _ = crate::executor::kill_tracked_children();
+    std::process::exit(0)
+}
+
+/// `println!`/`eprintln!` without the panic when the pipe or terminal is gone
+pub fn write_line(to_stderr: bool, line: &str) {
+    let result = if to_stderr {
+        writeln!(io::stderr().lock(), "{line}")
+    } else {
+        writeln!(io::stdout().lock(), "{line}")
+    };
+    if let Err(e) = result
+        && is_closed_output(&e)
+    {
+        exit_on_closed_output();
+    }
+}
+
+/// Turn the `failed printing to stdout: Broken pipe` panic of plain `println!`
+/// calls into a quiet exit; other panics keep the default report
+pub fn install_closed_output_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| info.payload().downcast_ref::<&str>().copied())
+            .unwrap_or_default();
+        if is_print_failure(message) {
+            exit_on_closed_output();
+        }
+        default_hook(info);
+    }));
+}
+
+fn is_print_failure(message: &str) -> bool {
+    message.starts_with("failed printing to std")
+        && (message.contains("Broken pipe") || message.contains("Input/output error"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_output_is_recognised() {
+        assert!(is_closed_output(&io::Error::from(
+            io::ErrorKind::BrokenPipe
+        )));
+        assert!(is_closed_output(&io::Error::from_raw_os_error(libc::EIO)));
+        assert!(!is_closed_output(&io::Error::from(io::ErrorKind::NotFound)));
+
+        assert!(is_print_failure(
+            "failed printing to stdout: Broken pipe (os error 32)"
+        ));
+        assert!(!is_print_failure("index out of bounds"));
+    }
+}
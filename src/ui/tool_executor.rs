@@ -1,26 +1,33 @@
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
 use crate::config::Config;
-use crate::config_loader;
-use crate::error::{self, Result};
+use crate::config_loader::{self, DorisConfig, Environment, process_detector};
+use crate::core::version::{self, DorisVersion};
+use crate::error::{self, CliError, Result};
 use crate::process;
-use crate::tools::Tool;
-use crate::ui::{print_error, print_info, print_success};
-use std::path::Path;
-
-pub fn execute_tool_enhanced(config: &Config, tool: &dyn Tool, service_name: &str) -> Result<()> {
-    let pid = match resolve_pid_if_required(tool) {
-        Some(pid) => pid,
-        None => return Ok(()),
-    };
+use crate::tools::{ExecutionResult, Impact, Tool};
+use crate::ui::{print_error, print_info, print_success, print_warning};
 
-    print_info(&format!("Executing {}...", tool.name()));
+/// PIDs entered by hand, per service, for the rest of the session
+static MANUAL_PIDS: Lazy<Mutex<HashMap<String, u32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 
-    match tool.execute(config, pid) {
-        Ok(result) => {
-            print_success(&result.message);
-            maybe_print_output_path(&result.output_path);
-            Ok(())
-        }
-        Err(error::CliError::GracefulExit) => Ok(()),
+/// Run `tool` and print its summary; `None` when nothing was executed
+pub fn execute_tool_enhanced(
+    config: &Config,
+    tool: &dyn Tool,
+    service_name: &str,
+) -> Result<Option<ExecutionResult>> {
+    let Some(pid) = prepare_execution(config, tool, service_name)? else {
+        return Ok(None);
+    };
+
+    match run_prepared(config, tool, pid) {
+        Ok(result) => Ok(Some(result)),
+        Err(error::CliError::GracefulExit) => Ok(None),
         Err(e) => {
             match crate::ui::error_handlers::handle_tool_execution_error(
                 config,
@@ -29,37 +36,427 @@ pub fn execute_tool_enhanced(config: &Config, tool: &dyn Tool, service_name: &st
                 tool.name(),
             )? {
                 Some(updated_config) => execute_tool_enhanced(&updated_config, tool, service_name),
-                None => Ok(()),
+                None => Ok(None),
             }
         }
     }
 }
 
-fn resolve_pid_if_required(tool: &dyn Tool) -> Option<u32> {
+/// Like [`execute_tool_enhanced`] but returns tool errors instead of offering
+/// recovery, for callers that run several tools in a row
+pub fn execute_tool_once(
+    config: &Config,
+    tool: &dyn Tool,
+    service_name: &str,
+) -> Result<Option<ExecutionResult>> {
+    match prepare_execution(config, tool, service_name)? {
+        Some(pid) => run_prepared(config, tool, pid).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// PID to run `tool` with, or `None` when a read-only, version or strict-mode
+/// check declined it
+fn prepare_execution(config: &Config, tool: &dyn Tool, service_name: &str) -> Result<Option<u32>> {
+    if let Some(reason) = read_only_violation(config, tool) {
+        print_warning(&format!(
+            "'{}' is blocked in read-only mode: {reason}. Only tools that collect into the output directory run.",
+            tool.name()
+        ));
+        return Ok(None);
+    }
+
+    let pid = resolve_pid_if_required(tool, service_name)?;
+
+    if !confirm_min_version(tool)? {
+        print_warning("Execution cancelled: the cluster version is below the tool's minimum.");
+        return Ok(None);
+    }
+
+    if !confirm_strict_mode(config, tool)? {
+        print_warning("Execution cancelled: confirmation did not match the profile name.");
+        return Ok(None);
+    }
+    Ok(Some(pid))
+}
+
+fn run_prepared(config: &Config, tool: &dyn Tool, pid: u32) -> Result<ExecutionResult> {
+    print_info(&format!("Executing {}...", tool.name()));
+
+    let started_at = Utc::now();
+    let timer = Instant::now();
+    let result = tool
+        .execute(config, pid)?
+        .with_timing(started_at, timer.elapsed());
+    print_success(&result.message);
+    print_summary(config, tool, &result);
+    Ok(result)
+}
+
+/// Environment whose process PID tools of `service_name` act on
+fn service_environment(service_name: &str) -> Option<Environment> {
+    match service_name {
+        "FE" => Some(Environment::FE),
+        "BE" => Some(Environment::BE),
+        _ => None,
+    }
+}
+
+fn no_local_process(env: Environment) -> CliError {
+    CliError::ProcessNotFound(format!(
+        "no local {env} process found — PID tools must run on the {env} host"
+    ))
+}
+
+/// PID of the local process a `service_name` tool should act on.
+///
+/// Mixed deployments record FE and BE separately; `process_pid` is only used
+/// when the detected environment is the one the service asks for.
+pub fn resolve_service_pid(doris: &DorisConfig, service_name: &str) -> Result<u32> {
+    let Some(env) = service_environment(service_name) else {
+        return doris
+            .process_pid
+            .ok_or_else(|| CliError::ProcessNotFound("No Doris process found".to_string()));
+    };
+    let dedicated = match env {
+        Environment::BE => doris.be_process_pid,
+        _ => doris.fe_process_pid,
+    };
+    dedicated
+        .or(doris.process_pid.filter(|_| doris.environment == env))
+        .ok_or_else(|| no_local_process(env))
+}
+
+fn resolve_pid_if_required(tool: &dyn Tool, service_name: &str) -> Result<u32> {
     if !tool.requires_pid() {
-        return Some(0);
+        return Ok(0);
+    }
+
+    if let Some(pid) = remembered_manual_pid(service_name) {
+        return Ok(pid);
+    }
+
+    if let Ok(doris) = config_loader::load_config() {
+        match resolve_service_pid(&doris, service_name) {
+            Ok(pid) if config_loader::is_pid_running(pid) => return Ok(pid),
+            // Stale PID: look for the process again below
+            Ok(_) => {}
+            Err(e) => return manual_pid_or(tool, service_name, e),
+        }
     }
 
-    if let Some(pid) = config_loader::get_current_pid() {
+    let Some(env) = service_environment(service_name) else {
+        return process::select_process_interactively();
+    };
+    let found = process_detector::detect_all_processes()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|p| p.environment == env);
+    match found {
+        Some(process) => {
+            print_info(&format!("Found {env} process"));
+            crate::ui::print_process_info(process.pid, &process.command);
+            Ok(process.pid)
+        }
+        None => manual_pid_or(tool, service_name, no_local_process(env)),
+    }
+}
+
+/// The session's manual PID for `service_name`, forgotten once its process exits
+fn remembered_manual_pid(service_name: &str) -> Option<u32> {
+    let mut pids = MANUAL_PIDS.lock().ok()?;
+    let pid = *pids.get(service_name)?;
+    if config_loader::is_pid_running(pid) {
         return Some(pid);
     }
+    pids.remove(service_name);
+    print_warning(&format!(
+        "Manually entered {service_name} PID {pid} is no longer running."
+    ));
+    None
+}
 
-    match process::select_process_interactively() {
-        Ok(pid) => Some(pid),
-        Err(_) => {
-            let tool_name = tool.name();
-            print_error(&format!("No {tool_name} processes found."));
-            None
+/// A positive numeric PID, ignoring surrounding whitespace
+pub fn parse_manual_pid(input: &str) -> Option<u32> {
+    input.trim().parse::<u32>().ok().filter(|pid| *pid > 0)
+}
+
+/// Detection failed with `err`; offer to take the PID from the user instead,
+/// e.g. inside containers or with renamed launch scripts
+fn manual_pid_or(tool: &dyn Tool, service_name: &str, err: CliError) -> Result<u32> {
+    print_error(&format!("{}: {err}", tool.name()));
+    if crate::ui::dialogs::select_index("Enter PID manually?", &["No", "Yes"])? != 1 {
+        return Err(err);
+    }
+
+    let input = crate::ui::dialogs::input_text(&format!("{service_name} PID"), "")?;
+    let Some(pid) = parse_manual_pid(&input) else {
+        return Err(CliError::InvalidInput(format!(
+            "'{}' is not a valid PID",
+            input.trim()
+        )));
+    };
+    if !config_loader::is_pid_running(pid) {
+        return Err(CliError::ProcessNotFound(format!(
+            "no running process with PID {pid}"
+        )));
+    }
+
+    let command = process_detector::get_process_command(pid)?;
+    crate::ui::print_process_info(pid, &command);
+    if crate::ui::dialogs::select_index("Use this process?", &["Yes", "Cancel"])? != 0 {
+        return Err(err);
+    }
+    if let Ok(mut pids) = MANUAL_PIDS.lock() {
+        pids.insert(service_name.to_string(), pid);
+    }
+    Ok(pid)
+}
+
+fn print_summary(config: &Config, tool: &dyn Tool, result: &ExecutionResult) {
+    print_info(&format!("{} {}", tool.name(), result.summary()));
+    if result.is_console_output() {
+        if config.stdout_only {
+            print_info("Output: (printed to console)");
         }
+    } else if result.output_size_bytes.is_none() && !result.output_path.as_os_str().is_empty() {
+        print_info(&format!(
+            "Output saved to: {}",
+            result.output_path.display()
+        ));
+    }
+}
+
+/// Minimum version `tool` needs when `detected` is below it
+fn unmet_min_version(tool: &dyn Tool, detected: DorisVersion) -> Option<DorisVersion> {
+    tool.min_version().filter(|min| detected < *min)
+}
+
+/// Warn and ask before running a tool the detected cluster is too old for;
+/// an undetectable version is not a reason to block anything
+fn confirm_min_version(tool: &dyn Tool) -> Result<bool> {
+    if tool.min_version().is_none() {
+        return Ok(true);
+    }
+    let Some(detected) = config_loader::load_config()
+        .ok()
+        .and_then(|mut doris| version::current(&mut doris))
+    else {
+        return Ok(true);
+    };
+    let Some(min) = unmet_min_version(tool, detected) else {
+        return Ok(true);
+    };
+    print_warning(&format!(
+        "'{}' needs Doris {min} or later; this cluster runs {detected}.",
+        tool.name()
+    ));
+    Ok(crate::ui::dialogs::select_index("Continue?", &["Cancel", "Run anyway"])? == 1)
+}
+
+/// Why read-only mode refuses `tool`, or None when it may run
+pub fn read_only_violation(config: &Config, tool: &dyn Tool) -> Option<&'static str> {
+    if !config.is_read_only() {
+        return None;
+    }
+    match tool.impact() {
+        Impact::ReadOnly => None,
+        Impact::ProcessAttach => Some("it attaches to the running process"),
+        Impact::WritesInstallDir => Some("it writes into the Doris install directory"),
+    }
+}
+
+/// Whether `tool` must be confirmed by typing the profile name before running
+pub fn strict_confirmation_required(config: &Config, tool: &dyn Tool) -> bool {
+    config.production_profile().is_some() && tool.is_heavy()
+}
+
+/// Typed confirmation must match the profile name exactly (surrounding whitespace ignored)
+pub fn confirmation_matches(expected: &str, typed: &str) -> bool {
+    typed.trim() == expected
+}
+
+fn confirm_strict_mode(config: &Config, tool: &dyn Tool) -> Result<bool> {
+    if !strict_confirmation_required(config, tool) {
+        return Ok(true);
     }
+    let profile = config.production_profile().unwrap_or_default();
+    print_warning(&format!(
+        "'{}' is a heavy tool and this is the production profile '{profile}'.",
+        tool.name()
+    ));
+    let typed = crate::ui::dialogs::input_text(
+        &format!("Type the profile name '{profile}' to continue"),
+        "",
+    )?;
+    Ok(confirmation_matches(profile, &typed))
 }
 
-fn maybe_print_output_path(output_path: &Path) {
-    if output_path
-        .to_str()
-        .filter(|p| !p.is_empty() && *p != "console_output")
-        .is_some()
-    {
-        print_info(&format!("Output saved to: {}", output_path.display()));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ExecutionResult;
+
+    struct FakeTool {
+        heavy: bool,
+    }
+
+    impl Tool for FakeTool {
+        fn name(&self) -> &str {
+            "fake"
+        }
+        fn description(&self) -> &str {
+            "fake tool"
+        }
+        fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
+            unreachable!()
+        }
+        fn is_heavy(&self) -> bool {
+            self.heavy
+        }
+    }
+
+    fn config(production: bool, profile: Option<&str>) -> Config {
+        Config {
+            production,
+            profile_name: profile.map(String::from),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn strict_gate_applies_only_to_heavy_tools_in_production() {
+        let heavy = FakeTool { heavy: true };
+        let light = FakeTool { heavy: false };
+
+        assert!(strict_confirmation_required(
+            &config(true, Some("prod")),
+            &heavy
+        ));
+        assert!(!strict_confirmation_required(
+            &config(true, Some("prod")),
+            &light
+        ));
+        assert!(!strict_confirmation_required(
+            &config(false, Some("prod")),
+            &heavy
+        ));
+    }
+
+    struct ImpactTool(Impact);
+
+    impl Tool for ImpactTool {
+        fn name(&self) -> &str {
+            "impact"
+        }
+        fn description(&self) -> &str {
+            "impact tool"
+        }
+        fn execute(&self, _config: &Config, _pid: u32) -> Result<ExecutionResult> {
+            unreachable!()
+        }
+        fn impact(&self) -> Impact {
+            self.0
+        }
+    }
+
+    #[test]
+    fn read_only_mode_blocks_attaching_and_install_dir_tools() {
+        let read_only = Config {
+            read_only: Some(true),
+            ..Config::default()
+        };
+        assert_eq!(
+            read_only_violation(&read_only, &ImpactTool(Impact::ReadOnly)),
+            None
+        );
+        assert!(
+            read_only_violation(&read_only, &ImpactTool(Impact::ProcessAttach))
+                .unwrap()
+                .contains("attaches")
+        );
+        assert!(
+            read_only_violation(&read_only, &ImpactTool(Impact::WritesInstallDir))
+                .unwrap()
+                .contains("install directory")
+        );
+        let normal = config(false, None);
+        assert_eq!(
+            read_only_violation(&normal, &ImpactTool(Impact::WritesInstallDir)),
+            None
+        );
+    }
+
+    fn doris(env: Environment, pid: u32, fe: Option<u32>, be: Option<u32>) -> DorisConfig {
+        DorisConfig {
+            environment: env,
+            process_pid: Some(pid),
+            fe_process_pid: fe,
+            be_process_pid: be,
+            ..DorisConfig::default()
+        }
+    }
+
+    #[test]
+    fn pid_follows_service_context() {
+        let mixed = doris(Environment::Mixed, 100, Some(100), Some(200));
+        assert_eq!(resolve_service_pid(&mixed, "FE").unwrap(), 100);
+        assert_eq!(resolve_service_pid(&mixed, "BE").unwrap(), 200);
+
+        let fe_only = doris(Environment::FE, 100, None, None);
+        assert_eq!(resolve_service_pid(&fe_only, "FE").unwrap(), 100);
+        let err = resolve_service_pid(&fe_only, "BE").unwrap_err();
+        assert!(matches!(err, CliError::ProcessNotFound(_)));
+        assert!(err.to_string().contains("must run on the BE host"));
+
+        let be_only = doris(Environment::BE, 200, None, Some(200));
+        assert_eq!(resolve_service_pid(&be_only, "BE").unwrap(), 200);
+        assert!(resolve_service_pid(&be_only, "FE").is_err());
+        assert_eq!(resolve_service_pid(&be_only, "Doctor").unwrap(), 200);
+    }
+
+    #[test]
+    fn manual_pid_must_be_a_positive_number() {
+        assert_eq!(parse_manual_pid(" 4242\n"), Some(4242));
+        assert_eq!(parse_manual_pid("0"), None);
+        assert_eq!(parse_manual_pid("-1"), None);
+        assert_eq!(parse_manual_pid("doris_be"), None);
+        assert_eq!(parse_manual_pid(""), None);
+    }
+
+    #[test]
+    fn confirmation_requires_exact_profile_name() {
+        assert!(confirmation_matches("prod-bj", "prod-bj"));
+        assert!(confirmation_matches("prod-bj", "  prod-bj\n"));
+        assert!(!confirmation_matches("prod-bj", "prod"));
+        assert!(!confirmation_matches("prod-bj", "PROD-BJ"));
+        assert!(!confirmation_matches("prod-bj", ""));
+    }
+
+    #[test]
+    fn artifacts_are_stamped_with_production_profile() {
+        let cfg = config(true, Some("prod bj"));
+        assert_eq!(
+            cfg.artifact_file_name("jstack_1.log"),
+            "prod_bj_jstack_1.log"
+        );
+        assert_eq!(
+            cfg.artifact_path("jstack_1.log"),
+            cfg.run_dir_path().join("prod_bj_jstack_1.log")
+        );
+        let flat = Config {
+            flat_output: true,
+            ..cfg.clone()
+        };
+        assert_eq!(
+            flat.artifact_path("jstack_1.log"),
+            flat.output_dir.join("prod_bj_jstack_1.log")
+        );
+
+        let cfg = config(false, Some("prod"));
+        assert_eq!(cfg.artifact_file_name("jstack_1.log"), "jstack_1.log");
+
+        let cfg = config(true, None);
+        assert_eq!(cfg.artifact_file_name("a.txt"), "production_a.txt");
     }
 }
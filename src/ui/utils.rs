@@ -1,5 +1,162 @@
+use chrono::{Duration, Local, NaiveDateTime};
+
 use crate::error::{CliError, Result};
 
+pub const TIME_RANGE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Log window to analyze
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeRange {
+    /// The last N minutes up to the newest log entry
+    Recent(i64),
+    /// Whole minutes from the start through the end minute
+    Between(NaiveDateTime, NaiveDateTime),
+}
+
+impl TimeRange {
+    /// Absolute range from two `YYYY-MM-DD HH:MM` inputs
+    pub fn parse_between(start: &str, end: &str) -> std::result::Result<Self, String> {
+        let parse = |label: &str, raw: &str| {
+            NaiveDateTime::parse_from_str(raw.trim(), TIME_RANGE_FORMAT)
+                .map_err(|_| format!("{label} '{}' is not YYYY-MM-DD HH:MM", raw.trim()))
+        };
+        let (start, end) = (parse("Start", start)?, parse("End", end)?);
+        if end < start {
+            return Err(format!(
+                "End {} is before start {}",
+                end.format(TIME_RANGE_FORMAT),
+                start.format(TIME_RANGE_FORMAT)
+            ));
+        }
+        Ok(Self::Between(start, end))
+    }
+
+    /// Inclusive bounds, with `Recent` anchored at `latest` to avoid clock and timezone skew
+    pub fn bounds(&self, latest: NaiveDateTime) -> (NaiveDateTime, NaiveDateTime) {
+        match *self {
+            Self::Recent(minutes) => (latest - Duration::minutes(minutes), latest),
+            Self::Between(start, end) => (start, end + Duration::seconds(59)),
+        }
+    }
+
+    /// Whether the range overlaps logs spanning `first..=last`
+    pub fn overlaps(&self, first: NaiveDateTime, last: NaiveDateTime) -> bool {
+        let (start, end) = self.bounds(last);
+        start <= last && end >= first
+    }
+}
+
+/// `2024-05-01 02:10:00 to 2024-05-01 02:40:59`
+pub fn describe_range(start: NaiveDateTime, end: NaiveDateTime) -> String {
+    format!(
+        "{} to {}",
+        start.format("%Y-%m-%d %H:%M:%S"),
+        end.format("%Y-%m-%d %H:%M:%S")
+    )
+}
+
+/// Gap between the newest log entry and local time beyond which analyzers warn
+pub const LOG_CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// How far the newest log entry is ahead of (+) or behind (-) `now`, when the
+/// gap exceeds [`LOG_CLOCK_SKEW_MINUTES`]
+pub fn log_clock_offset(latest: NaiveDateTime, now: NaiveDateTime) -> Option<Duration> {
+    let offset = latest - now;
+    (offset.num_minutes().abs() > LOG_CLOCK_SKEW_MINUTES).then_some(offset)
+}
+
+/// `+8h 00m`, `-1h 05m` or `+12m`
+pub fn describe_offset(offset: Duration) -> String {
+    let sign = if offset < Duration::zero() { '-' } else { '+' };
+    let minutes = offset.num_minutes().abs();
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{sign}{m}m"),
+        (h, m) => format!("{sign}{h}h {m:02}m"),
+    }
+}
+
+/// Warn when the newest log entry is far from the local clock, since recent
+/// windows end at log time rather than now. Returns the report header line.
+pub fn check_log_clock_skew(latest: NaiveDateTime) -> Option<String> {
+    report_log_clock_skew(latest, &crate::ui::sink::ConsoleSink)
+}
+
+/// [`check_log_clock_skew`] with the warning sent to `sink`
+pub fn report_log_clock_skew(
+    latest: NaiveDateTime,
+    sink: &dyn crate::ui::sink::OutputSink,
+) -> Option<String> {
+    let offset = describe_offset(log_clock_offset(latest, Local::now().naive_local())?);
+    sink.warning(&format!(
+        "Newest log entry ({}) is {offset} off local time; the analysis window follows log time, \
+         not your wall clock. FE logs may be in a different timezone, or the host clock drifted.",
+        latest.format("%Y-%m-%d %H:%M:%S")
+    ));
+    Some(format!(
+        "Log clock offset: {offset} vs local time (window is log-time based)"
+    ))
+}
+
+/// Longest "recent minutes" window accepted: one week of logs
+pub const MAX_RECENT_MINUTES: i64 = 7 * 24 * 60;
+
+/// "1-300 seconds", or "at least 1" without an upper bound
+fn range_hint(min: i64, max: Option<i64>, unit: Option<&str>) -> String {
+    let range = match max {
+        Some(max) => format!("{min}-{max}"),
+        None => format!("at least {min}"),
+    };
+    match unit {
+        Some(unit) => format!("{range} {unit}"),
+        None => range,
+    }
+}
+
+/// `raw` seconds from `source` checked against `1..=max`, falling back to `default`
+fn duration_secs_or_default(source: &str, raw: &str, default: u32, max: u32) -> u32 {
+    match parse_bounded(
+        raw,
+        i64::from(default),
+        1,
+        Some(i64::from(max)),
+        Some("seconds"),
+    ) {
+        Ok(secs) => secs as u32,
+        Err(msg) => {
+            crate::ui::print_warning(&format!("{source}: {msg}; using {default}s"));
+            default
+        }
+    }
+}
+
+/// Numeric prompt input checked against `min..=max`; empty input is `default`
+fn parse_bounded(
+    input: &str,
+    default: i64,
+    min: i64,
+    max: Option<i64>,
+    unit: Option<&str>,
+) -> std::result::Result<i64, String> {
+    let input = input.trim();
+    let value = if input.is_empty() {
+        default
+    } else {
+        input.parse::<i64>().map_err(|_| {
+            format!(
+                "'{input}' is not a whole number; enter {}",
+                range_hint(min, max, unit)
+            )
+        })?
+    };
+    if value < min || max.is_some_and(|max| value > max) {
+        return Err(format!(
+            "{value} is out of range; enter {}",
+            range_hint(min, max, unit)
+        ));
+    }
+    Ok(value)
+}
+
 pub struct InputHelper;
 
 impl InputHelper {
@@ -12,11 +169,84 @@ impl InputHelper {
         Ok(input)
     }
 
-    pub fn prompt_number_with_default(prompt: &str, default: i64, min: i64) -> Result<i64> {
-        let input_str = crate::ui::dialogs::input_text(prompt, &default.to_string())?;
+    /// Ask for a whole number in `min..=max`, asking again until one is given.
+    ///
+    /// Empty input takes `default`. `unit` is shown with the prompt and the
+    /// range, e.g. "Analyze recent minutes (1-10080 minutes)".
+    pub fn prompt_number_with_default(
+        prompt: &str,
+        default: i64,
+        min: i64,
+        max: Option<i64>,
+        unit: Option<&str>,
+    ) -> Result<i64> {
+        let prompt = format!("{prompt} ({})", range_hint(min, max, unit));
+        loop {
+            let input = crate::ui::dialogs::input_text(&prompt, &default.to_string())?;
+            match parse_bounded(&input, default, min, max, unit) {
+                Ok(value) => return Ok(value),
+                Err(msg) => crate::ui::print_warning(&msg),
+            }
+        }
+    }
 
-        let value: i64 = input_str.trim().parse().unwrap_or(default).max(min);
-        Ok(value)
+    /// Seconds in `1..=max`, e.g. a profile or poll duration
+    pub fn prompt_duration_secs(prompt: &str, default: u32, max: u32) -> Result<u32> {
+        let secs = Self::prompt_number_with_default(
+            prompt,
+            i64::from(default),
+            1,
+            Some(i64::from(max)),
+            Some("seconds"),
+        )?;
+        Ok(secs as u32)
+    }
+
+    /// Seconds in `1..=max` from env var `var`, if set; anything else is warned
+    /// about and replaced by `default`
+    pub fn duration_secs_from_env(var: &str, default: u32, max: u32) -> Option<u32> {
+        let raw = std::env::var(var).ok()?;
+        Some(duration_secs_or_default(var, &raw, default, max))
+    }
+
+    /// Ask for recent minutes or an absolute `YYYY-MM-DD HH:MM` start/end. Invalid or
+    /// reversed input, and ranges outside `available` log data, are asked again.
+    pub fn prompt_time_range(
+        default_minutes: i64,
+        available: (NaiveDateTime, NaiveDateTime),
+    ) -> Result<TimeRange> {
+        let (first, last) = available;
+        crate::ui::print_info(&format!("Log data covers {}", describe_range(first, last)));
+        let kind = crate::ui::dialogs::select_index(
+            "Time range",
+            &["Recent minutes", "Absolute start and end"],
+        )?;
+        if kind == 0 {
+            let minutes = Self::prompt_number_with_default(
+                "Analyze recent minutes",
+                default_minutes,
+                1,
+                Some(MAX_RECENT_MINUTES),
+                Some("minutes"),
+            )?;
+            return Ok(TimeRange::Recent(minutes));
+        }
+
+        loop {
+            let start = crate::ui::dialogs::input_text(
+                "Start (YYYY-MM-DD HH:MM)",
+                &last.format(TIME_RANGE_FORMAT).to_string(),
+            )?;
+            let end = crate::ui::dialogs::input_text(
+                "End (YYYY-MM-DD HH:MM)",
+                &last.format(TIME_RANGE_FORMAT).to_string(),
+            )?;
+            match TimeRange::parse_between(&start, &end) {
+                Ok(range) if range.overlaps(first, last) => return Ok(range),
+                Ok(_) => crate::ui::print_warning("No log data in that range; try again."),
+                Err(msg) => crate::ui::print_warning(&format!("{msg}; try again.")),
+            }
+        }
     }
 }
 
@@ -29,6 +259,15 @@ impl FormatHelper {
     pub fn fmt_int_u128(v: u128) -> String {
         Self::group_digits(&v.to_string())
     }
+    /// `+1,234` or `-1,234`
+    pub fn fmt_signed(v: i64) -> String {
+        let abs = Self::fmt_int(v.unsigned_abs());
+        if v < 0 {
+            format!("-{abs}")
+        } else {
+            format!("+{abs}")
+        }
+    }
 
     fn group_digits(s: &str) -> String {
         let bytes = s.as_bytes();
@@ -44,11 +283,104 @@ impl FormatHelper {
         out.chars().rev().collect()
     }
 
+    /// `s` in at most `max_len` columns, ending in `...` when shortened
     pub fn truncate_string(s: &str, max_len: usize) -> String {
-        if s.len() <= max_len {
-            s.to_string()
-        } else {
-            format!("{}...", &s[..max_len - 3])
-        }
+        super::table::truncate_to_width(s, max_len, "...")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(raw: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn bounded_numbers_accept_only_the_range() {
+        let minutes =
+            |input| parse_bounded(input, 60, 1, Some(MAX_RECENT_MINUTES), Some("minutes"));
+        assert_eq!(minutes(""), Ok(60));
+        assert_eq!(minutes("1"), Ok(1));
+        assert_eq!(minutes(" 10080 "), Ok(10080));
+        assert_eq!(
+            minutes("10081"),
+            Err("10081 is out of range; enter 1-10080 minutes".to_string())
+        );
+        assert!(minutes("0").is_err());
+        assert!(minutes("300000").is_err());
+        assert_eq!(
+            minutes("1h"),
+            Err("'1h' is not a whole number; enter 1-10080 minutes".to_string())
+        );
+        assert_eq!(parse_bounded("0", 7, 0, None, Some("days")), Ok(0));
+        assert_eq!(
+            parse_bounded("-1", 7, 0, None, Some("days")),
+            Err("-1 is out of range; enter at least 0 days".to_string())
+        );
+        assert_eq!(parse_bounded("300", 10, 1, Some(300), None), Ok(300));
+        assert!(parse_bounded("301", 10, 1, Some(300), None).is_err());
+    }
+
+    #[test]
+    fn time_ranges_validate_order_and_overlap() {
+        let range = TimeRange::parse_between(" 2024-05-01 02:10", "2024-05-01 02:40 ").unwrap();
+        let latest = at("2024-05-01 09:00:00");
+        assert_eq!(
+            range.bounds(latest),
+            (at("2024-05-01 02:10:00"), at("2024-05-01 02:40:59"))
+        );
+        assert_eq!(
+            TimeRange::Recent(30).bounds(latest),
+            (at("2024-05-01 08:30:00"), latest)
+        );
+
+        assert!(
+            TimeRange::parse_between("2024-05-01 02:40", "2024-05-01 02:10")
+                .unwrap_err()
+                .contains("before start")
+        );
+        assert!(
+            TimeRange::parse_between("02:10", "2024-05-01 02:40")
+                .unwrap_err()
+                .contains("not YYYY-MM-DD HH:MM")
+        );
+
+        assert!(range.overlaps(at("2024-05-01 02:30:00"), latest));
+        assert!(!range.overlaps(at("2024-05-01 03:00:00"), latest));
+        assert!(!range.overlaps(at("2024-04-30 00:00:00"), at("2024-05-01 02:00:00")));
+    }
+
+    #[test]
+    fn log_clock_offset_ignores_small_drift() {
+        let now = at("2024-05-01 10:00:00");
+        assert_eq!(log_clock_offset(at("2024-05-01 10:04:30"), now), None);
+        assert_eq!(log_clock_offset(at("2024-05-01 09:56:00"), now), None);
+
+        let ahead = log_clock_offset(at("2024-05-01 18:00:00"), now).unwrap();
+        assert_eq!(describe_offset(ahead), "+8h 00m");
+        let behind = log_clock_offset(at("2024-05-01 08:55:00"), now).unwrap();
+        assert_eq!(describe_offset(behind), "-1h 05m");
+        assert_eq!(describe_offset(Duration::minutes(12)), "+12m");
+    }
+
+    #[test]
+    fn signed_numbers_keep_their_sign_and_grouping() {
+        assert_eq!(FormatHelper::fmt_signed(1234567), "+1,234,567");
+        assert_eq!(FormatHelper::fmt_signed(-1234), "-1,234");
+        assert_eq!(FormatHelper::fmt_signed(0), "+0");
+    }
+
+    #[test]
+    fn env_durations_outside_the_range_fall_back() {
+        let secs = |raw| duration_secs_or_default("PROFILE_SECONDS", raw, 10, 300);
+        assert_eq!(secs("30"), 30);
+        assert_eq!(secs(" 300 "), 300);
+        assert_eq!(secs(""), 10);
+        assert_eq!(secs("0"), 10);
+        assert_eq!(secs("301"), 10);
+        assert_eq!(secs("4294967297"), 10);
+        assert_eq!(secs("1m"), 10);
     }
 }